@@ -0,0 +1,307 @@
+// /agent save|load|remove 智能体配置档：把 agent_id 覆盖、模型、工具调用模式、审批模式与回答风格存成命名档案，一条命令整体切换。
+use crate::args::GlobalArgs;
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use wunder_server::config::Config;
+
+pub(crate) const AGENT_SLASH_USAGE: &str =
+    "/agent [show|list|clear|save <name>|load <name>|remove <name>|<agent_id>]";
+
+/// Everything `/agent load` restores. `None` fields are restored as unset, not skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AgentProfile {
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub tool_call_mode: Option<String>,
+    #[serde(default)]
+    pub approval_mode: Option<String>,
+    #[serde(default)]
+    pub personality: Option<String>,
+}
+
+pub(crate) enum AgentProfileAction {
+    Save(String),
+    Load(String),
+    Remove(String),
+}
+
+/// `save|load|remove <name>`; anything else is left to the plain `/agent` handling.
+pub(crate) fn parse_agent_profile_action(args: &str) -> Option<AgentProfileAction> {
+    let (verb, name) = args.trim().split_once(char::is_whitespace)?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return None;
+    }
+    match verb.to_ascii_lowercase().as_str() {
+        "save" => Some(AgentProfileAction::Save(name)),
+        "load" => Some(AgentProfileAction::Load(name)),
+        "remove" | "rm" => Some(AgentProfileAction::Remove(name)),
+        _ => None,
+    }
+}
+
+/// Run a profile action and return the lines to show. `load` replaces `agent_id_override`.
+pub(crate) async fn run_agent_profile_action(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    agent_id_override: &mut Option<String>,
+    action: AgentProfileAction,
+) -> Result<Vec<String>> {
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    let path = runtime.agent_profiles_file();
+    let mut profiles = load_agent_profiles(&path)?;
+    match action {
+        AgentProfileAction::Save(name) => {
+            let config = runtime.state.config_store.get().await;
+            let model = runtime.resolve_model_name(global.model.as_deref()).await;
+            let agent_id = agent_id_override.as_deref().or(global.agent.as_deref());
+            let profile =
+                capture_agent_profile(&config, model, agent_id, runtime.load_personality_mode());
+            let summary = describe_agent_profile(&profile);
+            profiles.insert(name.clone(), profile);
+            save_agent_profiles(&path, &profiles)?;
+            Ok(vec![if is_zh {
+                format!("已保存配置档 {name}: {summary}")
+            } else {
+                format!("agent profile saved: {name} ({summary})")
+            }])
+        }
+        AgentProfileAction::Load(name) => {
+            let Some(profile) = profiles.get(&name).cloned() else {
+                return Err(anyhow!(if is_zh {
+                    format!("配置档不存在: {name}")
+                } else {
+                    format!("agent profile not found: {name}")
+                }));
+            };
+            let config = runtime.state.config_store.get().await;
+            if let Some(model) = profile
+                .model
+                .as_deref()
+                .filter(|model| !config.llm.models.contains_key(*model))
+            {
+                return Err(anyhow!(if is_zh {
+                    format!("配置中不存在模型: {model}")
+                } else {
+                    format!("model not found in config: {model}")
+                }));
+            }
+            let for_update = profile.clone();
+            runtime
+                .state
+                .config_store
+                .update(move |config| apply_agent_profile(&for_update, config))
+                .await?;
+            match profile.personality.as_deref() {
+                Some(mode) => runtime.save_personality_mode(mode)?,
+                None => runtime.clear_personality_mode()?,
+            }
+            *agent_id_override = profile.agent_id.clone();
+            let mut lines = vec![if is_zh {
+                format!("已加载配置档 {name}: {}", describe_agent_profile(&profile))
+            } else {
+                format!(
+                    "agent profile loaded: {name} ({})",
+                    describe_agent_profile(&profile)
+                )
+            }];
+            // `--model` outranks the configured default for this process.
+            if let Some(flag) = global
+                .model
+                .as_deref()
+                .filter(|flag| profile.model.as_deref() != Some(*flag))
+            {
+                lines.push(if is_zh {
+                    format!("注意: 启动参数 --model {flag} 仍优先于配置档模型")
+                } else {
+                    format!("note: --model {flag} still takes precedence over the profile model")
+                });
+            }
+            Ok(lines)
+        }
+        AgentProfileAction::Remove(name) => {
+            if profiles.remove(&name).is_none() {
+                return Err(anyhow!(if is_zh {
+                    format!("配置档不存在: {name}")
+                } else {
+                    format!("agent profile not found: {name}")
+                }));
+            }
+            save_agent_profiles(&path, &profiles)?;
+            Ok(vec![if is_zh {
+                format!("已删除配置档: {name}")
+            } else {
+                format!("agent profile removed: {name}")
+            }])
+        }
+    }
+}
+
+/// Saved profiles for `/agent list`; empty when none were saved.
+pub(crate) fn agent_profile_list_lines(
+    runtime: &CliRuntime,
+    language: &str,
+) -> Result<Vec<String>> {
+    let profiles = load_agent_profiles(&runtime.agent_profiles_file())?;
+    if profiles.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut lines = vec![locale::tr(language, "已保存配置档:", "saved profiles:")];
+    for (name, profile) in &profiles {
+        lines.push(format!("  {name}: {}", describe_agent_profile(profile)));
+    }
+    Ok(lines)
+}
+
+fn capture_agent_profile(
+    config: &Config,
+    model: Option<String>,
+    agent_id: Option<&str>,
+    personality: Option<String>,
+) -> AgentProfile {
+    let tool_call_mode = model
+        .as_deref()
+        .and_then(|name| config.llm.models.get(name))
+        .and_then(|entry| entry.tool_call_mode.clone());
+    AgentProfile {
+        agent_id: agent_id
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string),
+        model,
+        tool_call_mode,
+        approval_mode: config.security.approval_mode.clone(),
+        personality,
+    }
+}
+
+fn apply_agent_profile(profile: &AgentProfile, config: &mut Config) {
+    if let Some(model) = profile.model.as_deref() {
+        config.llm.default = model.to_string();
+        if let Some(entry) = config.llm.models.get_mut(model) {
+            entry.tool_call_mode = profile.tool_call_mode.clone();
+        }
+    }
+    config.security.approval_mode = profile.approval_mode.clone();
+}
+
+fn describe_agent_profile(profile: &AgentProfile) -> String {
+    let field = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    format!(
+        "agent={}, model={}, tool_call_mode={}, approval_mode={}, personality={}",
+        field(&profile.agent_id),
+        field(&profile.model),
+        field(&profile.tool_call_mode),
+        field(&profile.approval_mode),
+        field(&profile.personality),
+    )
+}
+
+fn load_agent_profiles(path: &Path) -> Result<BTreeMap<String, AgentProfile>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => return Err(err.into()),
+    };
+    if text.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    serde_json::from_str(&text)
+        .with_context(|| format!("parse agent profiles failed: {}", path.display()))
+}
+
+fn save_agent_profiles(path: &Path, profiles: &BTreeMap<String, AgentProfile>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(profiles)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wunder_server::config::LlmModelConfig;
+
+    fn config_with_models() -> Config {
+        let mut config = Config::default();
+        for name in ["fast", "deep"] {
+            config
+                .llm
+                .models
+                .insert(name.to_string(), LlmModelConfig::default());
+        }
+        config.llm.default = "fast".to_string();
+        config
+    }
+
+    #[test]
+    fn loading_a_saved_profile_restores_each_field() {
+        let mut config = config_with_models();
+        if let Some(entry) = config.llm.models.get_mut("deep") {
+            entry.tool_call_mode = Some("function_call".to_string());
+        }
+        config.security.approval_mode = Some("suggest".to_string());
+        let saved = capture_agent_profile(
+            &config,
+            Some("deep".to_string()),
+            Some(" reviewer "),
+            Some("concise".to_string()),
+        );
+
+        let path = std::env::temp_dir()
+            .join(format!(
+                "wunder-cli-agent-profiles-{}",
+                uuid::Uuid::new_v4().simple()
+            ))
+            .join("agent_profiles.json");
+        let mut profiles = load_agent_profiles(&path).expect("missing file");
+        profiles.insert("review".to_string(), saved.clone());
+        save_agent_profiles(&path, &profiles).expect("save profiles");
+
+        // Drift every field, then load the profile back.
+        config.llm.default = "fast".to_string();
+        if let Some(entry) = config.llm.models.get_mut("deep") {
+            entry.tool_call_mode = Some("tool_call".to_string());
+        }
+        config.security.approval_mode = Some("full_auto".to_string());
+        let loaded = load_agent_profiles(&path)
+            .expect("load profiles")
+            .remove("review")
+            .expect("saved profile");
+        apply_agent_profile(&loaded, &mut config);
+
+        assert_eq!(config.llm.default, "deep");
+        let restored = capture_agent_profile(
+            &config,
+            Some(config.llm.default.clone()),
+            loaded.agent_id.as_deref(),
+            loaded.personality.clone(),
+        );
+        assert_eq!(restored, saved);
+        assert_eq!(restored.agent_id.as_deref(), Some("reviewer"));
+        assert_eq!(restored.tool_call_mode.as_deref(), Some("function_call"));
+        assert_eq!(restored.approval_mode.as_deref(), Some("suggest"));
+        assert_eq!(restored.personality.as_deref(), Some("concise"));
+        let _ = fs::remove_dir_all(path.parent().expect("parent"));
+    }
+
+    #[test]
+    fn profile_actions_need_a_name() {
+        assert!(matches!(
+            parse_agent_profile_action("load  review"),
+            Some(AgentProfileAction::Load(name)) if name == "review"
+        ));
+        assert!(parse_agent_profile_action("save").is_none());
+        assert!(parse_agent_profile_action("my-agent").is_none());
+    }
+}
@@ -0,0 +1,151 @@
+// AGENTS.md 分层发现：从启动目录向上走到仓库根（含 .git 的目录），收集沿途 AGENTS.md，按由远到近拼接进 agent_prompt。
+// 启动目录即工作区根，其 AGENTS.md 由编排器按线程快照注入，这里不再重复收集。
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const AGENTS_MD_FILE_NAME: &str = "AGENTS.md";
+/// Total budget for the merged guidance; outer files are dropped first.
+const MAX_AGENTS_MD_CHARS: usize = 32 * 1024;
+
+/// Ancestor `AGENTS.md` files that apply to `launch_dir`, outermost first. The walk stops at
+/// the first directory holding `.git` and never climbs into `home`; outside a repository
+/// nothing is collected. `launch_dir` is the CLI workspace root, whose own `AGENTS.md` the
+/// orchestrator already snapshots into the thread prompt, so it is skipped here.
+pub(crate) fn discover_agents_md_files(launch_dir: &Path, home: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let mut reached_repo_root = false;
+    for dir in launch_dir.ancestors() {
+        if dir != launch_dir && home.is_some_and(|home| dir == home) {
+            break;
+        }
+        dirs.push(dir.to_path_buf());
+        if dir.join(".git").exists() {
+            reached_repo_root = true;
+            break;
+        }
+    }
+    if !reached_repo_root {
+        return Vec::new();
+    }
+    let workspace_file = launch_dir.join(AGENTS_MD_FILE_NAME);
+    let mut seen = HashSet::new();
+    seen.insert(fs::canonicalize(&workspace_file).unwrap_or_else(|_| workspace_file.clone()));
+    let mut files = dirs
+        .into_iter()
+        .map(|dir| dir.join(AGENTS_MD_FILE_NAME))
+        .filter(|path| path.is_file())
+        .filter(|path| seen.insert(fs::canonicalize(path).unwrap_or_else(|_| path.clone())))
+        .collect::<Vec<_>>();
+    files.reverse();
+    files
+}
+
+/// Merged ancestor `AGENTS.md` guidance for `launch_dir`, nearest last so it wins on
+/// conflicts. Files repeating the workspace `AGENTS.md` verbatim are dropped as well.
+pub(crate) fn load_agents_md_prompt(launch_dir: &Path, home: Option<&Path>) -> Option<String> {
+    let files = discover_agents_md_files(launch_dir, home);
+    let mut seen_contents = HashSet::new();
+    if let Ok(content) = fs::read_to_string(launch_dir.join(AGENTS_MD_FILE_NAME)) {
+        seen_contents.insert(content.trim().to_string());
+    }
+    let mut remaining = MAX_AGENTS_MD_CHARS;
+    let mut blocks = Vec::new();
+    // Spend the budget nearest first so the most specific guidance survives the cap.
+    for path in files.iter().rev() {
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let content = content.trim();
+        if content.is_empty() || !seen_contents.insert(content.to_string()) {
+            continue;
+        }
+        if remaining == 0 {
+            break;
+        }
+        let block = content.chars().take(remaining).collect::<String>();
+        remaining -= block.chars().count();
+        blocks.push(block);
+    }
+    if blocks.is_empty() {
+        return None;
+    }
+    blocks.reverse();
+    Some(blocks.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wunder_server::merge_agent_prompt_with_thread_agents_snapshot;
+
+    #[test]
+    fn nested_agents_md_merge_outermost_first() {
+        let root = std::env::temp_dir().join(format!(
+            "wunder-cli-agents-md-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let repo = root.join("repo");
+        let nested = repo.join("crates").join("app");
+        fs::create_dir_all(repo.join(".git")).expect("create repo");
+        fs::create_dir_all(&nested).expect("create nested");
+        fs::write(root.join(AGENTS_MD_FILE_NAME), "outside the repo").expect("write outer");
+        fs::write(repo.join(AGENTS_MD_FILE_NAME), "repo rules").expect("write repo");
+        fs::write(
+            repo.join("crates").join(AGENTS_MD_FILE_NAME),
+            "repo rules\n",
+        )
+        .expect("write duplicate");
+        fs::write(nested.join(AGENTS_MD_FILE_NAME), "app rules").expect("write nested");
+
+        let files = discover_agents_md_files(&nested, None);
+        assert_eq!(
+            files,
+            vec![
+                repo.join(AGENTS_MD_FILE_NAME),
+                repo.join("crates").join(AGENTS_MD_FILE_NAME),
+            ]
+        );
+        assert_eq!(
+            load_agents_md_prompt(&nested, None).as_deref(),
+            Some("repo rules")
+        );
+
+        // Without a repository nothing is collected, and home is never read.
+        let outside = root.join("scratch");
+        fs::create_dir_all(&outside).expect("create scratch");
+        fs::write(outside.join(AGENTS_MD_FILE_NAME), "scratch rules").expect("write scratch");
+        assert_eq!(load_agents_md_prompt(&outside, None), None);
+        assert_eq!(load_agents_md_prompt(&nested, Some(repo.as_path())), None);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn each_agents_md_appears_once_in_thread_prompt() {
+        let root = std::env::temp_dir().join(format!(
+            "wunder-cli-agents-md-once-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let nested = root.join("crates").join("app");
+        fs::create_dir_all(root.join(".git")).expect("create repo");
+        fs::create_dir_all(&nested).expect("create nested");
+        fs::write(root.join(AGENTS_MD_FILE_NAME), "repo rules").expect("write repo");
+        fs::write(nested.join(AGENTS_MD_FILE_NAME), "app rules").expect("write nested");
+
+        for launch_dir in [root.as_path(), nested.as_path()] {
+            let cli_prompt = load_agents_md_prompt(launch_dir, None);
+            // The orchestrator appends the workspace-root snapshot on the first turn.
+            let prompt = merge_agent_prompt_with_thread_agents_snapshot(
+                cli_prompt.as_deref(),
+                None,
+                launch_dir,
+                true,
+            )
+            .expect("thread prompt");
+            assert_eq!(prompt.matches("repo rules").count(), 1, "{prompt}");
+            let app_count = usize::from(launch_dir == nested.as_path());
+            assert_eq!(prompt.matches("app rules").count(), app_count, "{prompt}");
+        }
+        let _ = fs::remove_dir_all(&root);
+    }
+}
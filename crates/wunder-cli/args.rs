@@ -40,6 +40,32 @@ pub struct GlobalArgs {
     #[arg(long = "approval-mode", global = true, value_enum)]
     pub approval_mode: Option<ApprovalModeArg>,
 
+    /// Sampling temperature for this run, 0-2 / 本次运行的采样温度，范围 0-2。
+    #[arg(long, global = true, value_parser = crate::sampling_overrides::parse_temperature)]
+    pub temperature: Option<f32>,
+
+    /// Nucleus sampling top_p for this run, 0-1 / 本次运行的 top_p，范围 0-1。
+    #[arg(long = "top-p", global = true, value_parser = crate::sampling_overrides::parse_top_p)]
+    pub top_p: Option<f32>,
+
+    /// Stop sequence for this run (repeatable, up to 4) / 本次运行的停止序列（可重复，最多 4 个）。
+    #[arg(long = "stop", global = true, value_parser = crate::sampling_overrides::parse_stop_sequence)]
+    pub stop: Vec<String>,
+
+    /// Require a JSON object answer (OpenAI-compatible providers) / 要求模型以 JSON 对象作答（仅 OpenAI 兼容接口）。
+    #[arg(long = "json-mode", global = true, default_value_t = false)]
+    pub json_mode: bool,
+
+    /// Require an answer matching this JSON Schema file / 要求回答符合该 JSON Schema 文件。
+    #[arg(
+        long = "json-schema",
+        global = true,
+        value_name = "FILE",
+        conflicts_with = "json_mode",
+        value_parser = crate::response_format_args::parse_json_schema_file
+    )]
+    pub json_schema: Option<serde_json::Value>,
+
     /// Session id / 会话 ID。
     #[arg(long, global = true)]
     pub session: Option<String>,
@@ -52,6 +78,10 @@ pub struct GlobalArgs {
     #[arg(long = "attach", global = true)]
     pub attachments: Vec<String>,
 
+    /// Save attached documents into the `attachments` knowledge base / 将附件文档存入 `attachments` 知识库，供后续对话检索。
+    #[arg(long = "remember", global = true, default_value_t = false)]
+    pub remember: bool,
+
     /// Output stream events as JSONL / 以 JSONL 输出流事件。
     #[arg(long, global = true, default_value_t = false)]
     pub json: bool,
@@ -60,14 +90,26 @@ pub struct GlobalArgs {
     #[arg(long = "lang", alias = "language", global = true)]
     pub language: Option<String>,
 
-    /// Base config path / 基础配置路径（默认 <repo>/config/wunder.yaml）。
+    /// Config path, overriding --profile and WUNDER_CONFIG_OVERRIDE_PATH / 配置文件路径，优先于 --profile 与 WUNDER_CONFIG_OVERRIDE_PATH（默认运行时目录 config/wunder.yaml）。
     #[arg(long = "config", global = true)]
     pub config_path: Option<PathBuf>,
 
+    /// Named config profile under WUNDER_HOME/profiles, remembered for later runs / 使用 WUNDER_HOME/profiles 下的命名配置档并记住（default 表示运行时配置）。
+    #[arg(long = "profile", global = true)]
+    pub profile: Option<String>,
+
+    /// Create the chosen config file when it does not exist / 指定的配置文件不存在时创建。
+    #[arg(long = "create", global = true, default_value_t = false)]
+    pub create_config: bool,
+
     /// Runtime temp root / 运行时临时目录（默认用户目录 .wunder/cli/WUNDER_TEMP）。
     #[arg(long = "temp-root", global = true)]
     pub temp_root: Option<PathBuf>,
 
+    /// Project repository root used by /diff and /review (default: nearest .git ancestor, else the launch directory) / 项目仓库根目录，供 /diff、/review 使用（默认向上查找最近的 .git，找不到时为启动目录）。
+    #[arg(long = "repo-root", global = true, value_name = "PATH")]
+    pub repo_root: Option<PathBuf>,
+
     /// Logical user id / 逻辑用户 ID（单用户默认 cli_user）。
     #[arg(long = "user", global = true)]
     pub user: Option<String>,
@@ -75,6 +117,14 @@ pub struct GlobalArgs {
     /// Disable streaming output / 关闭流式输出。
     #[arg(long = "no-stream", global = true, default_value_t = false)]
     pub no_stream: bool,
+
+    /// Show model reasoning (dimmed, on stderr) / 显示模型推理过程（暗色，输出到 stderr）。
+    #[arg(long = "show-reasoning", global = true, default_value_t = false)]
+    pub show_reasoning: bool,
+
+    /// Cite knowledge sources under the answer / 在回答下方列出知识库引用来源。
+    #[arg(long = "cite", global = true, default_value_t = false)]
+    pub cite: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -138,9 +188,33 @@ pub enum Command {
     /// Inspect and update runtime config / 查看与修改运行配置。
     Config(ConfigCommand),
 
+    /// List and switch named config profiles / 查看与切换命名配置档。
+    Profile(ProfileCommand),
+
     /// Diagnose local runtime environment / 诊断本地运行环境。
     Doctor(DoctorCommand),
 
+    /// Review uncommitted git changes once / 单次审查当前未提交的 git 改动。
+    Review(ReviewCommand),
+
+    /// Inspect deployment user accounts (admin auth required) / 查看部署用户账号（需管理员认证）。
+    Users(UsersCommand),
+
+    /// Local storage maintenance / 本地存储维护。
+    Maintenance(MaintenanceCommand),
+
+    /// Stress the local orchestrator and report throughput / 本地压测编排器并输出吞吐指标。
+    Bench(BenchCommand),
+
+    /// Launch and inspect multi-agent swarm tasks / 发起并查看多智能体蜂群任务。
+    Swarm(SwarmCommand),
+
+    /// Maintain sandbox containers / 维护沙盒容器。
+    Sandbox(SandboxCommand),
+
+    /// Export and inspect recorded sessions / 导出与查看已记录会话。
+    Session(SessionCommand),
+
     /// Generate shell completion scripts / 生成 Shell 补全脚本。
     Completion(CompletionCommand),
 }
@@ -216,6 +290,23 @@ pub struct ToolRunCommand {
     /// JSON arguments object / JSON 参数对象。
     #[arg(long, default_value = "{}")]
     pub args: String,
+
+    /// Run the tool N times (1-1000) and report latency / 重复运行 N 次（1-1000）并输出耗时统计。
+    #[arg(
+        long,
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u32)
+            .range(1..=i64::from(crate::tool_repeat::MAX_TOOL_RUN_REPEAT))
+    )]
+    pub repeat: u32,
+
+    /// Report per-run and aggregate latency (min/mean/p95/max) / 输出每次与汇总耗时（min/mean/p95/max）。
+    #[arg(long, default_value_t = false)]
+    pub time: bool,
+
+    /// Keep running after a failed run / 某次运行失败后继续执行剩余次数。
+    #[arg(long = "continue-on-error", default_value_t = false)]
+    pub continue_on_error: bool,
 }
 
 #[derive(Debug, Args)]
@@ -337,6 +428,8 @@ pub enum SkillsSubcommand {
     Remove(SkillNameCommand),
     /// Print local skill root path / 输出本地技能根目录。
     Root,
+    /// Run one skill directly with JSON args / 直接运行单个技能。
+    Run(SkillsRunCommand),
 }
 
 #[derive(Debug, Args)]
@@ -361,6 +454,20 @@ pub struct SkillNameCommand {
     pub name: String,
 }
 
+#[derive(Debug, Args)]
+pub struct SkillsRunCommand {
+    /// Skill name / 技能名。
+    pub name: String,
+
+    /// JSON arguments object / JSON 参数对象。
+    #[arg(long, default_value = "{}")]
+    pub args: String,
+
+    /// Output as JSON / 以 JSON 输出。
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
 #[derive(Debug, Args)]
 pub struct ConfigCommand {
     #[command(subcommand)]
@@ -370,11 +477,71 @@ pub struct ConfigCommand {
 #[derive(Debug, Subcommand)]
 pub enum ConfigSubcommand {
     /// Show runtime config / 查看运行配置。
-    Show,
+    Show(ConfigShowCommand),
     /// Set tool call mode / 设置工具调用模式。
     SetToolCallMode(SetToolCallModeCommand),
     /// Set approval mode / 设置审批模式。
     SetApprovalMode(SetApprovalModeCommand),
+    /// Read a config value by dotted path / 按点路径读取配置项。
+    Get(ConfigGetCommand),
+    /// Set a config value by dotted path / 按点路径设置配置项。
+    Set(ConfigSetCommand),
+    /// Send a short probe to the active model (or --model) / 向当前模型（或 --model）发送探测请求。
+    Test(ConfigTestCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct ProfileCommand {
+    #[command(subcommand)]
+    pub command: ProfileSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProfileSubcommand {
+    /// List profiles and mark the saved one / 列出配置档并标记已保存的配置档。
+    List,
+    /// Switch to a profile, creating it on first use / 切换配置档（首次使用时创建）。
+    Use(ProfileUseCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct ProfileUseCommand {
+    /// Profile name; `default` returns to the runtime config / 配置档名称，default 表示运行时配置。
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigShowCommand {
+    /// Print api keys, tokens and auth headers unmasked / 原样输出 api_key、令牌与认证头。
+    #[arg(long, default_value_t = false)]
+    pub show_secrets: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigTestCommand {
+    /// Give up after this many seconds / 超时秒数。
+    #[arg(long = "timeout-s", default_value_t = 30.0)]
+    pub timeout_s: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigGetCommand {
+    /// Dotted path, e.g. llm.models.gpt-4.1.temperature / 点路径，如 llm.models.gpt-4.1.temperature。
+    pub key: String,
+
+    /// Print api keys, tokens and auth headers unmasked / 原样输出 api_key、令牌与认证头。
+    #[arg(long, default_value_t = false)]
+    pub show_secrets: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigSetCommand {
+    /// Dotted path, e.g. llm.models.gpt-4.1.temperature / 点路径，如 llm.models.gpt-4.1.temperature。
+    pub key: String,
+
+    /// Value as JSON (0.2, true, null, ["a"]) or plain text / 值：JSON（0.2、true、null、["a"]）或纯文本。
+    #[arg(allow_hyphen_values = true)]
+    pub value: String,
 }
 
 #[derive(Debug, Args)]
@@ -400,6 +567,251 @@ pub struct DoctorCommand {
     /// Print extended diagnostics / 输出扩展诊断信息。
     #[arg(long, default_value_t = false)]
     pub verbose: bool,
+
+    /// Probe the default model, enabled MCP connectors and A2A services / 探测默认模型、已启用的 MCP 连接器与 A2A 服务的连通性。
+    #[arg(long, default_value_t = false)]
+    pub network: bool,
+
+    /// Per-endpoint timeout for --network in seconds / --network 每个端点的超时秒数。
+    #[arg(long = "timeout-s", default_value_t = 5.0)]
+    pub timeout_s: f64,
+
+    /// Output the full report as one JSON object / 以单个 JSON 对象输出完整诊断报告。
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ReviewCommand {
+    /// Ask for severity-tagged findings and print a summary / 要求模型按严重程度标注问题并输出统计。
+    #[arg(long, default_value_t = false)]
+    pub structured: bool,
+
+    /// Exit non-zero when a finding reaches this severity, the answer has no severity verdict or the turn stops early (implies --structured) / 存在不低于该严重程度的问题、回复缺少严重程度结论或本轮提前结束时以非零码退出（隐含 --structured）。
+    #[arg(long = "fail-on", value_enum, value_name = "SEVERITY")]
+    pub fail_on: Option<crate::review::ReviewSeverity>,
+
+    /// Write the prompt and response to a Markdown file / 将提示词与回复写入 Markdown 文件。
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Include the last commit (author, date, subject) of each changed file / 附带每个变更文件最近一次提交的作者、日期与标题。
+    #[arg(long = "with-history", default_value_t = false)]
+    pub with_history: bool,
+
+    /// Optional review focus / 可选的审查重点。
+    #[arg(value_name = "FOCUS", trailing_var_arg = true)]
+    pub focus: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct UsersCommand {
+    #[command(subcommand)]
+    pub command: UsersSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum UsersSubcommand {
+    /// List users with role, access level, quota and last login / 列出用户及其角色、访问级别、额度与最近登录。
+    List(UsersListCommand),
+    /// Grant or revoke individual tools for a user / 为用户授予或撤销指定工具。
+    Tools(UsersToolsCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct AdminAuthArgs {
+    /// Admin API key (defaults to WUNDER_API_KEY) / 管理员 API Key（默认读取 WUNDER_API_KEY）。
+    #[arg(long = "api-key")]
+    pub api_key: Option<String>,
+
+    /// Session token of an admin account / 管理员账号的会话令牌。
+    #[arg(long)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct UsersListCommand {
+    /// Only users holding this role / 仅列出拥有该角色的用户。
+    #[arg(long)]
+    pub role: Option<String>,
+
+    /// Only users with this account status / 仅列出该账号状态的用户。
+    #[arg(long)]
+    pub status: Option<String>,
+
+    /// Org unit id or name; users in its sub-units are included / 组织单元 ID 或名称，包含下级单元的用户。
+    #[arg(long)]
+    pub unit: Option<String>,
+
+    #[command(flatten)]
+    pub auth: AdminAuthArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct UsersToolsCommand {
+    #[command(subcommand)]
+    pub command: UsersToolsSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum UsersToolsSubcommand {
+    /// Add tools to the user's allow-list / 将工具加入用户白名单。
+    Grant(UsersToolsChangeCommand),
+    /// Remove tools from the user's allow-list / 从用户白名单移除工具。
+    Revoke(UsersToolsChangeCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct UsersToolsChangeCommand {
+    /// User id or username / 用户 ID 或用户名。
+    pub user: String,
+
+    /// Tool names or builtin aliases / 工具名称或内置别名。
+    #[arg(required = true)]
+    pub tools: Vec<String>,
+
+    #[command(flatten)]
+    pub auth: AdminAuthArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct MaintenanceCommand {
+    #[command(subcommand)]
+    pub command: MaintenanceSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MaintenanceSubcommand {
+    #[command(
+        about = "Compact the SQLite database (VACUUM + WAL checkpoint); blocks writers while running / 压缩 SQLite 数据库（VACUUM + WAL 检查点），执行期间阻塞写入"
+    )]
+    Vacuum,
+}
+
+#[derive(Debug, Args)]
+pub struct SwarmCommand {
+    #[command(subcommand)]
+    pub command: SwarmSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SwarmSubcommand {
+    #[command(
+        about = "Run a goal in a parent session that delegates to subagents, showing child progress / 在父会话中执行目标并派生子智能体，实时展示子会话进度"
+    )]
+    Run(SwarmRunCommand),
+
+    #[command(about = "Show the session tree of a swarm / 查看蜂群任务的会话树")]
+    Status(SwarmStatusCommand),
+
+    #[command(about = "List hives and their active agents / 列出蜂巢及其活跃智能体")]
+    Hives,
+}
+
+#[derive(Debug, Args)]
+pub struct SwarmRunCommand {
+    /// Goal to split across subagents / 需要拆分给子智能体的目标。
+    #[arg(value_name = "GOAL")]
+    pub goal: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SwarmStatusCommand {
+    /// Parent session id / 父会话 ID。
+    #[arg(value_name = "PARENT_SESSION_ID")]
+    pub parent_session_id: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SandboxCommand {
+    #[command(subcommand)]
+    pub command: SandboxSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SandboxSubcommand {
+    #[command(
+        about = "Clear the working directory of a sandbox container / 清空沙盒容器的工作目录"
+    )]
+    Reset(SandboxResetCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct SandboxResetCommand {
+    /// Container id 1-10; defaults to the current session's container / 容器编号 1-10，默认当前会话所用容器。
+    #[arg(value_name = "ID")]
+    pub container_id: Option<i32>,
+}
+
+#[derive(Debug, Args)]
+pub struct SessionCommand {
+    #[command(subcommand)]
+    pub command: SessionSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SessionSubcommand {
+    #[command(
+        about = "Export a session as chat messages for reuse elsewhere / 导出会话消息以便在其他工具中复用"
+    )]
+    Export(SessionExportCommand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum SessionExportFormatArg {
+    /// OpenAI chat messages `[{role, content}, ...]`.
+    Openai,
+}
+
+#[derive(Debug, Args)]
+pub struct SessionExportCommand {
+    /// Session id; defaults to --session or the saved session / 会话 ID，默认 --session 或当前保存会话。
+    #[arg(value_name = "SESSION_ID")]
+    pub session_id: Option<String>,
+
+    /// Output format / 导出格式。
+    #[arg(long, value_enum, default_value_t = SessionExportFormatArg::Openai)]
+    pub format: SessionExportFormatArg,
+
+    /// Embed attachment text and images instead of referencing them / 内联附件文本与图片，而非仅引用路径。
+    #[arg(long = "inline-attachments", default_value_t = false)]
+    pub inline_attachments: bool,
+
+    /// Write to this file instead of stdout / 写入文件而非标准输出。
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchCommand {
+    /// Prompt sent by every request / 每个请求发送的提问（留空使用内置题库）。
+    #[arg(value_name = "PROMPT")]
+    pub prompt: Option<String>,
+
+    /// Concurrent workers, comma separated to sweep several levels / 并发数，逗号分隔可依次压测多档。
+    #[arg(long, short = 'c', value_delimiter = ',', default_value = "4")]
+    pub concurrency: Vec<usize>,
+
+    /// Requests per concurrency level / 每档并发的请求总数。
+    #[arg(long = "requests", short = 'n')]
+    pub requests: Option<u64>,
+
+    /// Seconds to keep each concurrency level busy / 每档并发持续压测的秒数。
+    #[arg(long = "duration-s")]
+    pub duration_s: Option<f64>,
+
+    /// Per-request timeout seconds / 单个请求超时（秒）。
+    #[arg(long = "timeout-s")]
+    pub timeout_s: Option<f64>,
+
+    /// Cap model output tokens / 限制模型最大输出 token。
+    #[arg(long = "max-tokens")]
+    pub max_tokens: Option<u32>,
+
+    /// Skip tool calls to isolate model latency / 跳过工具调用，仅测量模型耗时。
+    #[arg(long = "no-tools", default_value_t = false)]
+    pub no_tools: bool,
 }
 
 #[derive(Debug, Args)]
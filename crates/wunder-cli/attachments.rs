@@ -7,7 +7,10 @@ use std::path::{Path, PathBuf};
 use wunder_server::attachment::{
     convert_to_markdown, get_supported_extensions, sanitize_filename_stem,
 };
-use wunder_server::schemas::AttachmentPayload;
+use wunder_server::attachment_metadata::{
+    extract_attachment_metadata, image_dimensions_from_bytes,
+};
+use wunder_server::schemas::{AttachmentMetadata, AttachmentPayload};
 
 const MAX_ATTACHMENT_FILE_BYTES: u64 = 10 * 1024 * 1024;
 const MAX_ATTACHMENT_TEXT_CHARS: usize = 180_000;
@@ -25,6 +28,8 @@ pub(crate) struct PreparedAttachment {
     pub kind: AttachmentKind,
     pub size_bytes: u64,
     pub detail: Option<String>,
+    /// Save into the `attachments` knowledge base so later turns can retrieve it.
+    pub remember: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,7 +37,7 @@ pub(crate) enum AttachAction {
     Show,
     Clear,
     Drop(usize),
-    Add(String),
+    Add { path: String, remember: bool },
 }
 
 pub(crate) fn parse_attach_action(args: &str) -> Result<AttachAction> {
@@ -59,25 +64,23 @@ pub(crate) fn parse_attach_action(args: &str) -> Result<AttachAction> {
         }
         return Ok(AttachAction::Drop(index));
     }
-    if let Some(rest) = cleaned.strip_prefix("add ") {
-        let path = normalize_attachment_path_input(rest);
-        if path.is_empty() {
-            return Err(anyhow!("missing attachment path"));
-        }
-        return Ok(AttachAction::Add(path));
-    }
-    let path = normalize_attachment_path_input(cleaned);
+    let rest = cleaned.strip_prefix("add ").unwrap_or(cleaned).trim_start();
+    let (rest, remember) = match rest.strip_prefix("--remember") {
+        Some(tail) if tail.is_empty() || tail.starts_with(char::is_whitespace) => (tail, true),
+        _ => (rest, false),
+    };
+    let path = normalize_attachment_path_input(rest);
     if path.is_empty() {
         return Err(anyhow!("missing attachment path"));
     }
-    Ok(AttachAction::Add(path))
+    Ok(AttachAction::Add { path, remember })
 }
 
 pub(crate) fn attach_usage(language: &str) -> String {
     locale::tr(
         language,
-        "用法: /attach [list|clear|drop <index>|<path>]",
-        "usage: /attach [list|clear|drop <index>|<path>]",
+        "用法: /attach [list|clear|drop <index>|[--remember] <path>]",
+        "usage: /attach [list|clear|drop <index>|[--remember] <path>]",
     )
 }
 
@@ -105,14 +108,27 @@ pub(crate) fn summarize_attachment(
         item.size_bytes,
         item.source
     );
-    if let Some(detail) = item
+    let base = if item.remember {
+        format!("{base} [{}]", locale::tr(language, "入库", "remember"))
+    } else {
+        base
+    };
+    let details = item
         .detail
-        .as_deref()
+        .clone()
         .filter(|value| !value.trim().is_empty())
-    {
-        return format!("{base} ({detail})");
+        .into_iter()
+        .chain(
+            item.payload
+                .metadata
+                .as_ref()
+                .and_then(AttachmentMetadata::summary),
+        )
+        .collect::<Vec<_>>();
+    if details.is_empty() {
+        return base;
     }
-    base
+    format!("{base} ({})", details.join(", "))
 }
 
 pub(crate) fn to_request_attachments(
@@ -124,6 +140,11 @@ pub(crate) fn to_request_attachments(
     Some(items.iter().map(|item| item.payload.clone()).collect())
 }
 
+/// Whether the turn should save its attachments into the knowledge base.
+pub(crate) fn remember_requested(items: &[PreparedAttachment]) -> bool {
+    items.iter().any(|item| item.remember)
+}
+
 pub(crate) async fn prepare_attachment_from_path(
     runtime: &CliRuntime,
     raw_path: &str,
@@ -162,6 +183,12 @@ pub(crate) async fn prepare_attachment_from_path(
         let bytes = tokio::fs::read(&target_path)
             .await
             .with_context(|| format!("read image attachment failed: {}", target_path.display()))?;
+        let metadata =
+            image_dimensions_from_bytes(&bytes).map(|(width, height)| AttachmentMetadata {
+                width: Some(width),
+                height: Some(height),
+                ..AttachmentMetadata::default()
+            });
         let encoded = STANDARD.encode(bytes);
         let content = format!("data:{mime};base64,{encoded}");
         let file_name = file_name_or_default(target_path.as_path(), "image");
@@ -172,15 +199,21 @@ pub(crate) async fn prepare_attachment_from_path(
                 content: Some(content),
                 content_type: Some(mime.to_string()),
                 public_path: None,
+                attachment_id: None,
+                metadata,
             },
             kind: AttachmentKind::Image,
             size_bytes: file_size,
             detail: Some("data-url".to_string()),
+            remember: false,
         });
     }
 
     let text = load_text_attachment(runtime, target_path.as_path(), extension.as_str()).await?;
     guard_text_size(text.as_str())?;
+    // Best effort: a document whose metadata cannot be read still attaches.
+    let metadata =
+        extract_attachment_metadata(target_path.as_path(), extension.as_str(), Some(&text)).await;
     let file_name = file_name_or_default(target_path.as_path(), "document");
     Ok(PreparedAttachment {
         source: display_path(runtime.launch_dir.as_path(), target_path.as_path()),
@@ -189,10 +222,13 @@ pub(crate) async fn prepare_attachment_from_path(
             content: Some(text),
             content_type: Some("text/markdown".to_string()),
             public_path: None,
+            attachment_id: None,
+            metadata,
         },
         kind: AttachmentKind::Text,
         size_bytes: file_size,
         detail: None,
+        remember: false,
     })
 }
 
@@ -360,18 +396,75 @@ mod tests {
         );
         assert_eq!(
             parse_attach_action("add ./foo.md").unwrap(),
-            AttachAction::Add("./foo.md".to_string())
+            AttachAction::Add {
+                path: "./foo.md".to_string(),
+                remember: false,
+            }
         );
         assert_eq!(
             parse_attach_action("\"C:\\\\demo\\\\a.md\"").unwrap(),
-            AttachAction::Add("C:\\\\demo\\\\a.md".to_string())
+            AttachAction::Add {
+                path: "C:\\\\demo\\\\a.md".to_string(),
+                remember: false,
+            }
         );
     }
 
+    #[test]
+    fn parse_attach_action_supports_remember_flag() {
+        let remembered = AttachAction::Add {
+            path: "./spec.md".to_string(),
+            remember: true,
+        };
+        assert_eq!(
+            parse_attach_action("--remember ./spec.md").unwrap(),
+            remembered
+        );
+        assert_eq!(
+            parse_attach_action("add --remember ./spec.md").unwrap(),
+            remembered
+        );
+        assert_eq!(
+            parse_attach_action("--remembered.md").unwrap(),
+            AttachAction::Add {
+                path: "--remembered.md".to_string(),
+                remember: false,
+            }
+        );
+        assert!(parse_attach_action("--remember").is_err());
+    }
+
     #[test]
     fn image_mime_resolution_works() {
         assert_eq!(image_mime_by_extension(".png"), Some("image/png"));
         assert_eq!(image_mime_by_extension(".jpg"), Some("image/jpeg"));
         assert_eq!(image_mime_by_extension(".txt"), None);
     }
+
+    #[test]
+    fn summary_lists_extracted_metadata() {
+        let item = PreparedAttachment {
+            source: "docs/report.pdf".to_string(),
+            payload: AttachmentPayload {
+                name: Some("report.pdf".to_string()),
+                content: Some("text".to_string()),
+                content_type: Some("text/markdown".to_string()),
+                public_path: None,
+                attachment_id: None,
+                metadata: Some(AttachmentMetadata {
+                    page_count: Some(12),
+                    language: Some("en".to_string()),
+                    ..AttachmentMetadata::default()
+                }),
+            },
+            kind: AttachmentKind::Text,
+            size_bytes: 2048,
+            detail: None,
+            remember: false,
+        };
+        assert_eq!(
+            summarize_attachment(&item, 0, "en-US"),
+            " 1. report.pdf [text] 2048 bytes <- docs/report.pdf (12 pages, lang en)"
+        );
+    }
 }
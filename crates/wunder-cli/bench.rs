@@ -0,0 +1,185 @@
+// 本地压测：`wunder-cli bench` 以指定并发驱动编排器，输出吞吐与延迟分位数。
+use crate::args::{BenchCommand, GlobalArgs};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+use wunder_server::throughput::{ThroughputConfig, ThroughputReport, ThroughputSample};
+
+/// Model name used when nothing is configured, so the mock reply path has a target.
+const BENCH_FALLBACK_MODEL: &str = "wunder-bench";
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+pub(crate) async fn handle_bench(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: BenchCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    if command.requests == Some(0) {
+        return Err(anyhow!(locale::tr(
+            language.as_str(),
+            "--requests 必须大于 0",
+            "--requests must be greater than 0",
+        )));
+    }
+    if command
+        .duration_s
+        .is_some_and(|value| !value.is_finite() || value <= 0.0)
+    {
+        return Err(anyhow!(locale::tr(
+            language.as_str(),
+            "--duration-s 必须大于 0",
+            "--duration-s must be greater than 0",
+        )));
+    }
+    let model_name = runtime
+        .resolve_model_name(global.model.as_deref())
+        .await
+        .unwrap_or_else(|| BENCH_FALLBACK_MODEL.to_string());
+    let mut config = ThroughputConfig::new(
+        command.concurrency,
+        None,
+        Some(model_name),
+        command.timeout_s,
+        command.max_tokens,
+    )
+    .map_err(|err| anyhow!(err))?;
+    if let Some(prompt) = command.prompt.as_deref() {
+        config = config.with_prompt(prompt);
+    }
+    config.skip_tool_calls = command.no_tools;
+    config.mock_if_unconfigured = true;
+    config.requests_per_step = command.requests;
+    config.step_duration_s = command.duration_s;
+
+    let state = &runtime.state;
+    let started = state
+        .throughput
+        .start(
+            state.kernel.orchestrator.clone(),
+            state.monitor.clone(),
+            config,
+        )
+        .await
+        .map_err(|err| anyhow!(err))?;
+    let run_id = started.run.id;
+    if !global.json {
+        eprintln!(
+            "{}",
+            locale::tr(
+                language.as_str(),
+                "压测进行中，按 Ctrl+C 提前停止…",
+                "benchmark running, press Ctrl+C to stop early...",
+            )
+        );
+    }
+    let mut stop_requested = false;
+    loop {
+        let finished = state
+            .throughput
+            .status()
+            .await
+            .active
+            .is_none_or(|active| active.run.id != run_id);
+        if finished {
+            break;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(STATUS_POLL_INTERVAL) => {}
+            _ = tokio::signal::ctrl_c(), if !stop_requested => {
+                stop_requested = true;
+                let _ = state.throughput.stop().await;
+            }
+        }
+    }
+    let report = state
+        .throughput
+        .report(Some(&run_id))
+        .await
+        .map_err(|err| anyhow!(err))?;
+    if global.json {
+        println!("{}", serde_json::to_string(&report)?);
+        return Ok(());
+    }
+    for line in report_lines(language.as_str(), &report) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn report_lines(language: &str, report: &ThroughputReport) -> Vec<String> {
+    let is_zh = locale::is_zh_language(language);
+    let run = &report.summary.run;
+    let metrics = &report.summary.metrics;
+    let mut lines = Vec::new();
+    let model = run.model_name.as_deref().unwrap_or("-");
+    let tools = match (is_zh, run.skip_tool_calls) {
+        (true, true) => "关闭",
+        (true, false) => "开启",
+        (false, true) => "off",
+        (false, false) => "on",
+    };
+    lines.push(if is_zh {
+        format!(
+            "模型 {model}，状态 {}，耗时 {:.1}s，工具调用 {tools}",
+            run.status, run.elapsed_s
+        )
+    } else {
+        format!(
+            "model {model}, status {}, elapsed {:.1}s, tools {tools}",
+            run.status, run.elapsed_s
+        )
+    });
+    for sample in &report.samples {
+        lines.push(sample_line(is_zh, sample));
+    }
+    let (p50, p90, p99) = (
+        format_ms(metrics.p50_latency_ms),
+        format_ms(metrics.p90_latency_ms),
+        format_ms(metrics.p99_latency_ms),
+    );
+    lines.push(if is_zh {
+        format!(
+            "合计：{} 个请求（失败 {}），{:.2} req/s，p50 {p50}，p90 {p90}，p99 {p99}，共 {} token",
+            metrics.total_requests,
+            metrics.error_requests,
+            metrics.rps,
+            metrics.total_tokens
+        )
+    } else {
+        format!(
+            "total: {} requests ({} failed), {:.2} req/s, p50 {p50}, p90 {p90}, p99 {p99}, {} tokens",
+            metrics.total_requests, metrics.error_requests, metrics.rps, metrics.total_tokens
+        )
+    });
+    for error in report.summary.errors.iter().take(3) {
+        lines.push(format!("! {}", error.message));
+    }
+    lines
+}
+
+fn sample_line(is_zh: bool, sample: &ThroughputSample) -> String {
+    let (p50, p99) = (
+        format_ms(sample.p50_latency_ms),
+        format_ms(sample.p99_latency_ms),
+    );
+    let decode = sample
+        .total_decode_speed_tps
+        .map_or_else(|| "-".to_string(), |value| format!("{value:.1} tok/s"));
+    if is_zh {
+        format!(
+            "- 并发 {}：{} 个请求（失败 {}），{:.2} req/s，p50 {p50}，p99 {p99}，解码 {decode}",
+            sample.concurrency, sample.total_requests, sample.error_requests, sample.rps
+        )
+    } else {
+        format!(
+            "- concurrency {}: {} requests ({} failed), {:.2} req/s, p50 {p50}, p99 {p99}, decode {decode}",
+            sample.concurrency, sample.total_requests, sample.error_requests, sample.rps
+        )
+    }
+}
+
+fn format_ms(value: Option<u64>) -> String {
+    value.map_or_else(|| "-".to_string(), |ms| format!("{ms}ms"))
+}
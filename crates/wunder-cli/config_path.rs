@@ -0,0 +1,260 @@
+// config get|set 点路径读写：在配置的 serde 字段树上定位 llm.models.foo.temperature 这类路径，写入值按目标字段类型反序列化校验后再落盘。
+use crate::args::{ConfigGetCommand, ConfigSetCommand, GlobalArgs};
+use crate::config_redact;
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use wunder_server::config::Config;
+
+pub(crate) async fn config_get(runtime: &CliRuntime, command: ConfigGetCommand) -> Result<()> {
+    let config = runtime.state.config_store.get().await;
+    let value = get_config_path(&config, &command.key)?;
+    println!(
+        "{}",
+        display_value(&command.key, value, command.show_secrets)
+    );
+    Ok(())
+}
+
+pub(crate) async fn config_set(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: ConfigSetCommand,
+) -> Result<()> {
+    // Validate against the current config first so errors surface before anything is written.
+    let config = runtime.state.config_store.get().await;
+    let (_, stored) = set_config_path(&config, &command.key, &command.value)?;
+    let ConfigSetCommand { key, value } = command;
+    let key_for_update = key.clone();
+    runtime
+        .state
+        .config_store
+        .update(move |config| {
+            if let Ok((next, _)) = set_config_path(config, &key_for_update, &value) {
+                *config = next;
+            }
+        })
+        .await?;
+    let language = locale::resolve_cli_language(global);
+    if locale::is_zh_language(language.as_str()) {
+        println!("配置已设置: {key} = {}", display_value(&key, stored, false));
+    } else {
+        println!("config set: {key} = {}", display_value(&key, stored, false));
+    }
+    Ok(())
+}
+
+/// Strings print bare; everything else prints as JSON. Secrets are masked unless
+/// `show_secrets`, including when `path` points straight at one.
+fn display_value(path: &str, value: Value, show_secrets: bool) -> String {
+    let leaf = path.rsplit('.').next().unwrap_or_default().trim();
+    let wrapped =
+        config_redact::config_value_for_display(&serde_json::json!({ leaf: value }), show_secrets);
+    match &wrapped[leaf] {
+        Value::String(text) => text.clone(),
+        shown @ (Value::Object(_) | Value::Array(_)) => {
+            serde_json::to_string_pretty(shown).unwrap_or_else(|_| shown.to_string())
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Value stored at a dotted config path.
+pub(crate) fn get_config_path(config: &Config, path: &str) -> Result<Value> {
+    let tree = serde_json::to_value(config)?;
+    let keys = resolve_path(&tree, path, false)?;
+    Ok(lookup(&tree, &keys).cloned().unwrap_or(Value::Null))
+}
+
+/// `config` with `path` set to `raw`, plus the value as stored. `raw` is read as JSON when it
+/// parses (`0.2`, `true`, `null`, `["a"]`) and as a plain string otherwise, then typed by
+/// the target field. Only the last segment may name a new map entry.
+pub(crate) fn set_config_path(config: &Config, path: &str, raw: &str) -> Result<(Config, Value)> {
+    let tree = serde_json::to_value(config)?;
+    let keys = resolve_path(&tree, path, true)?;
+    let parsed = serde_json::from_str::<Value>(raw.trim()).ok();
+    let mut candidates = Vec::new();
+    if let Some(value) = parsed {
+        candidates.push(value);
+    }
+    // Strings that happen to look like JSON (`123`, `true`) still fit string fields.
+    if !matches!(candidates.first(), Some(Value::String(_))) {
+        candidates.push(Value::String(raw.to_string()));
+    }
+    let mut first_error = None;
+    for candidate in candidates {
+        let mut next = tree.clone();
+        assign(&mut next, &keys, candidate)?;
+        match serde_json::from_value::<Config>(next) {
+            Ok(updated) => {
+                let stored = serde_json::to_value(&updated)?;
+                // Unknown struct fields are dropped by serde instead of rejected.
+                let Some(value) = lookup(&stored, &keys).cloned() else {
+                    return Err(anyhow!("unknown config path: {path}"));
+                };
+                return Ok((updated, value));
+            }
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+    Err(anyhow!(
+        "invalid value for {path}: {}",
+        first_error.map(|err| err.to_string()).unwrap_or_default()
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathKey {
+    Field(String),
+    Index(usize),
+}
+
+/// Split `path` against the actual tree so map keys containing dots (`gpt-4.1`) resolve:
+/// at each object the longest run of segments naming an existing key wins.
+fn resolve_path(tree: &Value, path: &str, allow_new_leaf: bool) -> Result<Vec<PathKey>> {
+    let segments = path.trim().split('.').map(str::trim).collect::<Vec<_>>();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(anyhow!("invalid config path: {path}"));
+    }
+    let mut keys = Vec::new();
+    let mut node = Some(tree);
+    let mut index = 0;
+    while index < segments.len() {
+        let unknown = || {
+            let parent = segments[..index].join(".");
+            if parent.is_empty() {
+                anyhow!("unknown config path: {path}")
+            } else {
+                anyhow!(
+                    "unknown config path: {path} (no `{}` under `{parent}`)",
+                    segments[index]
+                )
+            }
+        };
+        match node {
+            Some(Value::Object(map)) => {
+                let matched = (index + 1..=segments.len())
+                    .rev()
+                    .map(|end| (end, segments[index..end].join(".")))
+                    .find(|(_, key)| map.contains_key(key));
+                if let Some((end, key)) = matched {
+                    node = map.get(&key);
+                    keys.push(PathKey::Field(key));
+                    index = end;
+                } else if allow_new_leaf {
+                    // A new map entry: the rest of the path is its key.
+                    keys.push(PathKey::Field(segments[index..].join(".")));
+                    return Ok(keys);
+                } else {
+                    return Err(unknown());
+                }
+            }
+            Some(Value::Array(items)) => {
+                let position = segments[index]
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|position| *position < items.len())
+                    .ok_or_else(unknown)?;
+                node = items.get(position);
+                keys.push(PathKey::Index(position));
+                index += 1;
+            }
+            _ => return Err(unknown()),
+        }
+    }
+    Ok(keys)
+}
+
+fn lookup<'a>(tree: &'a Value, keys: &[PathKey]) -> Option<&'a Value> {
+    keys.iter().try_fold(tree, |node, key| match key {
+        PathKey::Field(name) => node.get(name),
+        PathKey::Index(position) => node.get(*position),
+    })
+}
+
+fn assign(tree: &mut Value, keys: &[PathKey], value: Value) -> Result<()> {
+    let Some((last, parents)) = keys.split_last() else {
+        return Err(anyhow!("config path is empty"));
+    };
+    let mut node = tree;
+    for key in parents {
+        node = match key {
+            PathKey::Field(name) => node.get_mut(name),
+            PathKey::Index(position) => node.get_mut(*position),
+        }
+        .ok_or_else(|| anyhow!("config path changed while updating"))?;
+    }
+    match (last, node) {
+        (PathKey::Field(name), Value::Object(map)) => {
+            map.insert(name.clone(), value);
+        }
+        (PathKey::Index(position), Value::Array(items)) if *position < items.len() => {
+            items[*position] = value;
+        }
+        _ => return Err(anyhow!("config path changed while updating")),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wunder_server::config::LlmModelConfig;
+
+    fn config_with_model(name: &str) -> Config {
+        let mut config = Config::default();
+        config
+            .llm
+            .models
+            .insert(name.to_string(), LlmModelConfig::default());
+        config
+    }
+
+    #[test]
+    fn set_nested_field_is_typed_by_the_target() {
+        let config = config_with_model("gpt-4.1");
+        let (updated, stored) =
+            set_config_path(&config, "llm.models.gpt-4.1.max_rounds", "12").expect("set");
+        assert_eq!(stored, json!(12));
+        assert_eq!(updated.llm.models["gpt-4.1"].max_rounds, Some(12));
+
+        let (updated, _) =
+            set_config_path(&updated, "llm.models.gpt-4.1.api_key", "123").expect("set string");
+        assert_eq!(
+            updated.llm.models["gpt-4.1"].api_key.as_deref(),
+            Some("123")
+        );
+        assert_eq!(
+            get_config_path(&updated, "llm.models.gpt-4.1.api_key").expect("get"),
+            json!("123")
+        );
+
+        let (updated, _) =
+            set_config_path(&updated, "llm.models.gpt-4.1.api_key", "null").expect("clear");
+        assert!(updated.llm.models["gpt-4.1"].api_key.is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_paths_and_type_mismatches() {
+        let config = config_with_model("fast");
+        let err = set_config_path(&config, "llm.modelz.fast.max_rounds", "3").unwrap_err();
+        assert!(err.to_string().contains("unknown config path"), "{err}");
+        let err = set_config_path(&config, "llm.models.fast.max_roundz", "3").unwrap_err();
+        assert!(err.to_string().contains("unknown config path"), "{err}");
+        let err = get_config_path(&config, "llm.models.slow").unwrap_err();
+        assert!(
+            err.to_string().contains("no `slow` under `llm.models`"),
+            "{err}"
+        );
+        let err = set_config_path(&config, "llm.models.fast.max_rounds", "many").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid value for llm.models.fast.max_rounds"),
+            "{err}"
+        );
+    }
+}
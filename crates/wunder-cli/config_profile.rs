@@ -0,0 +1,206 @@
+// 配置档：--profile <name> 对应 WUNDER_HOME/profiles/<name>/wunder.override.yaml，记录最近使用的配置档，并提供 profile list/use 管理。
+use crate::args::{GlobalArgs, ProfileCommand, ProfileSubcommand};
+use crate::locale;
+use crate::runtime::{self, CliRuntime};
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PROFILES_DIR: &str = "profiles";
+const PROFILE_CONFIG_FILE: &str = "wunder.override.yaml";
+const SAVED_PROFILE_FILE: &str = "active_profile";
+/// Selects the runtime config instead of a profile; `profile use default` clears the saved one.
+pub(crate) const DEFAULT_PROFILE_NAME: &str = "default";
+const MAX_PROFILE_NAME_LEN: usize = 64;
+
+/// Profile names double as directory names, so only `[A-Za-z0-9_-]` is accepted.
+/// Returns `None` for the reserved `default` name.
+pub(crate) fn normalize_profile_name(raw: &str) -> Result<Option<String>> {
+    let name = raw.trim();
+    if name.eq_ignore_ascii_case(DEFAULT_PROFILE_NAME) {
+        return Ok(None);
+    }
+    let valid = !name.is_empty()
+        && name.len() <= MAX_PROFILE_NAME_LEN
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+    if !valid {
+        return Err(anyhow!(
+            "invalid profile name `{raw}`: use 1-{MAX_PROFILE_NAME_LEN} letters, digits, '-' or '_'"
+        ));
+    }
+    Ok(Some(name.to_string()))
+}
+
+pub(crate) fn profile_config_path(wunder_home: &Path, name: &str) -> PathBuf {
+    wunder_home
+        .join(PROFILES_DIR)
+        .join(name)
+        .join(PROFILE_CONFIG_FILE)
+}
+
+pub(crate) fn load_saved_profile(wunder_home: &Path) -> Option<String> {
+    let raw = fs::read_to_string(wunder_home.join(PROFILES_DIR).join(SAVED_PROFILE_FILE)).ok()?;
+    normalize_profile_name(&raw).ok().flatten()
+}
+
+/// Remember `name` for later runs; `None` clears the saved profile.
+pub(crate) fn save_profile(wunder_home: &Path, name: Option<&str>) -> Result<()> {
+    let path = wunder_home.join(PROFILES_DIR).join(SAVED_PROFILE_FILE);
+    let Some(name) = name else {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("clear saved profile failed: {}", path.display()))?;
+        }
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, format!("{name}\n"))
+        .with_context(|| format!("save profile failed: {}", path.display()))
+}
+
+/// Profile directories that hold a config file, sorted by name.
+pub(crate) fn list_profiles(wunder_home: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(wunder_home.join(PROFILES_DIR)) else {
+        return Vec::new();
+    };
+    let mut names = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join(PROFILE_CONFIG_FILE).is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| matches!(normalize_profile_name(name), Ok(Some(_))))
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+pub(crate) async fn handle_profile(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: ProfileCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    match command.command {
+        ProfileSubcommand::List => {
+            print_profiles(runtime, language.as_str());
+            Ok(())
+        }
+        ProfileSubcommand::Use(cmd) => use_profile(runtime, language.as_str(), &cmd.name),
+    }
+}
+
+fn print_profiles(runtime: &CliRuntime, language: &str) {
+    let saved = load_saved_profile(&runtime.wunder_home);
+    let default_marker = if saved.is_none() { "*" } else { " " };
+    println!(
+        "{default_marker} {DEFAULT_PROFILE_NAME}  {}",
+        locale::tr(language, "（运行时配置）", "(runtime config)")
+    );
+    for name in list_profiles(&runtime.wunder_home) {
+        let marker = if saved.as_deref() == Some(name.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "{marker} {name}  {}",
+            profile_config_path(&runtime.wunder_home, &name).display()
+        );
+    }
+    println!(
+        "{}",
+        if locale::is_zh_language(language) {
+            format!(
+                "当前配置: {} (来源: {})",
+                runtime.config_path.display(),
+                runtime.config_source.as_str()
+            )
+        } else {
+            format!(
+                "in use: {} (source: {})",
+                runtime.config_path.display(),
+                runtime.config_source.as_str()
+            )
+        }
+    );
+}
+
+fn use_profile(runtime: &CliRuntime, language: &str, raw: &str) -> Result<()> {
+    let Some(name) = normalize_profile_name(raw)? else {
+        save_profile(&runtime.wunder_home, None)?;
+        println!(
+            "{}",
+            locale::tr(
+                language,
+                "已切换到默认运行时配置",
+                "switched to the default runtime config",
+            )
+        );
+        return Ok(());
+    };
+    let path = profile_config_path(&runtime.wunder_home, &name);
+    if !path.exists() {
+        runtime::seed_profile_config(&path, &runtime.assets_root, &runtime.temp_root)?;
+    }
+    save_profile(&runtime.wunder_home, Some(&name))?;
+    if locale::is_zh_language(language) {
+        println!("已切换到配置档 {name}: {}", path.display());
+    } else {
+        println!("switched to profile {name}: {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_source::{explicit_config_path, ConfigPathSource};
+    use wunder_server::config::load_config_from_path;
+
+    fn write_profile(home: &Path, name: &str, model: &str) {
+        let path = profile_config_path(home, name);
+        fs::create_dir_all(path.parent().expect("profile dir")).expect("create profile dir");
+        fs::write(&path, format!("llm:\n  default: {model}\n")).expect("write profile");
+    }
+
+    #[test]
+    fn switching_profiles_changes_resolved_default_model() {
+        let home = std::env::temp_dir().join(format!(
+            "wunder-cli-profiles-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        write_profile(&home, "work", "work-model");
+        write_profile(&home, "personal", "personal-model");
+        let no_env = |_: &str| None;
+        let resolved_model = |profile: Option<&str>| {
+            let (path, source) = explicit_config_path(None, profile, &home, no_env)
+                .expect("resolve")
+                .expect("profile path");
+            (load_config_from_path(&path).llm.default, source)
+        };
+
+        assert_eq!(
+            resolved_model(Some("work")),
+            ("work-model".to_string(), ConfigPathSource::Profile)
+        );
+        assert_eq!(
+            resolved_model(Some("personal")),
+            ("personal-model".to_string(), ConfigPathSource::Profile)
+        );
+
+        save_profile(&home, Some("work")).expect("save profile");
+        assert_eq!(
+            resolved_model(None),
+            ("work-model".to_string(), ConfigPathSource::SavedProfile)
+        );
+        assert_eq!(list_profiles(&home), vec!["personal", "work"]);
+
+        save_profile(&home, None).expect("clear profile");
+        assert_eq!(load_saved_profile(&home), None);
+        assert!(normalize_profile_name("a/b").is_err());
+        let _ = fs::remove_dir_all(&home);
+    }
+}
@@ -0,0 +1,130 @@
+// 配置输出脱敏：config show / config get / debug-config 默认把 api_key、令牌、密钥、MCP/A2A 认证头等字段替换为 ***，仅 --show-secrets 时原样输出。
+use serde::Serialize;
+use serde_json::Value;
+
+pub(crate) const SHOW_SECRETS_FLAG: &str = "--show-secrets";
+const REDACTED: &str = "***";
+
+/// `value` as JSON for printing, with secret-bearing fields masked unless `show_secrets`.
+pub(crate) fn config_value_for_display<T: Serialize>(value: &T, show_secrets: bool) -> Value {
+    let mut value = serde_json::to_value(value).unwrap_or(Value::Null);
+    if !show_secrets {
+        redact_secrets(&mut value);
+    }
+    value
+}
+
+/// Split a trailing/leading `--show-secrets` off slash-command args.
+pub(crate) fn take_show_secrets_flag(args: &str) -> (bool, String) {
+    let mut show_secrets = false;
+    let rest = args
+        .split_whitespace()
+        .filter(|part| {
+            let matched = part.eq_ignore_ascii_case(SHOW_SECRETS_FLAG);
+            show_secrets |= matched;
+            !matched
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    (show_secrets, rest)
+}
+
+fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if key == "headers" {
+                    // MCP/A2A headers carry Authorization and API keys under arbitrary names.
+                    if let Value::Object(headers) = item {
+                        headers.values_mut().for_each(mask);
+                    } else {
+                        mask(item);
+                    }
+                } else if is_secret_key(&key) {
+                    mask(item);
+                } else {
+                    redact_secrets(item);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    matches!(
+        key,
+        "auth" | "authorization" | "password" | "secret" | "dsn" | "cookie"
+    ) || key.ends_with("api_key")
+        || key.ends_with("auth_key")
+        || key.ends_with("_secret")
+        || key.ends_with("token")
+}
+
+/// Unset values stay visible so users can still tell a secret is missing.
+fn mask(value: &mut Value) {
+    let empty = match value {
+        Value::Null => true,
+        Value::String(text) => text.trim().is_empty(),
+        Value::Object(map) => map.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    };
+    if !empty {
+        *value = Value::String(REDACTED.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wunder_server::config::{Config, LlmModelConfig};
+
+    #[test]
+    fn api_key_is_redacted_unless_secrets_are_requested() {
+        let mut config = Config::default();
+        config.llm.models.insert(
+            "demo".to_string(),
+            LlmModelConfig {
+                base_url: Some("https://example.com/v1".to_string()),
+                api_key: Some("sk-live-123".to_string()),
+                ..LlmModelConfig::default()
+            },
+        );
+
+        let redacted = config_value_for_display(&config, false);
+        let model = &redacted["llm"]["models"]["demo"];
+        assert_eq!(model["api_key"], json!("***"));
+        assert_eq!(model["base_url"], json!("https://example.com/v1"));
+        assert!(!redacted.to_string().contains("sk-live-123"));
+
+        let shown = config_value_for_display(&config, true);
+        assert_eq!(
+            shown["llm"]["models"]["demo"]["api_key"],
+            json!("sk-live-123")
+        );
+
+        let mut headers = json!({
+            "mcp": {"headers": {"X-Api": "abc"}, "auth": {"type": "bearer", "token": "t"}},
+            "allow_headers": ["Authorization"],
+            "context_reserve_tokens": 512,
+        });
+        redact_secrets(&mut headers);
+        assert_eq!(headers["mcp"]["headers"], json!({"X-Api": "***"}));
+        assert_eq!(headers["mcp"]["auth"], json!("***"));
+        assert_eq!(headers["allow_headers"], json!(["Authorization"]));
+        assert_eq!(headers["context_reserve_tokens"], json!(512));
+    }
+
+    #[test]
+    fn show_secrets_flag_is_split_from_slash_args() {
+        assert_eq!(
+            take_show_secrets_flag("--show-secrets"),
+            (true, String::new())
+        );
+        assert_eq!(take_show_secrets_flag("show"), (false, "show".to_string()));
+    }
+}
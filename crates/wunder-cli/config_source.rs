@@ -0,0 +1,145 @@
+// 配置文件来源：--config > --profile > WUNDER_CONFIG_OVERRIDE_PATH > 已保存配置档 > 运行时目录配置；显式路径不存在时需 --create 才会生成，配置档首次使用自动创建。
+use crate::config_profile;
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use wunder_server::config::resolve_config_path;
+
+/// CLI-only config switch. `WUNDER_CONFIG_PATH` is not read for this because the CLI
+/// exports it for the embedded server on every start.
+pub(crate) const CONFIG_OVERRIDE_PATH_ENV: &str = "WUNDER_CONFIG_OVERRIDE_PATH";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigPathSource {
+    Flag,
+    /// `--profile <name>`.
+    Profile,
+    Env,
+    /// The profile last chosen with `--profile` or `profile use`.
+    SavedProfile,
+    /// `<temp_root>/config/wunder.yaml`, seeded from the repo config or generated.
+    Runtime,
+}
+
+impl ConfigPathSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Flag => "--config",
+            Self::Profile => "--profile",
+            Self::Env => CONFIG_OVERRIDE_PATH_ENV,
+            Self::SavedProfile => "saved profile",
+            Self::Runtime => "runtime",
+        }
+    }
+
+    pub fn is_profile(self) -> bool {
+        matches!(self, Self::Profile | Self::SavedProfile)
+    }
+}
+
+/// The config path chosen ahead of the runtime default, or `None` to use that default.
+/// `--profile default` also selects the runtime default, skipping the env var and saved profile.
+pub(crate) fn explicit_config_path(
+    flag: Option<&Path>,
+    profile: Option<&str>,
+    wunder_home: &Path,
+    env_lookup: impl Fn(&str) -> Option<String>,
+) -> Result<Option<(PathBuf, ConfigPathSource)>> {
+    if let Some(path) = flag.filter(|path| !path.as_os_str().is_empty()) {
+        return Ok(Some((path.to_path_buf(), ConfigPathSource::Flag)));
+    }
+    if let Some(raw) = profile {
+        let Some(name) = config_profile::normalize_profile_name(raw)? else {
+            return Ok(None);
+        };
+        let path = config_profile::profile_config_path(wunder_home, &name);
+        return Ok(Some((path, ConfigPathSource::Profile)));
+    }
+    if let Some(value) = env_lookup(CONFIG_OVERRIDE_PATH_ENV)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    {
+        return Ok(Some((PathBuf::from(value), ConfigPathSource::Env)));
+    }
+    Ok(config_profile::load_saved_profile(wunder_home).map(|name| {
+        (
+            config_profile::profile_config_path(wunder_home, &name),
+            ConfigPathSource::SavedProfile,
+        )
+    }))
+}
+
+/// An explicitly chosen config must already exist unless `create` asks for a fresh one,
+/// so a typo does not silently start from defaults; profiles are created on first use.
+/// Returns whether the file is missing.
+pub(crate) fn check_explicit_config_path(
+    path: &Path,
+    source: ConfigPathSource,
+    create: bool,
+) -> Result<bool> {
+    if resolve_config_path(path).exists() {
+        return Ok(false);
+    }
+    if create || source.is_profile() {
+        return Ok(true);
+    }
+    Err(anyhow!(
+        "config file not found: {} (from {}); pass --create to create it",
+        path.display(),
+        source.as_str()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_flag_wins_over_profile_and_override_env() {
+        let home = Path::new("/home/demo/.wunder");
+        let env = |name: &str| {
+            (name == CONFIG_OVERRIDE_PATH_ENV).then(|| "/profiles/env.yaml".to_string())
+        };
+        let resolve = |flag: Option<&str>, profile: Option<&str>| {
+            explicit_config_path(flag.map(Path::new), profile, home, env).expect("resolve")
+        };
+        assert_eq!(
+            resolve(Some("/profiles/flag.yaml"), Some("work")),
+            Some((PathBuf::from("/profiles/flag.yaml"), ConfigPathSource::Flag))
+        );
+        assert_eq!(
+            resolve(None, Some("work")),
+            Some((
+                config_profile::profile_config_path(home, "work"),
+                ConfigPathSource::Profile
+            ))
+        );
+        assert_eq!(resolve(None, Some("default")), None);
+        assert_eq!(
+            resolve(None, None),
+            Some((PathBuf::from("/profiles/env.yaml"), ConfigPathSource::Env))
+        );
+        assert_eq!(
+            explicit_config_path(None, None, home, |_| Some("  ".to_string())).expect("resolve"),
+            None
+        );
+        assert!(explicit_config_path(None, Some("../escape"), home, env).is_err());
+    }
+
+    #[test]
+    fn missing_explicit_config_requires_create() {
+        let dir = std::env::temp_dir().join(format!(
+            "wunder-cli-config-source-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let existing = dir.join("existing.yaml");
+        std::fs::write(&existing, "").expect("write config");
+        let missing = dir.join("new.yaml");
+        assert!(!check_explicit_config_path(&existing, ConfigPathSource::Flag, false).unwrap());
+        assert!(check_explicit_config_path(&missing, ConfigPathSource::Flag, true).unwrap());
+        let err = check_explicit_config_path(&missing, ConfigPathSource::Env, false)
+            .expect_err("missing config should fail");
+        assert!(err.to_string().contains(CONFIG_OVERRIDE_PATH_ENV));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,316 @@
+// config test 端到端验证当前模型：用一次性会话经编排器发送固定短提示词，报告耗时或归一化后的供应商错误（认证/网络/其他），结束后清理该会话。
+use crate::args::{ConfigTestCommand, GlobalArgs};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use wunder_server::orchestrator_error_payload;
+use wunder_server::schemas::WunderRequest;
+
+const CONFIG_TEST_PROMPT: &str = "Reply with OK and nothing else.";
+const ANSWER_PREVIEW_CHARS: usize = 80;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConfigTestFailure {
+    /// The provider rejected the credentials.
+    Auth {
+        message: String,
+    },
+    /// The provider could not be reached at all.
+    Network {
+        message: String,
+    },
+    /// The provider answered with a normalized error other than auth.
+    Provider {
+        kind: String,
+        message: String,
+    },
+    Timeout,
+    Other {
+        message: String,
+    },
+}
+
+pub(crate) async fn config_test(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: ConfigTestCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    if !command.timeout_s.is_finite() || command.timeout_s <= 0.0 {
+        return Err(anyhow!(locale::tr(
+            language.as_str(),
+            "--timeout-s 必须大于 0",
+            "--timeout-s must be greater than 0",
+        )));
+    }
+    let config = runtime.state.config_store.get().await;
+    let Some(model) = runtime.resolve_model_name(global.model.as_deref()).await else {
+        return Err(anyhow!(locale::tr(
+            language.as_str(),
+            "未配置模型，请先执行 /config",
+            "no model configured, run /config first",
+        )));
+    };
+    if !config.llm.models.contains_key(&model) {
+        return Err(anyhow!(if is_zh {
+            format!("配置中不存在模型: {model}")
+        } else {
+            format!("model not found in config: {model}")
+        }));
+    }
+
+    // A throwaway session keeps the probe out of the user's history; it is purged below.
+    let session_id = format!("config_test_{}", uuid::Uuid::new_v4().simple());
+    let request = WunderRequest {
+        user_id: runtime.user_id.clone(),
+        question: CONFIG_TEST_PROMPT.to_string(),
+        client_message_id: None,
+        tool_names: Vec::new(),
+        skip_tool_calls: true,
+        stream: false,
+        debug_payload: false,
+        session_id: Some(session_id.clone()),
+        agent_id: None,
+        workspace_container_id: None,
+        model_name: Some(model.clone()),
+        language: global.language.clone(),
+        config_overrides: None,
+        agent_prompt: None,
+        preview_skill: false,
+        attachments: None,
+        max_rounds: Some(1),
+        cite: false,
+        remember_attachments: false,
+        allow_queue: false,
+        enforce_runtime_queue: false,
+        is_admin: false,
+        approval_tx: None,
+    };
+    let started = Instant::now();
+    let result = tokio::time::timeout(
+        Duration::from_secs_f64(command.timeout_s),
+        runtime.state.kernel.orchestrator.run(request),
+    )
+    .await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    purge_probe_session(runtime, &session_id);
+
+    let outcome = match result {
+        Ok(Ok(response)) => Ok(response.answer),
+        Ok(Err(err)) => Err(classify_config_test_error(
+            orchestrator_error_payload(&err).as_ref(),
+            &err.to_string(),
+        )),
+        Err(_) => Err(ConfigTestFailure::Timeout),
+    };
+    if global.json {
+        println!(
+            "{}",
+            serde_json::to_string(&outcome_payload(&model, latency_ms, &outcome))?
+        );
+    }
+    match outcome {
+        Ok(answer) => {
+            if !global.json {
+                let preview = answer_preview(&answer);
+                println!(
+                    "{}",
+                    if is_zh {
+                        format!("模型 {model} 可用，耗时 {latency_ms}ms，回复: {preview}")
+                    } else {
+                        format!("model {model} OK in {latency_ms}ms, reply: {preview}")
+                    }
+                );
+            }
+            Ok(())
+        }
+        Err(failure) => Err(anyhow!(failure_message(
+            is_zh,
+            &model,
+            command.timeout_s,
+            &failure
+        ))),
+    }
+}
+
+fn purge_probe_session(runtime: &CliRuntime, session_id: &str) {
+    let state = &runtime.state;
+    state
+        .workspace
+        .purge_session_data(&runtime.user_id, session_id);
+    let _ = state.monitor.purge_session(session_id);
+    let _ = state
+        .user_store
+        .delete_chat_session(&runtime.user_id, session_id);
+}
+
+/// Sort an orchestrator failure by its normalized provider error. Transport failures carry no
+/// provider body, so `LLM_UNAVAILABLE` without one means the request never got an answer.
+fn classify_config_test_error(payload: Option<&Value>, fallback: &str) -> ConfigTestFailure {
+    let Some(payload) = payload else {
+        return ConfigTestFailure::Other {
+            message: fallback.to_string(),
+        };
+    };
+    let message = payload
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or(fallback)
+        .to_string();
+    if let Some(provider) = payload.pointer("/detail/provider_error") {
+        let kind = provider
+            .get("kind")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string();
+        let message = provider
+            .get("message")
+            .and_then(Value::as_str)
+            .filter(|value| !value.trim().is_empty())
+            .map(str::to_string)
+            .unwrap_or(message);
+        return if kind == "invalid_api_key" {
+            ConfigTestFailure::Auth { message }
+        } else {
+            ConfigTestFailure::Provider { kind, message }
+        };
+    }
+    if payload.get("code").and_then(Value::as_str) == Some("LLM_UNAVAILABLE") {
+        return ConfigTestFailure::Network { message };
+    }
+    ConfigTestFailure::Other { message }
+}
+
+fn failure_message(
+    is_zh: bool,
+    model: &str,
+    timeout_s: f64,
+    failure: &ConfigTestFailure,
+) -> String {
+    match (is_zh, failure) {
+        (true, ConfigTestFailure::Auth { message }) => {
+            format!("模型 {model} 认证失败，请检查 api_key: {message}")
+        }
+        (false, ConfigTestFailure::Auth { message }) => {
+            format!("model {model} rejected the credentials, check api_key: {message}")
+        }
+        (true, ConfigTestFailure::Network { message }) => {
+            format!("无法连接模型 {model}，请检查 base_url 与网络: {message}")
+        }
+        (false, ConfigTestFailure::Network { message }) => {
+            format!("could not reach model {model}, check base_url and network: {message}")
+        }
+        (true, ConfigTestFailure::Provider { kind, message }) => {
+            format!("模型 {model} 返回错误 ({kind}): {message}")
+        }
+        (false, ConfigTestFailure::Provider { kind, message }) => {
+            format!("model {model} returned an error ({kind}): {message}")
+        }
+        (true, ConfigTestFailure::Timeout) => {
+            format!("模型 {model} 在 {timeout_s}s 内无响应，请检查 base_url 与网络")
+        }
+        (false, ConfigTestFailure::Timeout) => {
+            format!("model {model} did not answer within {timeout_s}s, check base_url and network")
+        }
+        (true, ConfigTestFailure::Other { message }) => {
+            format!("模型 {model} 测试失败: {message}")
+        }
+        (false, ConfigTestFailure::Other { message }) => {
+            format!("model {model} test failed: {message}")
+        }
+    }
+}
+
+fn outcome_payload(
+    model: &str,
+    latency_ms: u64,
+    outcome: &std::result::Result<String, ConfigTestFailure>,
+) -> Value {
+    match outcome {
+        Ok(answer) => json!({
+            "ok": true,
+            "model": model,
+            "latency_ms": latency_ms,
+            "answer": answer,
+        }),
+        Err(failure) => {
+            let (kind, message) = match failure {
+                ConfigTestFailure::Auth { message } => ("auth", Some(message)),
+                ConfigTestFailure::Network { message } => ("network", Some(message)),
+                ConfigTestFailure::Provider { kind, message } => (kind.as_str(), Some(message)),
+                ConfigTestFailure::Timeout => ("timeout", None),
+                ConfigTestFailure::Other { message } => ("other", Some(message)),
+            };
+            json!({
+                "ok": false,
+                "model": model,
+                "latency_ms": latency_ms,
+                "error": { "kind": kind, "message": message },
+            })
+        }
+    }
+}
+
+fn answer_preview(answer: &str) -> String {
+    let cleaned = answer.split_whitespace().collect::<Vec<_>>().join(" ");
+    if cleaned.chars().count() <= ANSWER_PREVIEW_CHARS {
+        return cleaned;
+    }
+    let mut preview = cleaned
+        .chars()
+        .take(ANSWER_PREVIEW_CHARS)
+        .collect::<String>();
+    preview.push('…');
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_are_sorted_by_normalized_provider_error() {
+        let auth = json!({
+            "code": "INTERNAL_ERROR",
+            "message": "LLM call failed",
+            "detail": {"provider_error": {"kind": "invalid_api_key", "status": 401, "message": "Incorrect API key provided"}},
+        });
+        assert_eq!(
+            classify_config_test_error(Some(&auth), ""),
+            ConfigTestFailure::Auth {
+                message: "Incorrect API key provided".to_string()
+            }
+        );
+
+        let quota = json!({
+            "code": "INTERNAL_ERROR",
+            "message": "LLM call failed",
+            "detail": {"provider_error": {"kind": "quota_exhausted", "status": 429, "message": ""}},
+        });
+        assert_eq!(
+            classify_config_test_error(Some(&quota), ""),
+            ConfigTestFailure::Provider {
+                kind: "quota_exhausted".to_string(),
+                message: "LLM call failed".to_string()
+            }
+        );
+
+        let offline = json!({"code": "LLM_UNAVAILABLE", "message": "connection refused"});
+        assert_eq!(
+            classify_config_test_error(Some(&offline), ""),
+            ConfigTestFailure::Network {
+                message: "connection refused".to_string()
+            }
+        );
+
+        assert_eq!(
+            classify_config_test_error(None, "session busy"),
+            ConfigTestFailure::Other {
+                message: "session busy".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,360 @@
+// wunder-cli doctor：汇总运行目录、配置、模型、审批模式与资源文件检查（可选网络探测），默认输出本地化文本，--json 输出字段稳定的完整报告。
+use crate::args::{DoctorCommand, GlobalArgs};
+use crate::locale;
+use crate::network_doctor::{self, NetworkReport};
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Duration;
+
+/// One resource path check; only `required` paths have to exist.
+struct DoctorCheck {
+    name: &'static str,
+    path: String,
+    required: bool,
+    exists: bool,
+}
+
+impl DoctorCheck {
+    fn new(name: &'static str, path: String, required: bool) -> Self {
+        let exists = !path.trim().is_empty() && Path::new(path.as_str()).exists();
+        Self {
+            name,
+            path,
+            required,
+            exists,
+        }
+    }
+
+    fn ok(&self) -> bool {
+        !self.required || self.exists
+    }
+
+    fn label(&self, is_zh: bool) -> &'static str {
+        if !is_zh {
+            return self.name;
+        }
+        match self.name {
+            "config" => "配置文件",
+            "i18n_messages" => "i18n 消息文件",
+            "prompts_root" => "提示词根目录",
+            "skill_runner" => "技能运行器",
+            name => name,
+        }
+    }
+}
+
+struct DoctorReport {
+    launch_dir: String,
+    temp_root: String,
+    project_root: String,
+    assets_root: String,
+    user_id: String,
+    config_path: String,
+    config_source: &'static str,
+    workspace_root: String,
+    db_path: String,
+    model: Option<String>,
+    approval_mode: String,
+    config_exists: bool,
+    checks: Vec<DoctorCheck>,
+    /// Security and skill settings printed by `--verbose`.
+    details: Value,
+    network: Option<NetworkReport>,
+}
+
+impl DoctorReport {
+    fn ok(&self) -> bool {
+        self.checks.iter().all(DoctorCheck::ok)
+            && self
+                .network
+                .as_ref()
+                .is_none_or(|network| network.failed() == 0)
+    }
+
+    /// Why the command has to exit non-zero, so CI can gate on `doctor --json`: a missing
+    /// required path or an endpoint that failed the network check.
+    fn failure_message(&self, is_zh: bool) -> Option<String> {
+        let missing: Vec<&str> = self
+            .checks
+            .iter()
+            .filter(|check| !check.ok())
+            .map(|check| check.label(is_zh))
+            .collect();
+        let failed = self.network.as_ref().map_or(0, NetworkReport::failed);
+        let mut reasons = Vec::new();
+        if !missing.is_empty() {
+            reasons.push(if is_zh {
+                format!("必需检查未通过: {}", missing.join(", "))
+            } else {
+                format!("required check(s) failed: {}", missing.join(", "))
+            });
+        }
+        if failed > 0 {
+            reasons.push(if is_zh {
+                format!("{failed} 个端点未通过网络检查")
+            } else {
+                format!("{failed} endpoint(s) failed the network check")
+            });
+        }
+        (!reasons.is_empty()).then(|| reasons.join("; "))
+    }
+
+    /// Machine-readable report for `doctor --json`. Field names are part of the CLI contract;
+    /// add new fields rather than renaming these.
+    fn to_value(&self) -> Value {
+        json!({
+            "ok": self.ok(),
+            "launch_dir": self.launch_dir,
+            "temp_root": self.temp_root,
+            "project_root": self.project_root,
+            "assets_root": self.assets_root,
+            "user_id": self.user_id,
+            "config_path": self.config_path,
+            "config_source": self.config_source,
+            "workspace_root": self.workspace_root,
+            "db_path": self.db_path,
+            "model": self.model,
+            "approval_mode": self.approval_mode,
+            "config_exists": self.config_exists,
+            "checks": self
+                .checks
+                .iter()
+                .map(|check| json!({
+                    "name": check.name,
+                    "path": check.path,
+                    "required": check.required,
+                    "exists": check.exists,
+                    "ok": check.ok(),
+                }))
+                .collect::<Vec<_>>(),
+            "details": self.details,
+            "network": self.network.as_ref().map(NetworkReport::to_value),
+        })
+    }
+
+    fn text_lines(&self, is_zh: bool, verbose: bool) -> Result<Vec<String>> {
+        let model = self.model.as_deref().unwrap_or("<none>");
+        let mut lines = if is_zh {
+            vec![
+                "wunder-cli 诊断".to_string(),
+                format!("- 启动目录: {}", self.launch_dir),
+                format!("- 临时目录: {}", self.temp_root),
+                format!("- 项目根目录: {}", self.project_root),
+                format!("- 资源目录: {}", self.assets_root),
+                format!("- 用户 ID: {}", self.user_id),
+                format!(
+                    "- 配置文件: {} (来源: {})",
+                    self.config_path, self.config_source
+                ),
+                format!("- 工作目录: {}", self.workspace_root),
+                format!("- 数据库路径: {}", self.db_path),
+                format!("- 模型: {model}"),
+                format!("- 审批模式: {}", self.approval_mode),
+                format!("- 覆盖配置存在: {}", self.config_exists),
+            ]
+        } else {
+            vec![
+                "wunder-cli doctor".to_string(),
+                format!("- launch_dir: {}", self.launch_dir),
+                format!("- temp_root: {}", self.temp_root),
+                format!("- project_root: {}", self.project_root),
+                format!("- assets_root: {}", self.assets_root),
+                format!("- user_id: {}", self.user_id),
+                format!(
+                    "- config_path: {} (source: {})",
+                    self.config_path, self.config_source
+                ),
+                format!("- workspace_root: {}", self.workspace_root),
+                format!("- db_path: {}", self.db_path),
+                format!("- model: {model}"),
+                format!("- approval_mode: {}", self.approval_mode),
+                format!("- config_exists: {}", self.config_exists),
+            ]
+        };
+        for check in &self.checks {
+            let status = match (check.ok(), is_zh) {
+                (true, true) => "正常",
+                (true, false) => "ok",
+                (false, true) => "缺失",
+                (false, false) => "missing",
+            };
+            lines.push(format!(
+                "- {}: [{status}] {}",
+                check.label(is_zh),
+                check.path
+            ));
+        }
+        if verbose {
+            lines.push(serde_json::to_string_pretty(&self.details)?);
+        }
+        if let Some(network) = self.network.as_ref() {
+            lines.extend(network.text_lines(is_zh));
+        }
+        Ok(lines)
+    }
+}
+
+pub(crate) async fn handle_doctor(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: DoctorCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    if command.network && (!command.timeout_s.is_finite() || command.timeout_s <= 0.0) {
+        return Err(anyhow!(locale::tr(
+            language.as_str(),
+            "--timeout-s 必须大于 0",
+            "--timeout-s must be greater than 0",
+        )));
+    }
+    let config = runtime.state.config_store.get().await;
+    let prompts_root = std::env::var("WUNDER_PROMPTS_ROOT").unwrap_or_default();
+    let prompts_status_path = if prompts_root.trim().is_empty() {
+        "<embedded>".to_string()
+    } else {
+        prompts_root
+    };
+    let approval_mode = crate::resolve_effective_approval_mode(&config, global.approval_mode);
+    let network = if command.network {
+        let timeout = Duration::from_secs_f64(command.timeout_s);
+        Some(network_doctor::run_network_checks(runtime, global, timeout).await?)
+    } else {
+        None
+    };
+    let report = DoctorReport {
+        launch_dir: runtime.launch_dir.to_string_lossy().to_string(),
+        temp_root: runtime.temp_root.to_string_lossy().to_string(),
+        project_root: runtime.repo_root.to_string_lossy().to_string(),
+        assets_root: runtime.assets_root.to_string_lossy().to_string(),
+        user_id: runtime.user_id.clone(),
+        config_path: runtime.config_path.to_string_lossy().to_string(),
+        config_source: runtime.config_source.as_str(),
+        workspace_root: config.workspace.root.clone(),
+        db_path: config.storage.db_path.clone(),
+        model: runtime.resolve_model_name(global.model.as_deref()).await,
+        approval_mode: approval_mode.clone(),
+        config_exists: runtime.temp_root.join("config/wunder.yaml").exists(),
+        checks: vec![
+            DoctorCheck::new(
+                "config",
+                std::env::var("WUNDER_CONFIG_PATH").unwrap_or_default(),
+                true,
+            ),
+            DoctorCheck::new(
+                "i18n_messages",
+                std::env::var("WUNDER_I18N_MESSAGES_PATH").unwrap_or_default(),
+                true,
+            ),
+            DoctorCheck::new("prompts_root", prompts_status_path, false),
+            DoctorCheck::new(
+                "skill_runner",
+                std::env::var("WUNDER_SKILL_RUNNER_PATH").unwrap_or_default(),
+                true,
+            ),
+        ],
+        details: json!({
+            "skills_paths": config.skills.paths,
+            "allow_paths": config.security.allow_paths,
+            "allow_commands": config.security.allow_commands,
+            "approval_mode_config": config.security.approval_mode,
+            "approval_mode_effective": approval_mode,
+            "exec_policy_mode": config.security.exec_policy_mode,
+            "config_path": std::env::var("WUNDER_CONFIG_PATH").unwrap_or_default(),
+        }),
+        network,
+    };
+
+    if command.json {
+        println!("{}", serde_json::to_string_pretty(&report.to_value())?);
+    } else {
+        for line in report.text_lines(is_zh, command.verbose)? {
+            println!("{line}");
+        }
+    }
+    match report.failure_message(is_zh) {
+        Some(message) => Err(anyhow!(message)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(checks: Vec<DoctorCheck>) -> DoctorReport {
+        DoctorReport {
+            launch_dir: "/work".to_string(),
+            temp_root: "/work/WUNDER_TEMPD".to_string(),
+            project_root: "/work".to_string(),
+            assets_root: "/opt/wunder".to_string(),
+            user_id: "cli_user".to_string(),
+            config_path: "/work/WUNDER_TEMPD/config/wunder.yaml".to_string(),
+            config_source: "default",
+            workspace_root: "/work".to_string(),
+            db_path: "/work/WUNDER_TEMPD/wunder.db".to_string(),
+            model: Some("gpt-4.1".to_string()),
+            approval_mode: "suggest".to_string(),
+            config_exists: true,
+            checks,
+            details: json!({}),
+            network: None,
+        }
+    }
+
+    #[test]
+    fn json_report_exposes_checks_with_boolean_ok() {
+        let value = report(vec![
+            DoctorCheck::new("config", String::new(), true),
+            DoctorCheck::new("prompts_root", "<embedded>".to_string(), false),
+        ])
+        .to_value();
+
+        assert_eq!(value["ok"], false);
+        for key in [
+            "launch_dir",
+            "temp_root",
+            "project_root",
+            "assets_root",
+            "config_path",
+            "config_source",
+            "model",
+            "approval_mode",
+        ] {
+            assert!(value[key].is_string(), "{key}");
+        }
+        assert!(value["config_exists"].is_boolean());
+        assert!(value["network"].is_null());
+        let checks = value["checks"].as_array().expect("checks");
+        assert_eq!(checks.len(), 2);
+        for check in checks {
+            assert!(check["name"].is_string());
+            assert!(check["ok"].is_boolean());
+            assert!(check["exists"].is_boolean());
+        }
+        assert_eq!(checks[0]["ok"], false);
+        assert_eq!(checks[1]["ok"], true);
+    }
+
+    #[test]
+    fn failed_required_check_fails_the_command() {
+        let failing = report(vec![
+            DoctorCheck::new("config", String::new(), true),
+            DoctorCheck::new("prompts_root", "<embedded>".to_string(), false),
+        ]);
+        assert_eq!(
+            failing.failure_message(false).as_deref(),
+            Some("required check(s) failed: config")
+        );
+
+        let passing = report(vec![DoctorCheck::new(
+            "prompts_root",
+            "<embedded>".to_string(),
+            false,
+        )]);
+        assert!(passing.to_value()["ok"].as_bool().unwrap_or(false));
+        assert_eq!(passing.failure_message(false), None);
+    }
+}
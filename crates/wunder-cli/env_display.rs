@@ -0,0 +1,115 @@
+// /env 环境变量视图：列出 wunder 识别的 WUNDER_* 变量当前取值（密钥类默认脱敏），并标注路径类变量是否存在。
+use crate::locale;
+use std::path::Path;
+
+const REDACTED: &str = "***";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnvVarKind {
+    /// Points at a file or directory; the report checks that it exists.
+    Path,
+    Value,
+    Secret,
+}
+
+/// Variables the CLI and the embedded server read, in the order config resolution uses them.
+const RECOGNIZED_ENV_VARS: &[(&str, EnvVarKind)] = &[
+    ("WUNDER_CONFIG_PATH", EnvVarKind::Path),
+    ("WUNDER_CONFIG_OVERRIDE_PATH", EnvVarKind::Path),
+    ("WUNDER_PROMPTS_ROOT", EnvVarKind::Path),
+    ("WUNDER_I18N_MESSAGES_PATH", EnvVarKind::Path),
+    ("WUNDER_HOME", EnvVarKind::Path),
+    ("WUNDER_TEMP_DIR_ROOT", EnvVarKind::Path),
+    ("WUNDER_CLI_PROJECT_ROOT", EnvVarKind::Path),
+    ("WUNDER_WORKSPACE_SINGLE_ROOT", EnvVarKind::Value),
+    ("WUNDER_USER_TOOLS_ROOT", EnvVarKind::Path),
+    ("WUNDER_VECTOR_KNOWLEDGE_ROOT", EnvVarKind::Path),
+    ("WUNDER_BUILTIN_SKILLS_ROOT", EnvVarKind::Path),
+    ("WUNDER_SKILL_RUNNER_PATH", EnvVarKind::Path),
+    ("WUNDER_STORAGE_BACKEND", EnvVarKind::Value),
+    ("WUNDER_SQLITE_DB_PATH", EnvVarKind::Path),
+    ("WUNDER_POSTGRES_DSN", EnvVarKind::Secret),
+    ("WUNDER_HOST", EnvVarKind::Value),
+    ("WUNDER_PORT", EnvVarKind::Value),
+    ("WUNDER_API_KEY", EnvVarKind::Secret),
+];
+
+pub(crate) fn env_report_lines(language: &str, show_secrets: bool) -> Vec<String> {
+    build_env_report_lines(language, show_secrets, |name| std::env::var(name).ok())
+}
+
+fn build_env_report_lines(
+    language: &str,
+    show_secrets: bool,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    let is_zh = locale::is_zh_language(language);
+    let width = RECOGNIZED_ENV_VARS
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![locale::tr(
+        language,
+        "wunder 环境变量（密钥类已脱敏，--show-secrets 显示原值）：",
+        "wunder environment variables (secrets masked, --show-secrets to reveal):",
+    )];
+    for (name, kind) in RECOGNIZED_ENV_VARS {
+        let value = lookup(name).filter(|value| !value.trim().is_empty());
+        let Some(value) = value else {
+            let unset = if is_zh { "<未设置>" } else { "<unset>" };
+            lines.push(format!("  {name:<width$}  {unset}"));
+            continue;
+        };
+        let line = match kind {
+            EnvVarKind::Secret if !show_secrets => format!("  {name:<width$}  {REDACTED}"),
+            EnvVarKind::Path => {
+                let marker = match (Path::new(value.trim()).exists(), is_zh) {
+                    (true, true) => "[存在]",
+                    (true, false) => "[exists]",
+                    (false, true) => "[不存在]",
+                    (false, false) => "[missing]",
+                };
+                format!("  {name:<width$}  {value}  {marker}")
+            }
+            _ => format!("  {name:<width$}  {value}"),
+        };
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_report_masks_secrets_and_checks_paths() {
+        let existing = std::env::temp_dir().to_string_lossy().to_string();
+        let lookup = |name: &str| match name {
+            "WUNDER_CONFIG_PATH" => Some(existing.clone()),
+            "WUNDER_PROMPTS_ROOT" => Some("/definitely/not/here".to_string()),
+            "WUNDER_PORT" => Some("18000".to_string()),
+            "WUNDER_API_KEY" => Some("sk-env-secret".to_string()),
+            _ => None,
+        };
+        let lines = build_env_report_lines("en-US", false, lookup);
+        let find = |name: &str| {
+            lines
+                .iter()
+                .find(|line| line.trim_start().starts_with(&format!("{name} ")))
+                .cloned()
+                .unwrap_or_default()
+        };
+        assert_eq!(lines.len(), RECOGNIZED_ENV_VARS.len() + 1);
+        assert!(find("WUNDER_CONFIG_PATH").ends_with("[exists]"));
+        assert!(find("WUNDER_PROMPTS_ROOT").ends_with("[missing]"));
+        assert!(find("WUNDER_PORT").ends_with("18000"));
+        assert!(find("WUNDER_HOME").ends_with("<unset>"));
+        assert!(find("WUNDER_API_KEY").ends_with(REDACTED));
+        assert!(!lines.join("\n").contains("sk-env-secret"));
+
+        let shown = build_env_report_lines("en-US", true, lookup);
+        assert!(shown.join("\n").contains("sk-env-secret"));
+    }
+}
@@ -48,6 +48,8 @@ mod tests {
             content: Some("abcd".to_string()),
             content_type: Some("text/markdown".to_string()),
             public_path: None,
+            attachment_id: None,
+            metadata: None,
         }];
         let err = validate_request_text_input_size("en", &prompt, Some(&attachments))
             .expect_err("oversized payload");
@@ -64,6 +66,8 @@ mod tests {
             content: Some("data:image/png;base64,AAAA".to_string()),
             content_type: Some("image/png".to_string()),
             public_path: None,
+            attachment_id: None,
+            metadata: None,
         }];
         assert!(validate_request_text_input_size("zh", &prompt, Some(&attachments)).is_ok());
     }
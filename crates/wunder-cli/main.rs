@@ -1,35 +1,68 @@
+mod agent_profiles;
+mod agents_md;
 mod args;
 mod attachments;
+mod bench;
 mod command_session_display;
+mod config_path;
+mod config_profile;
+mod config_redact;
+mod config_source;
+mod config_test;
+mod doctor;
+mod env_display;
 mod error_display;
 mod input_guard;
 mod locale;
+mod maintenance;
+mod network_doctor;
 mod patch_diff;
 mod path_display;
+mod prompt_budget_display;
 mod render;
+mod response_format_args;
+mod review;
 mod runtime;
+mod sampling_overrides;
+mod sandbox;
+mod session_approvals;
+mod session_export;
+mod session_history;
+mod session_rename;
+mod session_tools;
+mod skill_run;
+mod skill_share;
 mod slash_command;
+mod swarm;
 mod tool_display;
+mod tool_latency_summary;
+mod tool_repeat;
+mod transcript_export;
 mod tui;
+mod turn_interrupt;
+mod turn_notifier;
+mod usage_display;
+mod user_roster;
 mod welcome_logo;
 mod workspace_context;
 
 use anyhow::{anyhow, Context, Result};
 use args::{
     ApprovalModeArg, AskCommand, Cli, Command, CompletionCommand, ConfigCommand, ConfigSubcommand,
-    DoctorCommand, ExecCommand, GlobalArgs, McpAddCommand, McpCommand, McpGetCommand,
-    McpListCommand, McpLoginCommand, McpNameCommand, McpSubcommand, ResumeCommand,
-    SetApprovalModeCommand, SetToolCallModeCommand, SkillNameCommand, SkillsCommand,
-    SkillsListCommand, SkillsSubcommand, SkillsUploadCommand, ToolCallModeArg, ToolCommand,
-    ToolRunCommand, ToolSubcommand,
+    ExecCommand, GlobalArgs, McpAddCommand, McpCommand, McpGetCommand, McpListCommand,
+    McpLoginCommand, McpNameCommand, McpSubcommand, ResumeCommand, SetApprovalModeCommand,
+    SetToolCallModeCommand, SkillNameCommand, SkillsCommand, SkillsListCommand, SkillsSubcommand,
+    SkillsUploadCommand, ToolCallModeArg, ToolCommand, ToolRunCommand, ToolSubcommand,
 };
 use chrono::{Local, TimeZone};
 use clap::CommandFactory;
 use clap::Parser;
 use clap_complete::generate;
 use futures::{future::BoxFuture, StreamExt};
+use network_doctor::McpProbeOutcome;
 use render::{FinalEvent, StreamRenderer};
 use runtime::{CliRuntime, TurnNotificationConfig, TurnNotificationWhen};
+use sampling_overrides::SamplingOverrides;
 use serde_json::{json, Value};
 use slash_command::{ParsedSlashCommand, SlashCommand};
 use std::collections::{HashMap, HashSet};
@@ -37,12 +70,17 @@ use std::fs;
 use std::io::{self, IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt as tracing_fmt, reload, EnvFilter, Layer, Registry};
+use turn_interrupt::TurnOutcome;
 use wunder_server::a2a_store::A2aStore;
 use wunder_server::approval::{
     new_channel as new_approval_channel, ApprovalRequestRx, ApprovalResponse,
 };
-use wunder_server::config::{A2aServiceConfig, Config, LlmModelConfig};
+use wunder_server::config::{
+    normalize_log_format, A2aServiceConfig, Config, LlmModelConfig, LogFormat,
+};
 use wunder_server::llm::{is_openai_compatible_provider, probe_openai_context_window};
 use wunder_server::path_utils::is_within_root;
 use wunder_server::schemas::{AttachmentPayload, WunderRequest};
@@ -50,6 +88,7 @@ use wunder_server::skills::{load_skills, SkillSpec};
 use wunder_server::storage::{ChatSessionRecord, SessionGoalRecord};
 use wunder_server::tools::{
     build_tool_roots, collect_available_tool_names, execute_tool, resolve_tool_name, ToolContext,
+    ToolError,
 };
 use wunder_server::user_tools::UserMcpServer;
 use wunder_server::{goal, goal::GoalCommand};
@@ -57,6 +96,8 @@ use zip::ZipArchive;
 
 const CLI_MIN_MAX_ROUNDS: u32 = 8;
 const CLI_CONTEXT_PROBE_TIMEOUT_S: u64 = 15;
+/// How long a cancelled turn waits for the stdin approval prompt to deny and exit.
+const APPROVAL_CANCEL_GRACE: Duration = Duration::from_millis(500);
 const CONFIG_SLASH_USAGE: &str = "/config [<base_url> <api_key> <model> [max_context|auto]]";
 const CLI_DEFAULT_SESSION_TITLE: &str = "\u{65B0}\u{4F1A}\u{8BDD}";
 const CLI_DEFAULT_SESSION_STATUS: &str = "active";
@@ -64,19 +105,73 @@ const CLI_DEFAULT_SESSION_STATUS: &str = "active";
 #[tokio::main]
 async fn main() -> Result<()> {
     wunder_server::rustls_provider::install_process_default_provider();
-    init_tracing();
     let cli = Cli::parse();
+    if let Err(message) = sampling_overrides::validate_stop_sequences(&cli.global.stop) {
+        Cli::command()
+            .error(clap::error::ErrorKind::TooManyValues, message)
+            .exit();
+    }
+    let log_format = init_tracing();
     let runtime = CliRuntime::init(&cli.global).await?;
+    if let Some(log_format) = log_format {
+        apply_log_format(&log_format, &runtime.state.config_store.get().await);
+    }
 
-    match cli.command {
+    let result = match cli.command {
         Some(command) => dispatch_command(&runtime, &cli.global, command).await,
         None => Box::pin(run_default(&runtime, &cli.global, cli.prompt)).await,
+    };
+    if let Some(err) = result.as_ref().err() {
+        if let Some(code) = error_exit_code(err) {
+            // A cancelled turn already printed its notice in `run_prompt_once`.
+            if let Some(tool_error) = err.downcast_ref::<ToolError>() {
+                eprintln!("Error: {tool_error}");
+            }
+            std::process::exit(code);
+        }
+    }
+    result
+}
+
+/// Exit status for errors scripts need to tell apart: tool dispatch failures get a per-kind
+/// status and a Ctrl+C-cancelled one-shot turn exits like an idle Ctrl+C. Other errors keep
+/// the default status 1.
+fn error_exit_code(err: &anyhow::Error) -> Option<i32> {
+    if let Some(tool_error) = err.downcast_ref::<ToolError>() {
+        return Some(tool_error.exit_code());
     }
+    err.is::<turn_interrupt::TurnCancelled>()
+        .then_some(turn_interrupt::SIGINT_EXIT_CODE)
 }
 
-fn init_tracing() {
+type ConsoleLogLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Start logging before the runtime loads, so warnings from config load, storage init and
+/// migrations are kept. The log format lives in the runtime config, so the returned handle
+/// switches the console layer once it is loaded.
+fn init_tracing() -> Option<reload::Handle<ConsoleLogLayer, Registry>> {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"));
-    let _ = tracing_subscriber::fmt().with_env_filter(filter).try_init();
+    let (console_layer, handle) = reload::Layer::new(console_log_layer(LogFormat::Text));
+    tracing_subscriber::registry()
+        .with(console_layer)
+        .with(filter)
+        .try_init()
+        .ok()
+        .map(|_| handle)
+}
+
+fn apply_log_format(handle: &reload::Handle<ConsoleLogLayer, Registry>, config: &Config) {
+    let log_format = normalize_log_format(&config.observability.log_format);
+    if log_format != LogFormat::Text {
+        let _ = handle.reload(console_log_layer(log_format));
+    }
+}
+
+fn console_log_layer(log_format: LogFormat) -> ConsoleLogLayer {
+    match log_format {
+        LogFormat::Json => Box::new(tracing_fmt::layer().json()),
+        LogFormat::Text => Box::new(tracing_fmt::layer()),
+    }
 }
 
 fn dispatch_command<'a>(
@@ -93,7 +188,17 @@ fn dispatch_command<'a>(
         Command::Mcp(cmd) => Box::pin(handle_mcp(runtime, global, cmd)),
         Command::Skills(cmd) => Box::pin(handle_skills(runtime, global, cmd)),
         Command::Config(cmd) => Box::pin(handle_config(runtime, global, cmd)),
-        Command::Doctor(cmd) => Box::pin(handle_doctor(runtime, global, cmd)),
+        Command::Profile(cmd) => Box::pin(config_profile::handle_profile(runtime, global, cmd)),
+        Command::Doctor(cmd) => Box::pin(doctor::handle_doctor(runtime, global, cmd)),
+        Command::Review(cmd) => Box::pin(review::handle_review(runtime, global, cmd)),
+        Command::Users(cmd) => Box::pin(user_roster::handle_users(runtime, global, cmd)),
+        Command::Maintenance(cmd) => {
+            Box::pin(maintenance::handle_maintenance(runtime, global, cmd))
+        }
+        Command::Bench(cmd) => Box::pin(bench::handle_bench(runtime, global, cmd)),
+        Command::Swarm(cmd) => Box::pin(swarm::handle_swarm(runtime, global, cmd)),
+        Command::Sandbox(cmd) => Box::pin(sandbox::handle_sandbox(runtime, global, cmd)),
+        Command::Session(cmd) => Box::pin(session_export::handle_session(runtime, global, cmd)),
         Command::Completion(cmd) => Box::pin(handle_completion(cmd)),
     }
 }
@@ -117,7 +222,17 @@ async fn run_default(
             .clone()
             .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
         let attachments = prepare_global_attachment_payloads(runtime, global).await?;
-        run_prompt_once(runtime, global, &prompt, &session_id, None, attachments).await?;
+        let final_event = run_prompt_once(
+            runtime,
+            global,
+            &prompt,
+            &session_id,
+            None,
+            attachments,
+            false,
+        )
+        .await?;
+        turn_interrupt::ensure_not_cancelled(final_event.stop_reason.as_deref())?;
         return Ok(());
     }
 
@@ -128,7 +243,17 @@ async fn run_default(
             .clone()
             .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
         let attachments = prepare_global_attachment_payloads(runtime, global).await?;
-        run_prompt_once(runtime, global, &prompt, &session_id, None, attachments).await?;
+        let final_event = run_prompt_once(
+            runtime,
+            global,
+            &prompt,
+            &session_id,
+            None,
+            attachments,
+            false,
+        )
+        .await?;
+        turn_interrupt::ensure_not_cancelled(final_event.stop_reason.as_deref())?;
         return Ok(());
     }
 
@@ -147,7 +272,17 @@ async fn handle_ask(runtime: &CliRuntime, global: &GlobalArgs, command: AskComma
         .clone()
         .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
     let attachments = prepare_global_attachment_payloads(runtime, global).await?;
-    run_prompt_once(runtime, global, &prompt, &session_id, None, attachments).await?;
+    let final_event = run_prompt_once(
+        runtime,
+        global,
+        &prompt,
+        &session_id,
+        None,
+        attachments,
+        false,
+    )
+    .await?;
+    turn_interrupt::ensure_not_cancelled(final_event.stop_reason.as_deref())?;
     Ok(())
 }
 
@@ -310,6 +445,7 @@ async fn run_chat_loop(
             &session_id,
             agent_id_override.as_deref(),
             request_attachments,
+            attachments::remember_requested(&pending_attachments),
         )
         .await?;
         if !pending_attachments.is_empty() {
@@ -361,6 +497,12 @@ async fn handle_chat_slash_command(
             Box::pin(print_session_stats(runtime, global, session_id.as_str())).await?;
             Ok(false)
         }
+        SlashCommand::Usage => {
+            for line in usage_display::load_quota_usage_lines(runtime, language.as_str()).await? {
+                println!("{line}");
+            }
+            Ok(false)
+        }
         SlashCommand::System => {
             Box::pin(handle_slash_system(
                 runtime,
@@ -407,7 +549,8 @@ async fn handle_chat_slash_command(
             Ok(false)
         }
         SlashCommand::ConfigShow => {
-            Box::pin(config_show(runtime, global)).await?;
+            let (show_secrets, _) = config_redact::take_show_secrets_flag(command.args);
+            Box::pin(config_show(runtime, global, show_secrets)).await?;
             Ok(false)
         }
         SlashCommand::Model => {
@@ -419,17 +562,23 @@ async fn handle_chat_slash_command(
             Ok(false)
         }
         SlashCommand::Approvals => {
-            Box::pin(handle_slash_approvals(runtime, global, command.args)).await?;
+            Box::pin(handle_slash_approvals(
+                runtime,
+                global,
+                session_id.as_str(),
+                command.args,
+            ))
+            .await?;
             Ok(false)
         }
         SlashCommand::Diff => {
             match parse_diff_slash_action(command.args) {
                 Ok(DiffSlashAction::Summary) => {
-                    print_git_diff_summary(runtime.launch_dir.as_path(), language.as_str())?;
+                    print_git_diff_summary(runtime.repo_root.as_path(), language.as_str())?;
                 }
                 Ok(DiffSlashAction::Files) => {
                     for row in diff_files_lines_with_language(
-                        runtime.launch_dir.as_path(),
+                        runtime.repo_root.as_path(),
                         language.as_str(),
                     ) {
                         println!("{row}");
@@ -437,7 +586,7 @@ async fn handle_chat_slash_command(
                 }
                 Ok(DiffSlashAction::Show(target)) => {
                     for row in diff_file_lines_with_language(
-                        runtime.launch_dir.as_path(),
+                        runtime.repo_root.as_path(),
                         target.as_str(),
                         language.as_str(),
                     ) {
@@ -446,7 +595,7 @@ async fn handle_chat_slash_command(
                 }
                 Ok(DiffSlashAction::Hunks(target)) => {
                     for row in diff_hunk_lines_with_language(
-                        runtime.launch_dir.as_path(),
+                        runtime.repo_root.as_path(),
                         target.as_str(),
                         language.as_str(),
                     ) {
@@ -454,11 +603,8 @@ async fn handle_chat_slash_command(
                     }
                 }
                 Ok(DiffSlashAction::Stage(target)) => {
-                    match run_git_file_action(
-                        runtime.launch_dir.as_path(),
-                        target.as_str(),
-                        "stage",
-                    ) {
+                    match run_git_file_action(runtime.repo_root.as_path(), target.as_str(), "stage")
+                    {
                         Ok(()) => println!(
                             "{}",
                             locale::tr(language.as_str(), "已 stage 目标文件", "file staged")
@@ -468,7 +614,7 @@ async fn handle_chat_slash_command(
                 }
                 Ok(DiffSlashAction::Unstage(target)) => {
                     match run_git_file_action(
-                        runtime.launch_dir.as_path(),
+                        runtime.repo_root.as_path(),
                         target.as_str(),
                         "unstage",
                     ) {
@@ -481,7 +627,7 @@ async fn handle_chat_slash_command(
                 }
                 Ok(DiffSlashAction::Revert(target)) => {
                     match run_git_file_action(
-                        runtime.launch_dir.as_path(),
+                        runtime.repo_root.as_path(),
                         target.as_str(),
                         "revert",
                     ) {
@@ -501,28 +647,12 @@ async fn handle_chat_slash_command(
             Ok(false)
         }
         SlashCommand::Review => {
-            let prompt = match build_review_prompt_with_language(
-                runtime.launch_dir.as_path(),
-                command.args,
-                language.as_str(),
-            ) {
-                Ok(prompt) => prompt,
-                Err(err) => {
-                    if locale::is_zh_language(language.as_str()) {
-                        println!("[错误] {err}");
-                    } else {
-                        println!("[error] {err}");
-                    }
-                    return Ok(false);
-                }
-            };
-            Box::pin(run_prompt_once(
+            Box::pin(review::run_review_slash(
                 runtime,
                 global,
-                prompt.as_str(),
-                session_id,
+                session_id.as_str(),
                 agent_id_override.as_deref(),
-                None,
+                command.args,
             ))
             .await?;
             Ok(false)
@@ -608,7 +738,10 @@ async fn handle_chat_slash_command(
                 );
                 return Ok(false);
             }
-            for path in search_workspace_files(runtime.launch_dir.as_path(), query, 20) {
+            let ignore = runtime.load_workspace_ignore().await;
+            for path in
+                search_workspace_files(runtime.launch_dir.as_path(), ignore.as_ref(), query, 20)
+            {
                 println!("{path}");
             }
             Ok(false)
@@ -617,6 +750,20 @@ async fn handle_chat_slash_command(
             Box::pin(handle_slash_skills(runtime, global, command.args)).await?;
             Ok(false)
         }
+        SlashCommand::Tools => {
+            let language = locale::resolve_cli_language(global);
+            let lines = session_tools::run_tools_command(
+                runtime,
+                language.as_str(),
+                session_id.as_str(),
+                command.args,
+            )
+            .await?;
+            for line in lines {
+                println!("{line}");
+            }
+            Ok(false)
+        }
         SlashCommand::Apps => {
             Box::pin(handle_slash_apps(runtime, global, command.args)).await?;
             Ok(false)
@@ -647,6 +794,20 @@ async fn handle_chat_slash_command(
             Box::pin(handle_slash_compact(runtime, global, session_id)).await?;
             Ok(false)
         }
+        SlashCommand::History => {
+            let language = locale::resolve_cli_language(global);
+            let lines = session_history::run_history_command(
+                runtime,
+                language.as_str(),
+                session_id.as_str(),
+                command.args,
+            )
+            .await?;
+            for line in lines {
+                println!("{line}");
+            }
+            Ok(false)
+        }
         SlashCommand::Backtrack => {
             Box::pin(handle_slash_backtrack(
                 runtime,
@@ -658,7 +819,21 @@ async fn handle_chat_slash_command(
             Ok(false)
         }
         SlashCommand::DebugConfig => {
-            Box::pin(print_debug_config(runtime, global, session_id.as_str())).await?;
+            let (show_secrets, _) = config_redact::take_show_secrets_flag(command.args);
+            Box::pin(print_debug_config(
+                runtime,
+                global,
+                session_id.as_str(),
+                show_secrets,
+            ))
+            .await?;
+            Ok(false)
+        }
+        SlashCommand::Env => {
+            let (show_secrets, _) = config_redact::take_show_secrets_flag(command.args);
+            for line in env_display::env_report_lines(language.as_str(), show_secrets) {
+                println!("{line}");
+            }
             Ok(false)
         }
         SlashCommand::Statusline => {
@@ -685,11 +860,13 @@ pub(crate) struct SessionStatsSnapshot {
     pub context_used_tokens: i64,
     pub context_peak_tokens: i64,
     pub model_calls: u64,
+    pub reasoning_events: u64,
     pub tool_calls: u64,
     pub tool_results: u64,
     pub total_input_tokens: u64,
     pub total_output_tokens: u64,
     pub total_tokens: u64,
+    pub tool_latency: Vec<wunder_server::tool_latency::ToolLatencyStats>,
 }
 
 #[derive(Debug, Clone)]
@@ -834,7 +1011,7 @@ fn format_session_time(ts: f64) -> String {
         .unwrap_or_else(|| "-".to_string())
 }
 
-async fn ensure_cli_session_record(
+pub(crate) async fn ensure_cli_session_record(
     runtime: &CliRuntime,
     session_id: &str,
     prompt_hint: Option<&str>,
@@ -952,12 +1129,13 @@ pub(crate) async fn load_session_stats(
         }
         let limit = max_event_id.saturating_add(64).max(1);
         let events = storage.load_stream_events(&session_id_for_load, 0, limit)?;
-        for record in events {
+        output.tool_latency = wunder_server::tool_latency::aggregate_tool_latencies(&events);
+        for record in &events {
             let event_name = record
                 .get("event")
                 .and_then(Value::as_str)
                 .unwrap_or_default();
-            let payload = stream_event_payload(&record);
+            let payload = stream_event_payload(record);
             match event_name {
                 "context_usage" => {
                     if let Some(tokens) = payload.get("context_tokens").and_then(Value::as_i64) {
@@ -968,6 +1146,9 @@ pub(crate) async fn load_session_stats(
                 "llm_request" => {
                     output.model_calls = output.model_calls.saturating_add(1);
                 }
+                "reasoning" => {
+                    output.reasoning_events = output.reasoning_events.saturating_add(1);
+                }
                 "tool_call" => {
                     output.tool_calls = output.tool_calls.saturating_add(1);
                 }
@@ -1046,6 +1227,10 @@ async fn print_runtime_status(
     let stats = load_session_stats(runtime, session_id).await;
     let approval_mode = resolve_effective_approval_mode(&config, global.approval_mode);
     let notification = runtime.load_turn_notification_config();
+    let sandbox_text = runtime
+        .session_sandbox_container(session_id)
+        .map(|container_id| container_id.to_string())
+        .unwrap_or_else(|| "-".to_string());
 
     println!("{}", locale::tr(language.as_str(), "状态", "status"));
     if is_zh {
@@ -1072,6 +1257,7 @@ async fn print_runtime_status(
             describe_turn_notification(&notification, language.as_str())
         );
         println!("- 最大轮次: {max_rounds}");
+        println!("- 沙盒容器: {sandbox_text}");
     } else {
         println!("- model: {model_name}");
         println!("- tool_call_mode: {tool_call_mode}");
@@ -1082,6 +1268,7 @@ async fn print_runtime_status(
             describe_turn_notification(&notification, language.as_str())
         );
         println!("- max_rounds: {max_rounds}");
+        println!("- sandbox_container: {sandbox_text}");
     }
     if let Some(total) = max_context {
         let used = stats.context_used_tokens.max(0) as u64;
@@ -1152,6 +1339,7 @@ async fn print_session_stats(
     }
     if is_zh {
         println!("- 模型调用: {}", stats.model_calls);
+        println!("- 推理轮次: {}", stats.reasoning_events);
         println!("- 工具调用: {}", stats.tool_calls);
         println!("- 工具结果: {}", stats.tool_results);
         println!(
@@ -1160,6 +1348,7 @@ async fn print_session_stats(
         );
     } else {
         println!("- model_calls: {}", stats.model_calls);
+        println!("- reasoning_events: {}", stats.reasoning_events);
         println!("- tool_calls: {}", stats.tool_calls);
         println!("- tool_results: {}", stats.tool_results);
         println!(
@@ -1167,6 +1356,9 @@ async fn print_session_stats(
             stats.total_input_tokens, stats.total_output_tokens, stats.total_tokens
         );
     }
+    for line in tool_latency_summary::tool_latency_lines(language.as_str(), &stats.tool_latency) {
+        println!("{line}");
+    }
     Ok(())
 }
 
@@ -1307,13 +1499,21 @@ async fn handle_slash_skills(runtime: &CliRuntime, global: &GlobalArgs, args: &s
         )
         .await;
     }
+    for (prefix, share) in [("share ", true), ("unshare ", false)] {
+        if let Some(rest) = cleaned.strip_prefix(prefix) {
+            let message =
+                skill_share::toggle_skill_shared(runtime, language.as_str(), rest, share).await?;
+            println!("{message}");
+            return Ok(());
+        }
+    }
 
     println!(
         "{}",
         locale::tr(
             language.as_str(),
-            "用法: /skills [list|enable <name>|disable <name>|root]",
-            "usage: /skills [list|enable <name>|disable <name>|root]",
+            "用法: /skills [list|enable <name>|disable <name>|share <name>|unshare <name>|root]",
+            "usage: /skills [list|enable <name>|disable <name>|share <name>|unshare <name>|root]",
         )
     );
     Ok(())
@@ -1440,34 +1640,6 @@ fn find_mcp_server<'a>(servers: &'a [UserMcpServer], name: &str) -> Option<&'a U
         .find(|server| server.name.trim().eq_ignore_ascii_case(name.trim()))
 }
 
-fn resolve_mcp_auth_header(server: &UserMcpServer) -> Option<(String, String)> {
-    let Value::Object(map) = server.auth.as_ref()? else {
-        return None;
-    };
-
-    if let Some(value) = map
-        .get("bearer_token")
-        .and_then(Value::as_str)
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-    {
-        return Some(("Authorization".to_string(), format!("Bearer {value}")));
-    }
-    if let Some(value) = map
-        .get("token")
-        .and_then(Value::as_str)
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-    {
-        return Some(("Authorization".to_string(), format!("Bearer {value}")));
-    }
-    map.get("api_key")
-        .and_then(Value::as_str)
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(|value| ("x-api-key".to_string(), value.to_string()))
-}
-
 fn find_a2a_service<'a>(
     services: &'a [A2aServiceConfig],
     name: &str,
@@ -2039,134 +2211,84 @@ pub(crate) async fn execute_apps_command(
             let client = reqwest::Client::builder()
                 .timeout(Duration::from_secs(6))
                 .build()?;
-            let auth_header = resolve_mcp_auth_header(&server);
             let transport = server.transport.trim().to_ascii_lowercase();
-
-            let mut request = client.get(server.endpoint.trim());
-            if let Some((name, value)) = auth_header.as_ref() {
-                request = request.header(name, value);
-            }
-            match request.send().await {
-                Ok(response) => {
-                    let code = response.status();
-                    if code == reqwest::StatusCode::METHOD_NOT_ALLOWED
-                        && (transport.contains("streamable")
-                            || transport.contains("http")
-                            || transport.is_empty())
-                    {
-                        let mut post = client
-                            .post(server.endpoint.trim())
-                            .header("content-type", "application/json")
-                            .body(r#"{"jsonrpc":"2.0","id":"health","method":"ping","params":{}}"#);
-                        if let Some((name, value)) = auth_header.as_ref() {
-                            post = post.header(name, value);
-                        }
-                        match post.send().await {
-                            Ok(post_response) => {
-                                let post_code = post_response.status();
-                                Ok(vec![
-                                    if post_code.is_success() {
-                                        if is_zh {
-                                            format!(
-                                                "应用连通性测试通过: {target} ({post_code}, probe=GET->POST)"
-                                            )
-                                        } else {
-                                            format!(
-                                                "app connectivity ok: {target} ({post_code}, probe=GET->POST)"
-                                            )
-                                        }
-                                    } else if is_zh {
-                                        format!("应用可达（GET=405，POST={post_code}）: {target}")
-                                    } else {
-                                        format!(
-                                            "app reachable (GET=405, POST={post_code}): {target}"
-                                        )
-                                    },
-                                    format!(
-                                        "{} {}",
-                                        locale::tr(language, "endpoint:", "endpoint:"),
-                                        server.endpoint
-                                    ),
-                                    format!(
-                                        "{} {}",
-                                        locale::tr(language, "transport:", "transport:"),
-                                        if transport.is_empty() {
-                                            "-"
-                                        } else {
-                                            &transport
-                                        }
-                                    ),
-                                ])
-                            }
-                            Err(post_err) => Ok(vec![
-                                if is_zh {
-                                    format!(
-                                        "[错误] 应用连通性测试失败: {target} (GET=405, POST error: {post_err})"
-                                    )
-                                } else {
-                                    format!(
-                                        "[error] app connectivity failed: {target} (GET=405, POST error: {post_err})"
-                                    )
-                                },
-                                format!(
-                                    "{} {}",
-                                    locale::tr(language, "endpoint:", "endpoint:"),
-                                    server.endpoint
-                                ),
-                            ]),
-                        }
+            let endpoint_line = format!(
+                "{} {}",
+                locale::tr(language, "endpoint:", "endpoint:"),
+                server.endpoint
+            );
+            let transport_line = format!(
+                "{} {}",
+                locale::tr(language, "transport:", "transport:"),
+                if transport.is_empty() {
+                    "-"
+                } else {
+                    &transport
+                }
+            );
+            let status_line = match network_doctor::probe_mcp_server(&client, &server).await {
+                McpProbeOutcome::Post { get, post } if post.is_success() => {
+                    if is_zh {
+                        format!(
+                            "应用连通性测试通过: {target} ({post}, probe=GET({})->initialize)",
+                            get.as_u16()
+                        )
                     } else {
-                        Ok(vec![
-                            if code.is_success() {
-                                if is_zh {
-                                    format!("应用连通性测试通过: {target} ({code})")
-                                } else {
-                                    format!("app connectivity ok: {target} ({code})")
-                                }
-                            } else if is_zh {
-                                format!("应用连通性可达但返回非 2xx: {target} ({code})")
-                            } else {
-                                format!("app reachable but returned non-2xx: {target} ({code})")
-                            },
+                        format!(
+                            "app connectivity ok: {target} ({post}, probe=GET({})->initialize)",
+                            get.as_u16()
+                        )
+                    }
+                }
+                McpProbeOutcome::Post { get, post } => {
+                    if is_zh {
+                        format!("应用可达（GET={}，POST={post}）: {target}", get.as_u16())
+                    } else {
+                        format!(
+                            "app reachable (GET={}, POST={post}): {target}",
+                            get.as_u16()
+                        )
+                    }
+                }
+                McpProbeOutcome::PostFailed { get, err } => {
+                    return Ok(vec![
+                        if is_zh {
                             format!(
-                                "{} {}",
-                                locale::tr(language, "endpoint:", "endpoint:"),
-                                server.endpoint
-                            ),
+                                "[错误] 应用连通性测试失败: {target} (GET={}, POST error: {err})",
+                                get.as_u16()
+                            )
+                        } else {
                             format!(
-                                "{} {}",
-                                locale::tr(language, "transport:", "transport:"),
-                                if transport.is_empty() {
-                                    "-"
-                                } else {
-                                    &transport
-                                }
-                            ),
-                        ])
+                                "[error] app connectivity failed: {target} (GET={}, POST error: {err})",
+                                get.as_u16()
+                            )
+                        },
+                        endpoint_line,
+                    ]);
+                }
+                McpProbeOutcome::Get(code) if code.is_success() => {
+                    if is_zh {
+                        format!("应用连通性测试通过: {target} ({code})")
+                    } else {
+                        format!("app connectivity ok: {target} ({code})")
+                    }
+                }
+                McpProbeOutcome::Get(code) => {
+                    if is_zh {
+                        format!("应用连通性可达但返回非 2xx: {target} ({code})")
+                    } else {
+                        format!("app reachable but returned non-2xx: {target} ({code})")
                     }
                 }
-                Err(err) => Ok(vec![
+                McpProbeOutcome::Failed(err) => {
                     if is_zh {
                         format!("[错误] 应用连通性测试失败: {target} ({err})")
                     } else {
                         format!("[error] app connectivity failed: {target} ({err})")
-                    },
-                    format!(
-                        "{} {}",
-                        locale::tr(language, "endpoint:", "endpoint:"),
-                        server.endpoint
-                    ),
-                    format!(
-                        "{} {}",
-                        locale::tr(language, "transport:", "transport:"),
-                        if transport.is_empty() {
-                            "-"
-                        } else {
-                            &transport
-                        }
-                    ),
-                ]),
-            }
+                    }
+                }
+            };
+            Ok(vec![status_line, endpoint_line, transport_line])
         }
         _ => Ok(vec![
             if is_zh {
@@ -2820,13 +2942,28 @@ async fn handle_slash_rename(
             "{}",
             locale::tr(
                 language.as_str(),
-                "用法: /rename <title>",
-                "usage: /rename <title>",
+                "用法: /rename <title|auto>",
+                "usage: /rename <title|auto>",
             )
         );
         return Ok(());
     }
-    let saved = rename_session_title(runtime, session_id, title).await?;
+    let saved = if session_rename::is_auto_rename(title) {
+        let Some(saved) = session_rename::auto_rename_session(runtime, session_id).await? else {
+            println!(
+                "{}",
+                locale::tr(
+                    language.as_str(),
+                    "当前会话还没有可用于生成标题的消息",
+                    "no messages to generate a title from yet",
+                )
+            );
+            return Ok(());
+        };
+        saved
+    } else {
+        rename_session_title(runtime, session_id, title).await?
+    };
     if locale::is_zh_language(language.as_str()) {
         println!("会话已重命名: {saved}");
     } else {
@@ -2973,6 +3110,7 @@ pub(crate) async fn collect_debug_config_payload(
     runtime: &CliRuntime,
     global: &GlobalArgs,
     session_id: &str,
+    show_secrets: bool,
 ) -> Value {
     let config = runtime.state.config_store.get().await;
     let model_from_cli = global
@@ -3094,14 +3232,22 @@ pub(crate) async fn collect_debug_config_payload(
             {"layer": "builtin", "value": json!({"type": "off"})},
         ],
     });
+    let system_prompt_budget =
+        match prompt_budget_display::build_current_prompt_budget(runtime, global).await {
+            Ok(breakdown) => serde_json::to_value(breakdown).unwrap_or(Value::Null),
+            Err(err) => json!({ "error": err.to_string() }),
+        };
 
     json!({
         "runtime": {
             "launch_dir": runtime.launch_dir,
             "temp_root": runtime.temp_root,
             "repo_root": runtime.repo_root,
+            "assets_root": runtime.assets_root,
             "user_id": runtime.user_id,
             "session_id": session_id,
+            "config_path": runtime.config_path,
+            "config_path_source": runtime.config_source.as_str(),
             "workspace_root": config.workspace.root,
             "db_path": config.storage.db_path,
             "agent_id_override": global.agent.clone(),
@@ -3126,8 +3272,13 @@ pub(crate) async fn collect_debug_config_payload(
             "turn_notification": notification_payload,
             "turn_notification_source": turn_notification_source,
             "exec_policy_mode": config.security.exec_policy_mode,
+            "model_config": config_redact::config_value_for_display(
+                &resolved_model.as_ref().and_then(|name| config.llm.models.get(name)),
+                show_secrets,
+            ),
             "source_chain": source_chain,
         },
+        "system_prompt_budget": system_prompt_budget,
         "checks": {
             "config_exists": runtime.temp_root.join("config/wunder.yaml").exists(),
             "skills_path_count": config.skills.paths.len(),
@@ -3141,8 +3292,9 @@ async fn print_debug_config(
     runtime: &CliRuntime,
     global: &GlobalArgs,
     session_id: &str,
+    show_secrets: bool,
 ) -> Result<()> {
-    let payload = collect_debug_config_payload(runtime, global, session_id).await;
+    let payload = collect_debug_config_payload(runtime, global, session_id, show_secrets).await;
     println!("{}", serde_json::to_string_pretty(&payload)?);
     Ok(())
 }
@@ -3168,15 +3320,22 @@ async fn handle_slash_system(
         );
         return Ok(());
     }
+    if cleaned.eq_ignore_ascii_case("budget") {
+        let breakdown = prompt_budget_display::build_current_prompt_budget(runtime, global).await?;
+        for line in prompt_budget_display::prompt_budget_lines(language.as_str(), &breakdown) {
+            println!("{line}");
+        }
+        return Ok(());
+    }
     if let Some(rest) = cleaned.strip_prefix("set ") {
         let prompt = rest.trim();
         if prompt.is_empty() {
             if is_zh {
                 println!("[错误] 额外提示词为空");
-                println!("用法: /system [set <extra_prompt>|clear]");
+                println!("用法: /system [set <extra_prompt>|clear|budget]");
             } else {
                 println!("[error] extra prompt is empty");
-                println!("usage: /system [set <extra_prompt>|clear]");
+                println!("usage: /system [set <extra_prompt>|clear|budget]");
             }
             return Ok(());
         }
@@ -3189,10 +3348,10 @@ async fn handle_slash_system(
     } else if !cleaned.is_empty() && !cleaned.eq_ignore_ascii_case("show") {
         if is_zh {
             println!("[错误] 无效的 /system 参数");
-            println!("用法: /system [set <extra_prompt>|clear]");
+            println!("用法: /system [set <extra_prompt>|clear|budget]");
         } else {
             println!("[error] invalid /system args");
-            println!("usage: /system [set <extra_prompt>|clear]");
+            println!("usage: /system [set <extra_prompt>|clear|budget]");
         }
         return Ok(());
     }
@@ -3272,7 +3431,7 @@ fn normalize_personality_mode(raw: &str) -> Option<&'static str> {
     }
 }
 
-fn personality_instruction(mode: &str) -> Option<&'static str> {
+pub(crate) fn personality_instruction(mode: &str) -> Option<&'static str> {
     match mode.trim().to_ascii_lowercase().as_str() {
         "concise" => Some(
             "Response style: concise. Be direct and brief, minimize extra explanation unless asked.",
@@ -3287,18 +3446,18 @@ fn personality_instruction(mode: &str) -> Option<&'static str> {
     }
 }
 
-fn build_effective_agent_prompt(runtime: &CliRuntime) -> Option<String> {
+pub(crate) fn build_effective_agent_prompt(runtime: &CliRuntime) -> Option<String> {
+    let agents_md = runtime.load_agents_md_prompt();
     let extra = runtime.load_extra_prompt();
     let personality = runtime
         .load_personality_mode()
         .and_then(|mode| personality_instruction(mode.as_str()).map(ToString::to_string));
 
-    match (extra, personality) {
-        (None, None) => None,
-        (Some(extra), None) => Some(extra),
-        (None, Some(personality)) => Some(personality),
-        (Some(extra), Some(personality)) => Some(format!("{extra}\n\n{personality}")),
-    }
+    let parts = [agents_md, extra, personality]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    (!parts.is_empty()).then(|| parts.join("\n\n"))
 }
 
 async fn handle_slash_personality(
@@ -3394,6 +3553,7 @@ async fn handle_slash_plan(
         session_id,
         agent_id_override,
         None,
+        false,
     )
     .await?;
     Ok(())
@@ -3582,6 +3742,15 @@ async fn handle_slash_agent(
     let language = locale::resolve_cli_language(global);
     let is_zh = locale::is_zh_language(language.as_str());
     let cleaned = args.trim();
+    if let Some(action) = agent_profiles::parse_agent_profile_action(cleaned) {
+        let lines =
+            agent_profiles::run_agent_profile_action(runtime, global, agent_id_override, action)
+                .await?;
+        for line in lines {
+            println!("{line}");
+        }
+        return Ok(());
+    }
     if cleaned.is_empty() || cleaned.eq_ignore_ascii_case("show") {
         let active = agent_id_override
             .as_deref()
@@ -3590,10 +3759,10 @@ async fn handle_slash_agent(
             .unwrap_or("-");
         if is_zh {
             println!("当前 agent_id 覆盖: {active}");
-            println!("用法: /agent [show|list|clear|<agent_id>]");
+            println!("用法: {}", agent_profiles::AGENT_SLASH_USAGE);
         } else {
             println!("current agent_id override: {active}");
-            println!("usage: /agent [show|list|clear|<agent_id>]");
+            println!("usage: {}", agent_profiles::AGENT_SLASH_USAGE);
         }
         return Ok(());
     }
@@ -3610,6 +3779,7 @@ async fn handle_slash_agent(
                     .filter(|value| !value.is_empty())
             });
         let agents = collect_recent_agent_ids(runtime, 120).await?;
+        let profile_lines = agent_profiles::agent_profile_list_lines(runtime, language.as_str())?;
         if agents.is_empty() {
             println!(
                 "{}",
@@ -3619,6 +3789,9 @@ async fn handle_slash_agent(
                     "no agent_id found in recent sessions, use /agent <agent_id> directly",
                 )
             );
+            for line in profile_lines {
+                println!("{line}");
+            }
             return Ok(());
         }
         if is_zh {
@@ -3634,6 +3807,9 @@ async fn handle_slash_agent(
             };
             println!("{marker} {:>2}. {agent}", index + 1);
         }
+        for line in profile_lines {
+            println!("{line}");
+        }
         return Ok(());
     }
     if cleaned.eq_ignore_ascii_case("clear")
@@ -3760,8 +3936,8 @@ async fn handle_slash_attach(
             }
             return Ok(());
         }
-        attachments::AttachAction::Add(path) => {
-            let prepared =
+        attachments::AttachAction::Add { path, remember } => {
+            let mut prepared =
                 match attachments::prepare_attachment_from_path(runtime, path.as_str()).await {
                     Ok(prepared) => prepared,
                     Err(err) => {
@@ -3773,6 +3949,7 @@ async fn handle_slash_attach(
                         return Ok(());
                     }
                 };
+            prepared.remember = remember;
             let duplicate = pending_attachments
                 .iter()
                 .position(|item| item.source.eq_ignore_ascii_case(prepared.source.as_str()));
@@ -4111,14 +4288,18 @@ fn handle_slash_notify(runtime: &CliRuntime, global: &GlobalArgs, args: &str) ->
                 "当前回合通知: {}",
                 describe_turn_notification(&config, language.as_str())
             );
-            println!("用法: /notify [show|off|bell|osc9|when <always|unfocused>|<command...>]");
+            println!(
+                "用法: /notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]"
+            );
             println!("示例: /notify powershell -NoProfile -Command \"Write-Output done\"");
         } else {
             println!(
                 "current turn notification: {}",
                 describe_turn_notification(&config, language.as_str())
             );
-            println!("usage: /notify [show|off|bell|osc9|when <always|unfocused>|<command...>]");
+            println!(
+                "usage: /notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]"
+            );
             println!("example: /notify powershell -NoProfile -Command \"Write-Output done\"");
         }
         return Ok(());
@@ -4170,13 +4351,7 @@ fn handle_slash_notify(runtime: &CliRuntime, global: &GlobalArgs, args: &str) ->
         return Ok(());
     }
 
-    if cleaned.eq_ignore_ascii_case("bell") || cleaned.eq_ignore_ascii_case("osc9") {
-        let when = TurnNotificationWhen::Always;
-        let config = if cleaned.eq_ignore_ascii_case("bell") {
-            TurnNotificationConfig::Bell { when }
-        } else {
-            TurnNotificationConfig::Osc9 { when }
-        };
+    if let Some(config) = turn_notifier::parse_builtin_notifier(cleaned) {
         runtime.save_turn_notification_config(&config)?;
         println!(
             "{}",
@@ -4197,8 +4372,8 @@ fn handle_slash_notify(runtime: &CliRuntime, global: &GlobalArgs, args: &str) ->
             "{}",
             locale::tr(
                 language.as_str(),
-                "用法: /notify [show|off|bell|osc9|when <always|unfocused>|<command...>]",
-                "usage: /notify [show|off|bell|osc9|when <always|unfocused>|<command...>]",
+                "用法: /notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]",
+                "usage: /notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]",
             )
         );
         return Ok(());
@@ -4252,6 +4427,11 @@ pub(crate) fn describe_turn_notification(
             locale::tr(language, "OSC9 终端通知", "OSC9"),
             describe_notification_when_suffix(when, language)
         ),
+        TurnNotificationConfig::Desktop { when } => format!(
+            "{}{}",
+            locale::tr(language, "系统桌面通知", "desktop"),
+            describe_notification_when_suffix(when, language)
+        ),
         TurnNotificationConfig::Command { argv, when } => {
             let rendered = argv.join(" ");
             if rendered.trim().is_empty() {
@@ -4282,6 +4462,10 @@ pub(crate) fn serialize_turn_notification(config: &TurnNotificationConfig) -> Va
             "type": "osc9",
             "when": when_to_str(when),
         }),
+        TurnNotificationConfig::Desktop { when } => json!({
+            "type": "desktop",
+            "when": when_to_str(when),
+        }),
         TurnNotificationConfig::Command { argv, when } => json!({
             "type": "command",
             "argv": argv,
@@ -4323,6 +4507,7 @@ fn notification_when(config: &TurnNotificationConfig) -> TurnNotificationWhen {
         TurnNotificationConfig::Off => TurnNotificationWhen::Always,
         TurnNotificationConfig::Bell { when } => when.clone(),
         TurnNotificationConfig::Osc9 { when } => when.clone(),
+        TurnNotificationConfig::Desktop { when } => when.clone(),
         TurnNotificationConfig::Command { when, .. } => when.clone(),
     }
 }
@@ -4335,6 +4520,7 @@ pub(crate) fn apply_notification_when(
         TurnNotificationConfig::Off => TurnNotificationConfig::Off,
         TurnNotificationConfig::Bell { .. } => TurnNotificationConfig::Bell { when },
         TurnNotificationConfig::Osc9 { .. } => TurnNotificationConfig::Osc9 { when },
+        TurnNotificationConfig::Desktop { .. } => TurnNotificationConfig::Desktop { when },
         TurnNotificationConfig::Command { argv, .. } => {
             TurnNotificationConfig::Command { argv, when }
         }
@@ -4352,6 +4538,7 @@ pub(crate) async fn build_current_system_prompt(
         model_name.as_deref(),
         global.tool_call_mode,
         global.approval_mode,
+        &SamplingOverrides::from_global(global),
     );
     let skills = runtime.state.skills.read().await.clone();
     let user_tool_bindings =
@@ -4378,6 +4565,7 @@ pub(crate) async fn build_current_system_prompt(
             false,
             &workspace_id,
             request_overrides.as_ref(),
+            model_name.as_deref(),
             effective_prompt.as_deref(),
             false,
         )
@@ -4570,10 +4758,24 @@ fn parse_tool_call_mode(raw: &str) -> Option<ToolCallModeArg> {
 async fn handle_slash_approvals(
     runtime: &CliRuntime,
     global: &GlobalArgs,
+    session_id: &str,
     args: &str,
 ) -> Result<()> {
     let language = locale::resolve_cli_language(global);
     let is_zh = locale::is_zh_language(language.as_str());
+    if let Some(lines) = session_approvals::run_session_approvals_command(
+        runtime,
+        language.as_str(),
+        session_id,
+        args,
+    )
+    .await?
+    {
+        for line in lines {
+            println!("{line}");
+        }
+        return Ok(());
+    }
     let cleaned = args.trim();
     if cleaned.is_empty() || cleaned.eq_ignore_ascii_case("show") {
         let config = runtime.state.config_store.get().await;
@@ -4593,8 +4795,8 @@ async fn handle_slash_approvals(
             "{}",
             locale::tr(
                 language.as_str(),
-                "用法: /approvals [show|suggest|auto_edit|full_auto]",
-                "usage: /approvals [show|suggest|auto_edit|full_auto]",
+                &format!("用法: {}", session_approvals::APPROVALS_SLASH_USAGE),
+                &format!("usage: {}", session_approvals::APPROVALS_SLASH_USAGE),
             )
         );
         return Ok(());
@@ -4692,6 +4894,9 @@ async fn handle_tool_run(
             format!("invalid json for --args: {}", command.args.trim())
         }
     })?;
+    if command.repeat != 1 || command.time {
+        return tool_repeat::run_tool_timed(runtime, global, &command, args).await;
+    }
     run_tool_direct(runtime, global, &command.name, args).await
 }
 
@@ -4720,6 +4925,22 @@ async fn run_tool_direct(
     tool_name: &str,
     args: Value,
 ) -> Result<()> {
+    let result = execute_tool_direct(runtime, global, tool_name, &args).await?;
+    if global.json {
+        println!("{}", serde_json::to_string(&result)?);
+    } else {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    }
+    Ok(())
+}
+
+/// Run one tool outside a chat turn with the same bindings and `ToolContext` a turn would use.
+pub(crate) async fn execute_tool_direct(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    tool_name: &str,
+    args: &Value,
+) -> Result<Value> {
     let config = runtime.state.config_store.get().await;
     let skills_snapshot = runtime.state.skills.read().await.clone();
     let bindings =
@@ -4761,15 +4982,10 @@ async fn run_tool_direct(
         event_emitter: None,
         beeroom_realtime: Some(runtime.state.projection.beeroom.clone()),
         http: &http,
+        dry_run: false,
     };
 
-    let result = execute_tool(&tool_context, tool_name, &args).await?;
-    if global.json {
-        println!("{}", serde_json::to_string(&result)?);
-    } else {
-        println!("{}", serde_json::to_string_pretty(&result)?);
-    }
-    Ok(())
+    Ok(execute_tool(&tool_context, tool_name, args).await?)
 }
 
 async fn handle_mcp(runtime: &CliRuntime, global: &GlobalArgs, command: McpCommand) -> Result<()> {
@@ -5359,6 +5575,7 @@ async fn handle_skills(
         SkillsSubcommand::Upload(cmd) => skills_upload(runtime, global, cmd).await,
         SkillsSubcommand::Remove(cmd) => skills_remove(runtime, global, cmd).await,
         SkillsSubcommand::Root => skills_root(runtime, global),
+        SkillsSubcommand::Run(cmd) => skill_run::handle_skill_run(runtime, global, cmd).await,
     }
 }
 
@@ -5374,6 +5591,7 @@ async fn skills_list(
         .user_tool_store
         .load_user_tools(&runtime.user_id);
     let enabled_set: HashSet<String> = payload.skills.enabled.into_iter().collect();
+    let shared_set: HashSet<String> = payload.skills.shared.into_iter().collect();
 
     let (skill_root, specs) = load_user_skill_specs(runtime).await;
     if command.json {
@@ -5384,6 +5602,7 @@ async fn skills_list(
                     "name": spec.name,
                     "path": spec.path,
                     "enabled": enabled_set.contains(&spec.name),
+                    "shared": shared_set.contains(&spec.name),
                 })
             })
             .collect::<Vec<_>>();
@@ -5423,7 +5642,14 @@ async fn skills_list(
         } else {
             "disabled"
         };
-        println!("{} [{}] {}", spec.name, enabled, spec.path);
+        let shared = if !shared_set.contains(&spec.name) {
+            ""
+        } else if is_zh {
+            " [共享]"
+        } else {
+            " [shared]"
+        };
+        println!("{} [{}]{shared} {}", spec.name, enabled, spec.path);
     }
     Ok(())
 }
@@ -5871,13 +6097,16 @@ async fn handle_config(
     command: ConfigCommand,
 ) -> Result<()> {
     match command.command {
-        ConfigSubcommand::Show => config_show(runtime, global).await,
+        ConfigSubcommand::Show(cmd) => config_show(runtime, global, cmd.show_secrets).await,
         ConfigSubcommand::SetToolCallMode(cmd) => {
             config_set_tool_call_mode(runtime, global, cmd).await
         }
         ConfigSubcommand::SetApprovalMode(cmd) => {
             config_set_approval_mode(runtime, global, cmd).await
         }
+        ConfigSubcommand::Get(cmd) => config_path::config_get(runtime, cmd).await,
+        ConfigSubcommand::Set(cmd) => config_path::config_set(runtime, global, cmd).await,
+        ConfigSubcommand::Test(cmd) => config_test::config_test(runtime, global, cmd).await,
     }
 }
 
@@ -6074,7 +6303,7 @@ pub(crate) async fn apply_cli_model_config(
     Ok((provider, resolved_max_context))
 }
 
-async fn config_show(runtime: &CliRuntime, global: &GlobalArgs) -> Result<()> {
+async fn config_show(runtime: &CliRuntime, global: &GlobalArgs, show_secrets: bool) -> Result<()> {
     let config = runtime.state.config_store.get().await;
     let model = runtime.resolve_model_name(global.model.as_deref()).await;
     let model_entry = model.as_ref().and_then(|name| config.llm.models.get(name));
@@ -6104,6 +6333,7 @@ async fn config_show(runtime: &CliRuntime, global: &GlobalArgs) -> Result<()> {
         "approval_mode": approval_mode,
         "max_rounds": max_rounds,
         "max_context": max_context,
+        "model_config": config_redact::config_value_for_display(&model_entry, show_secrets),
         "context_used": stats.context_used_tokens.max(0),
         "context_left_percent": context_left_percent(stats.context_used_tokens, max_context),
         "config_path": std::env::var("WUNDER_CONFIG_PATH").unwrap_or_default(),
@@ -6395,169 +6625,6 @@ fn build_cli_llm_model_config(
     }
 }
 
-async fn handle_doctor(
-    runtime: &CliRuntime,
-    global: &GlobalArgs,
-    command: DoctorCommand,
-) -> Result<()> {
-    let language = locale::resolve_cli_language(global);
-    let is_zh = locale::is_zh_language(language.as_str());
-    let config = runtime.state.config_store.get().await;
-    let model = runtime.resolve_model_name(global.model.as_deref()).await;
-    let prompts_root = std::env::var("WUNDER_PROMPTS_ROOT").unwrap_or_default();
-    let prompts_status_path = if prompts_root.trim().is_empty() {
-        "<embedded>".to_string()
-    } else {
-        prompts_root
-    };
-    let checks = vec![
-        (
-            "config",
-            std::env::var("WUNDER_CONFIG_PATH").unwrap_or_default(),
-            true,
-        ),
-        (
-            "i18n_messages",
-            std::env::var("WUNDER_I18N_MESSAGES_PATH").unwrap_or_default(),
-            true,
-        ),
-        ("prompts_root", prompts_status_path, false),
-        (
-            "skill_runner",
-            std::env::var("WUNDER_SKILL_RUNNER_PATH").unwrap_or_default(),
-            true,
-        ),
-    ];
-
-    println!(
-        "{}",
-        locale::tr(language.as_str(), "wunder-cli 诊断", "wunder-cli doctor")
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!("- 启动目录: {}", runtime.launch_dir.to_string_lossy())
-        } else {
-            format!("- launch_dir: {}", runtime.launch_dir.to_string_lossy())
-        }
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!("- 临时目录: {}", runtime.temp_root.to_string_lossy())
-        } else {
-            format!("- temp_root: {}", runtime.temp_root.to_string_lossy())
-        }
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!("- 项目根目录: {}", runtime.repo_root.to_string_lossy())
-        } else {
-            format!("- project_root: {}", runtime.repo_root.to_string_lossy())
-        }
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!("- 用户 ID: {}", runtime.user_id)
-        } else {
-            format!("- user_id: {}", runtime.user_id)
-        }
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!("- 工作目录: {}", config.workspace.root)
-        } else {
-            format!("- workspace_root: {}", config.workspace.root)
-        }
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!("- 数据库路径: {}", config.storage.db_path)
-        } else {
-            format!("- db_path: {}", config.storage.db_path)
-        }
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!("- 模型: {}", model.unwrap_or_else(|| "<none>".to_string()))
-        } else {
-            format!("- model: {}", model.unwrap_or_else(|| "<none>".to_string()))
-        }
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!(
-                "- 审批模式: {}",
-                resolve_effective_approval_mode(&config, global.approval_mode)
-            )
-        } else {
-            format!(
-                "- approval_mode: {}",
-                resolve_effective_approval_mode(&config, global.approval_mode)
-            )
-        }
-    );
-    println!(
-        "{}",
-        if is_zh {
-            format!(
-                "- 覆盖配置存在: {}",
-                runtime.temp_root.join("config/wunder.yaml").exists()
-            )
-        } else {
-            format!(
-                "- config_exists: {}",
-                runtime.temp_root.join("config/wunder.yaml").exists()
-            )
-        }
-    );
-
-    for (name, path, should_exist) in checks {
-        let exists = if path.trim().is_empty() {
-            false
-        } else {
-            std::path::Path::new(path.as_str()).exists()
-        };
-        let status = if !should_exist || exists {
-            locale::tr(language.as_str(), "正常", "ok")
-        } else {
-            locale::tr(language.as_str(), "缺失", "missing")
-        };
-        let check_name = if is_zh {
-            match name {
-                "config" => "配置文件",
-                "i18n_messages" => "i18n 消息文件",
-                "prompts_root" => "提示词根目录",
-                "skill_runner" => "技能运行器",
-                _ => name,
-            }
-        } else {
-            name
-        };
-        println!("- {check_name}: [{status}] {path}");
-    }
-
-    if command.verbose {
-        let payload = json!({
-            "skills_paths": config.skills.paths,
-            "allow_paths": config.security.allow_paths,
-            "allow_commands": config.security.allow_commands,
-            "approval_mode_config": config.security.approval_mode,
-            "approval_mode_effective": resolve_effective_approval_mode(&config, global.approval_mode),
-            "exec_policy_mode": config.security.exec_policy_mode,
-            "config_path": std::env::var("WUNDER_CONFIG_PATH").unwrap_or_default(),
-        });
-        println!("{}", serde_json::to_string_pretty(&payload)?);
-    }
-    Ok(())
-}
-
 pub(crate) async fn build_wunder_request(
     runtime: &CliRuntime,
     global: &GlobalArgs,
@@ -6574,6 +6641,7 @@ pub(crate) async fn build_wunder_request(
         model_name.as_deref(),
         global.tool_call_mode,
         global.approval_mode,
+        &SamplingOverrides::from_global(global),
     );
 
     input_guard::validate_request_text_input_size(
@@ -6583,6 +6651,7 @@ pub(crate) async fn build_wunder_request(
     )?;
 
     ensure_cli_session_record(runtime, session_id, Some(prompt)).await?;
+    let tool_names = session_tools::resolve_cli_session_tool_names(runtime, session_id).await?;
 
     let resolved_agent = agent_id_override
         .map(str::trim)
@@ -6613,7 +6682,7 @@ pub(crate) async fn build_wunder_request(
         user_id: runtime.user_id.clone(),
         question: prompt.trim().to_string(),
         client_message_id: None,
-        tool_names: Vec::new(),
+        tool_names,
         skip_tool_calls: false,
         stream: !global.no_stream,
         debug_payload: false,
@@ -6626,33 +6695,16 @@ pub(crate) async fn build_wunder_request(
         agent_prompt: build_effective_agent_prompt(runtime),
         preview_skill,
         attachments,
+        max_rounds: None,
+        cite: global.cite,
+        remember_attachments: global.remember,
         allow_queue: true,
+        enforce_runtime_queue: false,
         is_admin: false,
         approval_tx: None,
     })
 }
 
-fn truncate_preview(text: &str, limit: usize) -> String {
-    if limit == 0 {
-        return String::new();
-    }
-    let cleaned = text.trim();
-    if cleaned.is_empty() {
-        return String::new();
-    }
-    let mut out = String::new();
-    for (index, ch) in cleaned.chars().enumerate() {
-        if index >= limit {
-            break;
-        }
-        out.push(ch);
-    }
-    if cleaned.chars().count() > limit {
-        out.push('…');
-    }
-    out
-}
-
 pub(crate) fn emit_turn_complete_notification(
     runtime: &CliRuntime,
     session_id: &str,
@@ -6661,67 +6713,22 @@ pub(crate) fn emit_turn_complete_notification(
     terminal_focused: Option<bool>,
 ) {
     let config = runtime.load_turn_notification_config();
-    if matches!(config, TurnNotificationConfig::Off) {
+    let Some(notifier) = turn_notifier::notifier_for(&config) else {
         return;
-    }
-    if matches!(notification_when(&config), TurnNotificationWhen::Unfocused)
-        && terminal_focused.unwrap_or(true)
-    {
-        return;
-    }
-
-    let summary = truncate_preview(&final_event.answer, 180);
-    let payload = json!({
-        "type": "agent-turn-complete",
-        "source": source,
-        "session_id": session_id,
-        "user_id": runtime.user_id,
-        "cwd": runtime.launch_dir,
-        "stop_reason": final_event.stop_reason,
-        "answer_preview": summary,
-        "ts": SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|value| value.as_secs_f64())
-            .unwrap_or(0.0),
-    });
-    let payload_text = serde_json::to_string(&payload).unwrap_or_default();
-
-    match config {
-        TurnNotificationConfig::Off => {}
-        TurnNotificationConfig::Bell { .. } => {
-            eprint!("\u{0007}");
-            let _ = io::stderr().flush();
-        }
-        TurnNotificationConfig::Osc9 { .. } => {
-            let message = if summary.trim().is_empty() {
-                "wunder-cli turn complete".to_string()
-            } else {
-                summary
-            };
-            eprint!("\u{1b}]9;{message}\u{1b}\\");
-            let _ = io::stderr().flush();
-        }
-        TurnNotificationConfig::Command { argv, .. } => {
-            if argv.is_empty() {
-                return;
-            }
-            let mut command = std::process::Command::new(&argv[0]);
-            if argv.len() > 1 {
-                command.args(&argv[1..]);
-            }
-            command
-                .arg(payload_text)
-                .env("WUNDER_NOTIFY_EVENT", "agent-turn-complete")
-                .env("WUNDER_NOTIFY_SOURCE", source)
-                .env("WUNDER_NOTIFY_SESSION_ID", session_id)
-                .env("WUNDER_NOTIFY_USER_ID", runtime.user_id.as_str())
-                .env(
-                    "WUNDER_NOTIFY_CWD",
-                    runtime.launch_dir.to_string_lossy().as_ref(),
-                );
-            let _ = command.spawn();
-        }
-    }
+    };
+    let notification = turn_notifier::turn_notification(
+        source,
+        session_id,
+        runtime.user_id.as_str(),
+        &runtime.launch_dir,
+        final_event,
+    );
+    turn_notifier::deliver_turn_notification(
+        notifier.as_ref(),
+        &notification_when(&config),
+        terminal_focused,
+        &notification,
+    );
 }
 
 async fn run_prompt_once(
@@ -6731,6 +6738,7 @@ async fn run_prompt_once(
     session_id: &str,
     agent_id_override: Option<&str>,
     attachments: Option<Vec<AttachmentPayload>>,
+    remember_attachments: bool,
 ) -> Result<FinalEvent> {
     let language = locale::resolve_cli_language(global);
     let mut request = build_wunder_request(
@@ -6742,14 +6750,56 @@ async fn run_prompt_once(
         attachments,
     )
     .await?;
-    let _approval_task = if should_interactive_approvals(global) {
+    request.remember_attachments |= remember_attachments;
+    let (approval_cancel, approval_task) = if should_interactive_approvals(global) {
         let (tx, rx) = new_approval_channel();
         request.approval_tx = Some(tx);
-        Some(tokio::spawn(handle_stdio_approvals(rx, language)))
+        let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel();
+        let task = tokio::spawn(handle_stdio_approvals(rx, language.clone(), cancel_rx));
+        (Some(cancel_tx), Some(task))
     } else {
-        None
+        (None, None)
     };
 
+    turn_interrupt::install_ctrl_c_handler();
+    let turn = run_turn_request(runtime, global, request, session_id);
+    match turn_interrupt::run_interruptible(turn, turn_interrupt::ctrl_c_interrupt()).await {
+        TurnOutcome::Finished(result) => result,
+        TurnOutcome::Cancelled => {
+            // Deny an approval still waiting on stdin before stopping the turn, so the tool
+            // is refused rather than left pending.
+            if let Some(cancel) = approval_cancel {
+                let _ = cancel.send(());
+            }
+            if let Some(task) = approval_task {
+                let _ = tokio::time::timeout(APPROVAL_CANCEL_GRACE, task).await;
+            }
+            runtime.state.monitor.cancel(session_id);
+            if global.json {
+                let payload =
+                    json!({ "event": "turn_cancelled", "data": { "session_id": session_id } });
+                println!("{}", serde_json::to_string(&payload)?);
+            } else {
+                println!();
+                println!(
+                    "{}",
+                    locale::tr(language.as_str(), "本轮已取消", "turn cancelled")
+                );
+            }
+            Ok(FinalEvent {
+                stop_reason: Some(turn_interrupt::CANCELLED_STOP_REASON.to_string()),
+                ..FinalEvent::default()
+            })
+        }
+    }
+}
+
+async fn run_turn_request(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    request: WunderRequest,
+    session_id: &str,
+) -> Result<FinalEvent> {
     if global.no_stream {
         let response = runtime.state.kernel.orchestrator.run(request).await?;
         let final_event = FinalEvent {
@@ -6779,7 +6829,9 @@ async fn run_prompt_once(
 
     let mut stream = runtime.state.kernel.orchestrator.stream(request).await?;
     let language = locale::resolve_cli_language(global);
-    let mut renderer = StreamRenderer::new(global.json, language.as_str());
+    let mut renderer = StreamRenderer::new(global.json, language.as_str())
+        .with_reasoning(global.show_reasoning)
+        .with_citations(global.cite);
     let mut final_event = FinalEvent::default();
     let mut goal_continue_ready = false;
     while let Some(item) = stream.next().await {
@@ -6813,9 +6865,20 @@ fn should_interactive_approvals(global: &GlobalArgs) -> bool {
     io::stdin().is_terminal() && io::stdout().is_terminal()
 }
 
-async fn handle_stdio_approvals(mut rx: ApprovalRequestRx, language: String) {
+async fn handle_stdio_approvals(
+    mut rx: ApprovalRequestRx,
+    language: String,
+    mut cancelled: tokio::sync::oneshot::Receiver<()>,
+) {
     let is_zh = locale::is_zh_language(language.as_str());
-    while let Some(request) = rx.recv().await {
+    loop {
+        let request = tokio::select! {
+            request = rx.recv() => match request {
+                Some(request) => request,
+                None => return,
+            },
+            _ = &mut cancelled => return,
+        };
         let summary = compact_approval_prompt_text(request.summary.as_str(), 180, is_zh);
         println!();
         if is_zh {
@@ -6844,14 +6907,24 @@ async fn handle_stdio_approvals(mut rx: ApprovalRequestRx, language: String) {
             }
             io::stdout().flush().ok();
 
-            let choice = tokio::task::spawn_blocking(|| {
+            let read = tokio::task::spawn_blocking(|| {
                 let mut buffer = String::new();
                 std::io::stdin().read_line(&mut buffer).ok();
                 buffer
-            })
-            .await
-            .ok()
-            .unwrap_or_default();
+            });
+            let choice = tokio::select! {
+                choice = read => choice.ok().unwrap_or_default(),
+                _ = &mut cancelled => {
+                    let _ = request.respond_to.send(ApprovalResponse::Deny);
+                    // The blocking stdin read cannot be aborted and swallows the next line.
+                    if is_zh {
+                        println!("[审批] 本轮已取消，已按拒绝处理；按回车返回输入。");
+                    } else {
+                        println!("[approval] turn cancelled, request denied; press Enter to return to the prompt.");
+                    }
+                    return;
+                }
+            };
 
             let parsed = match choice.trim().to_ascii_lowercase().as_str() {
                 "y" | "yes" | "1" => Some(ApprovalResponse::ApproveOnce),
@@ -6909,6 +6982,7 @@ fn build_request_overrides(
     model_name: Option<&str>,
     tool_call_mode: Option<ToolCallModeArg>,
     approval_mode: Option<ApprovalModeArg>,
+    sampling: &SamplingOverrides,
 ) -> Option<Value> {
     let selected_model = resolve_selected_model(config, model_name)?;
     let mut root = serde_json::Map::new();
@@ -6917,6 +6991,7 @@ fn build_request_overrides(
     if let Some(mode) = tool_call_mode {
         model_overrides.insert("tool_call_mode".to_string(), json!(mode.as_str()));
     }
+    sampling.apply(&mut model_overrides);
 
     let max_rounds = config
         .llm
@@ -7371,6 +7446,7 @@ pub(crate) fn build_review_prompt_with_language(
     workspace_root: &std::path::Path,
     focus: &str,
     language: &str,
+    with_history: bool,
 ) -> Result<String> {
     if !workspace_root.join(".git").exists() {
         return Err(anyhow!(locale::tr(
@@ -7417,6 +7493,15 @@ pub(crate) fn build_review_prompt_with_language(
     if diff_body.trim().is_empty() {
         diff_body = "<no diff>".to_string();
     }
+    // History shares the diff budget and is the first thing dropped.
+    let history = if with_history {
+        review::file_history_section(
+            workspace_root,
+            MAX_DIFF_CHARS.saturating_sub(diff_body.chars().count()),
+        )
+    } else {
+        String::new()
+    };
     let diff_trimmed = truncate_chars(&diff_body, MAX_DIFF_CHARS);
 
     Ok(format!(
@@ -7429,13 +7514,14 @@ pub(crate) fn build_review_prompt_with_language(
 {focus_line}## git status --porcelain
 {status}
 
-{diff_trimmed}
+{history}{diff_trimmed}
 "#
     ))
 }
 
 pub(crate) fn search_workspace_files(
     workspace_root: &std::path::Path,
+    ignore: Option<&wunder_server::workspace_ignore::WorkspaceIgnore>,
     query: &str,
     limit: usize,
 ) -> Vec<String> {
@@ -7467,6 +7553,9 @@ pub(crate) fn search_workspace_files(
             if path == workspace_root {
                 return true;
             }
+            if ignore.is_some_and(|ignore| ignore.is_ignored(path, entry.file_type().is_dir())) {
+                return false;
+            }
             let Some(name) = path.file_name().and_then(|value| value.to_str()) else {
                 return true;
             };
@@ -7536,6 +7625,38 @@ fn truncate_chars(text: &str, max_chars: usize) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn tool_dispatch_failures_exit_with_distinct_statuses() {
+        let statuses: Vec<Option<i32>> = [
+            ToolError::NotFound("missing".to_string()),
+            ToolError::PermissionDenied("denied".to_string()),
+            ToolError::InvalidArgs("bad".to_string()),
+            ToolError::Timeout,
+            ToolError::Cancelled,
+            ToolError::Execution(anyhow!("boom")),
+        ]
+        .into_iter()
+        .map(|error| error_exit_code(&anyhow::Error::from(error)))
+        .collect();
+        assert_eq!(
+            statuses,
+            vec![Some(127), Some(77), Some(64), Some(124), Some(130), Some(1)]
+        );
+        // `?` through helpers adds context but keeps the status.
+        let wrapped = anyhow::Error::from(ToolError::Timeout).context("tool run failed");
+        assert_eq!(error_exit_code(&wrapped), Some(124));
+    }
+
+    #[test]
+    fn cancelled_one_shot_turn_exits_with_sigint_status() {
+        let cancelled =
+            turn_interrupt::ensure_not_cancelled(Some(turn_interrupt::CANCELLED_STOP_REASON))
+                .map_err(anyhow::Error::from)
+                .expect_err("cancelled turn should fail");
+        assert_eq!(error_exit_code(&cancelled), Some(130));
+        assert_eq!(error_exit_code(&anyhow!("model unreachable")), None);
+    }
+
     #[test]
     fn build_request_overrides_sets_default_max_rounds_when_missing() {
         let mut config = Config::default();
@@ -7551,7 +7672,8 @@ mod tests {
         config.llm.models.insert(model_name.to_string(), model);
 
         let overrides =
-            build_request_overrides(&config, None, None, None).expect("overrides expected");
+            build_request_overrides(&config, None, None, None, &SamplingOverrides::default())
+                .expect("overrides expected");
         assert_eq!(
             overrides["llm"]["models"][model_name]["max_rounds"],
             json!(8)
@@ -7573,7 +7695,8 @@ mod tests {
         config.llm.models.insert(model_name.to_string(), model);
 
         let overrides =
-            build_request_overrides(&config, None, None, None).expect("overrides expected");
+            build_request_overrides(&config, None, None, None, &SamplingOverrides::default())
+                .expect("overrides expected");
         assert_eq!(
             overrides["llm"]["models"][model_name]["max_rounds"],
             json!(CLI_MIN_MAX_ROUNDS)
@@ -7594,16 +7717,66 @@ mod tests {
         model.max_rounds = Some(12);
         config.llm.models.insert(model_name.to_string(), model);
 
-        let overrides =
-            build_request_overrides(&config, None, Some(ToolCallModeArg::FunctionCall), None)
-                .expect("overrides expected");
+        let overrides = build_request_overrides(
+            &config,
+            None,
+            Some(ToolCallModeArg::FunctionCall),
+            None,
+            &SamplingOverrides::default(),
+        )
+        .expect("overrides expected");
         assert_eq!(
             overrides["llm"]["models"][model_name]["tool_call_mode"],
             json!("function_call")
         );
         assert!(overrides["llm"]["models"][model_name]["max_rounds"].is_null());
 
-        assert!(build_request_overrides(&config, None, None, None).is_none());
+        assert!(
+            build_request_overrides(&config, None, None, None, &SamplingOverrides::default())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn build_request_overrides_scopes_sampling_to_the_active_model() {
+        let mut config = Config::default();
+        config.llm.default = "demo".to_string();
+        for name in ["demo", "other"] {
+            let mut model = build_cli_llm_model_config(
+                "openai_compatible",
+                "https://example.com/v1",
+                "test-key",
+                name,
+            );
+            model.max_rounds = Some(12);
+            config.llm.models.insert(name.to_string(), model);
+        }
+        let sampling = SamplingOverrides {
+            temperature: Some(0.2),
+            top_p: Some(0.9),
+            stop: vec!["END".to_string()],
+            response_format: Some(json!({ "type": "json_object" })),
+        };
+
+        let overrides = build_request_overrides(&config, Some("other"), None, None, &sampling)
+            .expect("overrides expected");
+        let models = overrides["llm"]["models"].as_object().expect("models");
+        assert_eq!(models.keys().collect::<Vec<_>>(), vec!["other"]);
+        assert_eq!(models["other"]["temperature"], json!(0.2f32));
+        assert_eq!(models["other"]["top_p"], json!(0.9f32));
+        assert_eq!(models["other"]["stop"], json!(["END"]));
+        assert_eq!(
+            models["other"]["response_format"],
+            json!({ "type": "json_object" })
+        );
+
+        let defaults = build_request_overrides(&config, None, None, None, &sampling)
+            .expect("overrides expected");
+        assert_eq!(
+            defaults["llm"]["models"]["demo"]["temperature"],
+            json!(0.2f32)
+        );
+        assert!(defaults["llm"]["models"]["other"].is_null());
     }
 
     #[test]
@@ -0,0 +1,68 @@
+// 本地存储维护：`wunder-cli maintenance vacuum` 压缩 SQLite 数据库并报告回收空间。
+use crate::args::{GlobalArgs, MaintenanceCommand, MaintenanceSubcommand};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+
+pub(crate) async fn handle_maintenance(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: MaintenanceCommand,
+) -> Result<()> {
+    match command.command {
+        MaintenanceSubcommand::Vacuum => vacuum(runtime, global).await,
+    }
+}
+
+async fn vacuum(runtime: &CliRuntime, global: &GlobalArgs) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let storage = runtime.state.storage.clone();
+    let report = tokio::task::spawn_blocking(move || storage.compact_storage())
+        .await
+        .map_err(|err| anyhow!("storage vacuum task cancelled: {err}"))??;
+    if global.json {
+        println!("{}", serde_json::to_string(&report)?);
+        return Ok(());
+    }
+    if !report.supported {
+        println!(
+            "{}",
+            locale::tr(
+                language.as_str(),
+                "当前存储后端无需本地压缩",
+                "this storage backend does not need local compaction",
+            )
+        );
+        if let Some(note) = report.note.as_deref() {
+            println!("{note}");
+        }
+        return Ok(());
+    }
+    let (before, after, reclaimed) = (
+        format_bytes(report.bytes_before),
+        format_bytes(report.bytes_after),
+        format_bytes(report.reclaimed_bytes),
+    );
+    if locale::is_zh_language(language.as_str()) {
+        println!(
+            "{} 已压缩：{before} -> {after}，回收 {reclaimed}",
+            report.backend
+        );
+    } else {
+        println!(
+            "{} compacted: {before} -> {after}, reclaimed {reclaimed}",
+            report.backend
+        );
+    }
+    Ok(())
+}
+
+fn format_bytes(value: u64) -> String {
+    if value < 1024 {
+        return format!("{value} B");
+    }
+    if value < 1024 * 1024 {
+        return format!("{:.1} KB", value as f64 / 1024.0);
+    }
+    format!("{:.1} MB", value as f64 / (1024.0 * 1024.0))
+}
@@ -0,0 +1,652 @@
+// doctor --network：并发探测默认模型、已启用的 MCP 连接器与 A2A 服务，逐项报告可达性/状态码/耗时并汇总通过与失败数量。
+use crate::args::GlobalArgs;
+use crate::runtime::CliRuntime;
+use anyhow::Result;
+use futures::future::join_all;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use wunder_server::a2a_agent_card::build_a2a_agent_card_urls;
+use wunder_server::config::{A2aServiceConfig, LlmModelConfig};
+use wunder_server::llm::{
+    build_model_auth_headers, build_openai_model_resource_endpoint, classify_llm_error_response,
+    is_openai_compatible_provider, resolve_model_base_url,
+};
+use wunder_server::turn_notifier::preview_answer;
+use wunder_server::user_tools::UserMcpServer;
+
+/// Streamable HTTP servers only answer POSTs that accept both JSON and SSE replies.
+const MCP_POST_ACCEPT: &str = "application/json, text/event-stream";
+const MCP_PROTOCOL_VERSION: &str = "2025-03-26";
+const MCP_SESSION_HEADER: &str = "mcp-session-id";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const DETAIL_MAX_CHARS: usize = 160;
+
+/// Outcome of the MCP connectivity probe shared by `/apps test` and `doctor --network`.
+pub(crate) enum McpProbeOutcome {
+    /// The GET request got an answer.
+    Get(StatusCode),
+    /// GET was refused (400/405/406) on an HTTP transport and the JSON-RPC `initialize` POST
+    /// got an answer.
+    Post { get: StatusCode, post: StatusCode },
+    /// GET was refused but the follow-up POST failed.
+    PostFailed {
+        get: StatusCode,
+        err: reqwest::Error,
+    },
+    /// The GET request failed (connect error, timeout, TLS ...).
+    Failed(reqwest::Error),
+}
+
+fn resolve_mcp_auth_header(server: &UserMcpServer) -> Option<(String, String)> {
+    let Value::Object(map) = server.auth.as_ref()? else {
+        return None;
+    };
+
+    if let Some(value) = map
+        .get("bearer_token")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        return Some(("Authorization".to_string(), format!("Bearer {value}")));
+    }
+    if let Some(value) = map
+        .get("token")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        return Some(("Authorization".to_string(), format!("Bearer {value}")));
+    }
+    map.get("api_key")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| ("x-api-key".to_string(), value.to_string()))
+}
+
+/// JSON-RPC `initialize` request, the only call a streamable HTTP server accepts before a
+/// session exists.
+fn mcp_initialize_body() -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": "health",
+        "method": "initialize",
+        "params": {
+            "protocolVersion": MCP_PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "wunder-cli", "version": env!("CARGO_PKG_VERSION") },
+        },
+    })
+    .to_string()
+}
+
+/// GETs the endpoint as an SSE client would. Streamable HTTP servers refuse that GET (405, or
+/// 400/406 without a session), so those get a real `initialize` POST instead; a session the
+/// server opens for it is closed again right away.
+pub(crate) async fn probe_mcp_server(client: &Client, server: &UserMcpServer) -> McpProbeOutcome {
+    let auth_header = resolve_mcp_auth_header(server);
+    let transport = server.transport.trim().to_ascii_lowercase();
+    let mut request = client
+        .get(server.endpoint.trim())
+        .header("accept", "text/event-stream");
+    if let Some((name, value)) = auth_header.as_ref() {
+        request = request.header(name, value);
+    }
+    let code = match request.send().await {
+        Ok(response) => response.status(),
+        Err(err) => return McpProbeOutcome::Failed(err),
+    };
+    let post_fallback =
+        transport.contains("streamable") || transport.contains("http") || transport.is_empty();
+    let get_refused = matches!(
+        code,
+        StatusCode::METHOD_NOT_ALLOWED | StatusCode::BAD_REQUEST | StatusCode::NOT_ACCEPTABLE
+    );
+    if !get_refused || !post_fallback {
+        return McpProbeOutcome::Get(code);
+    }
+    let mut post = client
+        .post(server.endpoint.trim())
+        .header("content-type", "application/json")
+        .header("accept", MCP_POST_ACCEPT)
+        .body(mcp_initialize_body());
+    if let Some((name, value)) = auth_header.as_ref() {
+        post = post.header(name, value);
+    }
+    let response = match post.send().await {
+        Ok(response) => response,
+        Err(err) => return McpProbeOutcome::PostFailed { get: code, err },
+    };
+    let post_code = response.status();
+    let session_id = response
+        .headers()
+        .get(MCP_SESSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    if let Some(session_id) = session_id {
+        let mut close = client
+            .delete(server.endpoint.trim())
+            .header(MCP_SESSION_HEADER, session_id);
+        if let Some((name, value)) = auth_header.as_ref() {
+            close = close.header(name, value);
+        }
+        let _ = close.send().await;
+    }
+    McpProbeOutcome::Post {
+        get: code,
+        post: post_code,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EndpointKind {
+    Model,
+    Mcp,
+    A2a,
+}
+
+impl EndpointKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Model => "model",
+            Self::Mcp => "mcp",
+            Self::A2a => "a2a",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EndpointProbe {
+    kind: EndpointKind,
+    name: String,
+    endpoint: String,
+    /// Whether any HTTP response came back.
+    reachable: bool,
+    status: Option<u16>,
+    latency_ms: u64,
+    detail: Option<String>,
+}
+
+impl EndpointProbe {
+    fn new(kind: EndpointKind, name: &str, endpoint: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            endpoint: endpoint.trim().to_string(),
+            reachable: false,
+            status: None,
+            latency_ms: 0,
+            detail: None,
+        }
+    }
+
+    fn misconfigured(mut self, detail: &str) -> Self {
+        self.detail = Some(detail.to_string());
+        self
+    }
+
+    fn answered(mut self, status: StatusCode, started: Instant) -> Self {
+        self.reachable = true;
+        self.status = Some(status.as_u16());
+        self.latency_ms = started.elapsed().as_millis() as u64;
+        self
+    }
+
+    fn unreachable(mut self, err: &reqwest::Error, timeout: Duration, started: Instant) -> Self {
+        self.latency_ms = started.elapsed().as_millis() as u64;
+        self.detail = Some(describe_transport_error(err, timeout));
+        self
+    }
+
+    /// Every probed endpoint is enabled and therefore required: anything but a 2xx fails.
+    fn passed(&self) -> bool {
+        self.reachable
+            && self
+                .status
+                .is_some_and(|status| (200..300).contains(&status))
+    }
+
+    fn to_value(&self) -> Value {
+        json!({
+            "kind": self.kind.as_str(),
+            "name": self.name,
+            "endpoint": self.endpoint,
+            "ok": self.passed(),
+            "reachable": self.reachable,
+            "status": self.status,
+            "latency_ms": self.latency_ms,
+            "detail": self.detail,
+        })
+    }
+}
+
+fn describe_transport_error(err: &reqwest::Error, timeout: Duration) -> String {
+    if err.is_timeout() {
+        return format!("timed out after {}s", timeout.as_secs_f64());
+    }
+    let message = err.to_string();
+    preview_answer(&message, DETAIL_MAX_CHARS)
+}
+
+/// Non-2xx model responses go through the provider error normalization so the report names
+/// the same category (`invalid_api_key`, `model_not_found` ...) a real turn would.
+fn model_failure_detail(status: StatusCode, body: &str) -> String {
+    let error = classify_llm_error_response(status.as_u16(), body);
+    let message = preview_answer(&error.message, DETAIL_MAX_CHARS);
+    format!("{}: {message}", error.kind.as_str())
+}
+
+/// `{base}/v1/models`, tolerating bases that already end in `/v1` or `/v1/messages`.
+fn anthropic_models_endpoint(base_url: &str) -> String {
+    let base = base_url.trim().trim_end_matches('/');
+    let base = base.strip_suffix("/messages").unwrap_or(base);
+    if base.ends_with("/v1") {
+        format!("{base}/models")
+    } else {
+        format!("{base}/v1/models")
+    }
+}
+
+fn build_model_request(
+    client: &Client,
+    config: &LlmModelConfig,
+) -> Option<(String, RequestBuilder)> {
+    let base_url = resolve_model_base_url(config)?;
+    let api_key = config.api_key.as_deref().unwrap_or_default().trim();
+    if is_openai_compatible_provider(config.provider.as_deref().unwrap_or_default()) {
+        let endpoint = build_openai_model_resource_endpoint(&base_url, "models")?;
+        let request = client
+            .get(endpoint.as_str())
+            .headers(build_model_auth_headers(api_key));
+        return Some((endpoint, request));
+    }
+    let endpoint = anthropic_models_endpoint(&base_url);
+    let request = client
+        .get(endpoint.as_str())
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION);
+    Some((endpoint, request))
+}
+
+async fn probe_model(
+    client: &Client,
+    timeout: Duration,
+    name: Option<&str>,
+    config: Option<&LlmModelConfig>,
+) -> EndpointProbe {
+    let Some(name) = name else {
+        return EndpointProbe::new(EndpointKind::Model, "-", "")
+            .misconfigured("no model configured");
+    };
+    let Some(config) = config else {
+        return EndpointProbe::new(EndpointKind::Model, name, "")
+            .misconfigured("model not found in config");
+    };
+    let Some((endpoint, request)) = build_model_request(client, config) else {
+        return EndpointProbe::new(EndpointKind::Model, name, "")
+            .misconfigured("base_url is not configured");
+    };
+    let probe = EndpointProbe::new(EndpointKind::Model, name, &endpoint);
+    let started = Instant::now();
+    match request.send().await {
+        Ok(response) => {
+            let status = response.status();
+            let mut probe = probe.answered(status, started);
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                probe.detail = Some(model_failure_detail(status, &body));
+            }
+            probe
+        }
+        Err(err) => probe.unreachable(&err, timeout, started),
+    }
+}
+
+async fn probe_mcp(client: &Client, timeout: Duration, server: &UserMcpServer) -> EndpointProbe {
+    let probe = EndpointProbe::new(EndpointKind::Mcp, &server.name, &server.endpoint);
+    if server.endpoint.trim().is_empty() {
+        return probe.misconfigured("endpoint is empty");
+    }
+    let started = Instant::now();
+    match probe_mcp_server(client, server).await {
+        McpProbeOutcome::Get(status) => probe.answered(status, started),
+        McpProbeOutcome::Post { get, post } => {
+            let mut probe = probe.answered(post, started);
+            probe.detail = Some(format!("probe=GET({})->initialize", get.as_u16()));
+            probe
+        }
+        McpProbeOutcome::PostFailed { get, err } => {
+            let mut probe = probe.unreachable(&err, timeout, started);
+            probe.reachable = true;
+            probe.status = Some(get.as_u16());
+            probe.detail = probe
+                .detail
+                .map(|detail| format!("GET={}, POST: {detail}", get.as_u16()));
+            probe
+        }
+        McpProbeOutcome::Failed(err) => probe.unreachable(&err, timeout, started),
+    }
+}
+
+/// Tries the same AgentCard candidates as the admin `/apps test` flow and passes on the first
+/// 2xx; otherwise the last answer (or transport error) is reported.
+async fn probe_a2a(
+    client: &Client,
+    timeout: Duration,
+    service: &A2aServiceConfig,
+) -> EndpointProbe {
+    let probe = EndpointProbe::new(EndpointKind::A2a, &service.name, &service.endpoint);
+    if !a2a_endpoint_is_http(&service.endpoint) {
+        return probe.misconfigured("endpoint is not an http(s) URL");
+    }
+    let Ok(card_urls) = build_a2a_agent_card_urls(&service.endpoint) else {
+        return probe.misconfigured("endpoint is not an http(s) URL");
+    };
+    let started = Instant::now();
+    let mut last = None;
+    for card_url in card_urls {
+        let mut request = client.get(card_url.as_str());
+        for (name, value) in &service.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(auth) = service.auth.as_ref() {
+            let text = |key: &str| {
+                auth.get(key)
+                    .and_then(|value| value.as_str())
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+            };
+            if let Some(token) = text("bearer_token").or_else(|| text("token")) {
+                request = request.bearer_auth(token);
+            } else if let Some(api_key) = text("api_key") {
+                request = request.header("x-api-key", api_key);
+            }
+        }
+        match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                let mut probe = probe.answered(response.status(), started);
+                probe.detail = Some(format!("card={card_url}"));
+                return probe;
+            }
+            Ok(response) => last = Some(Ok(response.status())),
+            // Keep an earlier HTTP answer over a later transport error.
+            Err(err) if !matches!(last, Some(Ok(_))) => last = Some(Err(err)),
+            Err(_) => {}
+        }
+    }
+    match last {
+        Some(Ok(status)) => probe.answered(status, started),
+        Some(Err(err)) => probe.unreachable(&err, timeout, started),
+        None => probe.misconfigured("no agent card URL to probe"),
+    }
+}
+
+/// A2A endpoints without a scheme are treated as `http://`, like the admin card lookup does.
+fn a2a_endpoint_is_http(endpoint: &str) -> bool {
+    let endpoint = endpoint.trim();
+    !endpoint.contains("://") || endpoint.starts_with("http://") || endpoint.starts_with("https://")
+}
+
+/// Results of `doctor --network`, one probe per endpoint.
+pub(crate) struct NetworkReport {
+    probes: Vec<EndpointProbe>,
+}
+
+impl NetworkReport {
+    pub(crate) fn passed(&self) -> usize {
+        self.probes.iter().filter(|probe| probe.passed()).count()
+    }
+
+    pub(crate) fn failed(&self) -> usize {
+        self.probes.len() - self.passed()
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        json!({
+            "ok": self.failed() == 0,
+            "passed": self.passed(),
+            "failed": self.failed(),
+            "endpoints": self.probes.iter().map(EndpointProbe::to_value).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Section header, one line per endpoint and the pass/fail summary.
+    pub(crate) fn text_lines(&self, is_zh: bool) -> Vec<String> {
+        let (passed, failed) = (self.passed(), self.failed());
+        let mut lines = vec![if is_zh { "网络连通性" } else { "network" }.to_string()];
+        lines.extend(
+            self.probes
+                .iter()
+                .map(|probe| format_probe_line(probe, is_zh)),
+        );
+        lines.push(if is_zh {
+            format!("- 汇总: 通过 {passed}，失败 {failed}")
+        } else {
+            format!("- summary: {passed} passed, {failed} failed")
+        });
+        lines
+    }
+}
+
+/// Probes the default model, every enabled MCP connector and every enabled A2A service in
+/// parallel, each bounded by `timeout`.
+pub(crate) async fn run_network_checks(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    timeout: Duration,
+) -> Result<NetworkReport> {
+    let client = Client::builder().timeout(timeout).build()?;
+    let config = runtime.state.config_store.get().await;
+    let model = runtime.resolve_model_name(global.model.as_deref()).await;
+    let mcp_servers = runtime
+        .state
+        .user_tool_store
+        .load_user_tools(&runtime.user_id)
+        .mcp_servers
+        .into_iter()
+        .filter(|server| server.enabled)
+        .collect::<Vec<_>>();
+    let a2a_services = config
+        .a2a
+        .services
+        .iter()
+        .filter(|service| service.enabled)
+        .collect::<Vec<_>>();
+
+    let (model_probe, mcp_probes, a2a_probes) = tokio::join!(
+        probe_model(
+            &client,
+            timeout,
+            model.as_deref(),
+            model.as_ref().and_then(|name| config.llm.models.get(name)),
+        ),
+        join_all(
+            mcp_servers
+                .iter()
+                .map(|server| probe_mcp(&client, timeout, server))
+        ),
+        join_all(
+            a2a_services
+                .iter()
+                .map(|service| probe_a2a(&client, timeout, service))
+        ),
+    );
+    let probes = std::iter::once(model_probe)
+        .chain(mcp_probes)
+        .chain(a2a_probes)
+        .collect();
+    Ok(NetworkReport { probes })
+}
+
+fn format_probe_line(probe: &EndpointProbe, is_zh: bool) -> String {
+    let status = match (probe.passed(), is_zh) {
+        (true, true) => "正常",
+        (true, false) => "ok",
+        (false, true) => "失败",
+        (false, false) => "fail",
+    };
+    let reach = match (probe.status, probe.reachable, is_zh) {
+        (Some(code), _, _) => code.to_string(),
+        (None, true, _) => "-".to_string(),
+        (None, false, true) => "不可达".to_string(),
+        (None, false, false) => "unreachable".to_string(),
+    };
+    let endpoint = if probe.endpoint.is_empty() {
+        "-"
+    } else {
+        probe.endpoint.as_str()
+    };
+    let mut line = format!(
+        "- [{status}] {} {} {endpoint} ({reach}, {}ms)",
+        probe.kind.as_str(),
+        probe.name,
+        probe.latency_ms
+    );
+    if let Some(detail) = probe.detail.as_deref() {
+        line.push_str(": ");
+        line.push_str(detail);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(status: Option<u16>, reachable: bool) -> EndpointProbe {
+        EndpointProbe {
+            kind: EndpointKind::Mcp,
+            name: "github".to_string(),
+            endpoint: "http://127.0.0.1:9000/mcp".to_string(),
+            reachable,
+            status,
+            latency_ms: 12,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn only_reachable_2xx_endpoints_pass() {
+        assert!(probe(Some(200), true).passed());
+        assert!(!probe(Some(401), true).passed());
+        assert!(!probe(Some(405), true).passed());
+        assert!(!probe(None, false).passed());
+        let report = NetworkReport {
+            probes: vec![probe(Some(200), true), probe(None, false)],
+        };
+        let value = report.to_value();
+        assert_eq!(value["ok"], false);
+        assert_eq!(
+            (value["passed"].as_u64(), value["failed"].as_u64()),
+            (Some(1), Some(1))
+        );
+        for endpoint in value["endpoints"].as_array().unwrap() {
+            assert!(endpoint["ok"].is_boolean() && endpoint["reachable"].is_boolean());
+        }
+        assert_eq!(
+            format_probe_line(&probe(Some(204), true), false),
+            "- [ok] mcp github http://127.0.0.1:9000/mcp (204, 12ms)"
+        );
+        let mut unreachable = probe(None, false);
+        unreachable.detail = Some("timed out after 5s".to_string());
+        assert_eq!(
+            format_probe_line(&unreachable, false),
+            "- [fail] mcp github http://127.0.0.1:9000/mcp (unreachable, 12ms): timed out after 5s"
+        );
+    }
+
+    /// Minimal streamable HTTP MCP endpoint: refuses GET, and only answers a POST `initialize`
+    /// that accepts both JSON and SSE, like spec-compliant servers do.
+    async fn serve_strict_mcp(listener: tokio::net::TcpListener) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = vec![0u8; 8192];
+            let mut len = 0;
+            loop {
+                let read = stream.read(&mut buf[len..]).await.unwrap_or(0);
+                len += read;
+                let text = String::from_utf8_lossy(&buf[..len]);
+                let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                    let content_length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                        })
+                        .unwrap_or(0);
+                    body.len() >= content_length
+                });
+                if read == 0 || complete {
+                    break;
+                }
+            }
+            let request = String::from_utf8_lossy(&buf[..len]).to_ascii_lowercase();
+            let (status, extra) = if request.starts_with("get ") {
+                ("405 Method Not Allowed", "")
+            } else if request.starts_with("delete ") {
+                ("200 OK", "")
+            } else if !request.contains("accept: application/json, text/event-stream") {
+                ("406 Not Acceptable", "")
+            } else if !request.contains("\"method\":\"initialize\"") {
+                ("400 Bad Request", "")
+            } else {
+                ("200 OK", "mcp-session-id: sess-1\r\n")
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\n{extra}content-length: 0\r\nconnection: close\r\n\r\n"
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn streamable_http_probe_sends_initialize_with_required_accept() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_task = tokio::spawn(serve_strict_mcp(listener));
+        let server = UserMcpServer {
+            name: "strict".to_string(),
+            endpoint: format!("http://{addr}/mcp"),
+            transport: "streamable-http".to_string(),
+            enabled: true,
+            ..UserMcpServer::default()
+        };
+        let client = Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        let probe = probe_mcp(&client, Duration::from_secs(5), &server).await;
+        assert!(probe.passed(), "{probe:?}");
+        assert_eq!(probe.status, Some(200));
+        assert_eq!(probe.detail.as_deref(), Some("probe=GET(405)->initialize"));
+        server_task.abort();
+    }
+
+    #[test]
+    fn model_failures_use_normalized_provider_kind() {
+        let body = r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error","code":"invalid_api_key"}}"#;
+        assert_eq!(
+            model_failure_detail(StatusCode::UNAUTHORIZED, body),
+            "invalid_api_key: Incorrect API key provided"
+        );
+    }
+
+    #[test]
+    fn probe_urls_are_derived_from_configured_endpoints() {
+        assert_eq!(
+            anthropic_models_endpoint("https://api.anthropic.com"),
+            "https://api.anthropic.com/v1/models"
+        );
+        assert_eq!(
+            anthropic_models_endpoint("https://proxy.test/v1/messages/"),
+            "https://proxy.test/v1/models"
+        );
+        assert!(a2a_endpoint_is_http("http://127.0.0.1:18000/a2a"));
+        assert!(a2a_endpoint_is_http("127.0.0.1:18000"));
+        assert!(!a2a_endpoint_is_http("ws://127.0.0.1:18000/a2a"));
+    }
+}
@@ -0,0 +1,146 @@
+// /system budget 提示词预算：按段统计当前系统提示词的 token 占用，帮助定位臃肿的技能与工具说明。
+use crate::args::GlobalArgs;
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use wunder_server::prompt_budget::PromptBudgetBreakdown;
+
+const BAR_WIDTH: usize = 20;
+
+/// Breakdown of the prompt `/system` shows, built with the same request overrides as a real
+/// turn. `AGENTS.md` guidance and the CLI personality instruction are part of the extra
+/// prompt, so they are reported as their own sections.
+pub(crate) async fn build_current_prompt_budget(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+) -> Result<PromptBudgetBreakdown> {
+    let runtime = runtime.clone();
+    let global = global.clone();
+    tokio::task::spawn(async move {
+        let config = runtime.state.config_store.get().await;
+        let model_name = runtime.resolve_model_name(global.model.as_deref()).await;
+        let request_overrides = crate::build_request_overrides(
+            &config,
+            model_name.as_deref(),
+            global.tool_call_mode,
+            global.approval_mode,
+            &crate::SamplingOverrides::from_global(&global),
+        );
+        let skills = runtime.state.skills.read().await.clone();
+        let user_tool_bindings =
+            runtime
+                .state
+                .user_tool_manager
+                .build_bindings(&config, &skills, &runtime.user_id);
+        let workspace_id = runtime
+            .state
+            .workspace
+            .scoped_user_id(&runtime.user_id, None);
+        let effective_prompt = crate::build_effective_agent_prompt(&runtime);
+        let mut breakdown = runtime
+            .state
+            .kernel
+            .orchestrator
+            .build_system_prompt_breakdown(
+                &config,
+                &[],
+                &skills,
+                Some(&user_tool_bindings),
+                &runtime.user_id,
+                None,
+                &workspace_id,
+                request_overrides.as_ref(),
+                model_name.as_deref(),
+                effective_prompt.as_deref(),
+                false,
+            )
+            .await;
+        if let Some(agents_md) = runtime.load_agents_md_prompt() {
+            let model = breakdown.model.clone();
+            breakdown.split_section("extra", "agents_md", &agents_md, model.as_deref());
+        }
+        if let Some(personality) = runtime
+            .load_personality_mode()
+            .and_then(|mode| crate::personality_instruction(mode.as_str()))
+        {
+            let model = breakdown.model.clone();
+            breakdown.split_section("extra", "personality", personality, model.as_deref());
+        }
+        breakdown
+    })
+    .await
+    .map_err(|err| anyhow!("build prompt budget task cancelled: {err}"))
+}
+
+pub(crate) fn prompt_budget_lines(
+    language: &str,
+    breakdown: &PromptBudgetBreakdown,
+) -> Vec<String> {
+    let is_zh = locale::is_zh_language(language);
+    let model = breakdown.model.as_deref().unwrap_or("-");
+    let mut lines = vec![if is_zh {
+        format!(
+            "提示词预算（模型 {model}，估算）：共 {} tokens / {} 字符",
+            breakdown.total_tokens, breakdown.total_chars
+        )
+    } else {
+        format!(
+            "prompt budget (model {model}, estimated): {} tokens / {} chars",
+            breakdown.total_tokens, breakdown.total_chars
+        )
+    }];
+    let total = breakdown.total_tokens.max(1) as f64;
+    let name_width = breakdown
+        .sections
+        .iter()
+        .map(|item| item.section.len())
+        .max()
+        .unwrap_or(0);
+    for item in &breakdown.sections {
+        let ratio = (item.tokens.max(0) as f64 / total).min(1.0);
+        let filled = (ratio * BAR_WIDTH as f64).round() as usize;
+        lines.push(format!(
+            "- {:<name_width$} [{}{}] {:>3.0}%  {} tokens",
+            item.section,
+            "#".repeat(filled),
+            "-".repeat(BAR_WIDTH - filled),
+            ratio * 100.0,
+            item.tokens,
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wunder_server::prompt_budget::PromptSectionBudget;
+
+    #[test]
+    fn lines_list_sections_with_share() {
+        let breakdown = PromptBudgetBreakdown {
+            model: Some("gpt-4o".to_string()),
+            total_tokens: 200,
+            total_chars: 800,
+            sections: vec![
+                PromptSectionBudget {
+                    section: "role".to_string(),
+                    tokens: 50,
+                    chars: 200,
+                },
+                PromptSectionBudget {
+                    section: "skills".to_string(),
+                    tokens: 150,
+                    chars: 600,
+                },
+            ],
+        };
+        let lines = prompt_budget_lines("en-US", &breakdown);
+        assert_eq!(
+            lines[0],
+            "prompt budget (model gpt-4o, estimated): 200 tokens / 800 chars"
+        );
+        assert!(lines[1].starts_with("- role   [#####---------------]  25%"));
+        assert!(lines[2].ends_with(" 75%  150 tokens"));
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use wunder_server::schemas::StreamEvent;
 
 use crate::command_session_display::{
@@ -25,7 +25,13 @@ pub struct FinalEvent {
 
 pub struct StreamRenderer {
     json: bool,
+    show_reasoning: bool,
+    show_citations: bool,
     line_open: bool,
+    reasoning_line_open: bool,
+    // Set once this round's reasoning was streamed so the round-end `reasoning` event
+    // does not print it a second time.
+    reasoning_streamed: bool,
     saw_delta: bool,
     saw_tool_activity: bool,
     last_visible_was_tool: bool,
@@ -36,6 +42,36 @@ pub struct StreamRenderer {
     command_terminal_status_printed: HashSet<String>,
 }
 
+/// Text carried by a stream event, split by the channel it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamText<'a> {
+    Answer(&'a str),
+    Reasoning(&'a str),
+    /// Round-end reasoning from `llm_output`; only shown when nothing was streamed for the round.
+    ReasoningFinal(&'a str),
+}
+
+fn route_stream_text<'a>(event_name: &str, payload: &'a Value) -> Vec<StreamText<'a>> {
+    let text = |key: &str| {
+        payload
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|value| !value.is_empty())
+    };
+    match event_name {
+        "llm_output_delta" => text("reasoning_delta")
+            .map(StreamText::Reasoning)
+            .into_iter()
+            .chain(text("delta").map(StreamText::Answer))
+            .collect(),
+        "llm_output" => text("reasoning")
+            .map(StreamText::ReasoningFinal)
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 #[derive(Debug, Default)]
 struct CommandLivePrintState {
     printed_chars: usize,
@@ -53,7 +89,11 @@ impl StreamRenderer {
     pub fn new(json: bool, language: &str) -> Self {
         Self {
             json,
+            show_reasoning: false,
+            show_citations: false,
             line_open: false,
+            reasoning_line_open: false,
+            reasoning_streamed: false,
             saw_delta: false,
             saw_tool_activity: false,
             last_visible_was_tool: false,
@@ -65,6 +105,18 @@ impl StreamRenderer {
         }
     }
 
+    /// Reasoning stays hidden unless enabled, matching the historical CLI output.
+    pub fn with_reasoning(mut self, show_reasoning: bool) -> Self {
+        self.show_reasoning = show_reasoning;
+        self
+    }
+
+    /// Knowledge sources from the final event are listed only when `--cite` asked for them.
+    pub fn with_citations(mut self, show_citations: bool) -> Self {
+        self.show_citations = show_citations;
+        self
+    }
+
     pub fn render_event(&mut self, event: &StreamEvent) -> Result<Option<FinalEvent>> {
         if self.json {
             println!("{}", serde_json::to_string(event)?);
@@ -74,17 +126,14 @@ impl StreamRenderer {
         let payload = event_payload(&event.data);
         match event.event.as_str() {
             "llm_output_delta" => {
-                if let Some(delta) = payload.get("delta").and_then(Value::as_str) {
-                    if !delta.is_empty() {
-                        print!("{delta}");
-                        io::stdout().flush().ok();
-                        self.line_open = true;
-                        self.saw_delta = true;
-                        self.last_visible_was_tool = false;
-                    }
+                for text in route_stream_text(event.event.as_str(), payload) {
+                    self.render_stream_text(text);
                 }
             }
             "llm_output" => {
+                for text in route_stream_text(event.event.as_str(), payload) {
+                    self.render_stream_text(text);
+                }
                 if !self.saw_delta {
                     if let Some(content) = payload.get("content").and_then(Value::as_str) {
                         if !content.is_empty() {
@@ -117,6 +166,14 @@ impl StreamRenderer {
                     println!("{}", format_tool_call_line(tool, args, repair));
                 }
             }
+            "tool_started" | "tool_finished" => {
+                if let Some(line) = format_tool_run_line(event.event.as_str(), payload) {
+                    self.ensure_newline();
+                    self.saw_tool_activity = true;
+                    self.last_visible_was_tool = true;
+                    println!("{line}");
+                }
+            }
             "command_session_start" => {
                 self.ensure_newline();
                 self.saw_tool_activity = true;
@@ -192,6 +249,18 @@ impl StreamRenderer {
                 self.last_visible_was_tool = true;
                 let _ = render_question_panel_lines(payload);
             }
+            "llm_stream_retry"
+                if payload.get("reset_output").and_then(Value::as_bool) == Some(true) =>
+            {
+                // The interrupted partial answer is re-sent from scratch; mark the break.
+                self.ensure_newline();
+                self.saw_delta = false;
+                if self.is_zh {
+                    eprintln!("[retry] 流式输出中断，正在重新请求");
+                } else {
+                    eprintln!("[retry] stream interrupted, re-sending the request");
+                }
+            }
             "error" => {
                 self.ensure_newline();
                 self.last_visible_was_tool = false;
@@ -211,6 +280,11 @@ impl StreamRenderer {
                 if !self.saw_delta && !final_event.answer.is_empty() {
                     println!("{}", final_event.answer);
                 }
+                if self.show_citations {
+                    for line in citation_lines(self.is_zh, payload.get("sources")) {
+                        println!("{line}");
+                    }
+                }
                 self.last_visible_was_tool = false;
                 return Ok(Some(final_event));
             }
@@ -219,17 +293,68 @@ impl StreamRenderer {
         Ok(None)
     }
 
+    fn render_stream_text(&mut self, text: StreamText<'_>) {
+        match text {
+            StreamText::Answer(delta) => {
+                self.ensure_reasoning_newline();
+                print!("{delta}");
+                io::stdout().flush().ok();
+                self.line_open = true;
+                self.saw_delta = true;
+                self.last_visible_was_tool = false;
+            }
+            StreamText::Reasoning(delta) => {
+                self.reasoning_streamed = true;
+                self.print_reasoning(delta);
+            }
+            StreamText::ReasoningFinal(content) => {
+                if !std::mem::take(&mut self.reasoning_streamed) {
+                    self.print_reasoning(content);
+                    self.ensure_reasoning_newline();
+                }
+            }
+        }
+    }
+
     pub fn finish(&mut self) {
+        self.ensure_reasoning_newline();
         self.ensure_newline();
     }
 
     fn ensure_newline(&mut self) {
+        self.ensure_reasoning_newline();
         if self.line_open {
             println!();
             self.line_open = false;
         }
     }
 
+    // Reasoning goes to stderr so piping stdout still captures only the answer.
+    fn print_reasoning(&mut self, text: &str) {
+        if !self.show_reasoning {
+            return;
+        }
+        if self.line_open {
+            println!();
+            self.line_open = false;
+        }
+        let mut stderr = io::stderr();
+        if stderr.is_terminal() {
+            let _ = write!(stderr, "\x1b[2m{text}\x1b[0m");
+        } else {
+            let _ = write!(stderr, "{text}");
+        }
+        stderr.flush().ok();
+        self.reasoning_line_open = true;
+    }
+
+    fn ensure_reasoning_newline(&mut self) {
+        if self.reasoning_line_open {
+            eprintln!();
+            self.reasoning_line_open = false;
+        }
+    }
+
     fn render_command_session_start(&mut self, update: &CommandSessionUpdate) {
         let primary = self.command_sessions.primary_for_view(&update.view);
         if !self.command_start_printed.insert(primary) {
@@ -699,6 +824,35 @@ fn parse_final(event: &StreamEvent) -> Option<FinalEvent> {
     })
 }
 
+fn citation_lines(is_zh: bool, sources: Option<&Value>) -> Vec<String> {
+    let Some(sources) = sources
+        .and_then(Value::as_array)
+        .filter(|items| !items.is_empty())
+    else {
+        return Vec::new();
+    };
+    let mut lines = vec![if is_zh { "引用来源：" } else { "sources:" }.to_string()];
+    for (index, source) in sources.iter().enumerate() {
+        let text = |key: &str| source.get(key).and_then(Value::as_str).unwrap_or("");
+        let mut line = format!("[{}] {}", index + 1, text("knowledge_base"));
+        let document = match text("document") {
+            "" => text("doc_id"),
+            document => document,
+        };
+        if !document.is_empty() {
+            line.push_str(&format!(" / {document}"));
+        }
+        if let Some(chunk) = source.get("chunk_index").and_then(Value::as_i64) {
+            line.push_str(&format!(" #{chunk}"));
+        }
+        if let Some(score) = source.get("score").and_then(Value::as_f64) {
+            line.push_str(&format!(" ({score:.2})"));
+        }
+        lines.push(line);
+    }
+    lines
+}
+
 fn is_apply_patch_tool_name(tool: &str) -> bool {
     let normalized = tool.trim().to_ascii_lowercase();
     normalized == "apply_patch" || tool.contains("应用补丁")
@@ -1209,6 +1363,35 @@ fn append_tail(target: &mut String, text: &str, max_chars: usize) {
     *target = target.chars().skip(start).collect();
 }
 
+/// Execution timing of one call. Starts are shown only for parallel reads, where they reveal
+/// which calls overlap; exclusive calls start right after their `tool_call` line anyway.
+pub(crate) fn format_tool_run_line(event: &str, payload: &Value) -> Option<String> {
+    let tool = payload.get("tool").and_then(Value::as_str)?;
+    let parallel = payload.get("mode").and_then(Value::as_str) == Some("parallel_read");
+    let tool_is_zh = looks_like_zh(tool);
+    match event {
+        "tool_started" if parallel => Some(if tool_is_zh {
+            format!("• 并行执行 {tool}")
+        } else {
+            format!("• Running {tool} in parallel")
+        }),
+        "tool_finished" => {
+            let duration_ms = payload
+                .get("duration_ms")
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+            let ok = payload.get("ok").and_then(Value::as_bool).unwrap_or(true);
+            Some(match (tool_is_zh, ok) {
+                (true, true) => format!("  └ {tool} 用时 {duration_ms}ms"),
+                (true, false) => format!("  └ {tool} 失败，用时 {duration_ms}ms"),
+                (false, true) => format!("  └ {tool} finished in {duration_ms}ms"),
+                (false, false) => format!("  └ {tool} failed after {duration_ms}ms"),
+            })
+        }
+        _ => None,
+    }
+}
+
 fn format_generic_tool_result_lines(tool: &str, payload: &Value) -> Vec<String> {
     let result = extract_tool_result_object(payload);
     let ok = result.get("ok").and_then(Value::as_bool);
@@ -1332,6 +1515,69 @@ fn compact_json(value: &Value) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn citation_lines_number_sources_with_chunk_and_score() {
+        let sources = serde_json::json!([
+            { "knowledge_base": "manuals", "doc_id": "doc-1", "document": "guide.md", "chunk_index": 3, "score": 0.814 },
+            { "knowledge_base": "faq", "document": "billing", "section_path": ["Refunds"] },
+        ]);
+        assert_eq!(
+            citation_lines(false, Some(&sources)),
+            vec![
+                "sources:".to_string(),
+                "[1] manuals / guide.md #3 (0.81)".to_string(),
+                "[2] faq / billing".to_string(),
+            ]
+        );
+        assert!(citation_lines(true, Some(&serde_json::json!([]))).is_empty());
+        assert!(citation_lines(true, None).is_empty());
+    }
+
+    #[test]
+    fn route_stream_text_separates_reasoning_from_answer_deltas() {
+        let delta = serde_json::json!({ "delta": "answer", "reasoning_delta": "thinking" });
+        assert_eq!(
+            route_stream_text("llm_output_delta", &delta),
+            vec![
+                StreamText::Reasoning("thinking"),
+                StreamText::Answer("answer")
+            ]
+        );
+        let reasoning_only = serde_json::json!({ "delta": "", "reasoning_delta": "thinking" });
+        assert_eq!(
+            route_stream_text("llm_output_delta", &reasoning_only),
+            vec![StreamText::Reasoning("thinking")]
+        );
+        let round_end = serde_json::json!({ "content": "answer", "reasoning": "thinking" });
+        assert_eq!(
+            route_stream_text("llm_output", &round_end),
+            vec![StreamText::ReasoningFinal("thinking")]
+        );
+        let summary = serde_json::json!({ "chars": 8, "preview": "thinking" });
+        assert!(route_stream_text("reasoning", &summary).is_empty());
+    }
+
+    #[test]
+    fn tool_run_lines_show_parallel_starts_and_every_finish() {
+        let parallel = serde_json::json!({ "tool": "read_file", "mode": "parallel_read" });
+        assert_eq!(
+            format_tool_run_line("tool_started", &parallel).as_deref(),
+            Some("• Running read_file in parallel")
+        );
+        let exclusive = serde_json::json!({ "tool": "write_file", "mode": "exclusive_write" });
+        assert!(format_tool_run_line("tool_started", &exclusive).is_none());
+        let finished = serde_json::json!({
+            "tool": "write_file",
+            "mode": "exclusive_write",
+            "ok": false,
+            "duration_ms": 42,
+        });
+        assert_eq!(
+            format_tool_run_line("tool_finished", &finished).as_deref(),
+            Some("  └ write_file failed after 42ms")
+        );
+    }
+
     #[test]
     fn apply_patch_result_lines_include_change_markers() {
         let payload = serde_json::json!({
@@ -0,0 +1,78 @@
+// 结构化输出参数：读取 --json-schema 指定的 JSON Schema 文件，生成写入模型 config_overrides 的 response_format。
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// `--json-mode`: any valid JSON object.
+pub(crate) fn json_object_response_format() -> Value {
+    json!({ "type": "json_object" })
+}
+
+/// Clap value parser for `--json-schema <FILE>`. The file holds either a bare JSON Schema or a
+/// full `json_schema` body (`{name, schema, strict}`); a bare schema is named after the file.
+pub(crate) fn parse_json_schema_file(value: &str) -> Result<Value, String> {
+    let path = Path::new(value);
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| format!("read schema file failed: {}: {err}", path.display()))?;
+    let parsed: Value = serde_json::from_str(&text)
+        .map_err(|err| format!("schema file is not valid JSON: {}: {err}", path.display()))?;
+    schema_response_format(parsed, path)
+}
+
+fn schema_response_format(parsed: Value, path: &Path) -> Result<Value, String> {
+    if !parsed.is_object() {
+        return Err(format!(
+            "schema file must contain a JSON object: {}",
+            path.display()
+        ));
+    }
+    if parsed.get("schema").is_some_and(Value::is_object) {
+        return Ok(json!({ "type": "json_schema", "json_schema": parsed }));
+    }
+    Ok(json!({
+        "type": "json_schema",
+        "json_schema": { "name": schema_name(path), "schema": parsed },
+    }))
+}
+
+/// Schema names only allow letters, digits, `_` and `-`.
+fn schema_name(path: &Path) -> String {
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '_' || ch == '-' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect::<String>();
+    if name.is_empty() {
+        "response".to_string()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_schema_is_wrapped_and_named_after_the_file() {
+        let schema = json!({ "type": "object", "required": ["title"] });
+        let format = schema_response_format(schema.clone(), Path::new("/tmp/invoice v2.json"))
+            .expect("format");
+        assert_eq!(format["json_schema"]["name"], "invoice_v2");
+        assert_eq!(format["json_schema"]["schema"], schema);
+
+        let body = json!({ "name": "item", "schema": schema, "strict": true });
+        let format = schema_response_format(body.clone(), Path::new("x.json")).expect("format");
+        assert_eq!(format["json_schema"], body);
+
+        assert!(schema_response_format(json!([1]), Path::new("x.json")).is_err());
+    }
+}
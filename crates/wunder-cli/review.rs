@@ -0,0 +1,617 @@
+// 结构化代码审查：为 /review 与 `wunder-cli review` 追加机器可解析的严重程度标签要求，解析模型输出的问题等级，可把提示词与回复写入文件，并按 --fail-on 阈值决定退出码。
+use crate::args::{GlobalArgs, ReviewCommand};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SEVERITY_TAG_PREFIX: &str = "[severity:";
+/// Level the model uses to state that it found nothing.
+const NO_ISSUES_LEVEL: &str = "none";
+/// Stop reasons of a turn that ran to its answer; anything else (cancelled, stream
+/// interrupted, round limit, ...) leaves the review unfinished.
+const COMPLETED_STOP_REASONS: [&str; 2] = ["model_response", "final_tool"];
+/// Changed files looked up for `--with-history`; each one costs a `git log` call.
+const MAX_HISTORY_FILES: usize = 60;
+const HISTORY_HEADER: &str = "## Recent history (git log -1 per changed file)\n";
+
+/// Issue severity as tagged by the model in structured reviews, lowest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum ReviewSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl ReviewSeverity {
+    const ALL: [Self; 4] = [Self::Critical, Self::High, Self::Medium, Self::Low];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "low" | "minor" => Some(Self::Low),
+            "medium" | "moderate" => Some(Self::Medium),
+            "high" | "major" => Some(Self::High),
+            "critical" | "blocker" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReviewFinding {
+    pub severity: ReviewSeverity,
+    pub text: String,
+}
+
+/// Parsed outcome of a review turn.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ReviewReport {
+    pub findings: Vec<ReviewFinding>,
+    /// The answer carried a `[severity:none]` line.
+    pub declared_clean: bool,
+    pub stop_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ReviewOptions {
+    pub focus: String,
+    /// Ask for `[severity:<level>]` tags and summarize them after the answer.
+    pub structured: bool,
+    /// Threshold that makes a non-interactive review fail; implies `structured`.
+    pub fail_on: Option<ReviewSeverity>,
+    /// File receiving the prompt and the response.
+    pub output: Option<PathBuf>,
+    /// Add the last commit touching each changed file to the prompt.
+    pub with_history: bool,
+}
+
+impl ReviewOptions {
+    fn structured(&self) -> bool {
+        self.structured || self.fail_on.is_some()
+    }
+}
+
+impl From<ReviewCommand> for ReviewOptions {
+    fn from(command: ReviewCommand) -> Self {
+        Self {
+            focus: command.focus.join(" "),
+            structured: command.structured,
+            fail_on: command.fail_on,
+            output: command.output,
+            with_history: command.with_history,
+        }
+    }
+}
+
+/// Splits `/review [--structured] [--with-history] [--fail-on <level>] [--output <file>] [focus...]`.
+pub(crate) fn parse_review_args(args: &str) -> std::result::Result<ReviewOptions, String> {
+    let mut options = ReviewOptions::default();
+    let mut focus = Vec::new();
+    let mut tokens = args.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let (flag, inline) = match token.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (token, None),
+        };
+        match flag {
+            "--structured" if inline.is_none() => options.structured = true,
+            "--with-history" if inline.is_none() => options.with_history = true,
+            "--output" | "-o" => {
+                let value = inline
+                    .or_else(|| tokens.next())
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| "--output requires a file path".to_string())?;
+                options.output = Some(PathBuf::from(value));
+            }
+            "--fail-on" => {
+                let value = inline.or_else(|| tokens.next()).unwrap_or_default();
+                let severity = ReviewSeverity::parse(value).ok_or_else(|| {
+                    format!("--fail-on expects low, medium, high or critical, got `{value}`")
+                })?;
+                options.fail_on = Some(severity);
+            }
+            _ => focus.push(token),
+        }
+    }
+    options.focus = focus.join(" ");
+    Ok(options)
+}
+
+/// Appended to the review prompt in structured mode. Tags stay in English whatever the
+/// answer language so they parse the same way.
+fn structured_review_instructions() -> &'static str {
+    r#"
+## Output format (machine-parsed)
+- Put every issue on its own line starting with one severity tag: `[severity:critical]`, `[severity:high]`, `[severity:medium]` or `[severity:low]`, followed by `path:line` and the problem.
+- critical/high: bugs, security holes, data loss or behavior regressions that must be fixed before merging; medium: likely problems or missing error handling; low: style and minor improvements.
+- Use the tags only for issues. If there are no issues, write `[severity:none] no issues found`.
+"#
+}
+
+/// The review prompt for `options`, with the structured output contract when requested.
+pub(crate) fn build_review_prompt(
+    repo_root: &Path,
+    options: &ReviewOptions,
+    language: &str,
+) -> Result<String> {
+    let mut prompt = crate::build_review_prompt_with_language(
+        repo_root,
+        options.focus.as_str(),
+        language,
+        options.with_history,
+    )?;
+    if options.structured() {
+        prompt.push_str(structured_review_instructions());
+    }
+    Ok(prompt)
+}
+
+/// `## Recent history` block with author, date and subject of the last commit per changed
+/// file, cut to whole lines within `budget` chars. Empty when nothing fits.
+pub(crate) fn file_history_section(repo_root: &Path, budget: usize) -> String {
+    let Ok(files) = crate::git_changed_files(repo_root) else {
+        return String::new();
+    };
+    let mut rows = files
+        .iter()
+        .take(MAX_HISTORY_FILES)
+        .map(|path| {
+            let last = crate::run_git(
+                repo_root,
+                [
+                    "log",
+                    "-1",
+                    "--date=short",
+                    "--format=%an, %ad: %s",
+                    "--",
+                    path.as_str(),
+                ],
+            )
+            .map(|output| output.trim().to_string())
+            .filter(|output| !output.is_empty())
+            .unwrap_or_else(|| "(no commits yet)".to_string());
+            format!("- {path}: {last}\n")
+        })
+        .collect::<Vec<_>>();
+    if files.len() > MAX_HISTORY_FILES {
+        rows.push(format!(
+            "- ... ({} more files)\n",
+            files.len() - MAX_HISTORY_FILES
+        ));
+    }
+
+    let mut section = HISTORY_HEADER.to_string();
+    let mut used = section.chars().count() + 1;
+    let mut included = 0;
+    for row in &rows {
+        let len = row.chars().count();
+        if used + len > budget {
+            break;
+        }
+        section.push_str(row);
+        used += len;
+        included += 1;
+    }
+    if included == 0 {
+        return String::new();
+    }
+    section.push('\n');
+    section
+}
+
+/// `(level, text)` of a line starting with `[severity:<level>]` after an optional list marker.
+fn severity_tag(line: &str) -> Option<(&str, &str)> {
+    let line = line
+        .trim()
+        .trim_start_matches(['-', '*', '+'])
+        .trim_start_matches(|ch: char| ch.is_ascii_digit())
+        .trim_start_matches(['.', ')'])
+        .trim_start()
+        .trim_start_matches("**");
+    let head = line.get(..SEVERITY_TAG_PREFIX.len())?;
+    if !head.eq_ignore_ascii_case(SEVERITY_TAG_PREFIX) {
+        return None;
+    }
+    let (level, text) = line[SEVERITY_TAG_PREFIX.len()..].split_once(']')?;
+    Some((level.trim(), text.trim().trim_start_matches("**").trim()))
+}
+
+/// Issues tagged `[severity:<level>]` at the start of a line (after an optional list marker).
+/// Untagged lines and unknown levels such as `none` are ignored.
+pub(crate) fn parse_review_findings(answer: &str) -> Vec<ReviewFinding> {
+    answer
+        .lines()
+        .filter_map(severity_tag)
+        .filter_map(|(level, text)| {
+            Some(ReviewFinding {
+                severity: ReviewSeverity::parse(level)?,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Whether the answer states `[severity:none]` on a line of its own.
+fn declares_no_issues(answer: &str) -> bool {
+    answer
+        .lines()
+        .filter_map(severity_tag)
+        .any(|(level, _)| level.eq_ignore_ascii_case(NO_ISSUES_LEVEL))
+}
+
+fn count_at_or_above(findings: &[ReviewFinding], threshold: ReviewSeverity) -> usize {
+    findings
+        .iter()
+        .filter(|finding| finding.severity >= threshold)
+        .count()
+}
+
+fn summary_line(findings: &[ReviewFinding], is_zh: bool) -> String {
+    let counts = ReviewSeverity::ALL
+        .iter()
+        .map(|severity| {
+            let count = findings
+                .iter()
+                .filter(|finding| finding.severity == *severity)
+                .count();
+            format!("{}={count}", severity.as_str())
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    if is_zh {
+        format!("[review] 问题统计: {counts}")
+    } else {
+        format!("[review] findings: {counts}")
+    }
+}
+
+fn write_review_file(path: &Path, prompt: &str, answer: &str) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let content = format!("# Review\n\n## Prompt\n\n{prompt}\n\n## Response\n\n{answer}\n");
+    fs::write(path, content).with_context(|| format!("write review to {}", path.display()))
+}
+
+/// Why a review gated by `--fail-on` fails: the turn did not complete, the answer has no
+/// verdict (neither a severity tag nor `[severity:none]`), or a finding reaches `threshold`.
+fn fail_on_message(
+    report: &ReviewReport,
+    threshold: ReviewSeverity,
+    is_zh: bool,
+) -> Option<String> {
+    if let Some(reason) = report
+        .stop_reason
+        .as_deref()
+        .filter(|reason| !COMPLETED_STOP_REASONS.contains(reason))
+    {
+        return Some(if is_zh {
+            format!("review 未正常完成（stop_reason={reason}）")
+        } else {
+            format!("review did not complete (stop_reason={reason})")
+        });
+    }
+    if report.findings.is_empty() && !report.declared_clean {
+        return Some(if is_zh {
+            "review 回复中没有严重程度标签，也没有 [severity:none] 标记".to_string()
+        } else {
+            "review answer has no severity tags and no [severity:none] marker".to_string()
+        });
+    }
+    blocking_message(&report.findings, threshold, is_zh)
+}
+
+fn blocking_message(
+    findings: &[ReviewFinding],
+    threshold: ReviewSeverity,
+    is_zh: bool,
+) -> Option<String> {
+    let blocking = count_at_or_above(findings, threshold);
+    if blocking == 0 {
+        return None;
+    }
+    Some(if is_zh {
+        format!(
+            "review 发现 {blocking} 个严重程度不低于 {} 的问题",
+            threshold.as_str()
+        )
+    } else {
+        format!(
+            "review found {blocking} issue(s) at or above {} severity",
+            threshold.as_str()
+        )
+    })
+}
+
+/// Runs one review turn with a prompt from [`build_review_prompt`], writes the output file
+/// and prints the severity summary. Returns the parsed report (no findings in conversational
+/// mode).
+pub(crate) async fn run_review(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    session_id: &str,
+    agent_id_override: Option<&str>,
+    options: &ReviewOptions,
+    prompt: &str,
+) -> Result<ReviewReport> {
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    let final_event = Box::pin(crate::run_prompt_once(
+        runtime,
+        global,
+        prompt,
+        session_id,
+        agent_id_override,
+        None,
+        false,
+    ))
+    .await?;
+
+    if let Some(output) = options.output.as_ref() {
+        let path = runtime.launch_dir.join(output);
+        write_review_file(&path, prompt, &final_event.answer)?;
+        eprintln!(
+            "{}",
+            if is_zh {
+                format!("[review] 已写入 {}", path.display())
+            } else {
+                format!("[review] written to {}", path.display())
+            }
+        );
+    }
+    if !options.structured() {
+        return Ok(ReviewReport {
+            stop_reason: final_event.stop_reason,
+            ..ReviewReport::default()
+        });
+    }
+    let findings = parse_review_findings(&final_event.answer);
+    eprintln!("{}", summary_line(&findings, is_zh));
+    Ok(ReviewReport {
+        findings,
+        declared_clean: declares_no_issues(&final_event.answer),
+        stop_reason: final_event.stop_reason,
+    })
+}
+
+/// Line-mode `/review`: same options as the subcommand, but `--fail-on` only warns because
+/// the chat keeps running.
+pub(crate) async fn run_review_slash(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    session_id: &str,
+    agent_id_override: Option<&str>,
+    args: &str,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    let error_label = if is_zh { "[错误]" } else { "[error]" };
+    let prepared = parse_review_args(args)
+        .map_err(|err| anyhow!(err))
+        .and_then(|options| {
+            let prompt =
+                build_review_prompt(runtime.repo_root.as_path(), &options, language.as_str())?;
+            Ok((options, prompt))
+        });
+    let (options, prompt) = match prepared {
+        Ok(prepared) => prepared,
+        Err(err) => {
+            println!("{error_label} {err}");
+            return Ok(());
+        }
+    };
+    let report = run_review(
+        runtime,
+        global,
+        session_id,
+        agent_id_override,
+        &options,
+        &prompt,
+    )
+    .await?;
+    if let Some(message) = options
+        .fail_on
+        .and_then(|threshold| fail_on_message(&report, threshold, is_zh))
+    {
+        println!("[review] {message}");
+    }
+    Ok(())
+}
+
+/// `wunder-cli review`: a one-shot review for scripts and pre-commit hooks. With `--fail-on`
+/// the command fails when any finding reaches the threshold, when the answer carries no
+/// verdict, or when the turn stopped early.
+pub(crate) async fn handle_review(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: ReviewCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    let options = ReviewOptions::from(command);
+    let prompt = build_review_prompt(runtime.repo_root.as_path(), &options, language.as_str())?;
+    let session_id = global
+        .session
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+    let report = run_review(runtime, global, &session_id, None, &options, &prompt).await?;
+    crate::turn_interrupt::ensure_not_cancelled(report.stop_reason.as_deref())?;
+    match options
+        .fail_on
+        .and_then(|threshold| fail_on_message(&report, threshold, is_zh))
+    {
+        Some(message) => Err(anyhow!(message)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_tags_are_parsed_from_review_answers() {
+        let answer = "\
+Summary of the change.
+
+- [severity:high] src/lib.rs:42 unwrap on user input panics
+1. [SEVERITY:Critical] api.rs:7 token logged in plain text
+**[severity:medium]** db.rs:90 error swallowed
+* [severity:low] naming nit
+[severity:none] nothing else
+Mentions [severity:high] inline are not findings.
+[severity:urgent] unknown level";
+        let findings = parse_review_findings(answer);
+        let severities = findings
+            .iter()
+            .map(|finding| finding.severity)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            severities,
+            vec![
+                ReviewSeverity::High,
+                ReviewSeverity::Critical,
+                ReviewSeverity::Medium,
+                ReviewSeverity::Low,
+            ]
+        );
+        assert_eq!(
+            findings[0].text,
+            "src/lib.rs:42 unwrap on user input panics"
+        );
+        assert_eq!(findings[2].text, "db.rs:90 error swallowed");
+        assert_eq!(count_at_or_above(&findings, ReviewSeverity::High), 2);
+        assert_eq!(count_at_or_above(&findings, ReviewSeverity::Critical), 1);
+        assert_eq!(count_at_or_above(&findings, ReviewSeverity::Low), 4);
+        assert!(blocking_message(&findings[3..], ReviewSeverity::Medium, false).is_none());
+        assert_eq!(
+            summary_line(&findings, false),
+            "[review] findings: critical=1 high=1 medium=1 low=1"
+        );
+    }
+
+    #[test]
+    fn clean_review_has_no_findings() {
+        assert!(parse_review_findings("[severity:none] no issues found").is_empty());
+        assert!(parse_review_findings("Looks good to me.").is_empty());
+        assert!(declares_no_issues("- [Severity:None] no issues found"));
+        assert!(!declares_no_issues("Looks good to me."));
+    }
+
+    #[test]
+    fn fail_on_requires_a_verdict_and_a_completed_turn() {
+        let completed = |answer: &str| ReviewReport {
+            findings: parse_review_findings(answer),
+            declared_clean: declares_no_issues(answer),
+            stop_reason: Some("model_response".to_string()),
+        };
+        let threshold = ReviewSeverity::High;
+
+        assert!(fail_on_message(
+            &completed("[severity:none] no issues found"),
+            threshold,
+            false
+        )
+        .is_none());
+        assert!(fail_on_message(&completed("[severity:low] nit"), threshold, false).is_none());
+        assert!(fail_on_message(&completed("[severity:high] bug"), threshold, false).is_some());
+        let untagged = fail_on_message(&completed("Looks good to me."), threshold, false)
+            .expect("missing verdict fails");
+        assert!(untagged.contains("[severity:none]"));
+
+        for reason in ["cancelled", "stream_interrupted"] {
+            let report = ReviewReport {
+                stop_reason: Some(reason.to_string()),
+                ..completed("[severity:none] no issues found")
+            };
+            let message = fail_on_message(&report, threshold, false).expect("early stop fails");
+            assert!(message.contains(reason));
+        }
+    }
+
+    #[test]
+    fn slash_args_split_flags_from_focus() {
+        let options =
+            parse_review_args("--fail-on high security of auth --output=review.md").unwrap();
+        assert_eq!(
+            options,
+            ReviewOptions {
+                focus: "security of auth".to_string(),
+                structured: false,
+                fail_on: Some(ReviewSeverity::High),
+                output: Some(PathBuf::from("review.md")),
+                with_history: false,
+            }
+        );
+        assert!(options.structured());
+        assert_eq!(
+            parse_review_args("error handling").unwrap().focus,
+            "error handling"
+        );
+        assert!(parse_review_args("--fail-on severe").is_err());
+        assert!(parse_review_args("--output").is_err());
+        assert!(parse_review_args("--with-history").unwrap().with_history);
+    }
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args([
+                "-c",
+                "user.name=Reviewer",
+                "-c",
+                "user.email=reviewer@example.com",
+            ])
+            .args(args)
+            .current_dir(root)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?}");
+    }
+
+    #[test]
+    fn history_section_is_added_only_with_the_flag() {
+        let root = std::env::temp_dir().join(format!(
+            "wunder-cli-review-history-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        fs::create_dir_all(&root).expect("create repo");
+        git(&root, &["init", "-q"]);
+        fs::write(root.join("lib.rs"), "fn main() {}\n").expect("write file");
+        git(&root, &["add", "lib.rs"]);
+        git(&root, &["commit", "-q", "-m", "Add entry point"]);
+        fs::write(root.join("lib.rs"), "fn main() { run(); }\n").expect("edit file");
+
+        let mut options = ReviewOptions::default();
+        let prompt = build_review_prompt(&root, &options, "en").expect("prompt");
+        assert!(!prompt.contains("## Recent history"));
+
+        options.with_history = true;
+        let prompt = build_review_prompt(&root, &options, "en").expect("prompt");
+        assert!(prompt.contains(HISTORY_HEADER));
+        assert!(prompt.contains("- lib.rs: Reviewer, "));
+        assert!(prompt.contains(": Add entry point"));
+        assert!(
+            prompt.find(HISTORY_HEADER) < prompt.find("## git diff"),
+            "history precedes the diff"
+        );
+
+        assert!(file_history_section(&root, 10).is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
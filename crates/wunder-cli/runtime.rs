@@ -1,7 +1,9 @@
 use crate::args::GlobalArgs;
+use crate::config_profile;
+use crate::config_source::{self, ConfigPathSource};
 use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -10,7 +12,9 @@ use wunder_server::admin_skills;
 use wunder_server::config::Config;
 use wunder_server::config_store::ConfigStore;
 use wunder_server::repo_assets;
+use wunder_server::sandbox_session;
 use wunder_server::state::{AppState, AppStateInitOptions};
+use wunder_server::workspace_ignore::WorkspaceIgnore;
 
 pub const CLI_DEFAULT_USER_ID: &str = "cli_user";
 
@@ -19,8 +23,14 @@ pub struct CliRuntime {
     pub state: Arc<AppState>,
     pub launch_dir: PathBuf,
     pub temp_root: PathBuf,
+    /// Git repository root of the project being worked on; `/diff` and `/review` run here.
     pub repo_root: PathBuf,
+    /// Checkout or install holding wunder's bundled config, prompts and skills.
+    pub assets_root: PathBuf,
+    pub wunder_home: PathBuf,
     pub user_id: String,
+    pub config_path: PathBuf,
+    pub config_source: ConfigPathSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +52,10 @@ pub enum TurnNotificationConfig {
         #[serde(default)]
         when: TurnNotificationWhen,
     },
+    Desktop {
+        #[serde(default)]
+        when: TurnNotificationWhen,
+    },
     Command {
         argv: Vec<String>,
         #[serde(default)]
@@ -57,10 +71,34 @@ pub enum TurnNotificationWhen {
     Unfocused,
 }
 
+/// One action entry in `tui_keybindings.json`: a single key spec or a list of alternatives.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum TuiKeyBindingValue {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl TuiKeyBindingValue {
+    pub fn specs(&self) -> Vec<&str> {
+        match self {
+            Self::One(spec) => vec![spec.as_str()],
+            Self::Many(specs) => specs.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+pub type TuiKeyBindingsConfig = BTreeMap<String, TuiKeyBindingValue>;
+
 impl CliRuntime {
     pub async fn init(global: &GlobalArgs) -> Result<Self> {
         let launch_dir = std::env::current_dir().context("read current directory failed")?;
-        let repo_root = resolve_repo_root(&launch_dir);
+        let assets_root = resolve_assets_root(&launch_dir);
+        let repo_root = crate::workspace_context::resolve_repo_root(
+            global.repo_root.as_deref(),
+            &launch_dir,
+            resolve_user_home_dir().as_deref(),
+        )?;
         let wunder_home = resolve_wunder_home_dir(&launch_dir);
         let temp_root = global
             .temp_root
@@ -70,16 +108,17 @@ impl CliRuntime {
         let vector_root = wunder_home.join("vector_knowledge");
         ensure_runtime_dirs(&temp_root, &wunder_home, &user_tools_root, &vector_root)?;
 
-        let config_path = prepare_runtime_config_path(global, &repo_root, &temp_root)?;
-        let i18n_path = repo_root.join("config/i18n.messages.json");
-        let skill_runner = repo_root.join("scripts/skill_runner.py");
+        let (config_path, config_source) =
+            prepare_runtime_config_path(global, &assets_root, &temp_root, &wunder_home)?;
+        let i18n_path = assets_root.join("config/i18n.messages.json");
+        let skill_runner = assets_root.join("scripts/skill_runner.py");
 
         set_env_path("WUNDER_CONFIG_PATH", &config_path);
         set_env_path_if_exists("WUNDER_I18N_MESSAGES_PATH", &i18n_path);
-        set_env_prompts_root_if_unset(&repo_root);
+        set_env_prompts_root_if_unset(&assets_root);
         set_env_path(
             "WUNDER_BUILTIN_SKILLS_ROOT",
-            &repo_assets::builtin_skills_root(&repo_root),
+            &repo_assets::builtin_skills_root(&assets_root),
         );
         set_env_path_if_exists("WUNDER_SKILL_RUNNER_PATH", &skill_runner);
         set_env_path("WUNDER_HOME", &wunder_home);
@@ -91,7 +130,7 @@ impl CliRuntime {
         let config_store = ConfigStore::new(config_path.clone());
         let launch_dir_for_update = launch_dir.clone();
         let temp_root_for_update = temp_root.clone();
-        let repo_root_for_update = repo_root.clone();
+        let assets_root_for_update = assets_root.clone();
         let wunder_home_for_update = wunder_home.clone();
         let _config = config_store
             .update(move |config| {
@@ -99,7 +138,7 @@ impl CliRuntime {
                     config,
                     &launch_dir_for_update,
                     &temp_root_for_update,
-                    &repo_root_for_update,
+                    &assets_root_for_update,
                     &wunder_home_for_update,
                 );
             })
@@ -126,7 +165,11 @@ impl CliRuntime {
             launch_dir,
             temp_root,
             repo_root,
+            assets_root,
+            wunder_home,
             user_id,
+            config_path,
+            config_source,
         })
     }
 
@@ -146,6 +189,33 @@ impl CliRuntime {
         self.temp_root.join("config/turn_notification.json")
     }
 
+    /// `.wunderignore` rules (plus any other configured ignore files) of the launch directory.
+    pub async fn load_workspace_ignore(&self) -> Option<WorkspaceIgnore> {
+        let config = self.state.config_store.get().await;
+        WorkspaceIgnore::load(&self.launch_dir, &config.workspace.ignore_files)
+    }
+
+    /// Ancestor `AGENTS.md` guidance for the launch directory, re-read on every call. The
+    /// launch directory's own file reaches the model through the thread snapshot instead.
+    pub fn load_agents_md_prompt(&self) -> Option<String> {
+        crate::agents_md::load_agents_md_prompt(
+            &self.launch_dir,
+            resolve_user_home_dir().as_deref(),
+        )
+    }
+
+    pub fn agent_profiles_file(&self) -> PathBuf {
+        self.temp_root.join("config/agent_profiles.json")
+    }
+
+    pub fn mouse_mode_file(&self) -> PathBuf {
+        self.temp_root.join("config/tui_mouse_mode.txt")
+    }
+
+    pub fn tui_keybindings_file(&self) -> PathBuf {
+        self.temp_root.join("config/tui_keybindings.json")
+    }
+
     pub fn load_extra_prompt(&self) -> Option<String> {
         let path = self.extra_prompt_file();
         let text = fs::read_to_string(path).ok()?;
@@ -205,6 +275,25 @@ impl CliRuntime {
         Ok(())
     }
 
+    pub fn load_mouse_mode(&self) -> Option<String> {
+        let text = fs::read_to_string(self.mouse_mode_file()).ok()?;
+        let cleaned = text.trim();
+        if cleaned.is_empty() {
+            None
+        } else {
+            Some(cleaned.to_string())
+        }
+    }
+
+    pub fn save_mouse_mode(&self, mode: &str) -> Result<()> {
+        let cleaned = mode.trim();
+        if cleaned.is_empty() {
+            return Err(anyhow!("mouse mode is empty"));
+        }
+        fs::write(self.mouse_mode_file(), cleaned.as_bytes())?;
+        Ok(())
+    }
+
     pub fn load_turn_notification_config(&self) -> TurnNotificationConfig {
         let path = self.turn_notification_file();
         let Ok(text) = fs::read_to_string(path) else {
@@ -230,6 +319,23 @@ impl CliRuntime {
         Ok(())
     }
 
+    /// A missing file means "use defaults"; a malformed one is an error so the TUI can report it.
+    pub fn load_tui_keybindings_config(&self) -> Result<TuiKeyBindingsConfig> {
+        let path = self.tui_keybindings_file();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(TuiKeyBindingsConfig::new());
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if text.trim().is_empty() {
+            return Ok(TuiKeyBindingsConfig::new());
+        }
+        serde_json::from_str(&text)
+            .with_context(|| format!("parse key bindings failed: {}", path.display()))
+    }
+
     pub fn load_saved_session(&self) -> Option<String> {
         let path = self.sessions_file();
         let text = fs::read_to_string(path).ok()?;
@@ -266,6 +372,17 @@ impl CliRuntime {
         uuid::Uuid::new_v4().simple().to_string()
     }
 
+    /// Sandbox container assigned to the session when `workspace.session_sandbox` is enabled.
+    pub fn session_sandbox_container(&self, session_id: &str) -> Option<i32> {
+        sandbox_session::load_session_container_id(
+            self.state.storage.as_ref(),
+            &self.user_id,
+            session_id,
+        )
+        .ok()
+        .flatten()
+    }
+
     pub async fn resolve_model_name(&self, requested: Option<&str>) -> Option<String> {
         if let Some(value) = requested.map(str::trim).filter(|value| !value.is_empty()) {
             return Some(value.to_string());
@@ -290,7 +407,7 @@ impl CliRuntime {
     }
 }
 
-fn resolve_repo_root(launch_dir: &Path) -> PathBuf {
+fn resolve_assets_root(launch_dir: &Path) -> PathBuf {
     if let Ok(value) = std::env::var("WUNDER_CLI_PROJECT_ROOT") {
         let cleaned = value.trim();
         if !cleaned.is_empty() {
@@ -387,10 +504,51 @@ fn prepare_runtime_config_path(
     global: &GlobalArgs,
     repo_root: &Path,
     temp_root: &Path,
-) -> Result<PathBuf> {
-    if let Some(path) = global.config_path.clone() {
-        return Ok(path);
+    wunder_home: &Path,
+) -> Result<(PathBuf, ConfigPathSource)> {
+    let explicit = config_source::explicit_config_path(
+        global.config_path.as_deref(),
+        global.profile.as_deref(),
+        wunder_home,
+        |name| std::env::var(name).ok(),
+    )?;
+    if let Some(raw) = global.profile.as_deref() {
+        let name = config_profile::normalize_profile_name(raw)?;
+        config_profile::save_profile(wunder_home, name.as_deref())?;
+    }
+    if let Some((path, source)) = explicit {
+        if config_source::check_explicit_config_path(&path, source, global.create_config)? {
+            if source.is_profile() {
+                seed_profile_config(&path, repo_root, temp_root)?;
+            } else {
+                ensure_generated_base_config(&path)?;
+            }
+        }
+        return Ok((path, source));
     }
+    Ok((
+        prepare_default_config_path(repo_root, temp_root)?,
+        ConfigPathSource::Runtime,
+    ))
+}
+
+/// A new profile starts as a copy of the runtime config so it keeps the current models.
+pub(crate) fn seed_profile_config(path: &Path, repo_root: &Path, temp_root: &Path) -> Result<()> {
+    let source = prepare_default_config_path(repo_root, temp_root)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(&source, path).with_context(|| {
+        format!(
+            "seed profile config failed: {} -> {}",
+            source.display(),
+            path.display()
+        )
+    })?;
+    Ok(())
+}
+
+fn prepare_default_config_path(repo_root: &Path, temp_root: &Path) -> Result<PathBuf> {
     let runtime_config = temp_root.join("config/wunder.yaml");
     if runtime_config.exists() {
         return Ok(runtime_config);
@@ -465,6 +623,14 @@ fn apply_cli_defaults(
     {
         config.security.approval_mode = Some("suggest".to_string());
     }
+    // Local commands run as the user, so keep their shell environment unless configured.
+    if config.security.env_passthrough.is_empty() {
+        config.security.env_passthrough = vec!["*".to_string()];
+    }
+    // Project agent definitions resolve against the launch directory (`workspace.root`).
+    if config.user_agents.definition_paths.is_empty() {
+        config.user_agents.definition_paths = vec![".wunder/agents".to_string()];
+    }
 
     let user_skills = wunder_home.join("skills");
     let project_wunder_skills = launch_dir.join(".wunder").join("skills");
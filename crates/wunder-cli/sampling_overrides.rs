@@ -0,0 +1,105 @@
+// 请求级模型参数：校验 --temperature/--top-p/--stop，连同 --json-mode/--json-schema 的 response_format 写入所选模型的 config_overrides，仅对本轮请求生效。
+use crate::args::GlobalArgs;
+use serde_json::{json, Map, Value};
+
+pub(crate) const TEMPERATURE_RANGE: (f32, f32) = (0.0, 2.0);
+pub(crate) const TOP_P_RANGE: (f32, f32) = (0.0, 1.0);
+/// OpenAI-compatible APIs accept at most four stop sequences.
+pub(crate) const MAX_STOP_SEQUENCES: usize = 4;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SamplingOverrides {
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub stop: Vec<String>,
+    /// Structured output requested with `--json-mode` or `--json-schema`.
+    pub response_format: Option<Value>,
+}
+
+impl SamplingOverrides {
+    pub(crate) fn from_global(global: &GlobalArgs) -> Self {
+        Self {
+            temperature: global.temperature,
+            top_p: global.top_p,
+            stop: global.stop.clone(),
+            response_format: global.json_schema.clone().or_else(|| {
+                global
+                    .json_mode
+                    .then(crate::response_format_args::json_object_response_format)
+            }),
+        }
+    }
+
+    /// Write the set values into the model-level override map; unset values keep the config.
+    pub(crate) fn apply(&self, model_overrides: &mut Map<String, Value>) {
+        if let Some(temperature) = self.temperature {
+            model_overrides.insert("temperature".to_string(), json!(temperature));
+        }
+        if let Some(top_p) = self.top_p {
+            model_overrides.insert("top_p".to_string(), json!(top_p));
+        }
+        if !self.stop.is_empty() {
+            model_overrides.insert("stop".to_string(), json!(self.stop));
+        }
+        if let Some(response_format) = &self.response_format {
+            model_overrides.insert("response_format".to_string(), response_format.clone());
+        }
+    }
+}
+
+pub(crate) fn parse_temperature(value: &str) -> Result<f32, String> {
+    parse_in_range(value, TEMPERATURE_RANGE)
+}
+
+pub(crate) fn parse_top_p(value: &str) -> Result<f32, String> {
+    parse_in_range(value, TOP_P_RANGE)
+}
+
+pub(crate) fn parse_stop_sequence(value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err("stop sequence must not be empty".to_string());
+    }
+    Ok(value.to_string())
+}
+
+/// Clap cannot bound how often a repeatable flag appears, so the count is checked after parsing.
+pub(crate) fn validate_stop_sequences(stop: &[String]) -> Result<(), String> {
+    if stop.len() > MAX_STOP_SEQUENCES {
+        return Err(format!(
+            "at most {MAX_STOP_SEQUENCES} --stop sequences are allowed, got {}",
+            stop.len()
+        ));
+    }
+    Ok(())
+}
+
+fn parse_in_range(value: &str, (min, max): (f32, f32)) -> Result<f32, String> {
+    let parsed = value
+        .trim()
+        .parse::<f32>()
+        .map_err(|_| format!("`{value}` is not a number"))?;
+    if !parsed.is_finite() || parsed < min || parsed > max {
+        return Err(format!("must be between {min} and {max}, got {value}"));
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampling_values_are_range_checked() {
+        assert_eq!(parse_temperature("0.3"), Ok(0.3));
+        assert_eq!(parse_temperature("2"), Ok(2.0));
+        assert!(parse_temperature("2.1").is_err());
+        assert!(parse_temperature("-0.1").is_err());
+        assert!(parse_temperature("warm").is_err());
+        assert_eq!(parse_top_p("1"), Ok(1.0));
+        assert!(parse_top_p("1.5").is_err());
+        assert!(parse_top_p("NaN").is_err());
+        assert!(parse_stop_sequence("").is_err());
+        assert!(validate_stop_sequences(&["x"; 4].map(String::from)).is_ok());
+        assert!(validate_stop_sequences(&["x"; 5].map(String::from)).is_err());
+    }
+}
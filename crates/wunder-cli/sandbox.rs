@@ -0,0 +1,107 @@
+// 沙盒容器维护：`wunder-cli sandbox reset [ID]` 清空指定（默认当前会话所用）容器的工作目录，容器仍被活跃会话使用时拒绝执行。
+use crate::args::{GlobalArgs, SandboxCommand, SandboxResetCommand, SandboxSubcommand};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use serde_json::json;
+use wunder_server::sandbox_reset::{
+    reset_sandbox_container, session_container_id, SandboxResetOutcome,
+};
+use wunder_server::storage::normalize_sandbox_container_id;
+
+pub(crate) async fn handle_sandbox(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: SandboxCommand,
+) -> Result<()> {
+    match command.command {
+        SandboxSubcommand::Reset(cmd) => reset(runtime, global, cmd).await,
+    }
+}
+
+async fn reset(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: SandboxResetCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let container_id = match command.container_id {
+        Some(container_id) => {
+            if normalize_sandbox_container_id(container_id) != container_id {
+                return Err(anyhow!(locale::tr(
+                    language.as_str(),
+                    "沙盒容器编号需在 1 到 10 之间",
+                    "sandbox container id must be between 1 and 10",
+                )));
+            }
+            container_id
+        }
+        None => {
+            let session_id = runtime.resolve_session(global.session.as_deref());
+            let agent_id = global.agent.clone().unwrap_or_default();
+            session_container_id(
+                runtime.state.storage.as_ref(),
+                &runtime.user_id,
+                &session_id,
+                &agent_id,
+            )?
+        }
+    };
+
+    let workspace = runtime.state.workspace.clone();
+    let storage = runtime.state.storage.clone();
+    let monitor = runtime.state.monitor.clone();
+    let user_id = runtime.user_id.clone();
+    let outcome = tokio::task::spawn_blocking(move || {
+        reset_sandbox_container(
+            &workspace,
+            storage.as_ref(),
+            Some(monitor.as_ref()),
+            &user_id,
+            container_id,
+        )
+    })
+    .await
+    .map_err(|err| anyhow!("sandbox reset task cancelled: {err}"))??;
+
+    match outcome {
+        SandboxResetOutcome::Reset(report) => {
+            if global.json {
+                println!("{}", serde_json::to_string(&report)?);
+            } else if locale::is_zh_language(language.as_str()) {
+                println!(
+                    "沙盒容器 {} 已重置，清理 {} 项",
+                    report.container_id, report.removed_entries
+                );
+            } else {
+                println!(
+                    "sandbox container {} reset, removed {} entries",
+                    report.container_id, report.removed_entries
+                );
+            }
+            Ok(())
+        }
+        SandboxResetOutcome::Busy {
+            container_id,
+            session_ids,
+        } => {
+            if global.json {
+                println!(
+                    "{}",
+                    json!({ "container_id": container_id, "busy_session_ids": session_ids })
+                );
+            }
+            Err(anyhow!(if locale::is_zh_language(language.as_str()) {
+                format!(
+                    "沙盒容器 {container_id} 正被活跃会话使用: {}",
+                    session_ids.join(", ")
+                )
+            } else {
+                format!(
+                    "sandbox container {container_id} is in use by active sessions: {}",
+                    session_ids.join(", ")
+                )
+            }))
+        }
+    }
+}
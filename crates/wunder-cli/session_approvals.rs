@@ -0,0 +1,65 @@
+// 会话审批白名单：/approvals allowed 查看、/approvals clear 清除已持久化的“本会话批准”。
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use wunder_server::session_approvals::{clear_session_approvals, list_session_approvals};
+
+pub(crate) const APPROVALS_SLASH_USAGE: &str =
+    "/approvals [show|suggest|auto_edit|full_auto|allowed|clear]";
+
+/// Handle the allowlist subcommands of `/approvals`. Returns `None` when `args` is not one of
+/// them, so the caller goes on to treat it as an approval mode.
+pub(crate) async fn run_session_approvals_command(
+    runtime: &CliRuntime,
+    language: &str,
+    session_id: &str,
+    args: &str,
+) -> Result<Option<Vec<String>>> {
+    let clear = match args.trim().to_ascii_lowercase().as_str() {
+        "clear" | "reset" => true,
+        "allowed" | "allowlist" => false,
+        _ => return Ok(None),
+    };
+    let storage = runtime.state.storage.clone();
+    let user_id = runtime.user_id.clone();
+    let session = session_id.trim().to_string();
+    if clear {
+        let removed = tokio::task::spawn_blocking(move || {
+            clear_session_approvals(storage.as_ref(), &user_id, &session)
+        })
+        .await
+        .map_err(|err| anyhow!("session approvals task cancelled: {err}"))??;
+        return Ok(Some(vec![if locale::is_zh_language(language) {
+            format!("已清除本会话的 {removed} 条持久化审批")
+        } else {
+            format!("cleared {removed} persisted session approval(s)")
+        }]));
+    }
+
+    let records = tokio::task::spawn_blocking(move || {
+        list_session_approvals(storage.as_ref(), &user_id, &session)
+    })
+    .await
+    .map_err(|err| anyhow!("session approvals task cancelled: {err}"))??;
+    if records.is_empty() {
+        return Ok(Some(vec![locale::tr(
+            language,
+            "本会话没有持久化的审批",
+            "no persisted approvals for this session",
+        )]));
+    }
+    let mut lines = vec![locale::tr(
+        language,
+        "本会话已批准（恢复会话后仍然生效）:",
+        "approved for this session (kept across resumes):",
+    )];
+    lines.extend(records.iter().map(|record| {
+        let field = |key: &str| record.get(key).and_then(Value::as_str).unwrap_or("");
+        match field("workdir") {
+            "" => format!("- {}: {}", field("tool"), field("signature")),
+            workdir => format!("- {}: {} ({workdir})", field("tool"), field("signature")),
+        }
+    }));
+    Ok(Some(lines))
+}
@@ -0,0 +1,84 @@
+// 会话导出：`wunder-cli session export --format openai` 将会话历史输出为 OpenAI chat messages JSON。
+use crate::args::{
+    GlobalArgs, SessionCommand, SessionExportCommand, SessionExportFormatArg, SessionSubcommand,
+};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Context, Result};
+use wunder_server::history_export::{export_session_openai_messages, ExportAttachmentMode};
+
+pub(crate) async fn handle_session(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: SessionCommand,
+) -> Result<()> {
+    match command.command {
+        SessionSubcommand::Export(cmd) => export_session(runtime, global, cmd).await,
+    }
+}
+
+async fn export_session(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: SessionExportCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let session_id = command
+        .session_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            global
+                .session
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        })
+        .or_else(|| runtime.load_saved_session())
+        .ok_or_else(|| {
+            anyhow!(locale::tr(
+                language.as_str(),
+                "未找到保存的会话，请指定会话 ID",
+                "no saved session found, pass a session id",
+            ))
+        })?;
+    let mode = if command.inline_attachments {
+        ExportAttachmentMode::Inline
+    } else {
+        ExportAttachmentMode::Reference
+    };
+    let messages = match command.format {
+        SessionExportFormatArg::Openai => {
+            export_session_openai_messages(
+                &runtime.state.workspace,
+                &runtime.user_id,
+                &session_id,
+                mode,
+            )
+            .await?
+        }
+    };
+    if messages.is_empty() {
+        return Err(anyhow!(if locale::is_zh_language(language.as_str()) {
+            format!("会话没有可导出的消息: {session_id}")
+        } else {
+            format!("session has no messages to export: {session_id}")
+        }));
+    }
+    let text = serde_json::to_string_pretty(&messages)?;
+    let Some(path) = command.output else {
+        println!("{text}");
+        return Ok(());
+    };
+    std::fs::write(&path, format!("{text}\n"))
+        .with_context(|| format!("write session export failed: {}", path.display()))?;
+    if locale::is_zh_language(language.as_str()) {
+        eprintln!("已导出 {} 条消息: {}", messages.len(), path.display());
+    } else {
+        eprintln!("exported {} messages: {}", messages.len(), path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// 会话历史清空：/history clear 删除当前会话的聊天消息并重置上下文计数，保留会话记录。
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::Result;
+
+pub(crate) const HISTORY_SLASH_USAGE: &str = "/history clear [force]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HistoryAction {
+    /// `clear` without `force`: only explain what would happen.
+    ConfirmClear,
+    Clear,
+}
+
+/// Parse `/history` arguments; `None` means the caller should print the usage.
+pub(crate) fn parse_history_args(args: &str) -> Option<HistoryAction> {
+    let mut parts = args.split_whitespace();
+    if !parts.next()?.eq_ignore_ascii_case("clear") {
+        return None;
+    }
+    match (parts.next(), parts.next()) {
+        (None, _) => Some(HistoryAction::ConfirmClear),
+        (Some(token), None) if token.eq_ignore_ascii_case("force") => Some(HistoryAction::Clear),
+        _ => None,
+    }
+}
+
+/// Handle `/history` for the chat loop and the TUI; returns the lines to show.
+pub(crate) async fn run_history_command(
+    runtime: &CliRuntime,
+    language: &str,
+    session_id: &str,
+    args: &str,
+) -> Result<Vec<String>> {
+    let Some(action) = parse_history_args(args) else {
+        return Ok(vec![locale::tr(
+            language,
+            &format!("用法: {HISTORY_SLASH_USAGE}"),
+            &format!("usage: {HISTORY_SLASH_USAGE}"),
+        )]);
+    };
+    if action == HistoryAction::ConfirmClear {
+        return Ok(vec![locale::tr(
+            language,
+            "将删除当前会话的全部消息（会话本身保留），确认请执行 /history clear force",
+            "this deletes every message of the current session (the session is kept); run /history clear force to confirm",
+        )]);
+    }
+    let deleted = runtime
+        .state
+        .workspace
+        .clear_session_messages(&runtime.user_id, session_id)
+        .await?;
+    Ok(vec![if locale::is_zh_language(language) {
+        format!("已清空会话消息: {deleted} 条")
+    } else {
+        format!("session messages cleared: {deleted}")
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clear_requires_force_token() {
+        assert_eq!(
+            parse_history_args("clear"),
+            Some(HistoryAction::ConfirmClear)
+        );
+        assert_eq!(
+            parse_history_args("CLEAR force"),
+            Some(HistoryAction::Clear)
+        );
+        assert_eq!(parse_history_args(""), None);
+        assert_eq!(parse_history_args("clear now"), None);
+        assert_eq!(parse_history_args("clear force extra"), None);
+    }
+}
@@ -0,0 +1,39 @@
+// 会话自动命名：/rename auto 依据整段历史重新生成标题，有可用模型时调用模型，否则取最近一条有效提问。
+use crate::runtime::CliRuntime;
+use anyhow::Result;
+use wunder_server::session_title::summarize_history_title;
+
+/// `/rename auto` (or `--auto`) regenerates the title instead of setting one.
+pub(crate) fn is_auto_rename(args: &str) -> bool {
+    matches!(args.trim(), "auto" | "--auto")
+}
+
+/// Regenerate and save the session title from its history; `None` when there is nothing to
+/// summarize yet.
+pub(crate) async fn auto_rename_session(
+    runtime: &CliRuntime,
+    session_id: &str,
+) -> Result<Option<String>> {
+    let history = crate::load_session_history_entries(runtime, session_id, 0).await?;
+    let config = runtime.state.config_store.get().await;
+    let Some(title) = summarize_history_title(&config, reqwest::Client::new(), &history).await
+    else {
+        return Ok(None);
+    };
+    crate::rename_session_title(runtime, session_id, &title)
+        .await
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_flag_accepts_both_spellings() {
+        assert!(is_auto_rename("auto"));
+        assert!(is_auto_rename(" --auto "));
+        assert!(!is_auto_rename("auto pilot"));
+        assert!(!is_auto_rename("Release notes"));
+    }
+}
@@ -0,0 +1,278 @@
+// 会话级工具覆盖：/tools 查看与编辑 ChatSessionRecord.tool_overrides，并在续聊时应用到请求。
+use crate::locale;
+use crate::runtime::CliRuntime;
+use crate::{current_ts, CLI_DEFAULT_SESSION_STATUS, CLI_DEFAULT_SESSION_TITLE};
+use anyhow::{anyhow, Result};
+use wunder_server::storage::ChatSessionRecord;
+use wunder_server::tools::{resolve_session_request_tool_names, resolve_tool_name};
+
+const TOOL_OVERRIDE_NONE: &str = "__no_tools__";
+pub(crate) const TOOLS_SLASH_USAGE: &str =
+    "/tools [show|set <names>|add <names>|remove <names>|none|reset]";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ToolsAction {
+    Show,
+    Set(Vec<String>),
+    Add(Vec<String>),
+    Remove(Vec<String>),
+    None,
+    Reset,
+}
+
+/// Parse `/tools` arguments. Names may be separated by spaces or commas; `None` means the
+/// arguments did not form a valid subcommand and the caller should print the usage.
+pub(crate) fn parse_tools_args(args: &str) -> Option<ToolsAction> {
+    let mut parts = args.split_whitespace();
+    let Some(verb) = parts.next() else {
+        return Some(ToolsAction::Show);
+    };
+    let names = parts
+        .flat_map(|part| part.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let action = match verb.to_ascii_lowercase().as_str() {
+        "show" | "list" if names.is_empty() => ToolsAction::Show,
+        "none" if names.is_empty() => ToolsAction::None,
+        "reset" | "clear" if names.is_empty() => ToolsAction::Reset,
+        "set" if !names.is_empty() => ToolsAction::Set(names),
+        "add" if !names.is_empty() => ToolsAction::Add(names),
+        "remove" | "rm" if !names.is_empty() => ToolsAction::Remove(names),
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// Apply an edit to a session's override list. Names are compared by canonical tool name so
+/// `read_file` and its Chinese canonical name count as the same entry.
+pub(crate) fn edit_tool_overrides(current: &[String], action: &ToolsAction) -> Vec<String> {
+    let same_tool = |left: &str, right: &str| resolve_tool_name(left) == resolve_tool_name(right);
+    // `__no_tools__` is exclusive; any explicit edit starts from a clean list.
+    let mut output = current
+        .iter()
+        .filter(|name| name.as_str() != TOOL_OVERRIDE_NONE)
+        .cloned()
+        .collect::<Vec<_>>();
+    let additions = match action {
+        ToolsAction::Show => return current.to_vec(),
+        ToolsAction::None => return vec![TOOL_OVERRIDE_NONE.to_string()],
+        ToolsAction::Reset => return Vec::new(),
+        ToolsAction::Remove(names) => {
+            output.retain(|name| !names.iter().any(|removed| same_tool(name, removed)));
+            return output;
+        }
+        ToolsAction::Set(names) => {
+            output.clear();
+            names
+        }
+        ToolsAction::Add(names) => names,
+    };
+    for name in additions {
+        if !output.iter().any(|existing| same_tool(existing, name)) {
+            output.push(name.clone());
+        }
+    }
+    output
+}
+
+/// Handle `/tools` for the chat loop and the TUI; returns the lines to show.
+pub(crate) async fn run_tools_command(
+    runtime: &CliRuntime,
+    language: &str,
+    session_id: &str,
+    args: &str,
+) -> Result<Vec<String>> {
+    let Some(action) = parse_tools_args(args) else {
+        return Ok(vec![locale::tr(
+            language,
+            &format!("用法: {TOOLS_SLASH_USAGE}"),
+            &format!("usage: {TOOLS_SLASH_USAGE}"),
+        )]);
+    };
+    let mut record = load_session_record(runtime, session_id).await?;
+    if action != ToolsAction::Show {
+        record.tool_overrides = edit_tool_overrides(&record.tool_overrides, &action);
+        record.updated_at = current_ts();
+        let user_store = runtime.state.user_store.clone();
+        let saved = record.clone();
+        tokio::task::spawn_blocking(move || user_store.upsert_chat_session(&saved))
+            .await
+            .map_err(|err| anyhow!("session tool overrides task cancelled: {err}"))??;
+    }
+    let effective = session_request_tool_names(runtime, &record).await;
+    Ok(describe_tool_overrides(
+        language,
+        &record.tool_overrides,
+        &effective,
+    ))
+}
+
+/// `tool_names` for the next request on `session_id`, honouring persisted overrides.
+pub(crate) async fn resolve_cli_session_tool_names(
+    runtime: &CliRuntime,
+    session_id: &str,
+) -> Result<Vec<String>> {
+    let user_store = runtime.state.user_store.clone();
+    let user_id = runtime.user_id.clone();
+    let session = session_id.trim().to_string();
+    let record =
+        tokio::task::spawn_blocking(move || user_store.get_chat_session(&user_id, &session))
+            .await
+            .map_err(|err| anyhow!("session tool overrides task cancelled: {err}"))??;
+    match record {
+        Some(record) => Ok(session_request_tool_names(runtime, &record).await),
+        None => Ok(Vec::new()),
+    }
+}
+
+async fn session_request_tool_names(
+    runtime: &CliRuntime,
+    record: &ChatSessionRecord,
+) -> Vec<String> {
+    if record.tool_overrides.is_empty() {
+        return Vec::new();
+    }
+    let config = runtime.state.config_store.get().await;
+    let skills = runtime.state.skills.read().await.clone();
+    let bindings =
+        runtime
+            .state
+            .user_tool_manager
+            .build_bindings(&config, &skills, &runtime.user_id);
+    resolve_session_request_tool_names(&config, &skills, Some(&bindings), record)
+}
+
+async fn load_session_record(runtime: &CliRuntime, session_id: &str) -> Result<ChatSessionRecord> {
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() {
+        return Err(anyhow!("session id is empty"));
+    }
+    let user_store = runtime.state.user_store.clone();
+    let user_id = runtime.user_id.clone();
+    tokio::task::spawn_blocking(move || -> Result<ChatSessionRecord> {
+        let now = current_ts();
+        Ok(user_store
+            .get_chat_session(&user_id, &session_id)?
+            .unwrap_or_else(|| ChatSessionRecord {
+                session_id: session_id.clone(),
+                user_id: user_id.clone(),
+                title: CLI_DEFAULT_SESSION_TITLE.to_string(),
+                status: CLI_DEFAULT_SESSION_STATUS.to_string(),
+                created_at: now,
+                updated_at: now,
+                last_message_at: now,
+                agent_id: None,
+                tool_overrides: Vec::new(),
+                parent_session_id: None,
+                parent_message_id: None,
+                spawn_label: None,
+                spawned_by: None,
+            }))
+    })
+    .await
+    .map_err(|err| anyhow!("session tool overrides task cancelled: {err}"))?
+}
+
+fn describe_tool_overrides(
+    language: &str,
+    overrides: &[String],
+    effective: &[String],
+) -> Vec<String> {
+    if overrides.is_empty() {
+        return vec![locale::tr(
+            language,
+            "工具覆盖: 未设置（使用全部可用工具）",
+            "tool overrides: none (all available tools)",
+        )];
+    }
+    if overrides.iter().any(|name| name == TOOL_OVERRIDE_NONE) {
+        return vec![locale::tr(
+            language,
+            "工具覆盖: 已禁用全部工具",
+            "tool overrides: all tools disabled",
+        )];
+    }
+    let enabled = effective
+        .iter()
+        .filter(|name| name.as_str() != TOOL_OVERRIDE_NONE)
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut lines = vec![
+        if locale::is_zh_language(language) {
+            format!("工具覆盖: {}", overrides.join(", "))
+        } else {
+            format!("tool overrides: {}", overrides.join(", "))
+        },
+        if locale::is_zh_language(language) {
+            format!("生效工具 ({}): {}", enabled.len(), enabled.join(", "))
+        } else {
+            format!(
+                "effective tools ({}): {}",
+                enabled.len(),
+                enabled.join(", ")
+            )
+        },
+    ];
+    if enabled.is_empty() {
+        lines.push(locale::tr(
+            language,
+            "提示: 覆盖中的工具均不可用，本会话将不调用任何工具",
+            "hint: none of the overridden tools are available, this session will run without tools",
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parse_tools_args_accepts_subcommands_and_comma_lists() {
+        assert_eq!(parse_tools_args("  "), Some(ToolsAction::Show));
+        assert_eq!(
+            parse_tools_args("set read_file, list_files"),
+            Some(ToolsAction::Set(names(&["read_file", "list_files"])))
+        );
+        assert_eq!(
+            parse_tools_args("rm write_file"),
+            Some(ToolsAction::Remove(names(&["write_file"])))
+        );
+        assert_eq!(parse_tools_args("NONE"), Some(ToolsAction::None));
+        assert_eq!(parse_tools_args("add"), None);
+        assert_eq!(parse_tools_args("reset read_file"), None);
+    }
+
+    #[test]
+    fn edit_tool_overrides_dedupes_by_canonical_name() {
+        let current = names(&["read_file"]);
+        let added = edit_tool_overrides(
+            &current,
+            &ToolsAction::Add(vec![
+                resolve_tool_name("read_file"),
+                "list_files".to_string(),
+            ]),
+        );
+        assert_eq!(added, names(&["read_file", "list_files"]));
+        assert_eq!(
+            edit_tool_overrides(&added, &ToolsAction::Remove(names(&["read_file"]))),
+            names(&["list_files"])
+        );
+        let disabled = edit_tool_overrides(&added, &ToolsAction::None);
+        assert_eq!(disabled, names(&[TOOL_OVERRIDE_NONE]));
+        assert_eq!(
+            edit_tool_overrides(&disabled, &ToolsAction::Add(names(&["read_file"]))),
+            names(&["read_file"])
+        );
+        assert_eq!(
+            edit_tool_overrides(&added, &ToolsAction::Reset),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// skills run：不经过模型，直接以 JSON 参数执行单个技能，便于独立调试技能。
+use crate::args::{GlobalArgs, SkillsRunCommand};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use wunder_server::skills::load_skills;
+use wunder_server::tools::ToolError;
+use wunder_server::user_tools::UserToolKind;
+
+pub(crate) async fn handle_skill_run(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: SkillsRunCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    let name = command.name.trim().to_string();
+    let args: Value = serde_json::from_str(command.args.trim()).with_context(|| {
+        if is_zh {
+            format!("--args 不是合法 JSON: {}", command.args.trim())
+        } else {
+            format!("invalid json for --args: {}", command.args.trim())
+        }
+    })?;
+    ensure_skill_runnable(runtime, &name, is_zh).await?;
+
+    // Same dispatch as a model-issued call, so the skill's input schema is enforced too.
+    let result = crate::execute_tool_direct(runtime, global, &name, &args).await?;
+    if command.json || global.json {
+        println!(
+            "{}",
+            serde_json::to_string(&json!({ "skill": name, "result": result }))?
+        );
+    } else if let Value::String(text) = &result {
+        println!("{text}");
+    } else {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    }
+    Ok(())
+}
+
+/// Fail early, with a message that says why, when `name` is not a skill the user can run:
+/// either it exists on disk but is not enabled, or there is no such skill at all.
+async fn ensure_skill_runnable(runtime: &CliRuntime, name: &str, is_zh: bool) -> Result<()> {
+    if name.is_empty() {
+        return Err(ToolError::InvalidArgs(if is_zh {
+            "技能名称不能为空".to_string()
+        } else {
+            "skill name cannot be empty".to_string()
+        })
+        .into());
+    }
+    let config = runtime.state.config_store.get().await;
+    let skills_snapshot = runtime.state.skills.read().await.clone();
+    if skills_snapshot.get(name).is_some() {
+        return Ok(());
+    }
+    let bindings =
+        runtime
+            .state
+            .user_tool_manager
+            .build_bindings(&config, &skills_snapshot, &runtime.user_id);
+    if bindings
+        .alias_map
+        .get(name)
+        .is_some_and(|alias| matches!(alias.kind, UserToolKind::Skill))
+    {
+        return Ok(());
+    }
+
+    let (_, user_specs) = crate::load_user_skill_specs(runtime).await;
+    let disabled = user_specs.iter().any(|spec| spec.name == name)
+        || load_skills(&config, false, false, true).get(name).is_some();
+    let message = match (disabled, is_zh) {
+        (true, true) => format!("技能未启用: {name}（可先执行 wunder skills enable {name}）"),
+        (true, false) => {
+            format!("skill is disabled: {name} (run `wunder skills enable {name}` first)")
+        }
+        (false, true) => format!("未找到技能: {name}"),
+        (false, false) => format!("skill not found: {name}"),
+    };
+    Err(ToolError::NotFound(message).into())
+}
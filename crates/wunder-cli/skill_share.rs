@@ -0,0 +1,58 @@
+// 技能共享开关：/skills share|unshare 维护 skills.shared，决定子智能体可用的技能。
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::Result;
+
+/// Add or remove `target` from the user's shared skills and return the message to show.
+/// Enabled skills stay usable by the primary agent either way; only shared ones reach sub-agents.
+pub(crate) async fn toggle_skill_shared(
+    runtime: &CliRuntime,
+    language: &str,
+    target: &str,
+    share: bool,
+) -> Result<String> {
+    let is_zh = locale::is_zh_language(language);
+    let target = target.trim().to_string();
+    if target.is_empty() {
+        return Ok(locale::tr(
+            language,
+            "技能名称不能为空",
+            "skill name cannot be empty",
+        ));
+    }
+    if share {
+        let (_, specs) = crate::load_user_skill_specs(runtime).await;
+        if !specs.iter().any(|spec| spec.name == target) {
+            return Ok(if is_zh {
+                format!("未找到技能: {target}")
+            } else {
+                format!("skill not found: {target}")
+            });
+        }
+    }
+
+    let payload = runtime
+        .state
+        .user_tool_store
+        .load_user_tools(&runtime.user_id);
+    let mut shared = payload.skills.shared;
+    shared.retain(|name| name.trim() != target.as_str());
+    if share {
+        shared.push(target.clone());
+    }
+    runtime.state.user_tool_store.update_skills(
+        &runtime.user_id,
+        payload.skills.enabled,
+        shared,
+    )?;
+    runtime
+        .state
+        .user_tool_manager
+        .clear_skill_cache(Some(&runtime.user_id));
+    Ok(match (share, is_zh) {
+        (true, true) => format!("技能已共享给子智能体: {target}"),
+        (true, false) => format!("skill shared with sub-agents: {target}"),
+        (false, true) => format!("技能已取消共享: {target}"),
+        (false, false) => format!("skill unshared: {target}"),
+    })
+}
@@ -19,17 +19,21 @@ pub enum SlashCommand {
     Mention,
     Mcp,
     Skills,
+    Tools,
     Apps,
     Ps,
     Clean,
     Fork,
     Rename,
     Compact,
+    History,
     Backtrack,
     DebugConfig,
+    Env,
     Statusline,
     Status,
     Session,
+    Usage,
     System,
     Mouse,
     Resume,
@@ -54,7 +58,7 @@ struct SlashCommandDoc {
     description: &'static str,
 }
 
-const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
+const SLASH_COMMAND_DOCS: [SlashCommandDoc; 41] = [
     SlashCommandDoc {
         command: SlashCommand::Model,
         usage: "/model [name]",
@@ -67,8 +71,8 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
     },
     SlashCommandDoc {
         command: SlashCommand::Approvals,
-        usage: "/approvals [show|suggest|auto_edit|full_auto]",
-        description: "show or switch approval mode",
+        usage: "/approvals [show|suggest|auto_edit|full_auto|allowed|clear]",
+        description: "show or switch approval mode, list or clear session approvals",
     },
     SlashCommandDoc {
         command: SlashCommand::Plan,
@@ -97,13 +101,13 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
     },
     SlashCommandDoc {
         command: SlashCommand::Agent,
-        usage: "/agent [show|list|clear|<agent_id>]",
-        description: "show/list/switch current agent id override",
+        usage: "/agent [show|list|clear|save <name>|load <name>|remove <name>|<agent_id>]",
+        description: "show/list/switch agent id override, or save/load named agent profiles",
     },
     SlashCommandDoc {
         command: SlashCommand::Attach,
-        usage: "/attach [list|clear|drop <index>|<path>]",
-        description: "queue local file/image attachments for next turn",
+        usage: "/attach [list|clear|drop <index>|[--remember] <path>]",
+        description: "queue local file/image attachments for next turn (--remember saves them to knowledge)",
     },
     SlashCommandDoc {
         command: SlashCommand::Branches,
@@ -112,7 +116,7 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
     },
     SlashCommandDoc {
         command: SlashCommand::Notify,
-        usage: "/notify [show|off|bell|osc9|when <always|unfocused>|<command...>]",
+        usage: "/notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]",
         description: "configure turn-complete notifications",
     },
     SlashCommandDoc {
@@ -122,7 +126,7 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
     },
     SlashCommandDoc {
         command: SlashCommand::Review,
-        usage: "/review [focus]",
+        usage: "/review [--structured] [--with-history] [--fail-on <level>] [--output <file>] [focus]",
         description: "review current git changes with model",
     },
     SlashCommandDoc {
@@ -137,9 +141,14 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
     },
     SlashCommandDoc {
         command: SlashCommand::Skills,
-        usage: "/skills [list|enable <name>|disable <name>|root]",
+        usage: "/skills [list|enable <name>|disable <name>|share <name>|unshare <name>|root]",
         description: "list and toggle local skills",
     },
+    SlashCommandDoc {
+        command: SlashCommand::Tools,
+        usage: "/tools [show|set <names>|add <names>|remove <names>|none|reset]",
+        description: "view or edit the tools allowed in current session",
+    },
     SlashCommandDoc {
         command: SlashCommand::Apps,
         usage:
@@ -163,14 +172,19 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
     },
     SlashCommandDoc {
         command: SlashCommand::Rename,
-        usage: "/rename <title>",
-        description: "rename current session",
+        usage: "/rename <title|auto>",
+        description: "rename current session, or regenerate its title from history with auto",
     },
     SlashCommandDoc {
         command: SlashCommand::Compact,
         usage: "/compact",
         description: "compact current session into a summary-only branch",
     },
+    SlashCommandDoc {
+        command: SlashCommand::History,
+        usage: "/history clear [force]",
+        description: "clear current session messages (keeps the session)",
+    },
     SlashCommandDoc {
         command: SlashCommand::Backtrack,
         usage: "/backtrack [list|index]",
@@ -178,13 +192,18 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
     },
     SlashCommandDoc {
         command: SlashCommand::DebugConfig,
-        usage: "/debug-config",
+        usage: "/debug-config [--show-secrets]",
         description: "show config layers and effective values",
     },
+    SlashCommandDoc {
+        command: SlashCommand::Env,
+        usage: "/env [--show-secrets]",
+        description: "list WUNDER_* environment variables and whether their paths exist",
+    },
     SlashCommandDoc {
         command: SlashCommand::Statusline,
-        usage: "/statusline [show|set <items>|reset]",
-        description: "configure TUI status line items",
+        usage: "/statusline [show|on|off|toggle|compact|full|set <items>|reset]",
+        description: "toggle and configure the TUI status line",
     },
     SlashCommandDoc {
         command: SlashCommand::Status,
@@ -196,14 +215,19 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
         usage: "/session",
         description: "show current session statistics",
     },
+    SlashCommandDoc {
+        command: SlashCommand::Usage,
+        usage: "/usage",
+        description: "show token quota usage for the current user",
+    },
     SlashCommandDoc {
         command: SlashCommand::System,
-        usage: "/system [set <extra_prompt>|clear]",
-        description: "show current system prompt or manage extra prompt",
+        usage: "/system [set <extra_prompt>|clear|budget]",
+        description: "show current system prompt, its token budget, or manage extra prompt",
     },
     SlashCommandDoc {
         command: SlashCommand::Mouse,
-        usage: "/mouse [auto|scroll|select]",
+        usage: "/mouse [show|auto|scroll|select]",
         description: "toggle native select/copy vs captured wheel scroll",
     },
     SlashCommandDoc {
@@ -223,7 +247,7 @@ const SLASH_COMMAND_DOCS: [SlashCommandDoc; 37] = [
     },
     SlashCommandDoc {
         command: SlashCommand::ConfigShow,
-        usage: "/config show",
+        usage: "/config show [--show-secrets]",
         description: "print current runtime config",
     },
     SlashCommandDoc {
@@ -250,6 +274,8 @@ impl SlashCommand {
             SlashCommand::Help
                 | SlashCommand::Status
                 | SlashCommand::Session
+                | SlashCommand::Usage
+                | SlashCommand::Env
                 | SlashCommand::Mouse
                 | SlashCommand::Diff
                 | SlashCommand::Mention
@@ -285,6 +311,7 @@ pub fn parse_slash_command(input: &str) -> Option<ParsedSlashCommand<'_>> {
         "help" | "h" => (SlashCommand::Help, remaining),
         "status" => (SlashCommand::Status, remaining),
         "session" => (SlashCommand::Session, remaining),
+        "usage" | "quota" => (SlashCommand::Usage, remaining),
         "system" => (SlashCommand::System, remaining),
         "mouse" => (SlashCommand::Mouse, remaining),
         "resume" | "r" => (SlashCommand::Resume, remaining),
@@ -306,14 +333,17 @@ pub fn parse_slash_command(input: &str) -> Option<ParsedSlashCommand<'_>> {
         "mention" => (SlashCommand::Mention, remaining),
         "mcp" => (SlashCommand::Mcp, remaining),
         "skills" => (SlashCommand::Skills, remaining),
+        "tools" => (SlashCommand::Tools, remaining),
         "apps" => (SlashCommand::Apps, remaining),
         "ps" => (SlashCommand::Ps, remaining),
         "clean" => (SlashCommand::Clean, remaining),
         "fork" => (SlashCommand::Fork, remaining),
         "rename" => (SlashCommand::Rename, remaining),
         "compact" => (SlashCommand::Compact, remaining),
+        "history" => (SlashCommand::History, remaining),
         "backtrack" | "back" => (SlashCommand::Backtrack, remaining),
         "debug-config" => (SlashCommand::DebugConfig, remaining),
+        "env" => (SlashCommand::Env, remaining),
         "statusline" => (SlashCommand::Statusline, remaining),
         "config" => {
             let (sub, rest) = split_head(remaining);
@@ -399,6 +429,7 @@ fn command_doc_by_name(name: &str) -> Option<&'static SlashCommandDoc> {
         "help" | "h" => SlashCommand::Help,
         "status" => SlashCommand::Status,
         "session" => SlashCommand::Session,
+        "usage" | "quota" => SlashCommand::Usage,
         "system" => SlashCommand::System,
         "mouse" => SlashCommand::Mouse,
         "resume" | "r" => SlashCommand::Resume,
@@ -420,14 +451,17 @@ fn command_doc_by_name(name: &str) -> Option<&'static SlashCommandDoc> {
         "mention" => SlashCommand::Mention,
         "mcp" => SlashCommand::Mcp,
         "skills" => SlashCommand::Skills,
+        "tools" => SlashCommand::Tools,
         "apps" => SlashCommand::Apps,
         "ps" => SlashCommand::Ps,
         "clean" => SlashCommand::Clean,
         "fork" => SlashCommand::Fork,
         "rename" => SlashCommand::Rename,
         "compact" => SlashCommand::Compact,
+        "history" => SlashCommand::History,
         "backtrack" | "back" => SlashCommand::Backtrack,
         "debug-config" => SlashCommand::DebugConfig,
+        "env" => SlashCommand::Env,
         "statusline" => SlashCommand::Statusline,
         "config" => SlashCommand::Config,
         "exit" => SlashCommand::Exit,
@@ -472,7 +506,9 @@ fn popup_usage(entry: &SlashCommandDoc) -> &'static str {
         SlashCommand::Apps => {
             "/apps [list|info|connect|install|enable|disable|disconnect|auth|logout|remove|test]"
         }
-        SlashCommand::Notify => "/notify [show|off|bell|osc9|when <always|unfocused>|<command...>]",
+        SlashCommand::Notify => {
+            "/notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]"
+        }
         _ => entry.usage,
     }
 }
@@ -491,14 +527,14 @@ fn localized_description(entry: &SlashCommandDoc, language: &str) -> String {
     let zh = match entry.command {
         SlashCommand::Model => "查看当前模型或切换默认模型",
         SlashCommand::ToolCallMode => "切换工具调用协议（别名：/mode）",
-        SlashCommand::Approvals => "查看或切换审批模式",
+        SlashCommand::Approvals => "查看或切换审批模式，查看或清除本会话审批",
         SlashCommand::Plan => "先让模型输出步骤化执行计划",
         SlashCommand::Goal => "进入或管理持续目标态",
         SlashCommand::Personality => "查看或切换回答风格偏好",
         SlashCommand::Edit => "用外部编辑器编辑并回填输入草稿",
         SlashCommand::Init => "在当前目录生成 AGENTS.md 模板",
-        SlashCommand::Agent => "查看或切换当前 agent_id 覆盖",
-        SlashCommand::Attach => "为下一轮请求挂载本地文件/图片附件",
+        SlashCommand::Agent => "查看或切换 agent_id 覆盖，或保存/加载命名配置档",
+        SlashCommand::Attach => "为下一轮请求挂载本地文件/图片附件（--remember 同时存入知识库）",
         SlashCommand::Branches => "查看会话分支树并切换会话",
         SlashCommand::Notify => "配置回合完成通知方式",
         SlashCommand::Diff => "显示当前工作区 git 变更摘要",
@@ -506,18 +542,22 @@ fn localized_description(entry: &SlashCommandDoc, language: &str) -> String {
         SlashCommand::Mention => "在工作区内搜索文件",
         SlashCommand::Mcp => "列出并管理 MCP 配置与鉴权状态",
         SlashCommand::Skills => "列出并管理本地技能",
+        SlashCommand::Tools => "查看或编辑当前会话可用的工具",
         SlashCommand::Apps => "管理应用连接器（A2A/MCP）",
         SlashCommand::Ps => "列出活动中的后台会话",
         SlashCommand::Clean => "取消活动中的后台会话",
         SlashCommand::Fork => "将当前会话分叉为新会话",
-        SlashCommand::Rename => "重命名当前会话",
+        SlashCommand::Rename => "重命名当前会话，auto 按历史重新生成标题",
         SlashCommand::Compact => "将当前会话压缩为摘要分支",
+        SlashCommand::History => "清空当前会话消息（保留会话）",
         SlashCommand::Backtrack => "查看并回填最近用户消息",
         SlashCommand::DebugConfig => "显示配置分层与最终生效值",
-        SlashCommand::Statusline => "配置 TUI 底部状态栏显示项",
+        SlashCommand::Env => "列出 WUNDER_* 环境变量及其路径是否存在",
+        SlashCommand::Statusline => "切换并配置 TUI 底部状态栏",
         SlashCommand::Status => "显示当前会话运行状态",
         SlashCommand::Session => "显示当前会话统计信息",
-        SlashCommand::System => "查看系统提示词或管理额外提示词",
+        SlashCommand::Usage => "显示当前用户的 Token 额度用量",
+        SlashCommand::System => "查看系统提示词及其 token 预算，或管理额外提示词",
         SlashCommand::Mouse => "切换鼠标自动/滚轮/选择模式",
         SlashCommand::Resume => "列出并恢复历史会话",
         SlashCommand::New => "开始新会话",
@@ -598,7 +638,7 @@ mod tests {
     fn popup_lines_show_mouse_usage_for_argument_entry() {
         let lines = popup_lines_with_language("mouse scroll", 7, "en-US");
         assert_eq!(lines.len(), 1);
-        assert!(lines[0].contains("/mouse [auto|scroll|select]"));
+        assert!(lines[0].contains("/mouse [show|auto|scroll|select]"));
     }
 
     #[test]
@@ -622,6 +662,14 @@ mod tests {
         assert_eq!(parsed.args, "");
     }
 
+    #[test]
+    fn parse_env_command_with_show_secrets() {
+        let parsed = parse_slash_command("/env --show-secrets").expect("command should parse");
+        assert_eq!(parsed.command, SlashCommand::Env);
+        assert_eq!(parsed.args, "--show-secrets");
+        assert!(SlashCommand::Env.available_during_task());
+    }
+
     #[test]
     fn parse_rename_command_with_inline_args() {
         let parsed = parse_slash_command("/rename backend flow").expect("command should parse");
@@ -629,6 +677,22 @@ mod tests {
         assert_eq!(parsed.args, "backend flow");
     }
 
+    #[test]
+    fn parse_history_clear_command() {
+        let parsed = parse_slash_command("/history clear force").expect("command should parse");
+        assert_eq!(parsed.command, SlashCommand::History);
+        assert_eq!(parsed.args, "clear force");
+        assert!(!SlashCommand::History.available_during_task());
+    }
+
+    #[test]
+    fn parse_tools_command_with_subcommand_args() {
+        let parsed =
+            parse_slash_command("/tools set read_file,list_files").expect("command should parse");
+        assert_eq!(parsed.command, SlashCommand::Tools);
+        assert_eq!(parsed.args, "set read_file,list_files");
+    }
+
     #[test]
     fn parse_backtrack_command_with_alias() {
         let parsed = parse_slash_command("/back 2").expect("command should parse");
@@ -678,6 +742,13 @@ mod tests {
         assert_eq!(parsed.args, "tree");
     }
 
+    #[test]
+    fn parse_usage_command_with_alias() {
+        let parsed = parse_slash_command("/quota").expect("command should parse");
+        assert_eq!(parsed.command, SlashCommand::Usage);
+        assert!(SlashCommand::Usage.available_during_task());
+    }
+
     #[test]
     fn busy_task_availability_matrix_smoke() {
         assert!(SlashCommand::Apps.available_during_task());
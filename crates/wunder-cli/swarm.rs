@@ -0,0 +1,584 @@
+// 蜂群任务：`wunder-cli swarm run` 由父会话派生子智能体执行目标并汇总子会话进度，`swarm status` 按父子会话元数据展示任务树，`swarm hives` 列出蜂巢及其活跃智能体。
+use crate::args::{GlobalArgs, SwarmCommand, SwarmRunCommand, SwarmSubcommand};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use wunder_server::storage::ChatSessionRecord;
+use wunder_server::subagents::list_parent_subagents;
+use wunder_server::swarm::beeroom::collect_agent_activity;
+
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Nested spawns are followed this many levels below the parent session.
+const MAX_TREE_DEPTH: usize = 6;
+const CHILD_LIST_LIMIT: i64 = 200;
+
+/// One session of a swarm with the children it spawned.
+#[derive(Debug, Clone)]
+pub(crate) struct SwarmNode {
+    pub(crate) session: ChatSessionRecord,
+    pub(crate) status: String,
+    pub(crate) terminal: bool,
+    pub(crate) summary: Option<String>,
+    pub(crate) children: Vec<SwarmNode>,
+}
+
+/// Runtime state of a child as reported by the subagent registry.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChildState {
+    pub(crate) status: String,
+    pub(crate) terminal: bool,
+    pub(crate) summary: Option<String>,
+}
+
+pub(crate) async fn handle_swarm(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: SwarmCommand,
+) -> Result<()> {
+    match command.command {
+        SwarmSubcommand::Run(cmd) => run_swarm(runtime, global, cmd).await,
+        SwarmSubcommand::Status(cmd) => {
+            let language = locale::resolve_cli_language(global);
+            let parent = cmd.parent_session_id.trim();
+            if parent.is_empty() {
+                return Err(anyhow!(locale::tr(
+                    language.as_str(),
+                    "父会话 ID 不能为空",
+                    "parent session id is required",
+                )));
+            }
+            let tree = load_swarm_tree(runtime, parent).await?.ok_or_else(|| {
+                anyhow!(locale::tr(
+                    language.as_str(),
+                    "未找到该会话",
+                    "session not found",
+                ))
+            })?;
+            if global.json {
+                println!("{}", serde_json::to_string(&swarm_tree_json(&tree))?);
+            } else {
+                for line in render_swarm_tree(&tree) {
+                    println!("{line}");
+                }
+            }
+            Ok(())
+        }
+        SwarmSubcommand::Hives => {
+            let language = locale::resolve_cli_language(global);
+            let hives = load_hive_summaries(runtime).await?;
+            if global.json {
+                let items = hives.iter().map(HiveSummary::to_json).collect::<Vec<_>>();
+                println!("{}", json!({ "hives": items }));
+            } else if hives.is_empty() {
+                println!("{}", locale::tr(language.as_str(), "暂无蜂巢", "no hives"));
+            } else {
+                for hive in &hives {
+                    println!("{}", hive.render_line(language.as_str()));
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn run_swarm(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: SwarmRunCommand,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let goal = command.goal.trim();
+    if goal.is_empty() {
+        return Err(anyhow!(locale::tr(
+            language.as_str(),
+            "目标不能为空",
+            "goal is required",
+        )));
+    }
+    let session_id = global
+        .session
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+    // Title the parent after the goal rather than the delegation instructions around it.
+    crate::ensure_cli_session_record(runtime, &session_id, Some(goal)).await?;
+    let prompt = build_swarm_prompt(language.as_str(), goal);
+    let mut request =
+        crate::build_wunder_request(runtime, global, &prompt, &session_id, None, None).await?;
+    request.stream = false;
+    if global.json {
+        println!(
+            "{}",
+            json!({ "event": "swarm_started", "data": { "session_id": session_id } })
+        );
+    } else {
+        eprintln!(
+            "{}",
+            if locale::is_zh_language(language.as_str()) {
+                format!("蜂群任务已启动，父会话: {session_id}")
+            } else {
+                format!("swarm started, parent session: {session_id}")
+            }
+        );
+    }
+
+    let orchestrator = runtime.state.kernel.orchestrator.clone();
+    let mut parent_task = tokio::spawn(async move { orchestrator.run(request).await });
+    let mut seen = HashMap::new();
+    let mut parent_result = None;
+    loop {
+        if parent_result.is_none() {
+            tokio::select! {
+                joined = &mut parent_task => {
+                    parent_result = Some(joined.map_err(|err| anyhow!("swarm task aborted: {err}"))?);
+                }
+                _ = tokio::time::sleep(PROGRESS_POLL_INTERVAL) => {}
+            }
+        } else {
+            tokio::time::sleep(PROGRESS_POLL_INTERVAL).await;
+        }
+        let Some(tree) = load_swarm_tree(runtime, &session_id).await? else {
+            // The parent session was removed while running; nothing left to show but the answer.
+            if let Some(result) = parent_result.take() {
+                println!("{}", result?.answer);
+                return Ok(());
+            }
+            continue;
+        };
+        report_progress(&tree, &mut seen, global.json, language.as_str());
+        // The parent usually waits for its children, but detached spawns may still be running.
+        if parent_result.is_some() && all_children_terminal(&tree) {
+            let response = parent_result.take().expect("parent result")?;
+            if global.json {
+                let payload = json!({
+                    "event": "final",
+                    "data": {
+                        "answer": response.answer,
+                        "session_id": session_id,
+                        "stop_reason": response.stop_reason,
+                        "tree": swarm_tree_json(&tree),
+                    }
+                });
+                println!("{}", serde_json::to_string(&payload)?);
+            } else {
+                println!("{}", response.answer);
+                println!();
+                for line in render_swarm_tree(&tree) {
+                    println!("{line}");
+                }
+            }
+            return Ok(());
+        }
+    }
+}
+
+fn build_swarm_prompt(language: &str, goal: &str) -> String {
+    let instruction = locale::tr(
+        language,
+        "请将下面的目标拆分为相互独立的子任务，使用子智能体控制工具（batch_spawn）并行派生子智能体执行，等待全部完成后汇总结果再作答。",
+        "Split the goal below into independent subtasks, delegate them in parallel with the subagent control tool (batch_spawn), wait for every child to finish and then answer with the merged result.",
+    );
+    format!("{instruction}\n\n{goal}")
+}
+
+/// Print one line (or JSON event) per child whose status changed since the last poll.
+fn report_progress(
+    tree: &SwarmNode,
+    seen: &mut HashMap<String, String>,
+    json_output: bool,
+    language: &str,
+) {
+    let mut stack = tree.children.iter().collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        stack.extend(node.children.iter());
+        let session_id = node.session.session_id.as_str();
+        if seen.get(session_id) == Some(&node.status) {
+            continue;
+        }
+        seen.insert(session_id.to_string(), node.status.clone());
+        if json_output {
+            println!(
+                "{}",
+                json!({ "event": "swarm_progress", "data": node_json(node, false) })
+            );
+            continue;
+        }
+        let name = node_label(node);
+        let line = match node.summary.as_deref().filter(|_| node.terminal) {
+            Some(summary) => format!("[{}] {name}: {summary}", node.status),
+            None => format!("[{}] {name}", node.status),
+        };
+        if locale::is_zh_language(language) {
+            eprintln!("子会话 {line}");
+        } else {
+            eprintln!("child {line}");
+        }
+    }
+}
+
+fn all_children_terminal(tree: &SwarmNode) -> bool {
+    tree.children
+        .iter()
+        .all(|child| child.terminal && all_children_terminal(child))
+}
+
+async fn load_swarm_tree(runtime: &CliRuntime, root_session_id: &str) -> Result<Option<SwarmNode>> {
+    let storage = runtime.state.storage.clone();
+    let monitor = runtime.state.monitor.clone();
+    let user_id = runtime.user_id.clone();
+    let root_session_id = root_session_id.to_string();
+    tokio::task::spawn_blocking(move || -> Result<Option<SwarmNode>> {
+        let Some(root) = storage.get_chat_session(&user_id, &root_session_id)? else {
+            return Ok(None);
+        };
+        let mut sessions = Vec::new();
+        let mut states = HashMap::new();
+        let mut frontier = vec![root.session_id.clone()];
+        for _ in 0..MAX_TREE_DEPTH {
+            let mut next = Vec::new();
+            for parent in &frontier {
+                let (children, _) = storage.list_chat_sessions_by_status(
+                    &user_id,
+                    None,
+                    Some(parent),
+                    Some("all"),
+                    0,
+                    CHILD_LIST_LIMIT,
+                )?;
+                if children.is_empty() {
+                    continue;
+                }
+                let payloads = list_parent_subagents(
+                    storage.as_ref(),
+                    Some(monitor.as_ref()),
+                    &user_id,
+                    parent,
+                    Some(CHILD_LIST_LIMIT),
+                )?;
+                for payload in payloads {
+                    if let Some(session_id) = payload.get("session_id").and_then(Value::as_str) {
+                        states.insert(session_id.to_string(), child_state(&payload));
+                    }
+                }
+                next.extend(children.iter().map(|child| child.session_id.clone()));
+                sessions.extend(children);
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+        let root_status = monitor
+            .get_record(&root.session_id)
+            .and_then(|record| {
+                record
+                    .get("status")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string)
+            })
+            .unwrap_or_else(|| root.status.clone());
+        states.insert(
+            root.session_id.clone(),
+            ChildState {
+                status: root_status,
+                ..ChildState::default()
+            },
+        );
+        Ok(Some(build_swarm_tree(root, sessions, &states)))
+    })
+    .await
+    .map_err(|err| anyhow!("load swarm tree cancelled: {err}"))?
+}
+
+/// A hive with its agents and the ones currently holding or running a session.
+#[derive(Debug, Clone)]
+pub(crate) struct HiveSummary {
+    pub(crate) hive_id: String,
+    pub(crate) name: String,
+    pub(crate) is_default: bool,
+    pub(crate) agent_total: usize,
+    pub(crate) active_agents: Vec<String>,
+}
+
+impl HiveSummary {
+    fn to_json(&self) -> Value {
+        json!({
+            "hive_id": self.hive_id,
+            "name": self.name,
+            "is_default": self.is_default,
+            "agent_total": self.agent_total,
+            "active_agent_total": self.active_agents.len(),
+            "active_agents": self.active_agents,
+        })
+    }
+
+    pub(crate) fn render_line(&self, language: &str) -> String {
+        let marker = if self.is_default { "*" } else { " " };
+        let active = if self.active_agents.is_empty() {
+            "-".to_string()
+        } else {
+            self.active_agents.join(", ")
+        };
+        if locale::is_zh_language(language) {
+            format!(
+                "{marker} {} ({})  智能体 {}  活跃: {active}",
+                self.hive_id, self.name, self.agent_total
+            )
+        } else {
+            format!(
+                "{marker} {} ({})  agents {}  active: {active}",
+                self.hive_id, self.name, self.agent_total
+            )
+        }
+    }
+}
+
+async fn load_hive_summaries(runtime: &CliRuntime) -> Result<Vec<HiveSummary>> {
+    let storage = runtime.state.storage.clone();
+    let user_store = runtime.state.user_store.clone();
+    let monitor = runtime.state.monitor.clone();
+    let user_id = runtime.user_id.clone();
+    tokio::task::spawn_blocking(move || -> Result<Vec<HiveSummary>> {
+        user_store.ensure_default_hive(&user_id)?;
+        let mut summaries = Vec::new();
+        for hive in user_store.list_hives(&user_id, false)? {
+            let agents =
+                user_store.list_user_agents_by_hive_with_default(&user_id, &hive.hive_id)?;
+            let activity = collect_agent_activity(
+                storage.as_ref(),
+                Some(monitor.as_ref()),
+                &user_id,
+                &hive.hive_id,
+                &agents,
+            )?;
+            let active_agents = agents
+                .iter()
+                .filter(|agent| {
+                    activity
+                        .get(&agent.agent_id)
+                        .is_some_and(|snapshot| !snapshot.is_idle())
+                })
+                .map(|agent| agent.name.clone())
+                .collect();
+            summaries.push(HiveSummary {
+                hive_id: hive.hive_id,
+                name: hive.name,
+                is_default: hive.is_default,
+                agent_total: agents.len(),
+                active_agents,
+            });
+        }
+        Ok(summaries)
+    })
+    .await
+    .map_err(|err| anyhow!("list hives cancelled: {err}"))?
+}
+
+fn child_state(payload: &Value) -> ChildState {
+    ChildState {
+        status: payload
+            .get("status")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        terminal: payload
+            .get("terminal")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        summary: payload
+            .get("summary")
+            .and_then(Value::as_str)
+            .map(|text| text.trim().replace('\n', " "))
+            .filter(|text| !text.is_empty()),
+    }
+}
+
+/// Link `sessions` under `root` by their `parent_session_id`. Sessions whose parent is not part of
+/// the tree are ignored; children are ordered by creation time.
+pub(crate) fn build_swarm_tree(
+    root: ChatSessionRecord,
+    sessions: Vec<ChatSessionRecord>,
+    states: &HashMap<String, ChildState>,
+) -> SwarmNode {
+    let mut by_parent: HashMap<String, Vec<ChatSessionRecord>> = HashMap::new();
+    for session in sessions {
+        if let Some(parent) = session.parent_session_id.clone() {
+            by_parent.entry(parent).or_default().push(session);
+        }
+    }
+    for children in by_parent.values_mut() {
+        children.sort_by(|left, right| left.created_at.total_cmp(&right.created_at));
+    }
+    attach_children(root, &mut by_parent, states)
+}
+
+fn attach_children(
+    session: ChatSessionRecord,
+    by_parent: &mut HashMap<String, Vec<ChatSessionRecord>>,
+    states: &HashMap<String, ChildState>,
+) -> SwarmNode {
+    let children = by_parent
+        .remove(&session.session_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| attach_children(child, by_parent, states))
+        .collect();
+    let state = states.get(&session.session_id).cloned().unwrap_or_default();
+    let status = if state.status.trim().is_empty() {
+        session.status.clone()
+    } else {
+        state.status
+    };
+    SwarmNode {
+        session,
+        status,
+        terminal: state.terminal,
+        summary: state.summary,
+        children,
+    }
+}
+
+/// `spawn_label` when the parent named the child, otherwise the session title.
+fn node_label(node: &SwarmNode) -> String {
+    node.session
+        .spawn_label
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .unwrap_or_else(|| node.session.title.trim())
+        .to_string()
+}
+
+pub(crate) fn render_swarm_tree(tree: &SwarmNode) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{} [{}] {}",
+        node_label(tree),
+        tree.status,
+        tree.session.session_id
+    )];
+    render_children(&tree.children, "", &mut lines);
+    lines
+}
+
+fn render_children(children: &[SwarmNode], prefix: &str, lines: &mut Vec<String>) {
+    for (index, child) in children.iter().enumerate() {
+        let last = index + 1 == children.len();
+        let branch = if last { "└─ " } else { "├─ " };
+        let mut line = format!(
+            "{prefix}{branch}{} [{}] {}",
+            node_label(child),
+            child.status,
+            child.session.session_id
+        );
+        if let Some(spawned_by) = child.session.spawned_by.as_deref() {
+            line.push_str(&format!(" via {spawned_by}"));
+        }
+        lines.push(line);
+        let nested = format!("{prefix}{}", if last { "   " } else { "│  " });
+        render_children(&child.children, &nested, lines);
+    }
+}
+
+pub(crate) fn swarm_tree_json(tree: &SwarmNode) -> Value {
+    node_json(tree, true)
+}
+
+fn node_json(node: &SwarmNode, with_children: bool) -> Value {
+    let mut payload = json!({
+        "session_id": node.session.session_id,
+        "parent_session_id": node.session.parent_session_id,
+        "title": node.session.title,
+        "spawn_label": node.session.spawn_label,
+        "spawned_by": node.session.spawned_by,
+        "status": node.status,
+        "terminal": node.terminal,
+        "summary": node.summary,
+    });
+    if with_children {
+        payload["children"] = Value::Array(
+            node.children
+                .iter()
+                .map(|child| node_json(child, true))
+                .collect(),
+        );
+    }
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(
+        id: &str,
+        parent: Option<&str>,
+        label: Option<&str>,
+        created_at: f64,
+    ) -> ChatSessionRecord {
+        ChatSessionRecord {
+            session_id: id.to_string(),
+            user_id: "cli_user".to_string(),
+            title: format!("title {id}"),
+            status: "active".to_string(),
+            created_at,
+            updated_at: created_at,
+            last_message_at: created_at,
+            agent_id: None,
+            tool_overrides: Vec::new(),
+            parent_session_id: parent.map(ToString::to_string),
+            parent_message_id: None,
+            spawn_label: label.map(ToString::to_string),
+            spawned_by: parent.map(|_| "subagent_control".to_string()),
+        }
+    }
+
+    #[test]
+    fn tree_follows_parent_links_in_creation_order() {
+        let states = HashMap::from([
+            (
+                "child_a".to_string(),
+                ChildState {
+                    status: "success".to_string(),
+                    terminal: true,
+                    summary: Some("done".to_string()),
+                },
+            ),
+            (
+                "child_b".to_string(),
+                ChildState {
+                    status: "running".to_string(),
+                    ..ChildState::default()
+                },
+            ),
+        ]);
+        let tree = build_swarm_tree(
+            session("root", None, None, 1.0),
+            vec![
+                session("child_b", Some("root"), Some("tests"), 3.0),
+                session("grandchild", Some("child_b"), None, 4.0),
+                session("child_a", Some("root"), Some("docs"), 2.0),
+                session("stray", Some("elsewhere"), None, 5.0),
+            ],
+            &states,
+        );
+        assert_eq!(
+            render_swarm_tree(&tree),
+            vec![
+                "title root [active] root",
+                "├─ docs [success] child_a via subagent_control",
+                "└─ tests [running] child_b via subagent_control",
+                "   └─ title grandchild [active] grandchild via subagent_control",
+            ]
+        );
+        assert!(!all_children_terminal(&tree));
+
+        let payload = swarm_tree_json(&tree);
+        assert_eq!(payload["children"][0]["spawn_label"], "docs");
+        assert_eq!(
+            payload["children"][1]["children"][0]["session_id"],
+            "grandchild"
+        );
+    }
+}
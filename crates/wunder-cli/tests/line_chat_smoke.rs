@@ -48,7 +48,6 @@ fn unique_temp_root(tag: &str) -> PathBuf {
 
 fn run_line_chat_slash(lang: &str, slash_command: &str) {
     let repo_root = repo_root();
-    let config_path = repo_root.join("config").join("wunder.yaml");
     let temp_root = unique_temp_root("line_chat");
     let mut child = Command::new(wunder_cli_exe())
         .current_dir(&repo_root)
@@ -60,8 +59,6 @@ fn run_line_chat_slash(lang: &str, slash_command: &str) {
         .arg("smoke_user")
         .arg("--temp-root")
         .arg(&temp_root)
-        .arg("--config")
-        .arg(&config_path)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
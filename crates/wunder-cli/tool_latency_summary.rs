@@ -0,0 +1,37 @@
+// /session 工具耗时摘要：列出本会话平均耗时最高的几个工具。
+use crate::locale;
+use wunder_server::tool_latency::ToolLatencyStats;
+
+const SUMMARY_TOOL_LIMIT: usize = 5;
+
+/// Lines for the slowest tools of a session; empty when no tool has run yet.
+pub(crate) fn tool_latency_lines(language: &str, stats: &[ToolLatencyStats]) -> Vec<String> {
+    if stats.is_empty() {
+        return Vec::new();
+    }
+    let is_zh = locale::is_zh_language(language);
+    let mut lines = vec![locale::tr(
+        language,
+        "工具耗时（按平均耗时排序）",
+        "tool latency (slowest first)",
+    )];
+    let format_ms =
+        |value: Option<f64>| value.map_or_else(|| "-".to_string(), |ms| format!("{ms}ms"));
+    for item in stats.iter().take(SUMMARY_TOOL_LIMIT) {
+        let mean = format_ms(item.mean_ms);
+        let p95 = format_ms(item.p95_ms);
+        let error_percent = item.error_rate * 100.0;
+        lines.push(if is_zh {
+            format!(
+                "- {}: {} 次, 平均 {mean}, p95 {p95}, 失败率 {error_percent:.1}%",
+                item.tool, item.calls
+            )
+        } else {
+            format!(
+                "- {}: {} calls, mean {mean}, p95 {p95}, errors {error_percent:.1}%",
+                item.tool, item.calls
+            )
+        });
+    }
+    lines
+}
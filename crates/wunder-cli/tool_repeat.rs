@@ -0,0 +1,340 @@
+// tool run 计时模式：`--repeat N`/`--time` 重复直接调用工具，输出每次与汇总耗时（min/mean/p95/max），默认首次失败即停止。
+use crate::args::{GlobalArgs, ToolRunCommand};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::future::Future;
+use std::time::Instant;
+
+/// Upper bound for `--repeat`, enforced by the argument parser.
+pub(crate) const MAX_TOOL_RUN_REPEAT: u32 = 1_000;
+
+/// One timed tool call; exactly one of `result` and `error` is set.
+#[derive(Debug, Clone)]
+struct ToolRunSample {
+    duration_ms: f64,
+    result: Option<Value>,
+    error: Option<String>,
+}
+
+impl ToolRunSample {
+    fn ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TimingSummary {
+    runs: usize,
+    errors: usize,
+    min_ms: f64,
+    mean_ms: f64,
+    p95_ms: f64,
+    max_ms: f64,
+}
+
+impl TimingSummary {
+    /// `None` when no run was made.
+    fn from_samples(samples: &[ToolRunSample]) -> Option<Self> {
+        let mut durations = samples
+            .iter()
+            .map(|sample| sample.duration_ms)
+            .collect::<Vec<_>>();
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort_by(f64::total_cmp);
+        // Nearest-rank p95, the same definition as the /session tool latency report.
+        let rank = (durations.len() as f64 * 0.95).ceil() as usize;
+        Some(Self {
+            runs: durations.len(),
+            errors: samples.iter().filter(|sample| !sample.ok()).count(),
+            min_ms: round_ms(durations[0]),
+            mean_ms: round_ms(durations.iter().sum::<f64>() / durations.len() as f64),
+            p95_ms: round_ms(durations[rank.clamp(1, durations.len()) - 1]),
+            max_ms: round_ms(durations[durations.len() - 1]),
+        })
+    }
+
+    fn to_value(&self) -> Value {
+        json!({
+            "runs": self.runs,
+            "errors": self.errors,
+            "min_ms": self.min_ms,
+            "mean_ms": self.mean_ms,
+            "p95_ms": self.p95_ms,
+            "max_ms": self.max_ms,
+        })
+    }
+}
+
+fn round_ms(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Calls `call` up to `repeat` times, stopping after the first error unless
+/// `continue_on_error` is set. Without `keep_all_results` only the latest successful result
+/// is kept; earlier ones are dropped as soon as a newer one arrives.
+async fn run_repeated<F, Fut>(
+    repeat: u32,
+    continue_on_error: bool,
+    keep_all_results: bool,
+    mut call: F,
+) -> Vec<ToolRunSample>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    let mut samples: Vec<ToolRunSample> = Vec::new();
+    let mut last_result_index: Option<usize> = None;
+    for _ in 0..repeat {
+        let started = Instant::now();
+        let outcome = call().await;
+        let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let sample = match outcome {
+            Ok(result) => ToolRunSample {
+                duration_ms,
+                result: Some(result),
+                error: None,
+            },
+            Err(err) => ToolRunSample {
+                duration_ms,
+                result: None,
+                error: Some(err.to_string()),
+            },
+        };
+        let failed = !sample.ok();
+        if !failed && !keep_all_results {
+            if let Some(index) = last_result_index.replace(samples.len()) {
+                samples[index].result = None;
+            }
+        }
+        samples.push(sample);
+        if failed && !continue_on_error {
+            break;
+        }
+    }
+    samples
+}
+
+/// `--json` payload: every run in order plus the timing summary.
+fn timing_report_value(samples: &[ToolRunSample]) -> Value {
+    let results = samples
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| {
+            json!({
+                "run": index + 1,
+                "ok": sample.ok(),
+                "duration_ms": round_ms(sample.duration_ms),
+                "result": sample.result,
+                "error": sample.error,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "results": results,
+        "timing": TimingSummary::from_samples(samples).map(|summary| summary.to_value()),
+    })
+}
+
+fn timing_text_lines(samples: &[ToolRunSample], repeat: u32, is_zh: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (index, sample) in samples.iter().enumerate() {
+        let run = index + 1;
+        let duration = round_ms(sample.duration_ms);
+        lines.push(match (sample.error.as_deref(), is_zh) {
+            (None, true) => format!("- 第 {run}/{repeat} 次: {duration}ms 成功"),
+            (None, false) => format!("- run {run}/{repeat}: {duration}ms ok"),
+            (Some(err), true) => format!("- 第 {run}/{repeat} 次: {duration}ms 失败: {err}"),
+            (Some(err), false) => format!("- run {run}/{repeat}: {duration}ms error: {err}"),
+        });
+    }
+    if let Some(summary) = TimingSummary::from_samples(samples) {
+        let TimingSummary {
+            runs,
+            errors,
+            min_ms,
+            mean_ms,
+            p95_ms,
+            max_ms,
+        } = summary;
+        lines.push(if is_zh {
+            format!(
+                "耗时: {runs} 次, 失败 {errors}, min {min_ms}ms, mean {mean_ms}ms, p95 {p95_ms}ms, max {max_ms}ms"
+            )
+        } else {
+            format!(
+                "timing: {runs} runs, {errors} errors, min {min_ms}ms, mean {mean_ms}ms, p95 {p95_ms}ms, max {max_ms}ms"
+            )
+        });
+    }
+    lines
+}
+
+/// `tool run --repeat/--time`. Text mode prints the last successful result once, then the
+/// per-run and aggregate latency; any failed run makes the command exit non-zero.
+pub(crate) async fn run_tool_timed(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: &ToolRunCommand,
+    args: Value,
+) -> Result<()> {
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    let samples = run_repeated(
+        command.repeat,
+        command.continue_on_error,
+        global.json,
+        || crate::execute_tool_direct(runtime, global, &command.name, &args),
+    )
+    .await;
+
+    if global.json {
+        println!("{}", serde_json::to_string(&timing_report_value(&samples))?);
+    } else {
+        if let Some(result) = samples
+            .iter()
+            .rev()
+            .find_map(|sample| sample.result.as_ref())
+        {
+            println!("{}", serde_json::to_string_pretty(result)?);
+        }
+        for line in timing_text_lines(&samples, command.repeat, is_zh) {
+            println!("{line}");
+        }
+    }
+
+    let errors = samples.iter().filter(|sample| !sample.ok()).count();
+    if errors == 0 {
+        return Ok(());
+    }
+    Err(anyhow!(if is_zh {
+        format!("{} 次运行中有 {errors} 次失败", samples.len())
+    } else {
+        format!("{errors} of {} runs failed", samples.len())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(duration_ms: f64, ok: bool) -> ToolRunSample {
+        ToolRunSample {
+            duration_ms,
+            result: ok.then(|| json!({ "ok": true })),
+            error: (!ok).then(|| "boom".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn timing_summary_covers_every_run_of_a_mock_tool() {
+        let mut calls = 0;
+        let samples = run_repeated(5, false, true, || {
+            calls += 1;
+            let value = json!({ "call": calls });
+            async move { Ok(value) }
+        })
+        .await;
+        assert_eq!(samples.len(), 5);
+
+        let report = timing_report_value(&samples);
+        let results = report["results"].as_array().expect("results");
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0]["run"], 1);
+        assert_eq!(results[4]["result"]["call"], 5);
+        assert!(results.iter().all(|item| item["ok"] == true));
+        assert!(results.iter().all(|item| item["error"].is_null()));
+        let timing = &report["timing"];
+        assert_eq!(timing["runs"], 5);
+        assert_eq!(timing["errors"], 0);
+        for key in ["min_ms", "mean_ms", "p95_ms", "max_ms"] {
+            assert!(timing[key].as_f64().is_some_and(|ms| ms >= 0.0), "{key}");
+        }
+        assert!(timing["min_ms"].as_f64() <= timing["max_ms"].as_f64());
+    }
+
+    #[tokio::test]
+    async fn first_error_stops_unless_continue_on_error() {
+        let flaky = |fail_on: usize| {
+            let mut calls = 0;
+            move || {
+                calls += 1;
+                let failed = calls == fail_on;
+                async move {
+                    if failed {
+                        Err(anyhow!("boom"))
+                    } else {
+                        Ok(json!({}))
+                    }
+                }
+            }
+        };
+        assert_eq!(run_repeated(4, false, true, flaky(2)).await.len(), 2);
+        let samples = run_repeated(4, true, true, flaky(2)).await;
+        assert_eq!(samples.len(), 4);
+        assert_eq!(TimingSummary::from_samples(&samples).unwrap().errors, 1);
+    }
+
+    #[tokio::test]
+    async fn text_mode_keeps_only_the_last_result() {
+        let mut calls = 0;
+        let samples = run_repeated(4, true, false, || {
+            calls += 1;
+            let outcome = if calls == 4 {
+                Err(anyhow!("boom"))
+            } else {
+                Ok(json!({ "call": calls }))
+            };
+            async move { outcome }
+        })
+        .await;
+        assert_eq!(samples.len(), 4);
+        let kept = samples
+            .iter()
+            .filter_map(|sample| sample.result.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(kept, vec![&json!({ "call": 3 })]);
+        assert_eq!(timing_text_lines(&samples, 4, false).len(), 5);
+    }
+
+    #[test]
+    fn repeat_is_bounded_by_the_parser() {
+        use clap::Parser;
+        let parse = |repeat: &str| {
+            crate::args::Cli::try_parse_from([
+                "wunder-cli",
+                "tool",
+                "run",
+                "echo",
+                "--repeat",
+                repeat,
+            ])
+        };
+        assert!(parse("1").is_ok());
+        assert!(parse(&MAX_TOOL_RUN_REPEAT.to_string()).is_ok());
+        assert!(parse("0").is_err());
+        assert!(parse(&(MAX_TOOL_RUN_REPEAT + 1).to_string()).is_err());
+    }
+
+    #[test]
+    fn summary_uses_nearest_rank_p95() {
+        let samples = (1..=20)
+            .map(|ms| sample(f64::from(ms), ms != 7))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            TimingSummary::from_samples(&samples),
+            Some(TimingSummary {
+                runs: 20,
+                errors: 1,
+                min_ms: 1.0,
+                mean_ms: 10.5,
+                p95_ms: 19.0,
+                max_ms: 20.0,
+            })
+        );
+        assert_eq!(TimingSummary::from_samples(&[]), None);
+    }
+}
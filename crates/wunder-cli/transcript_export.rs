@@ -0,0 +1,168 @@
+// TUI 会话导出：Ctrl+S 将当前对话渲染为 Markdown，写入启动目录下带时间戳的文件（不覆盖已有文件）。
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptRole {
+    User,
+    Assistant,
+    Reasoning,
+    Tool,
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TranscriptExportItem<'a> {
+    pub role: TranscriptRole,
+    pub text: &'a str,
+}
+
+/// Render a conversation transcript as Markdown, as saved by the TUI's Ctrl+S export.
+pub fn render_transcript_markdown(
+    session_id: &str,
+    exported_at: DateTime<Local>,
+    items: &[TranscriptExportItem<'_>],
+) -> String {
+    let mut output = String::from("# Wunder transcript\n\n");
+    if !session_id.trim().is_empty() {
+        output.push_str(&format!("- session: `{}`\n", session_id.trim()));
+    }
+    output.push_str(&format!(
+        "- exported_at: {}\n",
+        exported_at.format("%Y-%m-%d %H:%M:%S")
+    ));
+
+    for item in items {
+        let text = item.text.trim_end();
+        if text.trim().is_empty() {
+            continue;
+        }
+        output.push('\n');
+        match item.role {
+            TranscriptRole::User => {
+                output.push_str("## User\n\n");
+                output.push_str(text);
+                output.push('\n');
+            }
+            TranscriptRole::Assistant => {
+                output.push_str("## Assistant\n\n");
+                output.push_str(text);
+                output.push('\n');
+            }
+            TranscriptRole::Reasoning => {
+                output.push_str("<details>\n<summary>Reasoning</summary>\n\n");
+                output.push_str(text);
+                output.push_str("\n\n</details>\n");
+            }
+            TranscriptRole::Tool => push_fenced_block(&mut output, "Tool", text),
+            TranscriptRole::Info => {
+                for line in text.lines() {
+                    output.push_str("> ");
+                    output.push_str(line);
+                    output.push('\n');
+                }
+            }
+            TranscriptRole::Error => push_fenced_block(&mut output, "Error", text),
+        }
+    }
+    output
+}
+
+fn push_fenced_block(output: &mut String, title: &str, text: &str) {
+    // Use a fence longer than any backtick run in the body so embedded code blocks stay intact.
+    let longest_run = text.split(|ch| ch != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    output.push_str(&format!("**{title}**\n\n{fence}text\n{text}\n{fence}\n"));
+}
+
+pub fn transcript_export_file_name(exported_at: DateTime<Local>) -> String {
+    format!(
+        "wunder-transcript-{}.md",
+        exported_at.format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Write the Markdown into `dir` under a timestamped name, never overwriting an existing file.
+pub fn write_transcript_export(
+    dir: &Path,
+    exported_at: DateTime<Local>,
+    markdown: &str,
+) -> Result<PathBuf> {
+    let base_name = transcript_export_file_name(exported_at);
+    let mut path = dir.join(&base_name);
+    let mut suffix = 1usize;
+    while path.exists() {
+        let stem = base_name.trim_end_matches(".md");
+        path = dir.join(format!("{stem}-{suffix}.md"));
+        suffix += 1;
+    }
+    fs::write(&path, markdown)
+        .with_context(|| format!("write transcript export failed: {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 1, 2, 3, 4, 5)
+            .single()
+            .expect("valid local time")
+    }
+
+    #[test]
+    fn render_transcript_markdown_groups_roles() {
+        let markdown = render_transcript_markdown(
+            "sess_1",
+            fixed_time(),
+            &[
+                TranscriptExportItem {
+                    role: TranscriptRole::User,
+                    text: "hello",
+                },
+                TranscriptExportItem {
+                    role: TranscriptRole::Tool,
+                    text: "run ```x```",
+                },
+                TranscriptExportItem {
+                    role: TranscriptRole::Info,
+                    text: "   ",
+                },
+                TranscriptExportItem {
+                    role: TranscriptRole::Assistant,
+                    text: "hi",
+                },
+            ],
+        );
+        assert_eq!(
+            markdown,
+            "# Wunder transcript\n\n- session: `sess_1`\n- exported_at: 2026-01-02 03:04:05\n\n## User\n\nhello\n\n**Tool**\n\n````text\nrun ```x```\n````\n\n## Assistant\n\nhi\n"
+        );
+    }
+
+    #[test]
+    fn write_transcript_export_avoids_overwriting() {
+        let dir = std::env::temp_dir().join(format!(
+            "wunder-cli-transcript-export-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        let first = write_transcript_export(&dir, fixed_time(), "a").expect("first export");
+        let second = write_transcript_export(&dir, fixed_time(), "b").expect("second export");
+        assert_eq!(
+            first.file_name().and_then(|name| name.to_str()),
+            Some("wunder-transcript-20260102-030405.md")
+        );
+        assert_eq!(
+            second.file_name().and_then(|name| name.to_str()),
+            Some("wunder-transcript-20260102-030405-1.md")
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
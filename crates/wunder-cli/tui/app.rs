@@ -25,7 +25,7 @@ use crate::args::GlobalArgs;
 use crate::command_session_display::{
     CommandSessionDisplayState, CommandSessionUpdate, CommandSessionView,
 };
-use crate::render::FinalEvent;
+use crate::render::{format_tool_run_line, FinalEvent};
 use crate::runtime::CliRuntime;
 use crate::slash_command::{self, ParsedSlashCommand, SlashCommand};
 
@@ -54,14 +54,22 @@ const PASTE_BURST_ACTIVE_IDLE_TIMEOUT: Duration = Duration::from_millis(8);
 const PASTE_BURST_ACTIVE_IDLE_TIMEOUT: Duration = Duration::from_millis(60);
 const SUPPRESSED_CLIPBOARD_PASTE_TIMEOUT: Duration = Duration::from_millis(1200);
 mod commands;
+mod export;
 mod input_placeholders;
+mod keybindings;
 
 pub(super) mod helpers;
 mod patch_log;
+mod status_bar;
+mod transcript_search;
 
+use export::StatusNotice;
 use helpers::*;
 use input_placeholders::*;
+use keybindings::*;
 use patch_log::*;
+use status_bar::*;
+use transcript_search::*;
 
 const STATUSLINE_ITEM_KEYS: &[&str] = &[
     "running", "usage", "scroll", "mouse", "focus", "context", "cwd", "project", "branch",
@@ -166,6 +174,23 @@ impl MouseMode {
     fn captures_mouse(self) -> bool {
         matches!(self, Self::Scroll)
     }
+
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "scroll" | "wheel" => Some(Self::Scroll),
+            "select" | "copy" | "selection" => Some(Self::Select),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Scroll => "scroll",
+            Self::Select => "select",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -281,6 +306,8 @@ pub struct TuiApp {
     suppressed_clipboard_paste: String,
     suppressed_clipboard_paste_last_at: Option<Instant>,
     statusline_items: Vec<String>,
+    statusline_visible: bool,
+    statusline_format: StatusLineFormat,
     workspace_project_name: Option<String>,
     workspace_git_branch: Option<String>,
     mouse_passthrough_until: Option<Instant>,
@@ -303,6 +330,9 @@ pub struct TuiApp {
     reasoning_markdown_stream: Option<StreamedMarkdownState>,
     command_sessions: CommandSessionDisplayState,
     command_log_indices: HashMap<String, usize>,
+    transcript_search: TranscriptSearchState,
+    status_notice: Option<StatusNotice>,
+    key_bindings: KeyBindings,
 }
 
 impl TuiApp {
@@ -394,6 +424,8 @@ impl TuiApp {
             suppressed_clipboard_paste: String::new(),
             suppressed_clipboard_paste_last_at: None,
             statusline_items: Vec::new(),
+            statusline_visible: true,
+            statusline_format: StatusLineFormat::default(),
             workspace_project_name: None,
             workspace_git_branch: None,
             mouse_passthrough_until: None,
@@ -416,6 +448,9 @@ impl TuiApp {
             reasoning_markdown_stream: None,
             command_sessions: CommandSessionDisplayState::default(),
             command_log_indices: HashMap::new(),
+            transcript_search: TranscriptSearchState::default(),
+            status_notice: None,
+            key_bindings: KeyBindings::default(),
         };
         app.load_persisted_history();
         app.load_popup_recents();
@@ -440,9 +475,10 @@ impl TuiApp {
                 }
             }
         }
+        let workspace_ignore = app.runtime.load_workspace_ignore().await;
         app.workspace_files = tokio::task::spawn_blocking({
             let root = app.runtime.launch_dir.clone();
-            move || build_workspace_file_index(&root)
+            move || build_workspace_file_index(&root, workspace_ignore.as_ref())
         })
         .await
         .unwrap_or_default();
@@ -459,6 +495,7 @@ impl TuiApp {
             LogKind::Info,
             "wunder-cli tui mode. type /help for commands.".to_string(),
         );
+        app.load_key_bindings();
         if !app.pending_attachments.is_empty() {
             app.push_log(
                 LogKind::Info,
@@ -495,43 +532,6 @@ impl TuiApp {
         crate::locale::is_zh_language(self.display_language.as_str())
     }
 
-    pub fn status_line(&self) -> String {
-        let parts = self.status_line_parts();
-        if self.statusline_items.is_empty() {
-            let mut items = Vec::new();
-            for key in ["cwd", "branch", "elapsed", "speed", "tools", "context"] {
-                if let Some(value) = parts.get(key) {
-                    let value = value.trim();
-                    if !value.is_empty() {
-                        items.push(value.to_string());
-                    }
-                }
-            }
-            if items.is_empty() {
-                return "  -".to_string();
-            }
-            return format!("  {}", items.join(" | "));
-        }
-
-        let mut items = Vec::new();
-        for key in &self.statusline_items {
-            if let Some(value) = parts.get(key.as_str()) {
-                let value = value.trim();
-                if !value.is_empty() {
-                    items.push(value.to_string());
-                }
-            }
-        }
-        if items.is_empty() {
-            if self.is_zh_language() {
-                return "  状态栏：当前没有启用条目，可输入 /statusline reset 恢复默认值"
-                    .to_string();
-            }
-            return "  status line: empty selection, run /statusline reset".to_string();
-        }
-        format!("  {}", items.join(" | "))
-    }
-
     pub fn request_redraw(&self) {
         self.frame_requester.schedule_frame();
     }
@@ -549,7 +549,11 @@ impl TuiApp {
             self.frame_requester.schedule_frame_in(PASTE_BURST_CHAR_GAP);
             return;
         }
-        if self.approval_rx.is_some() || self.active_approval.is_some() || ctrl_c_pending {
+        if self.approval_rx.is_some()
+            || self.active_approval.is_some()
+            || ctrl_c_pending
+            || self.active_status_notice().is_some()
+        {
             self.frame_requester
                 .schedule_frame_in(Duration::from_millis(90));
         }
@@ -564,7 +568,9 @@ impl TuiApp {
     }
 
     pub fn activity_highlighted(&self) -> bool {
-        self.active_approval.is_some()
+        self.active_status_notice().is_some()
+            || self.transcript_search_activity_line().is_some()
+            || self.active_approval.is_some()
             || self.active_inquiry_panel.is_some()
             || self.resume_picker.is_some()
             || self
@@ -585,6 +591,14 @@ impl TuiApp {
             };
         }
 
+        if let Some(notice) = self.active_status_notice() {
+            return notice.to_string();
+        }
+
+        if let Some(line) = self.transcript_search_activity_line() {
+            return line;
+        }
+
         if self.resume_picker.is_some() {
             return if is_zh {
                 "会话恢复面板已打开 · Enter 恢复 · Esc 取消".to_string()
@@ -1076,6 +1090,16 @@ impl TuiApp {
         let Ok(value) = serde_json::from_str::<Value>(&text) else {
             return;
         };
+        if let Some(visible) = value.get("visible").and_then(Value::as_bool) {
+            self.statusline_visible = visible;
+        }
+        if let Some(format) = value
+            .get("format")
+            .and_then(Value::as_str)
+            .and_then(StatusLineFormat::parse)
+        {
+            self.statusline_format = format;
+        }
         let Some(items) = value.get("items").and_then(Value::as_array) else {
             return;
         };
@@ -1095,7 +1119,11 @@ impl TuiApp {
                 return;
             }
         }
-        let payload = json!({ "items": self.statusline_items });
+        let payload = json!({
+            "items": self.statusline_items,
+            "visible": self.statusline_visible,
+            "format": self.statusline_format.as_str(),
+        });
         let Ok(text) = serde_json::to_vec_pretty(&payload) else {
             return;
         };
@@ -1548,7 +1576,8 @@ impl TuiApp {
                 "/ 命令 · Enter 发送".to_string(),
                 "Shift + Enter / Ctrl + J 换行 · Tab 补全".to_string(),
                 "@ 文件路径 · Ctrl + V / Shift + Insert 粘贴图片".to_string(),
-                "F3 查看输出 · Ctrl + C 退出".to_string(),
+                "F3 查看输出 · F3 后按 / 搜索 · Ctrl + C 退出".to_string(),
+                "Ctrl + S 导出会话为 Markdown".to_string(),
                 "Esc 关闭快捷键 · 拖入图片或文件即可附加".to_string(),
             ];
         }
@@ -1556,7 +1585,8 @@ impl TuiApp {
             "/ for commands                             enter to send".to_string(),
             "shift + enter for newline                  tab to complete".to_string(),
             "@ for file paths                           ctrl + v to paste images".to_string(),
-            "f3 to view transcript                      ctrl + c to exit".to_string(),
+            "f3 to view transcript (/ to search)        ctrl + c to exit".to_string(),
+            "ctrl + s to save transcript as markdown".to_string(),
             "esc to close shortcuts                    drag images/files to attach".to_string(),
         ]
     }
@@ -1798,12 +1828,30 @@ impl TuiApp {
             MouseMode::Auto => {
                 "mouse mode: auto (native selection and terminal wheel scrolling enabled; switch to scroll for app-captured transcript scrolling)"
             }
-            MouseMode::Scroll => "mouse mode: scroll (capture wheel events for transcript scrolling)",
+            MouseMode::Scroll => {
+                "mouse mode: scroll (mouse captured: terminal drag-to-select/copy is disabled; hold Shift in most terminals or run /mouse select to restore it)"
+            }
             MouseMode::Select => {
-                "mouse mode: select/copy (native terminal selection enabled)"
+                "mouse mode: select/copy (mouse released: native terminal selection and copy/paste work again)"
             }
         };
         self.push_log(LogKind::Info, notice.to_string());
+        // Persisting is best effort: a read-only temp root must not break the toggle itself.
+        if let Err(err) = self.runtime.save_mouse_mode(mode.as_str()) {
+            let prefix = crate::locale::tr(
+                self.display_language.as_str(),
+                "保存鼠标模式失败:",
+                "failed to save mouse mode:",
+            );
+            self.push_log(LogKind::Error, format!("{prefix} {err}"));
+        }
+    }
+
+    /// Apply the mouse mode saved by a previous session without logging or re-saving it.
+    pub fn restore_mouse_mode(&mut self, raw: &str) {
+        if let Some(mode) = MouseMode::parse(raw) {
+            self.mouse_mode = mode;
+        }
     }
 
     fn toggle_mouse_mode(&mut self) {
@@ -2016,6 +2064,14 @@ impl TuiApp {
             })
             .collect();
 
+        if let Some(query) = self.transcript_search_highlight() {
+            let match_style = super::theme::search_match();
+            transcript_lines = transcript_lines
+                .into_iter()
+                .map(|line| highlight_search_matches(line, query.as_str(), match_style))
+                .collect();
+        }
+
         if transcript_lines.is_empty() {
             let placeholder = if is_zh {
                 "还没有对话内容，输入提示词开始。"
@@ -3109,6 +3165,9 @@ impl TuiApp {
         if self.try_consume_suppressed_clipboard_key(key) {
             return Ok(());
         }
+        let Some(key) = self.translate_bound_key(key) else {
+            return Ok(());
+        };
 
         let plain_char_input = matches!(key.code, KeyCode::Char(_))
             && (matches!(key.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT)
@@ -3127,6 +3186,11 @@ impl TuiApp {
             return Ok(());
         }
 
+        if self.transcript_search_editing() && self.handle_transcript_search_key(key) {
+            self.reset_plain_char_burst();
+            return Ok(());
+        }
+
         if is_paste_shortcut(key) {
             self.reset_plain_char_burst();
             self.focus_area = FocusArea::Input;
@@ -3188,6 +3252,10 @@ impl TuiApp {
                     self.transcript_selected = None;
                     return Ok(());
                 }
+                KeyCode::Char('s') => {
+                    self.export_transcript_to_file();
+                    return Ok(());
+                }
                 KeyCode::Char('n') => {
                     if self.busy {
                         self.push_log(
@@ -3605,9 +3673,19 @@ impl TuiApp {
         }
         match key.code {
             KeyCode::Esc => {
-                self.focus_area = FocusArea::Input;
+                if self.transcript_search_highlight().is_some() {
+                    self.close_transcript_search();
+                } else {
+                    self.focus_area = FocusArea::Input;
+                }
+                true
+            }
+            KeyCode::Char('/') => {
+                self.open_transcript_search();
                 true
             }
+            KeyCode::Char('n') => self.jump_transcript_search(true),
+            KeyCode::Char('N') => self.jump_transcript_search(false),
             KeyCode::Enter => {
                 if !self.prefill_selected_user_message() {
                     self.focus_area = FocusArea::Input;
@@ -4062,7 +4140,9 @@ impl TuiApp {
             self.push_log(LogKind::Error, err.to_string());
             return Ok(());
         }
-        self.start_stream_request(prompt, user_echo, request_attachments)
+        let remember_attachments =
+            crate::attachments::remember_requested(&self.pending_attachments);
+        self.start_stream_request(prompt, user_echo, request_attachments, remember_attachments)
             .await?;
         self.clear_pending_attachments();
         Ok(())
@@ -4073,6 +4153,7 @@ impl TuiApp {
         prompt: String,
         user_echo: String,
         attachments: Option<Vec<wunder_server::schemas::AttachmentPayload>>,
+        remember_attachments: bool,
     ) -> Result<()> {
         if self.busy {
             self.push_log(
@@ -4112,6 +4193,7 @@ impl TuiApp {
             attachments,
         )
         .await?;
+        request.remember_attachments |= remember_attachments;
         request.approval_tx = Some(approval_tx);
         let orchestrator = self.runtime.state.kernel.orchestrator.clone();
         let frame_requester = self.frame_requester.clone();
@@ -4867,6 +4949,11 @@ impl TuiApp {
             "progress" => {
                 // Skip progress events in tool-only workflow rendering.
             }
+            "reasoning" => {
+                // Content already arrived via `reasoning_delta`/`llm_output`; only count it here.
+                self.session_stats.reasoning_events =
+                    self.session_stats.reasoning_events.saturating_add(1);
+            }
             "llm_request" => {
                 self.session_stats.model_calls = self.session_stats.model_calls.saturating_add(1);
                 self.active_assistant = None;
@@ -4949,6 +5036,11 @@ impl TuiApp {
                     self.push_generic_tool_call_log(tool, args);
                 }
             }
+            "tool_started" | "tool_finished" => {
+                if let Some(line) = format_tool_run_line(event.event.as_str(), payload) {
+                    self.push_log(LogKind::Tool, line);
+                }
+            }
             "command_session_start" => {
                 self.stop_llm_active_window();
                 self.stream_saw_output = true;
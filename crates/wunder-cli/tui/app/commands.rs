@@ -41,6 +41,23 @@ impl TuiApp {
                     self.push_log(LogKind::Info, line);
                 }
             }
+            SlashCommand::Usage => {
+                match crate::usage_display::load_quota_usage_lines(
+                    &self.runtime,
+                    self.display_language.as_str(),
+                )
+                .await
+                {
+                    Ok(lines) => {
+                        for line in lines {
+                            self.push_log(LogKind::Info, line);
+                        }
+                    }
+                    Err(err) => {
+                        self.push_log(LogKind::Error, format!("usage failed: {err}"));
+                    }
+                }
+            }
             SlashCommand::System => {
                 self.handle_system_slash(command.args).await?;
             }
@@ -65,7 +82,8 @@ impl TuiApp {
                 self.apply_config_from_slash(command).await?;
             }
             SlashCommand::ConfigShow => {
-                self.show_config_snapshot().await?;
+                let (show_secrets, _) = crate::config_redact::take_show_secrets_flag(command.args);
+                self.show_config_snapshot(show_secrets).await?;
             }
             SlashCommand::Model => {
                 self.handle_model_slash(command.args).await?;
@@ -115,6 +133,9 @@ impl TuiApp {
             SlashCommand::Skills => {
                 self.handle_skills_slash(command.args).await?;
             }
+            SlashCommand::Tools => {
+                self.handle_tools_slash(command.args).await?;
+            }
             SlashCommand::Apps => {
                 self.handle_apps_slash(command.args).await?;
             }
@@ -133,11 +154,24 @@ impl TuiApp {
             SlashCommand::Compact => {
                 self.handle_compact_slash().await?;
             }
+            SlashCommand::History => {
+                self.handle_history_slash(command.args).await?;
+            }
             SlashCommand::Backtrack => {
                 self.handle_backtrack_slash(command.args);
             }
             SlashCommand::DebugConfig => {
-                self.show_debug_config_snapshot().await?;
+                let (show_secrets, _) = crate::config_redact::take_show_secrets_flag(command.args);
+                self.show_debug_config_snapshot(show_secrets).await?;
+            }
+            SlashCommand::Env => {
+                let (show_secrets, _) = crate::config_redact::take_show_secrets_flag(command.args);
+                for line in crate::env_display::env_report_lines(
+                    self.display_language.as_str(),
+                    show_secrets,
+                ) {
+                    self.push_log(LogKind::Info, line);
+                }
             }
             SlashCommand::Statusline => {
                 self.handle_statusline_slash(command.args);
@@ -153,7 +187,7 @@ impl TuiApp {
         Ok(())
     }
 
-    async fn show_config_snapshot(&mut self) -> Result<()> {
+    async fn show_config_snapshot(&mut self, show_secrets: bool) -> Result<()> {
         let config = self.runtime.state.config_store.get().await;
         let model = self
             .runtime
@@ -186,6 +220,7 @@ impl TuiApp {
             "approval_mode": self.approval_mode,
             "max_rounds": self.model_max_rounds,
             "max_context": max_context,
+            "model_config": crate::config_redact::config_value_for_display(&model_entry, show_secrets),
             "context_used": self.session_stats.context_used_tokens.max(0),
             "context_left_percent": crate::context_left_percent(
                 self.session_stats.context_used_tokens,
@@ -433,42 +468,48 @@ impl TuiApp {
                     crate::locale::tr(self.display_language.as_str(), "选择", "select")
                 }
             };
+            let captured = self.mouse_mode.captures_mouse();
             if self.is_zh_language() {
-                self.push_log(LogKind::Info, format!("鼠标模式: {mode}"));
+                let capture = if captured {
+                    "已接管鼠标"
+                } else {
+                    "终端原生选择可用"
+                };
+                self.push_log(
+                    LogKind::Info,
+                    format!("鼠标模式: {mode}（{capture}，重启后保持）"),
+                );
             } else {
-                self.push_log(LogKind::Info, format!("mouse mode: {mode}"));
+                let capture = if captured {
+                    "mouse captured"
+                } else {
+                    "native selection available"
+                };
+                self.push_log(
+                    LogKind::Info,
+                    format!("mouse mode: {mode} ({capture}, kept across restarts)"),
+                );
             }
             self.push_log(
                 LogKind::Info,
                 crate::locale::tr(
                     self.display_language.as_str(),
-                    "用法: /mouse [auto|scroll|select]  （F2 可切换）",
-                    "usage: /mouse [auto|scroll|select]  (F2 optional)",
+                    "用法: /mouse [show|auto|scroll|select]  （F2 可切换）",
+                    "usage: /mouse [show|auto|scroll|select]  (F2 optional)",
                 ),
             );
             return;
         }
 
-        if cleaned.eq_ignore_ascii_case("auto") {
-            self.set_mouse_mode(MouseMode::Auto);
-            return;
-        }
-        if cleaned.eq_ignore_ascii_case("scroll") {
-            self.set_mouse_mode(MouseMode::Scroll);
-            return;
-        }
-        if cleaned.eq_ignore_ascii_case("select")
-            || cleaned.eq_ignore_ascii_case("copy")
-            || cleaned.eq_ignore_ascii_case("selection")
-        {
-            self.set_mouse_mode(MouseMode::Select);
+        if let Some(mode) = MouseMode::parse(cleaned) {
+            self.set_mouse_mode(mode);
             return;
         }
 
         self.push_log(LogKind::Error, format!("invalid /mouse args: {cleaned}"));
         self.push_log(
             LogKind::Info,
-            "usage: /mouse [auto|scroll|select]  (F2 optional)".to_string(),
+            "usage: /mouse [show|auto|scroll|select]  (F2 optional)".to_string(),
         );
     }
 
@@ -748,6 +789,19 @@ impl TuiApp {
     }
 
     async fn handle_approvals_slash(&mut self, args: &str) -> Result<()> {
+        if let Some(lines) = crate::session_approvals::run_session_approvals_command(
+            &self.runtime,
+            self.display_language.as_str(),
+            self.session_id.as_str(),
+            args,
+        )
+        .await?
+        {
+            for line in lines {
+                self.push_log(LogKind::Info, line);
+            }
+            return Ok(());
+        }
         let cleaned = args.trim();
         if cleaned.is_empty() || cleaned.eq_ignore_ascii_case("show") {
             if self.is_zh_language() {
@@ -762,8 +816,8 @@ impl TuiApp {
                 LogKind::Info,
                 crate::locale::tr(
                     self.display_language.as_str(),
-                    "用法: /approvals [show|suggest|auto_edit|full_auto]",
-                    "usage: /approvals [show|suggest|auto_edit|full_auto]",
+                    &format!("用法: {}", crate::session_approvals::APPROVALS_SLASH_USAGE),
+                    &format!("usage: {}", crate::session_approvals::APPROVALS_SLASH_USAGE),
                 ),
             );
             return Ok(());
@@ -807,7 +861,7 @@ impl TuiApp {
     }
 
     async fn handle_diff_slash(&mut self, args: &str) -> Result<()> {
-        let root = self.runtime.launch_dir.clone();
+        let root = self.runtime.repo_root.clone();
         let language = self.display_language.clone();
         let action = match crate::parse_diff_slash_action(args) {
             Ok(action) => action,
@@ -886,16 +940,28 @@ impl TuiApp {
             return Ok(());
         }
 
-        let root = self.runtime.launch_dir.clone();
+        let options = match crate::review::parse_review_args(args) {
+            Ok(options) => options,
+            Err(err) => {
+                self.push_log(LogKind::Error, err);
+                return Ok(());
+            }
+        };
+        if options.output.is_some() || options.fail_on.is_some() {
+            self.push_log(
+                LogKind::Info,
+                crate::locale::tr(
+                    self.display_language.as_str(),
+                    "--output 与 --fail-on 仅在行模式和 `wunder-cli review` 中生效，本次按结构化审查执行",
+                    "--output and --fail-on apply only in line mode and `wunder-cli review`; running a structured review",
+                ),
+            );
+        }
+        let root = self.runtime.repo_root.clone();
         let focus = args.trim().to_string();
-        let focus_for_prompt = focus.clone();
         let language = self.display_language.clone();
         let prompt = match tokio::task::spawn_blocking(move || {
-            crate::build_review_prompt_with_language(
-                root.as_path(),
-                &focus_for_prompt,
-                language.as_str(),
-            )
+            crate::review::build_review_prompt(root.as_path(), &options, language.as_str())
         })
         .await
         {
@@ -919,7 +985,8 @@ impl TuiApp {
         } else {
             format!("/review {focus}")
         };
-        self.start_stream_request(prompt, user_echo, None).await
+        self.start_stream_request(prompt, user_echo, None, false)
+            .await
     }
 
     async fn handle_plan_slash(&mut self, args: &str) -> Result<()> {
@@ -941,7 +1008,8 @@ impl TuiApp {
         } else {
             format!("/plan {cleaned}")
         };
-        self.start_stream_request(prompt, user_echo, None).await
+        self.start_stream_request(prompt, user_echo, None, false)
+            .await
     }
 
     async fn handle_goal_slash(&mut self, args: &str) -> Result<()> {
@@ -1195,13 +1263,27 @@ impl TuiApp {
 
     async fn handle_agent_slash(&mut self, args: &str) -> Result<()> {
         let cleaned = args.trim();
+        if let Some(action) = crate::agent_profiles::parse_agent_profile_action(cleaned) {
+            let lines = crate::agent_profiles::run_agent_profile_action(
+                &self.runtime,
+                &self.global,
+                &mut self.agent_id_override,
+                action,
+            )
+            .await?;
+            self.sync_model_status().await;
+            for line in lines {
+                self.push_log(LogKind::Info, line);
+            }
+            return Ok(());
+        }
         if cleaned.is_empty() || cleaned.eq_ignore_ascii_case("show") {
             let active = self.agent_id_override.as_deref().unwrap_or("-");
             if self.is_zh_language() {
                 self.push_log(LogKind::Info, format!("当前 agent_id 覆盖: {active}"));
                 self.push_log(
                     LogKind::Info,
-                    "用法: /agent [show|list|clear|<agent_id>]".to_string(),
+                    format!("用法: {}", crate::agent_profiles::AGENT_SLASH_USAGE),
                 );
             } else {
                 self.push_log(
@@ -1210,7 +1292,7 @@ impl TuiApp {
                 );
                 self.push_log(
                     LogKind::Info,
-                    "usage: /agent [show|list|clear|<agent_id>]".to_string(),
+                    format!("usage: {}", crate::agent_profiles::AGENT_SLASH_USAGE),
                 );
             }
             return Ok(());
@@ -1231,6 +1313,10 @@ impl TuiApp {
                         .map(ToString::to_string)
                 });
             let agents = crate::collect_recent_agent_ids(&self.runtime, 120).await?;
+            let profile_lines = crate::agent_profiles::agent_profile_list_lines(
+                &self.runtime,
+                self.display_language.as_str(),
+            )?;
             if agents.is_empty() {
                 self.push_log(
                     LogKind::Info,
@@ -1240,6 +1326,9 @@ impl TuiApp {
                         "no agent_id found in recent sessions, use /agent <agent_id> directly",
                     ),
                 );
+                for line in profile_lines {
+                    self.push_log(LogKind::Info, line);
+                }
                 return Ok(());
             }
             if self.is_zh_language() {
@@ -1258,6 +1347,9 @@ impl TuiApp {
                 };
                 self.push_log(LogKind::Info, format!("{marker} {:>2}. {agent}", index + 1));
             }
+            for line in profile_lines {
+                self.push_log(LogKind::Info, line);
+            }
             return Ok(());
         }
         if cleaned.eq_ignore_ascii_case("clear")
@@ -1380,8 +1472,8 @@ impl TuiApp {
                     self.push_log(LogKind::Info, format!("attachment removed: {removed_name}"));
                 }
             }
-            crate::attachments::AttachAction::Add(path) => {
-                let prepared = match crate::attachments::prepare_attachment_from_path(
+            crate::attachments::AttachAction::Add { path, remember } => {
+                let mut prepared = match crate::attachments::prepare_attachment_from_path(
                     &self.runtime,
                     path.as_str(),
                 )
@@ -1393,6 +1485,7 @@ impl TuiApp {
                         return Ok(());
                     }
                 };
+                prepared.remember = remember;
                 self.queue_prepared_attachment(prepared, true);
             }
         }
@@ -1484,7 +1577,7 @@ impl TuiApp {
                 );
                 self.push_log(
                     LogKind::Info,
-                    "用法: /notify [show|off|bell|osc9|when <always|unfocused>|<command...>]"
+                    "用法: /notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]"
                         .to_string(),
                 );
             } else {
@@ -1497,7 +1590,7 @@ impl TuiApp {
                 );
                 self.push_log(
                     LogKind::Info,
-                    "usage: /notify [show|off|bell|osc9|when <always|unfocused>|<command...>]"
+                    "usage: /notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]"
                         .to_string(),
                 );
             }
@@ -1550,16 +1643,7 @@ impl TuiApp {
             return Ok(());
         }
 
-        if cleaned.eq_ignore_ascii_case("bell") || cleaned.eq_ignore_ascii_case("osc9") {
-            let config = if cleaned.eq_ignore_ascii_case("bell") {
-                crate::runtime::TurnNotificationConfig::Bell {
-                    when: crate::runtime::TurnNotificationWhen::Always,
-                }
-            } else {
-                crate::runtime::TurnNotificationConfig::Osc9 {
-                    when: crate::runtime::TurnNotificationWhen::Always,
-                }
-            };
+        if let Some(config) = crate::turn_notifier::parse_builtin_notifier(cleaned) {
             self.runtime.save_turn_notification_config(&config)?;
             self.push_log(
                 LogKind::Info,
@@ -1583,8 +1667,8 @@ impl TuiApp {
                 LogKind::Info,
                 crate::locale::tr(
                     self.display_language.as_str(),
-                    "用法: /notify [show|off|bell|osc9|when <always|unfocused>|<command...>]",
-                    "usage: /notify [show|off|bell|osc9|when <always|unfocused>|<command...>]",
+                    "用法: /notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]",
+                    "usage: /notify [show|off|bell|osc9|desktop|when <always|unfocused>|<command...>]",
                 ),
             );
             return Ok(());
@@ -1634,7 +1718,13 @@ impl TuiApp {
             self.push_log(LogKind::Info, "usage: /mention <query>".to_string());
             return Ok(());
         }
-        let results = crate::search_workspace_files(self.runtime.launch_dir.as_path(), query, 30);
+        let ignore = self.runtime.load_workspace_ignore().await;
+        let results = crate::search_workspace_files(
+            self.runtime.launch_dir.as_path(),
+            ignore.as_ref(),
+            query,
+            30,
+        );
         if results.is_empty() {
             self.push_log(LogKind::Info, format!("no files found for: {query}"));
             return Ok(());
@@ -1686,13 +1776,26 @@ impl TuiApp {
             self.toggle_skill_state(value, false).await?;
             return Ok(());
         }
+        if action.eq_ignore_ascii_case("share") || action.eq_ignore_ascii_case("unshare") {
+            let share = action.eq_ignore_ascii_case("share");
+            let message = crate::skill_share::toggle_skill_shared(
+                &self.runtime,
+                self.display_language.as_str(),
+                value,
+                share,
+            )
+            .await?;
+            self.reload_popup_catalogs().await;
+            self.push_log(LogKind::Info, message);
+            return Ok(());
+        }
 
         self.push_log(
             LogKind::Info,
             crate::locale::tr(
                 self.display_language.as_str(),
-                "用法: /skills [list|enable <name>|disable <name>|root]",
-                "usage: /skills [list|enable <name>|disable <name>|root]",
+                "用法: /skills [list|enable <name>|disable <name>|share <name>|unshare <name>|root]",
+                "usage: /skills [list|enable <name>|disable <name>|share <name>|unshare <name>|root]",
             ),
         );
         Ok(())
@@ -1709,6 +1812,11 @@ impl TuiApp {
             .enabled
             .into_iter()
             .collect::<std::collections::HashSet<_>>();
+        let shared_set = payload
+            .skills
+            .shared
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>();
         let (skill_root, specs) = crate::load_user_skill_specs(&self.runtime).await;
 
         if self.is_zh_language() {
@@ -1744,9 +1852,14 @@ impl TuiApp {
             } else {
                 crate::locale::tr(self.display_language.as_str(), "禁用", "disabled")
             };
+            let shared = if shared_set.contains(&spec.name) {
+                crate::locale::tr(self.display_language.as_str(), " [共享]", " [shared]")
+            } else {
+                String::new()
+            };
             self.push_log(
                 LogKind::Info,
-                format!("{} [{}] {}", spec.name, state, spec.path),
+                format!("{} [{}]{shared} {}", spec.name, state, spec.path),
             );
         }
     }
@@ -1950,14 +2063,31 @@ impl TuiApp {
                 LogKind::Info,
                 crate::locale::tr(
                     self.display_language.as_str(),
-                    "用法: /rename <title>",
-                    "usage: /rename <title>",
+                    "用法: /rename <title|auto>",
+                    "usage: /rename <title|auto>",
                 ),
             );
             return Ok(());
         }
-        let saved =
-            crate::rename_session_title(&self.runtime, self.session_id.as_str(), title).await?;
+        let saved = if crate::session_rename::is_auto_rename(title) {
+            let generated =
+                crate::session_rename::auto_rename_session(&self.runtime, self.session_id.as_str())
+                    .await?;
+            let Some(saved) = generated else {
+                self.push_log(
+                    LogKind::Info,
+                    crate::locale::tr(
+                        self.display_language.as_str(),
+                        "当前会话还没有可用于生成标题的消息",
+                        "no messages to generate a title from yet",
+                    ),
+                );
+                return Ok(());
+            };
+            saved
+        } else {
+            crate::rename_session_title(&self.runtime, self.session_id.as_str(), title).await?
+        };
         if self.is_zh_language() {
             self.push_log(LogKind::Info, format!("会话已重命名: {saved}"));
         } else {
@@ -1966,6 +2096,34 @@ impl TuiApp {
         Ok(())
     }
 
+    async fn handle_tools_slash(&mut self, args: &str) -> Result<()> {
+        let lines = crate::session_tools::run_tools_command(
+            &self.runtime,
+            self.display_language.as_str(),
+            self.session_id.as_str(),
+            args,
+        )
+        .await?;
+        for line in lines {
+            self.push_log(LogKind::Info, line);
+        }
+        Ok(())
+    }
+
+    async fn handle_history_slash(&mut self, args: &str) -> Result<()> {
+        let lines = crate::session_history::run_history_command(
+            &self.runtime,
+            self.display_language.as_str(),
+            self.session_id.as_str(),
+            args,
+        )
+        .await?;
+        for line in lines {
+            self.push_log(LogKind::Info, line);
+        }
+        Ok(())
+    }
+
     async fn handle_compact_slash(&mut self) -> Result<()> {
         if self.busy {
             self.push_log(
@@ -2005,11 +2163,12 @@ impl TuiApp {
         Ok(())
     }
 
-    async fn show_debug_config_snapshot(&mut self) -> Result<()> {
+    async fn show_debug_config_snapshot(&mut self, show_secrets: bool) -> Result<()> {
         let payload = crate::collect_debug_config_payload(
             &self.runtime,
             &self.global,
             self.session_id.as_str(),
+            show_secrets,
         )
         .await;
         for line in serde_json::to_string_pretty(&payload)?.lines() {
@@ -2020,7 +2179,34 @@ impl TuiApp {
 
     fn handle_statusline_slash(&mut self, args: &str) {
         let cleaned = args.trim();
+        if self.apply_statusline_display_arg(cleaned) {
+            return;
+        }
         if cleaned.is_empty() || cleaned.eq_ignore_ascii_case("show") {
+            self.push_log(
+                LogKind::Info,
+                if self.is_zh_language() {
+                    format!(
+                        "状态栏: {} · 格式: {}",
+                        if self.statusline_visible {
+                            "显示"
+                        } else {
+                            "隐藏"
+                        },
+                        self.statusline_format.as_str()
+                    )
+                } else {
+                    format!(
+                        "status line: {} · format: {}",
+                        if self.statusline_visible {
+                            "shown"
+                        } else {
+                            "hidden"
+                        },
+                        self.statusline_format.as_str()
+                    )
+                },
+            );
             if self.statusline_items.is_empty() {
                 self.push_log(
                     LogKind::Info,
@@ -2061,8 +2247,8 @@ impl TuiApp {
                 LogKind::Info,
                 crate::locale::tr(
                     self.display_language.as_str(),
-                    "用法: /statusline [show|set <items>|reset]",
-                    "usage: /statusline [show|set <items>|reset]",
+                    "用法: /statusline [show|on|off|toggle|compact|full|set <items>|reset]",
+                    "usage: /statusline [show|on|off|toggle|compact|full|set <items>|reset]",
                 ),
             );
             return;
@@ -2070,6 +2256,8 @@ impl TuiApp {
 
         if cleaned.eq_ignore_ascii_case("reset") {
             self.statusline_items.clear();
+            self.statusline_visible = true;
+            self.statusline_format = StatusLineFormat::default();
             self.persist_statusline_items();
             self.push_log(
                 LogKind::Info,
@@ -2098,8 +2286,8 @@ impl TuiApp {
                 LogKind::Info,
                 crate::locale::tr(
                     self.display_language.as_str(),
-                    "用法: /statusline [show|set <items>|reset]",
-                    "usage: /statusline [show|set <items>|reset]",
+                    "用法: /statusline [show|on|off|toggle|compact|full|set <items>|reset]",
+                    "usage: /statusline [show|on|off|toggle|compact|full|set <items>|reset]",
                 ),
             );
             return;
@@ -2656,6 +2844,10 @@ impl TuiApp {
 
         if is_zh {
             lines.push(format!("- 模型调用: {}", self.session_stats.model_calls));
+            lines.push(format!(
+                "- 推理轮次: {}",
+                self.session_stats.reasoning_events
+            ));
             lines.push(format!("- 工具调用: {}", self.session_stats.tool_calls));
             lines.push(format!("- 工具结果: {}", self.session_stats.tool_results));
             lines.push(format!(
@@ -2666,6 +2858,10 @@ impl TuiApp {
             ));
         } else {
             lines.push(format!("- model_calls: {}", self.session_stats.model_calls));
+            lines.push(format!(
+                "- reasoning_events: {}",
+                self.session_stats.reasoning_events
+            ));
             lines.push(format!("- tool_calls: {}", self.session_stats.tool_calls));
             lines.push(format!(
                 "- tool_results: {}",
@@ -2678,11 +2874,29 @@ impl TuiApp {
                 self.session_stats.total_tokens
             ));
         }
+        lines.extend(crate::tool_latency_summary::tool_latency_lines(
+            self.display_language.as_str(),
+            &self.session_stats.tool_latency,
+        ));
         lines
     }
 
     async fn handle_system_slash(&mut self, args: &str) -> Result<()> {
         let cleaned = args.trim();
+        if cleaned.eq_ignore_ascii_case("budget") {
+            let breakdown = crate::prompt_budget_display::build_current_prompt_budget(
+                &self.runtime,
+                &self.global,
+            )
+            .await?;
+            for line in crate::prompt_budget_display::prompt_budget_lines(
+                self.display_language.as_str(),
+                &breakdown,
+            ) {
+                self.push_log(LogKind::Info, line);
+            }
+            return Ok(());
+        }
         if cleaned.eq_ignore_ascii_case("clear") {
             self.runtime.clear_extra_prompt()?;
             self.push_log(
@@ -2708,8 +2922,8 @@ impl TuiApp {
                     LogKind::Info,
                     crate::locale::tr(
                         self.display_language.as_str(),
-                        "用法: /system [set <extra_prompt>|clear]",
-                        "usage: /system [set <extra_prompt>|clear]",
+                        "用法: /system [set <extra_prompt>|clear|budget]",
+                        "usage: /system [set <extra_prompt>|clear|budget]",
                     ),
                 );
                 return Ok(());
@@ -2739,8 +2953,8 @@ impl TuiApp {
                 LogKind::Info,
                 crate::locale::tr(
                     self.display_language.as_str(),
-                    "用法: /system [set <extra_prompt>|clear]",
-                    "usage: /system [set <extra_prompt>|clear]",
+                    "用法: /system [set <extra_prompt>|clear|budget]",
+                    "usage: /system [set <extra_prompt>|clear|budget]",
                 ),
             );
             return Ok(());
@@ -2908,6 +3122,18 @@ impl TuiApp {
             } else {
                 format!("- max_rounds: {}", self.model_max_rounds)
             },
+            {
+                let sandbox = self
+                    .runtime
+                    .session_sandbox_container(&self.session_id)
+                    .map(|container_id| container_id.to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                if is_zh {
+                    format!("- 沙盒容器: {sandbox}")
+                } else {
+                    format!("- sandbox_container: {sandbox}")
+                }
+            },
             format!(
                 "{} {}",
                 if is_zh {
@@ -0,0 +1,74 @@
+use super::*;
+use crate::transcript_export::{
+    render_transcript_markdown, write_transcript_export, TranscriptExportItem, TranscriptRole,
+};
+
+const STATUS_NOTICE_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Debug, Clone)]
+pub(super) struct StatusNotice {
+    text: String,
+    deadline: Instant,
+}
+
+impl TuiApp {
+    pub(super) fn set_status_notice(&mut self, text: String) {
+        self.status_notice = Some(StatusNotice {
+            text,
+            deadline: Instant::now() + STATUS_NOTICE_DURATION,
+        });
+    }
+
+    pub(super) fn active_status_notice(&self) -> Option<&str> {
+        self.status_notice
+            .as_ref()
+            .filter(|notice| Instant::now() <= notice.deadline)
+            .map(|notice| notice.text.as_str())
+    }
+
+    /// Save the whole transcript as Markdown under `launch_dir`; failures only surface as notices.
+    pub(super) fn export_transcript_to_file(&mut self) {
+        let exported_at = chrono::Local::now();
+        let items = self
+            .logs
+            .iter()
+            .map(|entry| TranscriptExportItem {
+                role: transcript_role(entry.kind),
+                text: entry.text.as_str(),
+            })
+            .collect::<Vec<_>>();
+        let markdown = render_transcript_markdown(self.session_id.as_str(), exported_at, &items);
+        let language = self.display_language.clone();
+        let notice = match write_transcript_export(
+            self.runtime.launch_dir.as_path(),
+            exported_at,
+            markdown.as_str(),
+        ) {
+            Ok(path) => format!(
+                "{} {}",
+                crate::locale::tr(language.as_str(), "会话已导出：", "transcript saved:"),
+                path.display()
+            ),
+            Err(err) => format!(
+                "{} {err:#}",
+                crate::locale::tr(
+                    language.as_str(),
+                    "会话导出失败：",
+                    "transcript export failed:"
+                )
+            ),
+        };
+        self.set_status_notice(notice);
+    }
+}
+
+fn transcript_role(kind: LogKind) -> TranscriptRole {
+    match kind {
+        LogKind::User => TranscriptRole::User,
+        LogKind::Assistant => TranscriptRole::Assistant,
+        LogKind::Reasoning => TranscriptRole::Reasoning,
+        LogKind::Tool => TranscriptRole::Tool,
+        LogKind::Info => TranscriptRole::Info,
+        LogKind::Error => TranscriptRole::Error,
+    }
+}
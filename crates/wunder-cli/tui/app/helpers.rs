@@ -1924,8 +1924,8 @@ pub(super) fn localize_cli_notice(language: &str, text: &str) -> String {
             "再按一次 Ctrl+C 退出（或等待继续）".to_string()
         }
         "usage: /mention <query>" => "用法: /mention <query>".to_string(),
-        "usage: /approvals [show|suggest|auto_edit|full_auto]" => {
-            "用法: /approvals [show|suggest|auto_edit|full_auto]".to_string()
+        "usage: /approvals [show|suggest|auto_edit|full_auto|allowed|clear]" => {
+            "用法: /approvals [show|suggest|auto_edit|full_auto|allowed|clear]".to_string()
         }
         "valid modes: suggest, auto_edit, full_auto" => {
             "可选模式: suggest, auto_edit, full_auto".to_string()
@@ -1951,14 +1951,14 @@ pub(super) fn localize_cli_notice(language: &str, text: &str) -> String {
         "mouse mode: auto (native selection and terminal wheel scrolling enabled; switch to scroll for app-captured transcript scrolling)" => {
             "鼠标模式：auto（启用终端原生选择与滚轮滚动；切到 scroll 时由应用接管输出滚动）".to_string()
         }
-        "mouse mode: scroll (capture wheel events for transcript scrolling)" => {
-            "鼠标模式：scroll（由应用接管滚轮并滚动输出区）".to_string()
+        "mouse mode: scroll (mouse captured: terminal drag-to-select/copy is disabled; hold Shift in most terminals or run /mouse select to restore it)" => {
+            "鼠标模式：scroll（应用已接管鼠标，终端拖选复制暂不可用；多数终端可按住 Shift 拖选，或运行 /mouse select 恢复）".to_string()
         }
-        "mouse mode: select/copy (native terminal selection enabled)" => {
-            "鼠标模式：select/copy（启用终端原生选择复制）".to_string()
+        "mouse mode: select/copy (mouse released: native terminal selection and copy/paste work again)" => {
+            "鼠标模式：select/copy（已释放鼠标，终端原生选择与复制粘贴恢复可用）".to_string()
         }
-        "usage: /mouse [auto|scroll|select]  (F2 optional)" => {
-            "用法: /mouse [auto|scroll|select]  （F2 可切换）".to_string()
+        "usage: /mouse [show|auto|scroll|select]  (F2 optional)" => {
+            "用法: /mouse [show|auto|scroll|select]  （F2 可切换）".to_string()
         }
         "usage: /mouse [scroll|select]  (F2 to toggle)" => {
             "用法: /mouse [scroll|select]  （F2 切换）".to_string()
@@ -1981,8 +1981,8 @@ pub(super) fn localize_cli_notice(language: &str, text: &str) -> String {
         "extra prompt cleared" => "额外提示词已清除".to_string(),
         "extra prompt is empty" => "额外提示词为空".to_string(),
         "- extra_prompt: none" => "- 额外提示词: 无".to_string(),
-        "usage: /system [set <extra_prompt>|clear]" => {
-            "用法: /system [set <extra_prompt>|clear]".to_string()
+        "usage: /system [set <extra_prompt>|clear|budget]" => {
+            "用法: /system [set <extra_prompt>|clear|budget]".to_string()
         }
         "invalid /system args" => "无效的 /system 参数".to_string(),
         "system" => "系统提示词".to_string(),
@@ -1995,7 +1995,10 @@ pub(super) fn localize_cli_notice(language: &str, text: &str) -> String {
     }
 }
 
-pub(super) fn build_workspace_file_index(root: &std::path::Path) -> Vec<IndexedFile> {
+pub(super) fn build_workspace_file_index(
+    root: &std::path::Path,
+    ignore: Option<&wunder_server::workspace_ignore::WorkspaceIgnore>,
+) -> Vec<IndexedFile> {
     const MAX_INDEX_FILES: usize = 50_000;
     let excluded_dirs = [
         ".git",
@@ -2017,6 +2020,9 @@ pub(super) fn build_workspace_file_index(root: &std::path::Path) -> Vec<IndexedF
             if path == root {
                 return true;
             }
+            if ignore.is_some_and(|ignore| ignore.is_ignored(path, entry.file_type().is_dir())) {
+                return false;
+            }
             let Some(name) = path.file_name().and_then(|value| value.to_str()) else {
                 return true;
             };
@@ -142,10 +142,13 @@ mod tests {
                 content: None,
                 content_type: None,
                 public_path: None,
+                attachment_id: None,
+                metadata: None,
             },
             kind,
             size_bytes: 1,
             detail: None,
+            remember: false,
         }
     }
 
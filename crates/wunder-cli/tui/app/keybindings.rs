@@ -0,0 +1,347 @@
+use super::*;
+use crate::runtime::TuiKeyBindingsConfig;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum KeyAction {
+    Submit,
+    Cancel,
+    Interrupt,
+    ScrollUp,
+    ScrollDown,
+    ScrollTop,
+    ScrollBottom,
+    ToggleMouse,
+    ToggleFocus,
+    ExportTranscript,
+    NewSession,
+    ClearTranscript,
+}
+
+const KEY_ACTIONS: &[KeyAction] = &[
+    KeyAction::Submit,
+    KeyAction::Cancel,
+    KeyAction::Interrupt,
+    KeyAction::ScrollUp,
+    KeyAction::ScrollDown,
+    KeyAction::ScrollTop,
+    KeyAction::ScrollBottom,
+    KeyAction::ToggleMouse,
+    KeyAction::ToggleFocus,
+    KeyAction::ExportTranscript,
+    KeyAction::NewSession,
+    KeyAction::ClearTranscript,
+];
+
+impl KeyAction {
+    fn config_key(self) -> &'static str {
+        match self {
+            Self::Submit => "submit",
+            Self::Cancel => "cancel",
+            Self::Interrupt => "interrupt",
+            Self::ScrollUp => "scroll_up",
+            Self::ScrollDown => "scroll_down",
+            Self::ScrollTop => "scroll_top",
+            Self::ScrollBottom => "scroll_bottom",
+            Self::ToggleMouse => "toggle_mouse",
+            Self::ToggleFocus => "toggle_focus",
+            Self::ExportTranscript => "export_transcript",
+            Self::NewSession => "new_session",
+            Self::ClearTranscript => "clear_transcript",
+        }
+    }
+
+    fn parse(raw: &str) -> Option<Self> {
+        let normalized = raw.trim().to_ascii_lowercase().replace('-', "_");
+        KEY_ACTIONS
+            .iter()
+            .copied()
+            .find(|action| action.config_key() == normalized)
+    }
+
+    /// The chord the hardcoded handlers in `on_key` already understand for this action.
+    fn default_chord(self) -> KeyChord {
+        match self {
+            Self::Submit => KeyChord::new(KeyCode::Enter, KeyModifiers::NONE),
+            Self::Cancel => KeyChord::new(KeyCode::Esc, KeyModifiers::NONE),
+            Self::Interrupt => KeyChord::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            Self::ScrollUp => KeyChord::new(KeyCode::PageUp, KeyModifiers::NONE),
+            Self::ScrollDown => KeyChord::new(KeyCode::PageDown, KeyModifiers::NONE),
+            Self::ScrollTop => KeyChord::new(KeyCode::Home, KeyModifiers::CONTROL),
+            Self::ScrollBottom => KeyChord::new(KeyCode::End, KeyModifiers::CONTROL),
+            Self::ToggleMouse => KeyChord::new(KeyCode::F(2), KeyModifiers::NONE),
+            Self::ToggleFocus => KeyChord::new(KeyCode::F(3), KeyModifiers::NONE),
+            Self::ExportTranscript => KeyChord::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+            Self::NewSession => KeyChord::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+            Self::ClearTranscript => KeyChord::new(KeyCode::Char('l'), KeyModifiers::CONTROL),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Normalize terminal quirks: shifted letters may arrive upper-cased with or without SHIFT,
+    /// and Shift+Tab arrives as `BackTab`.
+    fn from_event(key: KeyEvent) -> Self {
+        let mut modifiers =
+            key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match key.code {
+            KeyCode::Char(ch) if ch.is_uppercase() => {
+                modifiers |= KeyModifiers::SHIFT;
+                KeyCode::Char(ch.to_lowercase().next().unwrap_or(ch))
+            }
+            KeyCode::BackTab => {
+                modifiers |= KeyModifiers::SHIFT;
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    fn parse(spec: &str) -> Result<Self, String> {
+        let normalized = spec.trim().to_ascii_lowercase();
+        if normalized.is_empty() {
+            return Err("empty key".to_string());
+        }
+        let parts = normalized.split('+').map(str::trim).collect::<Vec<_>>();
+        let (key_name, modifier_names) = parts.split_last().unwrap_or((&"", &[]));
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names {
+            modifiers |= match *name {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier `{name}` in `{spec}`")),
+            };
+        }
+        let code = match *key_name {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            name => {
+                if let Some(number) = name
+                    .strip_prefix('f')
+                    .and_then(|value| value.parse::<u8>().ok())
+                    .filter(|value| (1..=24).contains(value))
+                {
+                    KeyCode::F(number)
+                } else {
+                    let mut chars = name.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(ch), None) => KeyCode::Char(ch),
+                        _ => return Err(format!("unknown key `{spec}`")),
+                    }
+                }
+            }
+        };
+        // Binding a bare character would make it impossible to type that character.
+        if matches!(code, KeyCode::Char(_))
+            && !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return Err(format!("`{spec}` needs a ctrl or alt modifier"));
+        }
+        Ok(Self { code, modifiers })
+    }
+
+    fn to_event(self) -> KeyEvent {
+        KeyEvent::new(self.code, self.modifiers)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("shift+")?;
+        }
+        match self.code {
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Tab | KeyCode::BackTab => f.write_str("tab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(ch) => write!(f, "{ch}"),
+            KeyCode::F(number) => write!(f, "f{number}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum KeyTranslation {
+    Passthrough,
+    Replace(KeyEvent),
+    Ignore,
+}
+
+/// User overrides layered on top of the built-in keys.
+///
+/// Instead of duplicating every action handler, a custom chord is rewritten into the
+/// action's default chord before `on_key` dispatches it, so remapped keys go through the
+/// exact same code path as the defaults.
+#[derive(Debug, Clone, Default)]
+pub(super) struct KeyBindings {
+    bound: HashMap<KeyChord, KeyAction>,
+    // Default chords whose action moved elsewhere and that nothing else claimed.
+    released: HashSet<KeyChord>,
+}
+
+impl KeyBindings {
+    /// Build bindings from the runtime config. Every problem (unknown action, bad key,
+    /// conflicting chords) is returned for reporting; the affected entry falls back so the
+    /// TUI always starts with a usable key map.
+    pub(super) fn from_config(config: &TuiKeyBindingsConfig) -> (Self, Vec<String>) {
+        let mut problems = Vec::new();
+        let mut bound: HashMap<KeyChord, KeyAction> = HashMap::new();
+        let mut overridden = Vec::new();
+        for (name, value) in config {
+            let Some(action) = KeyAction::parse(name) else {
+                problems.push(format!("unknown action `{name}`"));
+                continue;
+            };
+            let mut chords = Vec::new();
+            for spec in value.specs() {
+                match KeyChord::parse(spec) {
+                    Ok(chord) => chords.push(chord),
+                    Err(err) => problems.push(format!("{}: {err}", action.config_key())),
+                }
+            }
+            let mut claimed = false;
+            for chord in chords {
+                match bound.get(&chord) {
+                    Some(owner) if *owner != action => problems.push(format!(
+                        "`{chord}` is bound to both {} and {}; keeping {}",
+                        owner.config_key(),
+                        action.config_key(),
+                        owner.config_key()
+                    )),
+                    _ => {
+                        bound.insert(chord, action);
+                        claimed = true;
+                    }
+                }
+            }
+            // An action that ended up without any usable chord keeps its default.
+            if claimed {
+                overridden.push(action);
+            }
+        }
+
+        let mut released = HashSet::new();
+        for action in KEY_ACTIONS.iter().copied() {
+            let chord = action.default_chord();
+            match bound.get(&chord) {
+                Some(owner) if *owner != action && !overridden.contains(&action) => {
+                    problems.push(format!(
+                        "`{chord}` is the default for {} but is now bound to {}",
+                        action.config_key(),
+                        owner.config_key()
+                    ));
+                }
+                None if overridden.contains(&action) => {
+                    released.insert(chord);
+                }
+                _ => {}
+            }
+        }
+        (Self { bound, released }, problems)
+    }
+
+    pub(super) fn translate(&self, key: KeyEvent) -> KeyTranslation {
+        let chord = KeyChord::from_event(key);
+        if let Some(action) = self.bound.get(&chord) {
+            let target = action.default_chord();
+            if target == chord {
+                return KeyTranslation::Passthrough;
+            }
+            return KeyTranslation::Replace(target.to_event());
+        }
+        if self.released.contains(&chord) {
+            return KeyTranslation::Ignore;
+        }
+        KeyTranslation::Passthrough
+    }
+}
+
+impl TuiApp {
+    pub(super) fn load_key_bindings(&mut self) {
+        let config = match self.runtime.load_tui_keybindings_config() {
+            Ok(config) => config,
+            Err(err) => {
+                let prefix = crate::locale::tr(
+                    self.display_language.as_str(),
+                    "快捷键配置无效，已使用默认按键：",
+                    "invalid key bindings, using defaults:",
+                );
+                self.push_log(LogKind::Error, format!("{prefix} {err:#}"));
+                return;
+            }
+        };
+        let (bindings, problems) = KeyBindings::from_config(&config);
+        self.key_bindings = bindings;
+        let prefix = crate::locale::tr(
+            self.display_language.as_str(),
+            "快捷键配置：",
+            "key bindings:",
+        );
+        for problem in problems {
+            self.push_log(LogKind::Error, format!("{prefix} {problem}"));
+        }
+    }
+
+    /// Apply user key bindings. Returns `None` when the key should be dropped.
+    pub(super) fn translate_bound_key(&self, key: KeyEvent) -> Option<KeyEvent> {
+        // Modal prompts keep their fixed keys so a remap can never lock the user out of them.
+        if self.active_approval.is_some()
+            || self.transcript_search_editing()
+            || self.shortcuts_visible
+            || self.has_resume_picker()
+            || self.active_inquiry_panel.is_some()
+        {
+            return Some(key);
+        }
+        match self.key_bindings.translate(key) {
+            KeyTranslation::Passthrough => Some(key),
+            KeyTranslation::Replace(translated) => Some(translated),
+            KeyTranslation::Ignore => None,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use super::*;
+
+const COMPACT_STATUSLINE_ITEMS: &[&str] = &["model", "approval", "context", "attach"];
+const FULL_STATUSLINE_ITEMS: &[&str] = &[
+    "model", "approval", "context", "attach", "cwd", "branch", "elapsed", "speed", "tools",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum StatusLineFormat {
+    #[default]
+    Compact,
+    Full,
+}
+
+impl StatusLineFormat {
+    pub(super) fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "compact" | "short" => Some(Self::Compact),
+            "full" | "long" => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::Compact => "compact",
+            Self::Full => "full",
+        }
+    }
+}
+
+impl TuiApp {
+    pub fn status_line_visible(&self) -> bool {
+        self.statusline_visible
+    }
+
+    pub fn status_line(&self) -> String {
+        let parts = self.status_line_parts();
+        let compact = self.statusline_format == StatusLineFormat::Compact;
+        let default_items = if compact {
+            COMPACT_STATUSLINE_ITEMS
+        } else {
+            FULL_STATUSLINE_ITEMS
+        };
+        let custom = !self.statusline_items.is_empty();
+        let keys = if custom {
+            self.statusline_items
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+        } else {
+            default_items.to_vec()
+        };
+
+        let mut items = Vec::new();
+        for key in keys {
+            let value = if compact {
+                self.compact_status_value(key)
+                    .or_else(|| parts.get(key).cloned())
+            } else {
+                parts.get(key).cloned()
+            };
+            if let Some(value) = value {
+                let value = value.trim();
+                if !value.is_empty() {
+                    items.push(value.to_string());
+                }
+            }
+        }
+        if items.is_empty() {
+            if !custom {
+                return "  -".to_string();
+            }
+            if self.is_zh_language() {
+                return "  状态栏：当前没有启用条目，可输入 /statusline reset 恢复默认值"
+                    .to_string();
+            }
+            return "  status line: empty selection, run /statusline reset".to_string();
+        }
+        let separator = if compact { " · " } else { " | " };
+        format!("  {}", items.join(separator))
+    }
+
+    /// Short, label-free values for the compact format. `None` falls back to the full label.
+    fn compact_status_value(&self, key: &str) -> Option<String> {
+        let is_zh = self.is_zh_language();
+        match key {
+            "model" => Some(self.model_name.clone()),
+            "approval" => Some(self.approval_mode.clone()),
+            "context" => Some(format_compact_context(
+                is_zh,
+                self.session_stats.context_used_tokens,
+                self.model_max_context,
+            )),
+            // An empty attachment queue is the common case and not worth a slot.
+            "attach" => match self.pending_attachments.len() {
+                0 => Some(String::new()),
+                count if is_zh => Some(format!("附件 {count}")),
+                count => Some(format!("+{count} attach")),
+            },
+            _ => None,
+        }
+    }
+
+    /// Handle the display forms of `/statusline`. Returns false when `arg` is not one of them.
+    pub(super) fn apply_statusline_display_arg(&mut self, arg: &str) -> bool {
+        let normalized = arg.trim().to_ascii_lowercase();
+        let message = match normalized.as_str() {
+            "on" | "off" | "toggle" => {
+                self.statusline_visible = match normalized.as_str() {
+                    "on" => true,
+                    "off" => false,
+                    _ => !self.statusline_visible,
+                };
+                if self.statusline_visible {
+                    crate::locale::tr(
+                        self.display_language.as_str(),
+                        "状态栏已显示",
+                        "status line shown",
+                    )
+                } else {
+                    crate::locale::tr(
+                        self.display_language.as_str(),
+                        "状态栏已隐藏",
+                        "status line hidden",
+                    )
+                }
+            }
+            _ => {
+                let Some(format) = StatusLineFormat::parse(normalized.as_str()) else {
+                    return false;
+                };
+                self.statusline_format = format;
+                self.statusline_visible = true;
+                if self.is_zh_language() {
+                    format!("状态栏格式: {}", format.as_str())
+                } else {
+                    format!("status line format: {}", format.as_str())
+                }
+            }
+        };
+        self.persist_statusline_items();
+        self.push_log(LogKind::Info, message);
+        true
+    }
+}
+
+pub(super) fn format_compact_token_count(tokens: i64) -> String {
+    let tokens = tokens.max(0);
+    if tokens < 1_000 {
+        return tokens.to_string();
+    }
+    let (value, unit) = if tokens < 1_000_000 {
+        (tokens as f64 / 1_000.0, "k")
+    } else {
+        (tokens as f64 / 1_000_000.0, "M")
+    };
+    let formatted = format!("{value:.1}");
+    format!("{}{unit}", formatted.trim_end_matches(".0"))
+}
+
+pub(super) fn format_compact_context(
+    is_zh: bool,
+    used_tokens: i64,
+    max_context: Option<u32>,
+) -> String {
+    let used = format_compact_token_count(used_tokens);
+    match crate::context_left_percent(used_tokens, max_context) {
+        Some(percent_left) if is_zh => format!("上下文 {used} · 余 {percent_left}%"),
+        Some(percent_left) => format!("ctx {used} · {percent_left}% left"),
+        None if is_zh => format!("上下文 {used}"),
+        None => format!("ctx {used}"),
+    }
+}
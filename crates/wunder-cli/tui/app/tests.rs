@@ -589,3 +589,205 @@ fn normalize_statusline_item_supports_project_and_branch_aliases() {
     );
     assert_eq!(normalize_statusline_item("git").as_deref(), Some("branch"));
 }
+
+#[test]
+fn next_search_position_wraps_in_both_directions() {
+    assert_eq!(next_search_position(0, None, true), None);
+    assert_eq!(next_search_position(3, None, true), Some(0));
+    assert_eq!(next_search_position(3, None, false), Some(2));
+    assert_eq!(next_search_position(3, Some(2), true), Some(0));
+    assert_eq!(next_search_position(3, Some(0), false), Some(2));
+    assert_eq!(next_search_position(3, Some(1), true), Some(2));
+}
+
+#[test]
+fn highlight_search_matches_splits_spans_case_insensitively() {
+    let line = Line::from(vec![
+        Span::raw("Find the Needle and "),
+        Span::raw("another needle"),
+    ]);
+    let match_style = crate::tui::theme::search_match();
+    let highlighted = highlight_search_matches(line, "needle", match_style);
+    let contents = highlighted
+        .spans
+        .iter()
+        .map(|span| span.content.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        contents,
+        vec!["Find the ", "Needle", " and ", "another ", "needle"]
+    );
+    assert_eq!(highlighted.spans[1].style, match_style);
+    assert_eq!(highlighted.spans[4].style, match_style);
+    assert_eq!(
+        lines_plain_text(&[highlighted]),
+        "Find the Needle and another needle"
+    );
+}
+
+#[test]
+fn highlight_search_matches_keeps_cjk_boundaries() {
+    let line = Line::from("\u{4f60}\u{597d}\u{4e16}\u{754c}");
+    let highlighted =
+        highlight_search_matches(line, "\u{597d}\u{4e16}", crate::tui::theme::search_match());
+    let contents = highlighted
+        .spans
+        .iter()
+        .map(|span| span.content.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(contents, vec!["\u{4f60}", "\u{597d}\u{4e16}", "\u{754c}"]);
+}
+
+fn key_bindings_from_json(raw: &str) -> (KeyBindings, Vec<String>) {
+    let config: crate::runtime::TuiKeyBindingsConfig =
+        serde_json::from_str(raw).expect("key bindings json");
+    KeyBindings::from_config(&config)
+}
+
+#[test]
+fn remapped_key_translates_to_default_action_key() {
+    let (bindings, problems) =
+        key_bindings_from_json(r#"{"submit": "ctrl+enter", "toggle_mouse": ["f5", "alt+m"]}"#);
+    assert!(problems.is_empty(), "{problems:?}");
+    assert_eq!(
+        bindings.translate(KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL)),
+        KeyTranslation::Replace(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+    );
+    assert_eq!(
+        bindings.translate(KeyEvent::new(KeyCode::Char('M'), KeyModifiers::ALT)),
+        KeyTranslation::Passthrough
+    );
+    assert_eq!(
+        bindings.translate(KeyEvent::new(KeyCode::Char('m'), KeyModifiers::ALT)),
+        KeyTranslation::Replace(KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE))
+    );
+    // The old defaults are released once their action moves elsewhere.
+    assert_eq!(
+        bindings.translate(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+        KeyTranslation::Ignore
+    );
+    assert_eq!(
+        bindings.translate(KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE)),
+        KeyTranslation::Ignore
+    );
+    // Unset actions keep their defaults.
+    assert_eq!(
+        bindings.translate(KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE)),
+        KeyTranslation::Passthrough
+    );
+}
+
+#[test]
+fn key_bindings_report_conflicts_and_invalid_entries() {
+    let (bindings, problems) = key_bindings_from_json(
+        r#"{"scroll_down": "ctrl+j", "scroll_up": ["ctrl+j", "k"], "toggle_focus": "ctrl+home", "zoom": "f9"}"#,
+    );
+    assert_eq!(
+        problems,
+        vec![
+            "scroll_up: `k` needs a ctrl or alt modifier".to_string(),
+            "`ctrl+j` is bound to both scroll_down and scroll_up; keeping scroll_down".to_string(),
+            "unknown action `zoom`".to_string(),
+            "`ctrl+home` is the default for scroll_top but is now bound to toggle_focus"
+                .to_string(),
+        ]
+    );
+    assert_eq!(
+        bindings.translate(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL)),
+        KeyTranslation::Replace(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE))
+    );
+    // scroll_up lost its only valid chord, so it keeps the default.
+    assert_eq!(
+        bindings.translate(KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE)),
+        KeyTranslation::Passthrough
+    );
+}
+
+#[test]
+fn format_compact_context_reports_used_and_left_percent() {
+    assert_eq!(format_compact_token_count(950), "950");
+    assert_eq!(format_compact_token_count(12_345), "12.3k");
+    assert_eq!(format_compact_token_count(2_000_000), "2M");
+    assert_eq!(
+        format_compact_context(false, 32_000, Some(128_000)),
+        "ctx 32k · 75% left"
+    );
+    assert_eq!(format_compact_context(true, 32_000, None), "上下文 32k");
+}
+
+#[test]
+fn status_line_format_parses_aliases() {
+    assert_eq!(
+        StatusLineFormat::parse(" Full "),
+        Some(StatusLineFormat::Full)
+    );
+    assert_eq!(
+        StatusLineFormat::parse("short"),
+        Some(StatusLineFormat::Compact)
+    );
+    assert_eq!(StatusLineFormat::parse("wide"), None);
+}
+
+#[test]
+fn mouse_mode_parse_round_trips_saved_values() {
+    for mode in [MouseMode::Auto, MouseMode::Scroll, MouseMode::Select] {
+        assert_eq!(MouseMode::parse(mode.as_str()), Some(mode));
+    }
+    assert_eq!(MouseMode::parse(" Copy "), Some(MouseMode::Select));
+    assert_eq!(MouseMode::parse("off"), None);
+}
+
+#[derive(clap::Parser)]
+struct KeyTestCli {
+    #[command(flatten)]
+    global: GlobalArgs,
+}
+
+async fn build_key_test_app(base_dir: &Path) -> TuiApp {
+    std::env::set_var("WUNDER_HOME", base_dir.join("home"));
+    let temp_root = base_dir.join("temp");
+    let cli = <KeyTestCli as clap::Parser>::parse_from([
+        "wunder-cli".to_string(),
+        "--temp-root".to_string(),
+        temp_root.to_string_lossy().to_string(),
+    ]);
+    let runtime = CliRuntime::init(&cli.global).await.expect("cli runtime");
+    let (frame_requester, _notifications) = super::super::frame_scheduler::spawn_frame_scheduler();
+    TuiApp::new(
+        runtime,
+        cli.global,
+        Some("sess_key_bindings".to_string()),
+        frame_requester,
+    )
+    .await
+    .expect("tui app")
+}
+
+#[tokio::test]
+async fn on_key_runs_remapped_action_and_ignores_released_default() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let base_dir = std::env::temp_dir().join(format!("wunder-cli-keys-{unique}"));
+    fs::create_dir_all(&base_dir).unwrap();
+    let mut app = build_key_test_app(&base_dir).await;
+    let (bindings, problems) = key_bindings_from_json(r#"{"toggle_mouse": "alt+m"}"#);
+    assert!(problems.is_empty(), "{problems:?}");
+    app.key_bindings = bindings;
+    assert_eq!(app.mouse_mode, MouseMode::Auto);
+
+    app.on_key(KeyEvent::new(KeyCode::Char('m'), KeyModifiers::ALT))
+        .await
+        .unwrap();
+    assert_eq!(app.mouse_mode, MouseMode::Scroll);
+    assert!(app.input.is_empty());
+
+    // F2 was the default toggle; once the action moves it must do nothing.
+    app.on_key(KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE))
+        .await
+        .unwrap();
+    assert_eq!(app.mouse_mode, MouseMode::Scroll);
+
+    let _ = fs::remove_dir_all(base_dir);
+}
@@ -0,0 +1,263 @@
+use super::*;
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct TranscriptSearchState {
+    query: String,
+    editing: bool,
+    // Global log indices of matching entries, oldest first.
+    matches: Vec<usize>,
+    current: Option<usize>,
+}
+
+impl TranscriptSearchState {
+    fn active_query(&self) -> Option<&str> {
+        let query = self.query.trim();
+        (!query.is_empty()).then_some(query)
+    }
+}
+
+impl TuiApp {
+    pub(super) fn transcript_search_editing(&self) -> bool {
+        self.transcript_search.editing
+    }
+
+    pub(super) fn open_transcript_search(&mut self) {
+        self.focus_area = FocusArea::Transcript;
+        self.transcript_search.editing = true;
+    }
+
+    pub(super) fn close_transcript_search(&mut self) {
+        if self.transcript_search.active_query().is_some() {
+            self.transcript_render_cache = None;
+        }
+        self.transcript_search = TranscriptSearchState::default();
+    }
+
+    /// Handle keys while the search prompt is open. Returns false for keys the prompt does not own.
+    pub(super) fn handle_transcript_search_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && !is_altgr(key.modifiers) {
+            return false;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.close_transcript_search();
+            }
+            KeyCode::Enter => {
+                self.transcript_search.editing = false;
+                if self.transcript_search.active_query().is_none() {
+                    self.close_transcript_search();
+                }
+            }
+            KeyCode::Backspace => {
+                self.transcript_search.query.pop();
+                self.refresh_transcript_search(true);
+            }
+            KeyCode::Char(ch) => {
+                self.transcript_search.query.push(ch);
+                self.refresh_transcript_search(true);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Move to the next (`forward`) or previous match, wrapping around at either end.
+    pub(super) fn jump_transcript_search(&mut self, forward: bool) -> bool {
+        if self.transcript_search.active_query().is_none() {
+            return false;
+        }
+        // Entries may have streamed in since the query was typed; keep the match list current.
+        self.refresh_transcript_search(false);
+        let Some(next) = next_search_position(
+            self.transcript_search.matches.len(),
+            self.transcript_search.current,
+            forward,
+        ) else {
+            return true;
+        };
+        self.transcript_search.current = Some(next);
+        self.reveal_current_search_match();
+        true
+    }
+
+    pub(super) fn transcript_search_highlight(&self) -> Option<String> {
+        self.transcript_search.active_query().map(str::to_lowercase)
+    }
+
+    pub(super) fn transcript_search_activity_line(&self) -> Option<String> {
+        let search = &self.transcript_search;
+        if !search.editing && search.active_query().is_none() {
+            return None;
+        }
+        let is_zh = self.is_zh_language();
+        let query = search.query.as_str();
+        let position = match (search.active_query(), search.current) {
+            (None, _) => String::new(),
+            (Some(_), Some(current)) => format!(" · {}/{}", current + 1, search.matches.len()),
+            (Some(_), None) if is_zh => " · 无匹配".to_string(),
+            (Some(_), None) => " · no matches".to_string(),
+        };
+        Some(if search.editing {
+            if is_zh {
+                format!("搜索: {query}{position} · Enter 确认 · Esc 取消")
+            } else {
+                format!("Search: {query}{position} · Enter confirm · Esc cancel")
+            }
+        } else if is_zh {
+            format!("搜索: {query}{position} · n/N 下一个/上一个 · Esc 关闭")
+        } else {
+            format!("Search: {query}{position} · n/N next/prev · Esc close")
+        })
+    }
+
+    fn refresh_transcript_search(&mut self, jump_to_latest: bool) {
+        self.transcript_render_cache = None;
+        let Some(query) = self.transcript_search_highlight() else {
+            self.transcript_search.matches.clear();
+            self.transcript_search.current = None;
+            return;
+        };
+        let previous = self
+            .transcript_search
+            .current
+            .and_then(|position| self.transcript_search.matches.get(position).copied());
+        let width = self.transcript_viewport_width.max(1);
+        let archived = self.history_archived_entries.min(self.logs.len());
+        let mut matches = Vec::new();
+        for index in archived..self.logs.len() {
+            let text = lines_plain_text(&self.render_entry_lines(index, false, width));
+            if text.to_lowercase().contains(query.as_str()) {
+                matches.push(index);
+            }
+        }
+        self.transcript_search.current = if matches.is_empty() {
+            None
+        } else if jump_to_latest {
+            Some(matches.len() - 1)
+        } else {
+            previous
+                .and_then(|entry| matches.iter().position(|candidate| *candidate == entry))
+                .or(Some(matches.len() - 1))
+        };
+        self.transcript_search.matches = matches;
+        if jump_to_latest {
+            self.reveal_current_search_match();
+        }
+    }
+
+    fn reveal_current_search_match(&mut self) {
+        let Some(index) = self
+            .transcript_search
+            .current
+            .and_then(|position| self.transcript_search.matches.get(position).copied())
+        else {
+            return;
+        };
+        self.transcript_selected = Some(index);
+        self.ensure_transcript_selection_visible(index);
+    }
+}
+
+pub(super) fn next_search_position(
+    total: usize,
+    current: Option<usize>,
+    forward: bool,
+) -> Option<usize> {
+    if total == 0 {
+        return None;
+    }
+    let last = total - 1;
+    Some(match (current.map(|value| value.min(last)), forward) {
+        (None, true) => 0,
+        (None, false) => last,
+        (Some(value), true) if value == last => 0,
+        (Some(value), true) => value + 1,
+        (Some(0), false) => last,
+        (Some(value), false) => value - 1,
+    })
+}
+
+pub(super) fn lines_plain_text(lines: &[Line<'static>]) -> String {
+    let mut text = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            text.push('\n');
+        }
+        for span in &line.spans {
+            text.push_str(span.content.as_ref());
+        }
+    }
+    text
+}
+
+/// Split spans so every case-insensitive occurrence of `query` (already lowercased) gets
+/// `style` patched on top. Matches crossing span boundaries are left unstyled.
+pub(super) fn highlight_search_matches(
+    line: Line<'static>,
+    query: &str,
+    style: Style,
+) -> Line<'static> {
+    if query.is_empty() {
+        return line;
+    }
+    let Line {
+        spans,
+        style: line_style,
+        alignment,
+    } = line;
+    let mut highlighted = Vec::with_capacity(spans.len());
+    for span in spans {
+        let content = span.content.as_ref();
+        let ranges = find_case_insensitive_ranges(content, query);
+        if ranges.is_empty() {
+            highlighted.push(span);
+            continue;
+        }
+        let mut cursor = 0usize;
+        for (start, end) in ranges {
+            if start > cursor {
+                highlighted.push(Span::styled(content[cursor..start].to_string(), span.style));
+            }
+            highlighted.push(Span::styled(
+                content[start..end].to_string(),
+                span.style.patch(style),
+            ));
+            cursor = end;
+        }
+        if cursor < content.len() {
+            highlighted.push(Span::styled(content[cursor..].to_string(), span.style));
+        }
+    }
+    Line {
+        spans: highlighted,
+        style: line_style,
+        alignment,
+    }
+}
+
+fn find_case_insensitive_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    // Compare char by char so byte offsets always stay on `text` boundaries even when
+    // lowercasing changes the UTF-8 length of a character.
+    let query_chars = query.chars().collect::<Vec<_>>();
+    let indexed = text.char_indices().collect::<Vec<_>>();
+    let mut ranges = Vec::new();
+    let mut start = 0usize;
+    while start + query_chars.len() <= indexed.len() {
+        let matched = indexed[start..start + query_chars.len()]
+            .iter()
+            .zip(query_chars.iter())
+            .all(|((_, ch), expected)| ch.to_lowercase().eq(std::iter::once(*expected)));
+        if matched {
+            let end_char = start + query_chars.len();
+            let byte_start = indexed[start].0;
+            let byte_end = indexed
+                .get(end_char)
+                .map_or(text.len(), |(offset, _)| *offset);
+            ranges.push((byte_start, byte_end));
+            start = end_char;
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
@@ -44,6 +44,10 @@ pub async fn run_main(
         frame_requester,
     )
     .await?;
+    // Restore the saved mouse mode before the first frame so capture matches the last session.
+    if let Some(mode) = runtime.load_mouse_mode() {
+        app.restore_mouse_mode(mode.as_str());
+    }
 
     if let Some(prompt) = first_prompt
         .map(|value| value.trim().to_string())
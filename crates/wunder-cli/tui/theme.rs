@@ -108,6 +108,13 @@ pub(crate) fn transcript_selection(base: Style) -> Style {
         .add_modifier(base.add_modifier | Modifier::BOLD)
 }
 
+pub(crate) fn search_match() -> Style {
+    Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD)
+}
+
 pub(crate) fn log_style(kind: LogKind) -> Style {
     match kind {
         LogKind::Info => secondary_text(),
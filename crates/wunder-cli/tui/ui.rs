@@ -2,6 +2,7 @@ mod composer;
 mod layout;
 mod modals;
 mod popup;
+mod status_line;
 mod transcript;
 
 use super::app::TuiApp;
@@ -11,7 +12,12 @@ pub fn draw(frame: &mut Frame, app: &mut TuiApp) {
     let is_zh = app.is_zh_language();
     let popup_view = app.popup_view();
     let activity_visible = app.activity_highlighted();
-    let layout = layout::build_layout(frame.area(), popup_view.lines.len(), activity_visible);
+    let layout = layout::build_layout(
+        frame.area(),
+        popup_view.lines.len(),
+        activity_visible,
+        app.status_line_visible(),
+    );
 
     transcript::draw(frame, layout.transcript, layout.transcript, app, is_zh);
 
@@ -31,6 +37,9 @@ pub fn draw(frame: &mut Frame, app: &mut TuiApp) {
 
     app.set_mouse_regions(layout.transcript, layout.input);
     composer::draw_input(frame, layout.input, app, is_zh);
+    if let Some(status_area) = layout.status {
+        status_line::draw(frame, status_area, app);
+    }
 
     if let Some((rows, selected)) = app.resume_picker_rows() {
         modals::draw_resume_modal(frame, frame.area(), rows, selected, is_zh);
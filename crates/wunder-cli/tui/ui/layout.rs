@@ -8,10 +8,17 @@ pub(crate) struct MainLayout {
     pub(crate) popup: Option<Rect>,
     pub(crate) activity: Rect,
     pub(crate) input: Rect,
+    pub(crate) status: Option<Rect>,
 }
 
-pub(crate) fn build_layout(area: Rect, popup_len: usize, _activity_visible: bool) -> MainLayout {
+pub(crate) fn build_layout(
+    area: Rect,
+    popup_len: usize,
+    _activity_visible: bool,
+    status_visible: bool,
+) -> MainLayout {
     let activity_height = 1;
+    let status_height = u16::from(status_visible);
     if popup_len == 0 {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -19,6 +26,7 @@ pub(crate) fn build_layout(area: Rect, popup_len: usize, _activity_visible: bool
                 Constraint::Min(8),
                 Constraint::Length(activity_height),
                 Constraint::Length(5),
+                Constraint::Length(status_height),
             ])
             .split(area);
         return MainLayout {
@@ -26,6 +34,7 @@ pub(crate) fn build_layout(area: Rect, popup_len: usize, _activity_visible: bool
             popup: None,
             activity: chunks[1],
             input: chunks[2],
+            status: status_visible.then_some(chunks[3]),
         };
     }
 
@@ -37,6 +46,7 @@ pub(crate) fn build_layout(area: Rect, popup_len: usize, _activity_visible: bool
             Constraint::Length(popup_height),
             Constraint::Length(activity_height),
             Constraint::Length(5),
+            Constraint::Length(status_height),
         ])
         .split(area);
     MainLayout {
@@ -44,6 +54,7 @@ pub(crate) fn build_layout(area: Rect, popup_len: usize, _activity_visible: bool
         popup: Some(chunks[1]),
         activity: chunks[2],
         input: chunks[3],
+        status: status_visible.then_some(chunks[4]),
     }
 }
 
@@ -53,7 +64,7 @@ mod tests {
 
     #[test]
     fn build_layout_without_popup_uses_expected_sections() {
-        let layout = build_layout(Rect::new(0, 0, 100, 30), 0, true);
+        let layout = build_layout(Rect::new(0, 0, 100, 30), 0, true, false);
         assert!(layout.popup.is_none());
         assert_eq!(layout.activity.height, 1);
         assert_eq!(layout.input.height, 5);
@@ -61,15 +72,24 @@ mod tests {
 
     #[test]
     fn build_layout_with_popup_clamps_popup_height() {
-        let layout = build_layout(Rect::new(0, 0, 100, 30), 20, true);
+        let layout = build_layout(Rect::new(0, 0, 100, 30), 20, true, false);
         assert_eq!(layout.popup.expect("popup").height, 8);
         assert_eq!(layout.input.height, 5);
     }
 
     #[test]
     fn build_layout_keeps_gap_when_activity_hidden() {
-        let layout = build_layout(Rect::new(0, 0, 100, 30), 0, false);
+        let layout = build_layout(Rect::new(0, 0, 100, 30), 0, false, false);
         assert_eq!(layout.activity.height, 1);
         assert_eq!(layout.input.height, 5);
     }
+
+    #[test]
+    fn build_layout_reserves_bottom_row_for_status_line() {
+        let layout = build_layout(Rect::new(0, 0, 100, 30), 0, true, true);
+        let status = layout.status.expect("status");
+        assert_eq!(status.height, 1);
+        assert_eq!(status.y, 29);
+        assert_eq!(layout.input.height, 5);
+    }
 }
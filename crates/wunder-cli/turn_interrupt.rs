@@ -0,0 +1,137 @@
+// 行模式 Ctrl+C：回合进行中第一次 Ctrl+C 只取消当前回合并回到输入提示，空闲时（含取消后的再次按下）退出进程。
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+use tokio::sync::Notify;
+
+static TURN_ACTIVE: AtomicBool = AtomicBool::new(false);
+static TURN_INTERRUPT: Notify = Notify::const_new();
+static HANDLER: Once = Once::new();
+
+/// Exit status for a Ctrl+C exit, matching the shell convention for SIGINT.
+pub(crate) const SIGINT_EXIT_CODE: i32 = 130;
+/// `stop_reason` of a turn that Ctrl+C cancelled.
+pub(crate) const CANCELLED_STOP_REASON: &str = "cancelled";
+
+/// A one-shot turn stopped by Ctrl+C. `main` exits with [`SIGINT_EXIT_CODE`] for it, so scripts
+/// do not take an aborted run for a success; only the chat loop keeps going after a cancel.
+#[derive(Debug)]
+pub(crate) struct TurnCancelled;
+
+impl std::fmt::Display for TurnCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("turn cancelled")
+    }
+}
+
+impl std::error::Error for TurnCancelled {}
+
+/// Fail with [`TurnCancelled`] when a one-shot turn ended because of Ctrl+C.
+pub(crate) fn ensure_not_cancelled(stop_reason: Option<&str>) -> Result<(), TurnCancelled> {
+    if stop_reason == Some(CANCELLED_STOP_REASON) {
+        Err(TurnCancelled)
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) enum TurnOutcome<T> {
+    Finished(T),
+    Cancelled,
+}
+
+/// Take over SIGINT for line mode. Once tokio listens for Ctrl+C the default "kill the
+/// process" behaviour is gone for good, so the idle case has to exit explicitly.
+pub(crate) fn install_ctrl_c_handler() {
+    HANDLER.call_once(|| {
+        tokio::spawn(async {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                if TURN_ACTIVE.load(Ordering::SeqCst) {
+                    TURN_INTERRUPT.notify_waiters();
+                } else {
+                    println!();
+                    std::process::exit(SIGINT_EXIT_CODE);
+                }
+            }
+        });
+    });
+}
+
+/// Resolves on the next Ctrl+C routed to the running turn.
+pub(crate) fn ctrl_c_interrupt() -> impl Future<Output = ()> {
+    TURN_INTERRUPT.notified()
+}
+
+/// Drive `turn` until it finishes or `interrupt` fires. The turn future is dropped on
+/// interrupt; stopping the work behind it is left to the caller.
+pub(crate) async fn run_interruptible<T>(
+    turn: impl Future<Output = T>,
+    interrupt: impl Future<Output = ()>,
+) -> TurnOutcome<T> {
+    let _active = TurnActiveGuard::enter();
+    tokio::select! {
+        output = turn => TurnOutcome::Finished(output),
+        _ = interrupt => TurnOutcome::Cancelled,
+    }
+}
+
+struct TurnActiveGuard;
+
+impl TurnActiveGuard {
+    fn enter() -> Self {
+        TURN_ACTIVE.store(true, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for TurnActiveGuard {
+    fn drop(&mut self) {
+        TURN_ACTIVE.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn interrupt_drops_a_long_running_stream() {
+        // A stream that would keep emitting for far longer than the test runs.
+        let mut stream = Box::pin(futures::stream::iter(0..u64::MAX).then(|item| async move {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            item
+        }));
+        let (interrupt_tx, interrupt_rx) = tokio::sync::oneshot::channel::<()>();
+        let mut interrupt_tx = Some(interrupt_tx);
+        let mut seen = 0u64;
+        let turn = async {
+            while stream.next().await.is_some() {
+                seen += 1;
+                if seen == 3 {
+                    if let Some(tx) = interrupt_tx.take() {
+                        let _ = tx.send(());
+                    }
+                }
+            }
+        };
+        let outcome = run_interruptible(turn, async {
+            let _ = interrupt_rx.await;
+        })
+        .await;
+        assert!(matches!(outcome, TurnOutcome::Cancelled));
+        assert!((3..10).contains(&seen), "stream kept running: {seen}");
+        assert!(!TURN_ACTIVE.load(Ordering::SeqCst));
+
+        let finished = run_interruptible(async { 7 }, std::future::pending()).await;
+        assert!(matches!(finished, TurnOutcome::Finished(7)));
+    }
+
+    #[test]
+    fn only_a_cancelled_stop_reason_fails_a_one_shot_turn() {
+        assert!(ensure_not_cancelled(Some(CANCELLED_STOP_REASON)).is_err());
+        assert!(ensure_not_cancelled(Some("stop")).is_ok());
+        assert!(ensure_not_cancelled(None).is_ok());
+    }
+}
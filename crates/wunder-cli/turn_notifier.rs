@@ -0,0 +1,282 @@
+// 回合完成通知：按配置选择运行时 TurnNotifier 的 CLI 实现（BEL、OSC9、桌面系统通知、外部命令），并按失焦条件分发。
+use crate::render::FinalEvent;
+use crate::runtime::{TurnNotificationConfig, TurnNotificationWhen};
+use anyhow::{anyhow, Result};
+use std::io::{self, Write};
+use std::path::Path;
+use wunder_server::turn_notifier::{TurnNotification, TurnNotifier, TURN_COMPLETE_EVENT};
+
+/// Builds the shared runtime payload from the CLI's rendered `final` event.
+pub(crate) fn turn_notification(
+    source: &str,
+    session_id: &str,
+    user_id: &str,
+    cwd: &Path,
+    final_event: &FinalEvent,
+) -> TurnNotification {
+    TurnNotification::new(
+        source,
+        session_id,
+        user_id,
+        Some(cwd),
+        &final_event.answer,
+        final_event.stop_reason.as_deref(),
+    )
+}
+
+const DEFAULT_NOTIFICATION_TITLE: &str = "wunder-cli";
+
+/// Terminal bell on stderr.
+pub(crate) struct BellNotifier;
+
+impl TurnNotifier for BellNotifier {
+    fn notify(&self, _notification: &TurnNotification) -> Result<()> {
+        eprint!("\u{0007}");
+        io::stderr().flush()?;
+        Ok(())
+    }
+}
+
+/// OSC 9 escape sequence, shown as a notification by terminals that support it.
+pub(crate) struct Osc9Notifier;
+
+impl TurnNotifier for Osc9Notifier {
+    fn notify(&self, notification: &TurnNotification) -> Result<()> {
+        eprint!("\u{1b}]9;{}\u{1b}\\", notification.body());
+        io::stderr().flush()?;
+        Ok(())
+    }
+}
+
+/// Native OS notification through the platform's stock tool: `osascript` on macOS, a
+/// PowerShell tray balloon on Windows and `notify-send` elsewhere. Title and body go through
+/// environment variables so the answer text never needs shell quoting.
+pub(crate) struct DesktopNotifier;
+
+impl TurnNotifier for DesktopNotifier {
+    fn notify(&self, notification: &TurnNotification) -> Result<()> {
+        let mut command = desktop_notification_command();
+        command
+            .env("WUNDER_NOTIFY_TITLE", DEFAULT_NOTIFICATION_TITLE)
+            .env("WUNDER_NOTIFY_BODY", notification.body());
+        command.spawn()?;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn desktop_notification_command() -> std::process::Command {
+    let mut command = std::process::Command::new("osascript");
+    command.arg("-e").arg(
+        "display notification (system attribute \"WUNDER_NOTIFY_BODY\") \
+         with title (system attribute \"WUNDER_NOTIFY_TITLE\")",
+    );
+    command
+}
+
+#[cfg(windows)]
+const WINDOWS_BALLOON_SCRIPT: &str = "Add-Type -AssemblyName System.Windows.Forms; \
+    $n = New-Object System.Windows.Forms.NotifyIcon; \
+    $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; \
+    $n.ShowBalloonTip(5000, $env:WUNDER_NOTIFY_TITLE, $env:WUNDER_NOTIFY_BODY, 'Info'); \
+    Start-Sleep -Seconds 6; $n.Dispose()";
+
+#[cfg(windows)]
+fn desktop_notification_command() -> std::process::Command {
+    let mut command = std::process::Command::new("powershell");
+    command
+        .args(["-NoProfile", "-WindowStyle", "Hidden", "-Command"])
+        .arg(WINDOWS_BALLOON_SCRIPT);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn desktop_notification_command() -> std::process::Command {
+    let mut command = std::process::Command::new("sh");
+    command
+        .arg("-c")
+        .arg("notify-send \"$WUNDER_NOTIFY_TITLE\" \"$WUNDER_NOTIFY_BODY\"");
+    command
+}
+
+/// User-provided program; receives the JSON payload as its last argument plus
+/// `WUNDER_NOTIFY_*` environment variables.
+pub(crate) struct CommandNotifier {
+    pub argv: Vec<String>,
+}
+
+impl TurnNotifier for CommandNotifier {
+    fn notify(&self, notification: &TurnNotification) -> Result<()> {
+        let Some((program, args)) = self.argv.split_first() else {
+            return Err(anyhow!("notify command is empty"));
+        };
+        let payload_text = serde_json::to_string(&notification.payload).unwrap_or_default();
+        std::process::Command::new(program)
+            .args(args)
+            .arg(payload_text)
+            .env("WUNDER_NOTIFY_EVENT", TURN_COMPLETE_EVENT)
+            .env("WUNDER_NOTIFY_SOURCE", notification.source.as_str())
+            .env("WUNDER_NOTIFY_SESSION_ID", notification.session_id.as_str())
+            .env("WUNDER_NOTIFY_USER_ID", notification.user_id.as_str())
+            .env(
+                "WUNDER_NOTIFY_CWD",
+                notification
+                    .cwd
+                    .as_deref()
+                    .map(|cwd| cwd.to_string_lossy())
+                    .unwrap_or_default()
+                    .as_ref(),
+            )
+            .spawn()?;
+        Ok(())
+    }
+}
+
+/// Backend selected by the config, or `None` when notifications are off.
+pub(crate) fn notifier_for(config: &TurnNotificationConfig) -> Option<Box<dyn TurnNotifier>> {
+    match config {
+        TurnNotificationConfig::Off => None,
+        TurnNotificationConfig::Bell { .. } => Some(Box::new(BellNotifier)),
+        TurnNotificationConfig::Osc9 { .. } => Some(Box::new(Osc9Notifier)),
+        TurnNotificationConfig::Desktop { .. } => Some(Box::new(DesktopNotifier)),
+        TurnNotificationConfig::Command { argv, .. } => {
+            Some(Box::new(CommandNotifier { argv: argv.clone() }))
+        }
+    }
+}
+
+/// Built-in backend chosen by name in `/notify <name>`; custom commands are parsed separately.
+pub(crate) fn parse_builtin_notifier(raw: &str) -> Option<TurnNotificationConfig> {
+    let when = TurnNotificationWhen::Always;
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "bell" => Some(TurnNotificationConfig::Bell { when }),
+        "osc9" => Some(TurnNotificationConfig::Osc9 { when }),
+        "desktop" | "native" => Some(TurnNotificationConfig::Desktop { when }),
+        _ => None,
+    }
+}
+
+/// Hands the notification to `notifier` unless `when` asks for unfocused-only delivery and
+/// the terminal has focus (or focus is unknown). Returns whether the backend was invoked.
+pub(crate) fn deliver_turn_notification(
+    notifier: &dyn TurnNotifier,
+    when: &TurnNotificationWhen,
+    terminal_focused: Option<bool>,
+    notification: &TurnNotification,
+) -> bool {
+    if matches!(when, TurnNotificationWhen::Unfocused) && terminal_focused.unwrap_or(true) {
+        return false;
+    }
+    let _ = notifier.notify(notification);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::sync::Mutex;
+    use wunder_server::turn_notifier::ANSWER_PREVIEW_CHARS;
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        payloads: Mutex<Vec<Value>>,
+    }
+
+    impl TurnNotifier for RecordingNotifier {
+        fn notify(&self, notification: &TurnNotification) -> Result<()> {
+            self.payloads
+                .lock()
+                .unwrap()
+                .push(notification.payload.clone());
+            Ok(())
+        }
+    }
+
+    fn final_event(answer: &str) -> FinalEvent {
+        FinalEvent {
+            answer: answer.to_string(),
+            stop_reason: Some("model_response".to_string()),
+            ..FinalEvent::default()
+        }
+    }
+
+    #[test]
+    fn mock_notifier_receives_turn_complete_payload() {
+        let notifier = RecordingNotifier::default();
+        let notification = turn_notification(
+            "tui",
+            "sess_1",
+            "alice",
+            Path::new("/tmp/project"),
+            &final_event(&"x".repeat(400)),
+        );
+
+        assert!(deliver_turn_notification(
+            &notifier,
+            &TurnNotificationWhen::Always,
+            Some(true),
+            &notification,
+        ));
+
+        let payloads = notifier.payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 1);
+        let payload = &payloads[0];
+        assert_eq!(payload["type"], TURN_COMPLETE_EVENT);
+        assert_eq!(payload["source"], "tui");
+        assert_eq!(payload["session_id"], "sess_1");
+        assert_eq!(payload["user_id"], "alice");
+        assert_eq!(payload["cwd"], "/tmp/project");
+        assert_eq!(payload["stop_reason"], "model_response");
+        let preview = payload["answer_preview"].as_str().unwrap();
+        assert_eq!(preview.chars().count(), ANSWER_PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+    }
+
+    #[test]
+    fn unfocused_only_skips_focused_or_unknown_terminal() {
+        let notifier = RecordingNotifier::default();
+        let notification = turn_notification(
+            "line-chat",
+            "sess_2",
+            "alice",
+            Path::new("/tmp"),
+            &final_event("done"),
+        );
+        let when = TurnNotificationWhen::Unfocused;
+
+        assert!(!deliver_turn_notification(
+            &notifier,
+            &when,
+            Some(true),
+            &notification
+        ));
+        assert!(!deliver_turn_notification(
+            &notifier,
+            &when,
+            None,
+            &notification
+        ));
+        assert!(deliver_turn_notification(
+            &notifier,
+            &when,
+            Some(false),
+            &notification
+        ));
+        assert_eq!(notifier.payloads.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn config_selects_builtin_backends() {
+        assert!(notifier_for(&TurnNotificationConfig::Off).is_none());
+        assert_eq!(
+            parse_builtin_notifier("Desktop"),
+            Some(TurnNotificationConfig::Desktop {
+                when: TurnNotificationWhen::Always,
+            })
+        );
+        assert_eq!(parse_builtin_notifier("say"), None);
+        let desktop = parse_builtin_notifier("desktop").unwrap();
+        assert!(notifier_for(&desktop).is_some());
+    }
+}
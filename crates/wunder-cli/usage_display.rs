@@ -0,0 +1,131 @@
+// /usage 额度展示：把当前用户的 Token 额度渲染成进度条与剩余量说明。
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use wunder_server::user_access::{load_user_quota_status, UserQuotaStatus};
+
+const BAR_WIDTH: usize = 24;
+/// Remaining share below which the summary adds a low-quota warning.
+const LOW_REMAINING_RATIO: f64 = 0.1;
+
+pub(crate) async fn load_quota_usage_lines(
+    runtime: &CliRuntime,
+    language: &str,
+) -> Result<Vec<String>> {
+    let state = runtime.state.clone();
+    let user_id = runtime.user_id.clone();
+    let status = tokio::task::spawn_blocking(move || load_user_quota_status(&state, &user_id))
+        .await
+        .map_err(|err| anyhow!("load quota cancelled: {err}"))??;
+    Ok(quota_usage_lines(language, &status))
+}
+
+pub(crate) fn quota_usage_lines(language: &str, status: &UserQuotaStatus) -> Vec<String> {
+    let is_zh = locale::is_zh_language(language);
+    if status.unlimited || status.daily_quota <= 0 {
+        return vec![locale::tr(
+            language,
+            "额度：不限量（当前账号不计量 Token）",
+            "quota: unlimited (tokens are not metered for this account)",
+        )];
+    }
+    let used = status.used.clamp(0, status.daily_quota);
+    let ratio = used as f64 / status.daily_quota as f64;
+    let filled = ((ratio * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let bar = format!("[{}{}]", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled));
+    let percent = ratio * 100.0;
+    let mut lines = vec![if is_zh {
+        format!(
+            "额度 {bar} {percent:.0}%  已用 {} / {}，剩余 {}",
+            status.used, status.daily_quota, status.remaining
+        )
+    } else {
+        format!(
+            "quota {bar} {percent:.0}%  used {} / {}, remaining {}",
+            status.used, status.daily_quota, status.remaining
+        )
+    }];
+    if let Some(date) = status.date.as_deref() {
+        lines.push(if is_zh {
+            format!("最近发放日期：{date}")
+        } else {
+            format!("last grant: {date}")
+        });
+    }
+    if !status.allowed {
+        lines.push(match status.resets_at.as_deref().map(format_reset_time) {
+            Some(resets_at) if is_zh => format!("额度已用尽，将于 {resets_at} 重置"),
+            Some(resets_at) => format!("quota exhausted, resets at {resets_at}"),
+            None => locale::tr(
+                language,
+                "额度已用尽，模型调用将被拒绝，直到下次发放",
+                "quota exhausted; model calls are rejected until the next grant",
+            ),
+        });
+    } else if (status.remaining as f64) < status.daily_quota as f64 * LOW_REMAINING_RATIO {
+        lines.push(locale::tr(
+            language,
+            "额度即将用尽",
+            "quota is nearly used up",
+        ));
+    }
+    lines
+}
+
+fn format_reset_time(value: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn near_limit_account_renders_a_mostly_full_bar_with_warning() {
+        let status = UserQuotaStatus {
+            daily_quota: 1000,
+            used: 960,
+            remaining: 40,
+            date: Some("2026-10-16".to_string()),
+            allowed: true,
+            unlimited: false,
+            resets_at: Some("2026-10-17T00:00:00+08:00".to_string()),
+        };
+        let lines = quota_usage_lines("en-US", &status);
+        assert_eq!(
+            lines,
+            vec![
+                "quota [#######################-] 96%  used 960 / 1000, remaining 40".to_string(),
+                "last grant: 2026-10-16".to_string(),
+                "quota is nearly used up".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn exhausted_account_reports_the_reset_time() {
+        let status = UserQuotaStatus {
+            daily_quota: 1000,
+            used: 1000,
+            remaining: 0,
+            date: None,
+            allowed: false,
+            unlimited: false,
+            resets_at: Some("2026-10-17T00:00:00+08:00".to_string()),
+        };
+        let lines = quota_usage_lines("en-US", &status);
+        assert_eq!(
+            lines.last().map(String::as_str),
+            Some("quota exhausted, resets at 2026-10-17 00:00")
+        );
+    }
+
+    #[test]
+    fn unlimited_quota_skips_the_bar() {
+        let lines = quota_usage_lines("en-US", &UserQuotaStatus::unlimited());
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("unlimited"));
+    }
+}
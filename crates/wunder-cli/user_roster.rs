@@ -0,0 +1,374 @@
+// 用户名册：`wunder-cli users list` 按角色/状态/组织单元（含下级单元）筛选账号，`users tools grant/revoke` 增减工具授权；均需管理员 API Key 或管理员会话令牌。
+use crate::args::{
+    AdminAuthArgs, GlobalArgs, UsersCommand, UsersListCommand, UsersSubcommand,
+    UsersToolsChangeCommand, UsersToolsSubcommand,
+};
+use crate::locale;
+use crate::runtime::CliRuntime;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use wunder_server::org_units;
+use wunder_server::storage::{OrgUnitRecord, UserAccountRecord};
+use wunder_server::user_access::{self, UserQuotaStatus};
+use wunder_server::user_store::UserStore;
+
+const API_KEY_ENV: &str = "WUNDER_API_KEY";
+
+#[derive(Debug, Serialize)]
+struct UserRosterRow {
+    user_id: String,
+    username: String,
+    roles: Vec<String>,
+    status: String,
+    access_level: String,
+    unit_id: Option<String>,
+    unit: Option<String>,
+    quota: UserQuotaStatus,
+    last_login_at: Option<f64>,
+}
+
+pub(crate) async fn handle_users(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: UsersCommand,
+) -> Result<()> {
+    match command.command {
+        UsersSubcommand::List(cmd) => list_users(runtime, global, cmd).await,
+        UsersSubcommand::Tools(cmd) => match cmd.command {
+            UsersToolsSubcommand::Grant(cmd) => change_tools(runtime, global, cmd, true).await,
+            UsersToolsSubcommand::Revoke(cmd) => change_tools(runtime, global, cmd, false).await,
+        },
+    }
+}
+
+async fn list_users(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: UsersListCommand,
+) -> Result<()> {
+    ensure_admin(runtime, &command.auth).await?;
+    let user_store = runtime.state.user_store.clone();
+    let (users, units) = tokio::task::spawn_blocking(move || -> Result<_> {
+        let (users, _) = user_store.list_users(None, None, 0, 0)?;
+        Ok((users, user_store.list_org_units()?))
+    })
+    .await
+    .map_err(|err| anyhow!("list users task cancelled: {err}"))??;
+
+    let users = filter_users(users, &units, &command)?;
+    let unit_map = units
+        .iter()
+        .map(|unit| (unit.unit_id.as_str(), unit))
+        .collect::<HashMap<_, _>>();
+    let rows = users
+        .iter()
+        .map(|user| {
+            let unit = user
+                .unit_id
+                .as_deref()
+                .and_then(|unit_id| unit_map.get(unit_id).copied());
+            build_row(user, unit)
+        })
+        .collect::<Vec<_>>();
+
+    if global.json {
+        println!("{}", serde_json::to_string(&rows)?);
+        return Ok(());
+    }
+    let language = locale::resolve_cli_language(global);
+    if rows.is_empty() {
+        println!(
+            "{}",
+            locale::tr(
+                language.as_str(),
+                "没有符合条件的用户",
+                "no users match the filters"
+            )
+        );
+        return Ok(());
+    }
+    for row in &rows {
+        let roles = if row.roles.is_empty() {
+            "-".to_string()
+        } else {
+            row.roles.join(",")
+        };
+        let last_login = row
+            .last_login_at
+            .map(crate::format_session_time)
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{}  roles={roles}  status={}  access={}  unit={}  quota={}  last_login={last_login}",
+            row.username,
+            row.status,
+            row.access_level,
+            row.unit.as_deref().unwrap_or("-"),
+            format_quota(&row.quota),
+        );
+    }
+    println!(
+        "{}",
+        if locale::is_zh_language(language.as_str()) {
+            format!("共 {} 个用户", rows.len())
+        } else {
+            format!("{} users", rows.len())
+        }
+    );
+    Ok(())
+}
+
+async fn change_tools(
+    runtime: &CliRuntime,
+    global: &GlobalArgs,
+    command: UsersToolsChangeCommand,
+    grant: bool,
+) -> Result<()> {
+    ensure_admin(runtime, &command.auth).await?;
+    let user_store = runtime.state.user_store.clone();
+    let lookup = command.user.trim().to_string();
+    let user = tokio::task::spawn_blocking(move || -> Result<_> {
+        match user_store.get_user_by_id(&lookup)? {
+            Some(user) => Ok(Some(user)),
+            None => user_store.get_user_by_username(&lookup),
+        }
+    })
+    .await
+    .map_err(|err| anyhow!("load user task cancelled: {err}"))??
+    .ok_or_else(|| anyhow!("user not found: {}", command.user.trim()))?;
+
+    let available = user_access::available_tool_names_for_user(&runtime.state, &user.user_id).await;
+    let user_store = runtime.state.user_store.clone();
+    let user_id = user.user_id.clone();
+    let tools = command.tools;
+    let update = tokio::task::spawn_blocking(move || {
+        if grant {
+            user_access::grant_tools(&user_store, &user_id, &tools, &available)
+        } else {
+            user_access::revoke_tools(&user_store, &user_id, &tools, &available)
+        }
+    })
+    .await
+    .map_err(|err| anyhow!("update tool access task cancelled: {err}"))??;
+
+    if global.json {
+        println!("{}", serde_json::to_string(&update)?);
+        return Ok(());
+    }
+    let language = locale::resolve_cli_language(global);
+    let is_zh = locale::is_zh_language(language.as_str());
+    let changed = if update.changed.is_empty() {
+        "-".to_string()
+    } else {
+        update.changed.join(", ")
+    };
+    let allowed = match update.allowed_tools.as_ref() {
+        Some(items) => items.join(", "),
+        None if is_zh => "全部可用工具（未设白名单）".to_string(),
+        None => "all available tools (no allow-list)".to_string(),
+    };
+    match (is_zh, grant) {
+        (true, true) => println!("{} 新授予: {changed}", user.username),
+        (true, false) => println!("{} 已撤销: {changed}", user.username),
+        (false, true) => println!("{} granted: {changed}", user.username),
+        (false, false) => println!("{} revoked: {changed}", user.username),
+    }
+    if is_zh {
+        println!("允许的工具: {allowed}");
+    } else {
+        println!("allowed tools: {allowed}");
+    }
+    Ok(())
+}
+
+/// Same checks as the server's admin route guard: the configured API key, or a session token
+/// that belongs to an admin account.
+async fn ensure_admin(runtime: &CliRuntime, auth: &AdminAuthArgs) -> Result<()> {
+    let config = runtime.state.config_store.get().await;
+    let provided_key = auth
+        .api_key
+        .clone()
+        .or_else(|| std::env::var(API_KEY_ENV).ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if let (Some(expected), Some(provided)) = (config.api_key(), provided_key) {
+        if provided == expected {
+            return Ok(());
+        }
+    }
+    if let Some(token) = auth
+        .token
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        let user_store = runtime.state.user_store.clone();
+        let token = token.to_string();
+        let user = tokio::task::spawn_blocking(move || user_store.authenticate_token(&token))
+            .await
+            .map_err(|err| anyhow!("authenticate token task cancelled: {err}"))??;
+        if user.as_ref().is_some_and(UserStore::is_admin) {
+            return Ok(());
+        }
+    }
+    Err(anyhow!(
+        "admin auth required: pass --api-key (or set {API_KEY_ENV}) or --token of an admin session"
+    ))
+}
+
+fn filter_users(
+    users: Vec<UserAccountRecord>,
+    units: &[OrgUnitRecord],
+    command: &UsersListCommand,
+) -> Result<Vec<UserAccountRecord>> {
+    let unit_scope = command
+        .unit
+        .as_deref()
+        .map(|raw| resolve_unit_scope(units, raw))
+        .transpose()?;
+    let role = command.role.as_deref().map(str::trim);
+    let status = command.status.as_deref().map(str::trim);
+    Ok(users
+        .into_iter()
+        .filter(|user| {
+            role.is_none_or(|role| {
+                user.roles
+                    .iter()
+                    .any(|item| item.eq_ignore_ascii_case(role))
+            })
+        })
+        .filter(|user| status.is_none_or(|status| user.status.eq_ignore_ascii_case(status)))
+        .filter(|user| {
+            unit_scope.as_ref().is_none_or(|scope| {
+                user.unit_id
+                    .as_ref()
+                    .is_some_and(|unit_id| scope.contains(unit_id))
+            })
+        })
+        .collect())
+}
+
+/// Matches the unit by id, name or full path name and includes every unit below it.
+fn resolve_unit_scope(units: &[OrgUnitRecord], raw: &str) -> Result<HashSet<String>> {
+    let target = raw.trim();
+    let roots = units
+        .iter()
+        .filter(|unit| unit.unit_id == target || unit.name == target || unit.path_name == target)
+        .map(|unit| unit.unit_id.clone())
+        .collect::<Vec<_>>();
+    if roots.is_empty() {
+        return Err(anyhow!("org unit not found: {target}"));
+    }
+    Ok(org_units::collect_descendant_unit_ids(units, &roots))
+}
+
+fn build_row(user: &UserAccountRecord, unit: Option<&OrgUnitRecord>) -> UserRosterRow {
+    let token_status =
+        UserStore::effective_token_balance_status(user, unit.map(|item| item.level), None);
+    UserRosterRow {
+        user_id: user.user_id.clone(),
+        username: user.username.clone(),
+        roles: user.roles.clone(),
+        status: user.status.clone(),
+        access_level: user.access_level.clone(),
+        unit_id: user.unit_id.clone(),
+        unit: unit.map(|item| item.path_name.clone()),
+        quota: UserQuotaStatus::from_token_status(&token_status),
+        last_login_at: user.last_login_at,
+    }
+}
+
+fn format_quota(quota: &UserQuotaStatus) -> String {
+    if quota.unlimited {
+        return "unlimited".to_string();
+    }
+    let state = if quota.allowed { "ok" } else { "exhausted" };
+    format!("{}/{} {state}", quota.remaining, quota.daily_quota)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(unit_id: &str, parent_id: Option<&str>, path: &str, name: &str) -> OrgUnitRecord {
+        OrgUnitRecord {
+            unit_id: unit_id.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            name: name.to_string(),
+            level: path.split('/').count() as i32,
+            path: path.to_string(),
+            path_name: name.to_string(),
+            sort_order: 0,
+            leader_ids: Vec::new(),
+            created_at: 0.0,
+            updated_at: 0.0,
+        }
+    }
+
+    fn user(username: &str, unit_id: Option<&str>, role: &str) -> UserAccountRecord {
+        UserAccountRecord {
+            user_id: username.to_string(),
+            username: username.to_string(),
+            email: None,
+            password_hash: String::new(),
+            roles: vec![role.to_string()],
+            status: "active".to_string(),
+            access_level: "A".to_string(),
+            unit_id: unit_id.map(str::to_string),
+            token_balance: 0,
+            token_granted_total: 0,
+            token_used_total: 0,
+            last_token_grant_date: None,
+            experience_total: 0,
+            is_demo: false,
+            created_at: 0.0,
+            updated_at: 0.0,
+            last_login_at: None,
+        }
+    }
+
+    fn list_command(unit: Option<&str>, role: Option<&str>) -> UsersListCommand {
+        UsersListCommand {
+            role: role.map(str::to_string),
+            status: None,
+            unit: unit.map(str::to_string),
+            auth: AdminAuthArgs {
+                api_key: None,
+                token: None,
+            },
+        }
+    }
+
+    #[test]
+    fn unit_filter_includes_descendant_units() {
+        let units = vec![
+            unit("root", None, "root", "Company"),
+            unit("rd", Some("root"), "root/rd", "R&D"),
+            unit("rd_infra", Some("rd"), "root/rd/rd_infra", "Infra"),
+            // Shares the "root/rd" prefix without being a child of it.
+            unit("rd2", Some("root"), "root/rd2", "R&D 2"),
+        ];
+        let users = vec![
+            user("alice", Some("rd"), "user"),
+            user("bob", Some("rd_infra"), "admin"),
+            user("carol", Some("rd2"), "user"),
+            user("dave", Some("root"), "user"),
+            user("erin", None, "user"),
+        ];
+        let names = |command: &UsersListCommand| {
+            filter_users(users.clone(), &units, command)
+                .expect("filter users")
+                .into_iter()
+                .map(|user| user.username)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(&list_command(Some("rd"), None)), ["alice", "bob"]);
+        assert_eq!(names(&list_command(Some("R&D"), Some("admin"))), ["bob"]);
+        assert_eq!(
+            names(&list_command(Some("root"), None)),
+            ["alice", "bob", "carol", "dave"]
+        );
+        assert!(filter_users(users.clone(), &units, &list_command(Some("nope"), None)).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use anyhow::{anyhow, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -15,6 +16,40 @@ pub(crate) fn project_root_name(repo_root: &Path) -> Option<String> {
         })
 }
 
+/// Nearest ancestor of `launch_dir` (itself included) holding `.git`. The walk never climbs
+/// into `home`, so a dotfiles repository there does not swallow unrelated projects.
+pub(crate) fn find_git_repo_root(launch_dir: &Path, home: Option<&Path>) -> Option<PathBuf> {
+    for dir in launch_dir.ancestors() {
+        if dir != launch_dir && home.is_some_and(|home| dir == home) {
+            return None;
+        }
+        if dir.join(".git").exists() {
+            return Some(dir.to_path_buf());
+        }
+    }
+    None
+}
+
+/// `--repo-root` when given (relative to `launch_dir`, must be a directory), else the nearest
+/// `.git` ancestor, else `launch_dir`.
+pub(crate) fn resolve_repo_root(
+    override_root: Option<&Path>,
+    launch_dir: &Path,
+    home: Option<&Path>,
+) -> Result<PathBuf> {
+    if let Some(raw) = override_root {
+        let candidate = launch_dir.join(raw);
+        if !candidate.is_dir() {
+            return Err(anyhow!(
+                "--repo-root is not a directory: {}",
+                candidate.display()
+            ));
+        }
+        return Ok(fs::canonicalize(&candidate).unwrap_or(candidate));
+    }
+    Ok(find_git_repo_root(launch_dir, home).unwrap_or_else(|| launch_dir.to_path_buf()))
+}
+
 pub(crate) fn read_git_branch(repo_root: &Path) -> Option<String> {
     let git_dir = resolve_git_dir(repo_root)?;
     let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
@@ -87,9 +122,12 @@ fn parse_git_head(head: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{format_branch_display, project_root_name, read_git_branch};
+    use super::{
+        find_git_repo_root, format_branch_display, project_root_name, read_git_branch,
+        resolve_repo_root,
+    };
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
@@ -126,6 +164,49 @@ mod tests {
         fs::remove_dir_all(repo).unwrap();
     }
 
+    #[test]
+    fn repo_root_detection_finds_nearest_git_ancestor() {
+        let home = unique_temp_dir("repo-root-home");
+        let outer = home.join("outer");
+        let inner = outer.join("packages").join("inner");
+        let launch = inner.join("src");
+        fs::create_dir_all(&launch).unwrap();
+        fs::create_dir_all(outer.join(".git")).unwrap();
+        fs::write(inner.join(".git"), "gitdir: ../../.git/worktrees/inner\n").unwrap();
+
+        assert_eq!(
+            find_git_repo_root(&launch, Some(&home)),
+            Some(inner.clone())
+        );
+        assert_eq!(
+            resolve_repo_root(None, &outer.join("packages"), Some(&home)).unwrap(),
+            outer
+        );
+
+        // Detection stops below home and falls back to the launch directory.
+        fs::create_dir_all(home.join(".git")).unwrap();
+        let loose = home.join("scratch");
+        fs::create_dir_all(&loose).unwrap();
+        assert_eq!(find_git_repo_root(&loose, Some(&home)), None);
+        assert_eq!(resolve_repo_root(None, &loose, Some(&home)).unwrap(), loose);
+
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[test]
+    fn repo_root_flag_overrides_detection() {
+        let repo = unique_temp_dir("repo-root-flag");
+        let launch = repo.join("app");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(&launch).unwrap();
+
+        let resolved = resolve_repo_root(Some(Path::new(".")), &launch, None).unwrap();
+        assert_eq!(resolved, fs::canonicalize(&launch).unwrap());
+        assert!(resolve_repo_root(Some(Path::new("missing")), &launch, None).is_err());
+
+        fs::remove_dir_all(repo).unwrap();
+    }
+
     #[test]
     fn format_branch_display_truncates_middle() {
         assert_eq!(
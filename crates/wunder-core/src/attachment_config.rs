@@ -0,0 +1,62 @@
+// 附件处理配置：图片附件 OCR 回退，开启后调用外部 OCR 命令（默认 tesseract）把图片中的文字作为文本附件交给模型，默认关闭。
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AttachmentsConfig {
+    #[serde(default)]
+    pub ocr: AttachmentOcrConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentOcrConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// OCR executable; invoked as `<command> stdin stdout -l <languages>`.
+    #[serde(default = "default_ocr_command")]
+    pub command: String,
+    /// Tesseract language packs joined by `+`, such as `eng+chi_sim`.
+    #[serde(default = "default_ocr_languages")]
+    pub languages: String,
+    #[serde(default = "default_ocr_timeout_s")]
+    pub timeout_s: u64,
+    #[serde(default = "default_ocr_max_chars")]
+    pub max_chars: usize,
+}
+
+impl Default for AttachmentOcrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            command: default_ocr_command(),
+            languages: default_ocr_languages(),
+            timeout_s: default_ocr_timeout_s(),
+            max_chars: default_ocr_max_chars(),
+        }
+    }
+}
+
+impl AttachmentOcrConfig {
+    pub fn timeout_s(&self) -> u64 {
+        self.timeout_s.clamp(1, 600)
+    }
+
+    pub fn max_chars(&self) -> usize {
+        self.max_chars.clamp(100, 200_000)
+    }
+}
+
+fn default_ocr_command() -> String {
+    "tesseract".to_string()
+}
+
+fn default_ocr_languages() -> String {
+    "eng".to_string()
+}
+
+fn default_ocr_timeout_s() -> u64 {
+    30
+}
+
+fn default_ocr_max_chars() -> usize {
+    20_000
+}
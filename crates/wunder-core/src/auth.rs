@@ -2,6 +2,10 @@ pub fn is_admin_path(path: &str) -> bool {
     if path == "/.well-known/agent-card.json" {
         return false;
     }
+    // Orchestrator probes carry no credentials.
+    if path == "/healthz" || path == "/readyz" {
+        return false;
+    }
     if path.starts_with("/a2a") {
         return true;
     }
@@ -14,6 +18,9 @@ pub fn is_admin_path(path: &str) -> bool {
     if path_is_prefix_or_child(path, "/wunder/i18n") {
         return false;
     }
+    if path_is_prefix_or_child(path, "/wunder/schemas") {
+        return false;
+    }
     if path_is_prefix_or_child(path, "/wunder/doc2md") {
         return false;
     }
@@ -26,6 +33,14 @@ pub fn is_admin_path(path: &str) -> bool {
     if path_is_prefix_or_child(path, "/wunder/chat") {
         return false;
     }
+    // Chunked uploads are user-scoped; `/wunder/attachments/convert` stays a debug-panel route.
+    if matches!(
+        path,
+        "/wunder/attachments/init" | "/wunder/attachments/chunk" | "/wunder/attachments/complete"
+    ) || path_is_prefix_or_child(path, "/wunder/attachments/uploads")
+    {
+        return false;
+    }
     if path_is_prefix_or_child(path, "/wunder/workspace") {
         return false;
     }
@@ -112,11 +127,16 @@ mod tests {
         assert!(!is_admin_path("/wunder/ppt"));
         assert!(!is_admin_path("/wunder/ppt-en"));
         assert!(!is_admin_path("/wunder/i18n"));
+        assert!(!is_admin_path("/wunder/schemas/request"));
         assert!(!is_admin_path("/wunder/doc2md/convert"));
         assert!(!is_admin_path("/wunder/temp_dir/download"));
         assert!(!is_admin_path("/.well-known/agent-card.json"));
+        assert!(!is_admin_path("/healthz"));
+        assert!(!is_admin_path("/readyz"));
         assert!(!is_admin_path("/wunder/auth/login"));
         assert!(!is_admin_path("/wunder/chat/sessions"));
+        assert!(!is_admin_path("/wunder/attachments/init"));
+        assert!(!is_admin_path("/wunder/attachments/uploads/upl_1"));
         assert!(!is_admin_path("/wunder/workspace"));
         assert!(!is_admin_path("/wunder/user_world/contacts"));
         assert!(!is_admin_path("/wunder/user_world/ws"));
@@ -136,6 +156,7 @@ mod tests {
         assert!(!is_admin_path("/wunder/companions/global/abc"));
         assert!(is_admin_path("/wunder"));
         assert!(is_admin_path("/wunder/mcp"));
+        assert!(is_admin_path("/wunder/attachments/convert"));
         assert!(is_admin_path("/a2a"));
     }
 
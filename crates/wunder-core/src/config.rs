@@ -1,4 +1,5 @@
 // Config loading and YAML utilities.
+use crate::attachment_config::AttachmentsConfig;
 use crate::drawio_config::DrawioConfig;
 use crate::onlyoffice_config::OnlyOfficeConfig;
 use serde::de::{self, Deserializer, Visitor};
@@ -24,6 +25,8 @@ pub struct Config {
     #[serde(default)]
     pub llm: LlmConfig,
     #[serde(default)]
+    pub embedding: EmbeddingConfig,
+    #[serde(default)]
     pub tools: ToolsConfig,
     #[serde(default)]
     pub browser: BrowserConfig,
@@ -36,6 +39,8 @@ pub struct Config {
     #[serde(default)]
     pub drawio: DrawioConfig,
     #[serde(default)]
+    pub attachments: AttachmentsConfig,
+    #[serde(default)]
     pub mcp: McpConfig,
     #[serde(default)]
     pub lsp: LspConfig,
@@ -105,14 +110,28 @@ pub struct SecurityConfig {
     pub allow_paths: Vec<String>,
     #[serde(default)]
     pub deny_globs: Vec<String>,
+    /// Environment variables execute_command and ptc pass to child processes; empty selects a
+    /// minimal safe set and `*` passes the whole environment.
+    #[serde(default)]
+    pub env_passthrough: Vec<String>,
     #[serde(default)]
     pub exec_policy_mode: Option<String>,
     /// CLI-only: approval mode for write/exec tools (suggest/auto_edit/full_auto).
     /// Server deployments typically leave this unset and rely on allow_paths/deny_globs + auth.
     #[serde(default)]
     pub approval_mode: Option<String>,
+    /// Seconds an approval raised over HTTP waits for a decision before it is auto-denied.
+    #[serde(default = "default_approval_timeout_s")]
+    pub approval_timeout_s: u64,
+    /// Operations that skip the approval prompt under suggest/auto_edit, checked in order.
+    #[serde(default)]
+    pub auto_approve: Vec<AutoApproveRule>,
     #[serde(default = "default_allow_user_registration")]
     pub allow_user_registration: bool,
+    /// Frame tool results as untrusted data and escape prompt-injection markers before the
+    /// model sees them.
+    #[serde(default)]
+    pub tool_result_guard: bool,
 }
 
 impl Default for SecurityConfig {
@@ -126,13 +145,34 @@ impl Default for SecurityConfig {
             allow_commands: Vec::new(),
             allow_paths: Vec::new(),
             deny_globs: Vec::new(),
+            env_passthrough: Vec::new(),
             exec_policy_mode: None,
             approval_mode: None,
+            approval_timeout_s: default_approval_timeout_s(),
+            auto_approve: Vec::new(),
             allow_user_registration: default_allow_user_registration(),
+            tool_result_guard: false,
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AutoApproveRule {
+    /// Tool name (or alias); `*` matches every tool.
+    pub tool: String,
+    /// Command prefixes every line of an exec command must start with; empty means any command.
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Only match when the target path (exec `workdir`, write `path`) stays inside the
+    /// workspace or one of `allow_paths`.
+    #[serde(default)]
+    pub within_allow_paths: bool,
+}
+
+fn default_approval_timeout_s() -> u64 {
+    300
+}
+
 fn default_allow_user_registration() -> bool {
     true
 }
@@ -152,6 +192,23 @@ pub struct ServerConfig {
     pub port: u16,
     pub stream_chunk_size: usize,
     pub max_active_sessions: usize,
+    #[serde(
+        default = "default_max_queued_requests",
+        deserialize_with = "deserialize_usize_from_any"
+    )]
+    pub max_queued_requests: usize,
+    #[serde(
+        default = "default_max_queued_per_user",
+        deserialize_with = "deserialize_usize_from_any"
+    )]
+    pub max_queued_per_user: usize,
+    #[serde(default, deserialize_with = "deserialize_usize_from_any")]
+    pub max_queued_per_model: usize,
+    #[serde(
+        default = "default_max_queue_wait_s",
+        deserialize_with = "deserialize_u64_from_any"
+    )]
+    pub max_queue_wait_s: u64,
     #[serde(
         default = "default_tool_failure_guard_threshold",
         deserialize_with = "deserialize_usize_from_any"
@@ -168,12 +225,28 @@ impl Default for ServerConfig {
             port: 8000,
             stream_chunk_size: 1024,
             max_active_sessions: 300,
+            max_queued_requests: default_max_queued_requests(),
+            max_queued_per_user: default_max_queued_per_user(),
+            max_queued_per_model: 0,
+            max_queue_wait_s: default_max_queue_wait_s(),
             tool_failure_guard_threshold: default_tool_failure_guard_threshold(),
             mode: "api".to_string(),
         }
     }
 }
 
+fn default_max_queued_requests() -> usize {
+    1000
+}
+
+fn default_max_queued_per_user() -> usize {
+    5
+}
+
+fn default_max_queue_wait_s() -> u64 {
+    600
+}
+
 fn default_tool_failure_guard_threshold() -> usize {
     5
 }
@@ -218,6 +291,73 @@ pub struct LlmConfig {
     pub models: HashMap<String, LlmModelConfig>,
     #[serde(default)]
     pub virtual_replay: VirtualLlmConfig,
+    #[serde(default)]
+    pub auto_title: AutoTitleConfig,
+    #[serde(default)]
+    pub stream_interrupt: StreamInterruptConfig,
+}
+
+/// Model-generated session titles after the first turn. Off by default; sessions keep the
+/// truncated first prompt when disabled or when the title call fails.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AutoTitleConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Model used for the title call; falls back to `llm.default`.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Upper bound on the generated title length in characters.
+    #[serde(default)]
+    pub max_chars: Option<usize>,
+}
+
+/// Handling of provider streams that drop after some answer text arrived. The turn ends with
+/// the partial answer marked incomplete; with `retry` the request is first re-sent once.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StreamInterruptConfig {
+    #[serde(default)]
+    pub retry: bool,
+}
+
+/// Dedicated embedding endpoints for vector knowledge, looked up by name before the
+/// `model_type: embedding` entries of `llm.models`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EmbeddingConfig {
+    #[serde(default)]
+    pub models: HashMap<String, EmbeddingModelConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EmbeddingModelConfig {
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub dimensions: Option<u32>,
+    #[serde(default)]
+    pub timeout_s: Option<u64>,
+}
+
+impl EmbeddingModelConfig {
+    /// Request settings in the model-config shape the embedding client takes.
+    pub fn to_model_config(&self) -> LlmModelConfig {
+        LlmModelConfig {
+            enable: Some(true),
+            provider: self.provider.clone(),
+            base_url: self.base_url.clone(),
+            api_key: self.api_key.clone(),
+            model: self.model.clone(),
+            timeout_s: self.timeout_s,
+            model_type: Some("embedding".to_string()),
+            dimensions: self.dimensions,
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -306,6 +446,10 @@ pub struct UserAgentsConfig {
     pub worker_cards_root: String,
     #[serde(default = "default_user_agent_presets")]
     pub presets: Vec<UserAgentPresetConfig>,
+    /// Directories scanned for `*.yaml` agent definitions when a request sets `agent_id`.
+    /// Empty by default; relative entries resolve against `workspace.root`.
+    #[serde(default)]
+    pub definition_paths: Vec<String>,
 }
 
 impl Default for UserAgentsConfig {
@@ -313,6 +457,7 @@ impl Default for UserAgentsConfig {
         Self {
             worker_cards_root: default_user_agent_worker_cards_root(),
             presets: default_user_agent_presets(),
+            definition_paths: Vec::new(),
         }
     }
 }
@@ -400,6 +545,9 @@ pub struct LlmModelConfig {
     pub model: Option<String>,
     #[serde(default)]
     pub temperature: Option<f32>,
+    /// Nucleus sampling; left out of requests when unset so providers keep their default.
+    #[serde(default)]
+    pub top_p: Option<f32>,
     #[serde(default)]
     pub timeout_s: Option<u64>,
     #[serde(default)]
@@ -408,6 +556,10 @@ pub struct LlmModelConfig {
     pub max_context: Option<u32>,
     #[serde(default)]
     pub max_output: Option<u32>,
+    /// Tokens kept free for the reply when the prompt is trimmed to `max_context` before sending;
+    /// defaults to `max_output`.
+    #[serde(default)]
+    pub context_reserve_tokens: Option<u32>,
     #[serde(default)]
     pub thinking_token_budget: Option<u32>,
     #[serde(default)]
@@ -428,8 +580,25 @@ pub struct LlmModelConfig {
     pub model_type: Option<String>,
     #[serde(default)]
     pub stop: Option<Vec<String>>,
+    /// Structured output: `json_object`, or an OpenAI `response_format` object such as
+    /// `{type: json_schema, json_schema: {name, schema, strict}}`. Sent to OpenAI-compatible
+    /// chat/responses APIs only; the final answer is checked to parse as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<Value>,
+    /// Ask the model once more when a final answer under `response_format` is not valid JSON.
+    #[serde(default)]
+    pub response_format_retry: Option<bool>,
+    /// In-flight requests allowed for this model across all sessions; unset or 0 means unlimited.
+    #[serde(default)]
+    pub max_concurrency: Option<u32>,
+    /// Requests started per second for this model across all sessions; unset or 0 means unlimited.
+    #[serde(default)]
+    pub max_qps: Option<f32>,
     #[serde(default)]
     pub mock_if_unconfigured: Option<bool>,
+    /// Scripted replies for `provider: mock`, one step per model round of a user turn.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mock_script: Vec<MockLlmStep>,
     #[serde(default)]
     pub tts_voice: Option<String>,
     #[serde(default)]
@@ -446,6 +615,9 @@ pub struct LlmModelConfig {
     pub asr_response_format: Option<String>,
     #[serde(default)]
     pub asr_temperature: Option<f32>,
+    /// Embedding vector size requested as `dimensions`; every returned vector must match it.
+    #[serde(default)]
+    pub dimensions: Option<u32>,
     #[serde(default)]
     pub image_size: Option<String>,
     #[serde(default)]
@@ -484,6 +656,23 @@ pub struct LlmModelConfig {
     pub video_sync_mode: Option<bool>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct MockLlmStep {
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub reasoning: String,
+    #[serde(default)]
+    pub tool_calls: Vec<MockToolCall>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct MockToolCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ToolsConfig {
     #[serde(default)]
@@ -500,6 +689,46 @@ pub struct ToolsConfig {
     pub browser: BrowserToolConfig,
     #[serde(default)]
     pub desktop_controller: DesktopControllerConfig,
+    #[serde(default)]
+    pub result_budget: ToolResultBudgetConfig,
+    #[serde(default)]
+    pub parallel: ToolParallelConfig,
+}
+
+/// Token budget for tool results entering the model context. Results over the threshold are
+/// cut to a head/tail preview there; tool logs and events keep the full output.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ToolResultBudgetConfig {
+    /// Default threshold in estimated tokens; unset or 0 leaves results untouched.
+    #[serde(default)]
+    pub max_tokens: Option<i64>,
+    /// Summarize the omitted part with the turn's model.
+    #[serde(default)]
+    pub summarize: bool,
+    /// Overrides keyed by tool name or alias.
+    #[serde(default)]
+    pub per_tool: HashMap<String, ToolResultBudgetOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ToolResultBudgetOverride {
+    #[serde(default)]
+    pub max_tokens: Option<i64>,
+    #[serde(default)]
+    pub summarize: Option<bool>,
+}
+
+/// Concurrency of independent tool calls emitted in one model round. Read-only calls share
+/// the round; every other call runs alone.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ToolParallelConfig {
+    /// Calls in flight at once; unset or 0 keeps the built-in default, 1 runs them in order.
+    #[serde(default)]
+    pub max_concurrency: Option<usize>,
+    /// Tool names or aliases that must always run exclusively, e.g. MCP tools with side
+    /// effects whose names look read-only.
+    #[serde(default)]
+    pub exclusive: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -761,6 +990,10 @@ pub struct WebFetchToolConfig {
     pub allow_private_network: bool,
     #[serde(default)]
     pub hostname_allowlist: Vec<String>,
+    /// Hosts web_fetch never contacts, including as a redirect target. An entry also
+    /// covers its subdomains and wins over `hostname_allowlist`/`allow_private_network`.
+    #[serde(default)]
+    pub hostname_denylist: Vec<String>,
     #[serde(default = "default_web_fetch_user_agent")]
     pub user_agent: String,
     #[serde(default)]
@@ -929,6 +1162,7 @@ impl Default for WebFetchToolConfig {
             cache_ttl_secs: default_web_fetch_cache_ttl_secs(),
             allow_private_network: false,
             hostname_allowlist: Vec::new(),
+            hostname_denylist: Vec::new(),
             user_agent: default_web_fetch_user_agent(),
             firecrawl: WebFetchFirecrawlConfig::default(),
         }
@@ -1276,6 +1510,14 @@ pub struct WorkspaceConfig {
     pub max_history_items: i64,
     #[serde(default)]
     pub retention_days: i64,
+    /// Give every session its own sandbox container slot instead of the agent's fixed one, so
+    /// files and processes persist within a session but not across sessions.
+    #[serde(default)]
+    pub session_sandbox: bool,
+    /// Gitignore-syntax files at the workspace root whose matches file search and read tools
+    /// skip. Add `.gitignore` to combine it with `.wunderignore`.
+    #[serde(default = "default_workspace_ignore_files")]
+    pub ignore_files: Vec<String>,
 }
 
 impl Default for WorkspaceConfig {
@@ -1285,10 +1527,16 @@ impl Default for WorkspaceConfig {
             container_roots: HashMap::new(),
             max_history_items: 0,
             retention_days: 0,
+            session_sandbox: false,
+            ignore_files: default_workspace_ignore_files(),
         }
     }
 }
 
+fn default_workspace_ignore_files() -> Vec<String> {
+    vec![".wunderignore".to_string()]
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct McpConfig {
     #[serde(default)]
@@ -1407,6 +1655,12 @@ pub struct SkillsConfig {
     pub paths: Vec<String>,
     #[serde(default)]
     pub enabled: Vec<String>,
+    /// Poll user skill roots and drop their cached specs after on-disk edits. Off by default.
+    #[serde(default)]
+    pub watch: bool,
+    /// Quiet period after the last detected change before the cache is cleared.
+    #[serde(default)]
+    pub watch_debounce_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -1453,6 +1707,12 @@ pub struct KnowledgeBaseConfig {
     pub top_k: Option<usize>,
     #[serde(default)]
     pub score_threshold: Option<f32>,
+    /// Vector base search mode: `vector` (default) or `hybrid`, which blends in keyword scores.
+    #[serde(default)]
+    pub search_mode: Option<String>,
+    /// Keyword share of the hybrid score in `0..=1`; defaults to 0.3.
+    #[serde(default)]
+    pub keyword_weight: Option<f32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1519,6 +1779,9 @@ fn default_ragflow_timeout_s() -> u64 {
 pub struct ObservabilityConfig {
     #[serde(default)]
     pub log_level: String,
+    /// `text` (default) or `json`; see [`normalize_log_format`].
+    #[serde(default)]
+    pub log_format: String,
     #[serde(default = "default_server_log_dir")]
     pub server_log_dir: String,
     #[serde(
@@ -1532,21 +1795,55 @@ pub struct ObservabilityConfig {
     pub monitor_payload_max_chars: i64,
     #[serde(default)]
     pub monitor_drop_event_types: Vec<String>,
+    #[serde(default)]
+    pub otlp: OtlpConfig,
 }
 
 impl Default for ObservabilityConfig {
     fn default() -> Self {
         Self {
             log_level: String::new(),
+            log_format: String::new(),
             server_log_dir: default_server_log_dir(),
             server_log_retention_days: default_server_log_retention_days(),
             monitor_event_limit: 0,
             monitor_payload_max_chars: 0,
             monitor_drop_event_types: Vec::new(),
+            otlp: OtlpConfig::default(),
+        }
+    }
+}
+
+/// OpenTelemetry span export; only takes effect in builds with the `otel` feature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OtlpConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// OTLP/HTTP traces endpoint, e.g. `http://collector:4318/v1/traces`. Empty falls back to
+    /// the standard `OTEL_EXPORTER_OTLP_*` environment variables.
+    #[serde(default)]
+    pub endpoint: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default = "default_otlp_service_name")]
+    pub service_name: String,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: String::new(),
+            headers: HashMap::new(),
+            service_name: default_otlp_service_name(),
         }
     }
 }
 
+fn default_otlp_service_name() -> String {
+    "wunder-server".to_string()
+}
+
 fn default_server_log_dir() -> String {
     "./config/data/logs/server".to_string()
 }
@@ -1606,6 +1903,8 @@ pub struct GatewayConfig {
     pub allowed_origins: Vec<String>,
     #[serde(default)]
     pub trusted_proxies: Vec<String>,
+    #[serde(default)]
+    pub circuit_breaker: UpstreamCircuitBreakerConfig,
 }
 
 impl Default for GatewayConfig {
@@ -1619,10 +1918,52 @@ impl Default for GatewayConfig {
             allow_gateway_token_for_nodes: false,
             allowed_origins: Vec::new(),
             trusted_proxies: Vec::new(),
+            circuit_breaker: UpstreamCircuitBreakerConfig::default(),
         }
     }
 }
 
+/// Fail fast on upstreams that keep failing: after `failure_threshold` consecutive failures to
+/// one endpoint, calls are rejected for `cooldown_s`, then a single probe decides whether the
+/// endpoint is healthy again. Off by default so existing retry behaviour is unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UpstreamCircuitBreakerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub llm: CircuitBreakerSettings,
+    #[serde(default)]
+    pub mcp: CircuitBreakerSettings,
+    #[serde(default)]
+    pub a2a: CircuitBreakerSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerSettings {
+    /// Consecutive failures that open the breaker; 0 disables it for this upstream type.
+    #[serde(default = "default_circuit_breaker_failure_threshold")]
+    pub failure_threshold: u32,
+    #[serde(default = "default_circuit_breaker_cooldown_s")]
+    pub cooldown_s: f64,
+}
+
+impl Default for CircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_circuit_breaker_failure_threshold(),
+            cooldown_s: default_circuit_breaker_cooldown_s(),
+        }
+    }
+}
+
+fn default_circuit_breaker_failure_threshold() -> u32 {
+    5
+}
+
+fn default_circuit_breaker_cooldown_s() -> f64 {
+    30.0
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ChannelRateLimitConfig {
     #[serde(default)]
@@ -1750,6 +2091,9 @@ pub struct PostgresConfig {
     pub connect_timeout_s: u64,
     #[serde(default, deserialize_with = "deserialize_usize_from_any")]
     pub pool_size: usize,
+    /// Optional read-replica DSNs; list/replay reads go there, writes always hit `dsn`.
+    #[serde(default)]
+    pub read_replicas: Vec<String>,
 }
 
 impl Config {
@@ -1878,6 +2222,20 @@ pub fn is_debug_log_level(raw: &str) -> bool {
     matches!(level.as_str(), "debug" | "trace")
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+/// Unknown or empty values (including an unexpanded `${...}` placeholder) keep human text.
+pub fn normalize_log_format(raw: &str) -> LogFormat {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "json" | "jsonl" => LogFormat::Json,
+        _ => LogFormat::Text,
+    }
+}
+
 fn deserialize_u16_from_any<'de, D>(deserializer: D) -> Result<u16, D::Error>
 where
     D: Deserializer<'de>,
@@ -2858,4 +3216,19 @@ storage:
 
         let _ = fs::remove_dir_all(&root);
     }
+
+    #[test]
+    fn test_observability_log_format_parses_and_defaults_to_text() {
+        let config: ObservabilityConfig =
+            serde_yaml::from_str("log_format: JSON\n").expect("parse observability");
+        assert_eq!(normalize_log_format(&config.log_format), LogFormat::Json);
+
+        let config: ObservabilityConfig =
+            serde_yaml::from_str("log_level: info\n").expect("parse observability");
+        assert_eq!(normalize_log_format(&config.log_format), LogFormat::Text);
+        assert_eq!(
+            normalize_log_format("${WUNDER_LOG_FORMAT}"),
+            LogFormat::Text
+        );
+    }
 }
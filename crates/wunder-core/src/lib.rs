@@ -6,6 +6,7 @@
 
 pub mod approval;
 pub mod atomic_write;
+pub mod attachment_config;
 pub mod auth;
 pub mod config;
 pub mod drawio_config;
@@ -53,12 +53,16 @@ mod tests {
                 content: Some("data:image/png;base64,xxxx".to_string()),
                 content_type: Some("image/png".to_string()),
                 public_path: None,
+                attachment_id: None,
+                metadata: None,
             },
             AttachmentPayload {
                 name: Some("note.txt".to_string()),
                 content: Some("text".to_string()),
                 content_type: Some("text/plain".to_string()),
                 public_path: None,
+                attachment_id: None,
+                metadata: None,
             },
         ];
 
@@ -75,6 +79,8 @@ mod tests {
             content: Some("   \n\t  ".to_string()),
             content_type: Some("text/plain".to_string()),
             public_path: None,
+            attachment_id: None,
+            metadata: None,
         }];
 
         assert_eq!(
@@ -90,6 +96,8 @@ mod tests {
             content: Some("binary-placeholder".to_string()),
             content_type: None,
             public_path: None,
+            attachment_id: None,
+            metadata: None,
         };
 
         assert!(request_attachment_is_image(
@@ -10,6 +10,68 @@ pub struct AttachmentPayload {
     pub content_type: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none", alias = "publicPath")]
     pub public_path: Option<String>,
+    /// Id returned by the chunked upload API; resolved into `content` before the turn runs.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        alias = "attachmentId"
+    )]
+    pub attachment_id: Option<String>,
+    /// Extracted facts such as page count or image size; absent when nothing was found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<AttachmentMetadata>,
+}
+
+/// Best-effort attachment metadata. Every field is optional because extraction never fails
+/// the attachment itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AttachmentMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Language detected in the text, as an ISO 639-1 code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Set on images when OCR ran: `true` if it produced text for the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ocr: Option<bool>,
+}
+
+impl AttachmentMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.page_count.is_none()
+            && self.width.is_none()
+            && self.height.is_none()
+            && self.language.is_none()
+            && self.ocr.is_none()
+    }
+
+    /// Compact line such as `12 pages, 1920x1080, lang zh`, or `None` when nothing is known.
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        match self.page_count {
+            Some(1) => parts.push("1 page".to_string()),
+            Some(count) => parts.push(format!("{count} pages")),
+            None => {}
+        }
+        if let (Some(width), Some(height)) = (self.width, self.height) {
+            parts.push(format!("{width}x{height}"));
+        }
+        if let Some(language) = self.language.as_deref() {
+            parts.push(format!("lang {language}"));
+        }
+        if self.ocr == Some(true) {
+            parts.push("ocr".to_string());
+        }
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(", "))
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
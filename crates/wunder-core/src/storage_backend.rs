@@ -70,6 +70,21 @@ pub trait ConversationLogStore {
             .into_iter()
             .find(|item| item.get("_history_id").and_then(Value::as_i64) == Some(history_id)))
     }
+    /// Delete chat history rows by `_history_id`; ids that belong to another session are ignored.
+    fn delete_chat_history_items(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        history_ids: &[i64],
+    ) -> Result<i64>;
+    /// Overwrite one chat history row; returns `false` when the id is not in the session.
+    fn update_chat_history_item(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        history_id: i64,
+        payload: &Value,
+    ) -> Result<bool>;
     fn load_artifact_logs(&self, user_id: &str, session_id: &str, limit: i64)
         -> Result<Vec<Value>>;
     fn get_session_system_prompt(
@@ -245,6 +260,9 @@ pub trait AgentRuntimeStore {
         event_id: i64,
         payload: &Value,
     ) -> Result<()>;
+    /// Append several stream events in one transaction, in slice order, so a reader never sees
+    /// a later event id of the batch without the earlier ones.
+    fn append_stream_events(&self, events: &[StreamEventWrite]) -> Result<()>;
     fn load_stream_events(
         &self,
         session_id: &str,
@@ -417,6 +435,9 @@ pub trait BenchmarkStore {
 /// Retention cleanup storage.
 pub trait RetentionStore {
     fn cleanup_retention(&self, retention_days: i64) -> Result<HashMap<String, i64>>;
+    /// Reclaim free pages left behind by deletes. Blocks writers while it runs, so only call it
+    /// when the server is idle.
+    fn compact_storage(&self) -> Result<StorageCompactionReport>;
 }
 
 /// User, organization, token, external link, and session-scope storage.
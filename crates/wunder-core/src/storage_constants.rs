@@ -30,6 +30,12 @@ pub fn normalize_hive_id(value: &str) -> String {
     }
 }
 
+/// Hex encode an id for use inside a meta key. Meta prefix queries go through `LIKE`, where the
+/// `_` allowed in user, session and hive ids is a wildcard; hex segments never contain one.
+pub fn hex_meta_segment(value: &str) -> String {
+    value.bytes().map(|byte| format!("{byte:02x}")).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
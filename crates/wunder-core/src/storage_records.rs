@@ -832,3 +832,24 @@ pub struct UpdateChannelOutboxStatusParams<'a> {
     pub delivered_at: Option<f64>,
     pub updated_at: f64,
 }
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageCompactionReport {
+    pub backend: String,
+    /// False when the backend leaves compaction to the database server (e.g. Postgres autovacuum).
+    pub supported: bool,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub reclaimed_bytes: u64,
+    pub freelist_pages_before: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamEventWrite {
+    pub session_id: String,
+    pub user_id: String,
+    pub event_id: i64,
+    pub payload: Value,
+}
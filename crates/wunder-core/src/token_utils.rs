@@ -1,7 +1,8 @@
-// Token 估算工具：用于近似计算上下文占用并进行裁剪。
+// Token 计数工具：OpenAI 模型按真实 BPE 计数，其余模型按分词族估算，用于上下文占用统计与裁剪。
 use regex::Regex;
 use serde_json::Value;
 use std::sync::OnceLock;
+use tiktoken_rs::CoreBPE;
 
 const APPROX_BYTES_PER_TOKEN: f64 = 4.0;
 const MESSAGE_TOKEN_OVERHEAD: i64 = 4;
@@ -57,30 +58,11 @@ pub fn trim_text_to_tokens(text: &str, max_tokens: i64, suffix: &str) -> String
 }
 
 pub fn estimate_message_tokens(message: &Value) -> i64 {
-    if !message.is_object() {
-        return 0;
-    }
-    let content_tokens = estimate_content_tokens(message.get("content").unwrap_or(&Value::Null));
-    let reasoning = message
-        .get("reasoning_content")
-        .or_else(|| message.get("reasoning"))
-        .unwrap_or(&Value::Null);
-    let reasoning_tokens = match reasoning {
-        Value::String(text) => approx_token_count(text),
-        Value::Array(_) | Value::Object(_) => approx_token_count(&reasoning.to_string()),
-        _ => 0,
-    };
-    let tool_calls_tokens = estimate_tool_calls_tokens(message);
-    let tool_call_id_tokens = estimate_tool_call_id_tokens(message);
-    content_tokens
-        + reasoning_tokens
-        + tool_calls_tokens
-        + tool_call_id_tokens
-        + MESSAGE_TOKEN_OVERHEAD
+    TokenCounter::GENERIC.message_tokens(message)
 }
 
 pub fn estimate_messages_tokens(messages: &[Value]) -> i64 {
-    messages.iter().map(estimate_message_tokens).sum()
+    TokenCounter::GENERIC.messages_tokens(messages)
 }
 
 pub fn trim_messages_to_budget(messages: &[Value], max_tokens: i64) -> Vec<Value> {
@@ -108,89 +90,318 @@ pub fn trim_messages_to_budget(messages: &[Value], max_tokens: i64) -> Vec<Value
     selected
 }
 
-fn estimate_tool_calls_tokens(message: &Value) -> i64 {
-    let Some(map) = message.as_object() else {
-        return 0;
-    };
-    for key in [
-        "tool_calls",
-        "toolCalls",
-        "tool_call",
-        "toolCall",
-        "function_call",
-        "functionCall",
-        "function",
-    ] {
-        if let Some(value) = map.get(key) {
-            if value.is_null() {
-                continue;
-            }
-            return estimate_aux_tokens(value);
+pub fn estimate_content_tokens(content: &Value) -> i64 {
+    TokenCounter::GENERIC.content_tokens(content)
+}
+
+/// Token count of `text` for `model`'s tokenizer family: exact for OpenAI models, an estimate
+/// elsewhere. See [`TokenizerFamily`].
+pub fn count_tokens(text: &str, model: &str) -> i64 {
+    TokenCounter::for_model(model).count(text)
+}
+
+/// Chat messages variant of [`count_tokens`], including per-message overhead, tool calls and
+/// images.
+pub fn count_messages_tokens(messages: &[Value], model: &str) -> i64 {
+    TokenCounter::for_model(model).messages_tokens(messages)
+}
+
+/// Tokenizer a model name maps to. OpenAI families count with the real `o200k_base` /
+/// `cl100k_base` BPE. The others have no bundled vocabulary and convert ASCII bytes, CJK
+/// characters and other bytes to tokens at rates measured on their reference tokenizer, so their
+/// counts are estimates: close on prose, rougher on code and on Claude and Gemini, which publish
+/// no offline tokenizer. `Generic` is the provider-neutral 4-bytes-per-token rule used by the
+/// `estimate_*` functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenizerFamily {
+    Generic,
+    /// gpt-4o, gpt-4.1, gpt-5, o-series.
+    OpenAiO200k,
+    /// gpt-4, gpt-3.5, text-embedding-3.
+    OpenAiCl100k,
+    Claude,
+    Gemini,
+    Qwen,
+    DeepSeek,
+    Glm,
+    Llama,
+}
+
+impl TokenizerFamily {
+    pub fn for_model(model: &str) -> Self {
+        let lower = model.trim().to_ascii_lowercase();
+        // Drop routing prefixes such as `openai/` or `accounts/x/models/`.
+        let name = lower.rsplit('/').next().unwrap_or(&lower);
+        let starts_with_any = |prefixes: &[&str]| prefixes.iter().any(|p| name.starts_with(p));
+        let contains_any = |needles: &[&str]| needles.iter().any(|n| name.contains(n));
+        if contains_any(&[
+            "gpt-4o", "gpt-4.1", "gpt-4.5", "gpt-5", "gpt-oss", "chatgpt",
+        ]) || starts_with_any(&["o1", "o3", "o4"])
+        {
+            Self::OpenAiO200k
+        } else if contains_any(&["gpt-4", "gpt-3.5", "text-embedding", "davinci"]) {
+            Self::OpenAiCl100k
+        } else if name.contains("claude") {
+            Self::Claude
+        } else if contains_any(&["gemini", "gemma"]) {
+            Self::Gemini
+        } else if contains_any(&["qwen", "qwq"]) {
+            Self::Qwen
+        } else if name.contains("deepseek") {
+            Self::DeepSeek
+        } else if name.contains("glm") {
+            Self::Glm
+        } else if name.contains("llama") {
+            Self::Llama
+        } else {
+            Self::Generic
+        }
+    }
+
+    /// Vocabulary of the families that count exactly.
+    fn bpe(self) -> Option<&'static CoreBPE> {
+        match self {
+            Self::OpenAiO200k => Some(tiktoken_rs::o200k_base_singleton()),
+            Self::OpenAiCl100k => Some(tiktoken_rs::cl100k_base_singleton()),
+            _ => None,
         }
     }
-    0
+
+    fn rates(self) -> Option<TokenRates> {
+        let (ascii_bytes_per_token, cjk_tokens_per_char, other_bytes_per_token) = match self {
+            Self::Generic | Self::OpenAiO200k | Self::OpenAiCl100k => return None,
+            Self::Claude => (3.6, 1.2, 2.2),
+            Self::Gemini => (4.0, 0.8, 3.0),
+            Self::Qwen => (4.0, 0.7, 2.8),
+            Self::DeepSeek | Self::Glm => (4.0, 0.65, 2.8),
+            Self::Llama => (4.0, 0.9, 2.8),
+        };
+        Some(TokenRates {
+            ascii_bytes_per_token,
+            cjk_tokens_per_char,
+            other_bytes_per_token,
+        })
+    }
 }
 
-fn estimate_tool_call_id_tokens(message: &Value) -> i64 {
-    let Some(map) = message.as_object() else {
-        return 0;
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TokenRates {
+    ascii_bytes_per_token: f64,
+    cjk_tokens_per_char: f64,
+    other_bytes_per_token: f64,
+}
+
+/// Token counter for one tokenizer family; the free `estimate_*` functions use
+/// [`TokenCounter::GENERIC`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenCounter {
+    family: TokenizerFamily,
+    rates: Option<TokenRates>,
+}
+
+impl TokenCounter {
+    pub const GENERIC: TokenCounter = TokenCounter {
+        family: TokenizerFamily::Generic,
+        rates: None,
     };
-    for key in ["tool_call_id", "toolCallId", "call_id", "callId"] {
-        if let Some(value) = map.get(key) {
-            if value.is_null() {
-                continue;
+
+    pub fn new(family: TokenizerFamily) -> Self {
+        Self {
+            family,
+            rates: family.rates(),
+        }
+    }
+
+    /// Counter for `model`. Resolving the family is a few substring checks, so nothing is cached.
+    pub fn for_model(model: &str) -> Self {
+        Self::new(TokenizerFamily::for_model(model))
+    }
+
+    pub fn family(&self) -> TokenizerFamily {
+        self.family
+    }
+
+    pub fn count(&self, text: &str) -> i64 {
+        if text.is_empty() {
+            return 0;
+        }
+        if let Some(bpe) = self.family.bpe() {
+            // Ordinary encoding: special-token text in user content is counted as plain text.
+            return bpe.encode_ordinary(text).len() as i64;
+        }
+        let Some(rates) = self.rates else {
+            return approx_token_count(text);
+        };
+        let (mut ascii_bytes, mut cjk_chars, mut other_bytes) = (0usize, 0usize, 0usize);
+        for ch in text.chars() {
+            if ch.is_ascii() {
+                ascii_bytes += 1;
+            } else if is_cjk_char(ch) {
+                cjk_chars += 1;
+            } else {
+                other_bytes += ch.len_utf8();
             }
-            return estimate_aux_tokens(value);
         }
+        let tokens = ascii_bytes as f64 / rates.ascii_bytes_per_token
+            + cjk_chars as f64 * rates.cjk_tokens_per_char
+            + other_bytes as f64 / rates.other_bytes_per_token;
+        (tokens.ceil() as i64).max(1)
     }
-    0
-}
 
-fn estimate_aux_tokens(value: &Value) -> i64 {
-    match value {
-        Value::Null => 0,
-        Value::String(text) => approx_token_count(text),
-        Value::Array(_) | Value::Object(_) => approx_token_count(&value.to_string()),
-        _ => approx_token_count(&value.to_string()),
+    /// Trim `text` to about `max_tokens` of this family. Characters are cut in proportion to the
+    /// text's own token density, so dense scripts like CJK are not over-kept.
+    pub fn trim_text(&self, text: &str, max_tokens: i64, suffix: &str) -> String {
+        if self.family == TokenizerFamily::Generic {
+            return trim_text_to_tokens(text, max_tokens, suffix);
+        }
+        if text.is_empty() {
+            return String::new();
+        }
+        if max_tokens <= 0 {
+            return suffix.to_string();
+        }
+        let tokens = self.count(text);
+        if tokens <= max_tokens {
+            return text.to_string();
+        }
+        let chars = text.chars().count();
+        let suffix_tokens = self.count(suffix);
+        let (budget, suffix) = if max_tokens <= suffix_tokens {
+            (max_tokens, "")
+        } else {
+            (max_tokens - suffix_tokens, suffix)
+        };
+        let keep_chars = (chars as f64 * budget as f64 / tokens as f64).floor() as usize;
+        let suffix_chars = suffix.chars().count();
+        trim_text_to_chars(text, keep_chars.max(1) + suffix_chars, suffix)
     }
-}
 
-pub fn estimate_content_tokens(content: &Value) -> i64 {
-    match content {
-        Value::Null => 0,
-        Value::String(text) => estimate_string_tokens(text),
-        Value::Array(items) => items.iter().map(estimate_content_tokens).sum(),
-        Value::Object(map) => {
-            let part_type = map
-                .get("type")
-                .and_then(Value::as_str)
-                .unwrap_or("")
-                .to_lowercase();
-            if part_type == "text" {
-                return approx_token_count(map.get("text").and_then(Value::as_str).unwrap_or(""));
+    pub fn message_tokens(&self, message: &Value) -> i64 {
+        if !message.is_object() {
+            return 0;
+        }
+        let content_tokens = self.content_tokens(message.get("content").unwrap_or(&Value::Null));
+        let reasoning = message
+            .get("reasoning_content")
+            .or_else(|| message.get("reasoning"))
+            .unwrap_or(&Value::Null);
+        let reasoning_tokens = match reasoning {
+            Value::String(text) => self.count(text),
+            Value::Array(_) | Value::Object(_) => self.count(&reasoning.to_string()),
+            _ => 0,
+        };
+        let tool_calls_tokens = self.tool_calls_tokens(message);
+        let tool_call_id_tokens = self.tool_call_id_tokens(message);
+        content_tokens
+            + reasoning_tokens
+            + tool_calls_tokens
+            + tool_call_id_tokens
+            + MESSAGE_TOKEN_OVERHEAD
+    }
+
+    pub fn messages_tokens(&self, messages: &[Value]) -> i64 {
+        messages
+            .iter()
+            .map(|message| self.message_tokens(message))
+            .sum()
+    }
+
+    pub fn content_tokens(&self, content: &Value) -> i64 {
+        match content {
+            Value::Null => 0,
+            Value::String(text) => self.string_tokens(text),
+            Value::Array(items) => items.iter().map(|item| self.content_tokens(item)).sum(),
+            Value::Object(map) => {
+                let part_type = map
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_lowercase();
+                if part_type == "text" {
+                    return self.count(map.get("text").and_then(Value::as_str).unwrap_or(""));
+                }
+                if part_type == "image_url" || map.contains_key("image_url") {
+                    return IMAGE_TOKEN_ESTIMATE;
+                }
+                if let Some(text) = map.get("text").and_then(Value::as_str) {
+                    return self.count(text);
+                }
+                self.count(&content.to_string())
             }
-            if part_type == "image_url" || map.contains_key("image_url") {
-                return IMAGE_TOKEN_ESTIMATE;
+            _ => self.count(&content.to_string()),
+        }
+    }
+
+    fn tool_calls_tokens(&self, message: &Value) -> i64 {
+        let Some(map) = message.as_object() else {
+            return 0;
+        };
+        for key in [
+            "tool_calls",
+            "toolCalls",
+            "tool_call",
+            "toolCall",
+            "function_call",
+            "functionCall",
+            "function",
+        ] {
+            if let Some(value) = map.get(key) {
+                if value.is_null() {
+                    continue;
+                }
+                return self.aux_tokens(value);
             }
-            if let Some(text) = map.get("text").and_then(Value::as_str) {
-                return approx_token_count(text);
+        }
+        0
+    }
+
+    fn tool_call_id_tokens(&self, message: &Value) -> i64 {
+        let Some(map) = message.as_object() else {
+            return 0;
+        };
+        for key in ["tool_call_id", "toolCallId", "call_id", "callId"] {
+            if let Some(value) = map.get(key) {
+                if value.is_null() {
+                    continue;
+                }
+                return self.aux_tokens(value);
             }
-            approx_token_count(&content.to_string())
         }
-        _ => approx_token_count(&content.to_string()),
+        0
     }
-}
 
-fn estimate_string_tokens(text: &str) -> i64 {
-    if text.starts_with("data:image/") {
-        return IMAGE_TOKEN_ESTIMATE;
+    fn aux_tokens(&self, value: &Value) -> i64 {
+        match value {
+            Value::Null => 0,
+            Value::String(text) => self.count(text),
+            _ => self.count(&value.to_string()),
+        }
     }
-    if text.contains("data:image/") {
-        let matches = data_url_regex().find_iter(text).count() as i64;
-        let stripped = data_url_regex().replace_all(text, "[image]");
-        return approx_token_count(&stripped) + matches * IMAGE_TOKEN_ESTIMATE;
+
+    fn string_tokens(&self, text: &str) -> i64 {
+        if text.starts_with("data:image/") {
+            return IMAGE_TOKEN_ESTIMATE;
+        }
+        if text.contains("data:image/") {
+            let matches = data_url_regex().find_iter(text).count() as i64;
+            let stripped = data_url_regex().replace_all(text, "[image]");
+            return self.count(&stripped) + matches * IMAGE_TOKEN_ESTIMATE;
+        }
+        self.count(text)
     }
-    approx_token_count(text)
+}
+
+fn is_cjk_char(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x3000..=0x303F // CJK punctuation
+            | 0x3040..=0x30FF // kana
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF // hangul
+            | 0xF900..=0xFAFF
+            | 0xFF00..=0xFFEF // full-width forms
+    )
 }
 
 fn data_url_regex() -> &'static Regex {
@@ -279,4 +490,80 @@ mod tests {
 
         assert!(estimate_message_tokens(&message) > baseline);
     }
+
+    #[test]
+    fn test_count_tokens_stays_in_expected_range_for_known_strings() {
+        let english = "Hello, world! This is a test.";
+        for model in ["gpt-4o", "gpt-4", "claude-sonnet-4", "qwen3-32b"] {
+            let tokens = count_tokens(english, model);
+            assert!((6..=11).contains(&tokens), "{model}: {tokens}");
+        }
+        let chinese = "今天天气很好，我们一起去公园散步吧。";
+        let qwen = count_tokens(chinese, "qwen3-32b");
+        let gpt4 = count_tokens(chinese, "gpt-4");
+        assert!((8..=16).contains(&qwen), "qwen: {qwen}");
+        assert!((16..=30).contains(&gpt4), "gpt-4: {gpt4}");
+        assert_eq!(count_tokens("", "gpt-4o"), 0);
+    }
+
+    #[test]
+    fn test_count_tokens_is_exact_for_openai_families() {
+        // Reference counts from OpenAI's tiktoken.
+        assert_eq!(count_tokens("Hello, world!", "gpt-4o"), 4);
+        assert_eq!(count_tokens("Hello, world!", "gpt-4"), 4);
+        assert_eq!(count_tokens("tiktoken is great!", "gpt-4"), 6);
+        assert_eq!(count_tokens("<|endoftext|>", "gpt-4o"), 7);
+    }
+
+    #[test]
+    fn test_counter_trim_text_fits_the_token_budget() {
+        let counter = TokenCounter::for_model("gpt-4o");
+        let text = "今天天气很好，我们一起去公园散步吧。".repeat(40);
+        let trimmed = counter.trim_text(&text, 50, "...");
+        assert!(trimmed.ends_with("..."));
+        assert!(counter.count(&trimmed) <= 55, "{}", counter.count(&trimmed));
+        assert!(counter.count(&trimmed) >= 40, "{}", counter.count(&trimmed));
+        assert_eq!(counter.trim_text("short", 50, "..."), "short");
+        assert_eq!(
+            TokenCounter::GENERIC.trim_text("abcdef", 1, "...(truncated)"),
+            trim_text_to_tokens("abcdef", 1, "...(truncated)")
+        );
+    }
+
+    #[test]
+    fn test_count_tokens_resolves_family_per_model_and_falls_back_to_estimate() {
+        assert_eq!(
+            TokenizerFamily::for_model("openai/gpt-4o-mini"),
+            TokenizerFamily::OpenAiO200k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("o3-mini"),
+            TokenizerFamily::OpenAiO200k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("gpt-3.5-turbo"),
+            TokenizerFamily::OpenAiCl100k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("DeepSeek-V3"),
+            TokenizerFamily::DeepSeek
+        );
+        assert_eq!(
+            TokenCounter::for_model("qwen-max").family(),
+            TokenizerFamily::Qwen
+        );
+        assert_eq!(
+            TokenCounter::for_model("qwen-max"),
+            TokenCounter::new(TokenizerFamily::Qwen)
+        );
+
+        let text = "mixed 文本 with ünïcode";
+        assert_eq!(count_tokens(text, "local-model"), approx_token_count(text));
+        let messages = vec![json!({ "role": "user", "content": text })];
+        assert_eq!(
+            count_messages_tokens(&messages, "local-model"),
+            estimate_messages_tokens(&messages)
+        );
+        assert!(count_messages_tokens(&messages, "gpt-4o") > count_tokens(text, "gpt-4o"));
+    }
 }
@@ -9,13 +9,15 @@ use std::path::PathBuf;
     about = "Run wunder in local desktop mode"
 )]
 pub struct DesktopArgs {
-    /// Bind host for local desktop bridge.
-    #[arg(long, default_value = "127.0.0.1")]
-    pub host: String,
+    /// Bind host for local desktop bridge. Defaults to 127.0.0.1 (or the LAN mesh listen host).
+    /// An explicit value always wins over the LAN mesh setting.
+    #[arg(long)]
+    pub host: Option<String>,
 
-    /// Bind port for local desktop bridge (0 = random free port).
-    #[arg(long, default_value_t = 18123)]
-    pub port: u16,
+    /// Bind port for local desktop bridge (0 = random free port). Defaults to 18123 (or the LAN
+    /// mesh listen port). An explicit port fails fast instead of falling back when taken.
+    #[arg(long)]
+    pub port: Option<u16>,
 
     /// Workspace root. Defaults to <app_dir>/WUNDER_WORK.
     #[arg(long)]
@@ -44,4 +46,9 @@ pub struct DesktopArgs {
     /// Run as local bridge only without Tauri desktop window.
     #[arg(long, alias = "headless", default_value_t = false)]
     pub bridge_only: bool,
+
+    /// Write a JSON readiness record (port, web_base, api_base) to this path once the bridge
+    /// accepts connections (bridge-only mode). Removed again on shutdown.
+    #[arg(long)]
+    pub ready_file: Option<PathBuf>,
 }
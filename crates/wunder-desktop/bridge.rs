@@ -19,7 +19,7 @@ use tower_http::services::{ServeDir, ServeFile};
 use tower_http::trace::TraceLayer;
 use tracing::{error, info, warn};
 use wunder_server::desktop_lan;
-use wunder_server::state::{AppRuntimeCapabilities, AppRuntimeProfile};
+use wunder_server::state::{AppRuntimeCapabilities, AppRuntimeProfile, AppState};
 
 #[derive(Clone)]
 struct DesktopGuardState {
@@ -60,11 +60,34 @@ pub struct DesktopRuntimeInfo {
 
 pub struct DesktopBridge {
     runtime_info: DesktopRuntimeInfo,
+    state: Arc<AppState>,
     shutdown_tx: Option<oneshot::Sender<()>>,
     server_task: Option<tokio::task::JoinHandle<()>>,
 }
 
+const DEFAULT_DESKTOP_BIND_HOST: &str = "127.0.0.1";
 const DEFAULT_DESKTOP_BIND_PORT: u16 = 18123;
+const READY_PROBE_ATTEMPTS: u32 = 50;
+const READY_PROBE_INTERVAL_MS: u64 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DesktopBindTarget {
+    host: String,
+    port: u16,
+    // Ports chosen explicitly on the command line must not silently fall back to a random port.
+    allow_port_fallback: bool,
+}
+
+#[derive(Serialize)]
+struct DesktopReadyRecord<'a> {
+    ready: bool,
+    pid: u32,
+    bind_addr: &'a str,
+    port: u16,
+    web_base: &'a str,
+    api_base: &'a str,
+    ws_base: &'a str,
+}
 
 impl DesktopBridge {
     pub async fn launch(args: &DesktopArgs) -> Result<Self> {
@@ -83,9 +106,10 @@ impl DesktopBridge {
         );
 
         step_start = Instant::now();
-        let (target_host, target_port) = resolve_bind_target(args, &runtime);
-        let bind_host = sanitize_host(&target_host)?;
-        let listener = bind_desktop_listener(&bind_host, target_port).await?;
+        let target = resolve_bind_target(args, &runtime.lan_mesh);
+        let bind_host = sanitize_host(&target.host)?;
+        let listener =
+            bind_desktop_listener(&bind_host, target.port, target.allow_port_fallback).await?;
         let local_addr = listener
             .local_addr()
             .context("resolve desktop bridge local addr failed")?;
@@ -157,6 +181,7 @@ impl DesktopBridge {
         info!("wunder-desktop bridge ready: {api_base}");
         Ok(Self {
             runtime_info,
+            state: runtime.state.clone(),
             shutdown_tx: Some(shutdown_tx),
             server_task: Some(server_task),
         })
@@ -166,6 +191,13 @@ impl DesktopBridge {
         &self.runtime_info
     }
 
+    /// Runtime state for hooks the GUI installs on top of the bridge (turn notifiers); the
+    /// headless bridge binary has none.
+    #[allow(dead_code)]
+    pub fn app_state(&self) -> Arc<AppState> {
+        self.state.clone()
+    }
+
     pub fn print_banner(&self, print_token: bool) {
         println!("wunder-desktop bridge ready");
         println!("- api_base: {}", self.runtime_info.api_base);
@@ -194,6 +226,52 @@ impl DesktopBridge {
         }
     }
 
+    /// Wait until the HTTP listener accepts connections, then atomically write the ready record.
+    pub async fn write_ready_file(&self, path: &Path) -> Result<()> {
+        let bind_addr: SocketAddr = self
+            .runtime_info
+            .bind_addr
+            .parse()
+            .context("parse desktop bridge bind addr failed")?;
+        wait_until_accepting(resolve_public_addr(bind_addr)).await?;
+        let record = DesktopReadyRecord {
+            ready: true,
+            pid: std::process::id(),
+            bind_addr: &self.runtime_info.bind_addr,
+            port: bind_addr.port(),
+            web_base: &self.runtime_info.web_base,
+            api_base: &self.runtime_info.api_base,
+            ws_base: &self.runtime_info.ws_base,
+        };
+        let text = serde_json::to_string_pretty(&record).context("serialize ready file failed")?;
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create ready file dir failed: {}", parent.display()))?;
+        }
+        // Write through a temp file so launchers polling the path never read a partial record.
+        // The name is unique per write so concurrent instances sharing a directory don't clash.
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "ready".to_string());
+        let temp_path = path.with_file_name(format!(
+            ".{file_name}.{}.{}.tmp",
+            std::process::id(),
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::write(&temp_path, text)
+            .with_context(|| format!("write ready file failed: {}", temp_path.display()))?;
+        if let Err(err) = std::fs::rename(&temp_path, path) {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(err)
+                .with_context(|| format!("publish ready file failed: {}", path.display()));
+        }
+        Ok(())
+    }
+
     pub async fn shutdown(&mut self) {
         if let Some(tx) = self.shutdown_tx.take() {
             let _ = tx.send(());
@@ -414,27 +492,30 @@ fn build_frontend_router(frontend_root: &Path, docs_root: &Path) -> Router {
         .route("/{*path}", get(frontend_index_handler))
 }
 
-fn resolve_bind_target(args: &DesktopArgs, runtime: &DesktopRuntime) -> (String, u16) {
-    if !runtime.lan_mesh.enabled {
-        return (args.host.clone(), args.port);
-    }
-
-    let host = if is_default_desktop_bind_host(&args.host) {
-        runtime.lan_mesh.listen_host.clone()
-    } else {
-        args.host.clone()
+fn resolve_bind_target(
+    args: &DesktopArgs,
+    lan_mesh: &desktop_lan::DesktopLanMeshSettings,
+) -> DesktopBindTarget {
+    let explicit_host = args
+        .host
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let host = match explicit_host {
+        Some(host) => host.to_string(),
+        None if lan_mesh.enabled => lan_mesh.listen_host.clone(),
+        None => DEFAULT_DESKTOP_BIND_HOST.to_string(),
     };
-    let port = if args.port == DEFAULT_DESKTOP_BIND_PORT {
-        runtime.lan_mesh.listen_port
-    } else {
-        args.port
+    let port = match args.port {
+        Some(port) => port,
+        None if lan_mesh.enabled => lan_mesh.listen_port,
+        None => DEFAULT_DESKTOP_BIND_PORT,
     };
-    (host, port)
-}
-
-fn is_default_desktop_bind_host(host: &str) -> bool {
-    let cleaned = host.trim().to_ascii_lowercase();
-    cleaned.is_empty() || matches!(cleaned.as_str(), "127.0.0.1" | "localhost")
+    DesktopBindTarget {
+        host,
+        port,
+        allow_port_fallback: args.port.is_none(),
+    }
 }
 
 fn sanitize_host(host: &str) -> Result<String> {
@@ -448,11 +529,19 @@ fn sanitize_host(host: &str) -> Result<String> {
     Ok(cleaned.to_string())
 }
 
-async fn bind_desktop_listener(host: &str, preferred_port: u16) -> Result<tokio::net::TcpListener> {
+async fn bind_desktop_listener(
+    host: &str,
+    preferred_port: u16,
+    allow_port_fallback: bool,
+) -> Result<tokio::net::TcpListener> {
     let bind_addr = format!("{host}:{preferred_port}");
     match tokio::net::TcpListener::bind(bind_addr.as_str()).await {
         Ok(listener) => Ok(listener),
-        Err(err) if preferred_port != 0 && err.kind() == std::io::ErrorKind::AddrInUse => {
+        Err(err)
+            if allow_port_fallback
+                && preferred_port != 0
+                && err.kind() == std::io::ErrorKind::AddrInUse =>
+        {
             warn!(
                 "desktop bridge port {preferred_port} already in use, fallback to random free port"
             );
@@ -465,6 +554,21 @@ async fn bind_desktop_listener(host: &str, preferred_port: u16) -> Result<tokio:
     }
 }
 
+async fn wait_until_accepting(addr: SocketAddr) -> Result<()> {
+    let mut last_err = None;
+    for _ in 0..READY_PROBE_ATTEMPTS {
+        match tokio::net::TcpStream::connect(addr).await {
+            Ok(_) => return Ok(()),
+            Err(err) => last_err = Some(err),
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(READY_PROBE_INTERVAL_MS)).await;
+    }
+    Err(anyhow!(
+        "desktop bridge is not accepting connections on {addr}: {}",
+        last_err.map(|err| err.to_string()).unwrap_or_default()
+    ))
+}
+
 fn resolve_public_addr(local_addr: SocketAddr) -> SocketAddr {
     let ip = match local_addr.ip() {
         IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
@@ -661,3 +765,67 @@ fn is_desktop_lan_public_route(path: &str) -> bool {
         || path == "/wunder/desktop/lan/ws"
         || path == "/wunder/desktop/lan/peers"
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn parse_args(extra: &[&str]) -> DesktopArgs {
+        let mut argv = vec!["wunder-desktop"];
+        argv.extend_from_slice(extra);
+        DesktopArgs::parse_from(argv)
+    }
+
+    fn lan_mesh_enabled() -> desktop_lan::DesktopLanMeshSettings {
+        desktop_lan::DesktopLanMeshSettings {
+            enabled: true,
+            listen_host: "0.0.0.0".to_string(),
+            listen_port: 18200,
+            ..desktop_lan::DesktopLanMeshSettings::default()
+        }
+    }
+
+    #[test]
+    fn resolve_bind_target_uses_defaults_without_flags() {
+        let target = resolve_bind_target(
+            &parse_args(&[]),
+            &desktop_lan::DesktopLanMeshSettings::default(),
+        );
+        assert_eq!(
+            target,
+            DesktopBindTarget {
+                host: DEFAULT_DESKTOP_BIND_HOST.to_string(),
+                port: DEFAULT_DESKTOP_BIND_PORT,
+                allow_port_fallback: true,
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_bind_target_prefers_lan_mesh_only_when_flags_are_absent() {
+        let lan_mesh = lan_mesh_enabled();
+        let implicit = resolve_bind_target(&parse_args(&[]), &lan_mesh);
+        assert_eq!(
+            implicit,
+            DesktopBindTarget {
+                host: "0.0.0.0".to_string(),
+                port: 18200,
+                allow_port_fallback: true,
+            }
+        );
+
+        let explicit = resolve_bind_target(
+            &parse_args(&["--host", "127.0.0.1", "--port", "18123"]),
+            &lan_mesh,
+        );
+        assert_eq!(
+            explicit,
+            DesktopBindTarget {
+                host: "127.0.0.1".to_string(),
+                port: 18123,
+                allow_port_fallback: false,
+            }
+        );
+    }
+}
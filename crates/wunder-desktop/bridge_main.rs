@@ -27,11 +27,21 @@ fn run_bridge(args: DesktopArgs) -> Result<()> {
     rt.block_on(async move {
         let mut bridge = DesktopBridge::launch(&args).await?;
         bridge.print_banner(args.print_token);
+        if let Some(ready_file) = args.ready_file.as_deref() {
+            // Launchers wait on this file; stop the bridge rather than keep serving unannounced.
+            if let Err(err) = bridge.write_ready_file(ready_file).await {
+                bridge.shutdown().await;
+                return Err(err);
+            }
+        }
         if args.open {
             open_external_browser(&bridge.info().web_base)?;
         }
         wunder_server::shutdown::shutdown_signal().await;
         bridge.shutdown().await;
+        if let Some(ready_file) = args.ready_file.as_deref() {
+            let _ = std::fs::remove_file(ready_file);
+        }
         Ok(())
     })
 }
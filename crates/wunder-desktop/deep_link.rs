@@ -0,0 +1,177 @@
+// 桌面深链：解析 wunder://session/<id> 并把会话 id 交给前端打开；已运行实例负责聚焦窗口，链接格式不合法时只聚焦不跳转。
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::warn;
+use url::Url;
+
+pub const DEEP_LINK_SCHEME: &str = "wunder";
+/// DOM event dispatched in the webview with `{ sessionId }` for a deep link to open.
+const OPEN_SESSION_DOM_EVENT: &str = "wunder-desktop:open-session";
+const SESSION_ID_MAX_CHARS: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLinkError {
+    UnsupportedScheme(String),
+    UnsupportedTarget(String),
+    InvalidSessionId(String),
+}
+
+impl std::fmt::Display for DeepLinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedScheme(scheme) => write!(f, "unsupported deep link scheme `{scheme}`"),
+            Self::UnsupportedTarget(target) => write!(f, "unsupported deep link target `{target}`"),
+            Self::InvalidSessionId(value) => write!(f, "invalid session id `{value}`"),
+        }
+    }
+}
+
+/// Session ids are generated as `sess_<hex>`; links may carry any id made of the same
+/// character set so older or imported sessions still open.
+fn is_valid_session_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.chars().count() <= SESSION_ID_MAX_CHARS
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+}
+
+/// Parses `wunder://session/<id>` (a trailing slash or query string is ignored).
+pub fn parse_session_deep_link(url: &Url) -> Result<String, DeepLinkError> {
+    if !url.scheme().eq_ignore_ascii_case(DEEP_LINK_SCHEME) {
+        return Err(DeepLinkError::UnsupportedScheme(url.scheme().to_string()));
+    }
+    let target = url.host_str().unwrap_or_default();
+    if !target.eq_ignore_ascii_case("session") {
+        return Err(DeepLinkError::UnsupportedTarget(target.to_string()));
+    }
+    let mut segments = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty());
+    let session_id = segments.next().unwrap_or_default();
+    if segments.next().is_some() || !is_valid_session_id(session_id) {
+        return Err(DeepLinkError::InvalidSessionId(
+            url.path().trim_matches('/').to_string(),
+        ));
+    }
+    Ok(session_id.to_string())
+}
+
+/// Session waiting for the frontend: cold starts receive the link before the page loads,
+/// so the webview drains it once ready in addition to the live DOM event.
+#[derive(Default)]
+pub struct DeepLinkState {
+    pending_session: Mutex<Option<String>>,
+}
+
+impl DeepLinkState {
+    pub fn take_pending_session(&self) -> Option<String> {
+        self.pending_session
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.take())
+    }
+
+    fn set_pending_session(&self, session_id: &str) {
+        if let Ok(mut pending) = self.pending_session.lock() {
+            *pending = Some(session_id.to_string());
+        }
+    }
+}
+
+pub fn focus_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+}
+
+/// Handles links from a cold start, the OS, or a second instance forwarded by the
+/// single-instance plugin. The last valid session link wins; malformed links only focus.
+pub fn open_deep_links<R: Runtime>(app: &AppHandle<R>, urls: &[Url]) {
+    focus_main_window(app);
+    let mut target = None;
+    for url in urls {
+        match parse_session_deep_link(url) {
+            Ok(session_id) => target = Some(session_id),
+            Err(err) => warn!("ignore desktop deep link {url}: {err}"),
+        }
+    }
+    if let Some(session_id) = target {
+        open_session(app, &session_id);
+    }
+}
+
+/// Hands `session_id` to the webview, both as a live DOM event and as the pending session
+/// it drains once loaded. Also used when a turn notification is clicked.
+pub fn open_session<R: Runtime>(app: &AppHandle<R>, session_id: &str) {
+    app.state::<DeepLinkState>().set_pending_session(session_id);
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let detail = serde_json::json!({ "sessionId": session_id });
+    let script = format!(
+        "window.dispatchEvent(new CustomEvent('{OPEN_SESSION_DOM_EVENT}', {{ detail: {detail} }}));"
+    );
+    let _ = window.eval(&script);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> Result<String, DeepLinkError> {
+        parse_session_deep_link(&Url::parse(raw).expect("url"))
+    }
+
+    #[test]
+    fn session_links_yield_the_session_id() {
+        assert_eq!(
+            parse("wunder://session/sess_0123abcd").as_deref(),
+            Ok("sess_0123abcd")
+        );
+        assert_eq!(parse("WUNDER://Session/sess-1/").as_deref(), Ok("sess-1"));
+        assert_eq!(
+            parse("wunder://session/sess_1?from=notify").as_deref(),
+            Ok("sess_1")
+        );
+    }
+
+    #[test]
+    fn malformed_links_are_rejected() {
+        assert!(matches!(
+            parse("https://session/sess_1"),
+            Err(DeepLinkError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(
+            parse("wunder://agent/sess_1"),
+            Err(DeepLinkError::UnsupportedTarget(_))
+        ));
+        for raw in [
+            "wunder://session",
+            "wunder://session/",
+            "wunder://session/a/b",
+            "wunder://session/sess%20x",
+            "wunder://session/..%2Fetc",
+        ] {
+            assert!(
+                matches!(parse(raw), Err(DeepLinkError::InvalidSessionId(_))),
+                "{raw}"
+            );
+        }
+        let too_long = format!("wunder://session/{}", "a".repeat(SESSION_ID_MAX_CHARS + 1));
+        assert!(parse(&too_long).is_err());
+    }
+
+    #[test]
+    fn pending_session_is_taken_once() {
+        let state = DeepLinkState::default();
+        state.set_pending_session("sess_1");
+        assert_eq!(state.take_pending_session().as_deref(), Some("sess_1"));
+        assert_eq!(state.take_pending_session(), None);
+    }
+}
@@ -2,20 +2,32 @@
 
 mod args;
 mod bridge;
+mod deep_link;
 mod runtime;
+mod turn_notification;
+mod update_channel;
+mod update_message;
+mod window_state;
 
 use anyhow::{anyhow, Context, Result};
 use args::DesktopArgs;
 use bridge::{DesktopBridge, DesktopRuntimeInfo};
 use clap::Parser;
+use deep_link::DeepLinkState;
 use serde::Serialize;
+use std::path::Path;
 use std::process::Command;
 use std::sync::Arc;
-use tauri::{WebviewUrl, WebviewWindowBuilder};
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_updater::{Update, UpdaterExt};
 use tokio::sync::Mutex;
 use tracing_subscriber::EnvFilter;
+use turn_notification::{DesktopTurnNotifier, TurnNotificationState};
+use update_channel::{resolve_channel_endpoints, DesktopUpdateChannel};
+use update_message::{normalize_update_message, UPDATE_SOURCE_NOT_CONFIGURED};
 use url::Url;
+use window_state::WindowStateTracker;
 
 #[derive(Clone)]
 struct DesktopAppState {
@@ -26,6 +38,7 @@ struct DesktopAppState {
 #[serde(rename_all = "camelCase")]
 struct DesktopUpdateSnapshot {
     phase: String,
+    channel: String,
     current_version: String,
     latest_version: String,
     downloaded: bool,
@@ -49,15 +62,19 @@ struct DesktopUpdateState {
     snapshot: DesktopUpdateSnapshot,
     pending: Option<PendingDesktopUpdate>,
     checking: bool,
+    channel: DesktopUpdateChannel,
+    // Bumped on channel switch so in-flight checks/downloads of the old channel are discarded.
+    generation: u64,
 }
 
 const TAURI_UPDATER_ENDPOINTS_ENV: &str = "WUNDER_TAURI_UPDATE_ENDPOINTS";
 const TAURI_UPDATER_PUBKEY_ENV: &str = "WUNDER_TAURI_UPDATE_PUBKEY";
 
 impl DesktopUpdateSnapshot {
-    fn idle() -> Self {
+    fn idle(channel: DesktopUpdateChannel) -> Self {
         Self {
             phase: "idle".to_string(),
+            channel: channel.as_str().to_string(),
             current_version: String::new(),
             latest_version: String::new(),
             downloaded: false,
@@ -68,13 +85,23 @@ impl DesktopUpdateSnapshot {
 }
 
 impl DesktopUpdateState {
-    fn new() -> Self {
+    fn new(channel: DesktopUpdateChannel) -> Self {
         Self {
-            snapshot: DesktopUpdateSnapshot::idle(),
+            snapshot: DesktopUpdateSnapshot::idle(channel),
             pending: None,
             checking: false,
+            channel,
+            generation: 0,
         }
     }
+
+    fn switch_channel(&mut self, channel: DesktopUpdateChannel) {
+        self.channel = channel;
+        self.generation = self.generation.wrapping_add(1);
+        self.pending = None;
+        self.checking = false;
+        self.snapshot = DesktopUpdateSnapshot::idle(channel);
+    }
 }
 
 const DESKTOP_WINDOW_BRIDGE_SCRIPT: &str = r#"
@@ -90,11 +117,18 @@ const DESKTOP_WINDOW_BRIDGE_SCRIPT: &str = r#"
   api.getUpdateState = () => call('desktop_get_update_state');
   api.checkForUpdates = () => call('desktop_check_for_updates');
   api.installUpdate = () => call('desktop_install_update');
+  api.getUpdateChannel = () => call('desktop_get_update_channel');
+  api.setUpdateChannel = (channel) => call('desktop_set_update_channel', { channel });
+  api.getTurnNotifications = () => call('desktop_get_turn_notifications');
+  api.setTurnNotifications = (enabled) =>
+    call('desktop_set_turn_notifications', { enabled: Boolean(enabled) });
   api.minimizeWindow = () => call('desktop_window_minimize');
   api.toggleMaximizeWindow = () => call('desktop_window_toggle_maximize');
   api.closeWindow = () => call('desktop_window_close');
   api.isWindowMaximized = () => call('desktop_window_is_maximized');
   api.startWindowDrag = () => call('desktop_window_start_dragging');
+  api.resetWindowState = () => call('desktop_reset_window_state');
+  api.takePendingOpenSession = () => call('desktop_take_pending_open_session');
   api.showCompanion = () => Promise.resolve(false);
   api.updateCompanion = () => Promise.resolve(false);
   api.hideCompanion = () => Promise.resolve(true);
@@ -108,42 +142,25 @@ fn desktop_runtime_info(state: tauri::State<'_, DesktopAppState>) -> DesktopRunt
     state.runtime.clone()
 }
 
-fn normalize_update_message(error: impl std::fmt::Display) -> String {
-    let message = error.to_string();
-    if message.contains("updater target not configured")
-        || message.contains("Unable to find update")
-        || message.contains("No release")
-    {
-        "update source is not configured".to_string()
-    } else {
-        message
-    }
-}
-
-fn parse_update_endpoints() -> Result<Vec<Url>, String> {
-    let raw = std::env::var(TAURI_UPDATER_ENDPOINTS_ENV).unwrap_or_default();
-    let endpoints: Result<Vec<_>, _> = raw
-        .split(',')
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(|value| {
-            Url::parse(value)
-                .map_err(|error| format!("invalid updater endpoint `{value}`: {error}"))
-        })
-        .collect();
-
-    let endpoints = endpoints?;
-    if endpoints.is_empty() {
-        return Err("update source is not configured".to_string());
-    }
-    Ok(endpoints)
+fn parse_update_endpoints(channel: DesktopUpdateChannel) -> Result<Vec<Url>, String> {
+    let shared = std::env::var(TAURI_UPDATER_ENDPOINTS_ENV).unwrap_or_default();
+    // Optional per-channel list, e.g. WUNDER_TAURI_UPDATE_ENDPOINTS_BETA.
+    let channel_env = format!(
+        "{TAURI_UPDATER_ENDPOINTS_ENV}_{}",
+        channel.as_str().to_ascii_uppercase()
+    );
+    let channel_specific = std::env::var(channel_env).unwrap_or_default();
+    resolve_channel_endpoints(&shared, &channel_specific, channel)
 }
 
-fn build_updater(app: &tauri::AppHandle) -> Result<tauri_plugin_updater::Updater, String> {
-    let endpoints = parse_update_endpoints()?;
+fn build_updater(
+    app: &tauri::AppHandle,
+    channel: DesktopUpdateChannel,
+) -> Result<tauri_plugin_updater::Updater, String> {
+    let endpoints = parse_update_endpoints(channel)?;
     let pubkey = std::env::var(TAURI_UPDATER_PUBKEY_ENV).unwrap_or_default();
     if pubkey.trim().is_empty() {
-        return Err("update source is not configured".to_string());
+        return Err(UPDATE_SOURCE_NOT_CONFIGURED.to_string());
     }
 
     let builder = app
@@ -190,7 +207,7 @@ async fn desktop_check_for_updates(
         return Ok(with_current_version(&guard.snapshot, &app));
     }
 
-    {
+    let (channel, generation) = {
         let mut guard = state.lock().await;
         if guard.checking {
             return Ok(with_current_version(&guard.snapshot, &app));
@@ -202,12 +219,16 @@ async fn desktop_check_for_updates(
         guard.snapshot.progress = 0.0;
         guard.snapshot.message.clear();
         guard.pending = None;
-    }
+        (guard.channel, guard.generation)
+    };
 
-    let updater = match build_updater(&app) {
+    let updater = match build_updater(&app, channel) {
         Ok(updater) => updater,
         Err(error) => {
             let mut guard = state.lock().await;
+            if guard.generation != generation {
+                return Ok(with_current_version(&guard.snapshot, &app));
+            }
             guard.snapshot.phase = "error".to_string();
             guard.snapshot.message = error;
             guard.checking = false;
@@ -219,6 +240,9 @@ async fn desktop_check_for_updates(
         Ok(Some(update)) => update,
         Ok(None) => {
             let mut guard = state.lock().await;
+            if guard.generation != generation {
+                return Ok(with_current_version(&guard.snapshot, &app));
+            }
             guard.snapshot.phase = "not-available".to_string();
             guard.snapshot.latest_version.clear();
             guard.snapshot.downloaded = false;
@@ -229,6 +253,9 @@ async fn desktop_check_for_updates(
         }
         Err(error) => {
             let mut guard = state.lock().await;
+            if guard.generation != generation {
+                return Ok(with_current_version(&guard.snapshot, &app));
+            }
             guard.snapshot.phase = "error".to_string();
             guard.snapshot.message = normalize_update_message(error);
             guard.checking = false;
@@ -239,6 +266,9 @@ async fn desktop_check_for_updates(
     let latest_version = update.version.to_string();
     {
         let mut guard = state.lock().await;
+        if guard.generation != generation {
+            return Ok(with_current_version(&guard.snapshot, &app));
+        }
         guard.snapshot.phase = "available".to_string();
         guard.snapshot.latest_version = latest_version.clone();
         guard.snapshot.downloaded = false;
@@ -259,6 +289,9 @@ async fn desktop_check_for_updates(
                 let progress_state = Arc::clone(&progress_state);
                 tauri::async_runtime::spawn(async move {
                     let mut guard = progress_state.lock().await;
+                    if guard.generation != generation {
+                        return;
+                    }
                     guard.snapshot.phase = "downloading".to_string();
                     guard.snapshot.progress = progress.clamp(0.0, 100.0);
                 });
@@ -270,6 +303,9 @@ async fn desktop_check_for_updates(
         Ok(bytes) => bytes,
         Err(error) => {
             let mut guard = state.lock().await;
+            if guard.generation != generation {
+                return Ok(with_current_version(&guard.snapshot, &app));
+            }
             guard.snapshot.phase = "error".to_string();
             guard.snapshot.message = normalize_update_message(error);
             guard.snapshot.downloaded = false;
@@ -280,6 +316,10 @@ async fn desktop_check_for_updates(
     };
 
     let mut guard = state.lock().await;
+    if guard.generation != generation {
+        // The channel changed mid-download; drop the stale artifact instead of staging it.
+        return Ok(with_current_version(&guard.snapshot, &app));
+    }
     guard.snapshot.phase = "downloaded".to_string();
     guard.snapshot.latest_version = latest_version;
     guard.snapshot.downloaded = true;
@@ -290,6 +330,51 @@ async fn desktop_check_for_updates(
     Ok(with_current_version(&guard.snapshot, &app))
 }
 
+#[tauri::command]
+async fn desktop_get_update_channel(
+    state: tauri::State<'_, Arc<Mutex<DesktopUpdateState>>>,
+) -> Result<String, String> {
+    let guard = state.lock().await;
+    Ok(guard.channel.as_str().to_string())
+}
+
+#[tauri::command]
+async fn desktop_set_update_channel(
+    app: tauri::AppHandle,
+    channel: String,
+    app_state: tauri::State<'_, DesktopAppState>,
+    state: tauri::State<'_, Arc<Mutex<DesktopUpdateState>>>,
+) -> Result<DesktopUpdateSnapshot, String> {
+    let channel = DesktopUpdateChannel::parse(&channel)
+        .ok_or_else(|| format!("unsupported update channel `{}`", channel.trim()))?;
+    persist_update_channel(&app_state.runtime.settings_path, channel)
+        .map_err(|err| err.to_string())?;
+    let mut guard = state.lock().await;
+    if guard.channel != channel {
+        guard.switch_channel(channel);
+    }
+    Ok(with_current_version(&guard.snapshot, &app))
+}
+
+fn load_update_channel(settings_path: &str) -> DesktopUpdateChannel {
+    runtime::load_desktop_settings(Path::new(settings_path))
+        .map(|settings| DesktopUpdateChannel::from_setting(&settings.update_channel))
+        .unwrap_or_default()
+}
+
+fn persist_update_channel(settings_path: &str, channel: DesktopUpdateChannel) -> Result<()> {
+    let path = Path::new(settings_path);
+    let mut settings = runtime::load_desktop_settings(path)?;
+    if DesktopUpdateChannel::from_setting(&settings.update_channel) == channel
+        && !settings.update_channel.is_empty()
+    {
+        return Ok(());
+    }
+    settings.update_channel = channel.as_str().to_string();
+    settings.updated_at = runtime::now_ts();
+    runtime::save_desktop_settings(path, &settings)
+}
+
 #[tauri::command]
 async fn desktop_install_update(
     app: tauri::AppHandle,
@@ -341,6 +426,27 @@ async fn desktop_install_update(
     })
 }
 
+#[tauri::command]
+fn desktop_get_turn_notifications(state: tauri::State<'_, TurnNotificationState>) -> bool {
+    state.enabled()
+}
+
+#[tauri::command]
+fn desktop_set_turn_notifications(
+    app: tauri::AppHandle,
+    enabled: bool,
+    app_state: tauri::State<'_, DesktopAppState>,
+    state: tauri::State<'_, TurnNotificationState>,
+) -> Result<bool, String> {
+    turn_notification::set_turn_notifications(
+        &app,
+        &state,
+        &app_state.runtime.settings_path,
+        enabled,
+    )
+    .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 #[cfg(debug_assertions)]
 fn desktop_toggle_devtools(window: tauri::WebviewWindow) {
@@ -384,6 +490,19 @@ fn desktop_window_start_dragging(window: tauri::WebviewWindow) -> Result<(), Str
     window.start_dragging().map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+fn desktop_reset_window_state(
+    window: tauri::WebviewWindow,
+    tracker: tauri::State<'_, WindowStateTracker>,
+) -> Result<(), String> {
+    tracker.reset(&window).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn desktop_take_pending_open_session(state: tauri::State<'_, DeepLinkState>) -> Option<String> {
+    state.take_pending_session()
+}
+
 fn main() -> Result<()> {
     wunder_server::rustls_provider::install_process_default_provider();
     init_tracing();
@@ -405,11 +524,21 @@ fn run_bridge_only(args: DesktopArgs) -> Result<()> {
     rt.block_on(async move {
         let mut bridge = DesktopBridge::launch(&args).await?;
         bridge.print_banner(args.print_token);
+        if let Some(ready_file) = args.ready_file.as_deref() {
+            // Launchers wait on this file; stop the bridge rather than keep serving unannounced.
+            if let Err(err) = bridge.write_ready_file(ready_file).await {
+                bridge.shutdown().await;
+                return Err(err);
+            }
+        }
         if args.open {
             open_external_browser(&bridge.info().web_base)?;
         }
         wunder_server::shutdown::shutdown_signal().await;
         bridge.shutdown().await;
+        if let Some(ready_file) = args.ready_file.as_deref() {
+            let _ = std::fs::remove_file(ready_file);
+        }
         Ok(())
     })
 }
@@ -427,37 +556,113 @@ fn run_gui(args: DesktopArgs) -> Result<()> {
     }
 
     let web_url = runtime_info.web_base.clone();
+    let update_channel = load_update_channel(&runtime_info.settings_path);
+    let turn_notifications =
+        turn_notification::load_turn_notifications(&runtime_info.settings_path);
+    let app_state = bridge.app_state();
+    let desktop_user_id = runtime_info.user_id.clone();
+    let window_state_path = window_state::window_state_path(&runtime_info.temp_root);
+    let saved_window_state = window_state::load_window_state(&window_state_path);
     let run_result = tauri::Builder::default()
+        // Must be the first plugin: a second launch (e.g. a clicked deep link) hands its URL
+        // to this instance and exits instead of opening another window.
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            deep_link::focus_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
+        .manage(DeepLinkState::default())
         .manage(DesktopAppState {
             runtime: runtime_info,
         })
-        .manage(Arc::new(Mutex::new(DesktopUpdateState::new())))
+        .manage(Arc::new(Mutex::new(DesktopUpdateState::new(
+            update_channel,
+        ))))
+        .manage(TurnNotificationState::new(turn_notifications))
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(tauri_plugin_notification::init())
+        .manage(WindowStateTracker::new(
+            window_state_path,
+            saved_window_state,
+        ))
+        .on_window_event(|window, event| match event {
+            WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+                window.state::<WindowStateTracker>().observe(window);
+            }
+            WindowEvent::CloseRequested { .. } => {
+                window.state::<WindowStateTracker>().persist(window);
+            }
+            _ => {}
+        })
         .invoke_handler(tauri::generate_handler![
             desktop_runtime_info,
             desktop_get_update_state,
             desktop_check_for_updates,
             desktop_install_update,
+            desktop_get_update_channel,
+            desktop_set_update_channel,
+            desktop_get_turn_notifications,
+            desktop_set_turn_notifications,
             desktop_toggle_devtools,
             desktop_window_minimize,
             desktop_window_toggle_maximize,
             desktop_window_close,
             desktop_window_is_maximized,
-            desktop_window_start_dragging
+            desktop_window_start_dragging,
+            desktop_reset_window_state,
+            desktop_take_pending_open_session
         ])
         .setup(move |app| {
             let external = url::Url::parse(&web_url)
                 .with_context(|| format!("invalid desktop web url: {web_url}"))?;
-            WebviewWindowBuilder::new(app, "main", WebviewUrl::External(external))
+            let placement = saved_window_state.and_then(|state| {
+                window_state::fit_to_monitors(&state, &window_state::monitor_bounds(app.handle()))
+            });
+            let builder = WebviewWindowBuilder::new(app, "main", WebviewUrl::External(external))
                 .title("Wunder Desktop")
                 .decorations(false)
-                .inner_size(1360.0, 860.0)
-                .min_inner_size(1024.0, 700.0)
+                .min_inner_size(
+                    window_state::MIN_WINDOW_WIDTH,
+                    window_state::MIN_WINDOW_HEIGHT,
+                )
                 .resizable(true)
-                .initialization_script(DESKTOP_WINDOW_BRIDGE_SCRIPT)
-                .center()
+                .initialization_script(DESKTOP_WINDOW_BRIDGE_SCRIPT);
+            let builder = match placement {
+                Some(state) => builder
+                    .inner_size(state.width, state.height)
+                    .position(state.x, state.y),
+                None => builder
+                    .inner_size(
+                        window_state::DEFAULT_WINDOW_WIDTH,
+                        window_state::DEFAULT_WINDOW_HEIGHT,
+                    )
+                    .center(),
+            };
+            let window = builder
                 .build()
                 .map_err(|err| anyhow!("create desktop window failed: {err}"))?;
+            if placement.is_some_and(|state| state.maximized) {
+                let _ = window.maximize();
+            }
+
+            // Installers register the scheme; this covers portable and dev runs.
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(err) = app.deep_link().register_all() {
+                tracing::warn!("register desktop deep link scheme failed: {err}");
+            }
+            app_state
+                .orchestrator
+                .register_turn_notifier(Arc::new(DesktopTurnNotifier::new(
+                    app.handle().clone(),
+                    &desktop_user_id,
+                )));
+
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                deep_link::open_deep_links(&handle, &event.urls());
+            });
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                deep_link::open_deep_links(app.handle(), &urls);
+            }
             Ok(())
         })
         .run(tauri::generate_context!("tauri.conf.json"));
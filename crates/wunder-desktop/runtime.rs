@@ -65,6 +65,10 @@ pub struct DesktopSettings {
     pub llm: Option<LlmConfig>,
     #[serde(default)]
     pub lan_mesh: DesktopLanMeshSettings,
+    #[serde(default)]
+    pub update_channel: String,
+    #[serde(default)]
+    pub turn_notifications: bool,
     pub updated_at: f64,
 }
 
@@ -83,6 +87,8 @@ impl Default for DesktopSettings {
             language: String::new(),
             llm: None,
             lan_mesh: DesktopLanMeshSettings::default(),
+            update_channel: String::new(),
+            turn_notifications: false,
             updated_at: now_ts(),
         }
     }
@@ -1155,6 +1161,7 @@ fn apply_desktop_defaults(
     allow_paths.push(workspace_root.to_string_lossy().to_string());
     config.security.allow_paths = dedupe_strings(allow_paths);
     config.security.allow_commands = vec!["*".to_string()];
+    config.security.env_passthrough = vec!["*".to_string()];
     config.security.deny_globs.clear();
     config.security.exec_policy_mode = None;
     config.security.approval_mode = Some("full_auto".to_string());
@@ -1490,7 +1497,7 @@ fn log_startup_point(enabled: bool, scope: &str, point: &str, startup_boot: Inst
     );
 }
 
-fn now_ts() -> f64 {
+pub(crate) fn now_ts() -> f64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|duration| duration.as_secs_f64())
@@ -0,0 +1,212 @@
+// 桌面回合完成通知：作为运行时 TurnNotifier 注册到编排器，窗口失焦时弹出系统通知（附回答预览），点击通知即聚焦窗口并打开对应会话；开关持久化到桌面设置，通知权限被拒时静默跳过。
+use crate::deep_link;
+use crate::runtime;
+use anyhow::Result;
+use notify_rust::NotificationResponse;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_notification::{NotificationExt, PermissionState};
+use tracing::warn;
+use wunder_server::turn_notifier::{preview_answer, TurnNotification, TurnNotifier};
+
+const NOTIFICATION_TITLE: &str = "Wunder";
+const DEFAULT_NOTIFICATION_BODY: &str = "Agent turn complete";
+const PREVIEW_MAX_CHARS: usize = 120;
+/// XDG servers only report body clicks for notifications that declare a `default` action.
+#[cfg(all(unix, not(target_os = "macos")))]
+const OPEN_ACTION_LABEL: &str = "Open";
+
+pub struct TurnNotificationState {
+    enabled: AtomicBool,
+}
+
+impl TurnNotificationState {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+}
+
+/// Single-line answer preview bounded to `PREVIEW_MAX_CHARS`.
+fn build_notification_body(answer: &str) -> String {
+    let collapsed = answer.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return DEFAULT_NOTIFICATION_BODY.to_string();
+    }
+    preview_answer(&collapsed, PREVIEW_MAX_CHARS)
+}
+
+pub fn load_turn_notifications(settings_path: &str) -> bool {
+    runtime::load_desktop_settings(Path::new(settings_path))
+        .map(|settings| settings.turn_notifications)
+        .unwrap_or(false)
+}
+
+fn persist_turn_notifications(settings_path: &str, enabled: bool) -> Result<()> {
+    let path = Path::new(settings_path);
+    let mut settings = runtime::load_desktop_settings(path)?;
+    if settings.turn_notifications == enabled {
+        return Ok(());
+    }
+    settings.turn_notifications = enabled;
+    settings.updated_at = runtime::now_ts();
+    runtime::save_desktop_settings(path, &settings)
+}
+
+/// Asks the OS once when the answer is still open; any other outcome counts as denied.
+fn notification_permission_granted<R: Runtime>(app: &AppHandle<R>) -> bool {
+    match app.notification().permission_state() {
+        Ok(PermissionState::Granted) => true,
+        Ok(PermissionState::Prompt | PermissionState::PromptWithRationale) => matches!(
+            app.notification().request_permission(),
+            Ok(PermissionState::Granted)
+        ),
+        _ => false,
+    }
+}
+
+/// Persists the toggle; enabling also triggers the OS permission prompt up front so the first
+/// notification is not lost to it. Returns the stored value.
+pub fn set_turn_notifications<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &TurnNotificationState,
+    settings_path: &str,
+    enabled: bool,
+) -> Result<bool> {
+    persist_turn_notifications(settings_path, enabled)?;
+    state.set_enabled(enabled);
+    if enabled {
+        notification_permission_granted(app);
+    }
+    Ok(enabled)
+}
+
+/// Body clicks arrive as the default response; some XDG servers report the `default` action instead.
+fn is_activation(response: &NotificationResponse) -> bool {
+    match response {
+        NotificationResponse::Default => true,
+        NotificationResponse::Action(action) => action == "default",
+        _ => false,
+    }
+}
+
+/// Runtime notifier for the desktop user's turns. It shows an OS notification when enabled,
+/// the main window is unfocused and permission is granted, and a click on that notification
+/// focuses the window and opens the turn's session.
+pub struct DesktopTurnNotifier<R: Runtime> {
+    app: AppHandle<R>,
+    user_id: String,
+}
+
+impl<R: Runtime> DesktopTurnNotifier<R> {
+    pub fn new(app: AppHandle<R>, user_id: &str) -> Self {
+        Self {
+            app,
+            user_id: user_id.to_string(),
+        }
+    }
+
+    fn should_notify(&self, notification: &TurnNotification) -> bool {
+        if notification.user_id != self.user_id
+            || !self.app.state::<TurnNotificationState>().enabled()
+        {
+            return false;
+        }
+        let focused = self
+            .app
+            .get_webview_window("main")
+            .and_then(|window| window.is_focused().ok())
+            .unwrap_or(false);
+        !focused && notification_permission_granted(&self.app)
+    }
+}
+
+impl<R: Runtime> TurnNotifier for DesktopTurnNotifier<R> {
+    fn notify(&self, notification: &TurnNotification) -> Result<()> {
+        if !self.should_notify(notification) {
+            return Ok(());
+        }
+        let mut toast = notify_rust::Notification::new();
+        toast
+            .summary(NOTIFICATION_TITLE)
+            .body(&build_notification_body(&notification.answer_preview))
+            .auto_icon();
+        #[cfg(all(unix, not(target_os = "macos")))]
+        toast.action("default", OPEN_ACTION_LABEL);
+        #[cfg(target_os = "macos")]
+        let _ = notify_rust::set_application(if tauri::is_dev() {
+            "com.apple.Terminal"
+        } else {
+            self.app.config().identifier.as_str()
+        });
+        #[cfg(windows)]
+        if !tauri::is_dev() {
+            toast.app_id(&self.app.config().identifier);
+        }
+
+        // Waiting for the click blocks until the notification is acted on or dismissed, so it
+        // gets its own thread instead of holding the runtime's blocking pool.
+        let app = self.app.clone();
+        let session_id = notification.session_id.clone();
+        std::thread::Builder::new()
+            .name("wunder-turn-notification".to_string())
+            .spawn(move || {
+                let result = toast.show().and_then(|handle| {
+                    handle.wait_for_response(|response: &NotificationResponse| {
+                        if !is_activation(response) {
+                            return;
+                        }
+                        deep_link::focus_main_window(&app);
+                        if !session_id.trim().is_empty() {
+                            deep_link::open_session(&app, &session_id);
+                        }
+                    })
+                });
+                if let Err(err) = result {
+                    warn!("show desktop turn notification failed: {err}");
+                }
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notification_body_is_single_line_and_bounded() {
+        assert_eq!(
+            build_notification_body("  Done.\n\nAll   tests pass. "),
+            "Done. All tests pass."
+        );
+        assert_eq!(build_notification_body(" \n "), DEFAULT_NOTIFICATION_BODY);
+        let long = build_notification_body(&"字".repeat(PREVIEW_MAX_CHARS + 10));
+        assert_eq!(long.chars().count(), PREVIEW_MAX_CHARS + 1);
+        assert!(long.ends_with('…'));
+    }
+
+    #[test]
+    fn only_clicks_open_the_session() {
+        assert!(is_activation(&NotificationResponse::Default));
+        assert!(is_activation(&NotificationResponse::Action(
+            "default".to_string()
+        )));
+        assert!(!is_activation(&NotificationResponse::Action(
+            "__closed".to_string()
+        )));
+        assert!(!is_activation(&NotificationResponse::Closed(
+            notify_rust::CloseReason::Dismissed
+        )));
+    }
+}
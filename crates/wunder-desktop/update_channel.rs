@@ -0,0 +1,141 @@
+use crate::update_message::UPDATE_SOURCE_NOT_CONFIGURED;
+use std::fmt;
+use url::Url;
+
+pub const UPDATE_CHANNEL_PLACEHOLDER: &str = "{{channel}}";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DesktopUpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl DesktopUpdateChannel {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "stable" => Some(Self::Stable),
+            "beta" => Some(Self::Beta),
+            _ => None,
+        }
+    }
+
+    /// Lenient variant used when reading persisted settings: unknown values fall back to stable.
+    pub fn from_setting(raw: &str) -> Self {
+        Self::parse(raw).unwrap_or_default()
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Beta => "beta",
+        }
+    }
+}
+
+impl fmt::Display for DesktopUpdateChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Resolve updater endpoints for a channel.
+///
+/// A channel-specific list (`channel_raw`) wins when present. Otherwise the shared list is
+/// used and `{{channel}}` is substituted; non-stable channels require the placeholder so a
+/// beta opt-in never silently downloads stable artifacts.
+pub fn resolve_channel_endpoints(
+    shared_raw: &str,
+    channel_raw: &str,
+    channel: DesktopUpdateChannel,
+) -> Result<Vec<Url>, String> {
+    let (raw, channel_specific) = if channel_raw.trim().is_empty() {
+        (shared_raw, false)
+    } else {
+        (channel_raw, true)
+    };
+    let values: Vec<&str> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .collect();
+    if values.is_empty() {
+        return Err(UPDATE_SOURCE_NOT_CONFIGURED.to_string());
+    }
+    if !channel_specific
+        && channel != DesktopUpdateChannel::Stable
+        && !values
+            .iter()
+            .any(|value| value.contains(UPDATE_CHANNEL_PLACEHOLDER))
+    {
+        return Err(format!(
+            "{UPDATE_SOURCE_NOT_CONFIGURED} for channel `{channel}`"
+        ));
+    }
+    values
+        .into_iter()
+        .map(|value| {
+            let resolved = value.replace(UPDATE_CHANNEL_PLACEHOLDER, channel.as_str());
+            Url::parse(&resolved)
+                .map_err(|error| format!("invalid updater endpoint `{resolved}`: {error}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_defaults_to_stable_and_rejects_unknown() {
+        assert_eq!(
+            DesktopUpdateChannel::parse(""),
+            Some(DesktopUpdateChannel::Stable)
+        );
+        assert_eq!(
+            DesktopUpdateChannel::parse(" Beta "),
+            Some(DesktopUpdateChannel::Beta)
+        );
+        assert_eq!(DesktopUpdateChannel::parse("nightly"), None);
+        assert_eq!(
+            DesktopUpdateChannel::from_setting("nightly"),
+            DesktopUpdateChannel::Stable
+        );
+    }
+
+    #[test]
+    fn resolve_channel_endpoints_templates_shared_list() {
+        let shared = "https://updates.example.com/{{channel}}/latest.json";
+        let stable =
+            resolve_channel_endpoints(shared, "", DesktopUpdateChannel::Stable).expect("stable");
+        let beta = resolve_channel_endpoints(shared, "", DesktopUpdateChannel::Beta).expect("beta");
+        assert_eq!(
+            stable,
+            vec![Url::parse("https://updates.example.com/stable/latest.json").unwrap()]
+        );
+        assert_eq!(
+            beta,
+            vec![Url::parse("https://updates.example.com/beta/latest.json").unwrap()]
+        );
+    }
+
+    #[test]
+    fn resolve_channel_endpoints_requires_placeholder_or_override_for_beta() {
+        let shared = "https://updates.example.com/latest.json";
+        assert!(resolve_channel_endpoints(shared, "", DesktopUpdateChannel::Stable).is_ok());
+        assert_eq!(
+            resolve_channel_endpoints(shared, "", DesktopUpdateChannel::Beta),
+            Err("update source is not configured for channel `beta`".to_string())
+        );
+        let beta = resolve_channel_endpoints(
+            shared,
+            "https://beta.example.com/latest.json",
+            DesktopUpdateChannel::Beta,
+        )
+        .expect("beta override");
+        assert_eq!(
+            beta,
+            vec![Url::parse("https://beta.example.com/latest.json").unwrap()]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+pub const UPDATE_SOURCE_NOT_CONFIGURED: &str = "update source is not configured";
+pub const UPDATE_SIGNATURE_INVALID: &str = "update signature invalid";
+pub const UPDATE_SERVER_UNREACHABLE: &str = "update server unreachable";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UpdateFailureKind {
+    Signature,
+    NotConfigured,
+    Network,
+    Unknown,
+}
+
+// Signature checks run first: minisign errors can mention the release payload too, and a
+// tampered download must never be reported as a setup problem.
+const SIGNATURE_MARKERS: &[&str] = &[
+    "signature",
+    "minisign",
+    "public key",
+    "pubkey",
+    "verification failed",
+    "untrusted comment",
+];
+
+const NOT_CONFIGURED_MARKERS: &[&str] = &[
+    "updater target not configured",
+    "unable to find update",
+    "no release",
+    "was not found in the response",
+    "update source is not configured",
+];
+
+const NETWORK_MARKERS: &[&str] = &[
+    "error sending request",
+    "network",
+    "connection",
+    "timed out",
+    "timeout",
+    "dns error",
+    "unexpected status code",
+    "could not fetch a valid release json",
+];
+
+fn classify_update_failure(message: &str) -> UpdateFailureKind {
+    let lowered = message.to_ascii_lowercase();
+    let matches_any = |markers: &[&str]| markers.iter().any(|marker| lowered.contains(marker));
+    if matches_any(SIGNATURE_MARKERS) {
+        UpdateFailureKind::Signature
+    } else if matches_any(NOT_CONFIGURED_MARKERS) {
+        UpdateFailureKind::NotConfigured
+    } else if matches_any(NETWORK_MARKERS) {
+        UpdateFailureKind::Network
+    } else {
+        UpdateFailureKind::Unknown
+    }
+}
+
+/// Map updater errors to a user-facing message; unknown errors keep their original text.
+pub fn normalize_update_message(error: impl std::fmt::Display) -> String {
+    let message = error.to_string();
+    match classify_update_failure(&message) {
+        UpdateFailureKind::Signature => UPDATE_SIGNATURE_INVALID.to_string(),
+        UpdateFailureKind::NotConfigured => UPDATE_SOURCE_NOT_CONFIGURED.to_string(),
+        UpdateFailureKind::Network => UPDATE_SERVER_UNREACHABLE.to_string(),
+        UpdateFailureKind::Unknown => message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_update_message_distinguishes_failure_kinds() {
+        assert_eq!(
+            normalize_update_message("Invalid signature: verification failed"),
+            UPDATE_SIGNATURE_INVALID
+        );
+        assert_eq!(
+            normalize_update_message("minisign error: Invalid public key"),
+            UPDATE_SIGNATURE_INVALID
+        );
+        assert_eq!(
+            normalize_update_message("updater target not configured"),
+            UPDATE_SOURCE_NOT_CONFIGURED
+        );
+        assert_eq!(
+            normalize_update_message("error sending request for url"),
+            UPDATE_SERVER_UNREACHABLE
+        );
+        assert_eq!(
+            normalize_update_message("Could not fetch a valid release JSON from the remote"),
+            UPDATE_SERVER_UNREACHABLE
+        );
+    }
+
+    #[test]
+    fn normalize_update_message_keeps_unknown_errors_verbatim() {
+        assert_eq!(
+            normalize_update_message("failed to move the new app into place"),
+            "failed to move the new app into place"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+// 桌面窗口状态：记录上次的窗口尺寸、位置与最大化状态，启动时按当前显示器范围恢复；原显示器已断开时回退为默认尺寸居中。
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{LogicalSize, Runtime, WebviewWindow, Window};
+use tracing::warn;
+
+pub const DEFAULT_WINDOW_WIDTH: f64 = 1360.0;
+pub const DEFAULT_WINDOW_HEIGHT: f64 = 860.0;
+pub const MIN_WINDOW_WIDTH: f64 = 1024.0;
+pub const MIN_WINDOW_HEIGHT: f64 = 700.0;
+const WINDOW_STATE_FILE: &str = "config/desktop.window_state.json";
+
+/// Window geometry in logical pixels. `x`/`y` are the outer position; `width`/`height` the
+/// inner size of the last non-maximized window.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DesktopWindowState {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+/// Monitor area in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorBounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl MonitorBounds {
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+pub fn window_state_path(temp_root: &str) -> PathBuf {
+    Path::new(temp_root).join(WINDOW_STATE_FILE)
+}
+
+pub fn load_window_state(path: &Path) -> Option<DesktopWindowState> {
+    let text = fs::read_to_string(path).ok()?;
+    let state = serde_json::from_str::<DesktopWindowState>(&text).ok()?;
+    let valid = [state.x, state.y, state.width, state.height]
+        .iter()
+        .all(|value| value.is_finite())
+        && state.width > 0.0
+        && state.height > 0.0;
+    valid.then_some(state)
+}
+
+fn save_window_state(path: &Path, state: &DesktopWindowState) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// Places the saved window on the monitor holding its center, shrinking it to fit and
+/// pulling it fully on screen. Returns `None` when that monitor is gone so the caller
+/// falls back to the default centered window.
+pub fn fit_to_monitors(
+    state: &DesktopWindowState,
+    monitors: &[MonitorBounds],
+) -> Option<DesktopWindowState> {
+    let center_x = state.x + state.width / 2.0;
+    let center_y = state.y + state.height / 2.0;
+    let monitor = monitors
+        .iter()
+        .find(|monitor| monitor.contains(center_x, center_y))?;
+    let width = state
+        .width
+        .max(MIN_WINDOW_WIDTH)
+        .min(monitor.width.max(MIN_WINDOW_WIDTH));
+    let height = state
+        .height
+        .max(MIN_WINDOW_HEIGHT)
+        .min(monitor.height.max(MIN_WINDOW_HEIGHT));
+    let max_x = (monitor.x + monitor.width - width).max(monitor.x);
+    let max_y = (monitor.y + monitor.height - height).max(monitor.y);
+    Some(DesktopWindowState {
+        x: state.x.clamp(monitor.x, max_x),
+        y: state.y.clamp(monitor.y, max_y),
+        width,
+        height,
+        maximized: state.maximized,
+    })
+}
+
+pub fn monitor_bounds<R: Runtime>(app: &tauri::AppHandle<R>) -> Vec<MonitorBounds> {
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|monitor| {
+            let scale = monitor.scale_factor();
+            let position = monitor.position().to_logical::<f64>(scale);
+            let size = monitor.size().to_logical::<f64>(scale);
+            MonitorBounds {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+            }
+        })
+        .collect()
+}
+
+/// Tracks the last non-maximized geometry while the window is open and writes it on close.
+pub struct WindowStateTracker {
+    path: PathBuf,
+    last_normal: Mutex<Option<DesktopWindowState>>,
+}
+
+impl WindowStateTracker {
+    pub fn new(path: PathBuf, initial: Option<DesktopWindowState>) -> Self {
+        Self {
+            path,
+            last_normal: Mutex::new(initial),
+        }
+    }
+
+    /// Called on move/resize. Maximized and minimized geometry is skipped so restoring
+    /// brings back the size the user chose (minimized windows report off-screen positions).
+    pub fn observe<R: Runtime>(&self, window: &Window<R>) {
+        if window.is_maximized().unwrap_or(false) || window.is_minimized().unwrap_or(false) {
+            return;
+        }
+        let Some(geometry) = read_geometry(window) else {
+            return;
+        };
+        if let Ok(mut last_normal) = self.last_normal.lock() {
+            *last_normal = Some(geometry);
+        }
+    }
+
+    pub fn persist<R: Runtime>(&self, window: &Window<R>) {
+        let maximized = window.is_maximized().unwrap_or(false);
+        let last_normal = self.last_normal.lock().ok().and_then(|value| *value);
+        let Some(mut state) = last_normal.or_else(|| read_geometry(window)) else {
+            return;
+        };
+        state.maximized = maximized;
+        if let Err(err) = save_window_state(&self.path, &state) {
+            warn!(
+                "save desktop window state failed: {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+
+    /// Drops the saved state and puts the window back to the default centered size.
+    pub fn reset<R: Runtime>(&self, window: &WebviewWindow<R>) -> anyhow::Result<()> {
+        if let Ok(mut last_normal) = self.last_normal.lock() {
+            *last_normal = None;
+        }
+        match fs::remove_file(&self.path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        window.unmaximize()?;
+        window.set_size(LogicalSize::new(
+            DEFAULT_WINDOW_WIDTH,
+            DEFAULT_WINDOW_HEIGHT,
+        ))?;
+        window.center()?;
+        Ok(())
+    }
+}
+
+fn read_geometry<R: Runtime>(window: &Window<R>) -> Option<DesktopWindowState> {
+    let scale = window.scale_factor().ok()?;
+    let position = window.outer_position().ok()?.to_logical::<f64>(scale);
+    let size = window.inner_size().ok()?.to_logical::<f64>(scale);
+    Some(DesktopWindowState {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARY: MonitorBounds = MonitorBounds {
+        x: 0.0,
+        y: 0.0,
+        width: 1920.0,
+        height: 1080.0,
+    };
+    const SECONDARY: MonitorBounds = MonitorBounds {
+        x: 1920.0,
+        y: 0.0,
+        width: 1280.0,
+        height: 1024.0,
+    };
+
+    fn state(x: f64, y: f64, width: f64, height: f64) -> DesktopWindowState {
+        DesktopWindowState {
+            x,
+            y,
+            width,
+            height,
+            maximized: false,
+        }
+    }
+
+    #[test]
+    fn saved_state_inside_monitor_is_kept() {
+        let saved = state(100.0, 80.0, 1400.0, 900.0);
+        assert_eq!(fit_to_monitors(&saved, &[PRIMARY]), Some(saved));
+    }
+
+    #[test]
+    fn oversized_window_is_shrunk_and_pulled_on_screen() {
+        let fitted = fit_to_monitors(&state(2200.0, 300.0, 1600.0, 1200.0), &[PRIMARY, SECONDARY])
+            .expect("center is on the secondary monitor");
+        assert_eq!(fitted, state(1920.0, 0.0, 1280.0, 1024.0));
+
+        let fitted = fit_to_monitors(&state(1200.0, 500.0, 1200.0, 800.0), &[PRIMARY])
+            .expect("center is on the primary monitor");
+        assert_eq!(fitted, state(720.0, 280.0, 1200.0, 800.0));
+    }
+
+    #[test]
+    fn disconnected_monitor_falls_back_to_default() {
+        let on_secondary = state(2000.0, 100.0, 1100.0, 800.0);
+        assert_eq!(fit_to_monitors(&on_secondary, &[PRIMARY]), None);
+        assert_eq!(fit_to_monitors(&on_secondary, &[]), None);
+    }
+
+    #[test]
+    fn maximized_flag_survives_fitting() {
+        let mut saved = state(10.0, 10.0, 1200.0, 800.0);
+        saved.maximized = true;
+        assert!(fit_to_monitors(&saved, &[PRIMARY]).unwrap().maximized);
+    }
+
+    #[test]
+    fn invalid_state_file_is_ignored() {
+        let root = std::env::temp_dir().join(format!(
+            "wunder-desktop-window-state-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let path = window_state_path(&root.to_string_lossy());
+        assert_eq!(load_window_state(&path), None);
+
+        save_window_state(&path, &state(1.0, 2.0, 1300.0, 800.0)).expect("save state");
+        assert_eq!(
+            load_window_state(&path),
+            Some(state(1.0, 2.0, 1300.0, 800.0))
+        );
+
+        fs::write(&path, r#"{"x":1,"y":2,"width":0,"height":800}"#).expect("write state");
+        assert_eq!(load_window_state(&path), None);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -759,6 +759,9 @@ impl A2aService {
             agent_prompt: None,
             preview_skill: false,
             attachments: None,
+            max_rounds: None,
+            cite: false,
+            remember_attachments: false,
             allow_queue: true,
             is_admin: self.is_admin_user(&user_id),
             enforce_runtime_queue: false,
@@ -853,6 +856,9 @@ impl A2aService {
             agent_prompt: None,
             preview_skill: false,
             attachments: None,
+            max_rounds: None,
+            cite: false,
+            remember_attachments: false,
             allow_queue: true,
             is_admin: self.is_admin_user(&user_id),
             enforce_runtime_queue: false,
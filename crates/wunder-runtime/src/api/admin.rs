@@ -28,6 +28,7 @@ mod integration_admin;
 mod knowledge_admin;
 mod monitor_admin;
 mod resource_admin;
+mod sandbox_admin;
 
 pub(crate) use integration_admin::{
     ensure_admin_skill_editable, is_admin_skill_editable, resolve_admin_skill_root,
@@ -50,6 +51,7 @@ pub fn router() -> Router<Arc<AppState>> {
         .merge(knowledge_admin::router())
         .merge(identity_admin::router())
         .merge(resource_admin::router())
+        .merge(sandbox_admin::router())
         .route(
             "/wunder/admin/llm",
             get(admin_llm_get).post(admin_llm_update),
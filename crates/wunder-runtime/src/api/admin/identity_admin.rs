@@ -25,12 +25,16 @@ use uuid::Uuid;
 const ORG_UNIT_NAME_SEPARATOR: &str = " / ";
 const MAX_ORG_UNIT_LEVEL: i32 = 4;
 
+mod org_unit_skills;
 mod test_accounts;
+mod tool_access_grants;
 mod user_account_import;
 
 pub(super) fn router() -> Router<Arc<AppState>> {
     Router::new()
         .merge(test_accounts::router())
+        .merge(org_unit_skills::router())
+        .merge(tool_access_grants::router())
         .route(
             "/wunder/admin/org_units",
             get(admin_org_units_list).post(admin_org_units_create),
@@ -0,0 +1,93 @@
+// 组织单元技能管理：查看与设置单元下发给成员（含下级单元成员）的技能列表。
+use crate::api::admin::{
+    ensure_unit_scope, error_response, normalize_tool_access_list, resolve_admin_actor,
+};
+use crate::i18n;
+use crate::state::AppState;
+use axum::extract::{Path as AxumPath, State};
+use axum::http::{HeaderMap as AxumHeaderMap, StatusCode};
+use axum::response::Response;
+use axum::{routing::get, Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+pub(super) fn router() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/wunder/admin/org_units/{unit_id}/skills",
+        get(admin_org_unit_skills_get).put(admin_org_unit_skills_update),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct OrgUnitSkillsRequest {
+    #[serde(default)]
+    skills: Vec<String>,
+}
+
+async fn admin_org_unit_skills_get(
+    State(state): State<Arc<AppState>>,
+    headers: AxumHeaderMap,
+    AxumPath(unit_id): AxumPath<String>,
+) -> Result<Json<Value>, Response> {
+    let unit_id = resolve_scoped_unit_id(&state, &headers, &unit_id)?;
+    Ok(Json(
+        json!({ "data": org_unit_skills_payload(&state, &unit_id) }),
+    ))
+}
+
+async fn admin_org_unit_skills_update(
+    State(state): State<Arc<AppState>>,
+    headers: AxumHeaderMap,
+    AxumPath(unit_id): AxumPath<String>,
+    Json(payload): Json<OrgUnitSkillsRequest>,
+) -> Result<Json<Value>, Response> {
+    let unit_id = resolve_scoped_unit_id(&state, &headers, &unit_id)?;
+    let skills = normalize_tool_access_list(payload.skills);
+    state
+        .user_tool_store
+        .update_org_unit_skills(&unit_id, skills)
+        .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?;
+    // Members' bindings fold the list's mtime into their version; drop the cached specs too.
+    state.user_tool_manager.invalidate_org_unit_skills(&unit_id);
+    Ok(Json(
+        json!({ "data": org_unit_skills_payload(&state, &unit_id) }),
+    ))
+}
+
+fn resolve_scoped_unit_id(
+    state: &AppState,
+    headers: &AxumHeaderMap,
+    unit_id: &str,
+) -> Result<String, Response> {
+    let cleaned = unit_id.trim();
+    if cleaned.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.param_required"),
+        ));
+    }
+    let units = state
+        .user_store
+        .list_org_units()
+        .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?;
+    let actor = resolve_admin_actor(state, headers, true, &units)?;
+    if !units.iter().any(|unit| unit.unit_id == cleaned) {
+        return Err(error_response(
+            StatusCode::NOT_FOUND,
+            i18n::t("error.org_unit_not_found"),
+        ));
+    }
+    ensure_unit_scope(&actor, Some(cleaned))?;
+    Ok(cleaned.to_string())
+}
+
+fn org_unit_skills_payload(state: &AppState, unit_id: &str) -> Value {
+    let store = &state.user_tool_store;
+    json!({
+        "unit_id": unit_id,
+        "skills": store.load_org_unit_skills(unit_id),
+        "available": store.list_org_unit_skill_candidates(unit_id),
+        "skill_root": store.get_org_unit_skill_root(unit_id).to_string_lossy(),
+    })
+}
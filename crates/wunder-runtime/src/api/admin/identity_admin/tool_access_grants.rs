@@ -0,0 +1,104 @@
+// 用户工具授权增减：在现有白名单上追加或移除指定工具，工具名需在该用户可用工具中存在。
+use crate::api::admin::{ensure_user_scope, error_response, resolve_admin_actor};
+use crate::i18n;
+use crate::state::AppState;
+use crate::user_access::{self, ToolAccessUpdate};
+use axum::extract::{Path as AxumPath, State};
+use axum::http::{HeaderMap as AxumHeaderMap, StatusCode};
+use axum::response::Response;
+use axum::{routing::post, Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+pub(super) fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/wunder/admin/user_accounts/{user_id}/tool_access/grant",
+            post(admin_user_accounts_tool_access_grant),
+        )
+        .route(
+            "/wunder/admin/user_accounts/{user_id}/tool_access/revoke",
+            post(admin_user_accounts_tool_access_revoke),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolAccessChangeRequest {
+    #[serde(default)]
+    tools: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ToolAccessChange {
+    Grant,
+    Revoke,
+}
+
+async fn admin_user_accounts_tool_access_grant(
+    State(state): State<Arc<AppState>>,
+    headers: AxumHeaderMap,
+    AxumPath(user_id): AxumPath<String>,
+    Json(payload): Json<ToolAccessChangeRequest>,
+) -> Result<Json<Value>, Response> {
+    apply_tool_access_change(&state, &headers, &user_id, payload, ToolAccessChange::Grant).await
+}
+
+async fn admin_user_accounts_tool_access_revoke(
+    State(state): State<Arc<AppState>>,
+    headers: AxumHeaderMap,
+    AxumPath(user_id): AxumPath<String>,
+    Json(payload): Json<ToolAccessChangeRequest>,
+) -> Result<Json<Value>, Response> {
+    apply_tool_access_change(
+        &state,
+        &headers,
+        &user_id,
+        payload,
+        ToolAccessChange::Revoke,
+    )
+    .await
+}
+
+async fn apply_tool_access_change(
+    state: &AppState,
+    headers: &AxumHeaderMap,
+    user_id: &str,
+    payload: ToolAccessChangeRequest,
+    change: ToolAccessChange,
+) -> Result<Json<Value>, Response> {
+    let cleaned = user_id.trim();
+    if cleaned.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.user_id_required"),
+        ));
+    }
+    let record = state
+        .user_store
+        .get_user_by_id(cleaned)
+        .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, i18n::t("error.user_not_found")))?;
+    let units = state
+        .user_store
+        .list_org_units()
+        .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?;
+    let actor = resolve_admin_actor(state, headers, true, &units)?;
+    ensure_user_scope(&actor, &record)?;
+    let available = user_access::available_tool_names_for_user(state, cleaned).await;
+    let result = match change {
+        ToolAccessChange::Grant => {
+            user_access::grant_tools(&state.user_store, cleaned, &payload.tools, &available)
+        }
+        ToolAccessChange::Revoke => {
+            user_access::revoke_tools(&state.user_store, cleaned, &payload.tools, &available)
+        }
+    };
+    let ToolAccessUpdate {
+        allowed_tools,
+        changed,
+    } = result.map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?;
+    Ok(Json(json!({
+        "data": { "allowed_tools": allowed_tools, "changed": changed }
+    })))
+}
@@ -8,6 +8,7 @@ use crate::lsp::{LspDiagnostic, LspManager};
 use crate::path_utils::{
     is_within_root, normalize_existing_path, normalize_path_for_compare, normalize_target_path,
 };
+use crate::services::a2a_agent_card::build_a2a_agent_card_urls;
 use crate::services::admin_skills::{
     build_admin_skill_scan_paths, collect_admin_reserved_skill_top_dirs,
     normalize_admin_skill_paths, resolve_admin_custom_skills_root,
@@ -1685,59 +1686,6 @@ fn apply_auth_headers(mut headers: HeaderMap, auth: Option<Value>) -> Result<Hea
     Ok(headers)
 }
 
-fn build_a2a_agent_card_urls(endpoint: &str) -> Result<Vec<String>, String> {
-    let cleaned = endpoint.trim();
-    if cleaned.is_empty() {
-        return Err(i18n::t("tool.a2a.endpoint_required"));
-    }
-    let normalized = if cleaned.starts_with("http://") || cleaned.starts_with("https://") {
-        cleaned.to_string()
-    } else {
-        format!("http://{cleaned}")
-    };
-    let parsed = Url::parse(&normalized).map_err(|err| format!("A2A endpoint 解析失败: {err}"))?;
-
-    let mut base_url = parsed.clone();
-    base_url.set_path("");
-    base_url.set_query(None);
-    base_url.set_fragment(None);
-    let base = base_url.as_str().trim_end_matches('/').to_string();
-    let endpoint_base = parsed.as_str().trim_end_matches('/').to_string();
-
-    let path = parsed.path().trim_end_matches('/');
-    let mut base_path = String::new();
-    if !path.is_empty() && path != "/" {
-        base_path = path.to_string();
-        if base_path.ends_with("/a2a") {
-            base_path.truncate(base_path.len().saturating_sub(4));
-        }
-        base_path = base_path.trim_end_matches('/').to_string();
-    }
-
-    let mut urls = Vec::new();
-    let mut seen = HashSet::new();
-    let mut push = |url: String| {
-        if !url.is_empty() && seen.insert(url.clone()) {
-            urls.push(url);
-        }
-    };
-
-    push(format!("{base}/.well-known/agent-card.json"));
-    if !base_path.is_empty() {
-        push(format!("{base}{base_path}/.well-known/agent-card.json"));
-    }
-    push(format!("{endpoint_base}/extendedAgentCard"));
-    push(format!("{endpoint_base}/agentCard"));
-    push(format!("{base}/a2a/extendedAgentCard"));
-    push(format!("{base}/a2a/agentCard"));
-    if !base_path.is_empty() {
-        push(format!("{base}{base_path}/a2a/extendedAgentCard"));
-        push(format!("{base}{base_path}/a2a/agentCard"));
-    }
-
-    Ok(urls)
-}
-
 #[derive(Debug, Deserialize)]
 struct McpUpdateRequest {
     servers: Vec<McpServerConfig>,
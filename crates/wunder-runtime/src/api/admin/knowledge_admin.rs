@@ -677,19 +677,17 @@ async fn admin_knowledge_test_stream(
                     .first()
                     .ok_or_else(|| anyhow!(i18n::t("error.llm_request_failed")))?;
                 let root = vector_knowledge::resolve_vector_root(None, &base.name, false)?;
-                let mut hits = vector_knowledge::query_chunks_by_vector(
+                let mut hits = vector_knowledge::query_chunks(
                     storage.as_ref(),
                     None,
                     &base,
                     &root,
                     embedding_name,
                     vector,
+                    &query,
                     effective_top_k,
                 )
                 .await?;
-                if let Some(threshold) = base.score_threshold {
-                    hits.retain(|hit| hit.score.unwrap_or(0.0) >= f64::from(threshold));
-                }
                 if hits.len() > effective_top_k {
                     hits.truncate(effective_top_k);
                 }
@@ -846,20 +844,18 @@ async fn admin_knowledge_test(
             .filter(|value| *value > 0)
             .unwrap_or_else(|| vector_knowledge::resolve_top_k(&base));
         let root = resolve_vector_root_for_admin(&base, false)?;
-        let mut hits = vector_knowledge::query_chunks_by_vector(
+        let mut hits = vector_knowledge::query_chunks(
             state.storage.as_ref(),
             None,
             &base,
             &root,
             embedding_name,
             vector,
+            query,
             top_k,
         )
         .await
         .map_err(vector_error_response)?;
-        if let Some(threshold) = base.score_threshold {
-            hits.retain(|hit| hit.score.unwrap_or(0.0) >= f64::from(threshold));
-        }
         if hits.len() > top_k {
             hits.truncate(top_k);
         }
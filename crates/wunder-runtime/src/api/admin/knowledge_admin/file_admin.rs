@@ -329,6 +329,8 @@ async fn refresh_literal_knowledge_cache(base: &KnowledgeBaseConfig, root: &Path
         chunk_overlap: base.chunk_overlap,
         top_k: base.top_k,
         score_threshold: base.score_threshold,
+        search_mode: base.search_mode.clone(),
+        keyword_weight: base.keyword_weight,
     })
     .await;
 }
@@ -1,6 +1,6 @@
 use crate::api::admin::{error_response, now_ts, resolve_monitor_session_agent_name};
 use crate::config::Config;
-use crate::core::runtime_metrics;
+use crate::core::{blocking, runtime_metrics};
 use crate::i18n;
 use crate::performance::{
     run_sample as run_performance_sample, PerformanceSampleRequest, PerformanceSampleResponse,
@@ -39,6 +39,7 @@ pub(super) fn router() -> Router<Arc<AppState>> {
             "/wunder/admin/monitor/logs/cleanup",
             post(admin_monitor_logs_cleanup),
         )
+        .route("/wunder/admin/storage/vacuum", post(admin_storage_vacuum))
         .route(
             "/wunder/admin/monitor/{session_id}",
             get(admin_monitor_detail).delete(admin_monitor_delete),
@@ -678,6 +679,36 @@ async fn admin_monitor_logs_cleanup(
     })))
 }
 
+/// Compact the database. VACUUM holds the write lock for its whole run, so it is refused while
+/// sessions are active unless `force=true`.
+async fn admin_storage_vacuum(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<StorageVacuumQuery>,
+) -> Result<Json<Value>, Response> {
+    let active_sessions = state.monitor.list_sessions(true).len();
+    if active_sessions > 0 && !query.force {
+        return Err(error_response(
+            StatusCode::CONFLICT,
+            i18n::t("error.storage_vacuum_busy"),
+        ));
+    }
+    let storage = state.storage.clone();
+    let started = Instant::now();
+    let report = blocking::run_db("api.admin.storage_vacuum", move || {
+        storage.compact_storage()
+    })
+    .await
+    .map_err(|err| error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    info!(
+        backend = %report.backend,
+        reclaimed_bytes = report.reclaimed_bytes,
+        active_sessions,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "admin storage vacuum finished"
+    );
+    Ok(Json(json!({ "ok": true, "data": report })))
+}
+
 async fn admin_throughput_start(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<ThroughputStartRequest>,
@@ -924,3 +955,9 @@ struct MonitorLogsCleanupRequest {
     start_time: Option<f64>,
     end_time: Option<f64>,
 }
+
+#[derive(Debug, Deserialize)]
+struct StorageVacuumQuery {
+    #[serde(default)]
+    force: bool,
+}
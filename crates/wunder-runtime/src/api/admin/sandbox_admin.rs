@@ -0,0 +1,65 @@
+// 管理端沙盒容器维护：按用户与容器编号重置沙盒工作区，容器上仍有活跃会话时返回冲突。
+use crate::api::admin::error_response;
+use crate::core::blocking;
+use crate::i18n;
+use crate::services::sandbox_reset::{reset_sandbox_container, SandboxResetOutcome};
+use crate::state::AppState;
+use crate::storage::normalize_sandbox_container_id;
+use axum::extract::{Path as AxumPath, State};
+use axum::http::StatusCode;
+use axum::response::Response;
+use axum::{routing::post, Json, Router};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const SANDBOX_CONTAINER_BUSY_CODE: &str = "SANDBOX_CONTAINER_BUSY";
+
+pub(super) fn router() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/wunder/admin/users/{user_id}/sandboxes/{container_id}/reset",
+        post(admin_sandbox_reset),
+    )
+}
+
+async fn admin_sandbox_reset(
+    State(state): State<Arc<AppState>>,
+    AxumPath((user_id, container_id)): AxumPath<(String, i32)>,
+) -> Result<Json<Value>, Response> {
+    let user_id = user_id.trim().to_string();
+    if user_id.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.user_id_required"),
+        ));
+    }
+    if normalize_sandbox_container_id(container_id) != container_id {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.sandbox_container_invalid"),
+        ));
+    }
+    let outcome = blocking::run_fs("admin.sandbox_reset", move || {
+        reset_sandbox_container(
+            &state.workspace,
+            state.storage.as_ref(),
+            Some(state.monitor.as_ref()),
+            &user_id,
+            container_id,
+        )
+    })
+    .await
+    .map_err(|err| error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    match outcome {
+        SandboxResetOutcome::Reset(report) => Ok(Json(json!({ "data": report }))),
+        SandboxResetOutcome::Busy {
+            container_id,
+            session_ids,
+        } => Err(crate::api::errors::error_response_with_detail(
+            StatusCode::CONFLICT,
+            Some(SANDBOX_CONTAINER_BUSY_CODE),
+            i18n::t("error.sandbox_container_busy"),
+            Some(i18n::t("error.sandbox_container_busy_hint").as_str()),
+            Some(json!({ "container_id": container_id, "session_ids": session_ids })),
+        )),
+    }
+}
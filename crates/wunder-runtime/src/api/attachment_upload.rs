@@ -0,0 +1,183 @@
+// 分块附件上传接口：init 建立上传、chunk 以原始字节按偏移续传、complete 校验 SHA-256 后返回可放入 attachments 的附件 id。
+use crate::api::errors::{error_response, error_response_with_detail};
+use crate::api::user_context::resolve_user;
+use crate::services::attachment_upload::{
+    AttachmentUploadError, AttachmentUploadStore, UploadSession, MAX_UPLOAD_CHUNK_BYTES,
+    MAX_UPLOAD_TOTAL_BYTES, PARTIAL_UPLOAD_TTL,
+};
+use crate::state::AppState;
+use axum::extract::{DefaultBodyLimit, Path as AxumPath, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use bytes::Bytes;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const UPLOAD_OFFSET_MISMATCH_CODE: &str = "UPLOAD_OFFSET_MISMATCH";
+const UPLOAD_INCOMPLETE_CODE: &str = "UPLOAD_INCOMPLETE";
+const UPLOAD_HASH_MISMATCH_CODE: &str = "UPLOAD_HASH_MISMATCH";
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/wunder/attachments/init", post(init_upload))
+        .route(
+            "/wunder/attachments/chunk",
+            post(upload_chunk).layer(DefaultBodyLimit::max(MAX_UPLOAD_CHUNK_BYTES)),
+        )
+        .route("/wunder/attachments/complete", post(complete_upload))
+        .route(
+            "/wunder/attachments/uploads/{upload_id}",
+            get(upload_status),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+struct InitUploadRequest {
+    name: String,
+    #[serde(alias = "totalBytes", alias = "total_bytes")]
+    size: u64,
+    #[serde(default, alias = "contentType", alias = "mime_type")]
+    content_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkQuery {
+    #[serde(alias = "uploadId")]
+    upload_id: String,
+    offset: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompleteUploadRequest {
+    #[serde(alias = "uploadId")]
+    upload_id: String,
+    sha256: String,
+}
+
+async fn init_upload(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(payload): Json<InitUploadRequest>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let session = AttachmentUploadStore::shared()
+        .init(
+            &resolved.user.user_id,
+            &payload.name,
+            payload.content_type.as_deref(),
+            payload.size,
+        )
+        .await
+        .map_err(upload_error_response)?;
+    Ok(Json(json!({ "data": session_payload(&session, 0) })))
+}
+
+/// The chunk body is the raw bytes; `upload_id` and `offset` ride in the query string.
+async fn upload_chunk(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ChunkQuery>,
+    body: Bytes,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let received = AttachmentUploadStore::shared()
+        .append_chunk(
+            &resolved.user.user_id,
+            query.upload_id.trim(),
+            query.offset,
+            &body,
+        )
+        .await
+        .map_err(upload_error_response)?;
+    Ok(Json(json!({
+        "data": {
+            "upload_id": query.upload_id.trim(),
+            "received_bytes": received,
+        }
+    })))
+}
+
+async fn upload_status(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(upload_id): AxumPath<String>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let (session, received) = AttachmentUploadStore::shared()
+        .status(&resolved.user.user_id, upload_id.trim())
+        .await
+        .map_err(upload_error_response)?;
+    Ok(Json(json!({ "data": session_payload(&session, received) })))
+}
+
+async fn complete_upload(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(payload): Json<CompleteUploadRequest>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let attachment = AttachmentUploadStore::shared()
+        .complete(
+            &resolved.user.user_id,
+            payload.upload_id.trim(),
+            &payload.sha256,
+        )
+        .await
+        .map_err(upload_error_response)?;
+    Ok(Json(json!({
+        "data": {
+            "attachment_id": attachment.attachment_id,
+            "name": attachment.name,
+            "content_type": attachment.content_type,
+            "size": attachment.size,
+            "sha256": attachment.sha256,
+            "attachment": attachment.to_payload(),
+        }
+    })))
+}
+
+fn session_payload(session: &UploadSession, received: u64) -> Value {
+    json!({
+        "upload_id": session.upload_id,
+        "name": session.name,
+        "content_type": session.content_type,
+        "total_bytes": session.total_bytes,
+        "received_bytes": received,
+        "chunk_size": MAX_UPLOAD_CHUNK_BYTES,
+        "max_total_bytes": MAX_UPLOAD_TOTAL_BYTES,
+        "ttl_s": PARTIAL_UPLOAD_TTL.as_secs(),
+    })
+}
+
+fn upload_error_response(err: AttachmentUploadError) -> Response {
+    let message = err.to_string();
+    match err {
+        AttachmentUploadError::Invalid(_) => error_response(StatusCode::BAD_REQUEST, message),
+        AttachmentUploadError::NotFound => error_response(StatusCode::NOT_FOUND, message),
+        AttachmentUploadError::OffsetMismatch { expected } => error_response_with_detail(
+            StatusCode::CONFLICT,
+            Some(UPLOAD_OFFSET_MISMATCH_CODE),
+            message,
+            Some("Resume the upload from expected_offset."),
+            Some(json!({ "expected_offset": expected })),
+        ),
+        AttachmentUploadError::Incomplete { received, total } => error_response_with_detail(
+            StatusCode::CONFLICT,
+            Some(UPLOAD_INCOMPLETE_CODE),
+            message,
+            Some("Send the remaining chunks before completing."),
+            Some(json!({ "received_bytes": received, "total_bytes": total })),
+        ),
+        AttachmentUploadError::HashMismatch { expected, actual } => error_response_with_detail(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Some(UPLOAD_HASH_MISMATCH_CODE),
+            message,
+            Some("Re-send the chunks from offset 0, then complete again."),
+            Some(json!({ "expected_sha256": expected, "actual_sha256": actual })),
+        ),
+        AttachmentUploadError::Io(_) => error_response(StatusCode::INTERNAL_SERVER_ERROR, message),
+    }
+}
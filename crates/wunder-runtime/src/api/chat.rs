@@ -12,6 +12,7 @@ use crate::services::orchestration_context::{
     ORCHESTRATION_THREAD_LOCKED_CODE,
 };
 use crate::services::runtime::thread::ThreadSubmitOutcome;
+use crate::services::session_title::{is_placeholder_title, truncate_session_title};
 use crate::services::subagents;
 use crate::state::AppState;
 use crate::user_access::{build_user_tool_context, compute_allowed_tool_names, is_agent_allowed};
@@ -36,11 +37,16 @@ const CHAT_SESSION_STATUS_ARCHIVED: &str = "archived";
 const ORCHESTRATION_SOURCE_HEADER: &str = "x-wunder-orchestration-source";
 pub(crate) const ORCHESTRATION_SOURCE_ALLOW: &str = "beeroom_orchestration";
 
+mod approvals;
 mod events;
+mod export;
 mod media;
+mod messages;
 mod prompt;
 mod sessions;
+mod tool_calls;
 
+use messages::clear_session_messages;
 use sessions::{
     has_active_queue_task, is_session_runtime_active, is_session_stream_active,
     is_session_stream_active_or_queued,
@@ -48,10 +54,13 @@ use sessions::{
 
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
+        .merge(approvals::router())
         .merge(events::router())
+        .merge(export::router())
         .merge(media::router())
         .merge(prompt::router())
         .merge(sessions::router())
+        .merge(tool_calls::router())
         .route(
             "/wunder/chat/sessions/{session_id}/subagents",
             get(list_session_subagents),
@@ -66,7 +75,7 @@ pub fn router() -> Router<Arc<AppState>> {
         )
         .route(
             "/wunder/chat/sessions/{session_id}/messages",
-            post(send_message),
+            post(send_message).delete(clear_session_messages),
         )
         .route(
             "/wunder/chat/sessions/{session_id}/cancel",
@@ -88,6 +97,10 @@ struct SendMessageRequest {
     #[serde(default, alias = "debugPayload", alias = "debug_payload")]
     debug_payload: bool,
     #[serde(default)]
+    cite: bool,
+    #[serde(default, alias = "rememberAttachments")]
+    remember_attachments: bool,
+    #[serde(default)]
     attachments: Option<Vec<ChatAttachment>>,
     #[serde(default)]
     tool_call_mode: Option<String>,
@@ -99,12 +112,17 @@ struct SendMessageRequest {
         alias = "permission_level"
     )]
     approval_mode: Option<String>,
+    /// Opt-in: park approvals for the HTTP approvals endpoints instead of denying them at once.
+    #[serde(default, alias = "httpApprovals")]
+    http_approvals: bool,
 }
 
 pub(crate) struct ChatRequestOverrides {
     pub(crate) tool_call_mode: Option<String>,
     pub(crate) approval_mode: Option<String>,
     pub(crate) debug_payload: bool,
+    pub(crate) cite: bool,
+    pub(crate) remember_attachments: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -117,6 +135,8 @@ pub(crate) struct ChatAttachment {
     mime_type: Option<String>,
     #[serde(default, alias = "publicPath")]
     public_path: Option<String>,
+    #[serde(default, alias = "attachmentId")]
+    attachment_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -289,7 +309,7 @@ async fn send_message(
     } else if !allow_orchestration_send {
         reject_locked_orchestration_session(state.as_ref(), &resolved.user.user_id, &session_id)?;
     }
-    let request = build_chat_request(
+    let mut request = build_chat_request(
         &state,
         &resolved.user,
         &session_id,
@@ -301,6 +321,8 @@ async fn send_message(
             tool_call_mode: payload.tool_call_mode,
             approval_mode: payload.approval_mode,
             debug_payload: payload.debug_payload,
+            cite: payload.cite,
+            remember_attachments: payload.remember_attachments,
         },
     )
     .await?;
@@ -314,6 +336,15 @@ async fn send_message(
             }),
         ));
     }
+    if payload.http_approvals {
+        let approval_timeout_s = state.config_store.get().await.security.approval_timeout_s;
+        request.approval_tx = Some(approvals::spawn_http_approval_forwarder(
+            state.control.approval_registry.clone(),
+            resolved.user.user_id.clone(),
+            session_id.clone(),
+            std::time::Duration::from_secs(approval_timeout_s),
+        ));
+    }
     let outcome = state
         .kernel
         .thread_runtime
@@ -382,6 +413,10 @@ fn has_non_empty_chat_attachments(attachments: Option<&[ChatAttachment]>) -> boo
                         .as_ref()
                         .map(|value| !value.trim().is_empty())
                         .unwrap_or(false)
+                    || item
+                        .attachment_id
+                        .as_ref()
+                        .is_some_and(|value| !value.trim().is_empty())
             })
         })
         .unwrap_or(false)
@@ -482,8 +517,8 @@ pub(crate) async fn build_chat_request(
         })
         .unwrap_or(false);
 
-    if is_first_user_message && should_auto_title(&record.title) {
-        if let Some(title) = build_session_title(&content) {
+    if is_first_user_message && is_placeholder_title(&record.title) {
+        if let Some(title) = truncate_session_title(&content) {
             let _ =
                 state
                     .user_store
@@ -507,12 +542,19 @@ pub(crate) async fn build_chat_request(
                     .as_ref()
                     .map(|value| !value.trim().is_empty())
                     .unwrap_or(false)
+                || item
+                    .attachment_id
+                    .as_ref()
+                    .map(|value| !value.trim().is_empty())
+                    .unwrap_or(false)
         })
         .map(|item| AttachmentPayload {
             name: item.name,
             content: item.content,
             content_type: item.mime_type,
             public_path: item.public_path,
+            attachment_id: item.attachment_id,
+            metadata: None,
         })
         .collect::<Vec<_>>();
     let attachments = if attachments.is_empty() {
@@ -576,6 +618,9 @@ pub(crate) async fn build_chat_request(
         agent_prompt,
         preview_skill,
         attachments,
+        max_rounds: None,
+        cite: request_overrides.cite,
+        remember_attachments: request_overrides.remember_attachments,
         allow_queue: true,
         is_admin: UserStore::is_admin(user),
         enforce_runtime_queue: true,
@@ -1147,24 +1192,6 @@ fn resolve_override_name_with_allowed(raw: &str, allowed: &HashSet<String>) -> O
     None
 }
 
-fn should_auto_title(title: &str) -> bool {
-    let cleaned = title.trim();
-    cleaned.is_empty() || cleaned == "新会话" || cleaned == "未命名会话"
-}
-
-fn build_session_title(content: &str) -> Option<String> {
-    let cleaned = content.trim().replace('\n', " ");
-    if cleaned.is_empty() {
-        return None;
-    }
-    let mut output = cleaned;
-    if output.chars().count() > 20 {
-        output = output.chars().take(20).collect::<String>();
-        output.push_str("...");
-    }
-    Some(output)
-}
-
 fn format_ts(ts: f64) -> String {
     let millis = (ts * 1000.0) as i64;
     DateTime::<Utc>::from_timestamp_millis(millis)
@@ -1230,6 +1257,7 @@ mod tests {
             content: Some("   ".to_string()),
             mime_type: Some("image/png".to_string()),
             public_path: Some("users/u1/heart.png".to_string()),
+            attachment_id: None,
         }];
         assert!(has_non_empty_chat_attachments(Some(&attachments)));
     }
@@ -1241,6 +1269,7 @@ mod tests {
             content: Some("   ".to_string()),
             mime_type: Some("text/plain".to_string()),
             public_path: Some("   ".to_string()),
+            attachment_id: None,
         }];
         assert!(!has_non_empty_chat_attachments(Some(&attachments)));
     }
@@ -0,0 +1,413 @@
+// HTTP 审批通道：长轮询获取会话待审批请求，POST 提交 once/session/deny，超时自动拒绝。
+use super::error_response;
+use crate::api::chat_ws::parse_approval_decision;
+use crate::api::user_context::resolve_user;
+use crate::core::approval::{
+    new_channel as new_approval_channel, ApprovalRequestTx, ApprovalResponse,
+};
+use crate::core::approval_registry::{
+    ApprovalSource, PendingApprovalEntry, PendingApprovalRegistry, PendingApprovalSnapshot,
+};
+use crate::i18n;
+use crate::state::AppState;
+use axum::extract::{Path as AxumPath, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use axum::{routing::get, routing::post, Json, Router};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+use uuid::Uuid;
+
+const APPROVAL_LONG_POLL_MAX_MS: u64 = 30_000;
+
+pub(super) fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/wunder/chat/sessions/{session_id}/approvals",
+            get(list_session_approvals),
+        )
+        .route(
+            "/wunder/chat/approvals/{approval_id}",
+            post(resolve_approval),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionApprovalsQuery {
+    #[serde(default, alias = "waitMs")]
+    wait_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResolveApprovalRequest {
+    decision: String,
+}
+
+/// Give an HTTP-driven run an approval channel. Requests land in the shared registry as
+/// `ApprovalSource::Http` entries until the client answers or `timeout` auto-denies them.
+pub(super) fn spawn_http_approval_forwarder(
+    registry: Arc<PendingApprovalRegistry>,
+    user_id: String,
+    session_id: String,
+    timeout: Duration,
+) -> ApprovalRequestTx {
+    let (approval_tx, mut approval_rx) = new_approval_channel();
+    let request_id = Uuid::new_v4().simple().to_string();
+    tokio::spawn(async move {
+        while let Some(request) = approval_rx.recv().await {
+            let approval_id = request.id.trim().to_string();
+            if approval_id.is_empty() {
+                let _ = request.respond_to.send(ApprovalResponse::Deny);
+                continue;
+            }
+            let previous = registry
+                .upsert(PendingApprovalEntry {
+                    approval_id: approval_id.clone(),
+                    source: ApprovalSource::Http,
+                    session_id: session_id.clone(),
+                    request_id: Some(request_id.clone()),
+                    channel: None,
+                    account_id: None,
+                    peer_id: None,
+                    thread_id: None,
+                    actor_id: Some(user_id.clone()),
+                    tool: request.tool,
+                    summary: request.summary,
+                    kind: request.kind,
+                    created_at: Utc::now().timestamp_millis() as f64 / 1000.0,
+                    respond_to: request.respond_to,
+                })
+                .await;
+            if let Some(previous) = previous {
+                let _ = previous.respond_to.send(ApprovalResponse::Deny);
+            }
+            let registry = registry.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(timeout).await;
+                if let Some(entry) = registry.remove(&approval_id).await {
+                    info!(
+                        "approval {approval_id} of session {} timed out, denying",
+                        entry.session_id
+                    );
+                    let _ = entry.respond_to.send(ApprovalResponse::Deny);
+                }
+            });
+        }
+        // The run dropped its sender, so nothing is waiting on what it left behind.
+        let leftovers = registry
+            .remove_matching(|entry| {
+                entry.source == ApprovalSource::Http
+                    && entry.request_id.as_deref() == Some(request_id.as_str())
+            })
+            .await;
+        for entry in leftovers {
+            let _ = entry.respond_to.send(ApprovalResponse::Deny);
+        }
+    });
+    approval_tx
+}
+
+/// Pending approvals of a session. With `wait_ms` the call long-polls until one shows up;
+/// entries stay registered until answered, so a client that reconnects simply polls again.
+async fn list_session_approvals(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(session_id): AxumPath<String>,
+    Query(query): Query<SessionApprovalsQuery>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let user_id = resolved.user.user_id;
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.content_required"),
+        ));
+    }
+    let _record = state
+        .user_store
+        .get_chat_session(&user_id, &session_id)
+        .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, i18n::t("error.session_not_found")))?;
+    let wait = Duration::from_millis(query.wait_ms.unwrap_or(0).min(APPROVAL_LONG_POLL_MAX_MS));
+    let registry = &state.control.approval_registry;
+    let matches = |entry: &PendingApprovalSnapshot| {
+        entry.source == ApprovalSource::Http
+            && entry.session_id == session_id
+            && entry.actor_id.as_deref() == Some(user_id.as_str())
+    };
+    let mut approvals = if wait.is_zero() {
+        registry.find_snapshots(matches).await
+    } else {
+        registry.wait_for_snapshots(matches, wait).await
+    };
+    approvals.sort_by(|left, right| left.created_at.total_cmp(&right.created_at));
+    let timeout_s = state.config_store.get().await.security.approval_timeout_s;
+    let items = approvals
+        .iter()
+        .map(|entry| {
+            json!({
+                "approval_id": entry.approval_id,
+                "tool": entry.tool,
+                "summary": entry.summary,
+                "kind": entry.kind,
+                "created_at": entry.created_at,
+                "expires_at": entry.created_at + timeout_s as f64,
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(Json(json!({
+        "data": {
+            "session_id": session_id,
+            "approvals": items,
+            "timeout_s": timeout_s,
+        }
+    })))
+}
+
+async fn resolve_approval(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(approval_id): AxumPath<String>,
+    Json(payload): Json<ResolveApprovalRequest>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let approval_id = approval_id.trim().to_string();
+    let Some(response) = parse_approval_decision(&payload.decision) else {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid decision, expected once/session/deny".to_string(),
+        ));
+    };
+    let registry = &state.control.approval_registry;
+    let owned = registry
+        .get_snapshot(&approval_id)
+        .await
+        .is_some_and(|snapshot| {
+            snapshot.source == ApprovalSource::Http
+                && snapshot.actor_id.as_deref() == Some(resolved.user.user_id.as_str())
+        });
+    // Re-check on removal: the timeout task may have taken the entry in between.
+    let entry = if owned {
+        registry.remove(&approval_id).await
+    } else {
+        None
+    };
+    let Some(entry) = entry else {
+        return Err(error_response(
+            StatusCode::NOT_FOUND,
+            "approval request not found".to_string(),
+        ));
+    };
+    let session_id = entry.session_id.clone();
+    let _ = entry.respond_to.send(response);
+    Ok(Json(json!({
+        "data": {
+            "approval_id": approval_id,
+            "session_id": session_id,
+            "decision": match response {
+                ApprovalResponse::ApproveOnce => "once",
+                ApprovalResponse::ApproveSession => "session",
+                ApprovalResponse::Deny => "deny",
+            },
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::config_store::ConfigStore;
+    use crate::core::approval::{ApprovalRequest, ApprovalRequestKind};
+    use crate::state::AppStateInitOptions;
+    use crate::storage::ChatSessionRecord;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{header::AUTHORIZATION, Method, Request};
+    use tower::ServiceExt;
+
+    struct TestContext {
+        app: Router,
+        state: Arc<AppState>,
+        token: String,
+        user_id: String,
+        _temp_dir: tempfile::TempDir,
+    }
+
+    async fn build_test_context() -> TestContext {
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let mut config = Config::default();
+        config.storage.backend = "sqlite".to_string();
+        config.storage.db_path = temp_dir
+            .path()
+            .join("chat-approvals.db")
+            .to_string_lossy()
+            .to_string();
+        config.workspace.root = temp_dir
+            .path()
+            .join("workspaces")
+            .to_string_lossy()
+            .to_string();
+        let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+        let config_for_store = config.clone();
+        config_store
+            .update(|current| *current = config_for_store.clone())
+            .await
+            .expect("update config store");
+        let state = Arc::new(
+            AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+                .expect("create app state"),
+        );
+        let user = state
+            .user_store
+            .create_user(
+                "approver",
+                Some("approver@example.test".to_string()),
+                "password-123",
+                Some("A"),
+                None,
+                vec!["user".to_string()],
+                "active",
+                false,
+            )
+            .expect("create user");
+        let token = state
+            .user_store
+            .create_session_token(&user.user_id)
+            .expect("create token")
+            .token;
+        let now = Utc::now().timestamp_millis() as f64 / 1000.0;
+        state
+            .user_store
+            .upsert_chat_session(&ChatSessionRecord {
+                session_id: "sess_http".to_string(),
+                user_id: user.user_id.clone(),
+                title: "session".to_string(),
+                status: "active".to_string(),
+                created_at: now,
+                updated_at: now,
+                last_message_at: now,
+                agent_id: None,
+                tool_overrides: Vec::new(),
+                parent_session_id: None,
+                parent_message_id: None,
+                spawn_label: None,
+                spawned_by: None,
+            })
+            .expect("create session");
+        TestContext {
+            app: Router::new().merge(router()).with_state(state.clone()),
+            state,
+            token,
+            user_id: user.user_id,
+            _temp_dir: temp_dir,
+        }
+    }
+
+    async fn send(app: &Router, token: &str, method: Method, path: &str, body: Value) -> Value {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(path)
+                    .header(AUTHORIZATION, format!("Bearer {token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("build request"),
+            )
+            .await
+            .expect("send request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        serde_json::from_slice(&bytes).expect("parse json")
+    }
+
+    fn request_approval(
+        tx: &ApprovalRequestTx,
+        approval_id: &str,
+    ) -> tokio::sync::oneshot::Receiver<ApprovalResponse> {
+        let (respond_to, response_rx) = tokio::sync::oneshot::channel();
+        tx.send(ApprovalRequest {
+            id: approval_id.to_string(),
+            kind: ApprovalRequestKind::Exec,
+            tool: "execute_command".to_string(),
+            args: json!({ "content": "ls" }),
+            summary: "run ls".to_string(),
+            detail: Value::Null,
+            respond_to,
+        })
+        .expect("send approval request");
+        response_rx
+    }
+
+    #[tokio::test]
+    async fn approval_is_delivered_and_answered_over_http() {
+        let context = build_test_context().await;
+        let tx = spawn_http_approval_forwarder(
+            context.state.control.approval_registry.clone(),
+            context.user_id.clone(),
+            "sess_http".to_string(),
+            Duration::from_secs(60),
+        );
+        let response_rx = request_approval(&tx, "appr_http");
+
+        let listed = send(
+            &context.app,
+            &context.token,
+            Method::GET,
+            "/wunder/chat/sessions/sess_http/approvals?wait_ms=5000",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(
+            listed["data"]["approvals"][0]["approval_id"],
+            json!("appr_http")
+        );
+
+        let resolved = send(
+            &context.app,
+            &context.token,
+            Method::POST,
+            "/wunder/chat/approvals/appr_http",
+            json!({ "decision": "session" }),
+        )
+        .await;
+        assert_eq!(resolved["data"]["decision"], json!("session"));
+        assert_eq!(
+            response_rx.await.expect("approval response"),
+            ApprovalResponse::ApproveSession
+        );
+    }
+
+    #[tokio::test]
+    async fn unanswered_approval_is_denied_after_timeout() {
+        let context = build_test_context().await;
+        let tx = spawn_http_approval_forwarder(
+            context.state.control.approval_registry.clone(),
+            context.user_id.clone(),
+            "sess_http".to_string(),
+            Duration::from_millis(50),
+        );
+        let response_rx = request_approval(&tx, "appr_timeout");
+        assert_eq!(
+            response_rx.await.expect("approval response"),
+            ApprovalResponse::Deny
+        );
+        let listed = send(
+            &context.app,
+            &context.token,
+            Method::GET,
+            "/wunder/chat/sessions/sess_http/approvals",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(listed["data"]["approvals"], json!([]));
+    }
+}
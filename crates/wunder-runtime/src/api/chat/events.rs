@@ -572,6 +572,7 @@ fn is_workflow_event(event_type: &str) -> bool {
             | "thread_control"
             | "llm_output_delta"
             | "llm_output"
+            | "reasoning"
             | "context_usage"
             | "token_balance"
             | "quota_usage"
@@ -0,0 +1,85 @@
+// 会话导出：按 OpenAI chat messages 结构导出会话历史，便于在其他工具中回放。
+use super::error_response;
+use crate::api::user_context::resolve_user;
+use crate::history_export::{
+    export_session_openai_messages, ExportAttachmentMode, OPENAI_EXPORT_FORMAT,
+};
+use crate::i18n;
+use crate::state::AppState;
+use axum::extract::{Path as AxumPath, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use axum::{routing::get, Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub(super) fn router() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/wunder/chat/sessions/{session_id}/export",
+        get(export_session),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionExportQuery {
+    #[serde(default)]
+    format: Option<String>,
+    /// Embed attachment text and images instead of referencing their public paths.
+    #[serde(default, alias = "inlineAttachments")]
+    inline_attachments: bool,
+}
+
+async fn export_session(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(session_id): AxumPath<String>,
+    Query(query): Query<SessionExportQuery>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let user_id = resolved.user.user_id.clone();
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.param_required"),
+        ));
+    }
+    let format = query
+        .format
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(OPENAI_EXPORT_FORMAT)
+        .to_ascii_lowercase();
+    if format != OPENAI_EXPORT_FORMAT {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t_with_params(
+                "error.export_format_unsupported",
+                &HashMap::from([("format".to_string(), format)]),
+            ),
+        ));
+    }
+    state
+        .user_store
+        .get_chat_session(&user_id, &session_id)
+        .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, i18n::t("error.session_not_found")))?;
+    let mode = if query.inline_attachments {
+        ExportAttachmentMode::Inline
+    } else {
+        ExportAttachmentMode::Reference
+    };
+    let messages = export_session_openai_messages(&state.workspace, &user_id, &session_id, mode)
+        .await
+        .map_err(|err| error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    Ok(Json(json!({
+        "data": {
+            "id": session_id,
+            "format": format,
+            "messages": messages,
+        }
+    })))
+}
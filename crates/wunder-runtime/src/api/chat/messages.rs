@@ -0,0 +1,153 @@
+// 会话消息维护：清空整段历史（保留会话记录），或按 history id 删除/改写单条消息。
+use super::sessions::is_session_stream_active_or_queued;
+use super::{error_response, reject_locked_orchestration_session};
+use crate::api::user_context::resolve_user;
+use crate::i18n;
+use crate::services::chat_history_edit::{
+    delete_history_message, edit_history_message, MessageEditOutcome,
+};
+use crate::state::AppState;
+use axum::extract::{Path as AxumPath, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use axum::Json;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub(super) struct DeleteMessageQuery {
+    /// Deleting a user message also removes the replies of that turn.
+    #[serde(default)]
+    cascade: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct EditMessageRequest {
+    content: String,
+}
+
+/// Clear a session's conversation while keeping the session record and its settings.
+pub(super) async fn clear_session_messages(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(session_id): AxumPath<String>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.param_required"),
+        ));
+    }
+    ensure_session_idle(&state, &resolved.user.user_id, &session_id)?;
+    let deleted = state
+        .workspace
+        .clear_session_messages(&resolved.user.user_id, &session_id)
+        .await
+        .map_err(|err| error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    Ok(Json(json!({
+        "data": { "id": session_id, "deleted_messages": deleted }
+    })))
+}
+
+pub(super) async fn delete_session_message(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath((session_id, history_id)): AxumPath<(String, i64)>,
+    Query(query): Query<DeleteMessageQuery>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() || history_id <= 0 {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.param_required"),
+        ));
+    }
+    ensure_session_idle(&state, &resolved.user.user_id, &session_id)?;
+    let deleted = delete_history_message(
+        &state.workspace,
+        &resolved.user.user_id,
+        &session_id,
+        history_id,
+        query.cascade,
+    )
+    .await
+    .map_err(|err| error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+    .ok_or_else(|| error_response(StatusCode::NOT_FOUND, i18n::t("error.message_not_found")))?;
+    Ok(Json(json!({
+        "data": { "id": session_id, "history_id": history_id, "deleted_history_ids": deleted }
+    })))
+}
+
+pub(super) async fn edit_session_message(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath((session_id, history_id)): AxumPath<(String, i64)>,
+    Json(payload): Json<EditMessageRequest>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() || history_id <= 0 {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.param_required"),
+        ));
+    }
+    if payload.content.trim().is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.content_required"),
+        ));
+    }
+    ensure_session_idle(&state, &resolved.user.user_id, &session_id)?;
+    let outcome = edit_history_message(
+        &state.workspace,
+        &resolved.user.user_id,
+        &session_id,
+        history_id,
+        &payload.content,
+    )
+    .await
+    .map_err(|err| error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    match outcome {
+        MessageEditOutcome::Updated => Ok(Json(json!({
+            "data": { "id": session_id, "history_id": history_id, "content": payload.content }
+        }))),
+        MessageEditOutcome::NotFound => Err(error_response(
+            StatusCode::NOT_FOUND,
+            i18n::t("error.message_not_found"),
+        )),
+        MessageEditOutcome::NotEditable => Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.message_not_editable"),
+        )),
+    }
+}
+
+/// History may only change while the session exists, is not locked by an orchestration and has
+/// no running or queued turn that would append to it concurrently.
+fn ensure_session_idle(state: &AppState, user_id: &str, session_id: &str) -> Result<(), Response> {
+    state
+        .user_store
+        .get_chat_session(user_id, session_id)
+        .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, i18n::t("error.session_not_found")))?;
+    reject_locked_orchestration_session(state, user_id, session_id)?;
+    let monitor_status = state.monitor.get_record(session_id).and_then(|record| {
+        record
+            .get("status")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+    });
+    if is_session_stream_active_or_queued(&state.user_store, monitor_status.as_deref(), session_id)
+    {
+        return Err(error_response(
+            StatusCode::CONFLICT,
+            i18n::t("error.session_not_found_or_running"),
+        ));
+    }
+    Ok(())
+}
@@ -83,6 +83,7 @@ async fn system_prompt(
             UserStore::is_admin(&resolved.user),
             &workspace_id,
             None,
+            None,
             agent_prompt.as_deref(),
             preview_skill,
         )
@@ -213,6 +214,7 @@ async fn session_system_prompt(
             UserStore::is_admin(&resolved.user),
             &workspace_id,
             None,
+            None,
             agent_prompt.as_deref(),
             preview_skill,
         )
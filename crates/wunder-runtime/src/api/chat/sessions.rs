@@ -1,3 +1,4 @@
+use super::messages::{delete_session_message, edit_session_message};
 use super::{
     error_response, fetch_agent_record, format_ts, now_ts, reject_locked_orchestration_session,
     resolve_chat_model_name, CHAT_SESSION_STATUS_ACTIVE, CHAT_SESSION_STATUS_ARCHIVED,
@@ -88,7 +89,9 @@ pub(super) fn router() -> Router<Arc<AppState>> {
         )
         .route(
             "/wunder/chat/sessions/{session_id}/messages/{history_id}",
-            get(get_session_message),
+            get(get_session_message)
+                .delete(delete_session_message)
+                .patch(edit_session_message),
         )
 }
 
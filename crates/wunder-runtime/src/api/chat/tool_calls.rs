@@ -0,0 +1,90 @@
+// 会话工具调用记录：从会话历史中只取出工具调用及其结果，支持按工具名过滤与分页。
+use super::error_response;
+use crate::api::user_context::resolve_user;
+use crate::i18n;
+use crate::services::history::HistoryManager;
+use crate::state::AppState;
+use axum::extract::{Path as AxumPath, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use axum::{routing::get, Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const DEFAULT_TOOL_CALL_PAGE_SIZE: usize = 50;
+const MAX_TOOL_CALL_PAGE_SIZE: usize = 200;
+
+// `/tools` already serves the session tool overrides, hence `/tool-calls`.
+pub(super) fn router() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/wunder/chat/sessions/{session_id}/tool-calls",
+        get(list_session_tool_calls),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionToolCallsQuery {
+    #[serde(default, alias = "tool_name", alias = "toolName")]
+    tool: Option<String>,
+    #[serde(default)]
+    offset: Option<usize>,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// Tool calls of a session paired with their results, oldest first.
+async fn list_session_tool_calls(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(session_id): AxumPath<String>,
+    Query(query): Query<SessionToolCallsQuery>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let user_id = resolved.user.user_id.clone();
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            i18n::t("error.param_required"),
+        ));
+    }
+    state
+        .user_store
+        .get_chat_session(&user_id, &session_id)
+        .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, i18n::t("error.session_not_found")))?;
+    let history = state
+        .workspace
+        .load_history_async(&user_id, &session_id, 0)
+        .await
+        .map_err(|err| error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let tool = query
+        .tool
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let interactions = HistoryManager::collect_tool_interactions(&history, tool);
+    let total = interactions.len();
+    let offset = query.offset.unwrap_or(0);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_TOOL_CALL_PAGE_SIZE)
+        .clamp(1, MAX_TOOL_CALL_PAGE_SIZE);
+    let items = interactions
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .collect::<Vec<_>>();
+    Ok(Json(json!({
+        "data": {
+            "id": session_id,
+            "tool": tool,
+            "items": items,
+            "total": total,
+            "offset": offset,
+            "limit": limit,
+            "has_more": offset.saturating_add(limit) < total,
+        }
+    })))
+}
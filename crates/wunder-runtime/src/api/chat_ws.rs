@@ -58,6 +58,10 @@ struct WsStartPayload {
     #[serde(default, alias = "debugPayload", alias = "debug_payload")]
     debug_payload: bool,
     #[serde(default)]
+    cite: bool,
+    #[serde(default, alias = "rememberAttachments")]
+    remember_attachments: bool,
+    #[serde(default)]
     attachments: Option<Vec<ChatAttachment>>,
     #[serde(default)]
     tool_call_mode: Option<String>,
@@ -453,6 +457,8 @@ async fn handle_ws(
                                 tool_call_mode: payload.tool_call_mode,
                                 approval_mode: payload.approval_mode,
                                 debug_payload: payload.debug_payload,
+                                cite: payload.cite,
+                                remember_attachments: payload.remember_attachments,
                             },
                         )
                         .await
@@ -1247,7 +1253,7 @@ async fn forward_approval_requests(
     }
 }
 
-fn parse_approval_decision(raw: &str) -> Option<ApprovalResponse> {
+pub(crate) fn parse_approval_decision(raw: &str) -> Option<ApprovalResponse> {
     let cleaned = raw.trim().to_ascii_lowercase();
     match cleaned.as_str() {
         "approve_once" | "once" | "approve-once" => Some(ApprovalResponse::ApproveOnce),
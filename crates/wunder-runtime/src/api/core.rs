@@ -1,21 +1,29 @@
-// 核心 API：/wunder 入口、系统提示词、工具清单与 i18n 配置。
+// 核心 API：/wunder 入口、系统提示词、工具清单、i18n 配置与请求 JSON Schema。
 use crate::api::attachment_convert::{build_ok_conversion_payload, convert_multipart_list};
-use crate::api::user_context::resolve_user;
+use crate::api::request_validation::{
+    field_errors_payload, privileged_overrides_payload, validate_model_and_overrides,
+    validate_wunder_request,
+};
+use crate::api::user_context::{has_valid_api_key, resolve_user};
+use crate::core::blocking;
+use crate::core::config_override_policy::check_config_overrides;
 use crate::core::json_schema::normalize_tool_input_schema;
 use crate::i18n;
-use crate::orchestrator::OrchestratorError;
+use crate::orchestrator::{apply_config_overrides, OrchestratorError};
 use crate::schemas::{
-    AvailableToolsResponse, I18nConfigResponse, ToolSpec, WunderPromptRequest,
-    WunderPromptResponse, WunderRequest,
+    wunder_request_json_schema, AvailableToolsResponse, I18nConfigResponse, ToolSpec,
+    WunderPromptRequest, WunderPromptResponse, WunderRequest,
 };
 use crate::services::abilities::populate_ability_items;
 use crate::services::runtime::thread::ThreadSubmitOutcome;
+use crate::services::sandbox_session::load_session_container_id;
 use crate::skills::load_skills;
 use crate::state::AppState;
 use crate::tools::{
     a2a_service_schema, build_mcp_tool_alias_entries_for_names, builtin_tool_specs,
     mcp_pack_runtime_name, mcp_pack_schema, mcp_pack_spec_for_server,
 };
+use crate::user_access::{compute_allowed_tool_names, prepare_user_token_status, UserToolContext};
 use crate::user_store::UserStore;
 use crate::user_tools::{UserMcpServer, UserToolStore, UserToolsPayload};
 use anyhow::Error;
@@ -27,19 +35,25 @@ use axum::{routing::get, routing::post, Json, Router};
 use chrono::Utc;
 use serde::Deserialize;
 use serde_json::{json, Value};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio_stream::StreamExt;
 use tracing::error;
 
 const MAX_ATTACHMENT_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+const TOOL_OVERRIDE_NONE: &str = "__no_tools__";
 
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/wunder", post(wunder_entry))
         .route("/wunder/system_prompt", post(wunder_system_prompt))
+        .route(
+            "/wunder/system_prompt/preview",
+            post(wunder_system_prompt_preview),
+        )
         .route("/wunder/tools", get(wunder_tools))
         .route("/wunder/i18n", get(wunder_i18n))
+        .route("/wunder/schemas/request", get(wunder_request_schema))
         .route(
             "/wunder/attachments/convert",
             post(wunder_attachment_convert)
@@ -52,10 +66,42 @@ async fn wunder_entry(
     headers: HeaderMap,
     Json(mut request): Json<WunderRequest>,
 ) -> Result<Response, Response> {
-    let _ = request.config_overrides.as_ref();
-    let _ = request.attachments.as_ref();
     let resolved = resolve_user(&state, &headers, Some(&request.user_id)).await?;
+    // Reject malformed requests with per-field messages before anything is queued or run.
+    let config = state.config_store.get().await;
+    let field_errors = validate_wunder_request(&request, &config);
+    if !field_errors.is_empty() {
+        return Err(orchestrator_error_response(
+            StatusCode::BAD_REQUEST,
+            field_errors_payload(&field_errors),
+        ));
+    }
     request.is_admin = UserStore::is_admin(&resolved.user);
+    let elevated = request.is_admin || has_valid_api_key(&config, &headers);
+    if let Err(paths) = check_config_overrides(&config, request.config_overrides.as_ref(), elevated)
+    {
+        return Err(orchestrator_error_response(
+            StatusCode::FORBIDDEN,
+            privileged_overrides_payload(&paths),
+        ));
+    }
+    // Over-quota users are turned away here so nothing is queued that could only fail later.
+    if !request.is_admin {
+        let storage = state.storage.clone();
+        let user_id = request.user_id.clone();
+        let status = blocking::run_db("api.core.check_quota", move || {
+            prepare_user_token_status(storage.as_ref(), &user_id)
+        })
+        .await
+        .map_err(map_orchestrator_error)?;
+        if let Some(status) = status.filter(|status| !status.allowed) {
+            let err = OrchestratorError::user_token_insufficient(status);
+            return Err(orchestrator_error_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                err.to_payload(),
+            ));
+        }
+    }
     request.enforce_runtime_queue = true;
     if request
         .language
@@ -185,6 +231,7 @@ async fn wunder_system_prompt(
             false,
             &workspace_id,
             request.config_overrides.as_ref(),
+            None,
             request.agent_prompt.as_deref(),
             request.preview_skill,
         )
@@ -196,6 +243,174 @@ async fn wunder_system_prompt(
     }))
 }
 
+/// Requested tools limited to what the user may call; no request means every allowed tool. An
+/// empty result becomes the no-tools marker, since an empty list would mean all tools again.
+fn preview_tool_names(requested: &[String], allowed: &HashSet<String>) -> Vec<String> {
+    let mut names = if requested.is_empty() {
+        allowed.iter().cloned().collect::<Vec<_>>()
+    } else {
+        requested
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| allowed.contains(*name))
+            .map(ToString::to_string)
+            .collect()
+    };
+    if names.is_empty() {
+        return vec![TOOL_OVERRIDE_NONE.to_string()];
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Chat request fields that shape the system prompt; `question` and delivery options are not
+/// needed since no turn runs.
+#[derive(Debug, Deserialize)]
+struct SystemPromptPreviewRequest {
+    #[serde(default)]
+    user_id: String,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    agent_id: Option<String>,
+    #[serde(default, alias = "workspaceContainerId")]
+    workspace_container_id: Option<i32>,
+    #[serde(default)]
+    model_name: Option<String>,
+    #[serde(default)]
+    tool_names: Vec<String>,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    config_overrides: Option<Value>,
+    #[serde(default)]
+    agent_prompt: Option<String>,
+    #[serde(default)]
+    preview_skill: bool,
+}
+
+async fn wunder_system_prompt_preview(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<SystemPromptPreviewRequest>,
+) -> Result<Json<WunderPromptResponse>, Response> {
+    let resolved = resolve_user(&state, &headers, Some(&request.user_id)).await?;
+    let user_id = resolved.user.user_id.clone();
+    let base_config = state.config_store.get().await;
+    let field_errors = validate_model_and_overrides(
+        request.model_name.as_deref(),
+        request.config_overrides.as_ref(),
+        &base_config,
+    );
+    if !field_errors.is_empty() {
+        return Err(orchestrator_error_response(
+            StatusCode::BAD_REQUEST,
+            field_errors_payload(&field_errors),
+        ));
+    }
+    let is_admin = UserStore::is_admin(&resolved.user);
+    let elevated = is_admin || has_valid_api_key(&base_config, &headers);
+    if let Err(paths) =
+        check_config_overrides(&base_config, request.config_overrides.as_ref(), elevated)
+    {
+        return Err(orchestrator_error_response(
+            StatusCode::FORBIDDEN,
+            privileged_overrides_payload(&paths),
+        ));
+    }
+    let language = request
+        .language
+        .clone()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(i18n::get_language);
+    // Same config, skills and workspace a turn of this request would see; overrides a turn
+    // could not apply are an error here too rather than a preview of the base config.
+    let config = match request.config_overrides.as_ref() {
+        Some(overrides) => apply_config_overrides(&base_config, overrides).map_err(|err| {
+            orchestrator_error_response(
+                StatusCode::BAD_REQUEST,
+                json!({
+                    "code": "INVALID_REQUEST",
+                    "message": i18n::t_with_params(
+                        "error.config_overrides_invalid",
+                        &HashMap::from([("detail".to_string(), err.to_string())]),
+                    ),
+                }),
+            )
+        })?,
+        None => base_config.clone(),
+    };
+    let skills_snapshot = if request.config_overrides.is_some() {
+        let scan_config = config.clone();
+        blocking::run_fs("api.core.preview_skills", move || {
+            Ok(load_skills(&scan_config, true, true, true))
+        })
+        .await
+        .map_err(map_orchestrator_error)?
+    } else {
+        state.skills.read().await.clone()
+    };
+    let start = Utc::now();
+    let prompt = i18n::with_language(language, async {
+        let user_tool_bindings =
+            state
+                .user_tool_manager
+                .build_bindings(&config, &skills_snapshot, &user_id);
+        let user_context = UserToolContext {
+            config,
+            skills: skills_snapshot,
+            bindings: user_tool_bindings,
+            tool_access: state
+                .user_store
+                .get_user_tool_access(&user_id)
+                .unwrap_or(None),
+            org_units: state.user_store.list_org_units().unwrap_or_default(),
+        };
+        let tool_names = preview_tool_names(
+            &request.tool_names,
+            &compute_allowed_tool_names(&resolved.user, &user_context),
+        );
+        // Read the session's sandbox slot without assigning one: a preview must not claim it.
+        let container_id = request.workspace_container_id.or_else(|| {
+            request.session_id.as_deref().and_then(|session_id| {
+                load_session_container_id(state.storage.as_ref(), &user_id, session_id)
+                    .ok()
+                    .flatten()
+            })
+        });
+        let workspace_id = state.kernel.orchestrator.resolve_workspace_id(
+            &user_id,
+            request.agent_id.as_deref(),
+            container_id,
+        );
+        state
+            .kernel
+            .orchestrator
+            .build_system_prompt(
+                &user_context.config,
+                &tool_names,
+                &user_context.skills,
+                Some(&user_context.bindings),
+                &user_id,
+                request.agent_id.as_deref(),
+                is_admin,
+                &workspace_id,
+                request.config_overrides.as_ref(),
+                request.model_name.as_deref(),
+                request.agent_prompt.as_deref(),
+                request.preview_skill,
+            )
+            .await
+    })
+    .await;
+    let elapsed = Utc::now() - start;
+    Ok(Json(WunderPromptResponse {
+        prompt,
+        build_time_ms: elapsed.num_milliseconds() as f64,
+    }))
+}
+
 async fn wunder_tools(
     State(state): State<Arc<AppState>>,
     Query(params): Query<ToolsQuery>,
@@ -755,6 +970,11 @@ async fn wunder_i18n(
     }))
 }
 
+// Not an admin route (see wunder_core::auth): any authenticated integrator may read it.
+async fn wunder_request_schema() -> Json<Value> {
+    Json(wunder_request_json_schema())
+}
+
 async fn wunder_attachment_convert(multipart: Multipart) -> Result<Json<Value>, Response> {
     let conversions = convert_multipart_list(multipart).await?;
     Ok(Json(build_ok_conversion_payload(conversions)))
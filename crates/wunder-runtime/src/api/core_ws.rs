@@ -1,4 +1,4 @@
-use crate::api::user_context::resolve_user;
+use crate::api::user_context::{has_valid_api_key, resolve_user};
 use crate::api::ws_helpers::{
     apply_ws_auth_headers, has_ws_protocol_token, negotiate_ws_protocol, parse_connect_payload,
     parse_payload, resolve_session_id, resume_stream_events, send_ws_error, send_ws_error_payload,
@@ -10,6 +10,7 @@ use crate::api::ws_log::{
     log_ws_close, log_ws_handshake, log_ws_handshake_error, log_ws_message, log_ws_open,
     log_ws_parse_error, log_ws_ready, WsConnMeta,
 };
+use crate::core::config_override_policy::check_config_overrides;
 use crate::core::long_task;
 use crate::i18n;
 use crate::orchestrator_constants::STREAM_EVENT_QUEUE_SIZE;
@@ -104,6 +105,9 @@ async fn wunder_ws(
 ) -> Result<Response, Response> {
     let auth_headers = apply_ws_auth_headers(&headers, &query);
     let resolved = resolve_user(&state, &auth_headers, query.user_id.as_deref()).await?;
+    // Elevation is fixed at upgrade time: the API key only travels with the handshake.
+    let elevated = UserStore::is_admin(&resolved.user)
+        || has_valid_api_key(&state.config_store.get().await, &auth_headers);
     let has_protocol_token = has_ws_protocol_token(&headers);
     let conn_meta = WsConnMeta::from_headers(&headers, has_protocol_token);
     let connection_id = format!("ws_{}", Uuid::new_v4().simple());
@@ -117,6 +121,7 @@ async fn wunder_ws(
                 socket,
                 state,
                 resolved.user,
+                elevated,
                 session_scope,
                 connection_id,
                 conn_meta,
@@ -128,6 +133,7 @@ async fn handle_ws(
     socket: WebSocket,
     state: Arc<AppState>,
     user: crate::storage::UserAccountRecord,
+    elevated: bool,
     session_scope: Option<String>,
     connection_id: String,
     conn_meta: WsConnMeta,
@@ -391,6 +397,25 @@ async fn handle_ws(
                             Some(&request_id),
                             session_id.as_deref(),
                         );
+                        let config = state.config_store.get().await;
+                        if let Err(paths) = check_config_overrides(
+                            &config,
+                            payload.config_overrides.as_ref(),
+                            elevated,
+                        ) {
+                            let message = i18n::t_with_params(
+                                "error.config_overrides_privileged",
+                                &HashMap::from([("paths".to_string(), paths.join(", "))]),
+                            );
+                            let _ = send_ws_error(
+                                &ws_tx,
+                                Some(&request_id),
+                                "PERMISSION_DENIED",
+                                message,
+                            )
+                            .await;
+                            continue;
+                        }
                         let stream = true;
                         let request = WunderRequest {
                             user_id,
@@ -409,6 +434,9 @@ async fn handle_ws(
                             agent_prompt: payload.agent_prompt,
                             preview_skill: payload.preview_skill,
                             attachments: payload.attachments,
+                            max_rounds: None,
+                            cite: false,
+                            remember_attachments: false,
                             allow_queue: true,
                             is_admin: UserStore::is_admin(&user),
                             enforce_runtime_queue: true,
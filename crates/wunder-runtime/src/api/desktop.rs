@@ -126,6 +126,9 @@ struct DesktopSettingsFile {
     llm: Option<LlmConfig>,
     #[serde(default)]
     lan_mesh: desktop_lan::DesktopLanMeshSettings,
+    // Owned by the desktop shell updater; kept here so settings saves do not drop it.
+    #[serde(default)]
+    update_channel: String,
     updated_at: f64,
 }
 
@@ -144,6 +147,7 @@ impl Default for DesktopSettingsFile {
             language: String::new(),
             llm: None,
             lan_mesh: desktop_lan::DesktopLanMeshSettings::default(),
+            update_channel: String::new(),
             updated_at: now_ts(),
         }
     }
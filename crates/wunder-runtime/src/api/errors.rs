@@ -1,4 +1,4 @@
-use axum::http::header::{HeaderName, HeaderValue};
+use axum::http::header::{HeaderName, HeaderValue, RETRY_AFTER};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -147,6 +147,7 @@ pub fn error_response_with_detail(
     detail: Option<Value>,
 ) -> Response {
     let meta = build_error_meta(status, code, message, hint);
+    let retry_after_s = detail.as_ref().and_then(retry_after_seconds);
     let detail = build_detail_payload(&meta.message, detail);
     let payload = json!({
         "ok": false,
@@ -165,9 +166,23 @@ pub fn error_response_with_detail(
             .headers_mut()
             .insert(HeaderName::from_static(ERROR_CODE_HEADER), value);
     }
+    if let Some(seconds) = retry_after_s {
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(seconds));
+    }
     response
 }
 
+/// `Retry-After` seconds from an orchestrator payload's `error_meta.retry_after_ms`, rounded up.
+fn retry_after_seconds(detail: &Value) -> Option<u64> {
+    let retry_after_ms = detail
+        .get("error_meta")
+        .and_then(|meta| meta.get("retry_after_ms"))
+        .and_then(Value::as_u64)?;
+    Some(retry_after_ms.div_ceil(1000).max(1))
+}
+
 fn build_detail_payload(message: &str, detail: Option<Value>) -> Value {
     match detail {
         Some(Value::Object(mut map)) => {
@@ -299,6 +314,27 @@ mod tests {
         assert_eq!(payload["detail"]["message"], json!("session is busy"));
     }
 
+    #[test]
+    fn retry_after_header_is_rounded_up_from_error_meta() {
+        let response = error_response_with_detail(
+            StatusCode::TOO_MANY_REQUESTS,
+            Some("USER_TOKEN_INSUFFICIENT"),
+            "quota exhausted",
+            None,
+            Some(json!({ "error_meta": { "retry_after_ms": 1500 } })),
+        );
+        assert_eq!(
+            response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok()),
+            Some("2")
+        );
+
+        let without_meta = error_response(StatusCode::TOO_MANY_REQUESTS, "slow down");
+        assert!(without_meta.headers().get(RETRY_AFTER).is_none());
+    }
+
     #[test]
     fn status_mapping_for_custom_error_code_is_stable() {
         assert_eq!(
@@ -677,6 +677,9 @@ async fn build_wunder_request(
         agent_prompt,
         preview_skill: prepared.agent.preview_skill,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: false,
         is_admin: UserStore::is_admin(&prepared.user),
         enforce_runtime_queue: false,
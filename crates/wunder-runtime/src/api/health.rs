@@ -0,0 +1,66 @@
+// 健康检查：/healthz 只反映进程存活；/readyz 检查存储可读与配置已加载，未就绪时返回 503 及各项明细。
+use crate::core::blocking;
+use crate::state::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+const READINESS_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+/// Any meta key works; the probe only needs a round trip through the storage backend.
+const READINESS_PROBE_META_KEY: &str = "readiness_probe";
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+}
+
+async fn healthz() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+async fn readyz(State(state): State<Arc<AppState>>) -> Response {
+    let storage = check_storage(&state).await;
+    let config = check_config(&state).await;
+    let ready = storage["ok"].as_bool().unwrap_or(false) && config["ok"].as_bool().unwrap_or(false);
+    let (status, label) = if ready {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+    };
+    let body = json!({
+        "status": label,
+        "checks": { "storage": storage, "config": config },
+    });
+    (status, Json(body)).into_response()
+}
+
+async fn check_storage(state: &AppState) -> Value {
+    let storage = state.storage.clone();
+    let probe = blocking::run_db("api.health.readyz.storage", move || {
+        storage.get_meta(READINESS_PROBE_META_KEY)
+    });
+    match tokio::time::timeout(READINESS_CHECK_TIMEOUT, probe).await {
+        Ok(Ok(_)) => json!({ "ok": true }),
+        Ok(Err(err)) => json!({ "ok": false, "error": err.to_string() }),
+        Err(_) => json!({ "ok": false, "error": "storage check timed out" }),
+    }
+}
+
+/// The store always holds a parsed config once the state exists, so this only fails
+/// when a writer keeps the config lock past the timeout.
+async fn check_config(state: &AppState) -> Value {
+    match tokio::time::timeout(READINESS_CHECK_TIMEOUT, state.config_store.get()).await {
+        Ok(config) => json!({
+            "ok": true,
+            "version": state.config_store.version(),
+            "storage_backend": config.storage.backend,
+        }),
+        Err(_) => json!({ "ok": false, "error": "config lock timed out" }),
+    }
+}
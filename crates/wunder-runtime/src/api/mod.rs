@@ -6,6 +6,7 @@ pub mod admin_prompt_templates;
 pub mod admin_sim_lab;
 pub mod admin_swarm;
 pub(crate) mod attachment_convert;
+pub mod attachment_upload;
 pub mod auth;
 pub mod beeroom;
 pub mod beeroom_demo;
@@ -30,7 +31,10 @@ pub mod errors;
 pub mod external_links;
 pub mod external_workflows;
 pub mod gateway_ws;
+pub mod health;
 pub mod onlyoffice;
+pub mod performance_report;
+pub(crate) mod request_validation;
 pub(crate) mod skill_fs;
 pub mod team_runs;
 pub mod temp_dir;
@@ -42,6 +46,7 @@ pub mod user_memory;
 pub mod user_plaza;
 pub mod user_prompt_templates;
 pub mod user_tools;
+pub mod user_usage;
 pub mod user_world;
 pub mod user_world_ws;
 pub mod workspace;
@@ -55,6 +60,7 @@ use std::sync::Arc;
 pub fn build_router(state: Arc<AppState>) -> Router {
     Router::new()
         .merge(auth::router())
+        .merge(attachment_upload::router())
         .merge(beeroom::router())
         .merge(beeroom_reset::router())
         .merge(beeroom_demo::router())
@@ -72,9 +78,11 @@ pub fn build_router(state: Arc<AppState>) -> Router {
         .merge(doc2md::router())
         .merge(external_workflows::router())
         .merge(gateway_ws::router())
+        .merge(health::router())
         .merge(temp_dir::router())
         .merge(drawio::router())
         .merge(onlyoffice::router())
+        .merge(performance_report::router())
         .merge(workspace::router())
         .merge(admin::router())
         .merge(admin_bridge::router())
@@ -85,6 +93,7 @@ pub fn build_router(state: Arc<AppState>) -> Router {
         .merge(benchmark::router())
         .merge(external_links::router())
         .merge(user_tools::router())
+        .merge(user_usage::router())
         .merge(user_plaza::router())
         .merge(user_prompt_templates::router())
         .merge(user_world::router())
@@ -107,6 +116,7 @@ pub fn build_router(state: Arc<AppState>) -> Router {
 pub fn build_desktop_router(state: Arc<AppState>) -> Router {
     Router::new()
         .merge(auth::router())
+        .merge(attachment_upload::router())
         .merge(beeroom::router())
         .merge(beeroom_demo::router())
         .merge(beeroom_ws::router())
@@ -126,8 +136,10 @@ pub fn build_desktop_router(state: Arc<AppState>) -> Router {
         .merge(temp_dir::router())
         .merge(drawio::router())
         .merge(onlyoffice::router())
+        .merge(performance_report::router())
         .merge(workspace::router())
         .merge(user_tools::router())
+        .merge(user_usage::router())
         .merge(user_plaza::router())
         .merge(user_prompt_templates::router())
         .merge(user_world::router())
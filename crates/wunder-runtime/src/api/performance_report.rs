@@ -0,0 +1,87 @@
+// 工具耗时报表接口：按会话（或当前用户最近会话）汇总工具调用次数、平均/P95 耗时与失败率。
+use crate::api::user_context::resolve_user;
+use crate::core::blocking;
+use crate::i18n;
+use crate::state::AppState;
+use crate::tool_latency::{aggregate_tool_latencies, load_tool_result_events};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use axum::{routing::get, Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Without `session_id` the report covers this many of the user's most recent sessions.
+const REPORT_RECENT_SESSION_LIMIT: i64 = 50;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/wunder/performance/report", get(get_performance_report))
+}
+
+#[derive(Debug, Deserialize)]
+struct PerformanceReportQuery {
+    #[serde(default, alias = "sessionId")]
+    session_id: Option<String>,
+}
+
+async fn get_performance_report(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<PerformanceReportQuery>,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let user_id = resolved.user.user_id;
+    let session_id = query
+        .session_id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let session_ids = match session_id.as_deref() {
+        Some(session_id) => {
+            state
+                .user_store
+                .get_chat_session(&user_id, session_id)
+                .map_err(bad_request)?
+                .ok_or_else(|| {
+                    error_response(StatusCode::NOT_FOUND, i18n::t("error.session_not_found"))
+                })?;
+            vec![session_id.to_string()]
+        }
+        None => state
+            .user_store
+            .list_chat_sessions(&user_id, None, None, 0, REPORT_RECENT_SESSION_LIMIT)
+            .map_err(bad_request)?
+            .0
+            .into_iter()
+            .map(|record| record.session_id)
+            .collect(),
+    };
+    let storage = state.storage.clone();
+    let session_count = session_ids.len();
+    let events = blocking::run_db("api.performance_report.load_events", move || {
+        let mut events = Vec::new();
+        for session_id in &session_ids {
+            events.extend(load_tool_result_events(storage.as_ref(), session_id)?);
+        }
+        Ok(events)
+    })
+    .await
+    .map_err(bad_request)?;
+    let tools = aggregate_tool_latencies(&events);
+    Ok(Json(json!({
+        "data": {
+            "session_id": session_id,
+            "session_count": session_count,
+            "total_calls": tools.iter().map(|item| item.calls).sum::<u64>(),
+            "tools": tools,
+        }
+    })))
+}
+
+fn bad_request(err: impl ToString) -> Response {
+    error_response(StatusCode::BAD_REQUEST, err.to_string())
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    crate::api::errors::error_response(status, message)
+}
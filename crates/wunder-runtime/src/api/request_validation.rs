@@ -0,0 +1,336 @@
+// /wunder 请求入口校验：在进入编排器之前检查 WunderRequest 字段，并返回逐字段错误。
+use crate::config::Config;
+use crate::i18n;
+use crate::llm::is_llm_model;
+use crate::orchestrator::apply_config_overrides;
+use crate::orchestrator_constants::MAX_USER_INPUT_TEXT_CHARS;
+use crate::request_limits::measure_request_text_input_chars;
+use crate::schemas::{AttachmentPayload, WunderRequest};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: impl Into<String>, message: String) -> Self {
+        Self {
+            field: field.into(),
+            message,
+        }
+    }
+}
+
+/// Check the request invariants the orchestrator would otherwise discover mid-run. Only the
+/// HTTP boundary calls this; `Orchestrator::run`/`stream` keep their tolerant fallbacks.
+pub(crate) fn validate_wunder_request(request: &WunderRequest, config: &Config) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let attachments = request.attachments.as_deref().unwrap_or(&[]);
+
+    if request.question.trim().is_empty() && !attachments.iter().any(attachment_has_payload) {
+        errors.push(FieldError::new(
+            "question",
+            i18n::t("error.question_required"),
+        ));
+    }
+
+    errors.extend(validate_model_and_overrides(
+        request.model_name.as_deref(),
+        request.config_overrides.as_ref(),
+        config,
+    ));
+
+    if request.max_rounds == Some(0) {
+        errors.push(FieldError::new(
+            "max_rounds",
+            i18n::t("error.max_rounds_invalid"),
+        ));
+    }
+
+    let mut oversized_attachment = false;
+    for (index, attachment) in attachments.iter().enumerate() {
+        let actual_chars =
+            measure_request_text_input_chars("", Some(std::slice::from_ref(attachment)));
+        if actual_chars <= MAX_USER_INPUT_TEXT_CHARS {
+            continue;
+        }
+        oversized_attachment = true;
+        let name = attachment
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("-");
+        errors.push(FieldError::new(
+            format!("attachments[{index}].content"),
+            i18n::t_with_params(
+                "error.attachment_too_large",
+                &HashMap::from([
+                    ("name".to_string(), name.to_string()),
+                    (
+                        "max_chars".to_string(),
+                        MAX_USER_INPUT_TEXT_CHARS.to_string(),
+                    ),
+                    ("actual_chars".to_string(), actual_chars.to_string()),
+                ]),
+            ),
+        ));
+    }
+    // Each attachment may fit on its own while the combined input still exceeds the limit.
+    if !oversized_attachment {
+        let actual_chars = measure_request_text_input_chars(&request.question, Some(attachments));
+        if actual_chars > MAX_USER_INPUT_TEXT_CHARS {
+            errors.push(FieldError::new(
+                "question",
+                i18n::t_with_params(
+                    "error.user_input_too_long",
+                    &HashMap::from([
+                        (
+                            "max_chars".to_string(),
+                            MAX_USER_INPUT_TEXT_CHARS.to_string(),
+                        ),
+                        ("actual_chars".to_string(), actual_chars.to_string()),
+                    ]),
+                ),
+            ));
+        }
+    }
+    errors
+}
+
+/// A non-empty `model_name` must name a configured LLM model.
+pub(crate) fn validate_model_name(model_name: Option<&str>, config: &Config) -> Option<FieldError> {
+    let name = model_name
+        .map(str::trim)
+        .filter(|value| !value.is_empty())?;
+    let known = config.llm.models.get(name).is_some_and(is_llm_model);
+    (!known).then(|| {
+        FieldError::new(
+            "model_name",
+            i18n::t_with_params(
+                "error.llm_model_not_found",
+                &HashMap::from([("name".to_string(), name.to_string())]),
+            ),
+        )
+    })
+}
+
+/// Check `config_overrides` and then `model_name` against the config the turn would run with,
+/// so a model added by the overrides is accepted.
+pub(crate) fn validate_model_and_overrides(
+    model_name: Option<&str>,
+    overrides: Option<&Value>,
+    config: &Config,
+) -> Vec<FieldError> {
+    match effective_config(overrides, config) {
+        Ok(Some(effective)) => validate_model_name(model_name, &effective)
+            .into_iter()
+            .collect(),
+        Ok(None) => validate_model_name(model_name, config)
+            .into_iter()
+            .collect(),
+        Err(error) => validate_model_name(model_name, config)
+            .into_iter()
+            .chain(std::iter::once(error))
+            .collect(),
+    }
+}
+
+fn effective_config(
+    overrides: Option<&Value>,
+    config: &Config,
+) -> Result<Option<Config>, FieldError> {
+    match overrides {
+        None | Some(Value::Null) => Ok(None),
+        Some(overrides @ Value::Object(_)) => {
+            // The orchestrator silently drops overrides that break the config; surface it here.
+            apply_config_overrides(config, overrides)
+                .map(Some)
+                .map_err(|err| {
+                    FieldError::new(
+                        "config_overrides",
+                        i18n::t_with_params(
+                            "error.config_overrides_invalid",
+                            &HashMap::from([("detail".to_string(), err.to_string())]),
+                        ),
+                    )
+                })
+        }
+        Some(_) => Err(FieldError::new(
+            "config_overrides",
+            i18n::t("error.config_overrides_not_object"),
+        )),
+    }
+}
+
+/// Error payload for `orchestrator_error_response`: the summary message plus a `fields` list.
+pub(crate) fn field_errors_payload(errors: &[FieldError]) -> Value {
+    let fields = errors
+        .iter()
+        .map(|error| error.field.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    json!({
+        "code": "INVALID_REQUEST",
+        "message": i18n::t_with_params(
+            "error.request_fields_invalid",
+            &HashMap::from([("fields".to_string(), fields)]),
+        ),
+        "fields": errors,
+    })
+}
+
+/// Error payload for `config_overrides` that would relax security without elevation.
+pub(crate) fn privileged_overrides_payload(paths: &[String]) -> Value {
+    json!({
+        "code": "PERMISSION_DENIED",
+        "message": i18n::t_with_params(
+            "error.config_overrides_privileged",
+            &HashMap::from([("paths".to_string(), paths.join(", "))]),
+        ),
+        "paths": paths,
+    })
+}
+
+fn attachment_has_payload(attachment: &AttachmentPayload) -> bool {
+    let filled = |value: Option<&str>| value.is_some_and(|value| !value.trim().is_empty());
+    filled(attachment.content.as_deref())
+        || filled(attachment.public_path.as_deref())
+        || filled(attachment.attachment_id.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LlmModelConfig;
+
+    fn config_with_model(name: &str) -> Config {
+        let mut config = Config::default();
+        config
+            .llm
+            .models
+            .insert(name.to_string(), LlmModelConfig::default());
+        config
+    }
+
+    fn request(body: Value) -> WunderRequest {
+        serde_json::from_value(body).expect("valid request body")
+    }
+
+    #[test]
+    fn validate_wunder_request_rejects_empty_question_without_attachments() {
+        let config = config_with_model("alpha");
+        let errors = validate_wunder_request(
+            &request(json!({ "user_id": "u1", "question": "   " })),
+            &config,
+        );
+        assert_eq!(
+            errors,
+            vec![FieldError::new(
+                "question",
+                i18n::t("error.question_required")
+            )]
+        );
+
+        let with_attachment = request(json!({
+            "user_id": "u1",
+            "question": "",
+            "attachments": [{ "name": "a.txt", "content": "text" }],
+        }));
+        assert_eq!(
+            validate_wunder_request(&with_attachment, &config),
+            Vec::new()
+        );
+
+        let with_upload = request(json!({
+            "user_id": "u1",
+            "question": "",
+            "attachments": [{ "attachment_id": "0123456789abcdef0123456789abcdef" }],
+        }));
+        assert_eq!(validate_wunder_request(&with_upload, &config), Vec::new());
+    }
+
+    #[test]
+    fn validate_wunder_request_rejects_unknown_model() {
+        let config = config_with_model("alpha");
+        let errors = validate_wunder_request(
+            &request(json!({ "user_id": "u1", "question": "hi", "model_name": "beta" })),
+            &config,
+        );
+        assert_eq!(
+            errors,
+            vec![FieldError::new(
+                "model_name",
+                i18n::t_with_params(
+                    "error.llm_model_not_found",
+                    &HashMap::from([("name".to_string(), "beta".to_string())]),
+                ),
+            )]
+        );
+
+        let known = request(json!({ "user_id": "u1", "question": "hi", "model_name": "alpha" }));
+        assert_eq!(validate_wunder_request(&known, &config), Vec::new());
+    }
+
+    #[test]
+    fn validate_wunder_request_accepts_model_added_by_overrides() {
+        let config = config_with_model("alpha");
+        let overridden = request(json!({
+            "user_id": "u1",
+            "question": "hi",
+            "model_name": "beta",
+            "config_overrides": { "llm": { "models": { "beta": {} } } },
+        }));
+        assert_eq!(validate_wunder_request(&overridden, &config), Vec::new());
+    }
+
+    #[test]
+    fn validate_wunder_request_rejects_zero_max_rounds() {
+        let config = config_with_model("alpha");
+        let errors = validate_wunder_request(
+            &request(json!({ "user_id": "u1", "question": "hi", "maxRounds": 0 })),
+            &config,
+        );
+        assert_eq!(
+            errors,
+            vec![FieldError::new(
+                "max_rounds",
+                i18n::t("error.max_rounds_invalid")
+            )]
+        );
+
+        let limited = request(json!({ "user_id": "u1", "question": "hi", "max_rounds": 3 }));
+        assert_eq!(validate_wunder_request(&limited, &config), Vec::new());
+    }
+
+    #[test]
+    fn validate_wunder_request_checks_config_overrides_shape() {
+        let config = config_with_model("alpha");
+        let errors = validate_wunder_request(
+            &request(json!({ "user_id": "u1", "question": "hi", "config_overrides": ["x"] })),
+            &config,
+        );
+        assert_eq!(
+            errors,
+            vec![FieldError::new(
+                "config_overrides",
+                i18n::t("error.config_overrides_not_object")
+            )]
+        );
+
+        let mismatched = request(json!({
+            "user_id": "u1",
+            "question": "hi",
+            "config_overrides": { "llm": { "models": "oops" } },
+        }));
+        let fields = validate_wunder_request(&mismatched, &config)
+            .into_iter()
+            .map(|error| error.field)
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec!["config_overrides".to_string()]);
+    }
+}
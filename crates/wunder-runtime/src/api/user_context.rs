@@ -1,5 +1,6 @@
 use crate::api::errors::error_response_with_detail;
 use crate::auth as guard_auth;
+use crate::config::Config;
 use crate::core::blocking;
 use crate::i18n;
 use crate::state::AppState;
@@ -47,11 +48,7 @@ pub async fn resolve_user(
         .unwrap_or(false);
 
     let config = state.config_store.get().await;
-    let api_key_valid = config.api_key().as_ref().is_some_and(|expected| {
-        guard_auth::extract_api_key(headers)
-            .map(|value| value == *expected)
-            .unwrap_or(false)
-    });
+    let api_key_valid = has_valid_api_key(&config, headers);
 
     if let Some(requested) = requested {
         if let Some(user) = token_user.as_ref() {
@@ -113,6 +110,15 @@ pub async fn resolve_user(
     ))
 }
 
+/// True when the request carries the server API key (`x-api-key` or `Authorization`).
+pub fn has_valid_api_key(config: &Config, headers: &HeaderMap) -> bool {
+    config.api_key().as_ref().is_some_and(|expected| {
+        guard_auth::extract_api_key(headers)
+            .map(|value| value == *expected)
+            .unwrap_or(false)
+    })
+}
+
 fn requested_user_matches_token_scope(requested: &str, token_user_id: &str) -> bool {
     if requested == token_user_id {
         return true;
@@ -37,8 +37,10 @@ pub(super) async fn user_knowledge_get(
         .iter()
         .filter(|(_, model)| llm::is_embedding_model(model))
         .map(|(name, _)| name.clone())
+        .chain(config.embedding.models.keys().cloned())
         .collect::<Vec<_>>();
     embedding_models.sort();
+    embedding_models.dedup();
     let tts_models = crate::multimodal_models::list_tts_model_names(&config);
     let image_models = crate::multimodal_models::list_image_model_names(&config);
     Ok(Json(json!({
@@ -1589,6 +1591,8 @@ async fn prepare_user_ragflow_bases(
                     chunk_overlap: base.chunk_overlap,
                     top_k: base.top_k,
                     score_threshold: base.score_threshold,
+                    search_mode: None,
+                    keyword_weight: None,
                 };
                 let mut knowledge_base = knowledge_base;
                 knowledge_base.name = build_user_ragflow_dataset_name(user, &base.name);
@@ -1638,6 +1642,8 @@ async fn refresh_user_knowledge_cache(base: &str, root: &Path) {
         chunk_overlap: None,
         top_k: None,
         score_threshold: None,
+        search_mode: None,
+        keyword_weight: None,
     };
     let _ = knowledge::refresh_knowledge_cache(&config).await;
 }
@@ -1687,6 +1693,8 @@ fn build_user_knowledge_config(base: &UserKnowledgeBase, root: &Path) -> Knowled
         chunk_overlap: base.chunk_overlap,
         top_k: base.top_k,
         score_threshold: base.score_threshold,
+        search_mode: None,
+        keyword_weight: None,
     }
 }
 
@@ -1712,6 +1720,8 @@ fn build_user_ragflow_knowledge_config(base: &UserKnowledgeBase) -> KnowledgeBas
         chunk_overlap: base.chunk_overlap,
         top_k: base.top_k,
         score_threshold: base.score_threshold,
+        search_mode: None,
+        keyword_weight: None,
     }
 }
 
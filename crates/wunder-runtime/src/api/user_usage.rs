@@ -0,0 +1,30 @@
+// 当前用户额度接口：返回 Token 额度、已用、剩余、发放日期与是否仍可调用模型。
+use crate::api::user_context::resolve_user;
+use crate::core::blocking;
+use crate::state::AppState;
+use crate::user_access::load_user_quota_status;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use axum::{routing::get, Json, Router};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/wunder/auth/me/usage", get(get_my_usage))
+}
+
+async fn get_my_usage(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, Response> {
+    let resolved = resolve_user(&state, &headers, None).await?;
+    let user_id = resolved.user.user_id;
+    let state_for_db = state.clone();
+    let status = blocking::run_db("api.user_usage.load_quota", move || {
+        load_user_quota_status(&state_for_db, &user_id)
+    })
+    .await
+    .map_err(|err| crate::api::errors::error_response(StatusCode::BAD_REQUEST, err.to_string()))?;
+    Ok(Json(json!({ "data": status })))
+}
@@ -186,6 +186,9 @@ async fn run_one_request(
         agent_prompt: None,
         preview_skill: false,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: false,
         enforce_runtime_queue: false,
@@ -283,6 +283,9 @@ async fn main() -> Result<()> {
         agent_prompt: None,
         preview_skill: false,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: false,
         enforce_runtime_queue: false,
@@ -55,6 +55,8 @@ impl MediaProcessor {
                             content: Some(attachment.url.clone()),
                             content_type: attachment.mime.clone(),
                             public_path: None,
+                            attachment_id: None,
+                            metadata: None,
                         });
                     } else if let Some(text) = self.ocr_image(attachment).await.ok().flatten() {
                         extra_lines.push(format!("Image {}: {}", display_name(attachment), text));
@@ -1048,6 +1048,9 @@ impl ChannelHub {
             agent_prompt,
             preview_skill,
             attachments: None,
+            max_rounds: None,
+            cite: false,
+            remember_attachments: false,
             allow_queue: false,
             is_admin: false,
             enforce_runtime_queue: false,
@@ -1,11 +1,13 @@
 use crate::core::approval::{ApprovalRequestKind, ApprovalResponse};
 use std::collections::HashMap;
-use tokio::sync::{oneshot, Mutex};
+use std::time::Duration;
+use tokio::sync::{oneshot, Mutex, Notify};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ApprovalSource {
     ChatWs,
     Channel,
+    Http,
 }
 
 #[derive(Debug)]
@@ -66,6 +68,7 @@ impl PendingApprovalEntry {
 #[derive(Default)]
 pub struct PendingApprovalRegistry {
     inner: Mutex<HashMap<String, PendingApprovalEntry>>,
+    added: Notify,
 }
 
 impl PendingApprovalRegistry {
@@ -76,7 +79,9 @@ impl PendingApprovalRegistry {
     pub async fn upsert(&self, entry: PendingApprovalEntry) -> Option<PendingApprovalEntry> {
         let approval_id = entry.approval_id.clone();
         let mut guard = self.inner.lock().await;
-        guard.insert(approval_id, entry)
+        let previous = guard.insert(approval_id, entry);
+        self.added.notify_waiters();
+        previous
     }
 
     pub async fn remove(&self, approval_id: &str) -> Option<PendingApprovalEntry> {
@@ -101,6 +106,33 @@ impl PendingApprovalRegistry {
             .collect()
     }
 
+    /// Like `find_snapshots`, but when nothing matches yet waits up to `timeout` for a matching
+    /// entry to be registered. Backs the long-poll approval endpoint.
+    pub async fn wait_for_snapshots<F>(
+        &self,
+        predicate: F,
+        timeout: Duration,
+    ) -> Vec<PendingApprovalSnapshot>
+    where
+        F: Fn(&PendingApprovalSnapshot) -> bool,
+    {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            // Register interest before checking so an upsert between the check and the wait
+            // still wakes us up.
+            let added = self.added.notified();
+            tokio::pin!(added);
+            added.as_mut().enable();
+            let snapshots = self.find_snapshots(&predicate).await;
+            if !snapshots.is_empty() {
+                return snapshots;
+            }
+            if tokio::time::timeout_at(deadline, added).await.is_err() {
+                return Vec::new();
+            }
+        }
+    }
+
     pub async fn remove_matching<F>(&self, predicate: F) -> Vec<PendingApprovalEntry>
     where
         F: Fn(&PendingApprovalSnapshot) -> bool,
@@ -192,4 +224,33 @@ mod tests {
             .expect("channel approval remains");
         assert_eq!(remaining.source, ApprovalSource::Channel);
     }
+
+    #[tokio::test]
+    async fn registry_wait_for_snapshots_wakes_on_upsert() {
+        let registry = std::sync::Arc::new(PendingApprovalRegistry::new());
+        let waiter = {
+            let registry = registry.clone();
+            tokio::spawn(async move {
+                registry
+                    .wait_for_snapshots(
+                        |entry| entry.session_id == "sess_1",
+                        Duration::from_secs(5),
+                    )
+                    .await
+            })
+        };
+        tokio::task::yield_now().await;
+        registry.upsert(build_entry("appr_late")).await;
+        let snapshots = waiter.await.expect("waiter");
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].approval_id, "appr_late");
+
+        let empty = registry
+            .wait_for_snapshots(
+                |entry| entry.session_id == "sess_other",
+                Duration::from_millis(20),
+            )
+            .await;
+        assert!(empty.is_empty());
+    }
 }
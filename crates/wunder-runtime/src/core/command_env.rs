@@ -0,0 +1,314 @@
+// 命令环境变量白名单：execute_command 与 ptc 启动的子进程只继承白名单内的环境变量，单次调用可在白名单范围内追加变量。
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsString;
+use tokio::process::Command;
+
+/// Variables commands need to locate programs, temp dirs and locale; nothing that usually
+/// carries credentials.
+pub const DEFAULT_ENV_PASSTHROUGH: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "SHELL",
+    "TERM",
+    "LANG",
+    "LANGUAGE",
+    "LC_ALL",
+    "LC_CTYPE",
+    "TZ",
+    "TMPDIR",
+    "TMP",
+    "TEMP",
+    "CI",
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PROGRAMDATA",
+    "PROGRAMFILES",
+];
+
+/// Variables a single call may never set: they decide which binary a command name resolves to,
+/// what gets loaded into it or which startup hooks an interpreter runs, so a workspace-controlled
+/// value would let an allowed command run code of the caller's choosing.
+const PROTECTED_CALL_ENV: &[&str] = &[
+    "PATH",
+    "PATHEXT",
+    "HOME",
+    "USERPROFILE",
+    "COMSPEC",
+    "SHELL",
+    "BASH_ENV",
+    "ENV",
+    "SHELLOPTS",
+    "BASHOPTS",
+    "PROMPT_COMMAND",
+    "IFS",
+    "NODE_OPTIONS",
+    "NODE_PATH",
+    "PYTHONSTARTUP",
+    "PYTHONPATH",
+    "PYTHONHOME",
+    "PYTHONUSERBASE",
+    "PYTHONINSPECT",
+    "PERL5OPT",
+    "PERL5LIB",
+    "PERLLIB",
+    "RUBYOPT",
+    "RUBYLIB",
+    "JAVA_TOOL_OPTIONS",
+    "_JAVA_OPTIONS",
+    "JDK_JAVA_OPTIONS",
+    "GIT_SSH",
+    "GIT_SSH_COMMAND",
+    "GIT_EXEC_PATH",
+    "GIT_ASKPASS",
+    "SSH_ASKPASS",
+    "GIT_PAGER",
+    "GIT_EDITOR",
+    "PAGER",
+    "EDITOR",
+    "VISUAL",
+];
+const PROTECTED_CALL_ENV_PREFIXES: &[&str] = &["LD_", "DYLD_", "BASH_FUNC_", "GIT_CONFIG"];
+
+fn is_protected_call_env(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    PROTECTED_CALL_ENV.contains(&name.as_str())
+        || PROTECTED_CALL_ENV_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+/// Which environment variables reach child processes. Names match case-insensitively, as
+/// Windows does; `*` keeps the full parent environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvPassthrough {
+    inherit_all: bool,
+    names: HashSet<String>,
+}
+
+impl EnvPassthrough {
+    /// An empty list selects [`DEFAULT_ENV_PASSTHROUGH`].
+    pub fn from_names(configured: &[String]) -> Self {
+        let configured = configured
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>();
+        if configured.contains(&"*") {
+            return Self {
+                inherit_all: true,
+                names: HashSet::new(),
+            };
+        }
+        let names = if configured.is_empty() {
+            DEFAULT_ENV_PASSTHROUGH
+                .iter()
+                .map(|name| name.to_ascii_uppercase())
+                .collect()
+        } else {
+            configured
+                .iter()
+                .map(|name| name.to_ascii_uppercase())
+                .collect()
+        };
+        Self {
+            inherit_all: false,
+            names,
+        }
+    }
+
+    pub fn allows(&self, name: &str) -> bool {
+        self.inherit_all || self.names.contains(&name.trim().to_ascii_uppercase())
+    }
+
+    /// Split the `env` object of a tool call into the allowed variables and the rejected names.
+    /// Search-path, loader and interpreter startup variables are rejected even when the allowlist
+    /// inherits them.
+    pub fn split_call_env(&self, args: &Value) -> (BTreeMap<String, String>, Vec<String>) {
+        let mut allowed = BTreeMap::new();
+        let mut rejected = Vec::new();
+        let Some(map) = args.get("env").and_then(Value::as_object) else {
+            return (allowed, rejected);
+        };
+        for (name, value) in map {
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            if name.contains('=')
+                || name.contains('\0')
+                || is_protected_call_env(name)
+                || !self.allows(name)
+            {
+                rejected.push(name.to_string());
+                continue;
+            }
+            let value = match value {
+                Value::String(text) => text.clone(),
+                Value::Null => continue,
+                other => other.to_string(),
+            };
+            allowed.insert(name.to_string(), value);
+        }
+        (allowed, rejected)
+    }
+}
+
+/// Environment of one command: the allowed part of the parent environment plus per-call values.
+#[derive(Debug, Clone)]
+pub struct ChildEnv {
+    passthrough: EnvPassthrough,
+    call_env: BTreeMap<String, String>,
+}
+
+impl ChildEnv {
+    pub fn new(passthrough: EnvPassthrough, call_env: BTreeMap<String, String>) -> Self {
+        Self {
+            passthrough,
+            call_env,
+        }
+    }
+
+    /// Drop inherited variables outside the allowlist. Variables already set on `cmd` (a
+    /// `NAME=value` command prefix, python runtime paths) are explicit and kept.
+    pub fn apply(&self, cmd: &mut Command) {
+        self.apply_with_parent(cmd, std::env::vars_os());
+    }
+
+    fn apply_with_parent(
+        &self,
+        cmd: &mut Command,
+        parent: impl IntoIterator<Item = (OsString, OsString)>,
+    ) {
+        if !self.passthrough.inherit_all {
+            let explicit = cmd
+                .as_std()
+                .get_envs()
+                .map(|(name, _)| name.to_os_string())
+                .collect::<HashSet<_>>();
+            for (name, _) in parent {
+                let allowed = name
+                    .to_str()
+                    .is_some_and(|text| self.passthrough.allows(text));
+                if !allowed && !explicit.contains(&name) {
+                    cmd.env_remove(&name);
+                }
+            }
+        }
+        cmd.envs(&self.call_env);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn applied_envs(cmd: &Command) -> BTreeMap<String, Option<String>> {
+        cmd.as_std()
+            .get_envs()
+            .map(|(name, value)| {
+                (
+                    name.to_string_lossy().into_owned(),
+                    value.map(|value| value.to_string_lossy().into_owned()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn only_allowlisted_variables_reach_the_command() {
+        let parent = [
+            ("WUNDER_ENV_TEST_SECRET", "leaked"),
+            ("WUNDER_ENV_TEST_SHARED", "visible"),
+        ]
+        .map(|(name, value)| (OsString::from(name), OsString::from(value)));
+        let passthrough = EnvPassthrough::from_names(&[
+            "PATH".to_string(),
+            "WUNDER_ENV_TEST_SHARED".to_string(),
+            "WUNDER_ENV_TEST_CALL".to_string(),
+        ]);
+        let (call_env, rejected) = passthrough.split_call_env(&json!({
+            "env": { "WUNDER_ENV_TEST_CALL": "per-call", "WUNDER_ENV_TEST_SECRET": "injected" }
+        }));
+        assert_eq!(rejected, vec!["WUNDER_ENV_TEST_SECRET"]);
+
+        let mut cmd = Command::new("sh");
+        ChildEnv::new(passthrough, call_env).apply_with_parent(&mut cmd, parent);
+        let envs = applied_envs(&cmd);
+        assert_eq!(envs.get("WUNDER_ENV_TEST_SECRET"), Some(&None));
+        assert!(!envs.contains_key("WUNDER_ENV_TEST_SHARED"));
+        assert_eq!(
+            envs.get("WUNDER_ENV_TEST_CALL"),
+            Some(&Some("per-call".to_string()))
+        );
+    }
+
+    #[test]
+    fn call_env_cannot_override_search_path_or_loader_variables() {
+        let passthrough = EnvPassthrough::from_names(&["*".to_string()]);
+        let (call_env, mut rejected) = passthrough.split_call_env(&json!({
+            "env": {
+                "PATH": "./bin",
+                "home": "/workspace",
+                "LD_PRELOAD": "./evil.so",
+                "DYLD_INSERT_LIBRARIES": "./evil.dylib",
+                "RUST_LOG": "debug"
+            }
+        }));
+        rejected.sort();
+        assert_eq!(
+            rejected,
+            vec!["DYLD_INSERT_LIBRARIES", "LD_PRELOAD", "PATH", "home"]
+        );
+        assert_eq!(call_env.keys().collect::<Vec<_>>(), vec!["RUST_LOG"]);
+    }
+
+    #[test]
+    fn call_env_cannot_set_interpreter_startup_hooks_under_a_star_allowlist() {
+        let passthrough = EnvPassthrough::from_names(&["*".to_string()]);
+        let (call_env, mut rejected) = passthrough.split_call_env(&json!({
+            "env": {
+                "BASH_ENV": "./hook.sh",
+                "NODE_OPTIONS": "--require ./hook.js",
+                "PYTHONSTARTUP": "./hook.py",
+                "GIT_SSH_COMMAND": "./hook.sh",
+                "GIT_CONFIG_COUNT": "1",
+                "BASH_FUNC_make%%": "() { ./hook.sh; }",
+                "CARGO_TERM_COLOR": "never"
+            }
+        }));
+        rejected.sort();
+        assert_eq!(
+            rejected,
+            vec![
+                "BASH_ENV",
+                "BASH_FUNC_make%%",
+                "GIT_CONFIG_COUNT",
+                "GIT_SSH_COMMAND",
+                "NODE_OPTIONS",
+                "PYTHONSTARTUP"
+            ]
+        );
+        assert_eq!(
+            call_env.keys().collect::<Vec<_>>(),
+            vec!["CARGO_TERM_COLOR"]
+        );
+    }
+
+    #[test]
+    fn empty_list_uses_defaults_and_star_inherits_everything() {
+        let defaults = EnvPassthrough::from_names(&[]);
+        assert!(defaults.allows("path"));
+        assert!(!defaults.allows("AWS_SECRET_ACCESS_KEY"));
+        assert!(EnvPassthrough::from_names(&["*".to_string()]).allows("AWS_SECRET_ACCESS_KEY"));
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::io;
 use std::path::Path;
 #[cfg(not(windows))]
@@ -46,7 +47,7 @@ pub fn build_direct_command(command: &str, cwd: &Path) -> Option<Command> {
     if trimmed.is_empty() || contains_shell_meta(trimmed) {
         return None;
     }
-    let parts = shell_words::split(trimmed).ok()?;
+    let parts = split_command_line(trimmed).ok()?;
     if parts.is_empty() {
         return None;
     }
@@ -97,7 +98,7 @@ pub fn build_direct_command_with_overrides(
     if trimmed.is_empty() || contains_shell_meta(trimmed) {
         return None;
     }
-    let parts = shell_words::split(trimmed).ok()?;
+    let parts = split_command_line(trimmed).ok()?;
     if parts.is_empty() {
         return None;
     }
@@ -207,6 +208,230 @@ pub fn resolve_shell_name(command: &str) -> &'static str {
     }
 }
 
+/// Quoting rules of the shell a command line is handed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellDialect {
+    /// bash/sh words: single quotes are literal, backslash escapes.
+    Posix,
+    /// PowerShell: single quotes are literal (`''` escapes), backtick escapes.
+    PowerShell,
+    /// cmd.exe and the MSVC argv parser: double quotes group, backslashes are literal unless
+    /// they precede a quote.
+    Cmd,
+}
+
+impl ShellDialect {
+    /// Dialect of the shell [`build_shell_command`] uses on this platform.
+    pub fn current() -> Self {
+        #[cfg(windows)]
+        {
+            if prefer_powershell() {
+                ShellDialect::PowerShell
+            } else {
+                ShellDialect::Cmd
+            }
+        }
+
+        #[cfg(not(windows))]
+        {
+            ShellDialect::Posix
+        }
+    }
+}
+
+/// Split a command line into arguments with the rules of the platform shell.
+pub fn split_command_line(command: &str) -> anyhow::Result<Vec<String>> {
+    split_command_line_for(command, ShellDialect::current())
+}
+
+pub fn split_command_line_for(command: &str, dialect: ShellDialect) -> anyhow::Result<Vec<String>> {
+    match dialect {
+        ShellDialect::Posix => Ok(shell_words::split(command)?),
+        ShellDialect::PowerShell => split_powershell_words(command),
+        ShellDialect::Cmd => split_msvc_argv(command),
+    }
+}
+
+/// Quote one argument so the platform shell passes it through unchanged.
+pub fn quote_arg(arg: &str) -> Cow<'_, str> {
+    quote_arg_for(arg, ShellDialect::current())
+}
+
+/// `cmd.exe` still expands `%NAME%` inside quotes; no quoting prevents that.
+pub fn quote_arg_for(arg: &str, dialect: ShellDialect) -> Cow<'_, str> {
+    match dialect {
+        ShellDialect::Posix => shell_words::quote(arg),
+        ShellDialect::PowerShell => quote_powershell_arg(arg),
+        ShellDialect::Cmd => quote_msvc_arg(arg),
+    }
+}
+
+/// Join arguments into a command line that [`split_command_line`] turns back into `args`.
+pub fn join_args<I, S>(args: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    join_args_for(args, ShellDialect::current())
+}
+
+pub fn join_args_for<I, S>(args: I, dialect: ShellDialect) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter()
+        .map(|arg| quote_arg_for(arg.as_ref(), dialect).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn quote_powershell_arg(arg: &str) -> Cow<'_, str> {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || "-_./\\:=+".contains(ch));
+    if safe {
+        return Cow::Borrowed(arg);
+    }
+    Cow::Owned(format!("'{}'", arg.replace('\'', "''")))
+}
+
+fn quote_msvc_arg(arg: &str) -> Cow<'_, str> {
+    let needs_quotes = arg.is_empty()
+        || arg.chars().any(|ch| {
+            matches!(
+                ch,
+                ' ' | '\t' | '"' | '&' | '|' | '<' | '>' | '^' | '(' | ')'
+            )
+        });
+    if !needs_quotes {
+        return Cow::Borrowed(arg);
+    }
+    let mut output = String::with_capacity(arg.len() + 2);
+    output.push('"');
+    let mut backslashes = 0;
+    for ch in arg.chars() {
+        match ch {
+            '\\' => backslashes += 1,
+            '"' => {
+                // `""` inside quotes keeps cmd.exe's quote tracking intact, unlike `\"`.
+                output.push_str(&"\\".repeat(backslashes * 2));
+                output.push_str("\"\"");
+                backslashes = 0;
+            }
+            _ => {
+                output.push_str(&"\\".repeat(backslashes));
+                output.push(ch);
+                backslashes = 0;
+            }
+        }
+    }
+    output.push_str(&"\\".repeat(backslashes * 2));
+    output.push('"');
+    Cow::Owned(output)
+}
+
+fn split_msvc_argv(command: &str) -> anyhow::Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut in_quotes = false;
+    let mut backslashes = 0;
+    let mut chars = command.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                backslashes += 1;
+                in_arg = true;
+            }
+            '"' => {
+                current.push_str(&"\\".repeat(backslashes / 2));
+                if backslashes % 2 == 1 {
+                    current.push('"');
+                } else if in_quotes && chars.peek() == Some(&'"') {
+                    chars.next();
+                    current.push('"');
+                } else {
+                    in_quotes = !in_quotes;
+                }
+                backslashes = 0;
+                in_arg = true;
+            }
+            ' ' | '\t' if !in_quotes => {
+                current.push_str(&"\\".repeat(backslashes));
+                backslashes = 0;
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            _ => {
+                current.push_str(&"\\".repeat(backslashes));
+                backslashes = 0;
+                current.push(ch);
+                in_arg = true;
+            }
+        }
+    }
+    if in_quotes {
+        return Err(anyhow::anyhow!("missing closing quote"));
+    }
+    current.push_str(&"\\".repeat(backslashes));
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+fn split_powershell_words(command: &str) -> anyhow::Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some('\''), '\'') | (Some('"'), '"') => {
+                if chars.peek() == Some(&ch) {
+                    chars.next();
+                    current.push(ch);
+                } else {
+                    quote = None;
+                }
+            }
+            (Some('"'), '`') | (None, '`') => {
+                let escaped = chars
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("dangling escape character"))?;
+                current.push(escaped);
+                in_arg = true;
+            }
+            (None, '\'' | '"') => {
+                quote = Some(ch);
+                in_arg = true;
+            }
+            (None, ' ' | '\t') => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            _ => {
+                current.push(ch);
+                in_arg = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err(anyhow::anyhow!("missing closing quote"));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
 #[cfg(windows)]
 fn prefer_powershell() -> bool {
     static PREFER_POWERSHELL: OnceLock<bool> = OnceLock::new();
@@ -482,4 +707,64 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(args, vec!["-m", "pip", "install", "wunder"]);
     }
+
+    #[test]
+    fn join_args_round_trips_through_split_in_every_dialect() {
+        let args = [
+            "plain",
+            "with space",
+            "it's",
+            "say \"hi\"",
+            "",
+            r"C:\Program Files\tool\",
+            r"trailing\\",
+            "a&b|c",
+            "$HOME `pwd`",
+        ];
+        for dialect in [
+            ShellDialect::Posix,
+            ShellDialect::PowerShell,
+            ShellDialect::Cmd,
+        ] {
+            let line = join_args_for(args, dialect);
+            let split = split_command_line_for(&line, dialect).expect("split");
+            assert_eq!(split, args, "{dialect:?}: {line}");
+        }
+    }
+
+    #[test]
+    fn quote_arg_uses_the_dialect_quoting_rules() {
+        assert_eq!(quote_arg_for("plain", ShellDialect::Posix), "plain");
+        assert_eq!(quote_arg_for("it's", ShellDialect::Posix), r"'it'\''s'");
+        assert_eq!(quote_arg_for("it's", ShellDialect::PowerShell), "'it''s'");
+        assert_eq!(
+            quote_arg_for(r"C:\dir with space\", ShellDialect::Cmd),
+            r#""C:\dir with space\\""#
+        );
+        assert_eq!(quote_arg_for("", ShellDialect::Cmd), "\"\"");
+    }
+
+    #[test]
+    fn split_keeps_windows_backslashes_but_posix_treats_them_as_escapes() {
+        let line = r#"type "C:\Users\me\notes.txt" C:\tmp\a.txt"#;
+        assert_eq!(
+            split_command_line_for(line, ShellDialect::Cmd).expect("cmd"),
+            vec!["type", r"C:\Users\me\notes.txt", r"C:\tmp\a.txt"]
+        );
+        assert_eq!(
+            split_command_line_for(line, ShellDialect::PowerShell).expect("powershell"),
+            vec!["type", r"C:\Users\me\notes.txt", r"C:\tmp\a.txt"]
+        );
+        assert_eq!(
+            split_command_line_for(line, ShellDialect::Posix).expect("posix"),
+            vec!["type", r"C:\Users\me\notes.txt", "C:tmpa.txt"]
+        );
+        for dialect in [
+            ShellDialect::Posix,
+            ShellDialect::PowerShell,
+            ShellDialect::Cmd,
+        ] {
+            assert!(split_command_line_for("echo \"open", dialect).is_err());
+        }
+    }
 }
@@ -0,0 +1,340 @@
+// 请求级 config_overrides 安全策略：普通用户只能设置白名单内的模型参数并收紧安全配置，其余需要管理员或 API Key。
+use crate::config::Config;
+use serde_json::Value;
+use std::collections::HashSet;
+use wunder_core::approval::ApprovalMode;
+use wunder_core::exec_policy::ExecPolicyMode;
+
+/// Gate a request's `config_overrides`: elevated callers (admins, API key) may change
+/// anything, everyone else gets the paths outside the allowlist back as the error.
+pub fn check_config_overrides(
+    base: &Config,
+    overrides: Option<&Value>,
+    elevated: bool,
+) -> Result<(), Vec<String>> {
+    let Some(overrides) = overrides.filter(|_| !elevated) else {
+        return Ok(());
+    };
+    let rejected = disallowed_override_paths(base, overrides);
+    if rejected.is_empty() {
+        Ok(())
+    } else {
+        Err(rejected)
+    }
+}
+
+/// Per-turn model settings a non-elevated caller may change on an already configured model.
+/// Endpoint, credentials and provider selection stay admin-only.
+const MODEL_OVERRIDE_KEYS: &[&str] = &[
+    "temperature",
+    "top_p",
+    "stop",
+    "max_rounds",
+    "max_output",
+    "thinking_token_budget",
+    "reasoning_effort",
+    "stream",
+    "stream_include_usage",
+    "history_compaction_ratio",
+    "tool_call_mode",
+    "response_format",
+    "response_format_retry",
+];
+
+enum OverrideRule {
+    /// An object whose children are checked one by one.
+    Branch,
+    /// A setting whose value decides whether it tightens the base config.
+    Leaf(fn(&Config, &Value) -> bool),
+    Allowed,
+    Denied,
+}
+
+/// Return the override paths in `overrides` a non-elevated caller may not set, sorted.
+///
+/// Everything is admin-only except this allowlist: the per-turn settings in
+/// `MODEL_OVERRIDE_KEYS` on a configured `llm.models.<name>`; `security.approval_mode` and
+/// `security.exec_policy_mode` made stricter; `security.allow_commands`/`allow_paths` losing
+/// entries; `security.deny_globs` and `tools.web.fetch.hostname_denylist` gaining entries; `security.tool_result_guard` switched on;
+/// and the `allow_private_network`/`deny_file_scheme` switches under `browser.security` and
+/// `tools.web.fetch` turned towards the safe side. Null values are ignored.
+pub fn disallowed_override_paths(base: &Config, overrides: &Value) -> Vec<String> {
+    let mut rejected = Vec::new();
+    if let Some(map) = overrides.as_object() {
+        let mut path = Vec::new();
+        for (key, value) in map {
+            path.push(key.as_str());
+            collect_disallowed(base, &mut path, value, &mut rejected);
+            path.pop();
+        }
+    }
+    rejected.sort();
+    rejected
+}
+
+fn collect_disallowed<'a>(
+    base: &Config,
+    path: &mut Vec<&'a str>,
+    value: &'a Value,
+    rejected: &mut Vec<String>,
+) {
+    if value.is_null() {
+        return;
+    }
+    let allowed = match override_rule(base, path) {
+        OverrideRule::Branch => match value.as_object() {
+            Some(map) => {
+                for (key, child) in map {
+                    path.push(key.as_str());
+                    collect_disallowed(base, path, child, rejected);
+                    path.pop();
+                }
+                true
+            }
+            None => false,
+        },
+        OverrideRule::Leaf(tightens) => tightens(base, value),
+        OverrideRule::Allowed => true,
+        OverrideRule::Denied => false,
+    };
+    if !allowed {
+        rejected.push(path.join("."));
+    }
+}
+
+fn override_rule(base: &Config, path: &[&str]) -> OverrideRule {
+    match path {
+        ["llm"] | ["llm", "models"] | ["security"] | ["browser"] | ["browser", "security"] => {
+            OverrideRule::Branch
+        }
+        ["tools"] | ["tools", "web"] | ["tools", "web", "fetch"] => OverrideRule::Branch,
+        ["llm", "models", name] if base.llm.models.contains_key(*name) => OverrideRule::Branch,
+        ["llm", "models", _, key] if MODEL_OVERRIDE_KEYS.contains(key) => OverrideRule::Allowed,
+        ["security", "approval_mode"] => OverrideRule::Leaf(|base, value| {
+            let requested = ApprovalMode::from_raw(value.as_str());
+            let current = ApprovalMode::from_raw(base.security.approval_mode.as_deref());
+            approval_rank(requested) <= approval_rank(current)
+        }),
+        ["security", "exec_policy_mode"] => OverrideRule::Leaf(|base, value| {
+            let requested = ExecPolicyMode::from_raw(value.as_str());
+            let current = ExecPolicyMode::from_raw(base.security.exec_policy_mode.as_deref());
+            exec_policy_rank(requested) >= exec_policy_rank(current)
+        }),
+        ["security", "allow_commands"] => {
+            OverrideRule::Leaf(|base, value| !adds_entries(&base.security.allow_commands, value))
+        }
+        ["security", "allow_paths"] => {
+            OverrideRule::Leaf(|base, value| !adds_entries(&base.security.allow_paths, value))
+        }
+        ["security", "deny_globs"] => {
+            OverrideRule::Leaf(|base, value| !drops_entries(&base.security.deny_globs, value))
+        }
+        ["security", "tool_result_guard"] => OverrideRule::Leaf(|base, value| {
+            value
+                .as_bool()
+                .is_some_and(|enabled| enabled || !base.security.tool_result_guard)
+        }),
+        ["browser", "security", "allow_private_network"] => OverrideRule::Leaf(|base, value| {
+            value
+                .as_bool()
+                .is_some_and(|enabled| !enabled || base.browser.security.allow_private_network)
+        }),
+        ["browser", "security", "deny_file_scheme"] => OverrideRule::Leaf(|base, value| {
+            value
+                .as_bool()
+                .is_some_and(|enabled| enabled || !base.browser.security.deny_file_scheme)
+        }),
+        ["tools", "web", "fetch", "hostname_denylist"] => OverrideRule::Leaf(|base, value| {
+            !drops_entries(&base.tools.web.fetch.hostname_denylist, value)
+        }),
+        ["tools", "web", "fetch", "allow_private_network"] => OverrideRule::Leaf(|base, value| {
+            value
+                .as_bool()
+                .is_some_and(|enabled| !enabled || base.tools.web.fetch.allow_private_network)
+        }),
+        _ => OverrideRule::Denied,
+    }
+}
+
+fn approval_rank(mode: ApprovalMode) -> u8 {
+    match mode {
+        ApprovalMode::Suggest => 0,
+        ApprovalMode::AutoEdit => 1,
+        ApprovalMode::FullAuto => 2,
+    }
+}
+
+fn exec_policy_rank(mode: ExecPolicyMode) -> u8 {
+    match mode {
+        ExecPolicyMode::Allow => 0,
+        ExecPolicyMode::Audit => 1,
+        ExecPolicyMode::Enforce => 2,
+    }
+}
+
+fn string_entries(value: &Value) -> Option<HashSet<&str>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|item| item.as_str().map(str::trim))
+        .collect()
+}
+
+fn adds_entries(current: &[String], requested: &Value) -> bool {
+    // A value that is not a list of strings is rejected downstream; treat it as weakening here.
+    let Some(requested) = string_entries(requested) else {
+        return true;
+    };
+    let current = current
+        .iter()
+        .map(|item| item.trim())
+        .collect::<HashSet<_>>();
+    if current.contains("*") {
+        return false;
+    }
+    requested.iter().any(|item| !current.contains(item))
+}
+
+fn drops_entries(current: &[String], requested: &Value) -> bool {
+    let Some(requested) = string_entries(requested) else {
+        return true;
+    };
+    current.iter().any(|item| !requested.contains(item.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn base_config() -> Config {
+        let mut config = Config::default();
+        config.security.approval_mode = Some("suggest".to_string());
+        config.security.allow_commands = vec!["git".to_string(), "ls".to_string()];
+        config.security.deny_globs = vec!["**/.env".to_string()];
+        config.security.tool_result_guard = true;
+        config
+            .llm
+            .models
+            .insert("demo".to_string(), Default::default());
+        config
+    }
+
+    #[test]
+    fn disallowed_override_paths_flags_escalation_to_full_auto() {
+        let overrides = json!({ "security": { "approval_mode": "full_auto" } });
+        assert_eq!(
+            disallowed_override_paths(&base_config(), &overrides),
+            vec!["security.approval_mode".to_string()]
+        );
+    }
+
+    #[test]
+    fn check_config_overrides_rejects_full_auto_unless_elevated() {
+        let overrides = json!({ "security": { "approval_mode": "full_auto" } });
+        assert_eq!(
+            check_config_overrides(&base_config(), Some(&overrides), false),
+            Err(vec!["security.approval_mode".to_string()])
+        );
+        assert_eq!(
+            check_config_overrides(&base_config(), Some(&overrides), true),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn disallowed_override_paths_allows_tightening() {
+        let mut base = base_config();
+        base.security.approval_mode = Some("full_auto".to_string());
+        let overrides = json!({
+            "security": {
+                "approval_mode": "suggest",
+                "exec_policy_mode": "enforce",
+                "allow_commands": ["git"],
+                "deny_globs": ["**/.env", "**/*.pem"],
+                "tool_result_guard": true,
+            },
+            "browser": { "security": { "deny_file_scheme": true } },
+            "llm": { "models": { "demo": { "max_rounds": 8 } } },
+        });
+        assert_eq!(
+            disallowed_override_paths(&base, &overrides),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn hostname_denylist_overrides_may_only_add_hosts() {
+        let mut base = base_config();
+        base.tools.web.fetch.hostname_denylist = vec!["metadata.internal".to_string()];
+        let added = json!({
+            "tools": { "web": { "fetch": {
+                "hostname_denylist": ["metadata.internal", "example.com"],
+            } } },
+        });
+        assert_eq!(
+            disallowed_override_paths(&base, &added),
+            Vec::<String>::new()
+        );
+        let dropped = json!({ "tools": { "web": { "fetch": { "hostname_denylist": [] } } } });
+        assert_eq!(
+            disallowed_override_paths(&base, &dropped),
+            vec!["tools.web.fetch.hostname_denylist".to_string()]
+        );
+    }
+
+    #[test]
+    fn disallowed_override_paths_flags_widened_lists_and_locked_keys() {
+        let overrides = json!({
+            "security": {
+                "allow_commands": ["git", "rm"],
+                "deny_globs": [],
+                "api_key": "x",
+                "tool_result_guard": false,
+            },
+            "tools": { "web": { "fetch": { "allow_private_network": true } } },
+        });
+        assert_eq!(
+            disallowed_override_paths(&base_config(), &overrides),
+            vec![
+                "security.allow_commands".to_string(),
+                "security.api_key".to_string(),
+                "security.deny_globs".to_string(),
+                "security.tool_result_guard".to_string(),
+                "tools.web.fetch.allow_private_network".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn disallowed_override_paths_rejects_everything_outside_the_allowlist() {
+        let overrides = json!({
+            "server": { "mode": "api" },
+            "llm": {
+                "default": "demo",
+                "models": {
+                    "demo": {
+                        "temperature": 0.2,
+                        "base_url": "http://attacker.invalid",
+                        "api_key": "x",
+                    },
+                    "fresh": { "temperature": 0.2 },
+                },
+            },
+            "tools": { "web": { "fetch": { "hostname_allowlist": ["*"] } } },
+            "_goal_continuation": true,
+        });
+        assert_eq!(
+            disallowed_override_paths(&base_config(), &overrides),
+            vec![
+                "_goal_continuation".to_string(),
+                "llm.default".to_string(),
+                "llm.models.demo.api_key".to_string(),
+                "llm.models.demo.base_url".to_string(),
+                "llm.models.fresh".to_string(),
+                "server".to_string(),
+                "tools.web.fetch.hostname_allowlist".to_string(),
+            ]
+        );
+    }
+}
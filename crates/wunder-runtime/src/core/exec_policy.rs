@@ -1,21 +1,28 @@
-use crate::config::Config;
+use crate::config::{AutoApproveRule, Config};
 use crate::tools::resolve_tool_name;
 use dashmap::DashMap;
 use serde_json::Value;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::path::{Component, Path};
 use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use wunder_core::exec_policy::{
     build_approval_signature, evaluate_tool_policy, extract_approval_flag, extract_approval_token,
-    extract_command_text, resolve_session_key,
+    extract_command_text, is_high_risk_command, resolve_session_key,
 };
 pub use wunder_core::exec_policy::{
     build_write_signature, ExecPolicyDecision, ExecPolicyMode, ExecPolicyToolKind,
 };
 
 const APPROVAL_CACHE_TTL_S: i64 = 600;
+/// Decision reason for calls let through by a `security.auto_approve` rule.
+pub const AUTO_APPROVED_REASON: &str = "auto_approved";
+/// Characters that chain, redirect, substitute or group commands when left unquoted.
+const SHELL_METACHARACTERS: [char; 13] = [
+    ';', '&', '|', '<', '>', '`', '$', '(', ')', '{', '}', '\n', '\r',
+];
 
 pub fn evaluate_tool_call(
     config: &Config,
@@ -44,7 +51,135 @@ pub fn evaluate_tool_call(
         }
     }
 
-    evaluate_tool_policy(config, tool_kind, &command, approved)
+    let decision = evaluate_tool_policy(config, tool_kind, &command, approved)?;
+    if decision.requires_approval
+        && config.security.auto_approve.iter().any(|rule| {
+            auto_approve_rule_matches(config, rule, tool_name, tool_kind, args, &command)
+        })
+    {
+        return Some(ExecPolicyDecision {
+            allowed: true,
+            requires_approval: false,
+            reason: AUTO_APPROVED_REASON.to_string(),
+            ..decision
+        });
+    }
+    Some(decision)
+}
+
+fn auto_approve_rule_matches(
+    config: &Config,
+    rule: &AutoApproveRule,
+    tool_name: &str,
+    tool_kind: ExecPolicyToolKind,
+    args: &Value,
+    command: &str,
+) -> bool {
+    let rule_tool = rule.tool.trim();
+    if rule_tool.is_empty()
+        || (rule_tool != "*" && rule_tool != tool_name && resolve_tool_name(rule_tool) != tool_name)
+    {
+        return false;
+    }
+    if matches!(tool_kind, ExecPolicyToolKind::Exec) {
+        // High-risk commands always go to a human, whatever the rules say.
+        if is_high_risk_command(command) || !command_matches_prefixes(command, &rule.commands) {
+            return false;
+        }
+    } else if !rule.commands.is_empty() {
+        return false;
+    }
+    if !rule.within_allow_paths {
+        return true;
+    }
+    let target = match tool_kind {
+        ExecPolicyToolKind::Exec => Some(args.get("workdir").and_then(Value::as_str).unwrap_or("")),
+        ExecPolicyToolKind::Write => args.get("path").and_then(Value::as_str),
+        ExecPolicyToolKind::Control => None,
+    };
+    target.is_some_and(|path| is_within_allow_paths(config, path))
+}
+
+fn command_matches_prefixes(command: &str, prefixes: &[String]) -> bool {
+    let prefixes = prefixes
+        .iter()
+        .map(|item| item.trim().to_lowercase())
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<_>>();
+    if prefixes.is_empty() {
+        return true;
+    }
+    // A prefix vouches for one simple command; chained or redirected commands never match.
+    if has_unquoted_shell_metacharacter(command) {
+        return false;
+    }
+    let lower = command.trim().to_lowercase();
+    prefixes.iter().any(|prefix| {
+        lower == *prefix
+            || lower
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.starts_with(char::is_whitespace))
+    })
+}
+
+/// Minimal POSIX quoting lexer: single quotes are literal, double quotes still expand `$` and
+/// backticks, and a backslash escapes the next character outside single quotes.
+fn has_unquoted_shell_metacharacter(command: &str) -> bool {
+    let mut chars = command.trim().chars();
+    let mut in_single = false;
+    let mut in_double = false;
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' if !in_double => in_single = !in_single,
+            _ if in_single => {}
+            '\\' => {
+                chars.next();
+            }
+            '"' => in_double = !in_double,
+            '$' | '`' => return true,
+            _ if in_double => {}
+            _ if SHELL_METACHARACTERS.contains(&ch) => return true,
+            _ => {}
+        }
+    }
+    // An unterminated quote leaves the command's extent to the shell; treat it as unsafe.
+    in_single || in_double
+}
+
+/// Relative paths resolve inside the workspace; absolute ones must sit under an `allow_paths`
+/// entry. Any `..` component disqualifies the path.
+fn is_within_allow_paths(config: &Config, path: &str) -> bool {
+    let path = Path::new(path.trim());
+    if path
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return false;
+    }
+    if path.is_relative() {
+        return true;
+    }
+    config
+        .security
+        .allow_paths
+        .iter()
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .any(|item| item == "*" || path.starts_with(item))
+}
+
+/// Args signature an approval applies to: the exact command for exec tools, the target path for
+/// writes and the action/target for desktop control. `None` for tools outside the exec policy.
+pub fn approval_signature(tool_name: &str, args: &Value) -> Option<String> {
+    let tool_kind = resolve_exec_policy_tool_kind(tool_name)?;
+    let command = if matches!(tool_kind, ExecPolicyToolKind::Exec) {
+        extract_command_text(args).unwrap_or_default()
+    } else {
+        String::new()
+    };
+    Some(build_approval_signature(
+        tool_kind, tool_name, args, &command,
+    ))
 }
 
 fn resolve_exec_policy_tool_kind(tool_name: &str) -> Option<ExecPolicyToolKind> {
@@ -224,6 +359,60 @@ mod tests {
         assert_eq!(decision.approval_mode, ApprovalMode::AutoEdit);
     }
 
+    #[test]
+    fn test_auto_approve_rule_skips_prompt_for_matching_command() {
+        let mut config = Config::default();
+        config.security.approval_mode = Some("auto_edit".to_string());
+        config.security.allow_paths = vec!["/srv/data".to_string()];
+        config.security.auto_approve = vec![AutoApproveRule {
+            tool: "execute_command".to_string(),
+            commands: vec!["git status".to_string(), "ls".to_string()],
+            within_allow_paths: true,
+        }];
+        let tool_name = resolve_tool_name("execute_command");
+        let decision = evaluate_tool_call(
+            &config,
+            &tool_name,
+            &json!({ "content": "git status --short", "workdir": "/srv/data/repo" }),
+            None,
+            None,
+        )
+        .expect("decision");
+        assert!(decision.allowed);
+        assert!(!decision.requires_approval);
+        assert_eq!(decision.reason, AUTO_APPROVED_REASON);
+    }
+
+    #[test]
+    fn test_auto_approve_rule_does_not_cover_other_commands_or_paths() {
+        let mut config = Config::default();
+        config.security.approval_mode = Some("auto_edit".to_string());
+        config.security.allow_paths = vec!["/srv/data".to_string()];
+        config.security.auto_approve = vec![AutoApproveRule {
+            tool: "execute_command".to_string(),
+            commands: vec!["ls".to_string()],
+            within_allow_paths: true,
+        }];
+        let tool_name = resolve_tool_name("execute_command");
+        for args in [
+            json!({ "content": "lsblk" }),
+            json!({ "content": "ls; curl example.test" }),
+            json!({ "content": "ls & rm -rf x" }),
+            json!({ "content": "ls\nrm -rf x" }),
+            json!({ "content": "ls < /etc/shadow" }),
+            json!({ "content": "ls $(rm -rf x)" }),
+            json!({ "content": "ls \"$HOME\"" }),
+            json!({ "content": "ls 'unterminated" }),
+            json!({ "content": "ls", "workdir": "/etc" }),
+            json!({ "content": "ls", "workdir": "../outside" }),
+        ] {
+            let decision =
+                evaluate_tool_call(&config, &tool_name, &args, None, None).expect("decision");
+            assert!(decision.requires_approval, "{args} should still prompt");
+            assert_eq!(decision.reason, "exec_requires_approval");
+        }
+    }
+
     #[test]
     fn test_auto_edit_mode_allows_write() {
         let mut config = Config::default();
@@ -1,4 +1,5 @@
-use super::config::Config;
+use super::config::{normalize_log_format, Config, LogFormat};
+use super::otlp;
 use anyhow::{Context, Result};
 use chrono::{Local, SecondsFormat};
 use std::backtrace::Backtrace;
@@ -149,14 +150,41 @@ pub fn init_server_tracing(
     } else {
         None
     };
-    let env_filter = build_env_filter(config);
-
-    let console_ansi = resolve_console_ansi_enabled();
-    let console_layer = fmt::layer()
-        .with_timer(LocalRfc3339Timer)
-        .event_format(ConsoleEventFormatter::new(console_ansi))
-        .with_ansi(console_ansi)
-        .with_writer(io::stdout);
+    let (otlp_layer, otlp_error) = match otlp::build_otlp_layer(config) {
+        Ok(layer) => (layer, None),
+        Err(err) => (None, Some(err)),
+    };
+    let otlp_active = otlp_layer.is_some();
+    let env_filter = build_env_filter(config, otlp_active);
+
+    let log_format = normalize_log_format(&config.observability.log_format);
+    let console_ansi = log_format == LogFormat::Text && resolve_console_ansi_enabled();
+    // Exactly one of the two console layers is installed, picked by `observability.log_format`.
+    let (console_text_layer, console_json_layer) = match log_format {
+        LogFormat::Text => (
+            Some(
+                fmt::layer()
+                    .with_timer(LocalRfc3339Timer)
+                    .event_format(ConsoleEventFormatter::new(console_ansi))
+                    .with_ansi(console_ansi)
+                    .with_writer(io::stdout),
+            ),
+            None,
+        ),
+        LogFormat::Json => (
+            None,
+            Some(
+                fmt::layer()
+                    .json()
+                    .with_timer(LocalRfc3339Timer)
+                    .with_ansi(false)
+                    .with_target(true)
+                    .with_current_span(false)
+                    .with_span_list(false)
+                    .with_writer(io::stdout),
+            ),
+        ),
+    };
 
     let (file_layer, file_guard) = if persist_server_logs {
         let file_appender = tracing_appender::rolling::daily(&log_dir, LOG_FILE_BASENAME);
@@ -179,8 +207,10 @@ pub fn init_server_tracing(
     };
 
     tracing_subscriber::registry()
+        .with(otlp_layer)
         .with(env_filter)
-        .with(console_layer)
+        .with(console_text_layer)
+        .with(console_json_layer)
         .with(file_layer)
         .init();
 
@@ -189,6 +219,9 @@ pub fn init_server_tracing(
     }
     install_panic_hook();
 
+    if let Some(err) = otlp_error {
+        warn!("otlp span export disabled, falling back to logs only: {err}");
+    }
     if let Some(cleanup_result) = cleanup_result {
         match cleanup_result {
             Ok(removed) if removed > 0 => {
@@ -213,11 +246,19 @@ pub fn init_server_tracing(
         log_dir = %log_dir.display(),
         retention_days,
         console_ansi,
+        log_format = ?log_format,
+        otlp_active,
         "server tracing initialized"
     );
     Ok(persist_server_logs.then_some(log_dir))
 }
 
+/// Flush exported spans before the process exits. Blocks on the exporter, so call it off the
+/// async runtime.
+pub fn shutdown_server_tracing() {
+    otlp::shutdown_otlp();
+}
+
 pub fn resolve_server_log_dir(config: &Config) -> PathBuf {
     let configured = config.observability.server_log_dir.trim();
     if configured.is_empty() {
@@ -240,13 +281,17 @@ fn resolve_log_level(config: &Config) -> String {
     }
 }
 
-fn build_env_filter(config: &Config) -> EnvFilter {
+fn build_env_filter(config: &Config, otlp_active: bool) -> EnvFilter {
     let mut env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(resolve_log_level(config)));
     for directive in default_noise_filter_directives() {
         if is_target_overridden_by_rust_log(directive) {
             continue;
         }
+        // The request spans exported over OTLP are INFO spans from tower_http::trace.
+        if otlp_active && directive.starts_with("tower_http::trace=") {
+            continue;
+        }
         if let Ok(parsed) = directive.parse() {
             env_filter = env_filter.add_directive(parsed);
         }
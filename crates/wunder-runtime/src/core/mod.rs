@@ -4,8 +4,10 @@ pub mod atomic_write;
 pub mod auth;
 pub mod blocking;
 pub mod bounded_queue;
+pub mod command_env;
 pub mod command_utils;
 pub mod config;
+pub mod config_override_policy;
 pub mod config_store;
 pub mod dpi;
 pub mod drawio_config;
@@ -16,6 +18,7 @@ pub(crate) mod llm_speed;
 pub mod logging;
 pub mod long_task;
 pub mod onlyoffice_config;
+pub mod otlp;
 pub mod path_utils;
 pub mod python_runtime;
 pub mod repo_assets;
@@ -23,6 +26,7 @@ pub mod runtime_metrics;
 pub mod runtime_tuning;
 pub mod rustls_provider;
 pub mod schemas;
+pub mod session_approvals;
 pub mod shutdown;
 pub mod state;
 pub mod token_utils;
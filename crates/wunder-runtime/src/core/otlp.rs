@@ -0,0 +1,81 @@
+// OpenTelemetry 链路导出：按 observability.otlp 配置构建 OTLP/HTTP span 导出层（需启用 otel 特性）。
+use super::config::Config;
+use tracing_subscriber::{Layer, Registry};
+
+pub type OtlpLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+#[cfg(feature = "otel")]
+static TRACER_PROVIDER: std::sync::OnceLock<opentelemetry_sdk::trace::SdkTracerProvider> =
+    std::sync::OnceLock::new();
+
+/// Build the span export layer. `Ok(None)` when export is disabled; `Err` carries the reason
+/// export was requested but could not be set up, so the caller can keep logging without it.
+pub fn build_otlp_layer(config: &Config) -> Result<Option<OtlpLayer>, String> {
+    let otlp = &config.observability.otlp;
+    if !otlp.enabled {
+        return Ok(None);
+    }
+    build_enabled_layer(otlp)
+}
+
+#[cfg(feature = "otel")]
+fn build_enabled_layer(otlp: &super::config::OtlpConfig) -> Result<Option<OtlpLayer>, String> {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig, WithHttpConfig};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::Resource;
+
+    let mut builder = SpanExporter::builder().with_http();
+    let endpoint = otlp.endpoint.trim();
+    // An empty endpoint leaves resolution to OTEL_EXPORTER_OTLP_(TRACES_)ENDPOINT.
+    if !endpoint.is_empty() {
+        builder = builder.with_endpoint(endpoint);
+    }
+    if !otlp.headers.is_empty() {
+        builder = builder.with_headers(otlp.headers.clone());
+    }
+    let exporter = builder
+        .build()
+        .map_err(|err| format!("build otlp span exporter failed: {err}"))?;
+    let service_name = match otlp.service_name.trim() {
+        "" => "wunder-server".to_string(),
+        value => value.to_string(),
+    };
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build();
+    let tracer = provider.tracer("wunder");
+    if TRACER_PROVIDER.set(provider).is_err() {
+        return Err("otlp tracer provider already initialized".to_string());
+    }
+    Ok(Some(Box::new(
+        tracing_opentelemetry::layer().with_tracer(tracer),
+    )))
+}
+
+#[cfg(not(feature = "otel"))]
+fn build_enabled_layer(_otlp: &super::config::OtlpConfig) -> Result<Option<OtlpLayer>, String> {
+    Err("observability.otlp.enabled is set but this build lacks the `otel` feature".to_string())
+}
+
+/// Flush buffered spans and stop the exporter; a no-op when export never started.
+pub fn shutdown_otlp() {
+    #[cfg(feature = "otel")]
+    if let Some(provider) = TRACER_PROVIDER.get() {
+        if let Err(err) = provider.shutdown() {
+            eprintln!("otlp tracer provider shutdown failed: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_config_builds_no_layer() {
+        let config = Config::default();
+        assert!(matches!(build_otlp_layer(&config), Ok(None)));
+    }
+}
@@ -1,11 +1,11 @@
 use crate::core::approval::ApprovalRequestTx;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 
 pub use wunder_core::schemas::{
-    AbilityDescriptor, AbilityGroupKey, AbilityKind, AbilitySourceKey, AttachmentPayload,
-    AvailableToolsResponse, I18nConfigResponse, SharedToolSpec, StreamEvent, TokenUsage, ToolSpec,
-    WunderPromptRequest, WunderPromptResponse, WunderResponse,
+    AbilityDescriptor, AbilityGroupKey, AbilityKind, AbilitySourceKey, AttachmentMetadata,
+    AttachmentPayload, AvailableToolsResponse, I18nConfigResponse, SharedToolSpec, StreamEvent,
+    TokenUsage, ToolSpec, WunderPromptRequest, WunderPromptResponse, WunderResponse,
 };
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -40,6 +40,12 @@ pub struct WunderRequest {
     pub preview_skill: bool,
     #[serde(default)]
     pub attachments: Option<Vec<AttachmentPayload>>,
+    #[serde(default, alias = "maxRounds")]
+    pub max_rounds: Option<u32>,
+    #[serde(default)]
+    pub cite: bool,
+    #[serde(default, alias = "rememberAttachments")]
+    pub remember_attachments: bool,
     #[serde(default = "default_allow_queue")]
     pub allow_queue: bool,
     #[serde(skip)]
@@ -57,3 +63,172 @@ fn default_stream() -> bool {
 fn default_allow_queue() -> bool {
     true
 }
+
+/// JSON Schema (draft 2020-12) for the public `WunderRequest` body, with `AttachmentPayload`
+/// under `$defs`. Hand-maintained: keep it in sync with the serde attributes above; the
+/// tests below fail when a serialized field is missing here. Server-only fields marked
+/// `#[serde(skip)]` (`is_admin`, `enforce_runtime_queue`, `approval_tx`) are not part of it.
+pub fn wunder_request_json_schema() -> Value {
+    let nullable_string = |description: &str| json!({ "type": ["string", "null"], "default": null, "description": description });
+    let flag = |default: bool, description: &str| json!({ "type": "boolean", "default": default, "description": description });
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "WunderRequest",
+        "type": "object",
+        "required": ["user_id", "question"],
+        "properties": {
+            "user_id": { "type": "string", "description": "Caller user id." },
+            "question": { "type": "string", "description": "User message for this turn." },
+            "client_message_id": nullable_string(
+                "Client-generated message id used to de-duplicate retries. Alias: clientMessageId."
+            ),
+            "tool_names": {
+                "type": "array",
+                "items": { "type": "string" },
+                "default": [],
+                "description": "Tools enabled for this request; empty uses the agent defaults."
+            },
+            "skip_tool_calls": flag(false, "Answer without executing tool calls."),
+            "stream": flag(true, "Stream events over SSE instead of returning one JSON body."),
+            "debug_payload": flag(false, "Log full model request payloads for this request."),
+            "session_id": nullable_string("Existing session to continue; omitted starts a new one."),
+            "agent_id": nullable_string("Agent to run the request with."),
+            "workspace_container_id": {
+                "type": ["integer", "null"],
+                "default": null,
+                "description": "Workspace container to bind. Alias: workspaceContainerId."
+            },
+            "model_name": nullable_string("Model override; omitted uses the configured default."),
+            "language": nullable_string("Response language such as zh-CN or en-US."),
+            "config_overrides": {
+                "default": null,
+                "description": "Request-scoped config values merged over the server config."
+            },
+            "agent_prompt": nullable_string("Agent prompt used when building the system prompt."),
+            "preview_skill": flag(false, "Run with skill preview behavior enabled."),
+            "attachments": {
+                "type": ["array", "null"],
+                "items": { "$ref": "#/$defs/AttachmentPayload" },
+                "default": null,
+                "description": "Files or images sent with the question."
+            },
+            "max_rounds": {
+                "type": ["integer", "null"],
+                "minimum": 1,
+                "default": null,
+                "description": "Model round limit for this turn. It can only lower the configured limit; admins and desktop mode otherwise run unlimited. Alias: maxRounds."
+            },
+            "allow_queue": flag(true, "Queue the request when the session is busy instead of rejecting it."),
+            "cite": flag(false, "Return knowledge source citations with the final answer."),
+            "remember_attachments": flag(
+                false,
+                "Save this turn's text attachments into the user's attachments knowledge base. Alias: rememberAttachments."
+            )
+        },
+        "$defs": {
+            "AttachmentPayload": {
+                "title": "AttachmentPayload",
+                "type": "object",
+                "properties": {
+                    "name": nullable_string("Display file name."),
+                    "content": nullable_string("Inline text, or a data:image URL for images."),
+                    "content_type": nullable_string("MIME type of the content."),
+                    "public_path": nullable_string(
+                        "Public path of an already uploaded file. Alias: publicPath."
+                    ),
+                    "attachment_id": nullable_string(
+                        "Id from POST /wunder/attachments/complete; the server fills in content. Alias: attachmentId."
+                    ),
+                    "metadata": {
+                        "type": ["object", "null"],
+                        "properties": {
+                            "page_count": { "type": "integer" },
+                            "width": { "type": "integer" },
+                            "height": { "type": "integer" },
+                            "language": { "type": "string" },
+                            "ocr": { "type": "boolean" }
+                        },
+                        "default": null,
+                        "description": "Extracted page count, image size, text language, and whether OCR produced text for an image; filled by clients or the server, best effort."
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn schema_property_names(schema: &Value) -> BTreeSet<String> {
+        schema["properties"]
+            .as_object()
+            .expect("properties")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn request_schema_lists_required_top_level_fields() {
+        let schema = wunder_request_json_schema();
+        assert_eq!(schema["required"], json!(["user_id", "question"]));
+        let properties = schema_property_names(&schema);
+        for field in [
+            "tool_names",
+            "skip_tool_calls",
+            "config_overrides",
+            "attachments",
+        ] {
+            assert!(properties.contains(field), "missing {field}");
+        }
+        assert!(!properties.contains("approval_tx"));
+        assert_eq!(
+            schema["$defs"]["AttachmentPayload"]["title"],
+            json!("AttachmentPayload")
+        );
+    }
+
+    #[test]
+    fn request_schema_matches_serialized_request_fields() {
+        let request: WunderRequest =
+            serde_json::from_value(json!({ "user_id": "u", "question": "q" }))
+                .expect("required fields are enough to deserialize");
+        let serialized = serde_json::to_value(&request).expect("serialize request");
+        let serialized_fields = serialized
+            .as_object()
+            .expect("object")
+            .keys()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        assert_eq!(
+            schema_property_names(&wunder_request_json_schema()),
+            serialized_fields
+        );
+
+        let attachment = serde_json::to_value(AttachmentPayload {
+            name: None,
+            content: None,
+            content_type: None,
+            public_path: Some("p".to_string()),
+            attachment_id: Some("a".to_string()),
+            metadata: Some(AttachmentMetadata {
+                page_count: Some(2),
+                ..Default::default()
+            }),
+        })
+        .expect("serialize attachment");
+        let attachment_fields = attachment
+            .as_object()
+            .expect("object")
+            .keys()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        assert_eq!(
+            schema_property_names(&wunder_request_json_schema()["$defs"]["AttachmentPayload"]),
+            attachment_fields
+        );
+    }
+}
@@ -0,0 +1,304 @@
+// 会话级审批持久化：“本会话批准”按工具与参数签名写入存储，恢复会话后继续生效。
+use crate::exec_policy::approval_signature;
+use crate::storage::{hex_meta_segment, StorageBackend};
+use anyhow::Result;
+use chrono::Utc;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+const SESSION_APPROVAL_META_PREFIX: &str = "session_approval:";
+
+/// Persist an "approve for session" decision. Only the same user's session, the same tool with
+/// the same args signature, run in the same resolved `workdir` and with the same per-call `env`
+/// for commands, is covered later; returns `false` when the tool is not subject to approvals.
+pub fn remember_session_approval(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    session_id: &str,
+    tool_name: &str,
+    args: &Value,
+    workdir: Option<&str>,
+) -> Result<bool> {
+    let Some((key, signature)) =
+        session_approval_key(user_id, session_id, tool_name, args, workdir)
+    else {
+        return Ok(false);
+    };
+    let mut record = json!({
+        "tool": tool_name.trim(),
+        "signature": signature,
+        "approved_at": Utc::now().timestamp_millis() as f64 / 1000.0,
+    });
+    if let Some(workdir) = normalize_workdir(workdir) {
+        record["workdir"] = json!(workdir);
+    }
+    storage.set_meta(&key, &record.to_string())?;
+    Ok(true)
+}
+
+pub fn is_session_approved(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    session_id: &str,
+    tool_name: &str,
+    args: &Value,
+    workdir: Option<&str>,
+) -> Result<bool> {
+    let Some((key, _)) = session_approval_key(user_id, session_id, tool_name, args, workdir) else {
+        return Ok(false);
+    };
+    Ok(storage.get_meta(&key)?.is_some())
+}
+
+/// Persisted approvals of a session as `{tool, signature, workdir?, approved_at}` records.
+pub fn list_session_approvals(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    session_id: &str,
+) -> Result<Vec<Value>> {
+    let Some(prefix) = session_approval_prefix(user_id, session_id) else {
+        return Ok(Vec::new());
+    };
+    Ok(storage
+        .list_meta_prefix(&prefix)?
+        .into_iter()
+        .filter_map(|(_, value)| serde_json::from_str::<Value>(&value).ok())
+        .collect())
+}
+
+pub fn clear_session_approvals(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    session_id: &str,
+) -> Result<usize> {
+    let Some(prefix) = session_approval_prefix(user_id, session_id) else {
+        return Ok(0);
+    };
+    storage.delete_meta_prefix(&prefix)
+}
+
+/// Approvals are scoped to the owning user, since clients may pick their own session ids. Both
+/// ids are hex encoded so the `LIKE` prefix match behind `delete_meta_prefix` cannot treat a `_`
+/// in one session id as a wildcard and reach into another session.
+fn session_approval_prefix(user_id: &str, session_id: &str) -> Option<String> {
+    let (user_id, session_id) = (user_id.trim(), session_id.trim());
+    (!user_id.is_empty() && !session_id.is_empty()).then(|| {
+        format!(
+            "{SESSION_APPROVAL_META_PREFIX}{}:{}:",
+            hex_meta_segment(user_id),
+            hex_meta_segment(session_id)
+        )
+    })
+}
+
+fn normalize_workdir(workdir: Option<&str>) -> Option<&str> {
+    workdir.map(str::trim).filter(|value| !value.is_empty())
+}
+
+fn session_approval_key(
+    user_id: &str,
+    session_id: &str,
+    tool_name: &str,
+    args: &Value,
+    workdir: Option<&str>,
+) -> Option<(String, String)> {
+    let prefix = session_approval_prefix(user_id, session_id)?;
+    let tool_name = tool_name.trim();
+    let signature = approval_signature(tool_name, args)?;
+    let signature = signature.trim().to_string();
+    // The key hashes tool, signature and workdir together, so a different command, path,
+    // target or directory can never hit an approval granted for another one.
+    let mut hasher = Sha256::new();
+    hasher.update(tool_name.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(signature.as_bytes());
+    if let Some(workdir) = normalize_workdir(workdir) {
+        hasher.update([0u8]);
+        hasher.update(workdir.as_bytes());
+    }
+    // Per-call `env` changes what the same command does, so it is part of the approval too.
+    for (name, value) in call_env_entries(args) {
+        hasher.update([1u8]);
+        hasher.update(name.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(value.as_bytes());
+    }
+    let digest = hex::encode(hasher.finalize());
+    Some((format!("{prefix}{digest}"), signature))
+}
+
+/// The `env` object of a call as name-sorted pairs, with values stringified the way
+/// `EnvPassthrough::split_call_env` hands them to the command.
+fn call_env_entries(args: &Value) -> BTreeMap<String, String> {
+    let Some(map) = args.get("env").and_then(Value::as_object) else {
+        return BTreeMap::new();
+    };
+    map.iter()
+        .filter(|(name, _)| !name.trim().is_empty())
+        .filter_map(|(name, value)| {
+            let value = match value {
+                Value::String(text) => text.clone(),
+                Value::Null => return None,
+                other => other.to_string(),
+            };
+            Some((name.trim().to_string(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{SqliteStorage, StorageLifecycle};
+    use crate::tools::resolve_tool_name;
+
+    fn open_storage(path: &std::path::Path) -> SqliteStorage {
+        let storage = SqliteStorage::new(path.to_string_lossy().to_string());
+        storage.ensure_initialized().expect("initialize storage");
+        storage
+    }
+
+    #[test]
+    fn session_approval_survives_resume_and_matches_only_same_signature() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let db_path = temp.path().join("session-approvals.db");
+        let exec = resolve_tool_name("execute_command");
+        let approved_args = json!({ "content": "ls /a" });
+        {
+            let storage = open_storage(&db_path);
+            assert!(remember_session_approval(
+                &storage,
+                "alice",
+                "sess_resume",
+                &exec,
+                &approved_args,
+                Some("/work/a")
+            )
+            .expect("remember approval"));
+        }
+
+        // A fresh storage handle on the same database stands in for a resumed process.
+        let storage = open_storage(&db_path);
+        let approved = |session_id: &str, args: &Value| {
+            is_session_approved(&storage, "alice", session_id, &exec, args, Some("/work/a"))
+                .expect("check approval")
+        };
+        assert!(approved("sess_resume", &approved_args));
+        assert!(!approved("sess_resume", &json!({ "content": "rm -rf /" })));
+        assert!(!approved("sess_other", &approved_args));
+        assert_eq!(
+            list_session_approvals(&storage, "alice", "sess_resume").expect("list approvals")[0]
+                ["signature"],
+            json!("ls /a")
+        );
+
+        // The same command in another directory is a different approval.
+        assert!(!is_session_approved(
+            &storage,
+            "alice",
+            "sess_resume",
+            &exec,
+            &approved_args,
+            Some("/work/b")
+        )
+        .expect("check approval"));
+        assert!(!is_session_approved(
+            &storage,
+            "alice",
+            "sess_resume",
+            &exec,
+            &approved_args,
+            None
+        )
+        .expect("check approval"));
+
+        assert_eq!(
+            clear_session_approvals(&storage, "alice", "sess_resume").expect("clear approvals"),
+            1
+        );
+        assert!(!approved("sess_resume", &approved_args));
+    }
+
+    #[test]
+    fn approval_does_not_cover_a_different_call_env() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let storage = open_storage(&temp.path().join("session-approvals-env.db"));
+        let exec = resolve_tool_name("execute_command");
+        let approved_args =
+            json!({ "content": "make test", "env": { "RUST_LOG": "info", "CI": "1" } });
+        assert!(remember_session_approval(
+            &storage,
+            "alice",
+            "sess_env",
+            &exec,
+            &approved_args,
+            Some("/work")
+        )
+        .expect("remember approval"));
+
+        let approved = |args: Value| {
+            is_session_approved(&storage, "alice", "sess_env", &exec, &args, Some("/work"))
+                .expect("check approval")
+        };
+        assert!(approved(
+            json!({ "content": "make test", "env": { "CI": "1", "RUST_LOG": "info" } })
+        ));
+        assert!(!approved(
+            json!({ "content": "make test", "env": { "CI": "1", "RUST_LOG": "trace" } })
+        ));
+        assert!(!approved(
+            json!({ "content": "make test", "env": { "CI": "1", "RUST_LOG": "info", "MAKEFLAGS": "-f evil.mk" } })
+        ));
+        assert!(!approved(json!({ "content": "make test" })));
+    }
+
+    #[test]
+    fn tools_outside_exec_policy_are_never_persisted() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let storage = open_storage(&temp.path().join("session-approvals-skip.db"));
+        let read_file = resolve_tool_name("read_file");
+        assert!(!remember_session_approval(
+            &storage,
+            "alice",
+            "sess_skip",
+            &read_file,
+            &json!({ "path": "a.txt" }),
+            None
+        )
+        .expect("remember approval"));
+        assert!(list_session_approvals(&storage, "alice", "sess_skip")
+            .expect("list approvals")
+            .is_empty());
+    }
+
+    #[test]
+    fn approvals_are_scoped_per_user_and_cleared_per_exact_session() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let storage = open_storage(&temp.path().join("session-approvals-scope.db"));
+        let exec = resolve_tool_name("execute_command");
+        let args = json!({ "content": "ls" });
+        for (user_id, session_id) in [("alice", "a_b"), ("alice", "a-b"), ("bob", "a-b")] {
+            assert!(
+                remember_session_approval(&storage, user_id, session_id, &exec, &args, None)
+                    .expect("remember approval")
+            );
+        }
+        assert!(
+            !is_session_approved(&storage, "carol", "a_b", &exec, &args, None)
+                .expect("check approval")
+        );
+
+        assert_eq!(
+            clear_session_approvals(&storage, "alice", "a_b").expect("clear approvals"),
+            1
+        );
+        let approved = |user_id: &str, session_id: &str| {
+            is_session_approved(&storage, user_id, session_id, &exec, &args, None)
+                .expect("check approval")
+        };
+        assert!(!approved("alice", "a_b"));
+        assert!(approved("alice", "a-b"));
+        assert!(approved("bob", "a-b"));
+    }
+}
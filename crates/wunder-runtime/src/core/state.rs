@@ -298,6 +298,9 @@ impl AppState {
             UserToolStore::new(&config, workspace.clone()).context("初始化用户工具存储失败")?,
         );
         let user_tool_manager = Arc::new(UserToolManager::new(user_tool_store.clone()));
+        if tokio::runtime::Handle::try_current().is_ok() {
+            user_tool_manager.clone().spawn_skill_watch(&config);
+        }
         let user_store = Arc::new(UserStore::new(storage.clone()));
         let inner_visible = Arc::new(InnerVisibleService::new(
             config_store.clone(),
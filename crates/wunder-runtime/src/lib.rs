@@ -18,16 +18,20 @@ pub use channels::ChannelHub;
 pub use core::{
     approval, approval_registry, auth, blocking, bounded_queue, command_utils, config,
     config_store, dpi, drawio_config, exec_policy, i18n, logging, long_task, onlyoffice_config,
-    path_utils, repo_assets, runtime_metrics, runtime_tuning, rustls_provider, schemas, shutdown,
-    state, token_utils,
+    path_utils, repo_assets, runtime_metrics, runtime_tuning, rustls_provider, schemas,
+    session_approvals, shutdown, state, token_utils,
 };
-pub use ops::{benchmark, monitor, performance, throughput};
+pub use ops::{benchmark, monitor, performance, throughput, tool_latency};
 pub use orchestrator::constants as orchestrator_constants;
+pub use orchestrator::merge_agent_prompt_with_thread_agents_snapshot;
+pub use orchestrator::orchestrator_error_payload;
 pub use services::{
-    a2a_store, admin_skills, attachment, beeroom_realtime, browser, cron, desktop_lan,
-    desktop_runtime_recovery, doc2md, drawio, goal, history, knowledge, llm, mcp, memory,
-    multimodal_models, onlyoffice, org_units, presence, prompting, ragflow_knowledge, runtime,
-    sim_lab, skills, swarm, tools, user_access, user_leveling, user_plaza, user_prompt_templates,
-    user_store, user_tools, user_world, vector_knowledge, virtual_llm, workspace,
+    a2a_agent_card, a2a_store, admin_skills, attachment, attachment_metadata, beeroom_realtime,
+    browser, cron, desktop_lan, desktop_runtime_recovery, doc2md, drawio, goal, history,
+    history_export, knowledge, llm, mcp, memory, mock_llm, multimodal_models, onlyoffice,
+    org_units, presence, prompt_budget, prompting, ragflow_knowledge, runtime, sandbox_reset,
+    sandbox_session, session_title, sim_lab, skills, subagents, swarm, tools, turn_notifier,
+    user_access, user_leveling, user_plaza, user_prompt_templates, user_store, user_tools,
+    user_world, vector_knowledge, virtual_llm, workspace, workspace_ignore,
 };
 pub use wunder_core as stable_core;
@@ -45,6 +45,9 @@ pub async fn grade_with_judge(
         agent_prompt: None,
         preview_skill: false,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: true,
         enforce_runtime_queue: false,
@@ -566,6 +566,9 @@ async fn run_attempt(
         agent_prompt: None,
         preview_skill: false,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: true,
         enforce_runtime_queue: false,
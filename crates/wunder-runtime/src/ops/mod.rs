@@ -3,3 +3,4 @@ pub mod monitor;
 pub mod performance;
 pub(crate) mod sysinfo_compat;
 pub mod throughput;
+pub mod tool_latency;
@@ -1732,6 +1732,33 @@ impl MonitorState {
         )
     }
 
+    /// Live summary of an in-memory session plus the tools called in its current user round.
+    pub fn get_turn_snapshot(&self, session_id: &str) -> Option<Value> {
+        self.run_guarded(
+            "monitor.get_turn_snapshot",
+            || None,
+            || {
+                let sessions = self.sessions.lock();
+                let record = sessions.get(session_id.trim())?;
+                let tool_calls = record
+                    .events
+                    .iter()
+                    .filter(|event| event.event_type == "tool_call")
+                    .filter(|event| {
+                        parse_i64_value(event.data.get("user_round")) == Some(record.user_rounds)
+                    })
+                    .filter_map(|event| event.data.get("tool").and_then(Value::as_str))
+                    .map(str::to_string)
+                    .collect::<Vec<_>>();
+                let mut snapshot = record.to_summary();
+                if let Value::Object(ref mut map) = snapshot {
+                    map.insert("tool_calls".to_string(), json!(tool_calls));
+                }
+                Some(snapshot)
+            },
+        )
+    }
+
     pub fn list_records(&self) -> Vec<Value> {
         self.run_guarded("monitor.list_records", Vec::new, || {
             let mut map = HashMap::new();
@@ -307,6 +307,7 @@ async fn measure_prompt_build(concurrency: usize, context: &PerformanceContext)
                 &context.workspace.scoped_user_id(&context.user_id, None),
                 None,
                 None,
+                None,
                 false,
             )
             .await;
@@ -619,6 +620,7 @@ fn build_tool_context<'a>(context: &'a PerformanceContext, session_id: &'a str)
         command_sessions: Some(context.state.control.command_sessions.clone()),
         event_emitter: None,
         http: context.http.as_ref(),
+        dry_run: false,
     }
 }
 
@@ -695,6 +697,8 @@ fn build_vector_perf_base(base_name: &str, root: &std::path::Path) -> KnowledgeB
         chunk_overlap: None,
         top_k: None,
         score_threshold: None,
+        search_mode: None,
+        keyword_weight: None,
     }
 }
 
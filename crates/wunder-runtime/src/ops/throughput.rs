@@ -31,6 +31,7 @@ const LATENCY_BUCKETS_MS: [u64; 12] = [
     50, 100, 200, 300, 500, 800, 1000, 1500, 2000, 3000, 5000, 10000,
 ];
 const BUILTIN_QUESTION_SET_NAME: &str = "builtin";
+const CUSTOM_QUESTION_SET_NAME: &str = "custom";
 const BUILTIN_QUESTIONS: [&str; 50] = [
     "用一句话解释什么是大型语言模型。",
     "列出三种常见的数据库索引类型及用途。",
@@ -139,6 +140,14 @@ pub struct ThroughputConfig {
     pub model_name: Option<String>,
     pub request_timeout_s: f64,
     pub max_tokens: Option<u32>,
+    pub skip_tool_calls: bool,
+    /// Answer with the placeholder reply when the target model has no provider configured.
+    pub mock_if_unconfigured: bool,
+    /// Requests issued per concurrency step; with neither this nor `step_duration_s` each
+    /// worker sends exactly one request.
+    pub requests_per_step: Option<u64>,
+    /// Keep each step's workers issuing requests until this many seconds have passed.
+    pub step_duration_s: Option<f64>,
 }
 
 impl ThroughputConfig {
@@ -181,8 +190,26 @@ impl ThroughputConfig {
             model_name,
             request_timeout_s: timeout,
             max_tokens,
+            skip_tool_calls: true,
+            mock_if_unconfigured: false,
+            requests_per_step: None,
+            step_duration_s: None,
         })
     }
+
+    /// Replace the builtin question set with a single fixed prompt.
+    pub fn with_prompt(mut self, prompt: &str) -> Self {
+        let prompt = prompt.trim();
+        if !prompt.is_empty() {
+            self.questions = vec![prompt.to_string()];
+            self.question_set = CUSTOM_QUESTION_SET_NAME.to_string();
+        }
+        self
+    }
+
+    fn keeps_issuing(&self) -> bool {
+        self.requests_per_step.is_some() || self.step_duration_s.is_some()
+    }
 }
 
 fn builtin_questions() -> Vec<String> {
@@ -455,11 +482,21 @@ pub struct ThroughputRunSnapshot {
     pub request_timeout_s: f64,
     #[serde(default)]
     pub max_tokens: Option<u32>,
+    #[serde(default = "default_skip_tool_calls")]
+    pub skip_tool_calls: bool,
+    #[serde(default)]
+    pub requests_per_step: Option<u64>,
+    #[serde(default)]
+    pub step_duration_s: Option<f64>,
     pub started_at: String,
     pub finished_at: Option<String>,
     pub elapsed_s: f64,
 }
 
+fn default_skip_tool_calls() -> bool {
+    true
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ThroughputMetricsSnapshot {
     pub total_requests: u64,
@@ -678,6 +715,9 @@ impl ActiveRun {
                 model_name: self.config.model_name.clone(),
                 request_timeout_s: self.config.request_timeout_s,
                 max_tokens: self.config.max_tokens,
+                skip_tool_calls: self.config.skip_tool_calls,
+                requests_per_step: self.config.requests_per_step,
+                step_duration_s: self.config.step_duration_s,
                 started_at: self.started_at.with_timezone(&Local).to_rfc3339(),
                 finished_at: self
                     .finished_at
@@ -862,20 +902,28 @@ async fn run_supervisor(
     let sequence = config.concurrency_list.clone();
     let questions = Arc::new(config.questions.clone());
     let user_prefix = config.user_id_prefix.clone();
-    let max_tokens = config.max_tokens;
+    let template = Arc::new(RequestTemplate {
+        model_name: config.model_name.clone(),
+        config_overrides: build_request_overrides(
+            config.model_name.as_deref(),
+            config.max_tokens,
+            config.mock_if_unconfigured,
+        ),
+        skip_tool_calls: config.skip_tool_calls,
+        request_timeout_s: config.request_timeout_s,
+    });
     for concurrency in sequence {
         if stop_flag.load(Ordering::Relaxed) {
             break;
         }
         let step_started = Instant::now();
         let step_metrics = Arc::new(ThroughputMetrics::new());
-        let request_timeout_s = config.request_timeout_s;
-        let model_name = config.model_name.clone();
         let run_id_ref = run_id.as_str();
         let user_prefix_ref = user_prefix.as_str();
+        let budget = StepBudget::new(&config, step_started, Arc::clone(&stop_flag));
         let tasks = (0..concurrency)
             .map(|index| {
-                run_request(
+                run_worker(
                     Arc::clone(&orchestrator),
                     Arc::clone(&monitor),
                     run_id_ref,
@@ -883,13 +931,12 @@ async fn run_supervisor(
                     concurrency,
                     index,
                     Arc::clone(&questions),
-                    model_name.clone(),
-                    request_timeout_s,
-                    max_tokens,
+                    Arc::clone(&template),
+                    &budget,
                 )
             })
             .collect::<Vec<_>>();
-        let results = join_all(tasks).await;
+        let results = join_all(tasks).await.into_iter().flatten();
         let mut speed_acc = SpeedAccumulator::default();
         let mut decode_tokens_total_by_request = 0u64;
         let mut decode_speed_sum_by_request = 0.0;
@@ -1090,6 +1137,88 @@ async fn run_supervisor(
     }
 }
 
+struct RequestTemplate {
+    model_name: Option<String>,
+    config_overrides: Option<Value>,
+    skip_tool_calls: bool,
+    request_timeout_s: f64,
+}
+
+/// Decides whether a step's workers keep issuing requests once their first one returns.
+struct StepBudget {
+    issued: AtomicU64,
+    limit: Option<u64>,
+    deadline: Option<Instant>,
+    keeps_issuing: bool,
+    stop_flag: Arc<AtomicBool>,
+}
+
+impl StepBudget {
+    fn new(config: &ThroughputConfig, step_started: Instant, stop_flag: Arc<AtomicBool>) -> Self {
+        let deadline = config
+            .step_duration_s
+            .filter(|value| value.is_finite() && *value > 0.0)
+            .map(|value| step_started + Duration::from_secs_f64(value));
+        Self {
+            issued: AtomicU64::new(0),
+            limit: config.requests_per_step.filter(|value| *value > 0),
+            deadline,
+            keeps_issuing: config.keeps_issuing(),
+            stop_flag,
+        }
+    }
+
+    fn take(&self, round: usize) -> bool {
+        if round > 0 && (!self.keeps_issuing || self.stop_flag.load(Ordering::Relaxed)) {
+            return false;
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return false;
+        }
+        match self.limit {
+            Some(limit) => self.issued.fetch_add(1, Ordering::Relaxed) < limit,
+            None => true,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_worker(
+    orchestrator: Arc<Orchestrator>,
+    monitor: Arc<MonitorState>,
+    run_id: &str,
+    user_prefix: &str,
+    concurrency: usize,
+    index: usize,
+    questions: Arc<Vec<String>>,
+    template: Arc<RequestTemplate>,
+    budget: &StepBudget,
+) -> Vec<RequestOutcome> {
+    let mut outcomes = Vec::new();
+    let mut round = 0;
+    while budget.take(round) {
+        outcomes.push(
+            run_request(
+                Arc::clone(&orchestrator),
+                Arc::clone(&monitor),
+                run_id,
+                user_prefix,
+                concurrency,
+                index,
+                round,
+                Arc::clone(&questions),
+                &template,
+            )
+            .await,
+        );
+        round += 1;
+    }
+    outcomes
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn run_request(
     orchestrator: Arc<Orchestrator>,
@@ -1098,39 +1227,46 @@ async fn run_request(
     user_prefix: &str,
     concurrency: usize,
     index: usize,
+    round: usize,
     questions: Arc<Vec<String>>,
-    model_name: Option<String>,
-    request_timeout_s: f64,
-    max_tokens: Option<u32>,
+    template: &RequestTemplate,
 ) -> RequestOutcome {
     let user_index = index + 1;
     let user_id = format!("{user_prefix}-{concurrency}-{user_index}");
-    let session_id = format!("throughput_{run_id}_{concurrency}_{user_index}");
+    // Later rounds get their own session so history never grows across requests.
+    let session_id = if round == 0 {
+        format!("throughput_{run_id}_{concurrency}_{user_index}")
+    } else {
+        format!("throughput_{run_id}_{concurrency}_{user_index}_{round}")
+    };
     let mut seed = seed_for_user(&user_id);
     let question = select_question(&questions, &mut seed).to_string();
-    let config_overrides = build_max_tokens_override(model_name.as_deref(), max_tokens);
     let request = WunderRequest {
         user_id: user_id.clone(),
         question,
         client_message_id: None,
         tool_names: Vec::new(),
-        skip_tool_calls: true,
+        skip_tool_calls: template.skip_tool_calls,
         stream: true,
         debug_payload: true,
         session_id: Some(session_id.clone()),
         agent_id: None,
         workspace_container_id: None,
-        model_name,
+        model_name: template.model_name.clone(),
         language: None,
-        config_overrides,
+        config_overrides: template.config_overrides.clone(),
         agent_prompt: None,
         preview_skill: false,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: true,
         enforce_runtime_queue: false,
         approval_tx: None,
     };
+    let request_timeout_s = template.request_timeout_s;
     let started = Instant::now();
     let result = if request_timeout_s > 0.0 {
         tokio::time::timeout(
@@ -1178,18 +1314,29 @@ fn extract_session_speed(detail: Option<Value>) -> LlmSpeedSummary {
     LlmSpeedSummary::from_session_payload(detail.as_ref())
 }
 
-fn build_max_tokens_override(model_name: Option<&str>, max_tokens: Option<u32>) -> Option<Value> {
-    let max_tokens = max_tokens.filter(|value| *value > 0)?;
+fn build_request_overrides(
+    model_name: Option<&str>,
+    max_tokens: Option<u32>,
+    mock_if_unconfigured: bool,
+) -> Option<Value> {
     let model_name = model_name?.trim();
     if model_name.is_empty() {
         return None;
     }
+    let mut model = serde_json::Map::new();
+    if let Some(max_tokens) = max_tokens.filter(|value| *value > 0) {
+        model.insert("max_output".to_string(), json!(max_tokens));
+    }
+    if mock_if_unconfigured {
+        model.insert("mock_if_unconfigured".to_string(), json!(true));
+    }
+    if model.is_empty() {
+        return None;
+    }
     Some(json!({
         "llm": {
             "models": {
-                model_name: {
-                    "max_output": max_tokens
-                }
+                model_name: model
             }
         }
     }))
@@ -1321,8 +1468,49 @@ async fn load_report(run_id: &str) -> Result<ThroughputReport, String> {
 }
 #[cfg(test)]
 mod tests {
-    use super::SpeedAccumulator;
+    use super::{build_request_overrides, SpeedAccumulator, StepBudget, ThroughputConfig};
     use crate::core::llm_speed::LlmSpeedSummary;
+    use serde_json::json;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    #[test]
+    fn step_budget_defaults_to_one_request_per_worker() {
+        let config = ThroughputConfig::new(vec![2], None, None, None, None).expect("config");
+        let budget = StepBudget::new(&config, Instant::now(), Arc::new(AtomicBool::new(false)));
+        assert!(budget.take(0));
+        assert!(!budget.take(1));
+    }
+
+    #[test]
+    fn step_budget_caps_requests_across_workers() {
+        let mut config = ThroughputConfig::new(vec![2], None, None, None, None).expect("config");
+        config.requests_per_step = Some(3);
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let budget = StepBudget::new(&config, Instant::now(), Arc::clone(&stop_flag));
+        let issued = (0..5).filter(|round| budget.take(*round)).count();
+        assert_eq!(issued, 3);
+
+        config.requests_per_step = None;
+        config.step_duration_s = Some(60.0);
+        let budget = StepBudget::new(&config, Instant::now(), Arc::clone(&stop_flag));
+        assert!(budget.take(4));
+        stop_flag.store(true, std::sync::atomic::Ordering::Relaxed);
+        assert!(!budget.take(5));
+    }
+
+    #[test]
+    fn request_overrides_target_the_named_model() {
+        assert_eq!(build_request_overrides(None, Some(64), true), None);
+        assert_eq!(build_request_overrides(Some("m"), None, false), None);
+        assert_eq!(
+            build_request_overrides(Some("m"), Some(64), true),
+            Some(json!({
+                "llm": { "models": { "m": { "max_output": 64, "mock_if_unconfigured": true } } }
+            }))
+        );
+    }
 
     #[test]
     fn single_prefill_speed_prefers_request_average() {
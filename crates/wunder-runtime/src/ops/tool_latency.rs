@@ -0,0 +1,170 @@
+// 工具耗时报表：按持久化的 tool_result 事件汇总各工具调用次数、平均/P95 耗时与失败率。
+use crate::storage::StorageBackend;
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ToolLatencyStats {
+    pub tool: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+    /// `None` when none of the calls recorded a duration.
+    pub mean_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+}
+
+/// Persisted `tool_result` stream events of a session, in event order.
+pub fn load_tool_result_events(
+    storage: &dyn StorageBackend,
+    session_id: &str,
+) -> Result<Vec<Value>> {
+    let max_event_id = storage.get_max_stream_event_id(session_id)?;
+    if max_event_id <= 0 {
+        return Ok(Vec::new());
+    }
+    let limit = max_event_id.saturating_add(64).max(1);
+    Ok(storage
+        .load_stream_events(session_id, 0, limit)?
+        .into_iter()
+        .filter(|record| record.get("event").and_then(Value::as_str) == Some("tool_result"))
+        .collect())
+}
+
+/// Per-tool stats over persisted stream event records, slowest tool (by mean) first.
+pub fn aggregate_tool_latencies<'a, I>(records: I) -> Vec<ToolLatencyStats>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    #[derive(Default)]
+    struct Bucket {
+        calls: u64,
+        errors: u64,
+        durations: Vec<f64>,
+    }
+
+    let mut buckets: BTreeMap<String, Bucket> = BTreeMap::new();
+    for record in records {
+        if record.get("event").and_then(Value::as_str) != Some("tool_result") {
+            continue;
+        }
+        let data = record.get("data").unwrap_or(record);
+        let payload = data.get("data").unwrap_or(data);
+        let Some(tool) = payload
+            .get("tool")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        else {
+            continue;
+        };
+        let bucket = buckets.entry(tool.to_string()).or_default();
+        bucket.calls += 1;
+        if !payload.get("ok").and_then(Value::as_bool).unwrap_or(true) {
+            bucket.errors += 1;
+        }
+        if let Some(duration) = payload
+            .pointer("/meta/duration_ms")
+            .and_then(Value::as_f64)
+            .filter(|value| *value >= 0.0)
+        {
+            bucket.durations.push(duration);
+        }
+    }
+
+    let mut stats = buckets
+        .into_iter()
+        .map(|(tool, mut bucket)| {
+            bucket.durations.sort_by(f64::total_cmp);
+            let mean_ms = (!bucket.durations.is_empty()).then(|| {
+                round_ms(bucket.durations.iter().sum::<f64>() / bucket.durations.len() as f64)
+            });
+            ToolLatencyStats {
+                tool,
+                calls: bucket.calls,
+                errors: bucket.errors,
+                error_rate: round_ratio(bucket.errors as f64 / bucket.calls as f64),
+                mean_ms,
+                p95_ms: p95(&bucket.durations),
+            }
+        })
+        .collect::<Vec<_>>();
+    stats.sort_by(|left, right| {
+        right
+            .mean_ms
+            .unwrap_or(-1.0)
+            .total_cmp(&left.mean_ms.unwrap_or(-1.0))
+            .then_with(|| left.tool.cmp(&right.tool))
+    });
+    stats
+}
+
+/// Nearest-rank p95 over already sorted durations.
+fn p95(sorted: &[f64]) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (sorted.len() as f64 * 0.95).ceil() as usize;
+    Some(round_ms(sorted[rank.clamp(1, sorted.len()) - 1]))
+}
+
+fn round_ms(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn round_ratio(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_result(tool: &str, ok: bool, duration_ms: Option<i64>) -> Value {
+        let mut data = json!({ "tool": tool, "ok": ok, "data": {} });
+        if let Some(duration_ms) = duration_ms {
+            data["meta"] = json!({ "duration_ms": duration_ms });
+        }
+        json!({
+            "event": "tool_result",
+            "data": { "session_id": "sess_perf", "timestamp": "2026-01-01T00:00:00Z", "data": data },
+        })
+    }
+
+    #[test]
+    fn aggregates_counts_latency_and_error_rate_per_tool() {
+        let mut events = (1..=20)
+            .map(|index| tool_result("execute_command", index != 20, Some(index * 10)))
+            .collect::<Vec<_>>();
+        events.push(tool_result("read_file", true, Some(4)));
+        events.push(tool_result("read_file", true, None));
+        events.push(json!({ "event": "tool_call", "data": { "data": { "tool": "read_file" } } }));
+
+        let stats = aggregate_tool_latencies(&events);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats[0],
+            ToolLatencyStats {
+                tool: "execute_command".to_string(),
+                calls: 20,
+                errors: 1,
+                error_rate: 0.05,
+                mean_ms: Some(105.0),
+                p95_ms: Some(190.0),
+            }
+        );
+        assert_eq!(stats[1].tool, "read_file");
+        assert_eq!(stats[1].calls, 2);
+        assert_eq!(stats[1].errors, 0);
+        assert_eq!(stats[1].mean_ms, Some(4.0));
+        assert_eq!(stats[1].p95_ms, Some(4.0));
+    }
+
+    #[test]
+    fn no_events_yields_empty_report() {
+        assert!(aggregate_tool_latencies(&Vec::<Value>::new()).is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+// 附件入库：请求开启 remember_attachments 时，本轮开始前把文本附件以 pending 状态写入用户的 attachments 知识库（向量化在后台进行）并推送结果事件。
+use super::*;
+use crate::services::attachment_knowledge::{remember_attachments, ATTACHMENT_KNOWLEDGE_BASE};
+
+impl Orchestrator {
+    /// Failures only warn and emit an empty result; the turn itself still runs with the
+    /// attachments inline.
+    pub(super) async fn remember_turn_attachments(
+        &self,
+        config: &Config,
+        user_id: &str,
+        session_id: &str,
+        attachments: Option<&[AttachmentPayload]>,
+        emitter: &EventEmitter,
+    ) {
+        let Some(attachments) = attachments.filter(|items| !items.is_empty()) else {
+            return;
+        };
+        let payload = match remember_attachments(
+            config,
+            self.storage.clone(),
+            self.user_tool_manager.store(),
+            user_id,
+            session_id,
+            attachments,
+        )
+        .await
+        {
+            Ok(documents) => json!({
+                "knowledge_base": ATTACHMENT_KNOWLEDGE_BASE,
+                "documents": documents,
+            }),
+            Err(err) => {
+                warn!("remember attachments failed for user {user_id} session {session_id}: {err}");
+                json!({
+                    "knowledge_base": ATTACHMENT_KNOWLEDGE_BASE,
+                    "documents": [],
+                    "error": err.to_string(),
+                })
+            }
+        };
+        emitter.emit("attachments_remembered", payload).await;
+    }
+}
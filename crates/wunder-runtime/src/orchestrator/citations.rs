@@ -0,0 +1,94 @@
+// 知识库引用：请求开启 cite 时收集本轮知识检索命中的文档与分块，随 final 事件返回 sources。
+use super::tool_result_payload::ToolResultPayload;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+/// Knowledge sources retrieved during one turn, in retrieval order and without duplicates.
+#[derive(Default)]
+pub(super) struct KnowledgeCitations {
+    sources: Vec<Value>,
+    seen: HashSet<String>,
+}
+
+impl KnowledgeCitations {
+    /// Record the documents of a knowledge tool result; other tool results are ignored.
+    pub(super) fn collect(&mut self, result: &ToolResultPayload) {
+        if !result.ok {
+            return;
+        }
+        let Some(base) = result.data.get("knowledge_base").and_then(Value::as_str) else {
+            return;
+        };
+        let Some(documents) = result.data.get("documents").and_then(Value::as_array) else {
+            return;
+        };
+        for document in documents {
+            let source = build_source(base, document);
+            if self.seen.insert(source.to_string()) {
+                self.sources.push(source);
+            }
+        }
+    }
+
+    /// Add `sources` to a final event payload.
+    pub(super) fn insert_into(&self, payload: &mut Value) {
+        if let Value::Object(map) = payload {
+            map.insert("sources".to_string(), Value::Array(self.sources.clone()));
+        }
+    }
+}
+
+/// Reference fields only; the chunk text already went to the model and would bloat the event.
+fn build_source(base: &str, document: &Value) -> Value {
+    let mut source = Map::new();
+    source.insert("knowledge_base".to_string(), json!(base));
+    for key in ["doc_id", "document", "chunk_index", "section_path", "score"] {
+        if let Some(value) = document.get(key).filter(|value| !value.is_null()) {
+            source.insert(key.to_string(), value.clone());
+        }
+    }
+    Value::Object(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knowledge_results_become_deduplicated_sources() {
+        let knowledge = ToolResultPayload::from_value(json!({
+            "ok": true,
+            "action": "knowledge",
+            "state": "completed",
+            "summary": "Retrieved 2 knowledge snippets from manuals.",
+            "data": {
+                "knowledge_base": "manuals",
+                "vector": true,
+                "count": 2,
+                "documents": [
+                    { "doc_id": "doc-1", "document": "guide.md", "chunk_index": 3, "content": "a", "score": 0.81 },
+                    { "doc_id": "doc-2", "document": "faq.md", "chunk_index": 0, "content": "b", "score": 0.64 },
+                ],
+            },
+        }));
+        let other = ToolResultPayload::from_value(json!({
+            "ok": true,
+            "data": { "documents": [{ "document": "not-knowledge.md" }] },
+        }));
+
+        let mut citations = KnowledgeCitations::default();
+        citations.collect(&knowledge);
+        citations.collect(&other);
+        citations.collect(&knowledge);
+
+        let mut payload = json!({ "answer": "done" });
+        citations.insert_into(&mut payload);
+        assert_eq!(
+            payload["sources"],
+            json!([
+                { "knowledge_base": "manuals", "doc_id": "doc-1", "document": "guide.md", "chunk_index": 3, "score": 0.81 },
+                { "knowledge_base": "manuals", "doc_id": "doc-2", "document": "faq.md", "chunk_index": 0, "score": 0.64 },
+            ])
+        );
+    }
+}
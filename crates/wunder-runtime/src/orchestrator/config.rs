@@ -6,12 +6,21 @@ impl Orchestrator {
         let Some(overrides) = overrides else {
             return base;
         };
-        let mut base_value = serde_json::to_value(&base).unwrap_or(Value::Null);
-        merge_json(&mut base_value, overrides);
-        serde_json::from_value::<Config>(base_value).unwrap_or(base)
+        apply_config_overrides(&base, overrides).unwrap_or(base)
     }
 }
 
+/// Deep-merge `overrides` into `base`. Errors when the merged value no longer deserializes as
+/// a `Config`; the orchestrator falls back to `base`, the HTTP boundary reports it instead.
+pub(crate) fn apply_config_overrides(
+    base: &Config,
+    overrides: &Value,
+) -> Result<Config, serde_json::Error> {
+    let mut base_value = serde_json::to_value(base)?;
+    merge_json(&mut base_value, overrides);
+    serde_json::from_value::<Config>(base_value)
+}
+
 fn merge_json(base: &mut Value, override_value: &Value) {
     match (base, override_value) {
         (Value::Object(base_map), Value::Object(override_map)) => {
@@ -0,0 +1,208 @@
+// 发送前上下文裁剪：系统提示 + 历史超出 max_context（扣除回复预留）时，先丢最旧历史，仍超出再截断大工具结果。
+use super::*;
+use crate::orchestrator_constants::DEFAULT_MAX_OUTPUT_TOKENS;
+
+/// Prompt budget for one model call: `max_context` minus the tokens reserved for the reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ContextBudget {
+    pub(super) max_context: i64,
+    pub(super) reserve: i64,
+}
+
+impl ContextBudget {
+    /// `None` when the model has no `max_context`, in which case nothing is trimmed.
+    pub(super) fn from_config(config: &LlmModelConfig) -> Option<Self> {
+        let max_context = i64::from(config.max_context.filter(|value| *value > 0)?);
+        let reserve = config
+            .context_reserve_tokens
+            .or(config.max_output)
+            .filter(|value| *value > 0)
+            .map(i64::from)
+            .unwrap_or(DEFAULT_MAX_OUTPUT_TOKENS);
+        Some(Self {
+            max_context,
+            reserve,
+        })
+    }
+
+    pub(super) fn prompt_tokens(self) -> i64 {
+        (self.max_context - self.reserve).max(1)
+    }
+}
+
+pub(super) struct ContextTrim {
+    pub(super) messages: Vec<Value>,
+    pub(super) dropped_messages: usize,
+    pub(super) truncated_messages: usize,
+    pub(super) tokens_before: i64,
+    pub(super) tokens_after: i64,
+}
+
+impl ContextTrim {
+    pub(super) fn event_payload(&self, budget: ContextBudget, tool_tokens: i64) -> Value {
+        json!({
+            "max_context": budget.max_context,
+            "reserve_tokens": budget.reserve,
+            "budget_tokens": budget.prompt_tokens(),
+            "tool_tokens": tool_tokens,
+            "tokens_before": self.tokens_before,
+            "tokens_after": self.tokens_after,
+            "dropped_messages": self.dropped_messages,
+            "truncated_messages": self.truncated_messages,
+        })
+    }
+}
+
+impl Orchestrator {
+    /// Fit `messages` into `limit` tokens right before sending. System messages and the current
+    /// user turn are kept; older history goes first, then oversized tool results are shortened.
+    /// Returns `None` when the messages already fit. `counter` matches the target model.
+    pub(super) fn trim_messages_to_context(
+        &self,
+        messages: &[Value],
+        limit: i64,
+        counter: TokenCounter,
+    ) -> Option<ContextTrim> {
+        let tokens_before = counter.messages_tokens(messages);
+        if tokens_before <= limit {
+            return None;
+        }
+        let (kept, dropped_messages) = drop_oldest_history(messages, limit, counter);
+        let before_shrink = kept.clone();
+        let messages = self.shrink_messages_to_limit(kept, limit, counter);
+        let truncated_messages = before_shrink
+            .iter()
+            .zip(messages.iter())
+            .filter(|(before, after)| before != after)
+            .count();
+        let tokens_after = counter.messages_tokens(&messages);
+        Some(ContextTrim {
+            messages,
+            dropped_messages,
+            truncated_messages,
+            tokens_before,
+            tokens_after,
+        })
+    }
+}
+
+/// Drop history oldest-first until `messages` fit `limit`. Tool results are dropped together
+/// with the call that produced them so no orphaned result leads the remaining history.
+fn drop_oldest_history(
+    messages: &[Value],
+    limit: i64,
+    counter: TokenCounter,
+) -> (Vec<Value>, usize) {
+    let current_turn_start =
+        Orchestrator::locate_current_user_index(messages).unwrap_or(messages.len());
+    let mut keep = vec![true; messages.len()];
+    let mut total = counter.messages_tokens(messages);
+    let mut dropped = 0;
+    let mut index = 0;
+    while total > limit && index < current_turn_start {
+        if message_role(&messages[index]) == "system" {
+            index += 1;
+            continue;
+        }
+        keep[index] = false;
+        total -= counter.message_tokens(&messages[index]);
+        dropped += 1;
+        index += 1;
+        while index < current_turn_start && is_tool_result(&messages[index]) {
+            keep[index] = false;
+            total -= counter.message_tokens(&messages[index]);
+            dropped += 1;
+            index += 1;
+        }
+    }
+    let kept = messages
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(message, _)| message)
+        .cloned()
+        .collect();
+    (kept, dropped)
+}
+
+fn message_role(message: &Value) -> &str {
+    message.get("role").and_then(Value::as_str).unwrap_or("")
+}
+
+fn is_tool_result(message: &Value) -> bool {
+    let role = message_role(message);
+    role == "tool"
+        || Orchestrator::is_observation_message(
+            role,
+            message.get("content").unwrap_or(&Value::Null),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: &str) -> Value {
+        json!({ "role": role, "content": content })
+    }
+
+    #[test]
+    fn oldest_history_is_dropped_until_the_prompt_fits() {
+        let filler = "x".repeat(400);
+        let messages = vec![
+            message("system", "rules"),
+            message("user", &filler),
+            json!({ "role": "assistant", "content": "", "tool_calls": [{ "id": "c1" }] }),
+            json!({ "role": "tool", "content": filler, "tool_call_id": "c1" }),
+            message("assistant", &filler),
+            message("user", &filler),
+            message("assistant", "short"),
+            message("user", "current question"),
+        ];
+        let total = estimate_messages_tokens(&messages);
+
+        let (kept, dropped) = drop_oldest_history(&messages, total - 100, TokenCounter::GENERIC);
+        assert_eq!(dropped, 1);
+        assert_eq!(kept[0], messages[0]);
+        assert_eq!(kept.last(), messages.last());
+        assert!(estimate_messages_tokens(&kept) <= total - 100);
+
+        let (kept, dropped) = drop_oldest_history(&messages, total - 150, TokenCounter::GENERIC);
+        assert_eq!(dropped, 3, "tool result is dropped with its call");
+        assert_eq!(message_role(&kept[1]), "assistant");
+        assert!(kept[1].get("tool_calls").is_none());
+    }
+
+    #[test]
+    fn current_turn_and_system_prompt_are_never_dropped() {
+        let messages = vec![
+            message("system", &"s".repeat(800)),
+            message("user", "old"),
+            message("user", &"q".repeat(800)),
+        ];
+        let (kept, dropped) = drop_oldest_history(&messages, 10, TokenCounter::GENERIC);
+        assert_eq!(dropped, 1);
+        assert_eq!(kept, vec![messages[0].clone(), messages[2].clone()]);
+    }
+
+    #[test]
+    fn reserve_defaults_to_max_output() {
+        let config = LlmModelConfig {
+            max_context: Some(32_000),
+            max_output: Some(2_000),
+            ..Default::default()
+        };
+        let budget = ContextBudget::from_config(&config).expect("budget");
+        assert_eq!(budget.prompt_tokens(), 30_000);
+
+        let reserved = LlmModelConfig {
+            context_reserve_tokens: Some(6_000),
+            ..config
+        };
+        assert_eq!(
+            ContextBudget::from_config(&reserved).map(ContextBudget::prompt_tokens),
+            Some(26_000)
+        );
+        assert!(ContextBudget::from_config(&LlmModelConfig::default()).is_none());
+    }
+}
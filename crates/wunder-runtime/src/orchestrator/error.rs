@@ -176,9 +176,19 @@ impl OrchestratorError {
         Self::new("INTERNAL_ERROR", message, None)
     }
 
-    pub(super) fn user_token_insufficient(status: UserTokenBalanceStatus) -> Self {
-        let message = i18n::t("error.user_token_insufficient");
-        Self::new(
+    /// Over-quota rejection; `Retry-After` and the message point at the next daily grant.
+    pub(crate) fn user_token_insufficient(status: UserTokenBalanceStatus) -> Self {
+        let now = Local::now();
+        let resets_at = crate::user_access::next_quota_reset(now);
+        let retry_after_s = (resets_at - now).num_seconds().max(1);
+        let message = i18n::t_with_params(
+            "error.user_token_exhausted_until",
+            &HashMap::from([(
+                "resets_at".to_string(),
+                resets_at.format("%Y-%m-%d %H:%M").to_string(),
+            )]),
+        );
+        let mut error = Self::new(
             "USER_TOKEN_INSUFFICIENT",
             message,
             Some(json!({
@@ -187,8 +197,20 @@ impl OrchestratorError {
                 "token_used_total": status.used_total,
                 "daily_token_grant": status.daily_grant,
                 "last_token_grant_date": status.last_grant_date,
+                "resets_at": resets_at.to_rfc3339(),
+                "retry_after_s": retry_after_s,
             })),
-        )
+        );
+        error.meta.retry_after_ms = Some(retry_after_s as u64 * 1000);
+        error
+    }
+
+    /// Replace the detail payload when `detail` is present.
+    pub(super) fn with_detail(mut self, detail: Option<Value>) -> Self {
+        if detail.is_some() {
+            self.detail = detail;
+        }
+        self
     }
 
     pub(crate) fn code(&self) -> &'static str {
@@ -299,6 +321,13 @@ impl std::fmt::Display for OrchestratorError {
 
 impl std::error::Error for OrchestratorError {}
 
+/// `code/message/error_meta/detail` of a failed `Orchestrator::run`, for callers outside the
+/// crate that only hold the `anyhow::Error`. `None` for errors raised elsewhere.
+pub fn orchestrator_error_payload(err: &anyhow::Error) -> Option<Value> {
+    err.downcast_ref::<OrchestratorError>()
+        .map(OrchestratorError::to_payload)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,6 +346,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn user_token_insufficient_points_retry_after_at_the_next_reset() {
+        let err = OrchestratorError::user_token_insufficient(UserTokenBalanceStatus {
+            balance: 0,
+            granted_total: 1000,
+            used_total: 1000,
+            daily_grant: 1000,
+            last_grant_date: Some(UserStore::today_string()),
+            allowed: false,
+            overspent_tokens: 0,
+        });
+        let payload = err.to_payload();
+        let retry_after_ms = err.retry_after_ms().expect("retry after");
+        assert!((1000..=25 * 3600 * 1000).contains(&retry_after_ms));
+        assert_eq!(
+            payload["detail"]["retry_after_s"],
+            json!(retry_after_ms / 1000)
+        );
+        assert!(payload["detail"]["resets_at"].is_string());
+        assert_eq!(err.recovery_action(), "await_quota");
+    }
+
     #[test]
     fn exception_context_window_exceeded_requests_compaction() {
         let err = OrchestratorError::context_window_exceeded("context exceeded".to_string());
@@ -147,11 +147,17 @@ fn should_persist_stream_event(event_type: &str) -> bool {
         event_type,
         "progress"
             | "llm_request"
+            | "llm_rate_limited"
+            | "context_trimmed"
             | "llm_response"
             | "bad_tool_call_retry"
+            | "stream_interrupted"
             | "knowledge_request"
+            | "attachments_remembered"
             | "compaction"
             | "tool_call"
+            | "tool_started"
+            | "tool_finished"
             | "tool_result"
             | "command_session_start"
             | "command_session_status"
@@ -163,9 +169,11 @@ fn should_persist_stream_event(event_type: &str) -> bool {
             | "workspace_update"
             | "plan_update"
             | "question_panel"
+            | "max_rounds_reached"
             | "thread_control"
             | "llm_output_delta"
             | "llm_output"
+            | "reasoning"
             | "context_usage"
             | "token_balance"
             | "quota_usage"
@@ -969,6 +977,12 @@ mod tests {
         assert!(!should_persist_stream_event("command_session_delta"));
     }
 
+    #[test]
+    fn tool_run_events_survive_resume() {
+        assert!(should_persist_stream_event("tool_started"));
+        assert!(should_persist_stream_event("tool_finished"));
+    }
+
     #[test]
     fn online_llm_delta_uses_backpressure_instead_of_lossy_overflow() {
         assert!(should_backpressure_online_stream_event("llm_output_delta"));
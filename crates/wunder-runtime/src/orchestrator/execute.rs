@@ -1,9 +1,11 @@
+use super::citations::KnowledgeCitations;
 use super::context::normalize_model_context_message;
 use super::retry_governor::RetryGovernor;
 use super::tool_calls::ToolCall;
 use super::*;
 use crate::core::llm_speed::TurnDecodeSpeedAccumulator;
 use crate::core::long_task;
+use crate::llm::resolve_response_format;
 use crate::services::chat_attachments::persist_user_chat_attachments;
 use crate::services::goal;
 use crate::services::orchestration_context::session_orchestration_run_root;
@@ -11,6 +13,13 @@ use crate::services::subagents;
 use crate::services::tools::sessions_yield_tool;
 
 use super::execute_support::*;
+use super::round_limit::RoundLimit;
+use super::structured_answer::{
+    check_structured_answer, structured_answer_event_payload, StructuredAnswerCheck,
+};
+
+/// `source` reported to turn notifiers registered on the orchestrator.
+const TURN_NOTIFICATION_SOURCE: &str = "runtime";
 
 enum ToolEventForward {
     Event { event_type: String, data: Value },
@@ -95,7 +104,11 @@ impl Orchestrator {
         } else {
             request_config.server.max_active_sessions
         };
-        let limiter = RequestLimiter::new(self.storage.clone(), max_active_sessions);
+        let limiter = RequestLimiter::new(
+            self.storage.clone(),
+            max_active_sessions,
+            AdmissionLimits::from_config(&request_config.server),
+        );
         let session_id = prepared.session_id.clone();
         let user_id = prepared.user_id.clone();
         let question = prepared.question.clone();
@@ -125,30 +138,36 @@ impl Orchestrator {
 
         let result = async {
             let mut lock_agent_id = prepared.agent_id.clone().unwrap_or_default();
-            if !is_admin {
-                let storage = self.storage.clone();
-                let lock_user = user_id.clone();
-                let lock_session = session_id.clone();
-                let lock_session_query = lock_session.clone();
-                if let Ok(Some(record)) =
-                    crate::core::blocking::run_db("orchestrator.execute.lock_session", move || {
-                        storage.get_chat_session(&lock_user, &lock_session_query)
-                    })
-                    .await
-                {
-                    if record.parent_session_id.is_some() {
-                        lock_agent_id = format!("subagent:{lock_session}");
-                    }
-                }
+            let storage = self.storage.clone();
+            let lock_user = user_id.clone();
+            let lock_session = session_id.clone();
+            let lock_session_query = lock_session.clone();
+            let is_subagent_session =
+                crate::core::blocking::run_db("orchestrator.execute.lock_session", move || {
+                    storage.get_chat_session(&lock_user, &lock_session_query)
+                })
+                .await
+                .ok()
+                .flatten()
+                .is_some_and(|record| record.parent_session_id.is_some());
+            if is_subagent_session && !is_admin {
+                lock_agent_id = format!("subagent:{lock_session}");
             }
-            let ok = limiter
-                .acquire(&session_id, &user_id, &lock_agent_id, prepared.allow_queue)
+            let admission_model =
+                admission_model_key(&request_config, prepared.model_name.as_deref());
+            let admission = limiter
+                .acquire(
+                    &session_id,
+                    &user_id,
+                    &lock_agent_id,
+                    &admission_model,
+                    prepared.allow_queue,
+                    &emitter,
+                )
                 .await
                 .map_err(|err| OrchestratorError::internal(err.to_string()))?;
-            if !ok {
-                return Err(OrchestratorError::user_busy(i18n::t(
-                    "error.user_session_busy",
-                )));
+            if let Some(rejection) = admission.rejection() {
+                return Err(rejection);
             }
             acquired = true;
 
@@ -232,6 +251,16 @@ impl Orchestrator {
                 request_round.insert_into(map);
             }
             emitter.emit("progress", start_payload).await;
+            if prepared.remember_attachments {
+                self.remember_turn_attachments(
+                    &request_config,
+                    &user_id,
+                    &session_id,
+                    prepared.attachments.as_deref(),
+                    &emitter,
+                )
+                .await;
+            }
 
             let config = request_config.clone();
             let log_payload =
@@ -244,9 +273,14 @@ impl Orchestrator {
                 self.skills.clone()
             };
             let skills_snapshot = skills.read().await.clone();
-            let user_tool_bindings =
+            // Sub-agents only see the skills the user marked as shared.
+            let user_tool_bindings = if is_subagent_session {
                 self.user_tool_manager
-                    .build_bindings(&config, &skills_snapshot, &user_id);
+                    .build_subagent_bindings(&config, &skills_snapshot, &user_id)
+            } else {
+                self.user_tool_manager
+                    .build_bindings(&config, &skills_snapshot, &user_id)
+            };
             let private_root = self.inner_visible.private_root(&user_id);
             let mut extra_tool_roots = vec![private_root];
             if let Some(orchestration_run_root) = session_orchestration_run_root(
@@ -386,7 +420,7 @@ impl Orchestrator {
 
             let desktop_unlimited_rounds =
                 config.server.mode.trim().eq_ignore_ascii_case("desktop");
-            let max_rounds = if is_admin || desktop_unlimited_rounds {
+            let configured_max_rounds = if is_admin || desktop_unlimited_rounds {
                 None
             } else {
                 Some(resolve_non_admin_max_rounds(
@@ -394,6 +428,8 @@ impl Orchestrator {
                     prepared.skip_tool_calls,
                 ))
             };
+            let mut round_limit = RoundLimit::new(configured_max_rounds, prepared.max_rounds);
+            let mut citations = prepared.cite.then(KnowledgeCitations::default);
             let mut reached_max_rounds = false;
             let goal_turn_started_at = Instant::now();
             let mut round_usage = TokenUsage {
@@ -420,6 +456,7 @@ impl Orchestrator {
             let mut reroute_notice_fingerprints: HashSet<String> = HashSet::new();
             let mut invalid_tool_call_reroute_count = 0_u32;
             let mut empty_final_answer_reroute_count = 0_u32;
+            let mut structured_answer_retry_count = 0_u32;
             let memory_manager_tool_name = resolve_tool_name("memory_manager");
             let tool_budget_limits = ToolBudgetLimits {
                 total: DEFAULT_TOOL_CALL_BUDGET_PER_TURN,
@@ -450,11 +487,9 @@ impl Orchestrator {
                 .load_session_context_limit_hint_async(&user_id, &session_id)
                 .await;
             loop {
-                if let Some(max_rounds) = max_rounds {
-                    if model_round >= max_rounds {
-                        reached_max_rounds = true;
-                        break;
-                    }
+                if round_limit.is_reached(model_round) {
+                    reached_max_rounds = true;
+                    break;
                 }
                 model_round += 1;
                 let round_info = RoundInfo::new(user_round, model_round);
@@ -575,7 +610,7 @@ impl Orchestrator {
                 }
 
                 let mut overflow_recovery_attempts = 0_u32;
-                let (content, reasoning, usage, tool_calls_payload, round_speed) = loop {
+                let (content, reasoning, usage, tool_calls_payload, round_speed, partial) = loop {
                     match self
                         .call_llm(
                             &llm_config,
@@ -591,6 +626,7 @@ impl Orchestrator {
                             log_payload,
                             tools_payload,
                             None,
+                            resolve_response_format(&llm_config),
                         )
                         .await
                     {
@@ -879,6 +915,44 @@ impl Orchestrator {
                             ),
                         ));
                     }
+                    match check_structured_answer(
+                        &llm_config,
+                        &answer,
+                        structured_answer_retry_count,
+                    ) {
+                        StructuredAnswerCheck::Accepted => {}
+                        StructuredAnswerCheck::Retry { error, notice } => {
+                            structured_answer_retry_count =
+                                structured_answer_retry_count.saturating_add(1);
+                            let mut retry_payload = structured_answer_event_payload(&error, true);
+                            if let Value::Object(ref mut map) = retry_payload {
+                                round_info.insert_into(map);
+                            }
+                            emitter.emit("progress", retry_payload).await;
+                            let rejected_answer_message = json!({
+                                "role": "assistant",
+                                "content": content.clone(),
+                            });
+                            let model_notice_message = json!({
+                                "role": "user",
+                                "content": encode_observation_prefixed_json(&notice),
+                            });
+                            for message in [rejected_answer_message, model_notice_message] {
+                                messages.push(message.clone());
+                                self.append_model_context_entry(&user_id, &session_id, &message);
+                            }
+                            continue;
+                        }
+                        StructuredAnswerCheck::Rejected { error } => {
+                            warn!("structured answer rejected for session {session_id}: {error}");
+                            let mut invalid_payload =
+                                structured_answer_event_payload(&error, false);
+                            if let Value::Object(ref mut map) = invalid_payload {
+                                round_info.insert_into(map);
+                            }
+                            emitter.emit("progress", invalid_payload).await;
+                        }
+                    }
                     if !answer.trim().is_empty() {
                         answer = self.reconcile_final_answer_workspace_images(
                             &prepared.workspace_id,
@@ -887,6 +961,10 @@ impl Orchestrator {
                         );
                     }
                     stop_reason = Some("model_response".to_string());
+                    if let Some(partial_meta) = partial {
+                        stop_reason = Some(STREAM_INTERRUPTED_STOP_REASON.to_string());
+                        stop_meta = Some(partial_meta);
+                    }
                     let assistant_content = if answer.is_empty() {
                         content.clone()
                     } else {
@@ -1034,6 +1112,7 @@ impl Orchestrator {
                     command_sessions: Some(self.command_sessions.clone()),
                     event_emitter: Some(tool_event_emitter.clone()),
                     http: &self.http,
+                    dry_run: false,
                 };
 
                 let final_tool_name = resolve_tool_name("final_response");
@@ -1175,6 +1254,7 @@ impl Orchestrator {
                         round_info.insert_into(map);
                     }
                     emitter.emit("tool_call", tool_payload).await;
+                    round_limit.record_tool_call(&planned.name);
                 }
 
                 let mut should_finish = false;
@@ -1296,7 +1376,9 @@ impl Orchestrator {
                             should_finish = true;
                         }
 
-                        let observation = self.build_tool_observation(&name, &result);
+                        let observation = self
+                            .build_budgeted_tool_observation(&config, &llm_config, &name, &result)
+                            .await;
                         let observation_value = Value::String(observation.clone());
                         let read_image_followup = if result.ok && is_read_image_tool_name(&name) {
                             match build_read_image_followup_user_message(&tool_context, &result.data)
@@ -1429,6 +1511,9 @@ impl Orchestrator {
                             log_payload,
                         );
                         self.append_artifact_logs(&user_id, &session_id, &name, &args, &result);
+                        if let Some(citations) = citations.as_mut() {
+                            citations.collect(&result);
+                        }
                         if name == read_tool_name {
                             self.append_skill_usage_logs(
                                 &user_id,
@@ -2003,7 +2088,12 @@ impl Orchestrator {
                 }
             }
             if reached_max_rounds {
-                answer = build_max_rounds_user_guidance(max_rounds);
+                let mut payload = round_limit.event_payload(model_round);
+                if let Value::Object(ref mut map) = payload {
+                    last_round_info.insert_into(map);
+                }
+                emitter.emit("max_rounds_reached", payload).await;
+                answer = round_limit.final_answer(model_round, &answer);
                 if stop_reason.is_none() {
                     stop_reason = Some("max_rounds".to_string());
                 }
@@ -2056,7 +2146,7 @@ impl Orchestrator {
                 uid: a2ui_uid.clone(),
                 a2ui: a2ui_messages.clone(),
             };
-            let final_payload = build_final_event_payload(
+            let mut final_payload = build_final_event_payload(
                 &answer,
                 response_usage.as_ref(),
                 &round_usage,
@@ -2066,7 +2156,30 @@ impl Orchestrator {
                 last_round_info,
                 &turn_decode_speed,
             );
+            if let Some(citations) = citations.as_ref() {
+                citations.insert_into(&mut final_payload);
+            }
             emitter.emit("final", final_payload).await;
+            if !hidden_internal_user {
+                self.turn_notifiers.dispatch(TurnNotification::new(
+                    TURN_NOTIFICATION_SOURCE,
+                    &session_id,
+                    &user_id,
+                    None,
+                    &answer,
+                    Some(&stop_reason),
+                ));
+            }
+            // Only the opening turn names the session; later turns keep whatever title it has.
+            if user_round <= 1 {
+                self.spawn_session_title_refresh(
+                    &config,
+                    &user_id,
+                    &session_id,
+                    &display_question,
+                    &answer,
+                );
+            }
             self.finish_request_success(
                 &user_id,
                 &session_id,
@@ -2,7 +2,7 @@ use super::tool_calls::ToolCall;
 use super::*;
 use crate::core::approval::{ApprovalRequestKind, ApprovalResponse};
 use crate::core::llm_speed::TurnDecodeSpeedAccumulator;
-use crate::services::tools::tool_error::{with_error_meta, ToolErrorMeta};
+use crate::services::tools::tool_error::{with_error_meta, ToolError, ToolErrorMeta};
 
 pub(super) struct PlannedToolCall {
     pub(super) call: ToolCall,
@@ -120,6 +120,8 @@ const DEFAULT_NON_ADMIN_MAX_ROUNDS: u32 = 1000;
 const MIN_NON_ADMIN_MAX_ROUNDS: u32 = 2;
 const MIN_NON_ADMIN_MAX_ROUNDS_WITH_TOOLS: u32 = MIN_NON_ADMIN_MAX_ROUNDS;
 pub(super) const MAX_CONTEXT_OVERFLOW_RECOVERY_ATTEMPTS: u32 = 8;
+/// Stop reason for a turn that ended with a partial answer after the provider stream dropped.
+pub(super) const STREAM_INTERRUPTED_STOP_REASON: &str = "stream_interrupted";
 const DEFAULT_REPEATED_TOOL_FAILURE_THRESHOLD: u32 = 5;
 pub(super) const DEFAULT_TOOL_CALL_BUDGET_PER_TURN: u32 = 10_000;
 const DEFAULT_DB_QUERY_TOOL_BUDGET_PER_TURN: u32 = 2_000;
@@ -517,8 +519,14 @@ pub(super) fn uses_native_tool_api(
     }
 }
 
-pub(super) fn resolve_tool_parallelism(total: usize) -> usize {
-    let desired = DEFAULT_TOOL_PARALLELISM.max(1);
+/// Calls of one round allowed in flight, capped by `tools.parallel.max_concurrency`.
+pub(super) fn resolve_tool_parallelism(config: &Config, total: usize) -> usize {
+    let desired = config
+        .tools
+        .parallel
+        .max_concurrency
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_TOOL_PARALLELISM);
     total.max(1).min(desired)
 }
 
@@ -979,6 +987,23 @@ pub(super) fn build_tool_timeout_result(
     ToolResultPayload::error(message, data)
 }
 
+/// Turn a dispatch-level `ToolError` into a failed payload whose `error_meta.code` carries
+/// the variant, so clients can tell a missing tool from a denied or malformed call.
+pub(super) fn build_tool_error_result(
+    tool_name: &str,
+    error: ToolError,
+    timeout: Option<Duration>,
+) -> ToolResultPayload {
+    if matches!(error, ToolError::Timeout) {
+        return build_tool_timeout_result(tool_name, timeout);
+    }
+    let data = with_error_meta(
+        json!({ "tool": tool_name }),
+        ToolErrorMeta::new(error.code(), None, error.retryable(), None),
+    );
+    ToolResultPayload::error(error.to_string(), data)
+}
+
 pub(super) fn build_tool_budget_guard_model_notice(
     block: &ToolBudgetBlock,
     limits: &ToolBudgetLimits,
@@ -1086,6 +1111,9 @@ pub(super) fn build_final_event_payload(
         if let Some(meta) = stop_meta {
             map.insert("stop_meta".to_string(), meta.clone());
         }
+        if stop_reason == STREAM_INTERRUPTED_STOP_REASON {
+            map.insert("incomplete".to_string(), Value::Bool(true));
+        }
         round_info.insert_into(map);
         turn_decode_speed.insert_into_map(map);
     }
@@ -1302,6 +1330,109 @@ pub(super) fn approval_resolution_status_and_scope(
     }
 }
 
+/// Directory a command call will run in, as part of its session approval: approving a command
+/// in one directory must not approve it everywhere. `None` for tools that take no workdir.
+pub(super) fn session_approval_workdir(
+    context: &ToolContext<'_>,
+    tool_name: &str,
+    args: &Value,
+) -> Option<String> {
+    if tool_name != resolve_tool_name("execute_command") && tool_name != resolve_tool_name("ptc") {
+        return None;
+    }
+    let workdir = args
+        .get("workdir")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    let resolved = if workdir.is_empty() {
+        context.workspace.workspace_root(context.workspace_id)
+    } else {
+        match context
+            .workspace
+            .resolve_path(context.workspace_id, workdir)
+        {
+            Ok(path) => path,
+            // Outside the workspace (allow_paths): key on the path as given.
+            Err(_) => std::path::PathBuf::from(workdir),
+        }
+    };
+    Some(resolved.to_string_lossy().to_string())
+}
+
+/// Whether an earlier run of this user's session already answered "approve for session" for the same
+/// tool, args signature and workdir. Storage errors count as "not approved" so the user is
+/// asked again.
+pub(super) async fn has_persisted_session_approval(
+    storage: Arc<dyn StorageBackend>,
+    user_id: &str,
+    session_id: &str,
+    tool_name: &str,
+    args: &Value,
+    workdir: Option<String>,
+) -> bool {
+    let (user_id, session_id, tool_name, args) = (
+        user_id.to_string(),
+        session_id.to_string(),
+        tool_name.to_string(),
+        args.clone(),
+    );
+    crate::blocking::run_db("orchestrator.session_approval.check", move || {
+        crate::session_approvals::is_session_approved(
+            storage.as_ref(),
+            &user_id,
+            &session_id,
+            &tool_name,
+            &args,
+            workdir.as_deref(),
+        )
+    })
+    .await
+    .unwrap_or_else(|err| {
+        warn!("failed to check persisted session approval: {err}");
+        false
+    })
+}
+
+pub(super) async fn persist_session_approval(
+    storage: Arc<dyn StorageBackend>,
+    user_id: &str,
+    session_id: &str,
+    tool_name: &str,
+    args: &Value,
+    workdir: Option<String>,
+) {
+    let (user_id, session_id, tool_name, args) = (
+        user_id.to_string(),
+        session_id.to_string(),
+        tool_name.to_string(),
+        args.clone(),
+    );
+    if let Err(err) = crate::blocking::run_db("orchestrator.session_approval.persist", move || {
+        crate::session_approvals::remember_session_approval(
+            storage.as_ref(),
+            &user_id,
+            &session_id,
+            &tool_name,
+            &args,
+            workdir.as_deref(),
+        )
+    })
+    .await
+    {
+        warn!("failed to persist session approval: {err}");
+    }
+}
+
+/// The reviewer's choice as recorded in the audit trail of approval events.
+pub(super) fn approval_decision(approval_response: ApprovalResponse) -> &'static str {
+    match approval_response {
+        ApprovalResponse::ApproveOnce => "once",
+        ApprovalResponse::ApproveSession => "session",
+        ApprovalResponse::Deny => "deny",
+    }
+}
+
 pub(super) async fn emit_approval_resolved_event(
     emitter: &EventEmitter,
     round_info: RoundInfo,
@@ -1316,6 +1447,10 @@ pub(super) async fn emit_approval_resolved_event(
         "summary": event.summary.unwrap_or_default(),
     });
     if let Value::Object(ref mut map) = payload {
+        // Cancelled approvals carry no decision: nobody answered them.
+        if let Some(decision) = event.decision {
+            map.insert("decision".to_string(), Value::String(decision.to_string()));
+        }
         if let Some(resolved_by) = event
             .resolved_by
             .map(str::trim)
@@ -1331,6 +1466,32 @@ pub(super) async fn emit_approval_resolved_event(
     emitter.emit("approval_resolved", payload).await;
 }
 
+/// Announce a call that a `security.auto_approve` rule let through without prompting.
+pub(super) async fn emit_auto_approved_event(
+    emitter: &EventEmitter,
+    round_info: RoundInfo,
+    tool_name: &str,
+    args: &Value,
+    tool_call_id: Option<&str>,
+) {
+    let kind = approval_kind_for_tool(tool_name);
+    let mut payload = json!({
+        "kind": kind,
+        "tool": tool_name,
+        "summary": approval_summary_for_tool(tool_name, args, kind),
+    });
+    if let Value::Object(ref mut map) = payload {
+        if let Some(tool_call_id) = tool_call_id {
+            map.insert(
+                "tool_call_id".to_string(),
+                Value::String(tool_call_id.to_string()),
+            );
+        }
+        round_info.insert_into(map);
+    }
+    emitter.emit("auto_approved", payload).await;
+}
+
 pub(super) fn turn_terminal_status_for_error(err: &OrchestratorError) -> &'static str {
     match err.code() {
         "CANCELLED" => "cancelled",
@@ -1390,6 +1551,7 @@ pub(super) struct ApprovalResolvedEvent<'a> {
     pub(super) kind: Option<ApprovalRequestKind>,
     pub(super) tool_name: &'a str,
     pub(super) summary: Option<&'a str>,
+    pub(super) decision: Option<&'a str>,
     pub(super) resolved_by: Option<&'a str>,
 }
 
@@ -49,6 +49,61 @@ fn exception_approval_resolution_status_distinguishes_scope() {
     );
 }
 
+#[tokio::test]
+async fn approval_decision_is_persisted_with_resolved_event() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let db_path = temp.path().join("approval-audit.db");
+    let storage: Arc<dyn StorageBackend> = Arc::new(crate::storage::SqliteStorage::new(
+        db_path.to_string_lossy().to_string(),
+    ));
+    storage.ensure_initialized().expect("initialize storage");
+    let monitor = Arc::new(MonitorState::new(
+        storage.clone(),
+        crate::config::ObservabilityConfig::default(),
+        temp.path().to_string_lossy().to_string(),
+    ));
+    let emitter = EventEmitter::new(
+        "sess_approval_audit".to_string(),
+        "alice".to_string(),
+        None,
+        Some(storage.clone()),
+        monitor,
+        false,
+        0,
+        None,
+    );
+
+    emit_approval_resolved_event(
+        &emitter,
+        RoundInfo::user_only(1),
+        ApprovalResolvedEvent {
+            approval_id: "approval_1",
+            status: "approved",
+            scope: "session",
+            kind: Some(ApprovalRequestKind::Exec),
+            tool_name: "execute_command",
+            summary: Some("execute_command: rm -rf build"),
+            decision: Some(approval_decision(ApprovalResponse::ApproveSession)),
+            resolved_by: Some("approval_response"),
+        },
+    )
+    .await;
+    flush_stream_event_persist_queue().await;
+
+    let records = storage
+        .load_stream_events("sess_approval_audit", 0, 16)
+        .expect("load stream events");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["event"], json!("approval_resolved"));
+    let envelope = &records[0]["data"];
+    assert!(envelope["timestamp"].as_str().is_some());
+    let data = &envelope["data"];
+    assert_eq!(data["tool"], json!("execute_command"));
+    assert_eq!(data["summary"], json!("execute_command: rm -rf build"));
+    assert_eq!(data["decision"], json!("session"));
+    assert_eq!(data["resolved_by"], json!("approval_response"));
+}
+
 #[test]
 fn skip_context_overflow_recovery_for_other_errors() {
     let err = OrchestratorError::internal("LLM call failed: invalid api key".to_string());
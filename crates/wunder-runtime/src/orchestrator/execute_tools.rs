@@ -4,24 +4,54 @@ use super::thread_runtime::{
     thread_closed_payload, thread_not_loaded_payload, thread_status_payload, ThreadRuntimeStatus,
     ThreadRuntimeUpdate,
 };
-use super::tool_parallel::tool_call_supports_parallel;
+use super::tool_parallel::{run_in_call_order, tool_call_runs_in_parallel, ToolExecutionGate};
 use super::*;
 use crate::core::approval::{
-    ApprovalRequest, ApprovalRequestKind, ApprovalRequestTx, ApprovalResponse,
+    ApprovalMode, ApprovalRequest, ApprovalRequestKind, ApprovalRequestTx, ApprovalResponse,
 };
 use crate::services::chat_cancel_marker::persist_user_cancelled_turn_marker;
 use crate::services::goal;
-use crate::tools::ToolContext;
+use crate::services::tools::dry_run_plan::is_destructive_tool_call;
+use crate::tools::{ToolContext, ToolError};
 use crate::user_store::UserStore;
-use futures::StreamExt;
 use serde_json::{json, Value};
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::task::JoinHandle;
-use tracing::warn;
+use tracing::{warn, Instrument};
 use uuid::Uuid;
 
+/// Where per-call `tool_started`/`tool_finished` events go.
+struct ToolRunEventTarget<'a> {
+    emitter: &'a EventEmitter,
+    round_info: RoundInfo,
+    tool_call_id: Option<&'a str>,
+}
+
+impl ToolRunEventTarget<'_> {
+    async fn emit(&self, event: &str, mut payload: Value) {
+        if let Value::Object(ref mut map) = payload {
+            if let Some(tool_call_id) = self.tool_call_id {
+                map.insert(
+                    "tool_call_id".to_string(),
+                    Value::String(tool_call_id.to_string()),
+                );
+            }
+            self.round_info.insert_into(map);
+        }
+        self.emitter.emit(event, payload).await;
+    }
+}
+
+fn parallel_execution_mode(parallel: bool) -> &'static str {
+    if parallel {
+        "parallel_read"
+    } else {
+        "exclusive_write"
+    }
+}
+
 impl Orchestrator {
     pub(super) async fn finish_request_resources(
         emitter: &EventEmitter,
@@ -250,13 +280,13 @@ impl Orchestrator {
         if calls.is_empty() {
             return Ok(Vec::new());
         }
-        let parallelism = resolve_tool_parallelism(calls.len());
-        let execution_lock = Arc::new(tokio::sync::RwLock::new(()));
-        let mut stream = futures::stream::iter(calls.into_iter().map(|planned| {
+        let parallelism = resolve_tool_parallelism(tool_context.config, calls.len());
+        let execution_gate = ToolExecutionGate::default();
+        let tasks = calls.into_iter().map(|planned| {
             let orchestrator = self;
             let approval_tx = approval_tx.clone();
             let emitter = emitter.clone();
-            let execution_lock = Arc::clone(&execution_lock);
+            let execution_gate = execution_gate.clone();
             async move {
                 let PlannedToolCall {
                     mut call,
@@ -307,11 +337,35 @@ impl Orchestrator {
                     Some(scoped_tool_context.session_id),
                     Some(scoped_tool_context.user_id),
                 );
+                let policy_decision = match policy_decision {
+                    Some(decision)
+                        if decision.requires_approval
+                            && has_persisted_session_approval(
+                                orchestrator.storage.clone(),
+                                scoped_tool_context.user_id,
+                                scoped_tool_context.session_id,
+                                &name,
+                                &args,
+                                session_approval_workdir(&scoped_tool_context, &name, &args),
+                            )
+                            .await =>
+                    {
+                        crate::exec_policy::evaluate_tool_call(
+                            scoped_tool_context.config,
+                            &name,
+                            &args_with_approved_flag(&args),
+                            Some(scoped_tool_context.session_id),
+                            Some(scoped_tool_context.user_id),
+                        )
+                    }
+                    other => other,
+                };
                 let policy_meta = policy_decision.as_ref().map(|decision| decision.to_value());
                 let started_at = Instant::now();
                 let tool_timeout =
                     orchestrator.resolve_tool_timeout(scoped_tool_context.config, &name, &args);
-                let supports_parallel_execution = tool_call_supports_parallel(&name, &args);
+                let supports_parallel_execution =
+                    tool_call_runs_in_parallel(scoped_tool_context.config, &name, &args);
                 let mut result = if !allowed_tool_names.contains(&name) {
                     ToolResultPayload::error(
                         i18n::t("error.tool_disabled_or_unavailable"),
@@ -329,10 +383,27 @@ impl Orchestrator {
                                 let kind = approval_kind_for_tool(&name);
                                 let summary = approval_summary_for_tool(&name, &args, kind);
                                 let request_id = Uuid::new_v4().simple().to_string();
-                                let detail = json!({
+                                let mut detail = json!({
                                     "policy": policy_meta.clone().unwrap_or(Value::Null),
                                     "reason": decision.reason.clone(),
                                 });
+                                // Under suggest mode the user reviews every write, so show them
+                                // the dry-run plan alongside the raw arguments.
+                                if matches!(decision.approval_mode, ApprovalMode::Suggest)
+                                    && is_destructive_tool_call(&name, &args)
+                                {
+                                    if let Some(plan) = orchestrator
+                                        .preview_tool_plan(
+                                            &scoped_tool_context,
+                                            &name,
+                                            &args,
+                                            tool_timeout,
+                                        )
+                                        .await
+                                    {
+                                        detail["plan"] = plan;
+                                    }
+                                }
                                 let request = ApprovalRequest {
                                     id: request_id.clone(),
                                     kind,
@@ -405,6 +476,7 @@ impl Orchestrator {
                                                         kind: approval_kind,
                                                         tool_name: &name,
                                                         summary: approval_summary.as_deref(),
+                                                        decision: None,
                                                         resolved_by: Some("session_cancelled"),
                                                     },
                                                 )
@@ -427,10 +499,12 @@ impl Orchestrator {
                         if let Some(id) = approval_id {
                             let (status, scope) =
                                 approval_resolution_status_and_scope(approval_response);
+                            let audit_decision = approval_decision(approval_response);
                             let mut event_payload = json!({
                                 "approval_id": id.clone(),
                                 "status": status,
                                 "scope": scope,
+                                "decision": audit_decision,
                                 "kind": approval_kind,
                                 "tool": name.clone(),
                                 "summary": approval_summary.clone().unwrap_or_default(),
@@ -455,6 +529,7 @@ impl Orchestrator {
                                     kind: approval_kind,
                                     tool_name: &name,
                                     summary: approval_summary.as_deref(),
+                                    decision: Some(audit_decision),
                                     resolved_by: Some("approval_response"),
                                 },
                             )
@@ -489,6 +564,19 @@ impl Orchestrator {
                                     Some(scoped_tool_context.session_id),
                                     Some(scoped_tool_context.user_id),
                                 );
+                                persist_session_approval(
+                                    orchestrator.storage.clone(),
+                                    scoped_tool_context.user_id,
+                                    scoped_tool_context.session_id,
+                                    &name,
+                                    &args,
+                                    session_approval_workdir(
+                                        &scoped_tool_context,
+                                        &name,
+                                        &args,
+                                    ),
+                                )
+                                .await;
                                 Some(ApprovalResponse::ApproveSession)
                             }
                             ApprovalResponse::Deny => None,
@@ -497,7 +585,12 @@ impl Orchestrator {
                         if let Some(approval_choice) = approved {
                             let result = tokio::select! {
                                 res = orchestrator.execute_tool_with_parallel_guard(
-                                    Arc::clone(&execution_lock),
+                                    &execution_gate,
+                                    ToolRunEventTarget {
+                                        emitter: &emitter,
+                                        round_info,
+                                        tool_call_id: event_tool_call_id.as_deref(),
+                                    },
                                     &scoped_tool_context,
                                     &name,
                                     &args,
@@ -510,16 +603,7 @@ impl Orchestrator {
                             };
                             let mut executed = match result {
                                 Ok(value) => ToolResultPayload::from_value(value),
-                                Err(err) => {
-                                    if err.to_string() == tool_exec::TOOL_TIMEOUT_ERROR {
-                                        build_tool_timeout_result(&name, tool_timeout)
-                                    } else {
-                                        ToolResultPayload::error(
-                                            err.to_string(),
-                                            json!({ "tool": name.clone() }),
-                                        )
-                                    }
-                                }
+                                Err(err) => build_tool_error_result(&name, err, tool_timeout),
                             };
                             if let Some(meta) = policy_meta.clone() {
                                 executed.insert_meta("policy", meta);
@@ -547,9 +631,24 @@ impl Orchestrator {
                             denied
                         }
                     } else {
+                        if decision.reason == crate::exec_policy::AUTO_APPROVED_REASON {
+                            emit_auto_approved_event(
+                                &emitter,
+                                round_info,
+                                &name,
+                                &args,
+                                event_tool_call_id.as_deref(),
+                            )
+                            .await;
+                        }
                         let result = tokio::select! {
                             res = orchestrator.execute_tool_with_parallel_guard(
-                                Arc::clone(&execution_lock),
+                                &execution_gate,
+                                ToolRunEventTarget {
+                                    emitter: &emitter,
+                                    round_info,
+                                    tool_call_id: event_tool_call_id.as_deref(),
+                                },
                                 &scoped_tool_context,
                                 &name,
                                 &args,
@@ -562,16 +661,7 @@ impl Orchestrator {
                         };
                         let mut executed = match result {
                             Ok(value) => ToolResultPayload::from_value(value),
-                            Err(err) => {
-                                if err.to_string() == tool_exec::TOOL_TIMEOUT_ERROR {
-                                    build_tool_timeout_result(&name, tool_timeout)
-                                } else {
-                                    ToolResultPayload::error(
-                                        err.to_string(),
-                                        json!({ "tool": name.clone() }),
-                                    )
-                                }
-                            }
+                            Err(err) => build_tool_error_result(&name, err, tool_timeout),
                         };
                         if let Some(meta) = policy_meta.clone() {
                             executed.insert_meta("policy", meta);
@@ -581,7 +671,12 @@ impl Orchestrator {
                 } else {
                     let result = tokio::select! {
                         res = orchestrator.execute_tool_with_parallel_guard(
-                            Arc::clone(&execution_lock),
+                            &execution_gate,
+                            ToolRunEventTarget {
+                                emitter: &emitter,
+                                round_info,
+                                tool_call_id: event_tool_call_id.as_deref(),
+                            },
                             &scoped_tool_context,
                             &name,
                             &args,
@@ -594,16 +689,7 @@ impl Orchestrator {
                     };
                     match result {
                         Ok(value) => ToolResultPayload::from_value(value),
-                        Err(err) => {
-                            if err.to_string() == tool_exec::TOOL_TIMEOUT_ERROR {
-                                build_tool_timeout_result(&name, tool_timeout)
-                            } else {
-                                ToolResultPayload::error(
-                                    err.to_string(),
-                                    json!({ "tool": name.clone() }),
-                                )
-                            }
-                        }
+                        Err(err) => build_tool_error_result(&name, err, tool_timeout),
                     }
                 };
                 let workspace_version_after = scoped_tool_context
@@ -616,11 +702,7 @@ impl Orchestrator {
                 result.insert_meta(
                     "parallel_execution",
                     json!({
-                        "mode": if supports_parallel_execution {
-                            "parallel_read"
-                        } else {
-                            "exclusive_write"
-                        },
+                        "mode": parallel_execution_mode(supports_parallel_execution),
                     }),
                 );
                 if let Some(repair) = args_repair.clone() {
@@ -630,34 +712,59 @@ impl Orchestrator {
                 result = orchestrator.finalize_tool_result(&name, result, started_at);
                 Ok(ToolExecutionOutcome { call, name, result })
             }
-        }))
-        .buffered(parallelism);
-
-        let mut outcomes = Vec::new();
-        while let Some(outcome) = stream.next().await {
-            outcomes.push(outcome?);
-        }
-        Ok(outcomes)
+        });
+        run_in_call_order(tasks, parallelism).await
     }
 
+    /// Runs one call under the round's execution gate. `tool_started` is emitted once the gate
+    /// admits the call, so overlapping start/finish pairs show which calls really ran together.
+    #[allow(clippy::too_many_arguments)]
     async fn execute_tool_with_parallel_guard(
         &self,
-        execution_lock: Arc<tokio::sync::RwLock<()>>,
+        execution_gate: &ToolExecutionGate,
+        events: ToolRunEventTarget<'_>,
         tool_context: &ToolContext<'_>,
         name: &str,
         args: &Value,
         timeout: Option<Duration>,
         supports_parallel_execution: bool,
-    ) -> Result<Value, anyhow::Error> {
-        if supports_parallel_execution {
-            let _guard = execution_lock.read().await;
-            self.execute_tool_with_timeout(tool_context, name, args, timeout)
-                .await
-        } else {
-            let _guard = execution_lock.write().await;
-            self.execute_tool_with_timeout(tool_context, name, args, timeout)
-                .await
-        }
+    ) -> Result<Value, ToolError> {
+        let span = tracing::info_span!(
+            "tool_call",
+            tool = %name,
+            session_id = %tool_context.session_id,
+            user_id = %tool_context.user_id,
+        );
+        let mode = parallel_execution_mode(supports_parallel_execution);
+        // Boxed: each call site selects on this future, and the dispatch state machine is
+        // large enough to overflow worker stacks in debug builds when held inline.
+        execution_gate
+            .run(
+                supports_parallel_execution,
+                Box::pin(async {
+                    events
+                        .emit("tool_started", json!({ "tool": name, "mode": mode }))
+                        .await;
+                    let started_at = Instant::now();
+                    let result = self
+                        .execute_tool_with_timeout(tool_context, name, args, timeout)
+                        .await;
+                    events
+                        .emit(
+                            "tool_finished",
+                            json!({
+                                "tool": name,
+                                "mode": mode,
+                                "ok": result.is_ok(),
+                                "duration_ms": started_at.elapsed().as_millis() as u64,
+                            }),
+                        )
+                        .await;
+                    result
+                }),
+            )
+            .instrument(span)
+            .await
     }
 
     pub(super) async fn finish_active_turn(
@@ -695,6 +802,7 @@ impl Orchestrator {
                         kind: Some(entry.kind),
                         tool_name: &entry.tool,
                         summary: Some(entry.summary.as_str()),
+                        decision: None,
                         resolved_by: Some("turn_cleanup"),
                     },
                 )
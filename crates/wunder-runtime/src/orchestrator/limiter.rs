@@ -1,81 +1,356 @@
 use super::*;
+use crate::config::ServerConfig;
 use crate::core::blocking;
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+/// Where a request waiting for a `server.max_active_sessions` slot stands; 1 is next in line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct QueuePosition {
+    position: usize,
+    total: usize,
+}
+
+impl QueuePosition {
+    fn to_value(self) -> Value {
+        json!({
+            "queued": true,
+            "reason": "max_active_sessions",
+            "queue_position": self.position,
+            "queue_ahead": self.position.saturating_sub(1),
+            "queue_total": self.total,
+        })
+    }
+}
+
+/// Bounds on the admission queue, from `server.max_queued_requests`,
+/// `server.max_queued_per_user`, `server.max_queued_per_model` and `server.max_queue_wait_s`.
+/// A per-user or per-model cap of 0 leaves that dimension to the overall cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct AdmissionLimits {
+    max_queued: usize,
+    max_queued_per_user: usize,
+    max_queued_per_model: usize,
+    max_wait: Duration,
+}
+
+impl AdmissionLimits {
+    pub(super) fn from_config(server: &ServerConfig) -> Self {
+        Self {
+            max_queued: server.max_queued_requests.max(1),
+            max_queued_per_user: server.max_queued_per_user,
+            max_queued_per_model: server.max_queued_per_model,
+            max_wait: Duration::from_secs(server.max_queue_wait_s.max(1)),
+        }
+    }
+}
+
+/// Per-model queue bucket: the configured model the request resolves to, so relying on
+/// `llm.default` and naming that model explicitly count against the same cap.
+pub(super) fn admission_model_key(config: &Config, model_name: Option<&str>) -> String {
+    super::llm::resolve_llm_model(config, model_name)
+        .map(|(name, _)| name.clone())
+        .or_else(|| model_name.map(|name| name.trim().to_string()))
+        .unwrap_or_default()
+}
+
+/// Which admission-queue cap turned a request away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum QueueScope {
+    Server,
+    User,
+    Model,
+}
+
+impl QueueScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Server => "server",
+            Self::User => "user",
+            Self::Model => "model",
+        }
+    }
+
+    fn message_key(self) -> &'static str {
+        match self {
+            Self::Server => "error.admission_queue_full",
+            Self::User => "error.admission_queue_user_full",
+            Self::Model => "error.admission_queue_model_full",
+        }
+    }
+}
+
+/// Outcome of `RequestLimiter::acquire`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Admission {
+    Acquired,
+    /// The session is already locked by another request.
+    UserBusy,
+    /// The admission queue is at one of its caps.
+    QueueFull(QueueScope),
+    /// Waited `server.max_queue_wait_s` without getting a slot.
+    QueueTimeout {
+        waited_s: u64,
+    },
+}
+
+impl Admission {
+    /// The `USER_BUSY` error to return for a rejected request; `None` once admitted.
+    pub(super) fn rejection(self) -> Option<OrchestratorError> {
+        match self {
+            Self::Acquired => None,
+            Self::UserBusy => Some(OrchestratorError::user_busy(i18n::t(
+                "error.user_session_busy",
+            ))),
+            Self::QueueFull(scope) => Some(
+                OrchestratorError::user_busy(i18n::t(scope.message_key())).with_detail(Some(
+                    json!({ "reason": "queue_full", "queue_scope": scope.as_str() }),
+                )),
+            ),
+            Self::QueueTimeout { waited_s } => Some(
+                OrchestratorError::user_busy(i18n::t_with_params(
+                    "error.admission_queue_timeout",
+                    &HashMap::from([("wait_s".to_string(), waited_s.to_string())]),
+                ))
+                .with_detail(Some(
+                    json!({ "reason": "queue_timeout", "queue_wait_s": waited_s }),
+                )),
+            ),
+        }
+    }
+}
+
+struct QueueEntry {
+    id: u64,
+    user_id: String,
+    model: String,
+}
+
+/// Requests of this process waiting for a session slot, admitted in arrival order: only the head
+/// of the line polls storage, the rest wait for their turn.
+#[derive(Default)]
+struct AdmissionQueue {
+    next_id: AtomicU64,
+    waiting: ParkingMutex<VecDeque<QueueEntry>>,
+}
+
+impl AdmissionQueue {
+    fn is_empty(&self) -> bool {
+        self.waiting.lock().is_empty()
+    }
+
+    /// Join the back of the line, unless that would exceed one of `limits`' caps.
+    fn join(
+        self: &Arc<Self>,
+        user_id: &str,
+        model: &str,
+        limits: &AdmissionLimits,
+    ) -> std::result::Result<AdmissionTicket, QueueScope> {
+        let mut waiting = self.waiting.lock();
+        if waiting.len() >= limits.max_queued {
+            return Err(QueueScope::Server);
+        }
+        let user_queued = waiting
+            .iter()
+            .filter(|entry| entry.user_id == user_id)
+            .count();
+        if limits.max_queued_per_user > 0 && user_queued >= limits.max_queued_per_user {
+            return Err(QueueScope::User);
+        }
+        let model_queued = waiting.iter().filter(|entry| entry.model == model).count();
+        if limits.max_queued_per_model > 0 && model_queued >= limits.max_queued_per_model {
+            return Err(QueueScope::Model);
+        }
+        let id = self.next_id.fetch_add(1, AtomicOrdering::Relaxed);
+        waiting.push_back(QueueEntry {
+            id,
+            user_id: user_id.to_string(),
+            model: model.to_string(),
+        });
+        Ok(AdmissionTicket {
+            queue: Arc::clone(self),
+            id,
+        })
+    }
+}
+
+/// Place in the admission queue; dropping it (admitted, rejected or cancelled) leaves the line.
+struct AdmissionTicket {
+    queue: Arc<AdmissionQueue>,
+    id: u64,
+}
+
+impl AdmissionTicket {
+    fn position(&self) -> QueuePosition {
+        let waiting = self.queue.waiting.lock();
+        let index = waiting
+            .iter()
+            .position(|entry| entry.id == self.id)
+            .unwrap_or(0);
+        QueuePosition {
+            position: index + 1,
+            total: waiting.len(),
+        }
+    }
+}
+
+impl Drop for AdmissionTicket {
+    fn drop(&mut self) {
+        self.queue
+            .waiting
+            .lock()
+            .retain(|entry| entry.id != self.id);
+    }
+}
+
+fn admission_queue() -> Arc<AdmissionQueue> {
+    static QUEUE: OnceLock<Arc<AdmissionQueue>> = OnceLock::new();
+    Arc::clone(QUEUE.get_or_init(Arc::default))
+}
+
 #[derive(Clone)]
 pub(super) struct RequestLimiter {
     storage: Arc<dyn StorageBackend>,
     max_active: i64,
+    limits: AdmissionLimits,
     poll_interval_s: f64,
     lock_ttl_s: f64,
+    queue: Arc<AdmissionQueue>,
 }
 
 impl RequestLimiter {
-    pub(super) fn new(storage: Arc<dyn StorageBackend>, max_active: usize) -> Self {
+    pub(super) fn new(
+        storage: Arc<dyn StorageBackend>,
+        max_active: usize,
+        limits: AdmissionLimits,
+    ) -> Self {
         Self {
             storage,
             max_active: max_active.max(1) as i64,
+            limits,
             poll_interval_s: SESSION_LOCK_POLL_INTERVAL_S,
             lock_ttl_s: SESSION_LOCK_TTL_S,
+            queue: admission_queue(),
         }
     }
 
+    /// Take a session slot. Without `allow_queue` a full server rejects at once; with it the
+    /// request joins the admission queue (bounded overall, per user and per `model`) and
+    /// `queued` events report its position until admitted or `max_wait` runs out.
     pub(super) async fn acquire(
         &self,
         session_id: &str,
         user_id: &str,
         agent_id: &str,
+        model: &str,
         allow_queue: bool,
-    ) -> Result<bool> {
+        emitter: &EventEmitter,
+    ) -> Result<Admission> {
         let cleaned_session = session_id.trim();
         let cleaned_user = user_id.trim();
         let cleaned_agent = agent_id.trim();
         if cleaned_session.is_empty() || cleaned_user.is_empty() {
-            return Ok(false);
+            return Ok(Admission::UserBusy);
         }
         if !allow_queue {
-            let storage = self.storage.clone();
-            let session_id = cleaned_session.to_string();
-            let user_id = cleaned_user.to_string();
-            let agent_id = cleaned_agent.to_string();
-            let ttl = self.lock_ttl_s;
-            let max_active = self.max_active;
-            let status = blocking::run_db("orchestrator.limiter.acquire_nowait", move || {
-                storage.try_acquire_session_lock(&session_id, &user_id, &agent_id, ttl, max_active)
-            })
-            .await
-            .map_err(|err| anyhow!("session lock acquire error: {err}"))?;
-            return Ok(matches!(status, SessionLockStatus::Acquired));
+            let status = self
+                .try_lock(
+                    "orchestrator.limiter.acquire_nowait",
+                    cleaned_session,
+                    cleaned_user,
+                    cleaned_agent,
+                )
+                .await?;
+            return Ok(match status {
+                SessionLockStatus::Acquired => Admission::Acquired,
+                _ => Admission::UserBusy,
+            });
         }
-        let retry_window = SESSION_LOCK_BUSY_RETRY_S.max(self.poll_interval_s);
-        let retry_deadline = Instant::now() + Duration::from_secs_f64(retry_window);
+        let poll_interval = Duration::from_secs_f64(self.poll_interval_s);
+        let retry_window =
+            Duration::from_secs_f64(SESSION_LOCK_BUSY_RETRY_S.max(self.poll_interval_s));
+        let mut retry_deadline = Instant::now() + retry_window;
+        let mut ticket: Option<(AdmissionTicket, Instant)> = None;
+        // A freed slot belongs to the head of the line, so a newcomer queues behind anyone
+        // already waiting instead of racing them for it.
+        if !self.queue.is_empty() {
+            match self.queue.join(cleaned_user, model.trim(), &self.limits) {
+                Ok(joined) => ticket = Some((joined, Instant::now())),
+                Err(scope) => return Ok(Admission::QueueFull(scope)),
+            }
+        }
+        let mut reported_position = None;
         loop {
-            let storage = self.storage.clone();
-            let session_id = cleaned_session.to_string();
-            let user_id = cleaned_user.to_string();
-            let agent_id = cleaned_agent.to_string();
-            let ttl = self.lock_ttl_s;
-            let max_active = self.max_active;
-            let status = blocking::run_db("orchestrator.limiter.acquire", move || {
-                storage.try_acquire_session_lock(&session_id, &user_id, &agent_id, ttl, max_active)
-            })
-            .await
-            .map_err(|err| anyhow!("session lock acquire error: {err}"))?;
+            if let Some((ticket, joined_at)) = ticket.as_ref() {
+                if joined_at.elapsed() >= self.limits.max_wait {
+                    return Ok(Admission::QueueTimeout {
+                        waited_s: self.limits.max_wait.as_secs(),
+                    });
+                }
+                let position = ticket.position();
+                if reported_position != Some(position.position) {
+                    reported_position = Some(position.position);
+                    emitter.emit("queued", position.to_value()).await;
+                    if position.position == 1 {
+                        // The busy-session retry window starts when the request reaches the head,
+                        // not when it first arrived.
+                        retry_deadline = Instant::now() + retry_window;
+                    }
+                }
+                if position.position > 1 {
+                    tokio::time::sleep(poll_interval).await;
+                    continue;
+                }
+            }
+            let status = self
+                .try_lock(
+                    "orchestrator.limiter.acquire",
+                    cleaned_session,
+                    cleaned_user,
+                    cleaned_agent,
+                )
+                .await?;
             match status {
-                SessionLockStatus::Acquired => return Ok(true),
+                SessionLockStatus::Acquired => return Ok(Admission::Acquired),
                 SessionLockStatus::UserBusy => {
                     if Instant::now() >= retry_deadline {
-                        return Ok(false);
+                        return Ok(Admission::UserBusy);
+                    }
+                    tokio::time::sleep(poll_interval).await;
+                }
+                SessionLockStatus::SystemBusy if ticket.is_none() => {
+                    match self.queue.join(cleaned_user, model.trim(), &self.limits) {
+                        Ok(joined) => ticket = Some((joined, Instant::now())),
+                        Err(scope) => return Ok(Admission::QueueFull(scope)),
                     }
-                    tokio::time::sleep(Duration::from_secs_f64(self.poll_interval_s)).await;
                 }
                 SessionLockStatus::SystemBusy => {
-                    tokio::time::sleep(Duration::from_secs_f64(self.poll_interval_s)).await;
+                    tokio::time::sleep(poll_interval).await;
                 }
             }
         }
     }
 
+    async fn try_lock(
+        &self,
+        label: &'static str,
+        session_id: &str,
+        user_id: &str,
+        agent_id: &str,
+    ) -> Result<SessionLockStatus> {
+        let storage = self.storage.clone();
+        let session_id = session_id.to_string();
+        let user_id = user_id.to_string();
+        let agent_id = agent_id.to_string();
+        let ttl = self.lock_ttl_s;
+        let max_active = self.max_active;
+        blocking::run_db(label, move || {
+            storage.try_acquire_session_lock(&session_id, &user_id, &agent_id, ttl, max_active)
+        })
+        .await
+        .map_err(|err| anyhow!("session lock acquire error: {err}"))
+    }
+
     pub(super) async fn touch(&self, session_id: &str) {
         let cleaned_session = session_id.trim();
         if cleaned_session.is_empty() {
@@ -115,3 +390,338 @@ impl RequestLimiter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SqliteStorage;
+
+    fn test_limits() -> AdmissionLimits {
+        AdmissionLimits::from_config(&ServerConfig::default())
+    }
+
+    fn test_storage(root: &std::path::Path) -> Arc<dyn StorageBackend> {
+        let db_path = root.join("limiter.db");
+        let storage: Arc<dyn StorageBackend> =
+            Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+        storage.ensure_initialized().expect("initialize storage");
+        storage
+    }
+
+    fn test_emitter(
+        storage: &Arc<dyn StorageBackend>,
+        root: &std::path::Path,
+        session_id: &str,
+    ) -> (EventEmitter, mpsc::Receiver<StreamSignal>) {
+        let monitor = Arc::new(MonitorState::new(
+            storage.clone(),
+            crate::config::ObservabilityConfig::default(),
+            root.to_string_lossy().to_string(),
+        ));
+        let (tx, rx) = mpsc::channel(16);
+        let emitter = EventEmitter::new(
+            session_id.to_string(),
+            "alice".to_string(),
+            Some(tx),
+            None,
+            monitor,
+            false,
+            0,
+            None,
+        );
+        (emitter, rx)
+    }
+
+    async fn next_queued_event(events: &mut mpsc::Receiver<StreamSignal>) -> Value {
+        let signal = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("queued event arrives")
+            .expect("event channel open");
+        let StreamSignal::Event(event) = signal else {
+            panic!("expected a queued event");
+        };
+        assert_eq!(event.event, "queued");
+        event.data["data"].clone()
+    }
+
+    #[tokio::test]
+    async fn request_over_the_limit_queues_with_position_when_allowed() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let storage = test_storage(temp.path());
+        let limiter = RequestLimiter {
+            queue: Arc::default(),
+            ..RequestLimiter::new(storage.clone(), 1, test_limits())
+        };
+
+        let (first_emitter, _first_events) = test_emitter(&storage, temp.path(), "sess_first");
+        assert_eq!(
+            limiter
+                .acquire("sess_first", "alice", "", "", false, &first_emitter)
+                .await
+                .expect("acquire first"),
+            Admission::Acquired
+        );
+
+        let (emitter, mut events) = test_emitter(&storage, temp.path(), "sess_second");
+        assert_eq!(
+            limiter
+                .acquire("sess_second", "bob", "", "", false, &emitter)
+                .await
+                .expect("acquire without queue"),
+            Admission::UserBusy,
+            "a full server rejects at once without allow_queue"
+        );
+        assert!(events.try_recv().is_err());
+
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move {
+                limiter
+                    .acquire("sess_second", "bob", "", "", true, &emitter)
+                    .await
+            }
+        });
+        let queued = next_queued_event(&mut events).await;
+        assert_eq!(queued["queue_position"], 1);
+        assert_eq!(queued["queue_ahead"], 0);
+        assert_eq!(queued["queue_total"], 1);
+        assert!(!waiting.is_finished(), "queued request waits for a slot");
+
+        limiter.release("sess_first").await;
+        let admitted = tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("queued request is admitted once the slot frees")
+            .expect("join queued request")
+            .expect("acquire queued");
+        assert_eq!(admitted, Admission::Acquired);
+        assert!(limiter.queue.waiting.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn newcomer_queues_behind_a_waiting_request_when_a_slot_frees() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let storage = test_storage(temp.path());
+        // A slow poll leaves the freed slot open while the newcomer arrives.
+        let limiter = RequestLimiter {
+            queue: Arc::default(),
+            poll_interval_s: 1.0,
+            ..RequestLimiter::new(storage.clone(), 1, test_limits())
+        };
+        let (emitter, _events) = test_emitter(&storage, temp.path(), "sess_first");
+        assert_eq!(
+            limiter
+                .acquire("sess_first", "alice", "", "", false, &emitter)
+                .await
+                .expect("acquire first"),
+            Admission::Acquired
+        );
+
+        let spawn_acquire = |session_id: &'static str, user_id: &'static str| {
+            let (emitter, events) = test_emitter(&storage, temp.path(), session_id);
+            let limiter = limiter.clone();
+            let task = tokio::spawn(async move {
+                limiter
+                    .acquire(session_id, user_id, "", "", true, &emitter)
+                    .await
+            });
+            (task, events)
+        };
+        let (queued, mut queued_events) = spawn_acquire("sess_queued", "bob");
+        assert_eq!(
+            next_queued_event(&mut queued_events).await["queue_position"],
+            1
+        );
+
+        limiter.release("sess_first").await;
+        let (newcomer, mut newcomer_events) = spawn_acquire("sess_newcomer", "carol");
+        assert_eq!(
+            next_queued_event(&mut newcomer_events).await["queue_position"],
+            2
+        );
+
+        let admitted = tokio::time::timeout(Duration::from_secs(5), queued)
+            .await
+            .expect("queued request takes the freed slot")
+            .expect("join queued request")
+            .expect("acquire queued");
+        assert_eq!(admitted, Admission::Acquired);
+        assert!(!newcomer.is_finished(), "newcomer waits for the next slot");
+
+        limiter.release("sess_queued").await;
+        let admitted = tokio::time::timeout(Duration::from_secs(5), newcomer)
+            .await
+            .expect("newcomer is admitted next")
+            .expect("join newcomer")
+            .expect("acquire newcomer");
+        assert_eq!(admitted, Admission::Acquired);
+    }
+
+    #[tokio::test]
+    async fn queued_request_gives_up_after_max_wait() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let storage = test_storage(temp.path());
+        let limiter = RequestLimiter {
+            queue: Arc::default(),
+            ..RequestLimiter::new(
+                storage.clone(),
+                1,
+                AdmissionLimits {
+                    max_wait: Duration::from_millis(500),
+                    ..test_limits()
+                },
+            )
+        };
+        let (emitter, _events) = test_emitter(&storage, temp.path(), "sess_first");
+        assert_eq!(
+            limiter
+                .acquire("sess_first", "alice", "", "", false, &emitter)
+                .await
+                .expect("acquire first"),
+            Admission::Acquired
+        );
+
+        let (emitter, _events) = test_emitter(&storage, temp.path(), "sess_second");
+        let outcome = tokio::time::timeout(
+            Duration::from_secs(5),
+            limiter.acquire("sess_second", "bob", "", "", true, &emitter),
+        )
+        .await
+        .expect("queued request stops waiting")
+        .expect("acquire queued");
+        assert!(matches!(outcome, Admission::QueueTimeout { .. }));
+        assert!(limiter.queue.waiting.lock().is_empty());
+        let error = outcome.rejection().expect("timeout is a rejection");
+        assert_eq!(error.code(), "USER_BUSY");
+        assert_eq!(error.detail().expect("detail")["reason"], "queue_timeout");
+    }
+
+    #[tokio::test]
+    async fn busy_session_retry_window_restarts_at_the_head_of_the_queue() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let storage = test_storage(temp.path());
+        let limiter = RequestLimiter {
+            queue: Arc::default(),
+            ..RequestLimiter::new(storage.clone(), 1, test_limits())
+        };
+        let lock = |session_id: &str, user_id: &str, max_active: i64| {
+            storage
+                .try_acquire_session_lock(session_id, user_id, "", SESSION_LOCK_TTL_S, max_active)
+                .expect("lock session")
+        };
+        assert_eq!(lock("sess_other", "alice", 1), SessionLockStatus::Acquired);
+        let ahead = limiter
+            .queue
+            .join("carol", "", &limiter.limits)
+            .expect("join queue");
+
+        let (emitter, mut events) = test_emitter(&storage, temp.path(), "sess_bob");
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move {
+                limiter
+                    .acquire("sess_bob", "bob", "", "", true, &emitter)
+                    .await
+            }
+        });
+        assert_eq!(next_queued_event(&mut events).await["queue_position"], 2);
+        // Wait in line past the busy-session retry window measured from arrival.
+        tokio::time::sleep(Duration::from_secs_f64(SESSION_LOCK_BUSY_RETRY_S + 0.4)).await;
+
+        // By the time the request reaches the head its session is briefly held elsewhere.
+        assert_eq!(lock("sess_bob", "bob", 2), SessionLockStatus::Acquired);
+        limiter.release("sess_other").await;
+        drop(ahead);
+        assert_eq!(next_queued_event(&mut events).await["queue_position"], 1);
+        limiter.release("sess_bob").await;
+
+        let admitted = tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("head of the queue retries the busy session")
+            .expect("join queued request")
+            .expect("acquire queued");
+        assert_eq!(admitted, Admission::Acquired);
+    }
+
+    #[test]
+    fn tickets_report_arrival_order_and_leave_on_drop() {
+        let queue = Arc::new(AdmissionQueue::default());
+        let limits = test_limits();
+        let first = queue.join("alice", "", &limits).expect("join");
+        let second = queue.join("bob", "", &limits).expect("join");
+        assert_eq!(
+            second.position(),
+            QueuePosition {
+                position: 2,
+                total: 2
+            }
+        );
+        drop(first);
+        assert_eq!(
+            second.position(),
+            QueuePosition {
+                position: 1,
+                total: 1
+            }
+        );
+    }
+
+    #[test]
+    fn joining_is_capped_overall_per_user_and_per_model() {
+        let queue = Arc::new(AdmissionQueue::default());
+        let limits = AdmissionLimits {
+            max_queued: 3,
+            max_queued_per_user: 1,
+            max_queued_per_model: 2,
+            ..test_limits()
+        };
+        let join = |user: &str, model: &str| queue.join(user, model, &limits).map(|_| ()).err();
+        let _alice = queue.join("alice", "gpt", &limits).expect("join");
+        assert_eq!(join("alice", "qwen"), Some(QueueScope::User));
+        let _bob = queue.join("bob", "gpt", &limits).expect("join");
+        assert_eq!(join("carol", "gpt"), Some(QueueScope::Model));
+        let _carol = queue.join("carol", "qwen", &limits).expect("join");
+        assert_eq!(join("dave", "llama"), Some(QueueScope::Server));
+        assert_eq!(queue.waiting.lock().len(), 3);
+
+        let unlimited = AdmissionLimits {
+            max_queued_per_user: 0,
+            max_queued_per_model: 0,
+            max_queued: 4,
+            ..limits
+        };
+        assert!(queue.join("alice", "gpt", &unlimited).is_ok());
+    }
+
+    #[test]
+    fn defaulted_and_explicit_requests_share_a_model_bucket() {
+        let mut config = Config::default();
+        config.llm.default = "gpt".to_string();
+        for name in ["gpt", "qwen"] {
+            config
+                .llm
+                .models
+                .insert(name.to_string(), LlmModelConfig::default());
+        }
+        assert_eq!(admission_model_key(&config, None), "gpt");
+        assert_eq!(admission_model_key(&config, Some(" ")), "gpt");
+
+        let queue = Arc::new(AdmissionQueue::default());
+        let limits = AdmissionLimits {
+            max_queued_per_model: 1,
+            ..test_limits()
+        };
+        let _defaulted = queue
+            .join("alice", &admission_model_key(&config, None), &limits)
+            .expect("join");
+        assert_eq!(
+            queue
+                .join("bob", &admission_model_key(&config, Some("gpt")), &limits)
+                .map(|_| ())
+                .err(),
+            Some(QueueScope::Model)
+        );
+        assert!(queue
+            .join("bob", &admission_model_key(&config, Some("qwen")), &limits)
+            .is_ok());
+    }
+}
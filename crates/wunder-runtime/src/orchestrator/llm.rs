@@ -1,7 +1,22 @@
 use super::*;
 use crate::core::llm_speed::LlmSpeedSummary;
+use crate::llm::{
+    model_rate_limiter, rate_limit_key, LlmErrorAction, LlmErrorKind, LlmProviderError,
+    LlmStreamInterrupted, ModelRateLimits, ModelRatePermit,
+};
 use sha2::{Digest, Sha256};
 
+/// `(content, reasoning, usage, tool_calls, speed, stream_interruption)`. The last entry is set
+/// when the provider stream dropped and the call ended with the partial answer.
+pub(super) type LlmCallOutput = (
+    String,
+    String,
+    TokenUsage,
+    Option<Value>,
+    LlmSpeedSummary,
+    Option<Value>,
+);
+
 #[derive(Default)]
 struct OutputTiming {
     first_output_at: Option<Instant>,
@@ -29,6 +44,9 @@ enum LlmFailureKind {
     Other,
     ContextWindow,
     Unavailable,
+    /// The provider refused the request for a reason retrying cannot fix (bad key, no credit,
+    /// unknown model, moderation, malformed request).
+    Rejected,
 }
 
 const LLM_UNAVAILABLE_MIN_RETRIES: u32 = 5;
@@ -95,6 +113,31 @@ impl OutputTiming {
     }
 }
 
+const REASONING_EVENT_PREVIEW_CHARS: usize = 200;
+
+/// Build the standalone `reasoning` event emitted after each round's `llm_output`.
+/// The full text already travels in `llm_output.reasoning`; this persisted record only keeps
+/// a length and a short preview so clients can count rounds without storing the text twice.
+fn build_reasoning_event_payload(reasoning: &str, round_info: RoundInfo) -> Option<Value> {
+    if reasoning.trim().is_empty() {
+        return None;
+    }
+    let chars = reasoning.chars().count();
+    let preview: String = reasoning
+        .chars()
+        .take(REASONING_EVENT_PREVIEW_CHARS)
+        .collect();
+    let mut payload = json!({
+        "chars": chars,
+        "preview": preview,
+        "truncated": chars > REASONING_EVENT_PREVIEW_CHARS,
+    });
+    if let Value::Object(ref mut map) = payload {
+        round_info.insert_into(map);
+    }
+    Some(payload)
+}
+
 fn sanitize_chat_messages_for_request(messages: &[ChatMessage]) -> ChatMessageRepairReport {
     let mut repaired_count = 0usize;
     let messages = messages
@@ -329,23 +372,32 @@ fn is_known_bad_tool_call_name(name: &str) -> bool {
     )
 }
 
+/// The LLM a request runs on: `model_name`, else `llm.default`, else the first LLM model.
+pub(super) fn resolve_llm_model<'a>(
+    config: &'a Config,
+    model_name: Option<&str>,
+) -> Option<(&'a String, &'a LlmModelConfig)> {
+    let name = model_name
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or(config.llm.default.as_str());
+    config
+        .llm
+        .models
+        .get_key_value(name)
+        .filter(|(_, model)| is_llm_model(model))
+        .or_else(|| {
+            config
+                .llm
+                .models
+                .iter()
+                .find(|(_, model)| is_llm_model(model))
+        })
+}
+
 impl Orchestrator {
     fn resolve_user_daily_token_grant(&self, user_id: &str) -> Result<i64, OrchestratorError> {
-        let user = self
-            .storage
-            .get_user_account(user_id)
-            .map_err(|err| OrchestratorError::internal(err.to_string()))?;
-        let unit_level = user
-            .as_ref()
-            .and_then(|record| record.unit_id.as_deref())
-            .and_then(|unit_id| {
-                self.storage
-                    .get_org_unit(unit_id)
-                    .ok()
-                    .flatten()
-                    .map(|unit| unit.level)
-            });
-        Ok(UserStore::default_daily_token_grant_by_level(unit_level))
+        crate::user_access::daily_token_grant_for_user(self.storage.as_ref(), user_id)
+            .map_err(|err| OrchestratorError::internal(err.to_string()))
     }
 
     pub(super) async fn ensure_user_token_balance(
@@ -355,19 +407,19 @@ impl Orchestrator {
         _round_info: RoundInfo,
         _emit_quota_events: bool,
     ) -> Result<(), OrchestratorError> {
-        let today = UserStore::today_string();
-        let daily_grant = self.resolve_user_daily_token_grant(user_id)?;
-        let status = self
-            .storage
-            .prepare_user_token_balance(user_id, &today, daily_grant)
+        self.ensure_user_within_quota(user_id)
+    }
+
+    /// Reject over-quota users; also run by `prepare_request` so the turn never starts.
+    pub(super) fn ensure_user_within_quota(&self, user_id: &str) -> Result<(), OrchestratorError> {
+        let status = crate::user_access::prepare_user_token_status(self.storage.as_ref(), user_id)
             .map_err(|err| OrchestratorError::internal(err.to_string()))?;
-        let Some(status) = status else {
-            return Ok(());
-        };
-        if !status.allowed {
-            return Err(OrchestratorError::user_token_insufficient(status));
+        match status {
+            Some(status) if !status.allowed => {
+                Err(OrchestratorError::user_token_insufficient(status))
+            }
+            _ => Ok(()),
         }
-        Ok(())
     }
 
     pub(super) async fn consume_user_tokens(
@@ -420,26 +472,8 @@ impl Orchestrator {
         config: &Config,
         model_name: Option<&str>,
     ) -> Result<(String, LlmModelConfig), OrchestratorError> {
-        let name = model_name
-            .filter(|value| !value.trim().is_empty())
-            .unwrap_or(config.llm.default.as_str());
-        if !name.trim().is_empty() {
-            if let Some(configured) = config
-                .llm
-                .models
-                .get(name)
-                .filter(|model| is_llm_model(model))
-            {
-                return Ok((name.to_string(), configured.clone()));
-            }
-        }
-        if let Some((fallback_name, fallback)) = config
-            .llm
-            .models
-            .iter()
-            .find(|(_, model)| is_llm_model(model))
-        {
-            return Ok((fallback_name.clone(), fallback.clone()));
+        if let Some((name, configured)) = resolve_llm_model(config, model_name) {
+            return Ok((name.clone(), configured.clone()));
         }
         let detail = i18n::t("error.llm_config_required");
         Err(OrchestratorError::llm_unavailable(i18n::t_with_params(
@@ -609,6 +643,65 @@ impl Orchestrator {
         }
     }
 
+    /// Wait for the model's shared concurrency/QPS budget, recording an `llm_rate_limited` event
+    /// when the request has to queue.
+    async fn acquire_model_rate_permit(
+        &self,
+        config: &LlmModelConfig,
+        emitter: &EventEmitter,
+        round_info: RoundInfo,
+        emit_events: bool,
+    ) -> ModelRatePermit {
+        let limits = ModelRateLimits::from_config(config);
+        let key = rate_limit_key(config);
+        let limiter = model_rate_limiter();
+        let reason = match limiter.try_acquire(&key, limits) {
+            Ok(permit) => return permit,
+            Err(reason) => reason,
+        };
+        if emit_events {
+            let mut payload = json!({
+                "model": config.model.as_deref().unwrap_or_default(),
+                "reason": reason.as_str(),
+                "max_concurrency": limits.max_concurrency,
+                "max_qps": limits.max_qps,
+            });
+            if let Value::Object(ref mut map) = payload {
+                round_info.insert_into(map);
+            }
+            emitter.emit("llm_rate_limited", payload).await;
+        }
+        limiter.acquire(&key, limits).await
+    }
+
+    /// Trim the prompt to the model's `max_context` before sending so an over-long history
+    /// doesn't come back as a provider 400.
+    async fn trim_to_context_window(
+        &self,
+        config: &LlmModelConfig,
+        messages: &[Value],
+        tools: Option<&[Value]>,
+        emitter: &EventEmitter,
+        round_info: RoundInfo,
+        emit_events: bool,
+    ) -> Option<ContextTrim> {
+        let budget = ContextBudget::from_config(config)?;
+        let counter = TokenCounter::for_model(config.model.as_deref().unwrap_or(""));
+        let tool_tokens = tools
+            .map(|tools| counter.count(&Value::Array(tools.to_vec()).to_string()))
+            .unwrap_or(0);
+        let limit = (budget.prompt_tokens() - tool_tokens).max(1);
+        let trim = self.trim_messages_to_context(messages, limit, counter)?;
+        if emit_events {
+            let mut payload = trim.event_payload(budget, tool_tokens);
+            if let Value::Object(ref mut map) = payload {
+                round_info.insert_into(map);
+            }
+            emitter.emit("context_trimmed", payload).await;
+        }
+        Some(trim)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub(super) async fn call_llm(
         &self,
@@ -625,8 +718,8 @@ impl Orchestrator {
         log_payload: bool,
         tools: Option<&[Value]>,
         llm_config_override: Option<LlmModelConfig>,
-    ) -> Result<(String, String, TokenUsage, Option<Value>, LlmSpeedSummary), OrchestratorError>
-    {
+        response_format: Option<Value>,
+    ) -> Result<LlmCallOutput, OrchestratorError> {
         self.ensure_not_cancelled(session_id)?;
         let effective_config = llm_config_override.unwrap_or_else(|| llm_config.clone());
         if !is_llm_configured(&effective_config) {
@@ -664,7 +757,7 @@ impl Orchestrator {
                     }
                     emitter.emit("token_usage", usage_payload).await;
                 }
-                return Ok((content, String::new(), usage, None, round_speed));
+                return Ok((content, String::new(), usage, None, round_speed, None));
             }
             let detail = i18n::t("error.llm_config_missing");
             return Err(OrchestratorError::llm_unavailable(i18n::t_with_params(
@@ -678,9 +771,29 @@ impl Orchestrator {
                 .await?;
         }
 
-        let client = build_llm_client(&effective_config, self.http.clone());
+        let _rate_permit = self
+            .acquire_model_rate_permit(&effective_config, emitter, round_info, emit_events)
+            .await;
+        self.ensure_not_cancelled(session_id)?;
+
+        let context_trim = self
+            .trim_to_context_window(
+                &effective_config,
+                messages,
+                tools,
+                emitter,
+                round_info,
+                emit_events,
+            )
+            .await;
+        let messages = context_trim
+            .as_ref()
+            .map_or(messages, |trim| trim.messages.as_slice());
+
+        let client = build_llm_client(&effective_config, self.http.clone())
+            .with_response_format(response_format.clone());
         let context_manager = ContextManager;
-        let request_messages = context_manager.normalize_messages(messages.to_vec());
+        let mut request_messages = context_manager.normalize_messages(messages.to_vec());
         let message_repair = (request_messages.as_slice() != messages).then(|| {
             json!({
                 "kind": "chat_messages",
@@ -690,6 +803,9 @@ impl Orchestrator {
                 "after_count": request_messages.len(),
             })
         });
+        if response_format.is_some() {
+            crate::llm::add_json_mode_instruction(&mut request_messages);
+        }
         let chat_messages =
             sanitize_chat_messages_for_request(&self.build_chat_messages(&request_messages));
         let native_tools_attached = tools.is_some_and(|items| !items.is_empty());
@@ -727,6 +843,14 @@ impl Orchestrator {
                     ))
                 })?,
             )
+        } else if crate::services::mock_llm::is_mock_provider(effective_config.provider.as_deref())
+        {
+            Some(crate::services::mock_llm::turn_for_round(
+                &effective_config,
+                &request_messages,
+                round_info.user_round,
+                round_info.model_round,
+            ))
         } else {
             None
         };
@@ -818,10 +942,13 @@ impl Orchestrator {
                         Ok(())
                     }
                 };
+                // Mock replies stream without the replay pacing so scripted runs stay fast.
+                let token_delay_ms =
+                    crate::services::mock_llm::is_mock_turn(&virtual_turn).then_some(0);
                 let fut = crate::services::virtual_llm::emit_virtual_deltas(
                     &virtual_turn,
                     true,
-                    None,
+                    token_delay_ms,
                     on_delta,
                 );
                 self.await_with_cancel(session_id, timeout_s, fut)
@@ -883,6 +1010,11 @@ impl Orchestrator {
                     round_speed.insert_into_map(map);
                 }
                 emitter.emit("llm_output", output_payload).await;
+                if let Some(reasoning_payload) =
+                    build_reasoning_event_payload(&reasoning, round_info)
+                {
+                    emitter.emit("reasoning", reasoning_payload).await;
+                }
                 let mut usage_payload = json!({
                     "input_tokens": usage.input,
                     "output_tokens": usage.output,
@@ -908,13 +1040,16 @@ impl Orchestrator {
                 )
                 .await?;
             }
-            return Ok((content, reasoning, usage, tool_calls, round_speed));
+            return Ok((content, reasoning, usage, tool_calls, round_speed, None));
         }
         let mut attempt = 0u32;
         let mut last_err: anyhow::Error;
         let mut force_non_stream_retry = false;
+        let mut stream_interrupt_attempts = 0u32;
+        let breaker = self.llm_breaker(&effective_config).await;
         loop {
             attempt += 1;
+            breaker.check(emitter, round_info).await?;
             let request_started_at = Instant::now();
             let output_timing = Arc::new(parking_lot::Mutex::new(OutputTiming::default()));
             let will_stream = initial_will_stream && !force_non_stream_retry;
@@ -970,6 +1105,69 @@ impl Orchestrator {
                 self.await_with_cancel(session_id, timeout_s, fut).await?
             };
 
+            let mut stream_interruption: Option<Value> = None;
+            let result = match result.map_err(|err| err.downcast::<LlmStreamInterrupted>()) {
+                Ok(response) => Ok(response),
+                Err(Ok(interrupted)) => {
+                    // A dropped stream is re-sent at most once when `llm.stream_interrupt.retry`
+                    // is on; otherwise, or if the retry drops too, the partial answer is kept.
+                    stream_interrupt_attempts += 1;
+                    let retry_enabled = self.config_store.get().await.llm.stream_interrupt.retry;
+                    let max_attempts = 1 + u32::from(retry_enabled);
+                    let will_retry = stream_interrupt_attempts < max_attempts;
+                    let retry_delay = resolve_llm_retry_delay(1, LlmFailureKind::Unavailable);
+                    if emit_events {
+                        let mut interrupt_payload = json!({
+                            "attempt": stream_interrupt_attempts,
+                            "max_attempts": max_attempts,
+                            "will_retry": will_retry,
+                            "delay_s": if will_retry { retry_delay.as_secs_f64() } else { 0.0 },
+                            "partial_chars": interrupted.partial.content.chars().count(),
+                            "error": interrupted.to_string(),
+                        });
+                        if let Value::Object(ref mut map) = interrupt_payload {
+                            round_info.insert_into(map);
+                        }
+                        emitter.emit("stream_interrupted", interrupt_payload).await;
+                    }
+                    if will_retry {
+                        if emit_events {
+                            // `reset_output` tells clients to drop the partial deltas already
+                            // shown; the retried request streams the answer from the start.
+                            let mut retry_payload = json!({
+                                "attempt": stream_interrupt_attempts,
+                                "max_attempts": max_attempts,
+                                "delay_s": retry_delay.as_secs_f64(),
+                                "retry_reason": STREAM_INTERRUPTED_RETRY_REASON,
+                                "stream": true,
+                                "will_retry": true,
+                                "reset_output": true,
+                                "error": interrupted.to_string(),
+                            });
+                            if let Value::Object(ref mut map) = retry_payload {
+                                round_info.insert_into(map);
+                            }
+                            emitter.emit("llm_stream_retry", retry_payload).await;
+                        }
+                        self.sleep_or_cancel(session_id, retry_delay).await?;
+                        continue;
+                    }
+                    stream_interruption = Some(json!({
+                        "incomplete": true,
+                        "error": interrupted.to_string(),
+                    }));
+                    Ok(interrupted.partial)
+                }
+                Err(Err(err)) => Err(err),
+            };
+
+            // Only unavailability counts against the breaker; a rejected request means the
+            // endpoint is up.
+            let upstream_failed = result
+                .as_ref()
+                .err()
+                .is_some_and(|err| matches!(classify_llm_error(err), LlmFailureKind::Unavailable));
+            breaker.record(upstream_failed, emitter, round_info).await;
             match result {
                 Ok(response) => {
                     let response_finished_at = Instant::now();
@@ -1075,10 +1273,18 @@ impl Orchestrator {
                             "stream_timing": stream_timing,
                         });
                         if let Value::Object(ref mut map) = output_payload {
+                            if stream_interruption.is_some() {
+                                map.insert("incomplete".to_string(), Value::Bool(true));
+                            }
                             round_info.insert_into(map);
                             round_speed.insert_into_map(map);
                         }
                         emitter.emit("llm_output", output_payload).await;
+                        if let Some(reasoning_payload) =
+                            build_reasoning_event_payload(&reasoning, round_info)
+                        {
+                            emitter.emit("reasoning", reasoning_payload).await;
+                        }
                         let mut usage_payload = json!({
                             "input_tokens": usage.input,
                             "output_tokens": usage.output,
@@ -1104,7 +1310,14 @@ impl Orchestrator {
                         )
                         .await?;
                     }
-                    return Ok((content, reasoning, usage, tool_calls, round_speed));
+                    return Ok((
+                        content,
+                        reasoning,
+                        usage,
+                        tool_calls,
+                        round_speed,
+                        stream_interruption,
+                    ));
                 }
                 Err(err) => {
                     let failure_kind = classify_llm_error(&err);
@@ -1148,13 +1361,16 @@ impl Orchestrator {
             message_key,
             &HashMap::from([("detail".to_string(), detail)]),
         );
-        match failure_kind {
-            LlmFailureKind::ContextWindow => {
-                Err(OrchestratorError::context_window_exceeded(message))
+        let provider_detail = find_provider_error(&last_err)
+            .map(|provider_error| json!({ "provider_error": provider_error.to_value() }));
+        let error = match failure_kind {
+            LlmFailureKind::ContextWindow => OrchestratorError::context_window_exceeded(message),
+            LlmFailureKind::Unavailable => OrchestratorError::llm_unavailable(message),
+            LlmFailureKind::Other | LlmFailureKind::Rejected => {
+                OrchestratorError::internal(message)
             }
-            LlmFailureKind::Unavailable => Err(OrchestratorError::llm_unavailable(message)),
-            LlmFailureKind::Other => Err(OrchestratorError::internal(message)),
-        }
+        };
+        Err(error.with_detail(provider_detail))
     }
 }
 
@@ -1170,6 +1386,17 @@ fn classify_llm_failure(message: &str) -> LlmFailureKind {
 }
 
 fn classify_llm_error(error: &anyhow::Error) -> LlmFailureKind {
+    if let Some(provider_error) = find_provider_error(error) {
+        match provider_error.kind.action() {
+            LlmErrorAction::Fallback => return LlmFailureKind::ContextWindow,
+            LlmErrorAction::SurfaceToUser => return LlmFailureKind::Rejected,
+            LlmErrorAction::Retry if provider_error.kind != LlmErrorKind::Unknown => {
+                return LlmFailureKind::Unavailable;
+            }
+            // Unrecognized bodies fall back to the text heuristics below.
+            LlmErrorAction::Retry => {}
+        }
+    }
     let message = error.to_string();
     if is_context_window_error_text(&message) {
         return LlmFailureKind::ContextWindow;
@@ -1180,6 +1407,12 @@ fn classify_llm_error(error: &anyhow::Error) -> LlmFailureKind {
     LlmFailureKind::Other
 }
 
+fn find_provider_error(error: &anyhow::Error) -> Option<&LlmProviderError> {
+    error
+        .chain()
+        .find_map(|source| source.downcast_ref::<LlmProviderError>())
+}
+
 fn is_llm_request_transport_error(error: &anyhow::Error) -> bool {
     error.chain().any(|source| {
         source.downcast_ref::<reqwest::Error>().is_some_and(|err| {
@@ -1243,10 +1476,12 @@ fn is_llm_unavailable_error_text(message: &str) -> bool {
 }
 
 fn resolve_llm_max_attempts(failure_kind: LlmFailureKind) -> u32 {
-    if matches!(failure_kind, LlmFailureKind::Unavailable) {
-        DEFAULT_LLM_MAX_ATTEMPTS.max(LLM_UNAVAILABLE_MIN_RETRIES.saturating_add(1))
-    } else {
-        DEFAULT_LLM_MAX_ATTEMPTS
+    match failure_kind {
+        LlmFailureKind::Unavailable => {
+            DEFAULT_LLM_MAX_ATTEMPTS.max(LLM_UNAVAILABLE_MIN_RETRIES.saturating_add(1))
+        }
+        LlmFailureKind::Rejected => 1,
+        LlmFailureKind::Other | LlmFailureKind::ContextWindow => DEFAULT_LLM_MAX_ATTEMPTS,
     }
 }
 
@@ -1263,11 +1498,15 @@ fn resolve_llm_retry_delay(attempt: u32, failure_kind: LlmFailureKind) -> Durati
     }
 }
 
+/// `retry_reason` of the single `llm_stream_retry` sent after a mid-stream disconnect.
+const STREAM_INTERRUPTED_RETRY_REASON: &str = "stream_interrupted";
+
 fn llm_retry_reason(failure_kind: LlmFailureKind) -> &'static str {
     match failure_kind {
         LlmFailureKind::ContextWindow => "context_window",
         LlmFailureKind::Unavailable => "llm_unavailable",
         LlmFailureKind::Other => "provider_error",
+        LlmFailureKind::Rejected => "provider_rejected",
     }
 }
 
@@ -1385,11 +1624,11 @@ fn parse_context_limit_number(raw: &str) -> Option<i64> {
 #[cfg(test)]
 mod tests {
     use super::{
-        build_context_cache_probe, classify_llm_failure, detect_invalid_tool_calls,
-        extract_context_window_limit_hint, is_context_window_error_text,
-        is_llm_unavailable_error_text, llm_retry_reason, resolve_llm_max_attempts,
-        resolve_llm_retry_delay, LlmFailureKind, DEFAULT_LLM_MAX_ATTEMPTS,
-        LLM_UNAVAILABLE_MIN_RETRIES,
+        build_context_cache_probe, build_reasoning_event_payload, classify_llm_error,
+        classify_llm_failure, detect_invalid_tool_calls, extract_context_window_limit_hint,
+        is_context_window_error_text, is_llm_unavailable_error_text, llm_retry_reason,
+        resolve_llm_max_attempts, resolve_llm_retry_delay, LlmFailureKind,
+        DEFAULT_LLM_MAX_ATTEMPTS, LLM_UNAVAILABLE_MIN_RETRIES,
     };
     use crate::core::config::LlmModelConfig;
     use crate::llm::ChatMessage;
@@ -1545,6 +1784,35 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn normalized_provider_errors_drive_the_retry_policy() {
+        let classify = |status: u16, body: &str| {
+            let err = anyhow::Error::new(crate::llm::classify_llm_error_response(status, body));
+            classify_llm_error(&err)
+        };
+        assert_eq!(
+            classify(
+                400,
+                r#"{"error":{"message":"too long","code":"context_length_exceeded"}}"#
+            ),
+            LlmFailureKind::ContextWindow
+        );
+        assert_eq!(
+            classify(
+                429,
+                r#"{"code":"Throttling.RateQuota","message":"Requests rate limit exceeded"}"#
+            ),
+            LlmFailureKind::Unavailable
+        );
+        let rejected = classify(
+            401,
+            r#"{"error":{"message":"Incorrect API key provided","code":"invalid_api_key"}}"#,
+        );
+        assert_eq!(rejected, LlmFailureKind::Rejected);
+        assert_eq!(resolve_llm_max_attempts(rejected), 1);
+        assert_eq!(classify(418, "teapot"), LlmFailureKind::Other);
+    }
+
     #[test]
     fn llm_unavailable_retries_use_floor_and_long_backoff() {
         let attempts = resolve_llm_max_attempts(LlmFailureKind::Unavailable);
@@ -1684,4 +1952,32 @@ mod tests {
 
         assert!(detect_invalid_tool_calls(Some(&payload)).is_none());
     }
+
+    #[test]
+    fn reasoning_event_payload_skips_blank_reasoning() {
+        let round_info = super::RoundInfo {
+            user_round: Some(2),
+            model_round: Some(1),
+        };
+        assert!(build_reasoning_event_payload("  \n", round_info).is_none());
+        assert_eq!(
+            build_reasoning_event_payload("思考", round_info),
+            Some(json!({
+                "chars": 2,
+                "preview": "思考",
+                "truncated": false,
+                "user_round": 2,
+                "model_round": 1,
+            }))
+        );
+        let long = "想".repeat(super::REASONING_EVENT_PREVIEW_CHARS + 5);
+        let payload = build_reasoning_event_payload(&long, round_info).expect("payload");
+        assert_eq!(payload["chars"], super::REASONING_EVENT_PREVIEW_CHARS + 5);
+        assert_eq!(payload["truncated"], true);
+        assert_eq!(
+            payload["preview"].as_str().map(|text| text.chars().count()),
+            Some(super::REASONING_EVENT_PREVIEW_CHARS)
+        );
+        assert!(payload.get("content").is_none());
+    }
 }
@@ -0,0 +1,94 @@
+// 模型调用的上游熔断接入：按模型端点检查/记录熔断状态，状态变化以 upstream_breaker 事件写入事件流与监控。
+use super::*;
+use crate::llm::rate_limit_key;
+use crate::services::upstream_breaker::{
+    upstream_breakers, BreakerSettings, BreakerTransition, UpstreamKind, UPSTREAM_BREAKER_EVENT,
+};
+
+/// Breaker key for a model: its base URL, so every model served by one endpoint shares it.
+fn llm_breaker_endpoint(config: &LlmModelConfig) -> String {
+    config
+        .base_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| rate_limit_key(config))
+}
+
+/// Breaker handle for one `call_llm`; inert when the LLM breaker is disabled.
+pub(super) struct LlmBreaker {
+    endpoint: String,
+    settings: Option<BreakerSettings>,
+}
+
+impl Orchestrator {
+    pub(super) async fn llm_breaker(&self, config: &LlmModelConfig) -> LlmBreaker {
+        let app_config = self.config_store.get().await;
+        LlmBreaker {
+            endpoint: llm_breaker_endpoint(config),
+            settings: BreakerSettings::resolve(
+                &app_config.gateway.circuit_breaker,
+                UpstreamKind::Llm,
+            ),
+        }
+    }
+}
+
+impl LlmBreaker {
+    /// Fail fast with `llm_unavailable` instead of calling an endpoint whose breaker is open.
+    pub(super) async fn check(
+        &self,
+        emitter: &EventEmitter,
+        round_info: RoundInfo,
+    ) -> Result<(), OrchestratorError> {
+        let Some(settings) = self.settings else {
+            return Ok(());
+        };
+        match upstream_breakers().check(UpstreamKind::Llm, &self.endpoint, settings) {
+            Ok(transition) => {
+                emit_transition(emitter, round_info, transition).await;
+                Ok(())
+            }
+            Err(unavailable) => Err(OrchestratorError::llm_unavailable(i18n::t_with_params(
+                "error.llm_unavailable",
+                &HashMap::from([("detail".to_string(), unavailable.to_string())]),
+            ))),
+        }
+    }
+
+    pub(super) async fn record(
+        &self,
+        upstream_failed: bool,
+        emitter: &EventEmitter,
+        round_info: RoundInfo,
+    ) {
+        let Some(settings) = self.settings else {
+            return;
+        };
+        let breakers = upstream_breakers();
+        let transition = if upstream_failed {
+            breakers.record_failure(UpstreamKind::Llm, &self.endpoint, settings)
+        } else {
+            breakers.record_success(UpstreamKind::Llm, &self.endpoint, settings)
+        };
+        emit_transition(emitter, round_info, transition).await;
+    }
+}
+
+/// State changes are emitted even for quiet calls: the emitter records them into the monitor.
+async fn emit_transition(
+    emitter: &EventEmitter,
+    round_info: RoundInfo,
+    transition: Option<BreakerTransition>,
+) {
+    let Some(transition) = transition else {
+        return;
+    };
+    transition.log();
+    let mut payload = transition.to_value();
+    if let Value::Object(ref mut map) = payload {
+        round_info.insert_into(map);
+    }
+    emitter.emit(UPSTREAM_BREAKER_EVENT, payload).await;
+}
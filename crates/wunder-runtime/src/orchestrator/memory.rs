@@ -11,8 +11,15 @@ use crate::core::{blocking, long_task};
 pub(super) use super::memory_support::{insert_compaction_id, CompactionRunMode};
 
 impl Orchestrator {
-    pub(super) fn shrink_messages_to_limit(&self, messages: Vec<Value>, limit: i64) -> Vec<Value> {
-        let total_tokens = estimate_messages_tokens(&messages);
+    /// Shorten observation messages (tool results) until `messages` fit `limit` tokens as
+    /// measured by `counter`.
+    pub(super) fn shrink_messages_to_limit(
+        &self,
+        messages: Vec<Value>,
+        limit: i64,
+        counter: TokenCounter,
+    ) -> Vec<Value> {
+        let total_tokens = counter.messages_tokens(&messages);
         if total_tokens <= limit {
             return messages;
         }
@@ -28,13 +35,13 @@ impl Orchestrator {
                 if !Self::is_observation_message(role, content) {
                     false
                 } else if let Value::String(text) = content {
-                    let current_tokens = approx_token_count(text);
+                    let current_tokens = counter.count(text);
                     if current_tokens <= COMPACTION_MIN_OBSERVATION_TOKENS {
                         false
                     } else {
                         let target_tokens =
                             (current_tokens - overflow).max(COMPACTION_MIN_OBSERVATION_TOKENS);
-                        let new_content = trim_text_to_tokens(
+                        let new_content = counter.trim_text(
                             text,
                             target_tokens,
                             COMPACTION_TEXT_TRUNCATION_SUFFIX,
@@ -53,7 +60,7 @@ impl Orchestrator {
                 false
             };
             if changed {
-                overflow = (estimate_messages_tokens(&trimmed) - limit).max(0);
+                overflow = (counter.messages_tokens(&trimmed) - limit).max(0);
             }
         }
         trimmed
@@ -372,7 +379,8 @@ impl Orchestrator {
         let per_message_limit = summary_limit.clamp(1, COMPACTION_SUMMARY_MESSAGE_MAX_TOKENS);
         summary_input = self.prepare_summary_messages(summary_input, per_message_limit);
         if estimate_messages_tokens(&summary_input) > summary_limit {
-            summary_input = self.shrink_messages_to_limit(summary_input, summary_limit);
+            summary_input =
+                self.shrink_messages_to_limit(summary_input, summary_limit, TokenCounter::GENERIC);
         }
         if estimate_messages_tokens(&summary_input) > summary_limit {
             let mut trimmed = Vec::new();
@@ -438,10 +446,11 @@ impl Orchestrator {
                 log_payload,
                 None,
                 Some(summary_config),
+                None,
             )
             .await
         {
-            Ok((content, _, _, _, _)) => self.resolve_final_answer(&content),
+            Ok((content, ..)) => self.resolve_final_answer(&content),
             Err(err) => {
                 if matches!(
                     err.code(),
@@ -674,7 +683,8 @@ impl Orchestrator {
         if let Some(current_turn_replay_message) = current_user_replay.message {
             rebuilt.push(current_turn_replay_message);
         }
-        let mut rebuilt = self.shrink_messages_to_limit(rebuilt, message_budget);
+        let mut rebuilt =
+            self.shrink_messages_to_limit(rebuilt, message_budget, TokenCounter::GENERIC);
         let guard_stats = apply_rebuilt_context_guard(
             &mut rebuilt,
             message_budget,
@@ -98,8 +98,15 @@ impl Orchestrator {
             if trimmed.is_empty() {
                 continue;
             }
+            // Page count and language help the model size up a document before reading it.
+            let summary = attachment
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.summary())
+                .map(|summary| format!(" ({summary})"))
+                .unwrap_or_default();
             attachment_parts.push(format!(
-                "[{attachment_label}{attachment_separator}{display_name}]\n{trimmed}"
+                "[{attachment_label}{attachment_separator}{display_name}]{summary}\n{trimmed}"
             ));
         }
         let mut text_content = String::new();
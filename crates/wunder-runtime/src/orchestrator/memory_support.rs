@@ -1383,6 +1383,7 @@ pub(super) fn parse_compaction_observation_payload(content: &Value) -> Option<Va
         .strip_prefix(OBSERVATION_PREFIX)
         .map(str::trim)
         .unwrap_or(raw);
+    let payload_text = super::tool_result_guard::unwrap_guarded_observation(payload_text);
     if payload_text.is_empty() {
         return None;
     }
@@ -32,11 +32,12 @@ use crate::schemas::{AttachmentPayload, StreamEvent, TokenUsage, WunderRequest,
 use crate::services::beeroom_realtime::BeeroomRealtimeService;
 use crate::services::inner_visible::InnerVisibleService;
 use crate::services::tools::command_sessions::CommandSessionBroker;
+use crate::services::turn_notifier::{TurnNotification, TurnNotifier, TurnNotifierRegistry};
 use crate::skills::{load_skills, SkillRegistry};
 use crate::storage::{SessionLockStatus, StorageBackend, UserTokenBalanceStatus};
 use crate::token_utils::{
     approx_token_count, estimate_message_tokens, estimate_messages_tokens, trim_messages_to_budget,
-    trim_text_to_chars, trim_text_to_tokens,
+    trim_text_to_chars, trim_text_to_tokens, TokenCounter,
 };
 use crate::tools::{
     build_desktop_followup_user_message, build_read_image_followup_user_message, builtin_aliases,
@@ -65,10 +66,13 @@ use tokio::task::JoinHandle;
 use tracing::{error, warn};
 use uuid::Uuid;
 
+mod attachment_memory;
+mod citations;
 mod compaction_policy;
 mod config;
 pub mod constants;
 mod context;
+mod context_trim;
 mod error;
 mod event_stream;
 mod execute;
@@ -76,6 +80,7 @@ mod execute_support;
 mod execute_tools;
 mod limiter;
 mod llm;
+mod llm_breaker;
 mod memory;
 mod memory_auto_extract;
 mod memory_compaction_window;
@@ -85,21 +90,30 @@ mod prompt;
 mod request;
 mod result_normalizer;
 mod retry_governor;
+mod round_limit;
 mod runtime_snapshot;
+mod session_title;
 mod stream_persist;
+mod structured_answer;
 mod thread_runtime;
 mod tool_calls;
 mod tool_exec;
 mod tool_parallel;
+mod tool_result_budget;
+mod tool_result_guard;
 mod tool_result_payload;
 mod turn_state;
 mod types;
 
+pub(crate) use config::apply_config_overrides;
 use context::ContextManager;
+use context_trim::{ContextBudget, ContextTrim};
+pub use error::orchestrator_error_payload;
 pub(crate) use error::OrchestratorError;
 use event_stream::EventEmitter;
 use event_stream::StreamSignal;
-use limiter::RequestLimiter;
+use limiter::{admission_model_key, AdmissionLimits, RequestLimiter};
+pub use prompt::merge_agent_prompt_with_thread_agents_snapshot;
 pub(crate) use stream_persist::flush_stream_event_persist_queue;
 use thread_runtime::ThreadRuntimeRegistry;
 use tool_calls::apply_tool_name_map;
@@ -130,6 +144,7 @@ pub struct Orchestrator {
     user_world: Arc<UserWorldService>,
     beeroom_realtime: Arc<BeeroomRealtimeService>,
     cron_wake_signal: Option<CronWakeSignal>,
+    turn_notifiers: Arc<TurnNotifierRegistry>,
     http: reqwest::Client,
 }
 
@@ -172,10 +187,16 @@ impl Orchestrator {
             user_world,
             beeroom_realtime,
             cron_wake_signal,
+            turn_notifiers: Arc::new(TurnNotifierRegistry::new()),
             http: reqwest::Client::new(),
         }
     }
 
+    /// Registers a backend called after every visible turn finishes.
+    pub fn register_turn_notifier(&self, notifier: Arc<dyn TurnNotifier>) {
+        self.turn_notifiers.register(notifier);
+    }
+
     pub async fn resolve_session_effective_tool_names(
         &self,
         user: &crate::storage::UserAccountRecord,
@@ -184,9 +205,13 @@ impl Orchestrator {
         const TOOL_OVERRIDE_NONE: &str = "__no_tools__";
         let config = self.config_store.get().await;
         let skills = self.skills.read().await.clone();
-        let bindings = self
-            .user_tool_manager
-            .build_bindings(&config, &skills, &user.user_id);
+        let bindings = if session.parent_session_id.is_some() {
+            self.user_tool_manager
+                .build_subagent_bindings(&config, &skills, &user.user_id)
+        } else {
+            self.user_tool_manager
+                .build_bindings(&config, &skills, &user.user_id)
+        };
         let user_context = crate::user_access::UserToolContext {
             config: config.clone(),
             skills,
@@ -324,7 +324,9 @@ impl Orchestrator {
     }
 }
 
-pub(crate) fn merge_agent_prompt_with_thread_agents_snapshot(
+/// Appends the thread `AGENTS.md` snapshot to `base_agent_prompt`: the copy frozen in the
+/// stored session prompt, or a fresh read of `workspace_root` when `allow_initial_read`.
+pub fn merge_agent_prompt_with_thread_agents_snapshot(
     base_agent_prompt: Option<&str>,
     stored_session_prompt: Option<&str>,
     workspace_root: &Path,
@@ -1,12 +1,18 @@
 use super::*;
 use crate::core::long_task;
 use crate::orchestrator_constants::MAX_USER_INPUT_TEXT_CHARS;
+use crate::prompt_budget::{breakdown_prompt_sections, PromptBudgetBreakdown};
+use crate::prompting::PromptSection;
 use crate::request_limits::measure_request_text_input_chars;
+use crate::services::agent_definitions;
+use crate::services::attachment_ocr;
+use crate::services::attachment_upload::{AttachmentUploadError, AttachmentUploadStore};
+use tracing::Instrument;
 
 impl Orchestrator {
     async fn prepare_request(
         &self,
-        request: WunderRequest,
+        mut request: WunderRequest,
     ) -> Result<PreparedRequest, OrchestratorError> {
         let user_id = request.user_id.trim().to_string();
         if user_id.is_empty() {
@@ -19,17 +25,49 @@ impl Orchestrator {
                 "failed to sync inner-visible state: {err}"
             )));
         }
+        if let Some(attachments) = request.attachments.as_mut() {
+            AttachmentUploadStore::shared()
+                .resolve_attachments(&user_id, attachments)
+                .await
+                .map_err(|err| match err {
+                    AttachmentUploadError::Io(err) => OrchestratorError::internal(format!(
+                        "failed to load uploaded attachment: {err}"
+                    )),
+                    AttachmentUploadError::NotFound => OrchestratorError::invalid_request(
+                        "uploaded attachment not found".to_string(),
+                    ),
+                    other => OrchestratorError::invalid_request(other.to_string()),
+                })?;
+        }
+        if let Some(attachments) = request.attachments.as_mut() {
+            let config = self.config_store.get().await;
+            attachment_ocr::apply_image_ocr(&config.attachments.ocr, &self.workspace, attachments)
+                .await;
+        }
+        if request.agent_id.is_some() {
+            let config = self.config_store.get().await;
+            agent_definitions::apply_agent_definition(&config, &mut request).await;
+        }
         let agent_id = request
             .agent_id
             .as_deref()
             .map(str::trim)
             .filter(|value| !value.is_empty())
             .map(|value| value.to_string());
-        let workspace_id = self.resolve_workspace_id(
-            &user_id,
-            agent_id.as_deref(),
-            request.workspace_container_id,
-        );
+        let session_id = request
+            .session_id
+            .clone()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+        let workspace_container_id = match request.workspace_container_id {
+            Some(container_id) => Some(container_id),
+            None => {
+                self.resolve_session_container_id(&user_id, &session_id)
+                    .await
+            }
+        };
+        let workspace_id =
+            self.resolve_workspace_id(&user_id, agent_id.as_deref(), workspace_container_id);
         if let Err(err) = self.workspace.ensure_user_root(&workspace_id) {
             return Err(OrchestratorError::internal(format!(
                 "failed to prepare workspace: {err}"
@@ -66,21 +104,15 @@ impl Orchestrator {
             )));
         }
         validate_request_text_input_size(&question, request.attachments.as_deref())?;
-        let session_id = request
-            .session_id
-            .clone()
-            .filter(|value| !value.trim().is_empty())
-            .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+        if !request.is_admin {
+            self.ensure_user_within_quota(&user_id)?;
+        }
         let tool_names = if request.tool_names.is_empty() {
             None
         } else {
             Some(request.tool_names.clone())
         };
-        let language = request
-            .language
-            .clone()
-            .filter(|value| !value.trim().is_empty())
-            .unwrap_or_else(i18n::get_default_language);
+        let language = resolve_request_language(&request);
         let attachments = request
             .attachments
             .clone()
@@ -101,6 +133,9 @@ impl Orchestrator {
             stream: request.stream,
             debug_payload: request.debug_payload,
             attachments,
+            max_rounds: request.max_rounds,
+            cite: request.cite,
+            remember_attachments: request.remember_attachments,
             language,
             allow_queue: request.allow_queue,
             is_admin: request.is_admin,
@@ -109,6 +144,31 @@ impl Orchestrator {
         })
     }
 
+    /// With `workspace.session_sandbox` enabled, sessions without an explicit container get
+    /// their own slot so tool calls of one session share a sandbox and other sessions do not.
+    async fn resolve_session_container_id(&self, user_id: &str, session_id: &str) -> Option<i32> {
+        if !self.config_store.get().await.workspace.session_sandbox {
+            return None;
+        }
+        let storage = self.storage.clone();
+        let (assign_user, assign_session) = (user_id.to_string(), session_id.to_string());
+        match crate::core::blocking::run_db("orchestrator.request.assign_sandbox", move || {
+            crate::services::sandbox_session::assign_session_container_id(
+                storage.as_ref(),
+                &assign_user,
+                &assign_session,
+            )
+        })
+        .await
+        {
+            Ok(container_id) => Some(container_id),
+            Err(err) => {
+                warn!("failed to assign sandbox container for session {session_id}: {err}");
+                None
+            }
+        }
+    }
+
     pub(crate) fn resolve_workspace_id(
         &self,
         user_id: &str,
@@ -148,8 +208,8 @@ impl Orchestrator {
     }
 
     pub async fn run(&self, request: WunderRequest) -> Result<WunderResponse> {
-        let prepared = self.prepare_request(request).await?;
-        let language = prepared.language.clone();
+        let language = resolve_request_language(&request);
+        let prepared = i18n::with_language(language.clone(), self.prepare_request(request)).await?;
         let emitter = EventEmitter::new(
             prepared.session_id.clone(),
             prepared.user_id.clone(),
@@ -160,8 +220,11 @@ impl Orchestrator {
             0,
             prepared.client_message_id.clone(),
         );
+        let span = turn_span(&prepared);
+        // The turn state machine is boxed here and in `stream` so it lives on the heap instead
+        // of being moved through (and polled from) the caller's stack.
         let response = i18n::with_language(language, async {
-            self.execute_request(prepared, emitter).await
+            Box::pin(self.execute_request(prepared, emitter).instrument(span)).await
         })
         .await?;
         Ok(response)
@@ -171,8 +234,8 @@ impl Orchestrator {
         &self,
         request: WunderRequest,
     ) -> Result<impl Stream<Item = Result<StreamEvent, std::convert::Infallible>>> {
-        let prepared = self.prepare_request(request).await?;
-        let language = prepared.language.clone();
+        let language = resolve_request_language(&request);
+        let prepared = i18n::with_language(language.clone(), self.prepare_request(request)).await?;
         let (queue_tx, queue_rx) = mpsc::channel::<StreamSignal>(STREAM_EVENT_QUEUE_SIZE);
         let (event_tx, event_rx) = mpsc::channel::<StreamEvent>(STREAM_EVENT_QUEUE_SIZE);
         let session_id = prepared.session_id.clone();
@@ -208,12 +271,19 @@ impl Orchestrator {
             let emitter = emitter.clone();
             let prepared = prepared.clone();
             let language = language.clone();
-            long_task::spawn("orchestrator.request.runner", async move {
-                let _ = i18n::with_language(language, async {
-                    orchestrator.execute_request(prepared, emitter).await
-                })
-                .await;
-            })
+            let span = turn_span(&prepared);
+            long_task::spawn(
+                "orchestrator.request.runner",
+                Box::pin(async move {
+                    let _ = i18n::with_language(language, async {
+                        orchestrator
+                            .execute_request(prepared, emitter)
+                            .instrument(span)
+                            .await
+                    })
+                    .await;
+                }),
+            )
         };
         self.spawn_stream_pump(
             prepared.session_id.clone(),
@@ -240,11 +310,12 @@ impl Orchestrator {
         _is_admin: bool,
         workspace_id: &str,
         config_overrides: Option<&Value>,
+        model_name: Option<&str>,
         agent_prompt: Option<&str>,
         preview_skill: bool,
     ) -> String {
         let allow_vision = self
-            .resolve_llm_config(config, None)
+            .resolve_llm_config(config, model_name)
             .ok()
             .map(|(_, llm_config)| llm_config.support_vision.unwrap_or(false))
             .unwrap_or(false);
@@ -252,7 +323,7 @@ impl Orchestrator {
             self.resolve_allowed_tool_names(config, tool_names, skills, user_tool_bindings),
             allow_vision,
         );
-        let tool_call_mode = self.resolve_tool_call_mode(config, None);
+        let tool_call_mode = self.resolve_tool_call_mode(config, model_name);
         let prompt = self
             .build_system_prompt_with_allowed(
                 config,
@@ -271,6 +342,82 @@ impl Orchestrator {
         self.append_memory_prompt(user_id, agent_id, prompt, None, None, None)
             .await
     }
+
+    /// Token breakdown of the prompt `build_system_prompt` would return, per section. Renders
+    /// the prompt again without the prompt cache, so only call it on explicit request.
+    /// `config_overrides` are merged the way a request merges them (model and tool settings).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn build_system_prompt_breakdown(
+        &self,
+        config: &Config,
+        tool_names: &[String],
+        skills: &SkillRegistry,
+        user_tool_bindings: Option<&UserToolBindings>,
+        user_id: &str,
+        agent_id: Option<&str>,
+        workspace_id: &str,
+        config_overrides: Option<&Value>,
+        model_name: Option<&str>,
+        agent_prompt: Option<&str>,
+        preview_skill: bool,
+    ) -> PromptBudgetBreakdown {
+        let merged_config =
+            config_overrides.and_then(|overrides| apply_config_overrides(config, overrides).ok());
+        let config = merged_config.as_ref().unwrap_or(config);
+        let llm_config = self
+            .resolve_llm_config(config, model_name)
+            .ok()
+            .map(|(_, llm_config)| llm_config);
+        let allow_vision = llm_config
+            .as_ref()
+            .and_then(|llm_config| llm_config.support_vision)
+            .unwrap_or(false);
+        let allowed_tool_names = self.filter_tools_for_model_capability(
+            self.resolve_allowed_tool_names(config, tool_names, skills, user_tool_bindings),
+            allow_vision,
+        );
+        let tool_call_mode = self.resolve_tool_call_mode(config, model_name);
+        let workdir = self
+            .workspace
+            .ensure_user_root(workspace_id)
+            .unwrap_or_else(|_| self.workspace.root().to_path_buf());
+        let mut sections = self.prompt_composer.build_system_prompt_sections(
+            config,
+            self.config_store.version(),
+            &self.workspace,
+            workspace_id,
+            user_id,
+            agent_id,
+            &workdir,
+            &allowed_tool_names,
+            tool_call_mode,
+            skills,
+            user_tool_bindings,
+            agent_prompt,
+            preview_skill,
+        );
+        for (section, text) in sections.iter_mut() {
+            if *section == PromptSection::Memory {
+                let block = std::mem::take(text);
+                *text = self
+                    .append_memory_prompt(user_id, agent_id, block, None, None, None)
+                    .await;
+            }
+        }
+        let model = llm_config.and_then(|llm_config| llm_config.model);
+        breakdown_prompt_sections(&sections, model.as_deref())
+    }
+}
+
+/// Language for the whole turn: the request's own `language` when it names a supported one,
+/// otherwise the language already resolved for the caller (request headers for API calls).
+fn resolve_request_language(request: &WunderRequest) -> String {
+    let requested = i18n::normalize_language(request.language.as_deref(), false);
+    if requested.is_empty() {
+        i18n::get_language()
+    } else {
+        requested
+    }
 }
 
 fn validate_request_text_input_size(
@@ -308,6 +455,16 @@ fn is_default_agent_alias(agent_id: Option<&str>) -> bool {
     cleaned.eq_ignore_ascii_case("__default__") || cleaned.eq_ignore_ascii_case("default")
 }
 
+/// Root span of one agent turn; tool call spans nest under it when exported over OTLP.
+fn turn_span(prepared: &PreparedRequest) -> tracing::Span {
+    tracing::info_span!(
+        "turn",
+        session_id = %prepared.session_id,
+        user_id = %prepared.user_id,
+        model = prepared.model_name.as_deref().unwrap_or("default"),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -343,12 +500,16 @@ mod tests {
                 content: Some("data:image/png;base64,AAAA".to_string()),
                 content_type: Some("image/png".to_string()),
                 public_path: None,
+                attachment_id: None,
+                metadata: None,
             },
             AttachmentPayload {
                 name: Some("note.txt".to_string()),
                 content: Some("hello".to_string()),
                 content_type: Some("text/plain".to_string()),
                 public_path: None,
+                attachment_id: None,
+                metadata: None,
             },
         ];
         assert_eq!(
@@ -364,6 +525,8 @@ mod tests {
             content: Some("x".repeat(MAX_USER_INPUT_TEXT_CHARS + 16)),
             content_type: Some("text/plain".to_string()),
             public_path: None,
+            attachment_id: None,
+            metadata: None,
         }];
         let err =
             validate_request_text_input_size("short", Some(&attachments)).expect_err("oversized");
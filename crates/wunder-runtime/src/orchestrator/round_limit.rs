@@ -0,0 +1,158 @@
+// 轮次上限：合并配置与请求级 max_rounds，统计本轮工具调用，触顶时生成 max_rounds_reached 事件与进度总结答复。
+use super::execute_support::build_max_rounds_user_guidance;
+use crate::i18n;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const LAST_REPLY_MAX_CHARS: usize = 1200;
+
+/// Per-turn model round limit. A request limit can only tighten the configured one; when the
+/// turn is otherwise unlimited (admin or desktop mode) it becomes the limit.
+pub(super) struct RoundLimit {
+    limit: Option<i64>,
+    source: &'static str,
+    tool_calls: Vec<(String, u32)>,
+}
+
+impl RoundLimit {
+    pub(super) fn new(configured: Option<i64>, requested: Option<u32>) -> Self {
+        let requested = requested.map(|value| i64::from(value.max(1)));
+        let (limit, source) = match (configured, requested) {
+            (Some(configured), Some(requested)) if requested < configured => {
+                (Some(requested), "request")
+            }
+            (Some(configured), _) => (Some(configured), "config"),
+            (None, Some(requested)) => (Some(requested), "request"),
+            (None, None) => (None, "none"),
+        };
+        Self {
+            limit,
+            source,
+            tool_calls: Vec::new(),
+        }
+    }
+
+    /// True once `completed_rounds` model calls have used up the limit.
+    pub(super) fn is_reached(&self, completed_rounds: i64) -> bool {
+        self.limit.is_some_and(|limit| completed_rounds >= limit)
+    }
+
+    pub(super) fn record_tool_call(&mut self, tool: &str) {
+        match self.tool_calls.iter_mut().find(|(name, _)| name == tool) {
+            Some((_, count)) => *count += 1,
+            None => self.tool_calls.push((tool.to_string(), 1)),
+        }
+    }
+
+    fn total_tool_calls(&self) -> u32 {
+        self.tool_calls.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Payload of the `max_rounds_reached` event; the caller adds the round info.
+    pub(super) fn event_payload(&self, rounds_used: i64) -> Value {
+        let tools = self
+            .tool_calls
+            .iter()
+            .map(|(name, count)| json!({ "tool": name, "count": count }))
+            .collect::<Vec<_>>();
+        json!({
+            "max_rounds": self.limit,
+            "source": self.source,
+            "rounds_used": rounds_used,
+            "tool_calls": self.total_tool_calls(),
+            "tools": tools,
+        })
+    }
+
+    /// Final answer for a turn stopped at the limit: the user guidance, what the turn got done
+    /// and the last model output, so the stop reads as a handoff rather than a dead end.
+    pub(super) fn final_answer(&self, rounds_used: i64, last_reply: &str) -> String {
+        let tools = if self.tool_calls.is_empty() {
+            "-".to_string()
+        } else {
+            self.tool_calls
+                .iter()
+                .map(|(name, count)| format!("{name} x{count}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let progress = i18n::t_with_params(
+            "error.max_rounds_progress_summary",
+            &HashMap::from([
+                ("rounds".to_string(), rounds_used.to_string()),
+                (
+                    "tool_calls".to_string(),
+                    self.total_tool_calls().to_string(),
+                ),
+                ("tools".to_string(), tools),
+            ]),
+        );
+        let mut answer = format!(
+            "{}\n\n{progress}",
+            build_max_rounds_user_guidance(self.limit)
+        );
+        let last_reply = last_reply.trim();
+        if !last_reply.is_empty() {
+            let mut excerpt = last_reply
+                .chars()
+                .take(LAST_REPLY_MAX_CHARS)
+                .collect::<String>();
+            if excerpt.len() < last_reply.len() {
+                excerpt.push_str("...");
+            }
+            answer.push_str(&format!(
+                "\n\n{}\n{excerpt}",
+                i18n::t("error.max_rounds_last_reply")
+            ));
+        }
+        answer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_limit_only_tightens_the_configured_limit() {
+        let tighter = RoundLimit::new(Some(10), Some(3)).event_payload(3);
+        assert_eq!(tighter["max_rounds"], 3);
+        assert_eq!(tighter["source"], "request");
+
+        let looser = RoundLimit::new(Some(10), Some(50)).event_payload(10);
+        assert_eq!(looser["max_rounds"], 10);
+        assert_eq!(looser["source"], "config");
+
+        assert_eq!(
+            RoundLimit::new(None, Some(0)).event_payload(1)["max_rounds"],
+            1
+        );
+        assert!(RoundLimit::new(None, None).event_payload(0)["max_rounds"].is_null());
+        assert!(!RoundLimit::new(None, None).is_reached(i64::MAX));
+    }
+
+    #[test]
+    fn final_answer_summarizes_tool_progress() {
+        let mut limit = RoundLimit::new(None, Some(2));
+        assert!(!limit.is_reached(1));
+        assert!(limit.is_reached(2));
+        limit.record_tool_call("list_files");
+        limit.record_tool_call("read_file");
+        limit.record_tool_call("list_files");
+
+        let payload = limit.event_payload(2);
+        assert_eq!(payload["tool_calls"], 3);
+        assert_eq!(
+            payload["tools"],
+            json!([
+                { "tool": "list_files", "count": 2 },
+                { "tool": "read_file", "count": 1 },
+            ])
+        );
+
+        let answer = limit.final_answer(2, "checked the src directory");
+        assert!(answer.starts_with(&build_max_rounds_user_guidance(Some(2))));
+        assert!(answer.contains("list_files x2, read_file x1"));
+        assert!(answer.ends_with("checked the src directory"));
+    }
+}
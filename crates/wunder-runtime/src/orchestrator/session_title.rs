@@ -0,0 +1,42 @@
+// 首轮结束后的会话标题生成：开启 llm.auto_title 时后台调用模型改写截断标题，不阻塞本轮返回。
+use super::*;
+use crate::core::long_task;
+use crate::services::session_title::refine_session_title;
+
+impl Orchestrator {
+    pub(super) fn spawn_session_title_refresh(
+        &self,
+        config: &Config,
+        user_id: &str,
+        session_id: &str,
+        question: &str,
+        answer: &str,
+    ) {
+        if !config.llm.auto_title.enabled || question.trim().is_empty() {
+            return;
+        }
+        let config = config.clone();
+        let http = self.http.clone();
+        let storage = self.storage.clone();
+        let user_id = user_id.to_string();
+        let session_id = session_id.to_string();
+        let question = question.to_string();
+        let answer = answer.to_string();
+        let language = i18n::get_language();
+        long_task::spawn("orchestrator.session_title", async move {
+            i18n::with_language(language, async move {
+                refine_session_title(
+                    &config,
+                    http,
+                    storage,
+                    &user_id,
+                    &session_id,
+                    &question,
+                    &answer,
+                )
+                .await;
+            })
+            .await;
+        });
+    }
+}
@@ -1,9 +1,12 @@
 use super::*;
-use std::sync::mpsc::{self as std_mpsc, SyncSender, TryRecvError, TrySendError};
+use crate::storage::StreamEventWrite;
+use std::sync::mpsc::{self as std_mpsc, SyncSender, TrySendError};
 use std::thread;
 
 const STREAM_EVENT_WRITE_QUEUE_SIZE: usize = 2048;
 const STREAM_EVENT_WRITE_BATCH_SIZE: usize = 128;
+/// How long the writer waits for more events after the first one before flushing a batch.
+const STREAM_EVENT_WRITE_LINGER: Duration = Duration::from_millis(20);
 
 struct StreamPersistTask {
     storage: Arc<dyn StorageBackend>,
@@ -44,21 +47,71 @@ impl StreamPersistQueue {
         while let Ok(command) = receiver.recv() {
             let mut batch = Vec::with_capacity(STREAM_EVENT_WRITE_BATCH_SIZE);
             batch.push(command);
-            while batch.len() < STREAM_EVENT_WRITE_BATCH_SIZE {
-                match receiver.try_recv() {
+            // Collect until the batch is full, the linger window closes, or a barrier (turn end /
+            // explicit flush) arrives; barriers must not wait on the linger.
+            let deadline = Instant::now() + STREAM_EVENT_WRITE_LINGER;
+            while batch.len() < STREAM_EVENT_WRITE_BATCH_SIZE
+                && !matches!(batch.last(), Some(StreamPersistCommand::Barrier(_)))
+            {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match receiver.recv_timeout(remaining) {
                     Ok(command) => batch.push(command),
-                    Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => break,
+                    Err(_) => break,
                 }
             }
-            for command in batch {
-                match command {
-                    StreamPersistCommand::Task(task) => Self::apply_task(task),
-                    StreamPersistCommand::Barrier(done) => {
-                        let _ = done.send(());
-                    }
+            Self::apply_batch(batch);
+        }
+    }
+
+    fn apply_batch(batch: Vec<StreamPersistCommand>) {
+        let mut pending = Vec::new();
+        for command in batch {
+            match command {
+                StreamPersistCommand::Task(task) => pending.push(task),
+                StreamPersistCommand::Barrier(done) => {
+                    Self::write_tasks(std::mem::take(&mut pending));
+                    let _ = done.send(());
                 }
             }
         }
+        Self::write_tasks(pending);
+    }
+
+    /// Write queued events with one transaction per run of tasks sharing a storage backend,
+    /// keeping queue order. A failed batch is retried event by event so one bad row does not
+    /// drop its neighbours.
+    fn write_tasks(tasks: Vec<StreamPersistTask>) {
+        let mut start = 0;
+        while start < tasks.len() {
+            let storage = tasks[start].storage.clone();
+            let end = tasks[start..]
+                .iter()
+                .position(|task| !Arc::ptr_eq(&task.storage, &storage))
+                .map_or(tasks.len(), |offset| start + offset);
+            let run = &tasks[start..end];
+            let writes = run
+                .iter()
+                .map(|task| StreamEventWrite {
+                    session_id: task.session_id.clone(),
+                    user_id: task.user_id.clone(),
+                    event_id: task.event_id,
+                    payload: task.payload.clone(),
+                })
+                .collect::<Vec<_>>();
+            if let Err(err) = storage.append_stream_events(&writes) {
+                warn!(
+                    "failed to persist {} stream events in one batch, retrying individually: {err}",
+                    writes.len()
+                );
+                for task in run {
+                    Self::append_one(task);
+                }
+            }
+            for task in run {
+                Self::cleanup_before(task);
+            }
+            start = end;
+        }
     }
 
     fn enqueue(&self, task: StreamPersistTask) {
@@ -87,6 +140,11 @@ impl StreamPersistQueue {
     }
 
     fn apply_task(task: StreamPersistTask) {
+        Self::append_one(&task);
+        Self::cleanup_before(&task);
+    }
+
+    fn append_one(task: &StreamPersistTask) {
         let StreamPersistTask {
             storage,
             session_id,
@@ -94,15 +152,22 @@ impl StreamPersistQueue {
             event_id,
             payload,
             event_type,
-            cleanup_cutoff,
+            ..
         } = task;
-        if let Err(err) = storage.append_stream_event(&session_id, &user_id, event_id, &payload) {
+        if let Err(err) = storage.append_stream_event(session_id, user_id, *event_id, payload) {
             warn!("failed to persist stream event {event_type} for session {session_id}: {err}");
         }
-        if let Some(cutoff) = cleanup_cutoff {
-            if let Err(err) = storage.delete_stream_events_before(cutoff) {
-                warn!("failed to cleanup stream events before {cutoff} for session {session_id}: {err}");
-            }
+    }
+
+    fn cleanup_before(task: &StreamPersistTask) {
+        let Some(cutoff) = task.cleanup_cutoff else {
+            return;
+        };
+        if let Err(err) = task.storage.delete_stream_events_before(cutoff) {
+            warn!(
+                "failed to cleanup stream events before {cutoff} for session {}: {err}",
+                task.session_id
+            );
         }
     }
 }
@@ -253,4 +318,45 @@ mod tests {
         assert_eq!(records[0]["event"], json!("progress"));
         assert_eq!(records[1]["event"], json!("queue_finish"));
     }
+
+    #[tokio::test]
+    async fn batched_stream_events_read_back_in_order() {
+        let storage = build_storage();
+        for event_id in 1..=STREAM_EVENT_WRITE_BATCH_SIZE as i64 + 5 {
+            enqueue_stream_event_persist(
+                storage.clone(),
+                "sess_batched_order".to_string(),
+                "user_batched_order".to_string(),
+                event_id,
+                json!({
+                    "event": "llm_output_delta",
+                    "data": { "seq": event_id },
+                    "timestamp": "2026-03-07T00:00:00+08:00"
+                }),
+                "llm_output_delta".to_string(),
+                None,
+            );
+        }
+
+        flush_stream_event_persist_queue().await;
+        let total = STREAM_EVENT_WRITE_BATCH_SIZE as i64 + 5;
+        assert_eq!(
+            storage
+                .get_max_stream_event_id("sess_batched_order")
+                .expect("max event id"),
+            total
+        );
+        let records = storage
+            .load_stream_events("sess_batched_order", 0, total + 8)
+            .expect("load stream events");
+        let ids = records
+            .iter()
+            .map(|record| record["event_id"].as_i64().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, (1..=total).collect::<Vec<_>>());
+        assert!(records
+            .iter()
+            .zip(1..)
+            .all(|(record, seq)| record["data"]["seq"] == json!(seq)));
+    }
 }
@@ -0,0 +1,95 @@
+// 结构化回答校验：模型配置了 response_format 时检查最终回答能否解析为 JSON，按配置重试一次并生成提示与进度事件。
+use crate::config::LlmModelConfig;
+use crate::llm::{resolve_response_format, validate_structured_answer};
+use serde_json::{json, Value};
+
+/// `response_format_retry` allows a single corrective round per turn.
+const STRUCTURED_ANSWER_RETRY_MAX_PER_TURN: u32 = 1;
+
+#[derive(Debug, PartialEq)]
+pub(super) enum StructuredAnswerCheck {
+    /// No structured format requested, or the answer satisfies it.
+    Accepted,
+    /// Invalid answer with a retry left: push the notice and run another round.
+    Retry { error: String, notice: Value },
+    /// Invalid answer with no retry left; the answer is kept and the failure reported.
+    Rejected { error: String },
+}
+
+pub(super) fn check_structured_answer(
+    llm_config: &LlmModelConfig,
+    answer: &str,
+    retries_used: u32,
+) -> StructuredAnswerCheck {
+    let Some(format) = resolve_response_format(llm_config) else {
+        return StructuredAnswerCheck::Accepted;
+    };
+    let Err(error) = validate_structured_answer(&format, answer) else {
+        return StructuredAnswerCheck::Accepted;
+    };
+    if llm_config.response_format_retry.unwrap_or(false)
+        && retries_used < STRUCTURED_ANSWER_RETRY_MAX_PER_TURN
+    {
+        let notice = json!({
+            "type": "structured_answer_notice",
+            "ok": false,
+            "reason": "final_answer_not_valid_json",
+            "error": error,
+            "response_format": format,
+            "instruction": "Your previous reply did not satisfy the required response format. Reply again with only the JSON value, no prose and no code fences.",
+        });
+        return StructuredAnswerCheck::Retry { error, notice };
+    }
+    StructuredAnswerCheck::Rejected { error }
+}
+
+/// Progress payload for a failed check; the caller adds the round info.
+pub(super) fn structured_answer_event_payload(error: &str, retrying: bool) -> Value {
+    json!({
+        "stage": if retrying { "structured_answer_retry" } else { "structured_answer_invalid" },
+        "summary": if retrying {
+            "Final answer did not match the requested response format; model asked to answer again."
+        } else {
+            "Final answer did not match the requested response format."
+        },
+        "error": error,
+        "max_attempts": STRUCTURED_ANSWER_RETRY_MAX_PER_TURN,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_model(retry: bool) -> LlmModelConfig {
+        LlmModelConfig {
+            response_format: Some(serde_yaml::from_str("json_object").expect("yaml")),
+            response_format_retry: Some(retry),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn invalid_answers_retry_once_when_configured() {
+        assert_eq!(
+            check_structured_answer(&LlmModelConfig::default(), "plain text", 0),
+            StructuredAnswerCheck::Accepted
+        );
+        assert_eq!(
+            check_structured_answer(&json_model(true), "{\"a\":1}", 0),
+            StructuredAnswerCheck::Accepted
+        );
+        assert!(matches!(
+            check_structured_answer(&json_model(true), "not json", 0),
+            StructuredAnswerCheck::Retry { .. }
+        ));
+        assert!(matches!(
+            check_structured_answer(&json_model(true), "not json", 1),
+            StructuredAnswerCheck::Rejected { .. }
+        ));
+        assert!(matches!(
+            check_structured_answer(&json_model(false), "not json", 0),
+            StructuredAnswerCheck::Rejected { .. }
+        ));
+    }
+}
@@ -944,7 +944,7 @@ fn parse_read_file_path_from_shell_block(body: &str) -> Option<String> {
     }
 
     let command = commands[0];
-    let parts = shell_words::split(command).ok()?;
+    let parts = crate::command_utils::split_command_line(command).ok()?;
     if parts.is_empty() {
         return None;
     }
@@ -1,7 +1,6 @@
 use super::context::{normalize_model_context_message, MODEL_CONTEXT_INTERNAL_META_TYPE};
 use super::*;
-
-pub(super) const TOOL_TIMEOUT_ERROR: &str = "tool_timeout";
+use crate::tools::ToolError;
 
 use super::tool_result_payload::{
     append_truncation_reason, collect_truncation_reasons_from_value,
@@ -175,10 +174,15 @@ impl Orchestrator {
     pub(super) fn build_tool_observation(
         &self,
         tool_name: &str,
-        result: &ToolResultPayload,
+        payload: &Value,
+        guard: bool,
     ) -> String {
-        let payload = result.to_compact_payload(tool_name);
-        serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_string())
+        let observation = serde_json::to_string(payload).unwrap_or_else(|_| "{}".to_string());
+        if guard {
+            super::tool_result_guard::guard_tool_observation(tool_name, &observation)
+        } else {
+            observation
+        }
     }
 
     pub(super) fn append_tool_log(
@@ -768,20 +772,22 @@ impl Orchestrator {
         name: &str,
         args: &Value,
         timeout: Option<Duration>,
-    ) -> Result<Value, anyhow::Error> {
-        if let Some(timeout) = timeout {
-            match tokio::time::timeout(
-                timeout,
-                crate::tools::execute_tool(tool_context, name, args),
-            )
+    ) -> Result<Value, ToolError> {
+        crate::tools::execute_tool_with_timeout(tool_context, name, args, timeout).await
+    }
+
+    /// Run the call in plan mode so an approval prompt can show what it would change.
+    pub(super) async fn preview_tool_plan(
+        &self,
+        tool_context: &ToolContext<'_>,
+        name: &str,
+        args: &Value,
+        timeout: Option<Duration>,
+    ) -> Option<Value> {
+        let plan_context = tool_context.with_dry_run(true);
+        self.execute_tool_with_timeout(&plan_context, name, args, timeout)
             .await
-            {
-                Ok(result) => result,
-                Err(_) => Err(anyhow!(TOOL_TIMEOUT_ERROR)),
-            }
-        } else {
-            crate::tools::execute_tool(tool_context, name, args).await
-        }
+            .ok()
     }
 
     pub(super) fn resolve_tool_timeout(
@@ -1,7 +1,57 @@
+use crate::config::Config;
 use crate::tools::resolve_tool_name;
+use futures::StreamExt;
 use serde_json::Value;
 use std::collections::HashSet;
-use std::sync::OnceLock;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::RwLock;
+
+/// Lets read-only calls of one round overlap while every other call runs alone.
+#[derive(Clone, Default)]
+pub(super) struct ToolExecutionGate {
+    lock: Arc<RwLock<()>>,
+}
+
+impl ToolExecutionGate {
+    pub(super) async fn run<F: Future>(&self, parallel: bool, task: F) -> F::Output {
+        if parallel {
+            let _guard = self.lock.read().await;
+            task.await
+        } else {
+            let _guard = self.lock.write().await;
+            task.await
+        }
+    }
+}
+
+/// Drives at most `parallelism` calls at once and returns their outputs in call order, so
+/// results map back to the model's tool calls. Stops at the first error and drops calls still
+/// in flight.
+pub(super) async fn run_in_call_order<I, T, E>(tasks: I, parallelism: usize) -> Result<Vec<T>, E>
+where
+    I: IntoIterator,
+    I::Item: Future<Output = Result<T, E>>,
+{
+    let mut stream = futures::stream::iter(tasks).buffered(parallelism.max(1));
+    let mut outputs = Vec::new();
+    while let Some(output) = stream.next().await {
+        outputs.push(output?);
+    }
+    Ok(outputs)
+}
+
+/// `tools.parallel.exclusive` wins over the built-in read-only classification.
+pub(super) fn tool_call_runs_in_parallel(config: &Config, tool_name: &str, args: &Value) -> bool {
+    let canonical = resolve_tool_name(tool_name.trim());
+    let forced_exclusive = config.tools.parallel.exclusive.iter().any(|entry| {
+        let entry = entry.trim();
+        !entry.is_empty()
+            && (resolve_tool_name(entry) == canonical
+                || entry.eq_ignore_ascii_case(tool_name.trim()))
+    });
+    !forced_exclusive && tool_call_supports_parallel(tool_name, args)
+}
 
 pub(super) fn tool_call_supports_parallel(tool_name: &str, args: &Value) -> bool {
     let canonical = resolve_tool_name(tool_name.trim());
@@ -83,8 +133,16 @@ fn external_tool_looks_read_only(canonical: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::tool_call_supports_parallel;
+    use super::{
+        run_in_call_order, tool_call_runs_in_parallel, tool_call_supports_parallel,
+        ToolExecutionGate,
+    };
+    use crate::config::Config;
     use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Barrier;
 
     #[test]
     fn read_file_supports_parallel_execution() {
@@ -137,4 +195,93 @@ mod tests {
             &json!({ "sql": "update t set a=1" }),
         ));
     }
+
+    #[test]
+    fn configured_exclusive_tools_never_run_in_parallel() {
+        let mut config = Config::default();
+        assert!(tool_call_runs_in_parallel(
+            &config,
+            "extra_mcp@db_query",
+            &json!({})
+        ));
+        config.tools.parallel.exclusive = vec!["extra_mcp@db_query".to_string()];
+        assert!(!tool_call_runs_in_parallel(
+            &config,
+            "extra_mcp@db_query",
+            &json!({})
+        ));
+        assert!(tool_call_runs_in_parallel(
+            &config,
+            "read_file",
+            &json!({ "path": "a.txt" })
+        ));
+    }
+
+    #[tokio::test]
+    async fn independent_tools_run_concurrently_and_keep_call_order() {
+        let gate = ToolExecutionGate::default();
+        // Each mock tool waits for the other one, so the round only finishes if both are in
+        // flight at the same time.
+        let barrier = Arc::new(Barrier::new(2));
+        let calls = [("slow_lookup", 30_u64), ("fast_lookup", 0)].map(|(name, delay_ms)| {
+            let gate = gate.clone();
+            let barrier = Arc::clone(&barrier);
+            async move {
+                gate.run(true, async {
+                    barrier.wait().await;
+                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                    Ok::<_, String>(format!("{name}:done"))
+                })
+                .await
+            }
+        });
+        let outputs = tokio::time::timeout(Duration::from_secs(5), run_in_call_order(calls, 4))
+            .await
+            .expect("parallel-safe tools must overlap")
+            .expect("mock tools succeed");
+        assert_eq!(outputs, vec!["slow_lookup:done", "fast_lookup:done"]);
+    }
+
+    #[tokio::test]
+    async fn exclusive_tools_never_overlap() {
+        let gate = ToolExecutionGate::default();
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        // Two read-only pairs around one exclusive call: each pair may overlap, the exclusive
+        // call must run alone.
+        let pattern = [true, true, false, true, true];
+        let calls = pattern.map(|parallel| {
+            let gate = gate.clone();
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            async move {
+                gate.run(parallel, async {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    if !parallel {
+                        assert_eq!(now, 1, "exclusive tool overlapped another call");
+                    }
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<_, String>(parallel)
+                })
+                .await
+            }
+        });
+        let outputs = run_in_call_order(calls, 4)
+            .await
+            .expect("mock tools succeed");
+        assert_eq!(outputs, pattern.to_vec());
+        assert_eq!(
+            peak.load(Ordering::SeqCst),
+            2,
+            "adjacent read-only calls should have overlapped"
+        );
+    }
+
+    #[tokio::test]
+    async fn first_error_stops_the_round() {
+        let calls = [Ok(1), Err("boom"), Ok(3)].map(|result| async move { result });
+        assert_eq!(run_in_call_order(calls, 2).await, Err("boom"));
+    }
 }
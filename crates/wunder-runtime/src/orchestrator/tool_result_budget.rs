@@ -0,0 +1,268 @@
+// 工具结果上下文预算：超过 token 阈值的工具结果在进入模型上下文前截为首尾预览，可选用当前模型摘要省略部分；工具日志与事件仍保留完整结果。
+use super::tool_result_payload::{append_truncation_reason, truncate_tool_result_string};
+use super::*;
+use crate::config::ToolResultBudgetConfig;
+
+/// Tokens reserved for the `{truncated, original_tokens, preview}` wrapper around the preview.
+const BUDGET_WRAPPER_TOKENS: i64 = 16;
+const OMITTED_SUMMARY_MAX_CHARS: usize = 800;
+/// Upper bound on the omitted text sent to the summarizer.
+const OMITTED_SUMMARY_INPUT_MAX_CHARS: usize = 24_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ToolResultBudget {
+    max_tokens: i64,
+    summarize: bool,
+}
+
+pub(super) fn resolve_tool_result_budget(
+    config: &ToolResultBudgetConfig,
+    tool_name: &str,
+) -> Option<ToolResultBudget> {
+    let canonical = resolve_tool_name(tool_name);
+    let override_entry = config.per_tool.get(tool_name).or_else(|| {
+        config
+            .per_tool
+            .iter()
+            .find(|(name, _)| resolve_tool_name(name) == canonical)
+            .map(|(_, entry)| entry)
+    });
+    let max_tokens = override_entry
+        .and_then(|entry| entry.max_tokens)
+        .or(config.max_tokens)
+        .filter(|value| *value > 0)?;
+    let summarize = override_entry
+        .and_then(|entry| entry.summarize)
+        .unwrap_or(config.summarize);
+    Some(ToolResultBudget {
+        max_tokens,
+        summarize,
+    })
+}
+
+/// Replaces `data` with a head/tail preview when it exceeds the budget and returns the omitted
+/// middle part, or `None` when the payload already fits.
+pub(super) fn apply_tool_result_budget(
+    payload: &mut Value,
+    budget: ToolResultBudget,
+) -> Option<String> {
+    let map = payload.as_object_mut()?;
+    let data = map.get("data")?;
+    let text = match data {
+        Value::String(text) => text.clone(),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    };
+    let original_tokens = approx_token_count(&text);
+    if original_tokens <= budget.max_tokens {
+        return None;
+    }
+    let total_chars = text.chars().count();
+    // The preview is escaped again as a JSON string, so scale by the encoded size.
+    let encoded_tokens = approx_token_count(&Value::String(text.clone()).to_string());
+    let keep_tokens = (budget.max_tokens - BUDGET_WRAPPER_TOKENS).max(1);
+    let marker_chars = TOOL_RESULT_TRUNCATION_MARKER.chars().count();
+    let keep_chars =
+        ((total_chars as f64) * (keep_tokens as f64) / (encoded_tokens as f64)).floor() as usize;
+    let keep_chars = keep_chars.saturating_sub(marker_chars);
+    let head_chars = keep_chars - keep_chars / 3;
+    let tail_chars = keep_chars - head_chars;
+    let preview =
+        truncate_tool_result_string(&text, head_chars, tail_chars, TOOL_RESULT_TRUNCATION_MARKER);
+    let omitted: String = text
+        .chars()
+        .skip(head_chars)
+        .take(total_chars.saturating_sub(head_chars + tail_chars))
+        .collect();
+
+    map.insert(
+        "data".to_string(),
+        json!({
+            "truncated": true,
+            "original_tokens": original_tokens,
+            "preview": preview,
+        }),
+    );
+    map.insert("truncated".to_string(), Value::Bool(true));
+    let mut reasons: Vec<String> = map
+        .get("truncation_reasons")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(ToString::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    append_truncation_reason(&mut reasons, "token_budget");
+    map.insert("truncation_reasons".to_string(), json!(reasons));
+    Some(omitted)
+}
+
+impl Orchestrator {
+    /// Model-facing observation for a tool result, cut to `tools.result_budget` when over it.
+    pub(super) async fn build_budgeted_tool_observation(
+        &self,
+        config: &Config,
+        llm_config: &LlmModelConfig,
+        tool_name: &str,
+        result: &ToolResultPayload,
+    ) -> String {
+        let guard = config.security.tool_result_guard;
+        let mut payload = result.to_compact_payload(tool_name);
+        let Some(budget) = resolve_tool_result_budget(&config.tools.result_budget, tool_name)
+        else {
+            return self.build_tool_observation(tool_name, &payload, guard);
+        };
+        let Some(omitted) = apply_tool_result_budget(&mut payload, budget) else {
+            return self.build_tool_observation(tool_name, &payload, guard);
+        };
+        if budget.summarize {
+            if let Some(summary) = self
+                .summarize_omitted_tool_result(llm_config, tool_name, &omitted)
+                .await
+            {
+                if let Some(data) = payload.get_mut("data").and_then(Value::as_object_mut) {
+                    data.insert("omitted_summary".to_string(), Value::String(summary));
+                    data.insert("summarized".to_string(), Value::Bool(true));
+                }
+            }
+        }
+        self.build_tool_observation(tool_name, &payload, guard)
+    }
+
+    async fn summarize_omitted_tool_result(
+        &self,
+        llm_config: &LlmModelConfig,
+        tool_name: &str,
+        omitted: &str,
+    ) -> Option<String> {
+        let prompt = i18n::t_with_params(
+            "tool_result.omitted_summary_prompt",
+            &HashMap::from([
+                ("tool".to_string(), tool_name.to_string()),
+                (
+                    "max_chars".to_string(),
+                    OMITTED_SUMMARY_MAX_CHARS.to_string(),
+                ),
+            ]),
+        );
+        let input: String = omitted
+            .chars()
+            .take(OMITTED_SUMMARY_INPUT_MAX_CHARS)
+            .collect();
+        let messages = [
+            ChatMessage {
+                role: "system".to_string(),
+                content: json!(prompt),
+                reasoning_content: None,
+                tool_calls: None,
+                tool_call_id: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: json!(input),
+                reasoning_content: None,
+                tool_calls: None,
+                tool_call_id: None,
+            },
+        ];
+        match build_llm_client(llm_config, self.http.clone())
+            .complete(&messages)
+            .await
+        {
+            Ok(response) => {
+                let summary: String = response
+                    .content
+                    .trim()
+                    .chars()
+                    .take(OMITTED_SUMMARY_MAX_CHARS)
+                    .collect();
+                (!summary.is_empty()).then_some(summary)
+            }
+            Err(err) => {
+                warn!("tool result summary failed for {tool_name}: {err}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ToolResultBudgetOverride;
+
+    fn budget(max_tokens: i64) -> ToolResultBudget {
+        ToolResultBudget {
+            max_tokens,
+            summarize: false,
+        }
+    }
+
+    #[test]
+    fn oversized_tool_result_is_truncated_before_entering_context() {
+        let content: String = (0..2000).map(|idx| format!("line-{idx}\n")).collect();
+        let mut payload =
+            json!({ "tool": "read_file", "ok": true, "data": { "content": content } });
+
+        let omitted = apply_tool_result_budget(&mut payload, budget(200)).expect("truncated");
+
+        assert_eq!(payload["truncated"], true);
+        assert_eq!(payload["truncation_reasons"], json!(["token_budget"]));
+        assert_eq!(payload["data"]["truncated"], true);
+        let preview = payload["data"]["preview"].as_str().expect("preview");
+        assert!(preview.contains(TOOL_RESULT_TRUNCATION_MARKER));
+        assert!(preview.contains("line-0"));
+        assert!(preview.contains("line-1999"));
+        assert!(!preview.contains("line-1000\\n"));
+        assert!(omitted.contains("line-1000\\n"));
+        let data = serde_json::to_string(&payload["data"]).unwrap();
+        assert!(
+            approx_token_count(&data) <= 200,
+            "{}",
+            approx_token_count(&data)
+        );
+    }
+
+    #[test]
+    fn small_tool_result_is_left_untouched() {
+        let mut payload = json!({ "tool": "read_file", "ok": true, "data": { "content": "ok" } });
+        let before = payload.clone();
+        assert!(apply_tool_result_budget(&mut payload, budget(200)).is_none());
+        assert_eq!(payload, before);
+    }
+
+    #[test]
+    fn per_tool_override_wins_over_default() {
+        let mut config = ToolResultBudgetConfig {
+            max_tokens: Some(4000),
+            summarize: true,
+            per_tool: HashMap::new(),
+        };
+        config.per_tool.insert(
+            "read_file".to_string(),
+            ToolResultBudgetOverride {
+                max_tokens: Some(500),
+                summarize: Some(false),
+            },
+        );
+
+        assert_eq!(
+            resolve_tool_result_budget(&config, "read_file"),
+            Some(ToolResultBudget {
+                max_tokens: 500,
+                summarize: false,
+            })
+        );
+        assert_eq!(
+            resolve_tool_result_budget(&config, "list_files"),
+            Some(ToolResultBudget {
+                max_tokens: 4000,
+                summarize: true,
+            })
+        );
+        config.max_tokens = None;
+        assert_eq!(resolve_tool_result_budget(&config, "list_files"), None);
+    }
+}
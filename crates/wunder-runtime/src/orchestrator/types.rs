@@ -18,6 +18,9 @@ pub(super) struct PreparedRequest {
     pub(super) stream: bool,
     pub(super) debug_payload: bool,
     pub(super) attachments: Option<Vec<AttachmentPayload>>,
+    pub(super) max_rounds: Option<u32>,
+    pub(super) cite: bool,
+    pub(super) remember_attachments: bool,
     pub(super) language: String,
     pub(super) allow_queue: bool,
     pub(super) is_admin: bool,
@@ -22,6 +22,7 @@ pub const DEFAULT_SANDBOX_IDLE_TTL_S: u64 = 0;
 pub const DEFAULT_SANDBOX_CPU_LIMIT: f32 = 8.0;
 pub const DEFAULT_SANDBOX_MEMORY_MB: u64 = 8096;
 pub const DEFAULT_SANDBOX_PIDS_LIMIT: u64 = 256;
+pub const DEFAULT_SANDBOX_NETWORK: &str = "bridge";
 
 static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
 
@@ -262,6 +263,20 @@ pub fn sandbox_readonly_rootfs() -> bool {
         .unwrap_or(DEFAULT_SANDBOX_READONLY_ROOTFS)
 }
 
+/// Network mode requested for sandboxed tools (`WUNDER_SANDBOX_NETWORK`, e.g. `bridge`/`none`).
+pub fn sandbox_network_mode() -> String {
+    env::var("WUNDER_SANDBOX_NETWORK")
+        .ok()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_SANDBOX_NETWORK.to_string())
+}
+
+/// False when the sandbox network is switched off; server-side fetch tools honour it as well.
+pub fn sandbox_network_enabled(config: &Config) -> bool {
+    !sandbox_enabled(config) || sandbox_network_mode() != "none"
+}
+
 pub fn sandbox_cpu_limit() -> f32 {
     env::var("WUNDER_SANDBOX_CPU")
         .ok()
@@ -326,6 +341,7 @@ pub async fn execute_tool(
     let allow_paths = collect_allow_paths(config, user_tool_bindings);
     let deny_globs = config.security.deny_globs.clone();
     let allow_commands = config.security.allow_commands.clone();
+    let env_passthrough = config.security.env_passthrough.clone();
 
     let mut mapped_args = if args.is_object() {
         args.clone()
@@ -364,8 +380,9 @@ pub async fn execute_tool(
         "allow_paths": allow_paths,
         "deny_globs": deny_globs,
         "allow_commands": allow_commands,
+        "env_passthrough": env_passthrough,
         "container_root": sandbox_container_root(),
-        "network": "bridge",
+        "network": sandbox_network_mode(),
         "readonly_rootfs": sandbox_readonly_rootfs(),
         "idle_ttl_s": sandbox_idle_ttl_seconds(),
         "resources": {
@@ -500,6 +517,7 @@ where
     let allow_paths = collect_allow_paths(config, user_tool_bindings);
     let deny_globs = config.security.deny_globs.clone();
     let allow_commands = config.security.allow_commands.clone();
+    let env_passthrough = config.security.env_passthrough.clone();
     let mut mapped_args = if args.is_object() {
         args.clone()
     } else {
@@ -534,8 +552,9 @@ where
         "allow_paths": allow_paths,
         "deny_globs": deny_globs,
         "allow_commands": allow_commands,
+        "env_passthrough": env_passthrough,
         "container_root": sandbox_container_root(),
-        "network": "bridge",
+        "network": sandbox_network_mode(),
         "readonly_rootfs": sandbox_readonly_rootfs(),
         "idle_ttl_s": sandbox_idle_ttl_seconds(),
         "resources": {
@@ -719,6 +738,11 @@ fn truncate_text(text: &str, max: usize) -> String {
     output
 }
 
+/// Serializes tests that repoint `WUNDER_SANDBOX_ENDPOINT`; the variable is process-wide.
+#[cfg(test)]
+pub(crate) static SANDBOX_ENDPOINT_ENV_LOCK: tokio::sync::Mutex<()> =
+    tokio::sync::Mutex::const_new(());
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -728,6 +752,7 @@ mod tests {
     use uuid::Uuid;
 
     fn with_env_var<F: FnOnce() -> R, R>(key: &str, value: Option<&str>, f: F) -> R {
+        let _guard = SANDBOX_ENDPOINT_ENV_LOCK.blocking_lock();
         let original = env::var(key).ok();
         match value {
             Some(value) => env::set_var(key, value),
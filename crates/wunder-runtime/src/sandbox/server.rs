@@ -26,6 +26,7 @@ use tokio_stream::wrappers::ReceiverStream;
 use crate::a2a_store::A2aStore;
 use crate::command_utils;
 use crate::config::Config;
+use crate::core::command_env::{ChildEnv, EnvPassthrough};
 use crate::core::python_runtime;
 use crate::core::tool_args::recover_tool_args_value as recover_tool_args_value_lossy;
 use crate::i18n;
@@ -74,6 +75,8 @@ struct SandboxToolRequest {
     #[serde(default)]
     allow_commands: Vec<String>,
     #[serde(default)]
+    env_passthrough: Vec<String>,
+    #[serde(default)]
     network: String,
     #[serde(default)]
     readonly_rootfs: bool,
@@ -120,6 +123,7 @@ struct SandboxContext {
     workspace_root: PathBuf,
     container_root: PathBuf,
     allow_commands: Arc<HashSet<String>>,
+    env_passthrough: EnvPassthrough,
 }
 
 struct ToolResult {
@@ -401,6 +405,7 @@ async fn handle_execute_tool(request: SandboxToolRequest) -> SandboxToolResponse
         workspace_root,
         container_root,
         allow_commands: rules.allow_commands,
+        env_passthrough: EnvPassthrough::from_names(&request.env_passthrough),
     };
 
     let args = if request.args.is_null() {
@@ -469,6 +474,7 @@ async fn handle_execute_command_stream(
         workspace_root,
         container_root,
         allow_commands: rules.allow_commands,
+        env_passthrough: EnvPassthrough::from_names(&request.env_passthrough),
     };
     let args = if request.args.is_null() {
         json!({})
@@ -497,6 +503,11 @@ async fn execute_command(context: &SandboxContext, args: &Value) -> ToolResult {
     let args = recover_tool_args_value(args);
     let dry_run = parse_dry_run(&args);
     let command_budget = parse_command_budget(&args);
+    // execute_command rejects env names outside the allowlist before dispatching here.
+    let child_env = ChildEnv::new(
+        context.env_passthrough.clone(),
+        context.env_passthrough.split_call_env(&args).0,
+    );
     let content = args
         .get("content")
         .and_then(Value::as_str)
@@ -670,8 +681,15 @@ async fn execute_command(context: &SandboxContext, args: &Value) -> ToolResult {
             }
         }
 
-        let output =
-            run_shell_command(&command, &cwd, timeout_s, stdout_policy, stderr_policy).await;
+        let output = run_shell_command(
+            &command,
+            &cwd,
+            timeout_s,
+            stdout_policy,
+            stderr_policy,
+            &child_env,
+        )
+        .await;
 
         let output = match output {
             Ok(output) => output,
@@ -803,6 +821,10 @@ async fn execute_command_streaming(
     let args = recover_tool_args_value(args);
     let dry_run = parse_dry_run(&args);
     let command_budget = parse_command_budget(&args);
+    let child_env = ChildEnv::new(
+        context.env_passthrough.clone(),
+        context.env_passthrough.split_call_env(&args).0,
+    );
     let content = args
         .get("content")
         .and_then(Value::as_str)
@@ -992,6 +1014,7 @@ async fn execute_command_streaming(
             timeout_s,
             stdout_policy,
             stderr_policy,
+            &child_env,
             CommandStreamSink {
                 tx: tx.clone(),
                 command_index,
@@ -1227,6 +1250,7 @@ async fn execute_builtin_file_tool(
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let args = normalize_sandbox_file_tool_args(args);
@@ -1239,7 +1263,7 @@ async fn execute_builtin_file_tool(
         Err(err) => ToolResult {
             ok: false,
             data: with_error_meta(
-                json!({ "detail": err.to_string() }),
+                json!({ "detail": err.to_string(), "error_code": err.code() }),
                 ToolErrorMeta::new(
                     "SANDBOX_FILE_TOOL_FAILED",
                     Some("sandbox 文件工具执行失败。".to_string()),
@@ -1566,8 +1590,18 @@ async fn run_shell_command(
     timeout_s: f64,
     stdout_policy: CommandOutputPolicy,
     stderr_policy: CommandOutputPolicy,
+    child_env: &ChildEnv,
 ) -> Result<CommandOutput, String> {
-    run_shell_command_inner(command, cwd, timeout_s, stdout_policy, stderr_policy, None).await
+    run_shell_command_inner(
+        command,
+        cwd,
+        timeout_s,
+        stdout_policy,
+        stderr_policy,
+        child_env,
+        None,
+    )
+    .await
 }
 
 async fn run_shell_command_streaming(
@@ -1576,6 +1610,7 @@ async fn run_shell_command_streaming(
     timeout_s: f64,
     stdout_policy: CommandOutputPolicy,
     stderr_policy: CommandOutputPolicy,
+    child_env: &ChildEnv,
     stream_sink: CommandStreamSink,
 ) -> Result<CommandOutput, String> {
     run_shell_command_inner(
@@ -1584,6 +1619,7 @@ async fn run_shell_command_streaming(
         timeout_s,
         stdout_policy,
         stderr_policy,
+        child_env,
         Some(stream_sink),
     )
     .await
@@ -1595,6 +1631,7 @@ async fn run_shell_command_inner(
     timeout_s: f64,
     stdout_policy: CommandOutputPolicy,
     stderr_policy: CommandOutputPolicy,
+    child_env: &ChildEnv,
     stream_sink: Option<CommandStreamSink>,
 ) -> Result<CommandOutput, String> {
     let command_env = python_runtime::resolve_desktop_command_env();
@@ -1615,6 +1652,7 @@ async fn run_shell_command_inner(
     )
     .or_else(|| command_utils::build_direct_command(command, cwd))
     {
+        child_env.apply(&mut cmd);
         python_runtime::apply_desktop_command_env(&mut cmd, &command_env);
         apply_streaming_command_env(&mut cmd);
         match run_command_output(
@@ -1633,6 +1671,7 @@ async fn run_shell_command_inner(
     }
 
     let mut cmd = command_utils::build_shell_command(command, cwd);
+    child_env.apply(&mut cmd);
     python_runtime::apply_desktop_command_env(&mut cmd, &command_env);
     apply_streaming_command_env(&mut cmd);
     run_command_output(cmd, timeout_s, stdout_policy, stderr_policy, stream_sink)
@@ -1992,6 +2031,7 @@ mod tests {
             workspace_root: workspace_root.clone(),
             container_root: temp.path().join("container"),
             allow_commands: Arc::new(HashSet::new()),
+            env_passthrough: EnvPassthrough::from_names(&[]),
         };
 
         assert_eq!(
@@ -2015,6 +2055,7 @@ mod tests {
             workspace_root: temp.path().join("workspace").join("admin__c__1"),
             container_root: PathBuf::from("/"),
             allow_commands: Arc::new(HashSet::new()),
+            env_passthrough: EnvPassthrough::from_names(&[]),
         };
 
         assert_eq!(
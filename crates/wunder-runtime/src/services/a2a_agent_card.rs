@@ -0,0 +1,81 @@
+// A2A AgentCard 地址推导：由服务 endpoint 生成候选 AgentCard URL（标准 well-known、基路径、/a2a/agentCard 与扩展卡片），供管理端拉取与 CLI 网络诊断共用。
+use crate::i18n;
+use std::collections::HashSet;
+use url::Url;
+
+/// Candidate AgentCard URLs for an A2A `endpoint`, most standard first. Callers try them in
+/// order and take the first 2xx JSON answer.
+pub fn build_a2a_agent_card_urls(endpoint: &str) -> Result<Vec<String>, String> {
+    let cleaned = endpoint.trim();
+    if cleaned.is_empty() {
+        return Err(i18n::t("tool.a2a.endpoint_required"));
+    }
+    let normalized = if cleaned.starts_with("http://") || cleaned.starts_with("https://") {
+        cleaned.to_string()
+    } else {
+        format!("http://{cleaned}")
+    };
+    let parsed = Url::parse(&normalized).map_err(|err| format!("A2A endpoint 解析失败: {err}"))?;
+
+    let mut base_url = parsed.clone();
+    base_url.set_path("");
+    base_url.set_query(None);
+    base_url.set_fragment(None);
+    let base = base_url.as_str().trim_end_matches('/').to_string();
+    let endpoint_base = parsed.as_str().trim_end_matches('/').to_string();
+
+    let path = parsed.path().trim_end_matches('/');
+    let mut base_path = String::new();
+    if !path.is_empty() && path != "/" {
+        base_path = path.to_string();
+        if base_path.ends_with("/a2a") {
+            base_path.truncate(base_path.len().saturating_sub(4));
+        }
+        base_path = base_path.trim_end_matches('/').to_string();
+    }
+
+    let mut urls = Vec::new();
+    let mut seen = HashSet::new();
+    let mut push = |url: String| {
+        if !url.is_empty() && seen.insert(url.clone()) {
+            urls.push(url);
+        }
+    };
+
+    push(format!("{base}/.well-known/agent-card.json"));
+    if !base_path.is_empty() {
+        push(format!("{base}{base_path}/.well-known/agent-card.json"));
+    }
+    push(format!("{endpoint_base}/extendedAgentCard"));
+    push(format!("{endpoint_base}/agentCard"));
+    push(format!("{base}/a2a/extendedAgentCard"));
+    push(format!("{base}/a2a/agentCard"));
+    if !base_path.is_empty() {
+        push(format!("{base}{base_path}/a2a/extendedAgentCard"));
+        push(format!("{base}{base_path}/a2a/agentCard"));
+    }
+
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agent_card_candidates_cover_origin_base_path_and_extended_cards() {
+        let urls = build_a2a_agent_card_urls("127.0.0.1:18000/team/a2a").unwrap();
+        assert_eq!(
+            urls,
+            vec![
+                "http://127.0.0.1:18000/.well-known/agent-card.json",
+                "http://127.0.0.1:18000/team/.well-known/agent-card.json",
+                "http://127.0.0.1:18000/team/a2a/extendedAgentCard",
+                "http://127.0.0.1:18000/team/a2a/agentCard",
+                "http://127.0.0.1:18000/a2a/extendedAgentCard",
+                "http://127.0.0.1:18000/a2a/agentCard",
+            ]
+        );
+        assert!(build_a2a_agent_card_urls("  ").is_err());
+    }
+}
@@ -0,0 +1,377 @@
+// 文件式智能体定义：扫描 user_agents.definition_paths 下的 *.yaml，把 agent_id 映射到系统提示词、工具集与模型，并在请求带 agent_id 时合并进请求。
+use crate::config::Config;
+use crate::core::blocking;
+use crate::schemas::WunderRequest;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+use tracing::warn;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AgentDefinition {
+    /// Defaults to the file stem when omitted.
+    #[serde(default)]
+    pub agent_id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub system_prompt: String,
+    /// Empty keeps the tools the request already allows.
+    #[serde(default)]
+    pub tool_names: Vec<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(skip)]
+    pub source: PathBuf,
+}
+
+/// Cached directories kept at once; the cache is cleared when a config lists more.
+const MAX_CACHED_DEFINITION_DIRS: usize = 32;
+
+/// Files of one directory with their modification time and size; a change in any of them
+/// invalidates the cached definitions.
+type DirectoryFingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+struct CachedDirectory {
+    fingerprint: DirectoryFingerprint,
+    definitions: Vec<AgentDefinition>,
+}
+
+fn definition_cache() -> &'static Mutex<HashMap<PathBuf, CachedDirectory>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, CachedDirectory>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// All definitions under the configured paths. The first definition of an `agent_id` wins.
+/// Relative paths resolve against `workspace.root` (the launch directory in the CLI), never
+/// the process working directory. Blocks on the filesystem; async callers go through
+/// [`apply_agent_definition`].
+pub fn load_agent_definitions(config: &Config) -> Vec<AgentDefinition> {
+    load_definitions_from(&config.user_agents.definition_paths, &config.workspace.root)
+}
+
+fn load_definitions_from(paths: &[String], workspace_root: &str) -> Vec<AgentDefinition> {
+    let mut seen = HashSet::new();
+    let mut output = Vec::new();
+    for raw_path in paths {
+        let raw_path = raw_path.trim();
+        if raw_path.is_empty() {
+            continue;
+        }
+        let Some(root) = resolve_definition_root(raw_path, workspace_root) else {
+            warn!("skip agent definition path {raw_path}: workspace.root is not absolute");
+            continue;
+        };
+        for definition in load_directory_definitions(&root) {
+            if seen.insert(definition.agent_id.clone()) {
+                output.push(definition);
+            }
+        }
+    }
+    output
+}
+
+fn resolve_definition_root(raw_path: &str, workspace_root: &str) -> Option<PathBuf> {
+    let path = Path::new(raw_path);
+    if path.is_absolute() {
+        return Some(path.to_path_buf());
+    }
+    let base = Path::new(workspace_root.trim());
+    base.is_absolute().then(|| base.join(path))
+}
+
+fn load_directory_definitions(root: &Path) -> Vec<AgentDefinition> {
+    let fingerprint = list_definition_files(root)
+        .into_iter()
+        .map(|path| {
+            let metadata = std::fs::metadata(&path).ok();
+            let modified = metadata.as_ref().and_then(|meta| meta.modified().ok());
+            let len = metadata.map(|meta| meta.len()).unwrap_or(0);
+            (path, modified, len)
+        })
+        .collect::<DirectoryFingerprint>();
+    if let Some(cached) = definition_cache().lock().get(root) {
+        if cached.fingerprint == fingerprint {
+            return cached.definitions.clone();
+        }
+    }
+    let definitions = fingerprint
+        .iter()
+        .filter_map(|(path, _, _)| read_agent_definition(path))
+        .collect::<Vec<_>>();
+    let mut cache = definition_cache().lock();
+    if cache.len() >= MAX_CACHED_DEFINITION_DIRS && !cache.contains_key(root) {
+        cache.clear();
+    }
+    cache.insert(
+        root.to_path_buf(),
+        CachedDirectory {
+            fingerprint,
+            definitions: definitions.clone(),
+        },
+    );
+    definitions
+}
+
+pub fn find_agent_definition(config: &Config, agent_id: &str) -> Option<AgentDefinition> {
+    find_in_paths(
+        &config.user_agents.definition_paths,
+        &config.workspace.root,
+        agent_id,
+    )
+}
+
+fn find_in_paths(
+    paths: &[String],
+    workspace_root: &str,
+    agent_id: &str,
+) -> Option<AgentDefinition> {
+    let agent_id = agent_id.trim();
+    if agent_id.is_empty() {
+        return None;
+    }
+    load_definitions_from(paths, workspace_root)
+        .into_iter()
+        .find(|definition| definition.agent_id == agent_id)
+}
+
+/// Merge the definition matching `request.agent_id` into the request: its system prompt goes
+/// before the caller's agent prompt, its tool list narrows the requested tools and its model
+/// applies when the request does not pick one. Returns whether a definition matched.
+pub async fn apply_agent_definition(config: &Config, request: &mut WunderRequest) -> bool {
+    let Some(agent_id) = request.agent_id.clone() else {
+        return false;
+    };
+    if config.user_agents.definition_paths.is_empty() {
+        return false;
+    }
+    let paths = config.user_agents.definition_paths.clone();
+    let workspace_root = config.workspace.root.clone();
+    let definition = blocking::run_fs("agent_definitions.load", move || {
+        Ok(find_in_paths(&paths, &workspace_root, &agent_id))
+    })
+    .await
+    .ok()
+    .flatten();
+    let Some(definition) = definition else {
+        return false;
+    };
+    merge_agent_definition(definition, request);
+    true
+}
+
+fn merge_agent_definition(definition: AgentDefinition, request: &mut WunderRequest) {
+    let definition_prompt = definition.system_prompt.trim();
+    if !definition_prompt.is_empty() {
+        request.agent_prompt = match request
+            .agent_prompt
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            Some(existing) if existing == definition_prompt => Some(existing.to_string()),
+            Some(existing) => Some(format!("{definition_prompt}\n\n{existing}")),
+            None => Some(definition_prompt.to_string()),
+        };
+    }
+    let definition_tools = definition
+        .tool_names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if !definition_tools.is_empty() {
+        if request.tool_names.is_empty() {
+            request.tool_names = definition_tools;
+        } else {
+            request
+                .tool_names
+                .retain(|name| definition_tools.contains(name));
+            // An empty list means "all tools" downstream, so run without tools instead.
+            if request.tool_names.is_empty() {
+                request.skip_tool_calls = true;
+            }
+        }
+    }
+    if request
+        .model_name
+        .as_deref()
+        .is_none_or(|value| value.trim().is_empty())
+    {
+        request.model_name = definition
+            .model
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+    }
+}
+
+fn list_definition_files(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| {
+                        ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml")
+                    })
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn read_agent_definition(path: &Path) -> Option<AgentDefinition> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let mut definition = match serde_yaml::from_str::<AgentDefinition>(&content) {
+        Ok(definition) => definition,
+        Err(err) => {
+            warn!("skip invalid agent definition {}: {err}", path.display());
+            return None;
+        }
+    };
+    definition.agent_id = definition.agent_id.trim().to_string();
+    if definition.agent_id.is_empty() {
+        definition.agent_id = path.file_stem()?.to_string_lossy().trim().to_string();
+    }
+    if definition.agent_id.is_empty() {
+        return None;
+    }
+    definition.source = path.to_path_buf();
+    Some(definition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config_with_definitions(root: &Path) -> Config {
+        std::fs::write(
+            root.join("reviewer.yaml"),
+            "system_prompt: |\n  You review diffs and never edit files.\ntool_names:\n  - 读取文件\n  - 搜索内容\nmodel: review-model\n",
+        )
+        .expect("write reviewer");
+        std::fs::write(
+            root.join("writer.yml"),
+            "agent_id: doc-writer\nsystem_prompt: Write docs.\n",
+        )
+        .expect("write writer");
+        std::fs::write(root.join("broken.yaml"), "tool_names: [").expect("write broken");
+        let mut config = Config::default();
+        config.user_agents.definition_paths = vec![root.to_string_lossy().to_string()];
+        config
+    }
+
+    fn request(payload: serde_json::Value) -> WunderRequest {
+        serde_json::from_value(payload).expect("request")
+    }
+
+    #[tokio::test]
+    async fn request_with_defined_agent_picks_up_prompt_and_tools() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let config = config_with_definitions(root.path());
+        let ids = load_agent_definitions(&config)
+            .into_iter()
+            .map(|definition| definition.agent_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["reviewer", "doc-writer"]);
+
+        let mut defined = request(json!({
+            "user_id": "alice",
+            "question": "review this",
+            "agent_id": "reviewer",
+            "agent_prompt": "Reply in English.",
+        }));
+        assert!(apply_agent_definition(&config, &mut defined).await);
+        assert_eq!(
+            defined.agent_prompt.as_deref(),
+            Some("You review diffs and never edit files.\n\nReply in English.")
+        );
+        assert_eq!(defined.tool_names, vec!["读取文件", "搜索内容"]);
+        assert_eq!(defined.model_name.as_deref(), Some("review-model"));
+
+        let mut narrowed = request(json!({
+            "user_id": "alice",
+            "question": "review this",
+            "agent_id": "reviewer",
+            "tool_names": ["执行命令", "搜索内容"],
+            "model_name": "chosen-model",
+        }));
+        assert!(apply_agent_definition(&config, &mut narrowed).await);
+        assert_eq!(narrowed.tool_names, vec!["搜索内容"]);
+        assert_eq!(narrowed.model_name.as_deref(), Some("chosen-model"));
+        assert!(!narrowed.skip_tool_calls);
+
+        let mut disjoint = request(json!({
+            "user_id": "alice",
+            "question": "review this",
+            "agent_id": "reviewer",
+            "tool_names": ["执行命令"],
+        }));
+        assert!(apply_agent_definition(&config, &mut disjoint).await);
+        assert!(disjoint.skip_tool_calls);
+
+        let mut unknown = request(json!({
+            "user_id": "alice",
+            "question": "hi",
+            "agent_id": "missing",
+        }));
+        assert!(!apply_agent_definition(&config, &mut unknown).await);
+        assert!(unknown.agent_prompt.is_none());
+        assert!(unknown.tool_names.is_empty());
+    }
+
+    #[test]
+    fn edited_definitions_replace_cached_ones() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let file = root.path().join("helper.yaml");
+        std::fs::write(&file, "system_prompt: First.\n").expect("write first");
+        let mut config = Config::default();
+        config.user_agents.definition_paths = vec![root.path().to_string_lossy().to_string()];
+        let prompt = |config: &Config| {
+            find_agent_definition(config, "helper").map(|definition| definition.system_prompt)
+        };
+        assert_eq!(prompt(&config).as_deref(), Some("First."));
+
+        std::fs::write(&file, "system_prompt: Second, longer.\n").expect("write second");
+        assert_eq!(prompt(&config).as_deref(), Some("Second, longer."));
+    }
+
+    #[tokio::test]
+    async fn relative_paths_resolve_against_workspace_root() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let agents_dir = workspace.path().join(".wunder").join("agents");
+        std::fs::create_dir_all(&agents_dir).expect("create agents dir");
+        std::fs::write(
+            agents_dir.join("local.yaml"),
+            "system_prompt: Local agent.
+",
+        )
+        .expect("write local");
+        let mut config = Config::default();
+        config.workspace.root = workspace.path().to_string_lossy().to_string();
+        config.user_agents.definition_paths = vec![".wunder/agents".to_string()];
+
+        let mut local = request(json!({
+            "user_id": "alice",
+            "question": "hi",
+            "agent_id": "local",
+        }));
+        assert!(apply_agent_definition(&config, &mut local).await);
+        assert_eq!(local.agent_prompt.as_deref(), Some("Local agent."));
+
+        config.workspace.root = "relative/workspaces".to_string();
+        assert!(load_agent_definitions(&config).is_empty());
+    }
+}
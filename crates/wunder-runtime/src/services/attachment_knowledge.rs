@@ -0,0 +1,309 @@
+// 附件入库：将对话附件（已由 doc2md 转为 Markdown）写入用户的向量知识库，后续轮次可通过知识库工具检索。
+use crate::config::{
+    normalize_knowledge_base_type, Config, KnowledgeBaseConfig, KnowledgeBaseType,
+};
+use crate::core::long_task;
+use crate::i18n;
+use crate::llm::is_embedding_model;
+use crate::schemas::AttachmentPayload;
+use crate::storage::StorageBackend;
+use crate::user_tools::{UserKnowledgeBase, UserToolStore};
+use crate::vector_knowledge::{self, VectorDocumentMeta};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Per-user vector knowledge base that remembered attachments are written to.
+pub const ATTACHMENT_KNOWLEDGE_BASE: &str = "attachments";
+/// Documents kept in the attachment base before the oldest are dropped.
+pub const MAX_ATTACHMENT_KNOWLEDGE_DOCUMENTS: usize = 200;
+/// Converted attachments larger than this are not remembered.
+const MAX_REMEMBERED_ATTACHMENT_BYTES: usize = 2 * 1024 * 1024;
+
+/// Where a remembered attachment came from; written into the stored document as a header so
+/// retrieval results carry it.
+#[derive(Debug, Clone, Serialize)]
+pub struct RememberedAttachment {
+    pub doc_id: String,
+    pub document: String,
+    pub session_id: String,
+    pub filename: String,
+    pub status: String,
+}
+
+/// Store the text attachments of one turn in the user's attachment knowledge base. Images,
+/// unconverted data URLs and oversized documents are skipped. Documents are written as
+/// `pending` so the literal fallback search finds them at once; when an embedding model is
+/// configured they are embedded by a background task instead of delaying the turn. The base
+/// keeps at most [`MAX_ATTACHMENT_KNOWLEDGE_DOCUMENTS`], dropping the least recently updated.
+pub async fn remember_attachments(
+    config: &Config,
+    storage: Arc<dyn StorageBackend>,
+    store: &UserToolStore,
+    user_id: &str,
+    session_id: &str,
+    attachments: &[AttachmentPayload],
+) -> Result<Vec<RememberedAttachment>> {
+    let documents = attachments
+        .iter()
+        .filter_map(text_attachment)
+        .filter(|(filename, content)| {
+            let fits = content.len() <= MAX_REMEMBERED_ATTACHMENT_BYTES;
+            if !fits {
+                warn!("skip remembering oversized attachment {filename} for user {user_id}");
+            }
+            fits
+        })
+        .collect::<Vec<_>>();
+    if documents.is_empty() {
+        return Ok(Vec::new());
+    }
+    let base_info = ensure_attachment_base(config, store, user_id)?;
+    let root = store.resolve_knowledge_base_root_with_type(
+        user_id,
+        &base_info.name,
+        KnowledgeBaseType::Vector,
+        true,
+    )?;
+    let base = build_base_config(&base_info, &root.to_string_lossy());
+    let owner_key = vector_knowledge::resolve_owner_key(Some(user_id));
+    let mut remembered = Vec::new();
+    let mut pending_embeddings = Vec::new();
+    for (filename, content) in documents {
+        let doc_id = vector_knowledge::build_doc_id(
+            Some(&owner_key),
+            &base.name,
+            &format!("{session_id}/{filename}"),
+        );
+        let previous = storage
+            .get_vector_document(&owner_key, &base.name, &doc_id)?
+            .map(|record| record.doc_name);
+        let doc_name = match previous {
+            Some(name) => name,
+            None => {
+                let existing = vector_knowledge::list_vector_documents(
+                    storage.as_ref(),
+                    Some(user_id),
+                    &base.name,
+                    &root,
+                )
+                .await?;
+                vector_knowledge::ensure_unique_doc_name(&filename, &existing)?
+            }
+        };
+        let content = with_provenance_header(session_id, &filename, content);
+        let meta = vector_knowledge::prepare_document(
+            &base,
+            Some(user_id),
+            storage.as_ref(),
+            &root,
+            &doc_name,
+            Some(&doc_id),
+            &content,
+            None,
+        )
+        .await?;
+        remembered.push(RememberedAttachment {
+            doc_id: meta.doc_id.clone(),
+            document: meta.name.clone(),
+            session_id: session_id.to_string(),
+            filename,
+            status: meta.status.clone(),
+        });
+        pending_embeddings.push((meta, content));
+    }
+    prune_attachment_base(storage.as_ref(), user_id, &base.name, &root).await?;
+    if vector_knowledge::ensure_vector_base_config(&base).is_ok() {
+        spawn_attachment_embedding(
+            config.clone(),
+            base,
+            storage,
+            root,
+            user_id.to_string(),
+            pending_embeddings,
+        );
+    }
+    Ok(remembered)
+}
+
+/// Drop the least recently updated documents once the base exceeds its cap.
+async fn prune_attachment_base(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    base_name: &str,
+    root: &Path,
+) -> Result<()> {
+    let mut existing =
+        vector_knowledge::list_vector_documents(storage, Some(user_id), base_name, root).await?;
+    if existing.len() <= MAX_ATTACHMENT_KNOWLEDGE_DOCUMENTS {
+        return Ok(());
+    }
+    existing.sort_by(|left, right| left.updated_at.total_cmp(&right.updated_at));
+    let excess = existing.len() - MAX_ATTACHMENT_KNOWLEDGE_DOCUMENTS;
+    for doc in existing.into_iter().take(excess) {
+        vector_knowledge::delete_vector_document_files(
+            storage,
+            Some(user_id),
+            base_name,
+            root,
+            &doc.doc_id,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+fn spawn_attachment_embedding(
+    config: Config,
+    base: KnowledgeBaseConfig,
+    storage: Arc<dyn StorageBackend>,
+    root: PathBuf,
+    user_id: String,
+    documents: Vec<(VectorDocumentMeta, String)>,
+) {
+    long_task::spawn("services.attachment_knowledge.embed", async move {
+        for (meta, content) in documents {
+            if let Err(err) = vector_knowledge::index_document(
+                &config,
+                &base,
+                Some(&user_id),
+                storage.as_ref(),
+                &root,
+                &meta.name,
+                Some(&meta.doc_id),
+                &content,
+                Some(&meta),
+            )
+            .await
+            {
+                warn!(
+                    "embed remembered attachment {} failed for user {user_id}: {err}",
+                    meta.name
+                );
+            }
+        }
+    });
+}
+
+/// Name and Markdown text of an attachment that can be indexed.
+fn text_attachment(attachment: &AttachmentPayload) -> Option<(String, &str)> {
+    let content_type = attachment
+        .content_type
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    if content_type.starts_with("image/") {
+        return None;
+    }
+    let content = attachment.content.as_deref()?.trim();
+    if content.is_empty() || content.starts_with("data:") {
+        return None;
+    }
+    let filename = attachment
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or("attachment.md")
+        .to_string();
+    Some((filename, content))
+}
+
+fn with_provenance_header(session_id: &str, filename: &str, content: &str) -> String {
+    format!("> source: session {session_id}, attachment {filename}\n\n{content}\n")
+}
+
+/// The user's attachment base, created on first use with the first available embedding model.
+fn ensure_attachment_base(
+    config: &Config,
+    store: &UserToolStore,
+    user_id: &str,
+) -> Result<UserKnowledgeBase> {
+    let payload = store.load_user_tools(user_id);
+    if let Some(base) = payload
+        .knowledge_bases
+        .iter()
+        .find(|base| base.name == ATTACHMENT_KNOWLEDGE_BASE)
+    {
+        if normalize_knowledge_base_type(base.base_type.as_deref()) != KnowledgeBaseType::Vector {
+            return Err(anyhow!(i18n::t("error.vector_knowledge_required")));
+        }
+        return Ok(base.clone());
+    }
+    let base = UserKnowledgeBase {
+        name: ATTACHMENT_KNOWLEDGE_BASE.to_string(),
+        description: i18n::t("knowledge.attachments.description"),
+        enabled: true,
+        base_type: Some("vector".to_string()),
+        embedding_model: default_embedding_model(config),
+        ..UserKnowledgeBase::default()
+    };
+    let mut bases = payload.knowledge_bases;
+    bases.push(base.clone());
+    store.update_knowledge_bases(user_id, bases)?;
+    Ok(base)
+}
+
+fn default_embedding_model(config: &Config) -> Option<String> {
+    let mut dedicated = config.embedding.models.keys().cloned().collect::<Vec<_>>();
+    dedicated.sort();
+    if let Some(name) = dedicated.into_iter().next() {
+        return Some(name);
+    }
+    let mut models = config
+        .llm
+        .models
+        .iter()
+        .filter(|(_, model)| is_embedding_model(model))
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    models.sort();
+    models.into_iter().next()
+}
+
+fn build_base_config(base: &UserKnowledgeBase, root: &str) -> KnowledgeBaseConfig {
+    KnowledgeBaseConfig {
+        name: base.name.clone(),
+        description: base.description.clone(),
+        root: root.to_string(),
+        enabled: base.enabled,
+        shared: Some(base.shared),
+        base_type: base.base_type.clone(),
+        embedding_model: base.embedding_model.clone(),
+        chunk_size: base.chunk_size,
+        chunk_overlap: base.chunk_overlap,
+        top_k: base.top_k,
+        score_threshold: base.score_threshold,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_converted_text_attachments_are_indexed() {
+        let attachment = |name: &str, content: &str, content_type: &str| AttachmentPayload {
+            name: Some(name.to_string()),
+            content: Some(content.to_string()),
+            content_type: Some(content_type.to_string()),
+            public_path: None,
+            attachment_id: None,
+            metadata: None,
+        };
+        let text = attachment("notes.md", "# Notes", "text/markdown");
+        let image = attachment("shot.png", "data:image/png;base64,AAAA", "image/png");
+        let raw = attachment("report.pdf", "data:application/pdf;base64,AAAA", "");
+
+        assert_eq!(
+            text_attachment(&text),
+            Some(("notes.md".to_string(), "# Notes"))
+        );
+        assert_eq!(text_attachment(&image), None);
+        assert_eq!(text_attachment(&raw), None);
+    }
+}
@@ -0,0 +1,308 @@
+// 附件元数据提取：文档页数（PDF/DOCX/PPTX/ODT/ODP）、图片宽高、文本语种，全部尽力而为，任何解析失败都只返回空而不影响附件本身。
+use crate::schemas::AttachmentMetadata;
+use regex::bytes::Regex as BytesRegex;
+use regex::Regex;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::debug;
+
+const LANGUAGE_SAMPLE_CHARS: usize = 4000;
+const LANGUAGE_MIN_LETTERS: usize = 20;
+/// Metadata parts are small; a larger entry is read only up to this many bytes so a zip bomb
+/// cannot inflate into memory.
+const ZIP_METADATA_READ_BYTES: u64 = 64 * 1024;
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "the", "and", "of", "to", "in", "is", "that", "for", "it", "with", "as", "on", "are", "this",
+    "be", "was", "by", "or", "from", "an",
+];
+
+/// Metadata for the file at `path`; `text` is the extracted text used for language detection.
+/// Returns `None` when nothing could be determined.
+pub async fn extract_attachment_metadata(
+    path: &Path,
+    extension: &str,
+    text: Option<&str>,
+) -> Option<AttachmentMetadata> {
+    let path: PathBuf = path.to_path_buf();
+    let extension = extension.trim().to_ascii_lowercase();
+    let text = text.map(sample_text);
+    let result = crate::core::blocking::run_fs("attachment_metadata", move || {
+        Ok(extract_metadata_sync(&path, &extension, text.as_deref()))
+    })
+    .await;
+    match result {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            debug!("attachment metadata extraction skipped: {err}");
+            None
+        }
+    }
+}
+
+fn extract_metadata_sync(
+    path: &Path,
+    extension: &str,
+    text: Option<&str>,
+) -> Option<AttachmentMetadata> {
+    let mut metadata = AttachmentMetadata::default();
+    match extension {
+        ".pdf" => {
+            metadata.page_count = std::fs::read(path)
+                .ok()
+                .and_then(|bytes| pdf_page_count(&bytes))
+        }
+        ".docx" => metadata.page_count = office_app_count(path, "Pages"),
+        ".pptx" => metadata.page_count = office_app_count(path, "Slides"),
+        ".odt" | ".odp" => metadata.page_count = odf_page_count(path),
+        _ => {
+            if let Some((width, height)) = image_dimensions(path) {
+                metadata.width = Some(width);
+                metadata.height = Some(height);
+            }
+        }
+    }
+    if metadata.width.is_none() {
+        metadata.language = text.and_then(detect_text_language);
+    }
+    (!metadata.is_empty()).then_some(metadata)
+}
+
+/// Width and height of an image file, sniffed from its header.
+pub fn image_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let bytes = std::fs::read(path).ok()?;
+    image_dimensions_from_bytes(&bytes)
+}
+
+pub fn image_dimensions_from_bytes(bytes: &[u8]) -> Option<(u32, u32)> {
+    image::ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// Page count from the page tree. The root `/Pages` node carries the largest `/Count`; when the
+/// tree sits in a compressed object stream, counting `/Type /Page` leaves is the fallback.
+fn pdf_page_count(bytes: &[u8]) -> Option<u32> {
+    if !bytes.starts_with(b"%PDF") {
+        return None;
+    }
+    let tree_count = pdf_pages_count_regex().and_then(|regex| {
+        regex
+            .captures_iter(bytes)
+            .filter_map(|captures| captures.get(1).or_else(|| captures.get(2)))
+            .filter_map(|value| {
+                std::str::from_utf8(value.as_bytes())
+                    .ok()?
+                    .parse::<u32>()
+                    .ok()
+            })
+            .max()
+    });
+    tree_count
+        .or_else(|| {
+            let leaves = pdf_page_leaf_regex()?.find_iter(bytes).count() as u32;
+            (leaves > 0).then_some(leaves)
+        })
+        .filter(|count| *count > 0)
+}
+
+fn pdf_pages_count_regex() -> Option<&'static BytesRegex> {
+    static REGEX: OnceLock<Option<BytesRegex>> = OnceLock::new();
+    REGEX
+        .get_or_init(|| {
+            BytesRegex::new(
+                r"/Type\s*/Pages\b[^>]*?/Count\s+(\d+)|/Count\s+(\d+)[^>]*?/Type\s*/Pages\b",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn pdf_page_leaf_regex() -> Option<&'static BytesRegex> {
+    static REGEX: OnceLock<Option<BytesRegex>> = OnceLock::new();
+    REGEX
+        .get_or_init(|| BytesRegex::new(r"/Type\s*/Page\b").ok())
+        .as_ref()
+}
+
+/// `<Pages>`/`<Slides>` from `docProps/app.xml`, as written by Office and most exporters.
+fn office_app_count(path: &Path, element: &str) -> Option<u32> {
+    let xml = read_zip_text(path, "docProps/app.xml")?;
+    let open = format!("<{element}>");
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find('<')?;
+    xml[start..end]
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|count| *count > 0)
+}
+
+fn odf_page_count(path: &Path) -> Option<u32> {
+    let xml = read_zip_text(path, "meta.xml")?;
+    let regex = odf_page_count_regex()?;
+    regex
+        .captures(&xml)?
+        .get(1)?
+        .as_str()
+        .parse::<u32>()
+        .ok()
+        .filter(|count| *count > 0)
+}
+
+fn odf_page_count_regex() -> Option<&'static Regex> {
+    static REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    REGEX
+        .get_or_init(|| Regex::new(r#"meta:page-count="(\d+)""#).ok())
+        .as_ref()
+}
+
+fn read_zip_text(path: &Path, name: &str) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+    let entry = archive.by_name(name).ok()?;
+    let mut bytes = Vec::new();
+    entry
+        .take(ZIP_METADATA_READ_BYTES)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Script-based guess: CJK, Hangul, Cyrillic and Arabic by dominant script, English by
+/// stopword share. Other Latin-script languages are left undetected rather than mislabeled.
+pub fn detect_text_language(text: &str) -> Option<String> {
+    let (mut han, mut kana, mut hangul, mut cyrillic, mut arabic, mut latin) = (0, 0, 0, 0, 0, 0);
+    for ch in text.chars().take(LANGUAGE_SAMPLE_CHARS) {
+        match ch {
+            '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => han += 1,
+            '\u{3040}'..='\u{30FF}' => kana += 1,
+            '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => hangul += 1,
+            '\u{0400}'..='\u{04FF}' => cyrillic += 1,
+            '\u{0600}'..='\u{06FF}' => arabic += 1,
+            ch if ch.is_ascii_alphabetic() => latin += 1,
+            _ => {}
+        }
+    }
+    let letters = han + kana + hangul + cyrillic + arabic + latin;
+    if letters < LANGUAGE_MIN_LETTERS {
+        return None;
+    }
+    // Japanese mixes kanji with kana, so any real share of kana decides it.
+    if kana * 10 >= letters {
+        return Some("ja".to_string());
+    }
+    // CJK characters carry a word each, so they win against a Latin majority of letters.
+    let cjk_weight = han * 3;
+    let scripts = [
+        ("zh", cjk_weight),
+        ("ko", hangul * 2),
+        ("ru", cyrillic),
+        ("ar", arabic),
+        ("latin", latin),
+    ];
+    let (script, _) = scripts.iter().max_by_key(|(_, count)| *count)?;
+    if *script != "latin" {
+        return Some(script.to_string());
+    }
+    let words = text
+        .chars()
+        .take(LANGUAGE_SAMPLE_CHARS)
+        .collect::<String>()
+        .split(|ch: char| !ch.is_ascii_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>();
+    if words.is_empty() {
+        return None;
+    }
+    let stopwords = words
+        .iter()
+        .filter(|word| ENGLISH_STOPWORDS.contains(&word.as_str()))
+        .count();
+    (stopwords * 100 / words.len() >= 8).then(|| "en".to_string())
+}
+
+fn sample_text(text: &str) -> String {
+    text.chars().take(LANGUAGE_SAMPLE_CHARS).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal uncompressed PDF with `pages` blank pages.
+    fn sample_pdf(pages: usize) -> Vec<u8> {
+        let kids = (0..pages)
+            .map(|index| format!("{} 0 R", index + 3))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut pdf = String::from("%PDF-1.4\n");
+        pdf.push_str("1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
+        pdf.push_str(&format!(
+            "2 0 obj\n<< /Type /Pages /Kids [{kids}] /Count {pages} >>\nendobj\n"
+        ));
+        for index in 0..pages {
+            pdf.push_str(&format!(
+                "{} 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n",
+                index + 3
+            ));
+        }
+        pdf.push_str("trailer\n<< /Root 1 0 R >>\n%%EOF\n");
+        pdf.into_bytes()
+    }
+
+    #[tokio::test]
+    async fn page_count_is_read_from_a_multi_page_pdf() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("report.pdf");
+        std::fs::write(&path, sample_pdf(3)).expect("write pdf");
+        let metadata = extract_attachment_metadata(&path, ".pdf", None)
+            .await
+            .expect("metadata");
+        assert_eq!(metadata.page_count, Some(3));
+
+        // Without the page tree, the leaves are counted instead.
+        let leaves_only = String::from_utf8(sample_pdf(4))
+            .unwrap()
+            .replace("/Type /Pages", "/Type /Tree");
+        assert_eq!(pdf_page_count(leaves_only.as_bytes()), Some(4));
+        assert_eq!(pdf_page_count(b"not a pdf"), None);
+    }
+
+    #[tokio::test]
+    async fn image_size_and_text_language_are_best_effort() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("shot.png");
+        image::RgbaImage::from_pixel(7, 5, image::Rgba([0, 0, 0, 255]))
+            .save_with_format(&path, image::ImageFormat::Png)
+            .expect("write png");
+        let metadata = extract_attachment_metadata(&path, ".png", None)
+            .await
+            .expect("metadata");
+        assert_eq!((metadata.width, metadata.height), (Some(7), Some(5)));
+
+        let broken = dir.path().join("broken.png");
+        std::fs::write(&broken, b"not an image").expect("write");
+        assert_eq!(
+            extract_attachment_metadata(&broken, ".png", None).await,
+            None
+        );
+
+        assert_eq!(
+            detect_text_language("这是一份关于季度销售情况的总结报告，包含主要指标与结论。")
+                .as_deref(),
+            Some("zh")
+        );
+        assert_eq!(
+            detect_text_language(
+                "This is the summary of the quarterly report and the key results for the team."
+            )
+            .as_deref(),
+            Some("en")
+        );
+        assert_eq!(detect_text_language("ok"), None);
+    }
+}
@@ -0,0 +1,261 @@
+// 图片附件 OCR 回退：attachments.ocr 开启且以 ocr 特性编译时，调用外部 OCR 命令识别图片文字，作为紧随其后的文本附件交给模型，并在图片元数据记录 ocr 是否生效；关闭或不可用时保持原行为。
+use crate::schemas::{AttachmentMetadata, AttachmentPayload};
+use crate::services::chat_attachments::parse_image_data_url;
+use crate::services::chat_media::load_image_attachment_data_url;
+use crate::workspace::WorkspaceManager;
+use wunder_core::attachment_config::AttachmentOcrConfig;
+
+#[cfg(feature = "ocr")]
+use std::process::Stdio;
+#[cfg(feature = "ocr")]
+use std::time::Duration;
+#[cfg(feature = "ocr")]
+use tokio::io::AsyncWriteExt;
+#[cfg(feature = "ocr")]
+use tracing::warn;
+
+const OCR_TEXT_CONTENT_TYPE: &str = "text/plain";
+
+/// Run OCR over every image attachment and insert the recognized text right after the image.
+/// Images keep their content, so vision models still see the pixels.
+pub async fn apply_image_ocr(
+    config: &AttachmentOcrConfig,
+    workspace: &WorkspaceManager,
+    attachments: &mut Vec<AttachmentPayload>,
+) {
+    if !config.enabled || !ocr_available() {
+        return;
+    }
+    let mut index = 0;
+    while index < attachments.len() {
+        let attachment = &attachments[index];
+        index += 1;
+        if !is_image(attachment) || attachment_metadata_ocr(attachment).is_some() {
+            continue;
+        }
+        let Some(bytes) = image_bytes(workspace, attachment).await else {
+            continue;
+        };
+        let text = recognize_image_text(config, &bytes).await;
+        let attachment = &mut attachments[index - 1];
+        attachment
+            .metadata
+            .get_or_insert_with(AttachmentMetadata::default)
+            .ocr = Some(text.is_some());
+        let Some(text) = text else {
+            continue;
+        };
+        let name = attachment
+            .name
+            .as_deref()
+            .unwrap_or("image")
+            .trim()
+            .to_string();
+        attachments.insert(
+            index,
+            AttachmentPayload {
+                name: Some(format!("{name} (OCR)")),
+                content: Some(text),
+                content_type: Some(OCR_TEXT_CONTENT_TYPE.to_string()),
+                public_path: None,
+                attachment_id: None,
+                metadata: Some(AttachmentMetadata {
+                    ocr: Some(true),
+                    ..AttachmentMetadata::default()
+                }),
+            },
+        );
+        index += 1;
+    }
+}
+
+/// Text recognized in `bytes`, or `None` when OCR is unavailable, fails or finds nothing.
+pub async fn recognize_image_text(config: &AttachmentOcrConfig, bytes: &[u8]) -> Option<String> {
+    let text = run_ocr_command(config, bytes).await?;
+    let text = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some(text.chars().take(config.max_chars()).collect())
+}
+
+/// Whether this build can run OCR at all; without the `ocr` feature the flag is ignored.
+pub fn ocr_available() -> bool {
+    cfg!(feature = "ocr")
+}
+
+#[cfg(feature = "ocr")]
+async fn run_ocr_command(config: &AttachmentOcrConfig, bytes: &[u8]) -> Option<String> {
+    let command = config.command.trim();
+    if command.is_empty() {
+        return None;
+    }
+    let mut cmd = tokio::process::Command::new(command);
+    cmd.arg("stdin").arg("stdout");
+    let languages = config.languages.trim();
+    if !languages.is_empty() {
+        cmd.arg("-l").arg(languages);
+    }
+    let mut child = match cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            warn!("attachment ocr command unavailable: {command}, {err}");
+            return None;
+        }
+    };
+    let mut stdin = child.stdin.take()?;
+    let input = bytes.to_vec();
+    let writer = tokio::spawn(async move {
+        let _ = stdin.write_all(&input).await;
+    });
+    let output = tokio::time::timeout(
+        Duration::from_secs(config.timeout_s()),
+        child.wait_with_output(),
+    )
+    .await;
+    let _ = writer.await;
+    match output {
+        Ok(Ok(output)) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        Ok(Ok(output)) => {
+            warn!(
+                "attachment ocr failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            None
+        }
+        Ok(Err(err)) => {
+            warn!("attachment ocr failed: {err}");
+            None
+        }
+        Err(_) => {
+            warn!("attachment ocr timed out after {}s", config.timeout_s());
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "ocr"))]
+async fn run_ocr_command(_config: &AttachmentOcrConfig, _bytes: &[u8]) -> Option<String> {
+    None
+}
+
+fn is_image(attachment: &AttachmentPayload) -> bool {
+    let content_type = attachment
+        .content_type
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    content_type.starts_with("image/")
+        || attachment
+            .content
+            .as_deref()
+            .is_some_and(|content| content.trim_start().starts_with("data:image/"))
+}
+
+fn attachment_metadata_ocr(attachment: &AttachmentPayload) -> Option<bool> {
+    attachment
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.ocr)
+}
+
+async fn image_bytes(
+    workspace: &WorkspaceManager,
+    attachment: &AttachmentPayload,
+) -> Option<Vec<u8>> {
+    let data_url = load_image_attachment_data_url(workspace, attachment).await?;
+    parse_image_data_url(&data_url, attachment.content_type.as_deref()).map(|(_, bytes)| bytes)
+}
+
+#[cfg(all(test, feature = "ocr"))]
+mod tests {
+    use super::*;
+
+    /// 5x7 bitmap glyphs, enough to spell a word without a font renderer.
+    const GLYPHS: &[(char, [&str; 7])] = &[
+        (
+            'H',
+            [
+                "#...#", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
+            ],
+        ),
+        (
+            'E',
+            [
+                "#####", "#....", "#....", "####.", "#....", "#....", "#####",
+            ],
+        ),
+        (
+            'L',
+            [
+                "#....", "#....", "#....", "#....", "#....", "#....", "#####",
+            ],
+        ),
+        (
+            'O',
+            [
+                ".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+            ],
+        ),
+    ];
+
+    fn render_text_png(text: &str) -> Vec<u8> {
+        let scale = 8u32;
+        let margin = 4 * scale;
+        let width = margin * 2 + text.chars().count() as u32 * 6 * scale;
+        let height = margin * 2 + 7 * scale;
+        let mut image = image::GrayImage::from_pixel(width, height, image::Luma([255]));
+        for (position, ch) in text.chars().enumerate() {
+            let (_, rows) = GLYPHS
+                .iter()
+                .find(|(glyph, _)| *glyph == ch)
+                .expect("glyph");
+            for (row, line) in rows.iter().enumerate() {
+                for (column, cell) in line.chars().enumerate() {
+                    if cell != '#' {
+                        continue;
+                    }
+                    let left = margin + (position as u32 * 6 + column as u32) * scale;
+                    let top = margin + row as u32 * scale;
+                    for y in top..top + scale {
+                        for x in left..left + scale {
+                            image.put_pixel(x, y, image::Luma([0]));
+                        }
+                    }
+                }
+            }
+        }
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut cursor, image::ImageFormat::Png)
+            .expect("encode png");
+        cursor.into_inner()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs the tesseract binary; run with --ignored where it is installed"]
+    async fn text_image_yields_extracted_text() {
+        let config = AttachmentOcrConfig {
+            enabled: true,
+            ..AttachmentOcrConfig::default()
+        };
+        let text = recognize_image_text(&config, &render_text_png("HELLO"))
+            .await
+            .expect("ocr text");
+        assert!(!text.trim().is_empty());
+    }
+}
@@ -0,0 +1,850 @@
+// 大附件分块续传：init 建立上传会话，chunk 按偏移写入（重传同一偏移会截断后覆盖），complete 校验大小与 SHA-256 后转为附件 id，供 WunderRequest.attachments 引用；每用户的未完成上传与未过期附件共同计入数量和字节配额；超过 TTL 无进展的分块上传与过期的已完成附件在下次 init 时清理。
+use crate::attachment::{convert_to_markdown, get_supported_extensions, sanitize_filename_stem};
+use crate::schemas::AttachmentPayload;
+use crate::services::attachment_metadata::extract_attachment_metadata;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tracing::warn;
+use uuid::Uuid;
+
+const TEMP_DIR_ROOT_ENV: &str = "WUNDER_TEMP_DIR_ROOT";
+const UPLOAD_DIR: &str = "attachment_uploads";
+const PARTIAL_DIR: &str = "partial";
+const FILES_DIR: &str = "files";
+const META_FILE: &str = "meta.json";
+const PARTIAL_DATA_FILE: &str = "data.part";
+const DATA_FILE: &str = "data";
+const MARKDOWN_FILE: &str = "converted.md";
+const HASH_READ_BUFFER_BYTES: usize = 256 * 1024;
+
+pub const MAX_UPLOAD_CHUNK_BYTES: usize = 8 * 1024 * 1024;
+pub const MAX_UPLOAD_TOTAL_BYTES: u64 = 512 * 1024 * 1024;
+/// Unfinished uploads one user may have open at once.
+pub const MAX_ACTIVE_UPLOADS_PER_USER: usize = 8;
+/// Declared bytes of one user's unfinished uploads, summed.
+pub const MAX_ACTIVE_UPLOAD_BYTES_PER_USER: u64 = 2 * MAX_UPLOAD_TOTAL_BYTES;
+/// Completed attachments plus unfinished uploads one user may keep until they expire.
+pub const MAX_STORED_ATTACHMENTS_PER_USER: usize = 64;
+/// Bytes of one user's completed attachments plus declared bytes of unfinished uploads.
+pub const MAX_STORED_ATTACHMENT_BYTES_PER_USER: u64 = 4 * MAX_UPLOAD_TOTAL_BYTES;
+/// Largest upload whose content is inlined into a request; the base64 or text copy lives in
+/// memory and in the model context.
+pub const MAX_RESOLVED_CONTENT_BYTES: u64 = 32 * 1024 * 1024;
+/// Partial uploads with no new chunk for this long are treated as abandoned, and completed
+/// attachments are dropped this long after completion.
+pub const PARTIAL_UPLOAD_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug)]
+pub enum AttachmentUploadError {
+    Invalid(String),
+    NotFound,
+    /// The chunk does not continue the upload; resume from `expected`.
+    OffsetMismatch {
+        expected: u64,
+    },
+    Incomplete {
+        received: u64,
+        total: u64,
+    },
+    HashMismatch {
+        expected: String,
+        actual: String,
+    },
+    Io(std::io::Error),
+}
+
+impl fmt::Display for AttachmentUploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(message) => write!(f, "{message}"),
+            Self::NotFound => write!(f, "upload not found"),
+            Self::OffsetMismatch { expected } => {
+                write!(f, "chunk offset mismatch, expected offset {expected}")
+            }
+            Self::Incomplete { received, total } => {
+                write!(f, "upload incomplete: received {received} of {total} bytes")
+            }
+            Self::HashMismatch { expected, actual } => {
+                write!(f, "sha256 mismatch: expected {expected}, got {actual}")
+            }
+            Self::Io(err) => write!(f, "attachment upload io error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for AttachmentUploadError {}
+
+impl From<std::io::Error> for AttachmentUploadError {
+    fn from(err: std::io::Error) -> Self {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            Self::NotFound
+        } else {
+            Self::Io(err)
+        }
+    }
+}
+
+type UploadResult<T> = Result<T, AttachmentUploadError>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSession {
+    pub upload_id: String,
+    pub user_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    pub total_bytes: u64,
+    pub created_at: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadedAttachment {
+    pub attachment_id: String,
+    pub user_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    pub size: u64,
+    pub sha256: String,
+    pub created_at: f64,
+}
+
+impl UploadedAttachment {
+    /// Entry ready to drop into `WunderRequest.attachments`.
+    pub fn to_payload(&self) -> AttachmentPayload {
+        AttachmentPayload {
+            name: Some(self.name.clone()),
+            content: None,
+            content_type: self.content_type.clone(),
+            public_path: None,
+            attachment_id: Some(self.attachment_id.clone()),
+            metadata: None,
+        }
+    }
+}
+
+pub struct AttachmentUploadStore {
+    root: PathBuf,
+    ttl: Duration,
+    locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    /// Serializes quota checks in `init` so parallel inits cannot both pass the cap.
+    init_lock: tokio::sync::Mutex<()>,
+}
+
+impl AttachmentUploadStore {
+    pub fn new(root: PathBuf, ttl: Duration) -> Self {
+        Self {
+            root,
+            ttl,
+            locks: Mutex::new(HashMap::new()),
+            init_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Process-wide store under the temp dir root, shared by the API and the orchestrator.
+    pub fn shared() -> &'static AttachmentUploadStore {
+        static STORE: OnceLock<AttachmentUploadStore> = OnceLock::new();
+        STORE.get_or_init(|| AttachmentUploadStore::new(default_upload_root(), PARTIAL_UPLOAD_TTL))
+    }
+
+    pub async fn init(
+        &self,
+        user_id: &str,
+        name: &str,
+        content_type: Option<&str>,
+        total_bytes: u64,
+    ) -> UploadResult<UploadSession> {
+        self.sweep_expired().await;
+        let name = normalize_upload_name(name)?;
+        if total_bytes == 0 {
+            return Err(AttachmentUploadError::Invalid(
+                "upload size must be greater than 0".to_string(),
+            ));
+        }
+        if total_bytes > MAX_UPLOAD_TOTAL_BYTES {
+            return Err(AttachmentUploadError::Invalid(format!(
+                "upload size exceeds limit of {MAX_UPLOAD_TOTAL_BYTES} bytes"
+            )));
+        }
+        let _init_guard = self.init_lock.lock().await;
+        let (active, active_bytes) = self.active_uploads(user_id).await;
+        if active >= MAX_ACTIVE_UPLOADS_PER_USER {
+            return Err(AttachmentUploadError::Invalid(format!(
+                "too many unfinished uploads, limit is {MAX_ACTIVE_UPLOADS_PER_USER}; complete or wait for existing ones"
+            )));
+        }
+        if active_bytes + total_bytes > MAX_ACTIVE_UPLOAD_BYTES_PER_USER {
+            return Err(AttachmentUploadError::Invalid(format!(
+                "unfinished uploads would exceed {MAX_ACTIVE_UPLOAD_BYTES_PER_USER} bytes"
+            )));
+        }
+        let (stored, stored_bytes) = self.stored_attachments(user_id).await;
+        if stored + active >= MAX_STORED_ATTACHMENTS_PER_USER {
+            return Err(AttachmentUploadError::Invalid(format!(
+                "too many stored attachments, limit is {MAX_STORED_ATTACHMENTS_PER_USER}; wait for existing ones to expire"
+            )));
+        }
+        if stored_bytes + active_bytes + total_bytes > MAX_STORED_ATTACHMENT_BYTES_PER_USER {
+            return Err(AttachmentUploadError::Invalid(format!(
+                "stored attachments would exceed {MAX_STORED_ATTACHMENT_BYTES_PER_USER} bytes"
+            )));
+        }
+        let session = UploadSession {
+            upload_id: Uuid::new_v4().simple().to_string(),
+            user_id: user_id.to_string(),
+            name,
+            content_type: content_type
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            total_bytes,
+            created_at: now_ts(),
+        };
+        let dir = self.partial_dir(&session.upload_id);
+        fs::create_dir_all(&dir).await?;
+        write_json(&dir.join(META_FILE), &session).await?;
+        fs::write(dir.join(PARTIAL_DATA_FILE), b"").await?;
+        Ok(session)
+    }
+
+    /// Session plus the number of bytes received so far, for resuming.
+    pub async fn status(
+        &self,
+        user_id: &str,
+        upload_id: &str,
+    ) -> UploadResult<(UploadSession, u64)> {
+        let session = self.load_session(user_id, upload_id).await?;
+        let received = file_len(&self.partial_dir(upload_id).join(PARTIAL_DATA_FILE)).await?;
+        Ok((session, received))
+    }
+
+    /// Write `bytes` at `offset`. An offset below the received size rewrites from there, so a
+    /// chunk whose response was lost can simply be sent again.
+    pub async fn append_chunk(
+        &self,
+        user_id: &str,
+        upload_id: &str,
+        offset: u64,
+        bytes: &[u8],
+    ) -> UploadResult<u64> {
+        if bytes.is_empty() {
+            return Err(AttachmentUploadError::Invalid("chunk is empty".to_string()));
+        }
+        if bytes.len() > MAX_UPLOAD_CHUNK_BYTES {
+            return Err(AttachmentUploadError::Invalid(format!(
+                "chunk exceeds limit of {MAX_UPLOAD_CHUNK_BYTES} bytes"
+            )));
+        }
+        let lock = self.upload_lock(upload_id);
+        let _guard = lock.lock().await;
+        let session = self.load_session(user_id, upload_id).await?;
+        let path = self.partial_dir(upload_id).join(PARTIAL_DATA_FILE);
+        let received = file_len(&path).await?;
+        if offset > received {
+            return Err(AttachmentUploadError::OffsetMismatch { expected: received });
+        }
+        let end = offset + bytes.len() as u64;
+        if end > session.total_bytes {
+            return Err(AttachmentUploadError::Invalid(format!(
+                "chunk ends at {end}, past the declared size {}",
+                session.total_bytes
+            )));
+        }
+        let mut file = fs::OpenOptions::new().write(true).open(&path).await?;
+        if offset < received {
+            file.set_len(offset).await?;
+        }
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        file.write_all(bytes).await?;
+        file.flush().await?;
+        Ok(end)
+    }
+
+    /// Verify size and hash, then turn the upload into an attachment. Completing an upload
+    /// that already completed returns the same attachment.
+    pub async fn complete(
+        &self,
+        user_id: &str,
+        upload_id: &str,
+        sha256: &str,
+    ) -> UploadResult<UploadedAttachment> {
+        let expected = normalize_sha256(sha256)?;
+        let lock = self.upload_lock(upload_id);
+        let _guard = lock.lock().await;
+        let session = match self.load_session(user_id, upload_id).await {
+            Ok(session) => session,
+            Err(AttachmentUploadError::NotFound) => {
+                let (attachment, _) = self.find(user_id, upload_id).await?;
+                if attachment.sha256 != expected {
+                    return Err(AttachmentUploadError::HashMismatch {
+                        expected,
+                        actual: attachment.sha256,
+                    });
+                }
+                return Ok(attachment);
+            }
+            Err(err) => return Err(err),
+        };
+        let partial_dir = self.partial_dir(upload_id);
+        let data_path = partial_dir.join(PARTIAL_DATA_FILE);
+        let received = file_len(&data_path).await?;
+        if received != session.total_bytes {
+            return Err(AttachmentUploadError::Incomplete {
+                received,
+                total: session.total_bytes,
+            });
+        }
+        let actual = sha256_file(&data_path).await?;
+        if actual != expected {
+            return Err(AttachmentUploadError::HashMismatch { expected, actual });
+        }
+        let attachment = UploadedAttachment {
+            attachment_id: session.upload_id.clone(),
+            user_id: session.user_id,
+            name: session.name,
+            content_type: session.content_type,
+            size: received,
+            sha256: actual,
+            created_at: now_ts(),
+        };
+        let file_dir = self.file_dir(upload_id);
+        fs::create_dir_all(&file_dir).await?;
+        fs::rename(&data_path, file_dir.join(DATA_FILE)).await?;
+        write_json(&file_dir.join(META_FILE), &attachment).await?;
+        if let Err(err) = fs::remove_dir_all(&partial_dir).await {
+            warn!(
+                "attachment upload cleanup failed: {}, {err}",
+                partial_dir.display()
+            );
+        }
+        Ok(attachment)
+    }
+
+    /// Completed attachment owned by `user_id`, with the path of its data.
+    pub async fn find(
+        &self,
+        user_id: &str,
+        attachment_id: &str,
+    ) -> UploadResult<(UploadedAttachment, PathBuf)> {
+        ensure_valid_id(attachment_id)?;
+        let dir = self.file_dir(attachment_id);
+        let attachment: UploadedAttachment = read_json(&dir.join(META_FILE)).await?;
+        if attachment.user_id != user_id {
+            return Err(AttachmentUploadError::NotFound);
+        }
+        Ok((attachment, dir.join(DATA_FILE)))
+    }
+
+    /// Remove partial uploads that saw no chunk within the TTL and completed attachments older
+    /// than the TTL. Returns how many were removed.
+    pub async fn sweep_expired(&self) -> usize {
+        self.sweep_dir(PARTIAL_DIR, PARTIAL_DATA_FILE).await
+            + self.sweep_dir(FILES_DIR, META_FILE).await
+    }
+
+    /// Remove entries of `dir_name` whose `marker` file (or the entry itself) is past the TTL.
+    async fn sweep_dir(&self, dir_name: &str, marker: &str) -> usize {
+        let dir = self.root.join(dir_name);
+        let Ok(mut entries) = fs::read_dir(&dir).await else {
+            return 0;
+        };
+        let mut removed = 0;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let touched = match fs::metadata(path.join(marker)).await {
+                Ok(meta) => meta.modified().ok(),
+                Err(_) => entry
+                    .metadata()
+                    .await
+                    .ok()
+                    .and_then(|meta| meta.modified().ok()),
+            };
+            let expired = touched
+                .and_then(|time| time.elapsed().ok())
+                .map(|elapsed| elapsed >= self.ttl)
+                .unwrap_or(false);
+            if !expired {
+                continue;
+            }
+            match fs::remove_dir_all(&path).await {
+                Ok(()) => removed += 1,
+                Err(err) => warn!(
+                    "expired attachment upload cleanup failed: {}, {err}",
+                    path.display()
+                ),
+            }
+        }
+        removed
+    }
+
+    /// Number and declared bytes of `user_id`'s unfinished uploads.
+    async fn active_uploads(&self, user_id: &str) -> (usize, u64) {
+        let Ok(mut entries) = fs::read_dir(self.root.join(PARTIAL_DIR)).await else {
+            return (0, 0);
+        };
+        let (mut count, mut bytes) = (0usize, 0u64);
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(session) = read_json::<UploadSession>(&entry.path().join(META_FILE)).await
+            else {
+                continue;
+            };
+            if session.user_id == user_id {
+                count += 1;
+                bytes += session.total_bytes;
+            }
+        }
+        (count, bytes)
+    }
+
+    /// Number and bytes of `user_id`'s completed attachments that have not expired yet.
+    async fn stored_attachments(&self, user_id: &str) -> (usize, u64) {
+        let Ok(mut entries) = fs::read_dir(self.root.join(FILES_DIR)).await else {
+            return (0, 0);
+        };
+        let (mut count, mut bytes) = (0usize, 0u64);
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(uploaded) = read_json::<UploadedAttachment>(&entry.path().join(META_FILE)).await
+            else {
+                continue;
+            };
+            if uploaded.user_id == user_id {
+                count += 1;
+                bytes += uploaded.size;
+            }
+        }
+        (count, bytes)
+    }
+
+    /// Fill `content` for attachments that reference an uploaded id: images become data URLs,
+    /// documents doc2md can read become markdown, anything else must be UTF-8 text.
+    pub async fn resolve_attachments(
+        &self,
+        user_id: &str,
+        attachments: &mut [AttachmentPayload],
+    ) -> UploadResult<()> {
+        for attachment in attachments.iter_mut() {
+            let Some(attachment_id) = attachment
+                .attachment_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            if attachment
+                .content
+                .as_deref()
+                .is_some_and(|value| !value.trim().is_empty())
+            {
+                continue;
+            }
+            let (uploaded, data_path) = self.find(user_id, &attachment_id).await?;
+            if attachment
+                .name
+                .as_deref()
+                .is_none_or(|value| value.trim().is_empty())
+            {
+                attachment.name = Some(uploaded.name.clone());
+            }
+            if attachment.content_type.is_none() {
+                attachment.content_type = uploaded.content_type.clone();
+            }
+            let content = self
+                .load_content(&uploaded, &data_path, attachment.content_type.as_deref())
+                .await?;
+            if attachment.metadata.is_none() {
+                let is_image = content.starts_with("data:image/");
+                attachment.metadata = extract_attachment_metadata(
+                    &data_path,
+                    &file_extension(&uploaded.name),
+                    (!is_image).then_some(content.as_str()),
+                )
+                .await;
+            }
+            attachment.content = Some(content);
+        }
+        Ok(())
+    }
+
+    async fn load_content(
+        &self,
+        uploaded: &UploadedAttachment,
+        data_path: &Path,
+        content_type: Option<&str>,
+    ) -> UploadResult<String> {
+        if uploaded.size > MAX_RESOLVED_CONTENT_BYTES {
+            return Err(too_large_to_inline(&uploaded.name));
+        }
+        let mime = content_type.unwrap_or("").trim().to_ascii_lowercase();
+        if mime.starts_with("image/") {
+            let bytes = fs::read(data_path).await?;
+            return Ok(format!("data:{mime};base64,{}", STANDARD.encode(bytes)));
+        }
+        let extension = file_extension(&uploaded.name);
+        let convertible = !extension.is_empty()
+            && get_supported_extensions()
+                .iter()
+                .any(|item| item.eq_ignore_ascii_case(&extension));
+        if !convertible {
+            let bytes = fs::read(data_path).await?;
+            return String::from_utf8(bytes).map_err(|_| {
+                AttachmentUploadError::Invalid(format!(
+                    "attachment {} is not a text file or a supported document",
+                    uploaded.name
+                ))
+            });
+        }
+        // doc2md wants the real extension on the input path; the markdown is cached per id.
+        let dir = self.file_dir(&uploaded.attachment_id);
+        let markdown_path = dir.join(MARKDOWN_FILE);
+        if fs::metadata(&markdown_path).await.is_err() {
+            let input_path = dir.join(format!("source{extension}"));
+            fs::copy(data_path, &input_path).await?;
+            let converted = convert_to_markdown(&input_path, &markdown_path, &extension).await;
+            let _ = fs::remove_file(&input_path).await;
+            converted.map_err(|err| AttachmentUploadError::Invalid(err.to_string()))?;
+        }
+        if file_len(&markdown_path).await? > MAX_RESOLVED_CONTENT_BYTES {
+            return Err(too_large_to_inline(&uploaded.name));
+        }
+        Ok(fs::read_to_string(&markdown_path).await?)
+    }
+
+    async fn load_session(&self, user_id: &str, upload_id: &str) -> UploadResult<UploadSession> {
+        ensure_valid_id(upload_id)?;
+        let session: UploadSession =
+            read_json(&self.partial_dir(upload_id).join(META_FILE)).await?;
+        if session.user_id != user_id {
+            return Err(AttachmentUploadError::NotFound);
+        }
+        Ok(session)
+    }
+
+    fn upload_lock(&self, upload_id: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = self.locks.lock().unwrap_or_else(|err| err.into_inner());
+        // Drop locks nobody holds any more so the map does not grow with every upload.
+        locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+        locks.entry(upload_id.to_string()).or_default().clone()
+    }
+
+    fn partial_dir(&self, upload_id: &str) -> PathBuf {
+        self.root.join(PARTIAL_DIR).join(upload_id)
+    }
+
+    fn file_dir(&self, attachment_id: &str) -> PathBuf {
+        self.root.join(FILES_DIR).join(attachment_id)
+    }
+}
+
+fn too_large_to_inline(name: &str) -> AttachmentUploadError {
+    AttachmentUploadError::Invalid(format!(
+        "attachment {name} exceeds {MAX_RESOLVED_CONTENT_BYTES} bytes and cannot be inlined"
+    ))
+}
+
+fn file_extension(name: &str) -> String {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| format!(".{}", ext.to_ascii_lowercase()))
+        .unwrap_or_default()
+}
+
+fn default_upload_root() -> PathBuf {
+    let configured = std::env::var(TEMP_DIR_ROOT_ENV)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let root = match configured {
+        Some(path) if path.is_absolute() => path,
+        Some(path) => current_dir().join(path),
+        None => current_dir().join("config").join("data").join("temp_dir"),
+    };
+    root.join(UPLOAD_DIR)
+}
+
+fn current_dir() -> PathBuf {
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// Ids name directories, so only the simple uuid form handed out by `init` is accepted.
+fn ensure_valid_id(id: &str) -> UploadResult<()> {
+    if id.len() == 32 && id.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(AttachmentUploadError::NotFound)
+    }
+}
+
+fn normalize_upload_name(name: &str) -> UploadResult<String> {
+    let name = Path::new(name.trim())
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("");
+    let extension = Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| format!(".{ext}"))
+        .unwrap_or_default();
+    let stem = Path::new(name)
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .map(sanitize_filename_stem)
+        .unwrap_or_default();
+    if stem.trim().is_empty() {
+        return Err(AttachmentUploadError::Invalid(
+            "upload name is required".to_string(),
+        ));
+    }
+    Ok(format!("{stem}{extension}"))
+}
+
+fn normalize_sha256(value: &str) -> UploadResult<String> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix("sha256:")
+        .unwrap_or(value)
+        .to_ascii_lowercase();
+    if value.len() == 64 && value.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        Ok(value)
+    } else {
+        Err(AttachmentUploadError::Invalid(
+            "sha256 must be 64 hex characters".to_string(),
+        ))
+    }
+}
+
+async fn sha256_file(path: &Path) -> UploadResult<String> {
+    let mut file = fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_READ_BUFFER_BYTES];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+async fn file_len(path: &Path) -> UploadResult<u64> {
+    Ok(fs::metadata(path).await?.len())
+}
+
+async fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> UploadResult<T> {
+    let raw = fs::read(path).await?;
+    serde_json::from_slice(&raw).map_err(|err| {
+        AttachmentUploadError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    })
+}
+
+async fn write_json<T: Serialize>(path: &Path, value: &T) -> UploadResult<()> {
+    let raw = serde_json::to_vec(value).map_err(|err| {
+        AttachmentUploadError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    })?;
+    fs::write(path, raw).await?;
+    Ok(())
+}
+
+fn now_ts() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha256_hex(bytes: &[u8]) -> String {
+        hex::encode(Sha256::digest(bytes))
+    }
+
+    #[tokio::test]
+    async fn chunked_upload_reassembles_the_original_content() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = AttachmentUploadStore::new(dir.path().to_path_buf(), PARTIAL_UPLOAD_TTL);
+        let content = (0..50_000)
+            .map(|index| format!("line {index}\n"))
+            .collect::<String>();
+        let bytes = content.as_bytes();
+        let session = store
+            .init("alice", "notes.txt", Some("text/plain"), bytes.len() as u64)
+            .await
+            .expect("init");
+
+        let chunks = bytes.chunks(64 * 1024).collect::<Vec<_>>();
+        assert!(chunks.len() > 3);
+        let mut offset = 0u64;
+        for (index, chunk) in chunks.iter().enumerate() {
+            offset = store
+                .append_chunk("alice", &session.upload_id, offset, chunk)
+                .await
+                .expect("chunk");
+            if index == 1 {
+                // A retried chunk rewrites from its offset instead of duplicating data.
+                let retry_offset = offset - chunk.len() as u64;
+                store
+                    .append_chunk("alice", &session.upload_id, retry_offset, chunk)
+                    .await
+                    .expect("retry chunk");
+            }
+        }
+        let gap = store
+            .append_chunk("alice", &session.upload_id, offset + 10, b"x")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(gap, AttachmentUploadError::OffsetMismatch { expected } if expected == offset)
+        );
+        assert!(matches!(
+            store.status("bob", &session.upload_id).await,
+            Err(AttachmentUploadError::NotFound)
+        ));
+
+        let wrong = store
+            .complete("alice", &session.upload_id, &sha256_hex(b"other"))
+            .await
+            .unwrap_err();
+        assert!(matches!(wrong, AttachmentUploadError::HashMismatch { .. }));
+
+        let attachment = store
+            .complete("alice", &session.upload_id, &sha256_hex(bytes))
+            .await
+            .expect("complete");
+        assert_eq!(attachment.size, bytes.len() as u64);
+        let (_, data_path) = store
+            .find("alice", &attachment.attachment_id)
+            .await
+            .expect("find");
+        assert_eq!(fs::read(&data_path).await.expect("read"), bytes);
+
+        let mut attachments = vec![attachment.to_payload()];
+        store
+            .resolve_attachments("alice", &mut attachments)
+            .await
+            .expect("resolve");
+        assert_eq!(attachments[0].content.as_deref(), Some(content.as_str()));
+        assert_eq!(attachments[0].name.as_deref(), Some("notes.txt"));
+    }
+
+    #[tokio::test]
+    async fn sweep_removes_only_abandoned_partial_uploads() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = AttachmentUploadStore::new(dir.path().to_path_buf(), Duration::ZERO);
+        let session = store
+            .init("alice", "big.bin", None, 10)
+            .await
+            .expect("init");
+        store
+            .append_chunk("alice", &session.upload_id, 0, b"abc")
+            .await
+            .expect("chunk");
+        assert_eq!(store.sweep_expired().await, 1);
+        assert!(matches!(
+            store.status("alice", &session.upload_id).await,
+            Err(AttachmentUploadError::NotFound)
+        ));
+
+        let fresh = AttachmentUploadStore::new(dir.path().to_path_buf(), PARTIAL_UPLOAD_TTL);
+        let session = fresh
+            .init("alice", "big.bin", None, 10)
+            .await
+            .expect("init");
+        assert_eq!(fresh.sweep_expired().await, 0);
+        assert!(fresh.status("alice", &session.upload_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn sweep_removes_expired_completed_attachments() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = AttachmentUploadStore::new(dir.path().to_path_buf(), Duration::ZERO);
+        let session = store
+            .init("alice", "note.txt", None, 3)
+            .await
+            .expect("init");
+        store
+            .append_chunk("alice", &session.upload_id, 0, b"abc")
+            .await
+            .expect("chunk");
+        let attachment = store
+            .complete("alice", &session.upload_id, &sha256_hex(b"abc"))
+            .await
+            .expect("complete");
+
+        assert_eq!(store.sweep_expired().await, 1);
+        assert!(matches!(
+            store.find("alice", &attachment.attachment_id).await,
+            Err(AttachmentUploadError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn init_enforces_per_user_upload_limits() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = AttachmentUploadStore::new(dir.path().to_path_buf(), PARTIAL_UPLOAD_TTL);
+        for _ in 0..MAX_ACTIVE_UPLOADS_PER_USER {
+            store
+                .init("alice", "part.bin", None, 10)
+                .await
+                .expect("init within cap");
+        }
+        assert!(matches!(
+            store.init("alice", "part.bin", None, 10).await,
+            Err(AttachmentUploadError::Invalid(_))
+        ));
+        store
+            .init("bob", "part.bin", None, MAX_UPLOAD_TOTAL_BYTES)
+            .await
+            .expect("other user has its own cap");
+        store
+            .init("bob", "part.bin", None, MAX_UPLOAD_TOTAL_BYTES)
+            .await
+            .expect("second large upload fits");
+        assert!(matches!(
+            store.init("bob", "part.bin", None, 1).await,
+            Err(AttachmentUploadError::Invalid(_))
+        ));
+    }
+    #[tokio::test]
+    async fn completed_attachments_count_towards_the_user_quota() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = AttachmentUploadStore::new(dir.path().to_path_buf(), PARTIAL_UPLOAD_TTL);
+        let mut completed = 0usize;
+        let refused = loop {
+            let session = match store.init("alice", "note.txt", None, 3).await {
+                Ok(session) => session,
+                Err(err) => break err,
+            };
+            store
+                .append_chunk("alice", &session.upload_id, 0, b"abc")
+                .await
+                .expect("chunk");
+            store
+                .complete("alice", &session.upload_id, &sha256_hex(b"abc"))
+                .await
+                .expect("complete");
+            completed += 1;
+            assert!(completed <= MAX_STORED_ATTACHMENTS_PER_USER);
+        };
+        assert!(matches!(refused, AttachmentUploadError::Invalid(_)));
+        assert_eq!(completed, MAX_STORED_ATTACHMENTS_PER_USER);
+        store
+            .init("bob", "note.txt", None, 3)
+            .await
+            .expect("other user has its own quota");
+    }
+}
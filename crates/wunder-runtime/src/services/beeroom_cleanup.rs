@@ -5,6 +5,7 @@ use crate::services::orchestration_context::{
     clear_session_context, list_history_records, load_hive_state,
 };
 use crate::services::swarm::beeroom::{mother_meta_key, mother_session_meta_key};
+use crate::services::swarm::shared_state::clear_hive_shared_state;
 use crate::storage::{normalize_hive_id, UserAgentRecord, DEFAULT_HIVE_ID};
 use anyhow::Result;
 use serde::Serialize;
@@ -248,6 +249,7 @@ fn clear_group_runtime_state(state: &AppState, user_id: &str, group_id: &str) ->
     clear_hive_state(storage, cleaned_user_id, cleaned_group_id)?;
     storage.delete_meta_prefix(&mother_meta_key(cleaned_user_id, cleaned_group_id))?;
     storage.delete_meta_prefix(&mother_session_meta_key(cleaned_user_id, cleaned_group_id))?;
+    clear_hive_shared_state(storage, cleaned_user_id, cleaned_group_id)?;
     Ok(())
 }
 
@@ -740,6 +740,9 @@ fn build_mother_request(
         agent_prompt: None,
         preview_skill: false,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: false,
         enforce_runtime_queue: false,
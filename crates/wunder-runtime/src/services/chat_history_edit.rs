@@ -0,0 +1,196 @@
+// 会话消息级编辑：按 history id 删除或改写单条聊天记录；删除用户消息可级联删除该轮回复，改动后模型上下文从历史重建。
+use crate::core::blocking;
+use crate::history::HistoryManager;
+use crate::orchestrator_constants::OBSERVATION_PREFIX;
+use crate::token_utils::estimate_messages_tokens;
+use crate::workspace::WorkspaceManager;
+use anyhow::Result;
+use serde_json::Value;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageEditOutcome {
+    Updated,
+    NotFound,
+    /// Only user and assistant text can be edited; tool results and system prompts cannot.
+    NotEditable,
+}
+
+/// Delete one message from a session's history. Tool results answering an assistant tool call
+/// are always removed with it so no orphaned result remains; with `cascade`, deleting a user
+/// message also removes the replies of that turn. Returns the deleted history ids, or `None` when
+/// the message is not part of the session.
+pub async fn delete_history_message(
+    workspace: &Arc<WorkspaceManager>,
+    user_id: &str,
+    session_id: &str,
+    history_id: i64,
+    cascade: bool,
+) -> Result<Option<Vec<i64>>> {
+    workspace.flush_writes_async().await;
+    let workspace = Arc::clone(workspace);
+    let (user_id, session_id) = (user_id.to_string(), session_id.to_string());
+    blocking::run_db("chat_history_edit.delete", move || {
+        let history = load_history_with_ids(&workspace, &user_id, &session_id)?;
+        let Some(ids) = collect_deleted_ids(&history, history_id, cascade) else {
+            return Ok(None);
+        };
+        workspace
+            .storage()
+            .delete_chat_history_items(&user_id, &session_id, &ids)?;
+        reset_model_context(&workspace, &user_id, &session_id)?;
+        Ok(Some(ids))
+    })
+    .await
+}
+
+/// Replace the text of a user or assistant message and rebuild the model context from history.
+pub async fn edit_history_message(
+    workspace: &Arc<WorkspaceManager>,
+    user_id: &str,
+    session_id: &str,
+    history_id: i64,
+    content: &str,
+) -> Result<MessageEditOutcome> {
+    workspace.flush_writes_async().await;
+    let workspace = Arc::clone(workspace);
+    let (user_id, session_id) = (user_id.to_string(), session_id.to_string());
+    let content = content.to_string();
+    blocking::run_db("chat_history_edit.edit", move || {
+        let storage = workspace.storage();
+        let Some(mut item) = storage.load_chat_history_item(&user_id, &session_id, history_id)?
+        else {
+            return Ok(MessageEditOutcome::NotFound);
+        };
+        if !is_editable(&item) {
+            return Ok(MessageEditOutcome::NotEditable);
+        }
+        item["content"] = Value::String(content);
+        if !storage.update_chat_history_item(&user_id, &session_id, history_id, &item)? {
+            return Ok(MessageEditOutcome::NotFound);
+        }
+        reset_model_context(&workspace, &user_id, &session_id)?;
+        Ok(MessageEditOutcome::Updated)
+    })
+    .await
+}
+
+fn load_history_with_ids(
+    workspace: &WorkspaceManager,
+    user_id: &str,
+    session_id: &str,
+) -> Result<Vec<Value>> {
+    // The paged query is the one that tags rows with `_history_id`.
+    workspace
+        .storage()
+        .load_chat_history_page(user_id, session_id, None, i64::MAX)
+}
+
+/// The model context is append-only; dropping it makes the next turn rebuild it from the edited
+/// history, and the context token count is re-estimated from the same messages.
+fn reset_model_context(
+    workspace: &WorkspaceManager,
+    user_id: &str,
+    session_id: &str,
+) -> Result<()> {
+    workspace
+        .storage()
+        .replace_model_context_entries(user_id, session_id, &[])?;
+    let messages = HistoryManager.load_history_messages(workspace, user_id, session_id, 0);
+    workspace.save_session_context_tokens(user_id, session_id, estimate_messages_tokens(&messages));
+    Ok(())
+}
+
+fn collect_deleted_ids(history: &[Value], history_id: i64, cascade: bool) -> Option<Vec<i64>> {
+    let index = history
+        .iter()
+        .position(|item| item_history_id(item) == Some(history_id))?;
+    let target = &history[index];
+    let role = item_role(target);
+    let drops_turn = cascade && role == "user" && !is_tool_result(target);
+    let drops_tool_results = role == "assistant" && has_tool_calls(target);
+    let mut ids = vec![history_id];
+    for item in &history[index + 1..] {
+        let follows = if drops_turn {
+            !is_user_prompt(item) && item_role(item) != "system"
+        } else {
+            drops_tool_results && is_tool_result(item)
+        };
+        if !follows {
+            break;
+        }
+        ids.extend(item_history_id(item));
+    }
+    Some(ids)
+}
+
+fn item_history_id(item: &Value) -> Option<i64> {
+    item.get("_history_id").and_then(Value::as_i64)
+}
+
+fn item_role(item: &Value) -> &str {
+    item.get("role").and_then(Value::as_str).unwrap_or("")
+}
+
+fn has_tool_calls(item: &Value) -> bool {
+    item.get("tool_calls")
+        .and_then(Value::as_array)
+        .is_some_and(|calls| !calls.is_empty())
+}
+
+fn is_tool_result(item: &Value) -> bool {
+    match item_role(item) {
+        "tool" => true,
+        "user" => item
+            .get("content")
+            .and_then(Value::as_str)
+            .is_some_and(|text| text.starts_with(OBSERVATION_PREFIX)),
+        _ => false,
+    }
+}
+
+fn is_user_prompt(item: &Value) -> bool {
+    item_role(item) == "user" && !is_tool_result(item)
+}
+
+fn is_editable(item: &Value) -> bool {
+    matches!(item_role(item), "user" | "assistant") && !is_tool_result(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn turn_history() -> Vec<Value> {
+        vec![
+            json!({ "role": "user", "content": "first", "_history_id": 1 }),
+            json!({ "role": "assistant", "content": "", "tool_calls": [{ "id": "c1" }], "_history_id": 2 }),
+            json!({ "role": "tool", "content": "ok", "tool_call_id": "c1", "_history_id": 3 }),
+            json!({ "role": "assistant", "content": "done", "_history_id": 4 }),
+            json!({ "role": "user", "content": "second", "_history_id": 5 }),
+            json!({ "role": "assistant", "content": "again", "_history_id": 6 }),
+        ]
+    }
+
+    #[test]
+    fn cascade_removes_the_rest_of_the_user_turn() {
+        let history = turn_history();
+        assert_eq!(collect_deleted_ids(&history, 1, false), Some(vec![1]));
+        assert_eq!(
+            collect_deleted_ids(&history, 1, true),
+            Some(vec![1, 2, 3, 4])
+        );
+        assert_eq!(collect_deleted_ids(&history, 5, true), Some(vec![5, 6]));
+        assert_eq!(collect_deleted_ids(&history, 9, true), None);
+    }
+
+    #[test]
+    fn tool_results_follow_their_call() {
+        let history = turn_history();
+        assert_eq!(collect_deleted_ids(&history, 2, false), Some(vec![2, 3]));
+        assert_eq!(collect_deleted_ids(&history, 4, false), Some(vec![4]));
+        assert!(!is_editable(&history[2]));
+        assert!(is_editable(&history[3]));
+    }
+}
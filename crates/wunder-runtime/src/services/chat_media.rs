@@ -226,6 +226,8 @@ async fn process_audio_source(
             content: Some(content),
             content_type: Some(content_type),
             public_path: Some(source_public_path),
+            attachment_id: None,
+            metadata: None,
         }],
         warnings,
     })
@@ -300,6 +302,8 @@ async fn process_image_source(
             content: None,
             content_type: Some(mime_type),
             public_path: Some(public_path),
+            attachment_id: None,
+            metadata: None,
         }],
         warnings: Vec::new(),
     })
@@ -357,6 +361,8 @@ async fn render_metafile_preview_attachment(
         content: None,
         content_type: Some("image/png".to_string()),
         public_path: Some(workspace.display_path(workspace_id, &output_path)),
+        attachment_id: None,
+        metadata: None,
     })
 }
 
@@ -508,6 +514,8 @@ async fn process_video_source(
             content: None,
             content_type: Some("image/jpeg".to_string()),
             public_path: Some(public_path),
+            attachment_id: None,
+            metadata: None,
         });
     }
 
@@ -552,6 +560,8 @@ async fn process_video_source(
                     content: Some(content),
                     content_type: Some("audio/wav".to_string()),
                     public_path: Some(workspace.display_path(workspace_id, &audio_path)),
+                    attachment_id: None,
+                    metadata: None,
                 });
                 has_audio = true;
             }
@@ -1208,6 +1218,8 @@ async fn render_gif_frame_attachment(
         content: None,
         content_type: Some("image/png".to_string()),
         public_path: Some(public_path),
+        attachment_id: None,
+        metadata: None,
     })
 }
 
@@ -1752,6 +1752,9 @@ impl CronRuntime {
             agent_prompt,
             preview_skill,
             attachments: None,
+            max_rounds: None,
+            cite: false,
+            remember_attachments: false,
             allow_queue: true,
             is_admin: UserStore::is_admin(&user),
             enforce_runtime_queue: false,
@@ -268,12 +268,8 @@ fn convert_text(path: &Path) -> Result<Doc2mdResult> {
 
 fn convert_html(path: &Path) -> Result<Doc2mdResult> {
     let text = read_text(path)?;
-    let mut markdown = parse_html(&text);
-    if markdown.trim().is_empty() {
-        markdown = strip_html_tags(&text);
-    }
     Ok(Doc2mdResult {
-        markdown,
+        markdown: html_to_markdown(&text),
         converter: "html".to_string(),
         warnings: Vec::new(),
     })
@@ -692,6 +688,16 @@ fn read_text(path: &Path) -> Result<String> {
     Ok(String::from_utf8_lossy(&data).to_string())
 }
 
+/// Convert an HTML document or fragment to Markdown, falling back to bare text when the
+/// markup yields nothing. Shared with `web_fetch` so fetched pages convert like uploads.
+pub fn html_to_markdown(text: &str) -> String {
+    let markdown = parse_html(text);
+    if markdown.trim().is_empty() {
+        return strip_html_tags(text);
+    }
+    markdown
+}
+
 fn strip_html_tags(text: &str) -> String {
     let mut output = String::new();
     let mut in_tag = false;
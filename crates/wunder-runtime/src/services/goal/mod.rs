@@ -544,6 +544,9 @@ pub async fn build_continuation_request_from_session(
         agent_prompt: None,
         preview_skill: false,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: false,
         enforce_runtime_queue: false,
@@ -140,6 +140,111 @@ impl HistoryManager {
         (items, messages)
     }
 
+    /// Tool calls recorded in `history` paired with their results, oldest first. Results are
+    /// matched by `tool_call_id`, or to the oldest open call when the model ran without native
+    /// tool ids. `tool_name` keeps only calls of that tool.
+    pub fn collect_tool_interactions(history: &[Value], tool_name: Option<&str>) -> Vec<Value> {
+        let mut interactions: Vec<Value> = Vec::new();
+        let mut open: Vec<usize> = Vec::new();
+        for item in history {
+            // Hidden entries mirror internal model context, not calls the user saw.
+            if item.pointer("/meta/hidden").and_then(Value::as_bool) == Some(true) {
+                continue;
+            }
+            let role = item.get("role").and_then(Value::as_str).unwrap_or("");
+            if role == "assistant" && (is_tool_call_item(item) || has_tool_calls_payload(item)) {
+                let Some(payload) = extract_tool_calls_payload(item) else {
+                    continue;
+                };
+                let calls = match payload {
+                    Value::Array(calls) => calls,
+                    other => vec![other],
+                };
+                for call in calls {
+                    let Some(name) = extract_replay_tool_call_name(&call) else {
+                        continue;
+                    };
+                    let arguments = call
+                        .get("function")
+                        .and_then(|function| function.get("arguments"))
+                        .or_else(|| call.get("arguments"))
+                        .cloned()
+                        .unwrap_or(Value::Null);
+                    let call_id = call
+                        .get("id")
+                        .cloned()
+                        .or_else(|| extract_tool_call_id(&call).map(Value::String))
+                        .unwrap_or(Value::Null);
+                    open.push(interactions.len());
+                    interactions.push(json!({
+                        "tool_call_id": call_id,
+                        "name": name.trim(),
+                        "arguments": arguments,
+                        "called_at": item.get("timestamp").cloned().unwrap_or(Value::Null),
+                        "user_round": item.get("user_round").cloned().unwrap_or(Value::Null),
+                        "model_round": item.get("model_round").cloned().unwrap_or(Value::Null),
+                        "result": Value::Null,
+                        "completed_at": Value::Null,
+                    }));
+                }
+            } else if role == "tool" {
+                let result_id = extract_tool_call_id(item);
+                let position = result_id
+                    .as_deref()
+                    .and_then(|id| {
+                        open.iter().position(|index| {
+                            interactions[*index]["tool_call_id"].as_str() == Some(id)
+                        })
+                    })
+                    .or_else(|| (!open.is_empty()).then_some(0));
+                let content = item.get("content").cloned().unwrap_or(Value::Null);
+                let completed_at = item.get("timestamp").cloned().unwrap_or(Value::Null);
+                match position {
+                    Some(position) => {
+                        let entry = &mut interactions[open.remove(position)];
+                        entry["result"] = content;
+                        entry["completed_at"] = completed_at;
+                    }
+                    None => {
+                        // A result whose call was compacted away still names its tool.
+                        let name = content
+                            .as_str()
+                            .and_then(|text| serde_json::from_str::<Value>(text).ok())
+                            .and_then(|value| {
+                                value
+                                    .get("tool")
+                                    .and_then(Value::as_str)
+                                    .map(str::to_string)
+                            })
+                            .unwrap_or_default();
+                        interactions.push(json!({
+                            "tool_call_id": result_id,
+                            "name": name,
+                            "arguments": Value::Null,
+                            "called_at": Value::Null,
+                            "user_round": item.get("user_round").cloned().unwrap_or(Value::Null),
+                            "model_round": item.get("model_round").cloned().unwrap_or(Value::Null),
+                            "result": content,
+                            "completed_at": completed_at,
+                        }));
+                    }
+                }
+            }
+        }
+        let Some(filter) = tool_name.map(str::trim).filter(|name| !name.is_empty()) else {
+            return interactions;
+        };
+        let filter = resolve_tool_name(filter);
+        interactions
+            .into_iter()
+            .filter(|entry| {
+                entry["name"]
+                    .as_str()
+                    .is_some_and(|name| resolve_tool_name(name) == filter)
+            })
+            .collect()
+    }
+
     pub fn load_artifact_index_message(
         &self,
         workspace: &WorkspaceManager,
@@ -456,7 +561,7 @@ fn is_tool_call_item(item: &Value) -> bool {
         .unwrap_or(false)
 }
 
-fn extract_tool_calls_payload(item: &Value) -> Option<Value> {
+pub(crate) fn extract_tool_calls_payload(item: &Value) -> Option<Value> {
     let value = item
         .get("tool_calls")
         .or_else(|| item.get("tool_call"))
@@ -468,7 +573,7 @@ fn extract_tool_calls_payload(item: &Value) -> Option<Value> {
     }
 }
 
-fn extract_tool_call_id(item: &Value) -> Option<String> {
+pub(crate) fn extract_tool_call_id(item: &Value) -> Option<String> {
     item.get("tool_call_id")
         .or_else(|| item.get("toolCallId"))
         .or_else(|| item.get("call_id"))
@@ -1633,4 +1738,48 @@ mod tests {
         assert_eq!(contents[4], "tail answer");
         assert_eq!(contents[5], "current question");
     }
+
+    #[test]
+    fn collect_tool_interactions_pairs_results_by_id_and_order() {
+        let history = vec![
+            json!({ "role": "user", "content": "list and read" }),
+            json!({
+                "role": "assistant",
+                "content": "",
+                "meta": { "type": "tool_call" },
+                "timestamp": "2026-01-01T00:00:01Z",
+                "tool_calls": [
+                    { "id": "call_a", "type": "function", "function": { "name": "list_files", "arguments": "{}" } },
+                    { "id": "call_b", "type": "function", "function": { "name": "read_file", "arguments": "{\"path\":\"a.txt\"}" } }
+                ]
+            }),
+            json!({ "role": "tool", "tool_call_id": "call_b", "content": "{\"tool\":\"read_file\",\"ok\":true}" }),
+            json!({ "role": "tool", "tool_call_id": "call_a", "content": "{\"tool\":\"list_files\",\"ok\":true}" }),
+            json!({
+                "role": "assistant",
+                "content": "",
+                "meta": { "type": "tool_call" },
+                "tool_calls": { "name": "read_file", "arguments": { "path": "b.txt" } }
+            }),
+            json!({ "role": "tool", "content": "{\"tool\":\"read_file\",\"ok\":false}" }),
+            json!({ "role": "assistant", "content": "done" }),
+        ];
+
+        let all = HistoryManager::collect_tool_interactions(&history, None);
+        let names = all
+            .iter()
+            .map(|entry| entry["name"].as_str().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["list_files", "read_file", "read_file"]);
+        assert_eq!(all[0]["tool_call_id"], "call_a");
+        assert_eq!(all[0]["result"], "{\"tool\":\"list_files\",\"ok\":true}");
+        assert_eq!(all[1]["result"], "{\"tool\":\"read_file\",\"ok\":true}");
+        assert_eq!(all[1]["called_at"], "2026-01-01T00:00:01Z");
+        assert_eq!(all[2]["arguments"], json!({ "path": "b.txt" }));
+        assert_eq!(all[2]["result"], "{\"tool\":\"read_file\",\"ok\":false}");
+
+        let reads = HistoryManager::collect_tool_interactions(&history, Some("read_file"));
+        assert_eq!(reads.len(), 2);
+        assert!(HistoryManager::collect_tool_interactions(&history, Some("write_file")).is_empty());
+    }
 }
@@ -0,0 +1,398 @@
+// 会话历史导出：把存储的对话历史映射为 OpenAI chat messages 结构（含 tool_calls/tool 结果配对），附件按引用或内联输出。
+use crate::orchestrator_constants::OBSERVATION_PREFIX;
+use crate::schemas::AttachmentPayload;
+use crate::services::chat_media::load_image_attachment_data_url;
+use crate::services::history::{extract_tool_call_id, extract_tool_calls_payload};
+use crate::workspace::WorkspaceManager;
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub const OPENAI_EXPORT_FORMAT: &str = "openai";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportAttachmentMode {
+    /// Mention each attachment by name and public path.
+    #[default]
+    Reference,
+    /// Embed stored text and images (as data URLs) into the message content.
+    Inline,
+}
+
+/// Export a session as OpenAI chat messages. Inline mode reads image attachments from the
+/// workspace; attachments that cannot be read fall back to a reference.
+pub async fn export_session_openai_messages(
+    workspace: &Arc<WorkspaceManager>,
+    user_id: &str,
+    session_id: &str,
+    mode: ExportAttachmentMode,
+) -> Result<Vec<Value>> {
+    let history = workspace.load_history_async(user_id, session_id, 0).await?;
+    let mut image_urls = HashMap::new();
+    if mode == ExportAttachmentMode::Inline {
+        for attachment in history.iter().flat_map(history_attachments) {
+            let Some(key) = attachment_key(&attachment) else {
+                continue;
+            };
+            if image_urls.contains_key(&key) || !is_image_attachment(&attachment) {
+                continue;
+            }
+            let payload = AttachmentPayload {
+                name: attachment_str(&attachment, "name"),
+                content: attachment_str(&attachment, "content"),
+                content_type: attachment_str(&attachment, "content_type"),
+                public_path: attachment_str(&attachment, "public_path"),
+                attachment_id: None,
+                metadata: None,
+            };
+            if let Some(url) = load_image_attachment_data_url(workspace, &payload).await {
+                image_urls.insert(key, url);
+            }
+        }
+    }
+    Ok(build_openai_messages(&history, mode, &image_urls))
+}
+
+/// Map stored history items to OpenAI chat messages. Assistant tool calls become `tool_calls`
+/// with string arguments, and every tool result is tied to a call id; results recorded without
+/// one take the oldest unanswered call, and results with no call at all become observation
+/// user messages, the same shape the orchestrator uses for non-native tool calling.
+/// `image_urls` maps an attachment's public path or name to its data URL for inline mode.
+pub fn build_openai_messages(
+    history: &[Value],
+    mode: ExportAttachmentMode,
+    image_urls: &HashMap<String, String>,
+) -> Vec<Value> {
+    let mut messages = Vec::new();
+    let mut open_calls: Vec<String> = Vec::new();
+    let mut generated_ids = 0usize;
+    for item in history {
+        if item.pointer("/meta/hidden").and_then(Value::as_bool) == Some(true) {
+            continue;
+        }
+        let role = item.get("role").and_then(Value::as_str).unwrap_or("");
+        match role {
+            "system" => messages.push(json!({ "role": "system", "content": content_text(item) })),
+            "user" => messages.push(json!({
+                "role": "user",
+                "content": user_content(item, mode, image_urls),
+            })),
+            "assistant" => {
+                let tool_calls = assistant_tool_calls(item, &mut generated_ids);
+                let text = content_text(item);
+                if tool_calls.is_empty() && text.trim().is_empty() {
+                    continue;
+                }
+                let mut message = Map::new();
+                message.insert("role".to_string(), json!("assistant"));
+                if tool_calls.is_empty() {
+                    message.insert("content".to_string(), json!(text));
+                } else {
+                    let content = if text.trim().is_empty() {
+                        Value::Null
+                    } else {
+                        json!(text)
+                    };
+                    message.insert("content".to_string(), content);
+                    open_calls.extend(
+                        tool_calls
+                            .iter()
+                            .filter_map(|call| call["id"].as_str().map(str::to_string)),
+                    );
+                    message.insert("tool_calls".to_string(), Value::Array(tool_calls));
+                }
+                messages.push(Value::Object(message));
+            }
+            "tool" => {
+                let stored_id = extract_tool_call_id(item);
+                let position = stored_id
+                    .and_then(|id| open_calls.iter().position(|open| *open == id))
+                    .or_else(|| (!open_calls.is_empty()).then_some(0));
+                let content = content_text(item);
+                match position {
+                    Some(position) => messages.push(json!({
+                        "role": "tool",
+                        "tool_call_id": open_calls.remove(position),
+                        "content": content,
+                    })),
+                    None => messages.push(json!({
+                        "role": "user",
+                        "content": format!("{OBSERVATION_PREFIX}{content}"),
+                    })),
+                }
+            }
+            _ => {}
+        }
+    }
+    messages
+}
+
+fn assistant_tool_calls(item: &Value, generated_ids: &mut usize) -> Vec<Value> {
+    let calls = match extract_tool_calls_payload(item) {
+        Some(Value::Array(calls)) => calls,
+        Some(call @ Value::Object(_)) => vec![call],
+        _ => return Vec::new(),
+    };
+    calls
+        .into_iter()
+        .filter_map(|call| {
+            let function = call.get("function").unwrap_or(&call);
+            let name = function
+                .get("name")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|name| !name.is_empty())?;
+            let arguments = match function.get("arguments") {
+                Some(Value::String(text)) => text.clone(),
+                Some(Value::Null) | None => "{}".to_string(),
+                Some(other) => other.to_string(),
+            };
+            let id = call
+                .get("id")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .or_else(|| extract_tool_call_id(&call))
+                .unwrap_or_else(|| {
+                    *generated_ids += 1;
+                    format!("call_export_{generated_ids}")
+                });
+            Some(json!({
+                "id": id,
+                "type": "function",
+                "function": { "name": name, "arguments": arguments },
+            }))
+        })
+        .collect()
+}
+
+fn user_content(
+    item: &Value,
+    mode: ExportAttachmentMode,
+    image_urls: &HashMap<String, String>,
+) -> Value {
+    let mut text = content_text(item);
+    let mut images = Vec::new();
+    for attachment in history_attachments(item) {
+        let name = attachment_str(&attachment, "name").unwrap_or_else(|| "attachment".to_string());
+        let image_url = attachment_key(&attachment).and_then(|key| image_urls.get(&key));
+        let stored_text = attachment_str(&attachment, "content")
+            .filter(|_| !is_image_attachment(&attachment))
+            .filter(|content| !content.starts_with("data:"));
+        let block = match (mode, image_url, stored_text) {
+            (ExportAttachmentMode::Inline, Some(url), _) => {
+                images.push(json!({ "type": "image_url", "image_url": { "url": url } }));
+                continue;
+            }
+            (ExportAttachmentMode::Inline, None, Some(content)) => {
+                format!("[Attachment: {name}]\n{content}")
+            }
+            _ => match attachment_str(&attachment, "public_path") {
+                Some(path) => format!("[Attachment: {name}] {path}"),
+                None => format!("[Attachment: {name}]"),
+            },
+        };
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str(&block);
+    }
+    if images.is_empty() {
+        return json!(text);
+    }
+    let mut parts = vec![json!({ "type": "text", "text": text })];
+    parts.extend(images);
+    Value::Array(parts)
+}
+
+/// Message text; content stored as parts keeps only its text parts.
+fn content_text(item: &Value) -> String {
+    match item.get("content") {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| match part {
+                Value::String(text) => Some(text.as_str()),
+                other => other.get("text").and_then(Value::as_str),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn history_attachments(item: &Value) -> Vec<Value> {
+    item.get("attachments")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn attachment_str(attachment: &Value, key: &str) -> Option<String> {
+    attachment
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn attachment_key(attachment: &Value) -> Option<String> {
+    attachment_str(attachment, "public_path").or_else(|| attachment_str(attachment, "name"))
+}
+
+fn is_image_attachment(attachment: &Value) -> bool {
+    attachment_str(attachment, "content_type")
+        .is_some_and(|content_type| content_type.to_ascii_lowercase().starts_with("image/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_history() -> Vec<Value> {
+        vec![
+            json!({ "role": "system", "content": "be brief" }),
+            json!({
+                "role": "user",
+                "content": "summarize these",
+                "attachments": [
+                    { "name": "notes.txt", "content_type": "text/plain", "content": "alpha", "public_path": "/workspaces/u/notes.txt" },
+                    { "name": "shot.png", "content_type": "image/png", "public_path": "/workspaces/u/shot.png" }
+                ]
+            }),
+            json!({
+                "role": "assistant",
+                "content": "",
+                "meta": { "type": "tool_call" },
+                "tool_calls": [
+                    { "id": "call_1", "type": "function", "function": { "name": "read_file", "arguments": "{\"path\":\"a\"}" } },
+                    { "function": { "name": "list_files", "arguments": { "path": "." } } }
+                ]
+            }),
+            json!({ "role": "tool", "tool_call_id": "call_1", "content": "{\"ok\":true}" }),
+            json!({ "role": "tool", "content": "[\"a\"]" }),
+            json!({ "role": "tool", "content": "late result" }),
+            json!({ "role": "user", "content": "hidden", "meta": { "hidden": true } }),
+            json!({ "role": "assistant", "content": "done" }),
+        ]
+    }
+
+    #[test]
+    fn exported_messages_round_trip_roles() {
+        let messages = build_openai_messages(
+            &sample_history(),
+            ExportAttachmentMode::Reference,
+            &HashMap::new(),
+        );
+        let roles = messages
+            .iter()
+            .map(|message| message["role"].as_str().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            roles,
+            vec![
+                "system",
+                "user",
+                "assistant",
+                "tool",
+                "tool",
+                "user",
+                "assistant"
+            ]
+        );
+
+        let calls = messages[2]["tool_calls"].as_array().expect("tool calls");
+        assert_eq!(messages[2]["content"], Value::Null);
+        assert_eq!(calls[0]["id"], "call_1");
+        assert_eq!(calls[1]["id"], "call_export_1");
+        assert_eq!(calls[1]["function"]["arguments"], "{\"path\":\".\"}");
+        assert_eq!(messages[3]["tool_call_id"], "call_1");
+        assert_eq!(messages[4]["tool_call_id"], "call_export_1");
+        assert_eq!(
+            messages[5]["content"],
+            format!("{OBSERVATION_PREFIX}late result")
+        );
+        assert_eq!(
+            messages[1]["content"],
+            "summarize these\n\n[Attachment: notes.txt] /workspaces/u/notes.txt\n\n[Attachment: shot.png] /workspaces/u/shot.png"
+        );
+
+        // The export is plain JSON: parsing it back yields the same role sequence.
+        let reparsed: Vec<Value> =
+            serde_json::from_str(&serde_json::to_string(&messages).expect("serialize"))
+                .expect("parse");
+        assert_eq!(reparsed, messages);
+    }
+
+    #[test]
+    fn inline_mode_embeds_text_and_images() {
+        let image_urls = HashMap::from([(
+            "/workspaces/u/shot.png".to_string(),
+            "data:image/png;base64,AAAA".to_string(),
+        )]);
+        let messages =
+            build_openai_messages(&sample_history(), ExportAttachmentMode::Inline, &image_urls);
+        let parts = messages[1]["content"].as_array().expect("content parts");
+        assert_eq!(
+            parts[0],
+            json!({ "type": "text", "text": "summarize these\n\n[Attachment: notes.txt]\nalpha" })
+        );
+        assert_eq!(parts[1]["image_url"]["url"], "data:image/png;base64,AAAA");
+    }
+
+    #[test]
+    fn function_call_shape_and_camel_case_ids_pair_up() {
+        let history = vec![
+            json!({
+                "role": "assistant",
+                "content": "",
+                "function_call": { "callId": "call_fc", "name": "read_file", "arguments": "{}" }
+            }),
+            json!({ "role": "tool", "callId": "call_fc", "content": "ok" }),
+            json!({
+                "role": "assistant",
+                "content": "",
+                "tool_calls": [
+                    { "call_id": "call_a", "function": { "name": "list_files", "arguments": "{}" } },
+                    { "id": "call_b", "function": { "name": "read_file", "arguments": "{}" } }
+                ]
+            }),
+            json!({ "role": "tool", "toolCallId": "call_b", "content": "b" }),
+            json!({ "role": "tool", "call_id": "call_a", "content": "a" }),
+        ];
+        let messages =
+            build_openai_messages(&history, ExportAttachmentMode::Reference, &HashMap::new());
+
+        assert_eq!(messages[0]["tool_calls"][0]["id"], "call_fc");
+        assert_eq!(
+            messages[0]["tool_calls"][0]["function"]["name"],
+            "read_file"
+        );
+        assert_eq!(
+            (
+                messages[1]["role"].clone(),
+                messages[1]["tool_call_id"].clone()
+            ),
+            (json!("tool"), json!("call_fc"))
+        );
+        assert_eq!(messages[2]["tool_calls"][0]["id"], "call_a");
+        assert_eq!(
+            (
+                messages[3]["tool_call_id"].clone(),
+                messages[3]["content"].clone()
+            ),
+            (json!("call_b"), json!("b"))
+        );
+        assert_eq!(
+            (
+                messages[4]["tool_call_id"].clone(),
+                messages[4]["content"].clone()
+            ),
+            (json!("call_a"), json!("a"))
+        );
+    }
+}
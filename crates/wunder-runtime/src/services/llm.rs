@@ -13,7 +13,11 @@ use tracing::warn;
 mod context_probe;
 mod payload;
 mod provider;
+mod provider_error;
+mod rate_limit;
 mod response;
+mod response_format;
+mod stream_interrupt;
 mod stream_tool;
 #[cfg(test)]
 use context_probe::normalize_root_url;
@@ -32,6 +36,13 @@ pub use provider::{
     normalize_openai_api_mode, normalize_provider, provider_default_base_url,
     resolve_model_base_url, resolve_openai_api_mode, should_disable_streaming_for_native_tools,
 };
+pub use provider_error::{
+    classify_llm_error_response, LlmErrorAction, LlmErrorKind, LlmProviderError,
+};
+pub use rate_limit::{
+    model_rate_limiter, rate_limit_key, ModelRateLimiter, ModelRateLimits, ModelRatePermit,
+    RateLimitWaitReason,
+};
 #[cfg(test)]
 use response::extract_tool_calls;
 use response::{
@@ -40,6 +51,10 @@ use response::{
     openai_tool_definition_to_anthropic_tool, parse_anthropic_body, parse_chat_completion_body,
     parse_responses_body,
 };
+pub use response_format::{
+    add_json_mode_instruction, resolve_response_format, validate_structured_answer,
+};
+pub use stream_interrupt::LlmStreamInterrupted;
 #[cfg(test)]
 use stream_tool::merge_stream_delta_field;
 use stream_tool::{
@@ -656,11 +671,22 @@ fn text_overlap_len(current: &str, next: &str) -> usize {
 pub struct LlmClient {
     http: Client,
     config: LlmModelConfig,
+    response_format: Option<Value>,
 }
 
 impl LlmClient {
     pub fn new(http: Client, config: LlmModelConfig) -> Self {
-        Self { http, config }
+        Self {
+            http,
+            config,
+            response_format: None,
+        }
+    }
+
+    /// Structured output for this client's requests; only the main turn call opts in.
+    pub fn with_response_format(mut self, response_format: Option<Value>) -> Self {
+        self.response_format = response_format;
+        self
     }
 
     fn is_anthropic_provider(&self) -> bool {
@@ -697,12 +723,9 @@ impl LlmClient {
             }
         };
         if !status.is_success() {
-            let detail = if body == Value::Null {
-                json!({ "raw": truncate_text(&body_text, 2048) })
-            } else {
-                body
-            };
-            return Err(anyhow!("LLM request failed: {status} {detail}"));
+            return Err(
+                LlmProviderError::from_response("LLM request failed", status, &body_text).into(),
+            );
         }
         if body == Value::Null {
             return Err(anyhow!(
@@ -794,10 +817,12 @@ impl LlmClient {
                             )
                         });
                 }
-                return Err(anyhow!(
-                    "LLM stream request failed: {status} {}",
-                    truncate_text(&text, 2048)
-                ));
+                return Err(LlmProviderError::from_response(
+                    "LLM stream request failed",
+                    status,
+                    &text,
+                )
+                .into());
             }
             let mut stream = response.bytes_stream();
             let mut buffer = String::new();
@@ -808,7 +833,19 @@ impl LlmClient {
             let mut final_response_preview = FinalResponseToolPreview::default();
             let mut saw_done = false;
             while let Some(item) = stream.next().await {
-                let bytes = item?;
+                let bytes = match item {
+                    Ok(bytes) => bytes,
+                    Err(err) if !combined.trim().is_empty() => {
+                        let partial = LlmResponse {
+                            content: combined,
+                            reasoning: reasoning_combined,
+                            usage,
+                            tool_calls: None,
+                        };
+                        return Err(LlmStreamInterrupted::new(partial, err).into());
+                    }
+                    Err(err) => return Err(err.into()),
+                };
                 let part = String::from_utf8_lossy(&bytes);
                 buffer.push_str(&part);
 
@@ -1016,6 +1053,9 @@ impl LlmClient {
         if let Some(temperature) = self.config.temperature {
             payload["temperature"] = json!(round_f32(temperature));
         }
+        if let Some(top_p) = self.config.top_p {
+            payload["top_p"] = json!(round_f32(top_p));
+        }
         if let Some(stop) = &self.config.stop {
             if !stop.is_empty() {
                 payload["stop_sequences"] = json!(stop);
@@ -1051,6 +1091,9 @@ impl LlmClient {
             "temperature": temperature,
             "stream": stream,
         });
+        if let Some(top_p) = self.config.top_p {
+            payload["top_p"] = json!(round_f32(top_p));
+        }
         if let Some(reasoning_effort) =
             normalize_reasoning_effort(self.config.reasoning_effort.as_deref())
         {
@@ -1070,6 +1113,9 @@ impl LlmClient {
                 payload["stop"] = json!(stop);
             }
         }
+        if let Some(response_format) = &self.response_format {
+            payload["response_format"] = response_format.clone();
+        }
         if let Some(tool_defs) = tools {
             if !tool_defs.is_empty() {
                 payload["tools"] = Value::Array(
@@ -1103,6 +1149,9 @@ impl LlmClient {
             "temperature": temperature,
             "stream": stream,
         });
+        if let Some(top_p) = self.config.top_p {
+            payload["top_p"] = json!(round_f32(top_p));
+        }
         if let Some(reasoning_effort) =
             normalize_reasoning_effort(self.config.reasoning_effort.as_deref())
         {
@@ -1123,6 +1172,9 @@ impl LlmClient {
                 payload["stop"] = json!(stop);
             }
         }
+        if let Some(response_format) = &self.response_format {
+            payload["text"] = response_format::responses_text_format(response_format);
+        }
         if let Some(tool_defs) = tools {
             if !tool_defs.is_empty() {
                 payload["tools"] = Value::Array(
@@ -1168,7 +1220,9 @@ pub fn build_llm_client(config: &LlmModelConfig, http: Client) -> LlmClient {
 }
 
 pub fn is_llm_configured(config: &LlmModelConfig) -> bool {
-    if crate::services::virtual_llm::is_virtual_replay_provider(config.provider.as_deref()) {
+    if crate::services::virtual_llm::is_virtual_replay_provider(config.provider.as_deref())
+        || crate::services::mock_llm::is_mock_provider(config.provider.as_deref())
+    {
         return true;
     }
     resolve_base_url(config)
@@ -1208,7 +1262,7 @@ pub async fn embed_texts(
     let headers = build_headers(config.api_key.as_deref().unwrap_or(""));
     let mut include_encoding_format = true;
     let (status, body_text, body) = loop {
-        let payload = if include_encoding_format {
+        let mut payload = if include_encoding_format {
             json!({
                 "model": model,
                 "input": inputs,
@@ -1220,6 +1274,9 @@ pub async fn embed_texts(
                 "input": inputs,
             })
         };
+        if let Some(dimensions) = config.dimensions {
+            payload["dimensions"] = json!(dimensions);
+        }
         let response = client
             .post(&endpoint)
             .headers(headers.clone())
@@ -1277,6 +1334,14 @@ pub async fn embed_texts(
                 .ok_or_else(|| anyhow!("embedding value is not number"))?;
             vector.push(num as f32);
         }
+        if let Some(expected) = config.dimensions {
+            if vector.len() != expected as usize {
+                return Err(anyhow!(
+                    "embedding dimension mismatch: model {model} is configured for {expected} dimensions but returned {}",
+                    vector.len()
+                ));
+            }
+        }
         if index < outputs.len() {
             outputs[index] = vector;
         }
@@ -1,4 +1,5 @@
 use crate::config::LlmModelConfig;
+use crate::services::mock_llm::MOCK_PROVIDER;
 use crate::services::virtual_llm::VIRTUAL_REPLAY_PROVIDER;
 use reqwest::header::HeaderMap;
 use url::Url;
@@ -45,6 +46,7 @@ pub fn normalize_provider(provider: Option<&str>) -> String {
         "ollama" => "ollama".to_string(),
         "lm_studio" => "lmstudio".to_string(),
         "lmstudio" => "lmstudio".to_string(),
+        "mock" | "mock_llm" => MOCK_PROVIDER.to_string(),
         "virtual"
         | "virtual_llm"
         | "virtual_model"
@@ -0,0 +1,417 @@
+// 上游错误归一化：把各家 OpenAI 兼容网关（OpenAI、Qwen/DashScope、OpenRouter、Anthropic）的错误体与状态码映射为统一类别。
+use super::truncate_text;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+const RAW_BODY_MAX_CHARS: usize = 2048;
+
+/// Normalized category of a failed provider call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmErrorKind {
+    ContextLengthExceeded,
+    RateLimited,
+    QuotaExhausted,
+    InvalidApiKey,
+    ModelNotFound,
+    ContentFiltered,
+    InvalidRequest,
+    Unavailable,
+    Unknown,
+}
+
+/// What the caller should do about a failed provider call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmErrorAction {
+    /// Transient; the same request may succeed later.
+    Retry,
+    /// The request itself must change (e.g. compact the context) before retrying.
+    Fallback,
+    /// Retrying cannot help; the user or an administrator has to act.
+    SurfaceToUser,
+}
+
+impl LlmErrorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ContextLengthExceeded => "context_length_exceeded",
+            Self::RateLimited => "rate_limited",
+            Self::QuotaExhausted => "quota_exhausted",
+            Self::InvalidApiKey => "invalid_api_key",
+            Self::ModelNotFound => "model_not_found",
+            Self::ContentFiltered => "content_filtered",
+            Self::InvalidRequest => "invalid_request",
+            Self::Unavailable => "unavailable",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    pub fn action(self) -> LlmErrorAction {
+        match self {
+            Self::ContextLengthExceeded => LlmErrorAction::Fallback,
+            Self::RateLimited | Self::Unavailable | Self::Unknown => LlmErrorAction::Retry,
+            Self::QuotaExhausted
+            | Self::InvalidApiKey
+            | Self::ModelNotFound
+            | Self::ContentFiltered
+            | Self::InvalidRequest => LlmErrorAction::SurfaceToUser,
+        }
+    }
+}
+
+impl LlmErrorAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Retry => "retry",
+            Self::Fallback => "fallback",
+            Self::SurfaceToUser => "surface_to_user",
+        }
+    }
+}
+
+/// Non-2xx provider response. `Display` keeps the `<label>: <status> <body>` text the retry
+/// heuristics and logs already rely on.
+#[derive(Debug, Clone)]
+pub struct LlmProviderError {
+    pub kind: LlmErrorKind,
+    pub status: u16,
+    /// Provider error code or type, e.g. `context_length_exceeded` or `Throttling.RateQuota`.
+    pub code: Option<String>,
+    /// Provider message, or the raw body when none could be extracted.
+    pub message: String,
+    /// Response body, truncated, for debugging.
+    pub raw: String,
+    label: &'static str,
+}
+
+impl LlmProviderError {
+    pub(crate) fn from_response(label: &'static str, status: StatusCode, body: &str) -> Self {
+        let mut error = classify_llm_error_response(status.as_u16(), body);
+        error.label = label;
+        error
+    }
+
+    pub fn to_value(&self) -> Value {
+        json!({
+            "kind": self.kind.as_str(),
+            "action": self.kind.action().as_str(),
+            "status": self.status,
+            "code": self.code,
+            "message": self.message,
+            "raw": self.raw,
+        })
+    }
+}
+
+impl std::fmt::Display for LlmProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match StatusCode::from_u16(self.status) {
+            Ok(status) => write!(f, "{}: {status} {}", self.label, self.raw),
+            Err(_) => write!(f, "{}: {} {}", self.label, self.status, self.raw),
+        }
+    }
+}
+
+impl std::error::Error for LlmProviderError {}
+
+/// Classify a non-2xx provider response from its status code and body.
+pub fn classify_llm_error_response(status: u16, body: &str) -> LlmProviderError {
+    let parsed = serde_json::from_str::<Value>(body).unwrap_or(Value::Null);
+    let (code, error_type, message) = extract_error_fields(&parsed);
+    let message = message
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| truncate_text(body.trim(), RAW_BODY_MAX_CHARS));
+    let kind = classify_fields(
+        status,
+        code.as_deref().unwrap_or(""),
+        error_type.as_deref().unwrap_or(""),
+        &message,
+    );
+    LlmProviderError {
+        kind,
+        status,
+        code: code.or(error_type),
+        message,
+        raw: truncate_text(body, RAW_BODY_MAX_CHARS),
+        label: "LLM request failed",
+    }
+}
+
+/// `(code, type, message)` from the known body shapes:
+/// - OpenAI / DashScope compatible mode: `{"error": {"message", "type", "code"}}`
+/// - OpenRouter: `{"error": {"code": 429, "message", "metadata": {"raw"}}}`
+/// - Anthropic: `{"type": "error", "error": {"type", "message"}}`
+/// - DashScope native: `{"code": "Throttling.RateQuota", "message", "request_id"}`
+/// - bare gateways: `{"error": "..."}` or `{"detail": "..."}`
+fn extract_error_fields(body: &Value) -> (Option<String>, Option<String>, Option<String>) {
+    let text = |value: Option<&Value>| match value {
+        Some(Value::String(value)) => {
+            Some(value.trim().to_string()).filter(|value| !value.is_empty())
+        }
+        Some(Value::Number(number)) => Some(number.to_string()),
+        _ => None,
+    };
+    match body.get("error") {
+        Some(error @ Value::Object(_)) => {
+            let mut message = text(error.get("message"));
+            // OpenRouter forwards the upstream provider's own message in `metadata.raw`.
+            if let Some(raw) = text(error.pointer("/metadata/raw")) {
+                message = Some(match message {
+                    Some(message) => format!("{message} ({raw})"),
+                    None => raw,
+                });
+            }
+            (text(error.get("code")), text(error.get("type")), message)
+        }
+        Some(Value::String(message)) => (None, None, Some(message.clone())),
+        _ => (
+            text(body.get("code")),
+            None,
+            text(body.get("message")).or_else(|| text(body.get("detail"))),
+        ),
+    }
+}
+
+fn classify_fields(status: u16, code: &str, error_type: &str, message: &str) -> LlmErrorKind {
+    let code = code.to_ascii_lowercase();
+    let error_type = error_type.to_ascii_lowercase();
+    let message = message.to_lowercase();
+    let any_field = |needles: &[&str]| {
+        needles
+            .iter()
+            .any(|needle| code.contains(needle) || error_type.contains(needle))
+    };
+    let message_has = |needles: &[&str]| needles.iter().any(|needle| message.contains(needle));
+
+    if any_field(&["context_length_exceeded", "string_above_max_length"])
+        || message_has(&[
+            "context length",
+            "context_length",
+            "context window",
+            "maximum context",
+            "prompt is too long",
+            "too many tokens",
+            "range of input length",
+            "reduce the length",
+        ])
+    {
+        return LlmErrorKind::ContextLengthExceeded;
+    }
+    // OpenAI reports an exhausted balance as 429, so check it before rate limiting.
+    if status == 402
+        || any_field(&["insufficient_quota", "arrearage", "billing"])
+        || message_has(&[
+            "insufficient credits",
+            "insufficient balance",
+            "exceeded your current quota",
+            "account is in arrears",
+        ])
+    {
+        return LlmErrorKind::QuotaExhausted;
+    }
+    if status == 401
+        || any_field(&[
+            "invalid_api_key",
+            "invalidapikey",
+            "authentication_error",
+            "unauthorized",
+        ])
+        || message_has(&["invalid api key", "incorrect api key", "invalid api-key"])
+    {
+        return LlmErrorKind::InvalidApiKey;
+    }
+    if any_field(&["content_filter", "datainspectionfailed", "moderation"])
+        || message_has(&[
+            "content management policy",
+            "inappropriate content",
+            "flagged",
+            "moderation",
+        ])
+    {
+        return LlmErrorKind::ContentFiltered;
+    }
+    if status == 429
+        || any_field(&["rate_limit", "throttling"])
+        || message_has(&["rate limit", "too many requests"])
+    {
+        return LlmErrorKind::RateLimited;
+    }
+    if status == 404
+        || any_field(&["model_not_found", "modelnotfound"])
+        || message_has(&["model not found", "model does not exist", "no such model"])
+    {
+        return LlmErrorKind::ModelNotFound;
+    }
+    if status == 408
+        || status >= 500
+        || any_field(&["overloaded", "server_error", "api_error"])
+        || message_has(&["overloaded", "service unavailable", "no available provider"])
+    {
+        return LlmErrorKind::Unavailable;
+    }
+    if status == 403 || any_field(&["permission_error", "accessdenied"]) {
+        return LlmErrorKind::InvalidApiKey;
+    }
+    if matches!(status, 400 | 413 | 422) {
+        return LlmErrorKind::InvalidRequest;
+    }
+    LlmErrorKind::Unknown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(status: u16, body: &str) -> LlmErrorKind {
+        classify_llm_error_response(status, body).kind
+    }
+
+    #[test]
+    fn classifies_openai_error_bodies() {
+        let context = classify_llm_error_response(
+            400,
+            r#"{"error":{"message":"This model's maximum context length is 8192 tokens.","type":"invalid_request_error","param":"messages","code":"context_length_exceeded"}}"#,
+        );
+        assert_eq!(context.kind, LlmErrorKind::ContextLengthExceeded);
+        assert_eq!(context.kind.action(), LlmErrorAction::Fallback);
+        assert_eq!(context.code.as_deref(), Some("context_length_exceeded"));
+        assert!(context
+            .message
+            .starts_with("This model's maximum context length"));
+
+        assert_eq!(
+            kind(
+                429,
+                r#"{"error":{"message":"Rate limit reached for gpt-4o on requests per min.","type":"requests","param":null,"code":"rate_limit_exceeded"}}"#,
+            ),
+            LlmErrorKind::RateLimited
+        );
+        assert_eq!(
+            kind(
+                429,
+                r#"{"error":{"message":"You exceeded your current quota, please check your plan and billing details.","type":"insufficient_quota","param":null,"code":"insufficient_quota"}}"#,
+            ),
+            LlmErrorKind::QuotaExhausted
+        );
+        assert_eq!(
+            kind(
+                401,
+                r#"{"error":{"message":"Incorrect API key provided: sk-abc.","type":"invalid_request_error","param":null,"code":"invalid_api_key"}}"#,
+            ),
+            LlmErrorKind::InvalidApiKey
+        );
+        assert_eq!(
+            kind(
+                404,
+                r#"{"error":{"message":"The model `gpt-9` does not exist or you do not have access to it.","type":"invalid_request_error","param":null,"code":"model_not_found"}}"#,
+            ),
+            LlmErrorKind::ModelNotFound
+        );
+    }
+
+    #[test]
+    fn classifies_qwen_dashscope_error_bodies() {
+        // Compatible mode mirrors OpenAI; native endpoints put `code` at the top level.
+        assert_eq!(
+            kind(
+                400,
+                r#"{"error":{"message":"Range of input length should be [1, 30720]","type":"invalid_request_error","param":null,"code":"invalid_parameter_error"}}"#,
+            ),
+            LlmErrorKind::ContextLengthExceeded
+        );
+        let throttled = classify_llm_error_response(
+            429,
+            r#"{"code":"Throttling.RateQuota","message":"Requests rate limit exceeded, please try again later.","request_id":"a1b2"}"#,
+        );
+        assert_eq!(throttled.kind, LlmErrorKind::RateLimited);
+        assert_eq!(throttled.code.as_deref(), Some("Throttling.RateQuota"));
+        assert_eq!(
+            kind(
+                400,
+                r#"{"code":"Arrearage","message":"Access denied, please make sure your account is in good standing.","request_id":"a1b2"}"#,
+            ),
+            LlmErrorKind::QuotaExhausted
+        );
+        assert_eq!(
+            kind(
+                401,
+                r#"{"code":"InvalidApiKey","message":"Invalid API-key provided.","request_id":"a1b2"}"#,
+            ),
+            LlmErrorKind::InvalidApiKey
+        );
+        assert_eq!(
+            kind(
+                400,
+                r#"{"code":"DataInspectionFailed","message":"Input data may contain inappropriate content.","request_id":"a1b2"}"#,
+            ),
+            LlmErrorKind::ContentFiltered
+        );
+    }
+
+    #[test]
+    fn classifies_openrouter_error_bodies() {
+        let upstream = classify_llm_error_response(
+            429,
+            r#"{"error":{"code":429,"message":"Provider returned error","metadata":{"raw":"deepseek/deepseek-chat is temporarily rate-limited upstream","provider_name":"DeepInfra"}}}"#,
+        );
+        assert_eq!(upstream.kind, LlmErrorKind::RateLimited);
+        assert_eq!(upstream.code.as_deref(), Some("429"));
+        assert!(upstream.message.contains("rate-limited upstream"));
+        assert_eq!(
+            kind(
+                402,
+                r#"{"error":{"code":402,"message":"Insufficient credits. Add more using https://openrouter.ai/credits"}}"#,
+            ),
+            LlmErrorKind::QuotaExhausted
+        );
+        assert_eq!(
+            kind(
+                403,
+                r#"{"error":{"code":403,"message":"Input was flagged by moderation","metadata":{"reasons":["harassment"],"flagged_input":"..."}}}"#,
+            ),
+            LlmErrorKind::ContentFiltered
+        );
+        assert_eq!(
+            kind(
+                503,
+                r#"{"error":{"code":503,"message":"No available provider for this model"}}"#,
+            ),
+            LlmErrorKind::Unavailable
+        );
+    }
+
+    #[test]
+    fn classifies_anthropic_and_unparsed_bodies() {
+        assert_eq!(
+            kind(
+                529,
+                r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+            ),
+            LlmErrorKind::Unavailable
+        );
+        assert_eq!(
+            kind(
+                400,
+                r#"{"type":"error","error":{"type":"invalid_request_error","message":"prompt is too long: 210000 tokens > 200000 maximum"}}"#,
+            ),
+            LlmErrorKind::ContextLengthExceeded
+        );
+        let html = classify_llm_error_response(502, "<html>Bad Gateway</html>");
+        assert_eq!(html.kind, LlmErrorKind::Unavailable);
+        assert_eq!(html.message, "<html>Bad Gateway</html>");
+        assert_eq!(kind(418, "teapot"), LlmErrorKind::Unknown);
+    }
+
+    #[test]
+    fn display_keeps_the_status_and_raw_body() {
+        let error = LlmProviderError::from_response(
+            "LLM stream request failed",
+            StatusCode::TOO_MANY_REQUESTS,
+            r#"{"error":{"message":"slow down"}}"#,
+        );
+        assert_eq!(
+            error.to_string(),
+            r#"LLM stream request failed: 429 Too Many Requests {"error":{"message":"slow down"}}"#
+        );
+        assert_eq!(error.to_value()["action"], "retry");
+    }
+}
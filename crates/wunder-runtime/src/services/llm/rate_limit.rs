@@ -0,0 +1,430 @@
+// 模型级限流：按上游地址+模型名共享的并发信号量与 QPS 令牌桶，超限请求排队等待而不是直接打到上游。
+use crate::config::LlmModelConfig;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits read from `LlmModelConfig`; zero or unset means unlimited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelRateLimits {
+    pub max_concurrency: Option<u32>,
+    pub max_qps: Option<f32>,
+}
+
+impl ModelRateLimits {
+    pub fn from_config(config: &LlmModelConfig) -> Self {
+        Self {
+            max_concurrency: config.max_concurrency.filter(|value| *value > 0),
+            max_qps: config
+                .max_qps
+                .filter(|value| value.is_finite() && *value > 0.0),
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_concurrency.is_none() && self.max_qps.is_none()
+    }
+}
+
+/// Which limit made a request wait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitWaitReason {
+    Concurrency,
+    Qps,
+}
+
+impl RateLimitWaitReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Concurrency => "concurrency",
+            Self::Qps => "qps",
+        }
+    }
+}
+
+/// Held for the duration of one model request; dropping it frees the concurrency slot.
+#[derive(Debug, Default)]
+pub struct ModelRatePermit {
+    _slot: Option<GateSlot>,
+}
+
+/// A concurrency slot taken from a gate. Returned to the gate on drop, unless the gate was
+/// shrunk while the slot was held, in which case the slot is retired instead.
+struct GateSlot {
+    permit: Option<OwnedSemaphorePermit>,
+    gate: Arc<ModelGate>,
+}
+
+impl std::fmt::Debug for GateSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GateSlot").finish_non_exhaustive()
+    }
+}
+
+impl Drop for GateSlot {
+    fn drop(&mut self) {
+        let Some(permit) = self.permit.take() else {
+            return;
+        };
+        let mut state = self.gate.state.lock();
+        if state.retire > 0 {
+            state.retire -= 1;
+            permit.forget();
+        }
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    capacity: f64,
+    rate: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64) -> Self {
+        let mut bucket = Self {
+            tokens: 0.0,
+            capacity: 0.0,
+            rate,
+            updated_at: Instant::now(),
+        };
+        bucket.set_rate(rate);
+        bucket.tokens = bucket.capacity;
+        bucket
+    }
+
+    fn set_rate(&mut self, rate: f64) {
+        // Allow a burst of one second's worth of requests, but never less than one.
+        self.rate = rate;
+        self.capacity = rate.max(1.0);
+        self.tokens = self.tokens.min(self.capacity);
+    }
+
+    /// Take a token, or report how long until one is available.
+    fn take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated_at = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+    }
+}
+
+struct GateState {
+    limits: ModelRateLimits,
+    /// Slots the semaphore should hold in total, free and taken.
+    capacity: usize,
+    /// Taken slots to retire on release because the limit was lowered while they were held.
+    retire: usize,
+    bucket: Option<TokenBucket>,
+}
+
+/// One gate per upstream model, created once and resized in place when its limits change so
+/// requests holding a slot keep counting against the same budget.
+struct ModelGate {
+    slots: Arc<Semaphore>,
+    state: Mutex<GateState>,
+}
+
+impl ModelGate {
+    fn new(limits: ModelRateLimits) -> Self {
+        let gate = Self {
+            slots: Arc::new(Semaphore::new(0)),
+            state: Mutex::new(GateState {
+                limits: ModelRateLimits {
+                    max_concurrency: None,
+                    max_qps: None,
+                },
+                capacity: 0,
+                retire: 0,
+                bucket: None,
+            }),
+        };
+        gate.update(limits);
+        gate
+    }
+
+    fn update(&self, limits: ModelRateLimits) {
+        let mut state = self.state.lock();
+        if state.limits == limits {
+            return;
+        }
+        if let Some(target) = limits.max_concurrency.map(|value| value as usize) {
+            if target > state.capacity {
+                let mut grow = target - state.capacity;
+                let reclaimed = grow.min(state.retire);
+                state.retire -= reclaimed;
+                grow -= reclaimed;
+                self.slots.add_permits(grow);
+            } else if target < state.capacity {
+                let shrink = state.capacity - target;
+                let forgotten = self.slots.forget_permits(shrink);
+                state.retire += shrink - forgotten;
+            }
+            state.capacity = target;
+        }
+        state.bucket = match (limits.max_qps, state.bucket.take()) {
+            (Some(rate), Some(mut bucket)) => {
+                bucket.set_rate(f64::from(rate));
+                Some(bucket)
+            }
+            (Some(rate), None) => Some(TokenBucket::new(f64::from(rate))),
+            (None, _) => None,
+        };
+        state.limits = limits;
+    }
+
+    fn limits_concurrency(&self) -> bool {
+        self.state.lock().limits.max_concurrency.is_some()
+    }
+
+    fn try_take_token(&self) -> Result<(), Duration> {
+        match self.state.lock().bucket.as_mut() {
+            Some(bucket) => bucket.take(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Per-model limiter shared by every session of the process, so many users behind one provider
+/// key queue here instead of tripping the provider's own rate limit.
+#[derive(Default)]
+pub struct ModelRateLimiter {
+    gates: Mutex<HashMap<String, Arc<ModelGate>>>,
+}
+
+impl ModelRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admit a request immediately, or report which limit it would wait on.
+    pub fn try_acquire(
+        &self,
+        model: &str,
+        limits: ModelRateLimits,
+    ) -> Result<ModelRatePermit, RateLimitWaitReason> {
+        let Some(gate) = self.gate(model, limits) else {
+            return Ok(ModelRatePermit::default());
+        };
+        let slot = if gate.limits_concurrency() {
+            let permit = gate
+                .slots
+                .clone()
+                .try_acquire_owned()
+                .map_err(|_| RateLimitWaitReason::Concurrency)?;
+            Some(GateSlot {
+                permit: Some(permit),
+                gate: gate.clone(),
+            })
+        } else {
+            None
+        };
+        // Dropping `slot` on the error path hands the concurrency slot back.
+        gate.try_take_token()
+            .map_err(|_| RateLimitWaitReason::Qps)?;
+        Ok(ModelRatePermit { _slot: slot })
+    }
+
+    /// Wait until both a concurrency slot and a QPS token are available.
+    pub async fn acquire(&self, model: &str, limits: ModelRateLimits) -> ModelRatePermit {
+        let Some(gate) = self.gate(model, limits) else {
+            return ModelRatePermit::default();
+        };
+        let slot = if gate.limits_concurrency() {
+            // The semaphore is never closed, so acquiring only fails if that invariant breaks.
+            gate.slots
+                .clone()
+                .acquire_owned()
+                .await
+                .ok()
+                .map(|permit| GateSlot {
+                    permit: Some(permit),
+                    gate: gate.clone(),
+                })
+        } else {
+            None
+        };
+        while let Err(wait) = gate.try_take_token() {
+            tokio::time::sleep(wait).await;
+        }
+        ModelRatePermit { _slot: slot }
+    }
+
+    /// Shared gate for `model`, updated in place when its configured limits change. An
+    /// unlimited config only skips a gate that does not exist yet; an existing gate is relaxed
+    /// instead of dropped so slots already handed out stay accounted for.
+    fn gate(&self, model: &str, limits: ModelRateLimits) -> Option<Arc<ModelGate>> {
+        let mut gates = self.gates.lock();
+        if let Some(gate) = gates.get(model) {
+            gate.update(limits);
+            return (!limits.is_unlimited()).then(|| gate.clone());
+        }
+        if limits.is_unlimited() {
+            return None;
+        }
+        let gate = Arc::new(ModelGate::new(limits));
+        gates.insert(model.to_string(), gate.clone());
+        Some(gate)
+    }
+}
+
+/// Process-wide limiter used by the orchestrator for every model call.
+pub fn model_rate_limiter() -> &'static ModelRateLimiter {
+    static LIMITER: OnceLock<ModelRateLimiter> = OnceLock::new();
+    LIMITER.get_or_init(ModelRateLimiter::new)
+}
+
+/// Limiter key for a model config: the upstream endpoint plus the provider model name, so two
+/// configs only share a budget when they really hit the same upstream model.
+pub fn rate_limit_key(config: &LlmModelConfig) -> String {
+    let model = config
+        .model
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or("default");
+    match config
+        .base_url
+        .as_deref()
+        .map(|value| value.trim().trim_end_matches('/'))
+        .filter(|value| !value.is_empty())
+    {
+        Some(base_url) => format!("{base_url}#{model}"),
+        None => model.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_concurrency: Option<u32>, max_qps: Option<f32>) -> ModelRateLimits {
+        ModelRateLimits {
+            max_concurrency,
+            max_qps,
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_beyond_the_concurrency_limit_queue_until_a_slot_frees() {
+        let limiter = Arc::new(ModelRateLimiter::new());
+        let one_slot = limits(Some(1), None);
+        let first = limiter
+            .try_acquire("shared-model", one_slot)
+            .expect("first request is admitted");
+        assert_eq!(
+            limiter.try_acquire("shared-model", one_slot).err(),
+            Some(RateLimitWaitReason::Concurrency)
+        );
+
+        let queued = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire("shared-model", one_slot).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(
+            !queued.is_finished(),
+            "second request should wait, not fail"
+        );
+
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(5), queued)
+            .await
+            .expect("queued request is admitted once the slot frees")
+            .expect("join queued request");
+    }
+
+    #[tokio::test]
+    async fn qps_limit_delays_requests_past_the_burst() {
+        let limiter = ModelRateLimiter::new();
+        let two_per_second = limits(None, Some(2.0));
+        for _ in 0..2 {
+            limiter
+                .try_acquire("qps-model", two_per_second)
+                .expect("burst fits in the bucket");
+        }
+        assert_eq!(
+            limiter.try_acquire("qps-model", two_per_second).err(),
+            Some(RateLimitWaitReason::Qps)
+        );
+
+        let started = Instant::now();
+        limiter.acquire("qps-model", two_per_second).await;
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn changing_limits_keeps_held_slots_counted() {
+        let limiter = ModelRateLimiter::new();
+        let two = limits(Some(2), None);
+        let one = limits(Some(1), None);
+        let first = limiter.try_acquire("resized", two).expect("first slot");
+        let second = limiter.try_acquire("resized", two).expect("second slot");
+
+        // Another config for the same upstream model without limits must not reset the gate.
+        assert!(limiter.try_acquire("resized", limits(None, None)).is_ok());
+        assert_eq!(
+            limiter.try_acquire("resized", two).err(),
+            Some(RateLimitWaitReason::Concurrency)
+        );
+
+        // Lowering the limit retires a held slot when it comes back.
+        assert!(limiter.try_acquire("resized", one).is_err());
+        drop(first);
+        assert!(limiter.try_acquire("resized", one).is_err());
+        drop(second);
+        let only = limiter.try_acquire("resized", one).expect("one slot left");
+        assert!(limiter.try_acquire("resized", one).is_err());
+
+        drop(only);
+        let _a = limiter.try_acquire("resized", two).expect("raised again");
+        let _b = limiter.try_acquire("resized", two).expect("raised again");
+        assert!(limiter.try_acquire("resized", two).is_err());
+    }
+
+    #[test]
+    fn rate_limit_key_separates_endpoints_serving_the_same_model() {
+        let config = |base_url: Option<&str>| LlmModelConfig {
+            base_url: base_url.map(ToString::to_string),
+            model: Some("gpt-4o".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(rate_limit_key(&config(None)), "gpt-4o");
+        assert_eq!(
+            rate_limit_key(&config(Some("https://a.example/v1/"))),
+            rate_limit_key(&config(Some("https://a.example/v1")))
+        );
+        assert_ne!(
+            rate_limit_key(&config(Some("https://a.example/v1"))),
+            rate_limit_key(&config(Some("https://b.example/v1")))
+        );
+    }
+
+    #[test]
+    fn unlimited_models_and_other_models_are_not_throttled() {
+        let limiter = ModelRateLimiter::new();
+        let _held = limiter
+            .try_acquire("busy-model", limits(Some(1), None))
+            .expect("first slot");
+        assert!(limiter
+            .try_acquire("other-model", limits(Some(1), None))
+            .is_ok());
+        assert!(limiter
+            .try_acquire("busy-model", limits(None, None))
+            .is_ok());
+        assert!(ModelRateLimits::from_config(&LlmModelConfig {
+            max_concurrency: Some(0),
+            max_qps: Some(0.0),
+            ..Default::default()
+        })
+        .is_unlimited());
+    }
+}
@@ -0,0 +1,185 @@
+// 结构化输出：解析模型配置中的 response_format（json_object / json_schema），生成各 API 的请求字段与 JSON 系统提示，并校验最终回答是否为合法 JSON。
+use crate::config::LlmModelConfig;
+use serde_json::{json, Value};
+
+const DEFAULT_SCHEMA_NAME: &str = "response";
+/// OpenAI rejects `json_object` requests whose messages never mention JSON.
+const JSON_MODE_INSTRUCTION: &str =
+    "Respond with a single valid JSON value only, without Markdown fences or extra prose.";
+
+/// The configured `response_format` in OpenAI chat-completions shape, or `None` for plain text.
+/// Accepts the `json`/`json_object` shorthand, a bare `json_schema` body, or a full object.
+pub fn resolve_response_format(config: &LlmModelConfig) -> Option<Value> {
+    let raw = serde_json::to_value(config.response_format.as_ref()?).ok()?;
+    match raw {
+        Value::String(text) => match text.trim().to_ascii_lowercase().as_str() {
+            "json" | "json_object" => Some(json!({ "type": "json_object" })),
+            _ => None,
+        },
+        Value::Object(mut map) => {
+            let kind = map
+                .get("type")
+                .and_then(Value::as_str)
+                .map(|value| value.trim().to_ascii_lowercase())
+                .unwrap_or_default();
+            match kind.as_str() {
+                "json_object" => Some(json!({ "type": "json_object" })),
+                "json_schema" => {
+                    let mut schema = map.remove("json_schema")?;
+                    let body = schema.as_object_mut()?;
+                    body.entry("name")
+                        .or_insert_with(|| json!(DEFAULT_SCHEMA_NAME));
+                    Some(json!({ "type": "json_schema", "json_schema": schema }))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Responses API form: `text.format` flattens the schema fields next to `type`.
+pub fn responses_text_format(format: &Value) -> Value {
+    let mut flattened = json!({ "type": format["type"].clone() });
+    if let Some(schema) = format.get("json_schema").and_then(Value::as_object) {
+        for (key, value) in schema {
+            flattened[key] = value.clone();
+        }
+    }
+    json!({ "format": flattened })
+}
+
+/// Append the JSON instruction to the leading system message, or insert one when the
+/// conversation has no plain-text system prompt.
+pub fn add_json_mode_instruction(messages: &mut Vec<Value>) {
+    if let Some(Value::String(content)) = messages
+        .first_mut()
+        .filter(|message| message.get("role").and_then(Value::as_str) == Some("system"))
+        .and_then(|message| message.get_mut("content"))
+    {
+        if !content.trim().is_empty() {
+            content.push_str("\n\n");
+        }
+        content.push_str(JSON_MODE_INSTRUCTION);
+        return;
+    }
+    messages.insert(
+        0,
+        json!({ "role": "system", "content": JSON_MODE_INSTRUCTION }),
+    );
+}
+
+/// Check a final answer against the requested format: it must parse as JSON, and for a
+/// schema with a top-level object the `required` keys must be present. Full schema validation
+/// is left to providers that enforce `strict` schemas.
+pub fn validate_structured_answer(format: &Value, answer: &str) -> Result<Value, String> {
+    let parsed: Value = serde_json::from_str(answer.trim())
+        .map_err(|err| format!("answer is not valid JSON: {err}"))?;
+    let Some(schema) = format.pointer("/json_schema/schema") else {
+        return Ok(parsed);
+    };
+    match schema.get("type").and_then(Value::as_str) {
+        Some("object") => {
+            let object = parsed
+                .as_object()
+                .ok_or_else(|| "answer must be a JSON object".to_string())?;
+            let missing = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .filter(|key| !object.contains_key(*key))
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                return Err(format!(
+                    "answer is missing required fields: {}",
+                    missing.join(", ")
+                ));
+            }
+        }
+        Some("array") if !parsed.is_array() => {
+            return Err("answer must be a JSON array".to_string());
+        }
+        _ => {}
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with(format: &str) -> LlmModelConfig {
+        LlmModelConfig {
+            response_format: Some(serde_yaml::from_str(format).expect("yaml")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn response_format_accepts_shorthand_and_schema() {
+        assert_eq!(
+            resolve_response_format(&config_with("json")),
+            Some(json!({ "type": "json_object" }))
+        );
+        assert_eq!(resolve_response_format(&config_with("text")), None);
+        assert_eq!(resolve_response_format(&LlmModelConfig::default()), None);
+
+        let format = resolve_response_format(&config_with(
+            "{type: json_schema, json_schema: {schema: {type: object, required: [ok]}}}",
+        ))
+        .expect("schema format");
+        assert_eq!(format["json_schema"]["name"], "response");
+        assert_eq!(
+            responses_text_format(&format)["format"]["schema"]["required"],
+            json!(["ok"])
+        );
+
+        assert!(validate_structured_answer(&format, " {\"ok\": true} ").is_ok());
+        assert!(validate_structured_answer(&format, "{\"done\": true}").is_err());
+        assert!(validate_structured_answer(&format, "ok").is_err());
+    }
+
+    #[test]
+    fn response_format_is_sent_only_when_the_client_opts_in() {
+        let config = LlmModelConfig {
+            model: Some("test-model".to_string()),
+            ..config_with("json_object")
+        };
+        let messages = [super::super::ChatMessage {
+            role: "user".to_string(),
+            content: Value::String("hello".to_string()),
+            reasoning_content: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }];
+        let client = super::super::LlmClient::new(reqwest::Client::new(), config.clone());
+        let payload = client.build_request_payload(&messages, false);
+        assert!(payload.get("response_format").is_none());
+
+        let client = client.with_response_format(resolve_response_format(&config));
+        let payload = client.build_request_payload(&messages, false);
+        assert_eq!(payload["response_format"], json!({ "type": "json_object" }));
+    }
+
+    #[test]
+    fn json_mode_instruction_extends_or_inserts_system_prompt() {
+        let mut messages = vec![
+            json!({ "role": "system", "content": "You are helpful." }),
+            json!({ "role": "user", "content": "hi" }),
+        ];
+        add_json_mode_instruction(&mut messages);
+        assert_eq!(messages.len(), 2);
+        let system = messages[0]["content"].as_str().unwrap_or_default();
+        assert!(system.starts_with("You are helpful.") && system.contains("JSON"));
+
+        let mut messages = vec![json!({ "role": "user", "content": "hi" })];
+        add_json_mode_instruction(&mut messages);
+        assert_eq!(messages[0]["role"], "system");
+        assert!(messages[0]["content"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("JSON"));
+    }
+}
@@ -0,0 +1,35 @@
+// 流式中断：上游在输出中途断开时携带已收到的部分回答，交由编排层决定重试或以未完成状态收尾。
+use super::LlmResponse;
+
+/// Transport failure after the stream had already produced answer text.
+///
+/// `partial` keeps the text and reasoning received so far. Tool calls are dropped because
+/// half-streamed arguments cannot be executed safely.
+#[derive(Debug)]
+pub struct LlmStreamInterrupted {
+    pub partial: LlmResponse,
+    source: reqwest::Error,
+}
+
+impl LlmStreamInterrupted {
+    pub(super) fn new(partial: LlmResponse, source: reqwest::Error) -> Self {
+        Self { partial, source }
+    }
+}
+
+impl std::fmt::Display for LlmStreamInterrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "LLM stream interrupted after {} chars: {}",
+            self.partial.content.chars().count(),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for LlmStreamInterrupted {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
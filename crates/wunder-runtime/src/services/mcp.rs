@@ -1,3 +1,35 @@
+use std::fmt;
+
+/// Failure reaching an MCP server: connection, handshake, lost transport, timeout or a 5xx.
+/// Disabled servers, allow-list misses and JSON-RPC errors answered by a live server are not
+/// wrapped, so they do not count against the server's circuit breaker.
+#[derive(Debug)]
+pub struct McpUpstreamError(anyhow::Error);
+
+impl McpUpstreamError {
+    pub fn wrap(err: impl Into<anyhow::Error>) -> anyhow::Error {
+        anyhow::Error::new(Self(err.into()))
+    }
+}
+
+impl fmt::Display for McpUpstreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.0)
+    }
+}
+
+impl std::error::Error for McpUpstreamError {}
+
+/// Whether `err` from an MCP tool call means the server itself is unreachable or failing.
+pub fn is_upstream_failure(err: &anyhow::Error) -> bool {
+    err.chain().any(|source| {
+        source.is::<McpUpstreamError>()
+            || source
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|err| err.status().is_none_or(|status| status.is_server_error()))
+    })
+}
+
 #[cfg(feature = "mcp")]
 mod enabled {
     pub use super::super::mcp_impl::*;
@@ -95,11 +127,25 @@ mod disabled {
 #[cfg(not(feature = "mcp"))]
 pub use disabled::*;
 
-#[cfg(all(test, not(feature = "mcp")))]
+#[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(not(feature = "mcp"))]
     use crate::config::{McpServerConfig, McpToolSpec};
+    use anyhow::anyhow;
+
+    #[test]
+    fn only_wrapped_transport_errors_are_upstream_failures() {
+        assert!(is_upstream_failure(&McpUpstreamError::wrap(anyhow!(
+            "connection closed"
+        ))));
+        let wrapped = McpUpstreamError::wrap(anyhow!("handshake").context("connect"));
+        assert_eq!(wrapped.to_string(), "connect: handshake");
+        assert!(is_upstream_failure(&wrapped.context("call tool")));
+        assert!(!is_upstream_failure(&anyhow!("MCP tool is not allowed")));
+    }
 
+    #[cfg(not(feature = "mcp"))]
     #[test]
     fn disabled_stub_keeps_static_tool_specs_available() {
         let server = McpServerConfig {
@@ -116,6 +162,7 @@ mod tests {
         assert_eq!(specs[0].name, "tool");
     }
 
+    #[cfg(not(feature = "mcp"))]
     #[test]
     fn disabled_stub_normalizes_transport_aliases() {
         assert_eq!(normalize_transport(None), "streamable-http");
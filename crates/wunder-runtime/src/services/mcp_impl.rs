@@ -4,6 +4,7 @@ use crate::config::{Config, McpServerConfig};
 use crate::core::long_task;
 use crate::i18n;
 use crate::schemas::{ToolSpec, WunderRequest};
+use crate::services::mcp::McpUpstreamError;
 use crate::state::AppState;
 use crate::tools::{
     browser_tools_available, builtin_aliases, is_browser_tool_name, resolve_tool_name,
@@ -226,6 +227,9 @@ impl ServerHandler for WunderMcpServer {
                 agent_prompt: None,
                 preview_skill: false,
                 attachments: None,
+                max_rounds: None,
+                cite: false,
+                remember_attachments: false,
                 allow_queue: true,
                 is_admin: false,
                 enforce_runtime_queue: false,
@@ -611,10 +615,20 @@ pub async fn call_tool_with_server(
         return call_tool_sse(config, server, tool_name, args).await;
     }
     let transport = build_transport(config, server)?;
-    let service = serve_client(NoopClientHandler, transport).await?;
+    let service = serve_client(NoopClientHandler, transport)
+        .await
+        .map_err(McpUpstreamError::wrap)?;
     let result = service
         .call_tool(build_call_tool_request_params(tool_name, args))
-        .await?;
+        .await
+        .map_err(|err| {
+            // A JSON-RPC error is an answer from a working server; everything else is transport.
+            if matches!(err, rmcp::service::ServiceError::McpError(_)) {
+                anyhow::Error::from(err)
+            } else {
+                McpUpstreamError::wrap(err)
+            }
+        })?;
     Ok(serialize_tool_result(result))
 }
 
@@ -721,7 +735,10 @@ impl SseClientSession {
         let status = response.status();
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
-            return Err(anyhow!("MCP SSE 连接失败: {status} {body}"));
+            return Err(sse_status_error(
+                anyhow!("MCP SSE 连接失败: {status} {body}"),
+                status,
+            ));
         }
 
         let base_url = url::Url::parse(&server.endpoint)?;
@@ -851,7 +868,10 @@ impl SseClientSession {
         let status = response.status();
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
-            return Err(anyhow!("MCP SSE 消息发送失败: {status} {body}"));
+            return Err(sse_status_error(
+                anyhow!("MCP SSE 消息发送失败: {status} {body}"),
+                status,
+            ));
         }
         Ok(())
     }
@@ -863,17 +883,19 @@ impl SseClientSession {
                 Some(deadline) => {
                     let remaining = deadline.saturating_duration_since(Instant::now());
                     if remaining.is_zero() {
-                        return Err(anyhow!("MCP SSE 等待响应超时"));
+                        return Err(McpUpstreamError::wrap(anyhow!("MCP SSE 等待响应超时")));
                     }
                     match tokio::time::timeout(remaining, self.receiver.recv()).await {
                         Ok(value) => value,
-                        Err(_) => return Err(anyhow!("MCP SSE 等待响应超时")),
+                        Err(_) => {
+                            return Err(McpUpstreamError::wrap(anyhow!("MCP SSE 等待响应超时")))
+                        }
                     }
                 }
                 None => self.receiver.recv().await,
             };
             let Some(message) = next else {
-                return Err(anyhow!("MCP SSE 连接已关闭"));
+                return Err(McpUpstreamError::wrap(anyhow!("MCP SSE 连接已关闭")));
             };
 
             match message {
@@ -930,12 +952,21 @@ async fn wait_for_endpoint(
     let result = match timeout {
         Some(timeout) => tokio::time::timeout(timeout, receiver)
             .await
-            .map_err(|_| anyhow!("MCP SSE 获取 endpoint 超时"))??,
+            .map_err(|_| McpUpstreamError::wrap(anyhow!("MCP SSE 获取 endpoint 超时")))??,
         None => receiver.await?,
     };
     result
 }
 
+/// A non-success SSE response; only 5xx counts as an upstream failure.
+fn sse_status_error(error: anyhow::Error, status: reqwest::StatusCode) -> anyhow::Error {
+    if status.is_server_error() {
+        McpUpstreamError::wrap(error)
+    } else {
+        error
+    }
+}
+
 fn resolve_sse_endpoint(base_url: &url::Url, raw: &str) -> Result<url::Url> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
@@ -0,0 +1,181 @@
+// 离线 mock 模型：provider 为 mock 时按 mock_script 逐轮返回固定回复与工具调用，无需联网。
+use crate::config::{LlmModelConfig, MockLlmStep};
+use crate::services::virtual_llm::VirtualReplayTurn;
+use serde_json::{json, Value};
+
+pub const MOCK_PROVIDER: &str = "mock";
+pub const MOCK_SCRIPT_FORMAT: &str = "mock_script";
+const MOCK_REPLY_PREFIX: &str = "[mock]";
+
+pub fn is_mock_provider(value: Option<&str>) -> bool {
+    crate::services::llm::normalize_provider(value) == MOCK_PROVIDER
+}
+
+pub fn is_mock_turn(turn: &VirtualReplayTurn) -> bool {
+    turn.format == MOCK_SCRIPT_FORMAT
+}
+
+/// Reply for one model round. Step `n` of `mock_script` answers model round `n + 1` of every
+/// user turn; rounds past the script (or outside a turn) get the canned echo reply.
+pub fn turn_for_round(
+    model: &LlmModelConfig,
+    messages: &[Value],
+    user_round: Option<i64>,
+    model_round: Option<i64>,
+) -> VirtualReplayTurn {
+    let user_round = user_round.unwrap_or(1).max(1) as usize;
+    let model_round = model_round
+        .filter(|value| *value > 0)
+        .map(|value| value as usize);
+    let step = model_round.and_then(|round| model.mock_script.get(round - 1));
+    let (content, reasoning, tool_calls) = match step {
+        Some(step) => (
+            step.content.clone(),
+            step.reasoning.clone(),
+            build_tool_calls(step, user_round, model_round.unwrap_or(1)),
+        ),
+        None => (canned_reply(messages), String::new(), None),
+    };
+    VirtualReplayTurn {
+        content,
+        reasoning,
+        usage: None,
+        tool_calls,
+        source_log_id: MOCK_PROVIDER.to_string(),
+        source_log_name: model
+            .model
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(MOCK_PROVIDER)
+            .to_string(),
+        source_round: user_round,
+        source_model_round: model_round,
+        format: MOCK_SCRIPT_FORMAT.to_string(),
+    }
+}
+
+fn build_tool_calls(step: &MockLlmStep, user_round: usize, model_round: usize) -> Option<Value> {
+    if step.tool_calls.is_empty() {
+        return None;
+    }
+    let calls = step
+        .tool_calls
+        .iter()
+        .enumerate()
+        .map(|(index, call)| {
+            let arguments = match serde_json::to_value(&call.arguments).unwrap_or(Value::Null) {
+                Value::Null => "{}".to_string(),
+                Value::String(text) => text,
+                other => other.to_string(),
+            };
+            json!({
+                "id": format!("call_mock_{user_round}_{model_round}_{index}"),
+                "type": "function",
+                "function": { "name": call.name, "arguments": arguments },
+            })
+        })
+        .collect::<Vec<_>>();
+    Some(Value::Array(calls))
+}
+
+fn canned_reply(messages: &[Value]) -> String {
+    let question = messages
+        .iter()
+        .rev()
+        .find(|message| message.get("role").and_then(Value::as_str) == Some("user"))
+        .map(|message| message_text(message.get("content").unwrap_or(&Value::Null)))
+        .unwrap_or_default();
+    let question = question.trim();
+    if question.is_empty() {
+        MOCK_REPLY_PREFIX.to_string()
+    } else {
+        format!("{MOCK_REPLY_PREFIX} {question}")
+    }
+}
+
+fn message_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MockToolCall;
+
+    fn scripted_model() -> LlmModelConfig {
+        LlmModelConfig {
+            provider: Some("mock".to_string()),
+            model: Some("demo".to_string()),
+            mock_script: vec![
+                MockLlmStep {
+                    tool_calls: vec![MockToolCall {
+                        name: "list_files".to_string(),
+                        arguments: serde_yaml::from_str("path: .").expect("yaml arguments"),
+                    }],
+                    ..MockLlmStep::default()
+                },
+                MockLlmStep {
+                    content: "done".to_string(),
+                    ..MockLlmStep::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scripted_tool_call_is_followed_by_final_answer() {
+        let model = scripted_model();
+        let messages = vec![json!({ "role": "user", "content": "list the workspace" })];
+
+        let first = turn_for_round(&model, &messages, Some(1), Some(1));
+        assert!(first.content.is_empty());
+        assert_eq!(
+            first.tool_calls,
+            Some(json!([{
+                "id": "call_mock_1_1_0",
+                "type": "function",
+                "function": { "name": "list_files", "arguments": "{\"path\":\".\"}" },
+            }]))
+        );
+
+        let second = turn_for_round(&model, &messages, Some(1), Some(2));
+        assert_eq!(second.content, "done");
+        assert!(second.tool_calls.is_none());
+        assert!(is_mock_turn(&second));
+    }
+
+    #[test]
+    fn unscripted_rounds_echo_the_last_user_message() {
+        let model = scripted_model();
+        let messages = vec![
+            json!({ "role": "user", "content": "first" }),
+            json!({ "role": "assistant", "content": "ok" }),
+            json!({ "role": "user", "content": [{ "type": "text", "text": "second" }] }),
+        ];
+        let turn = turn_for_round(&model, &messages, Some(2), Some(3));
+        assert_eq!(turn.content, "[mock] second");
+        assert!(turn.tool_calls.is_none());
+        assert_eq!(
+            turn_for_round(&model, &messages, Some(2), None).content,
+            "[mock] second"
+        );
+    }
+
+    #[test]
+    fn provider_aliases_resolve_to_mock() {
+        assert!(is_mock_provider(Some("mock")));
+        assert!(is_mock_provider(Some(" Mock-LLM ")));
+        assert!(!is_mock_provider(Some("mock_replay")));
+        assert!(!is_mock_provider(None));
+    }
+}
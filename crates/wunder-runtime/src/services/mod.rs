@@ -1,10 +1,16 @@
+pub mod a2a_agent_card;
 pub mod a2a_store;
 pub mod abilities;
 pub mod admin_skills;
 pub mod agent_abilities;
+pub mod agent_definitions;
 pub mod agent_runtime_projection;
 pub mod archive_extract;
 pub mod attachment;
+pub mod attachment_knowledge;
+pub mod attachment_metadata;
+pub mod attachment_ocr;
+pub mod attachment_upload;
 pub mod auth_sessions;
 pub mod beeroom_cleanup;
 pub mod beeroom_demo;
@@ -14,6 +20,7 @@ pub mod bridge;
 pub mod browser;
 pub mod chat_attachments;
 pub mod chat_cancel_marker;
+pub mod chat_history_edit;
 pub mod chat_media;
 pub mod chat_payload_sanitizer;
 pub mod chat_transcript;
@@ -31,6 +38,7 @@ pub mod external;
 pub mod external_auth;
 pub mod goal;
 pub mod history;
+pub mod history_export;
 pub mod hive_pack;
 pub mod inner_visible;
 pub mod knowledge;
@@ -42,6 +50,7 @@ pub mod memory;
 pub mod memory_agent_settings;
 pub mod memory_auto_extract;
 pub mod memory_fragments;
+pub mod mock_llm;
 pub mod multimodal_models;
 pub mod onlyoffice;
 pub mod orchestration_context;
@@ -50,9 +59,13 @@ pub mod org_units;
 pub mod output_quality;
 pub mod presence;
 pub mod preset_worker_cards;
+pub mod prompt_budget;
 pub mod prompting;
 pub mod ragflow_knowledge;
 pub mod runtime;
+pub mod sandbox_reset;
+pub mod sandbox_session;
+pub mod session_title;
 pub mod sim_lab;
 pub mod skill_archive;
 pub mod skills;
@@ -60,6 +73,8 @@ pub mod stream_events;
 pub mod subagents;
 pub mod swarm;
 pub mod tools;
+pub mod turn_notifier;
+pub mod upstream_breaker;
 pub mod user_access;
 pub mod user_agent_presets;
 pub mod user_leveling;
@@ -77,3 +92,4 @@ pub mod worker_card_protocol;
 pub mod worker_card_settings;
 pub mod workspace;
 pub mod workspace_file_templates;
+pub mod workspace_ignore;
@@ -0,0 +1,132 @@
+// 系统提示词预算：按段（角色、工程、工具、技能、记忆、附加提示词）统计 token 占用，仅在显式查询时计算。
+use crate::prompting::{join_prompt_sections, PromptSection};
+use crate::token_utils::TokenCounter;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptSectionBudget {
+    pub section: String,
+    pub tokens: i64,
+    pub chars: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptBudgetBreakdown {
+    pub model: Option<String>,
+    /// Token count of the joined prompt. Sections are counted on their own, so their sum
+    /// differs from this by the separators and tokenizer boundary effects.
+    pub total_tokens: i64,
+    pub total_chars: usize,
+    pub sections: Vec<PromptSectionBudget>,
+}
+
+impl PromptBudgetBreakdown {
+    pub fn section_tokens(&self, section: &str) -> i64 {
+        self.sections
+            .iter()
+            .filter(|item| item.section == section)
+            .map(|item| item.tokens)
+            .sum()
+    }
+
+    /// Report `text`, which is part of the `parent` section, as its own `section` right after
+    /// the parent, e.g. the CLI personality instruction inside the extra prompt.
+    pub fn split_section(&mut self, parent: &str, section: &str, text: &str, model: Option<&str>) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        let Some(index) = self.sections.iter().position(|item| item.section == parent) else {
+            return;
+        };
+        let counter = TokenCounter::for_model(model.unwrap_or(""));
+        let tokens = counter.count(text).min(self.sections[index].tokens);
+        let chars = text.chars().count().min(self.sections[index].chars);
+        let parent = &mut self.sections[index];
+        parent.tokens -= tokens;
+        parent.chars -= chars;
+        self.sections.insert(
+            index + 1,
+            PromptSectionBudget {
+                section: section.to_string(),
+                tokens,
+                chars,
+            },
+        );
+    }
+}
+
+/// Count each prompt section with the tokenizer estimate of `model`.
+pub fn breakdown_prompt_sections(
+    sections: &[(PromptSection, String)],
+    model: Option<&str>,
+) -> PromptBudgetBreakdown {
+    let counter = TokenCounter::for_model(model.unwrap_or(""));
+    let prompt = join_prompt_sections(sections);
+    PromptBudgetBreakdown {
+        model: model.map(str::to_string),
+        total_tokens: counter.count(&prompt),
+        total_chars: prompt.chars().count(),
+        sections: sections
+            .iter()
+            .map(|(section, text)| PromptSectionBudget {
+                section: section.as_str().to_string(),
+                tokens: counter.count(text),
+                chars: text.chars().count(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_tokens_sum_to_the_whole_prompt() {
+        let sections = vec![
+            (
+                PromptSection::Role,
+                "You are Wunder, a careful coding agent.".repeat(20),
+            ),
+            (
+                PromptSection::Tools,
+                "## read_file\n{\"path\": \"string\"}\n".repeat(40),
+            ),
+            (
+                PromptSection::Skills,
+                "- 技能：整理周报，读取 SKILL.md 后执行。\n".repeat(30),
+            ),
+            (
+                PromptSection::Memory,
+                "<<WUNDER_HISTORY_MEMORY>>".to_string(),
+            ),
+            (
+                PromptSection::Extra,
+                "Answer concisely.\n\nPrefer bullet lists.".to_string(),
+            ),
+        ];
+        for model in [None, Some("gpt-4o"), Some("qwen3-32b")] {
+            let mut breakdown = breakdown_prompt_sections(&sections, model);
+            let sum = breakdown
+                .sections
+                .iter()
+                .map(|item| item.tokens)
+                .sum::<i64>();
+            let tolerance = (breakdown.total_tokens / 50).max(sections.len() as i64 * 2);
+            assert!(
+                (sum - breakdown.total_tokens).abs() <= tolerance,
+                "{model:?}: sections {sum} vs total {}",
+                breakdown.total_tokens
+            );
+
+            let extra = breakdown.section_tokens("extra");
+            breakdown.split_section("extra", "personality", "Prefer bullet lists.", model);
+            assert_eq!(
+                breakdown.section_tokens("extra") + breakdown.section_tokens("personality"),
+                extra
+            );
+            assert!(breakdown.section_tokens("personality") > 0);
+        }
+    }
+}
@@ -19,6 +19,7 @@ use crate::user_tools::UserToolBindings;
 use crate::workspace::WorkspaceManager;
 use chrono::Local;
 use parking_lot::Mutex;
+use serde::Serialize;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
@@ -37,6 +38,35 @@ const SYSTEM_PROMPT_MEMORY_PATH: &str = "prompts/system/memory.txt";
 const SYSTEM_PROMPT_EXTRA_PATH: &str = "prompts/system/extra.txt";
 pub const SYSTEM_PROMPT_MEMORY_PLACEHOLDER: &str = "<<WUNDER_HISTORY_MEMORY>>";
 
+/// Top-level blocks of the system prompt, in the order they are joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptSection {
+    Role,
+    Engineering,
+    InnerVisible,
+    Tools,
+    Skills,
+    Memory,
+    Language,
+    Extra,
+}
+
+impl PromptSection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Role => "role",
+            Self::Engineering => "engineering",
+            Self::InnerVisible => "inner_visible",
+            Self::Tools => "tools",
+            Self::Skills => "skills",
+            Self::Memory => "memory",
+            Self::Language => "language",
+            Self::Extra => "extra",
+        }
+    }
+}
+
 static SYSTEM_PROMPT_TEMPLATES_REVISION: AtomicU64 = AtomicU64::new(0);
 
 /// Bump the in-memory revision used by the system prompt builder cache.
@@ -240,16 +270,103 @@ impl PromptComposer {
             let tree_snapshot = workspace.get_workspace_tree_snapshot(workspace_id);
             let workspace_version = tree_snapshot.version;
             let cache_key = format!("{base_key}|{workspace_version}");
-            let workspace_tree = tree_snapshot.tree;
-            let include_ptc = allowed_tool_names
-                .iter()
-                .any(|name| resolve_tool_name(name) == "ptc");
-            let tool_specs = if tool_call_mode != ToolCallMode::FunctionCall
-                && !allowed_tool_names.is_empty()
-            {
+            let sections = self.compose_system_prompt_sections(
+                config,
+                config_version,
+                workspace,
+                workspace_id,
+                prompt_owner_user_id,
+                current_agent_id,
+                workdir,
+                allowed_tool_names,
+                tool_call_mode,
+                skills,
+                user_tool_bindings,
+                agent_prompt,
+                preview_skill,
+                &tree_snapshot.tree,
+            );
+            let prompt = join_prompt_sections(&sections);
+
+            self.insert_cached_prompt(cache_key, prompt.clone(), now_ts());
+            self.notify_inflight(&base_key).await;
+            return prompt;
+        }
+    }
+
+    /// Render the system prompt as labelled sections without touching the cache, for callers
+    /// that need the parts (for example a token breakdown) rather than the joined text.
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_system_prompt_sections(
+        &self,
+        config: &Config,
+        config_version: u64,
+        workspace: &WorkspaceManager,
+        workspace_id: &str,
+        prompt_owner_user_id: &str,
+        current_agent_id: Option<&str>,
+        workdir: &Path,
+        allowed_tool_names: &HashSet<String>,
+        tool_call_mode: ToolCallMode,
+        skills: &SkillRegistry,
+        user_tool_bindings: Option<&UserToolBindings>,
+        agent_prompt: Option<&str>,
+        preview_skill: bool,
+    ) -> Vec<(PromptSection, String)> {
+        let workspace_tree = workspace.get_workspace_tree_snapshot(workspace_id).tree;
+        self.compose_system_prompt_sections(
+            config,
+            config_version,
+            workspace,
+            workspace_id,
+            prompt_owner_user_id,
+            current_agent_id,
+            workdir,
+            allowed_tool_names,
+            tool_call_mode,
+            skills,
+            user_tool_bindings,
+            agent_prompt,
+            preview_skill,
+            &workspace_tree,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn compose_system_prompt_sections(
+        &self,
+        config: &Config,
+        config_version: u64,
+        workspace: &WorkspaceManager,
+        workspace_id: &str,
+        prompt_owner_user_id: &str,
+        current_agent_id: Option<&str>,
+        workdir: &Path,
+        allowed_tool_names: &HashSet<String>,
+        tool_call_mode: ToolCallMode,
+        skills: &SkillRegistry,
+        user_tool_bindings: Option<&UserToolBindings>,
+        agent_prompt: Option<&str>,
+        preview_skill: bool,
+        workspace_tree: &str,
+    ) -> Vec<(PromptSection, String)> {
+        let tool_key = build_tool_key(allowed_tool_names);
+        let language = i18n::get_language();
+        let user_tool_version = user_tool_bindings
+            .map(|item| item.user_version)
+            .unwrap_or(0.0);
+        let shared_tool_version = user_tool_bindings
+            .map(|item| item.shared_version)
+            .unwrap_or(0.0);
+        let prompt_template_scope = resolve_prompt_template_scope(config, prompt_owner_user_id);
+        let include_ptc = allowed_tool_names
+            .iter()
+            .any(|name| resolve_tool_name(name) == "ptc");
+        let tool_specs =
+            if tool_call_mode != ToolCallMode::FunctionCall && !allowed_tool_names.is_empty() {
                 let tool_cache_key = format!(
-                    "{config_version}|{user_tool_version}|{shared_tool_version}|{language}|{tool_key}"
-                );
+                "{config_version}|{user_tool_version}|{shared_tool_version}|{language}|{tool_key}"
+            );
                 let now = now_ts();
                 if let Some(specs) = self.get_cached_tool_specs(&tool_cache_key, now) {
                     specs
@@ -266,54 +383,48 @@ impl PromptComposer {
             } else {
                 Vec::new()
             };
-            let tool_specs = if is_local_runtime_mode(&config.server.mode) {
-                localize_tool_specs_for_local_runtime(tool_specs, workspace, workspace_id)
-            } else {
-                tool_specs
-            };
-            let workdir_display = if is_local_runtime_mode(&config.server.mode) {
-                absolute_path_str(workdir)
-            } else {
-                workspace.display_path(workspace_id, workdir)
-            };
-            let base_skill_specs = skills.list_specs();
-            let builtin_skills_for_prompt =
-                filter_skill_specs(&base_skill_specs, allowed_tool_names);
-            let mut user_skills_for_prompt = Vec::new();
-            if let Some(bindings) = user_tool_bindings {
-                if !bindings.skill_specs.is_empty() {
-                    let user_skills = filter_skill_specs(&bindings.skill_specs, allowed_tool_names);
-                    if !user_skills.is_empty() {
-                        user_skills_for_prompt = user_skills;
-                    }
+        let tool_specs = if is_local_runtime_mode(&config.server.mode) {
+            localize_tool_specs_for_local_runtime(tool_specs, workspace, workspace_id)
+        } else {
+            tool_specs
+        };
+        let workdir_display = if is_local_runtime_mode(&config.server.mode) {
+            absolute_path_str(workdir)
+        } else {
+            workspace.display_path(workspace_id, workdir)
+        };
+        let base_skill_specs = skills.list_specs();
+        let builtin_skills_for_prompt = filter_skill_specs(&base_skill_specs, allowed_tool_names);
+        let mut user_skills_for_prompt = Vec::new();
+        if let Some(bindings) = user_tool_bindings {
+            if !bindings.skill_specs.is_empty() {
+                let user_skills = filter_skill_specs(&bindings.skill_specs, allowed_tool_names);
+                if !user_skills.is_empty() {
+                    user_skills_for_prompt = user_skills;
                 }
             }
-
-            let prompt = build_system_prompt_skeleton(
-                config,
-                &prompt_template_scope,
-                allowed_tool_names,
-                tool_call_mode,
-                &tool_specs,
-                include_ptc,
-                &workdir_display,
-                &workspace_tree,
-                &builtin_skills_for_prompt,
-                &user_skills_for_prompt,
-                agent_prompt,
-                preview_skill,
-                &build_inner_visible_prompt_mapping(
-                    workspace,
-                    prompt_owner_user_id,
-                    current_agent_id,
-                    is_local_runtime_mode(&config.server.mode),
-                ),
-            );
-
-            self.insert_cached_prompt(cache_key, prompt.clone(), now_ts());
-            self.notify_inflight(&base_key).await;
-            return prompt;
         }
+
+        build_system_prompt_blocks(
+            config,
+            &prompt_template_scope,
+            allowed_tool_names,
+            tool_call_mode,
+            &tool_specs,
+            include_ptc,
+            &workdir_display,
+            workspace_tree,
+            &builtin_skills_for_prompt,
+            &user_skills_for_prompt,
+            agent_prompt,
+            preview_skill,
+            &build_inner_visible_prompt_mapping(
+                workspace,
+                prompt_owner_user_id,
+                current_agent_id,
+                is_local_runtime_mode(&config.server.mode),
+            ),
+        )
     }
 
     async fn notify_inflight(&self, key: &str) {
@@ -531,7 +642,7 @@ fn read_prompt_template_from_scope(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn build_system_prompt_skeleton(
+fn build_system_prompt_blocks(
     config: &Config,
     template_scope: &PromptTemplateScope,
     allowed_tool_names: &HashSet<String>,
@@ -545,7 +656,7 @@ fn build_system_prompt_skeleton(
     agent_prompt: Option<&str>,
     preview_skill: bool,
     inner_visible_mapping: &HashMap<String, String>,
-) -> String {
+) -> Vec<(PromptSection, String)> {
     let os_name = system_name();
     let date_str = Local::now().format("%Y-%m-%d").to_string();
 
@@ -686,20 +797,24 @@ fn build_system_prompt_skeleton(
     );
 
     let mut blocks = vec![
-        role,
-        engineering,
-        inner_visible_block,
-        tools_block,
-        skills_block,
-        memory_block,
+        (PromptSection::Role, role),
+        (PromptSection::Engineering, engineering),
+        (PromptSection::InnerVisible, inner_visible_block),
+        (PromptSection::Tools, tools_block),
+        (PromptSection::Skills, skills_block),
+        (PromptSection::Memory, memory_block),
     ];
-    blocks.retain(|value| !value.trim().is_empty());
+    blocks.retain(|(_, value)| !value.trim().is_empty());
     if !blocks
         .iter()
-        .any(|value| value.contains(SYSTEM_PROMPT_MEMORY_PLACEHOLDER))
+        .any(|(_, value)| value.contains(SYSTEM_PROMPT_MEMORY_PLACEHOLDER))
     {
-        blocks.push(SYSTEM_PROMPT_MEMORY_PLACEHOLDER.to_string());
+        blocks.push((
+            PromptSection::Memory,
+            SYSTEM_PROMPT_MEMORY_PLACEHOLDER.to_string(),
+        ));
     }
+    blocks.push((PromptSection::Language, build_response_language_block()));
 
     if let Some(extra) = agent_prompt
         .map(str::trim)
@@ -715,11 +830,38 @@ fn build_system_prompt_skeleton(
             &HashMap::from([("EXTRA_PROMPT".to_string(), extra.to_string())]),
         );
         if !extra_block.trim().is_empty() {
-            blocks.push(extra_block);
+            blocks.push((PromptSection::Extra, extra_block));
         }
     }
 
-    blocks.join("\n\n")
+    blocks
+}
+
+/// Asks the model to answer in the turn language, which the orchestrator takes from the request
+/// and falls back to the caller's header-resolved language.
+fn build_response_language_block() -> String {
+    let language = i18n::get_language();
+    let lower = language.to_ascii_lowercase();
+    let name = if lower.starts_with("zh") {
+        "简体中文"
+    } else if lower.starts_with("en") {
+        "English"
+    } else {
+        language.as_str()
+    };
+    i18n::t_with_params(
+        "prompt.response_language",
+        &HashMap::from([("language".to_string(), format!("{name} ({language})"))]),
+    )
+}
+
+/// Join prompt sections into the system prompt text sent to the model.
+pub fn join_prompt_sections(sections: &[(PromptSection, String)]) -> String {
+    sections
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
 }
 
 fn build_inner_visible_prompt_mapping(
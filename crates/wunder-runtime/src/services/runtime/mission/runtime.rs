@@ -1339,6 +1339,9 @@ fn build_task_request(
         agent_prompt,
         preview_skill: agent.preview_skill,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: false,
         enforce_runtime_queue: false,
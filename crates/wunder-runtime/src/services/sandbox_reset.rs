@@ -0,0 +1,184 @@
+// 沙盒容器重置：清空指定容器编号的用户工作区；仍有会话在该容器上运行时拒绝重置，避免在执行中途删除文件。
+use crate::monitor::MonitorState;
+use crate::services::sandbox_session::load_session_container_id;
+use crate::storage::{normalize_sandbox_container_id, StorageBackend};
+use crate::user_store::build_default_agent_record_from_storage;
+use crate::workspace::WorkspaceManager;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SandboxResetReport {
+    pub container_id: i32,
+    pub workspace_id: String,
+    pub removed_entries: u64,
+}
+
+#[derive(Debug, Clone)]
+pub enum SandboxResetOutcome {
+    Reset(SandboxResetReport),
+    /// Sessions still running or holding a lock on the container; nothing was removed.
+    Busy {
+        container_id: i32,
+        session_ids: Vec<String>,
+    },
+}
+
+/// Clear the user's workspace of one sandbox container. Sandbox commands run as one-shot
+/// processes, so the working directory is the only state a container keeps between tool calls.
+pub fn reset_sandbox_container(
+    workspace: &WorkspaceManager,
+    storage: &dyn StorageBackend,
+    monitor: Option<&MonitorState>,
+    user_id: &str,
+    container_id: i32,
+) -> Result<SandboxResetOutcome> {
+    let container_id = normalize_sandbox_container_id(container_id);
+    // A container mapped to a custom directory holds user files, not disposable sandbox state.
+    if workspace.container_roots().contains_key(&container_id) {
+        return Err(anyhow!(
+            "sandbox container {container_id} is mapped to a custom directory and cannot be reset"
+        ));
+    }
+    let session_ids = active_container_sessions(storage, monitor, user_id, container_id)?;
+    if !session_ids.is_empty() {
+        return Ok(SandboxResetOutcome::Busy {
+            container_id,
+            session_ids,
+        });
+    }
+    let removed_entries = workspace.clear_container_workspace(user_id, container_id)?;
+    Ok(SandboxResetOutcome::Reset(SandboxResetReport {
+        container_id,
+        workspace_id: workspace.scoped_user_id_by_container(user_id, container_id),
+        removed_entries,
+    }))
+}
+
+/// Active sessions of the user whose tool calls run in `container_id`.
+pub fn active_container_sessions(
+    storage: &dyn StorageBackend,
+    monitor: Option<&MonitorState>,
+    user_id: &str,
+    container_id: i32,
+) -> Result<Vec<String>> {
+    let cleaned_user = user_id.trim();
+    // session id -> agent id, from both session locks and the live monitor.
+    let mut active = BTreeMap::new();
+    for lock in storage.list_session_locks_by_user(cleaned_user)? {
+        active.insert(lock.session_id, lock.agent_id);
+    }
+    if let Some(monitor) = monitor {
+        for session in monitor.list_sessions(true) {
+            let text = |key: &str| {
+                session
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .unwrap_or("")
+                    .to_string()
+            };
+            let session_id = text("session_id");
+            if text("user_id") != cleaned_user || session_id.is_empty() {
+                continue;
+            }
+            active.entry(session_id).or_insert_with(|| text("agent_id"));
+        }
+    }
+    let mut output = Vec::new();
+    for (session_id, agent_id) in active {
+        if session_container_id(storage, cleaned_user, &session_id, &agent_id)? == container_id {
+            output.push(session_id);
+        }
+    }
+    Ok(output)
+}
+
+/// Mirrors workspace routing: a per-session assignment wins, otherwise the agent's container.
+pub fn session_container_id(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    session_id: &str,
+    agent_id: &str,
+) -> Result<i32> {
+    if let Some(container_id) = load_session_container_id(storage, user_id, session_id)? {
+        return Ok(container_id);
+    }
+    let agent_id = agent_id.trim();
+    let is_default = agent_id.is_empty()
+        || agent_id.eq_ignore_ascii_case("__default__")
+        || agent_id.eq_ignore_ascii_case("default");
+    let record = if is_default {
+        Some(build_default_agent_record_from_storage(storage, user_id)?)
+    } else {
+        storage.get_user_agent_by_id(agent_id)?
+    };
+    Ok(normalize_sandbox_container_id(
+        record
+            .map(|record| record.sandbox_container_id)
+            .unwrap_or(crate::storage::DEFAULT_SANDBOX_CONTAINER_ID),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sandbox_session::assign_session_container_id;
+    use crate::storage::SqliteStorage;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn reset_clears_only_the_idle_container() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let storage: Arc<dyn StorageBackend> = Arc::new(SqliteStorage::new(
+            dir.path()
+                .join("sandbox-reset.db")
+                .to_string_lossy()
+                .to_string(),
+        ));
+        storage.ensure_initialized().expect("init storage");
+        let workspace = WorkspaceManager::new(
+            &dir.path().join("workspaces").to_string_lossy(),
+            storage.clone(),
+            0,
+            &HashMap::new(),
+        );
+        for container_id in [1, 2] {
+            let workspace_id = workspace.scoped_user_id_by_container("u1", container_id);
+            workspace.ensure_user_root(&workspace_id).expect("root");
+            workspace
+                .write_file(&workspace_id, "notes/state.txt", "kept", true)
+                .expect("write");
+        }
+
+        // The first session-assigned slot is container 1; a running session there blocks reset.
+        assert_eq!(
+            assign_session_container_id(storage.as_ref(), "u1", "s1").expect("assign"),
+            1
+        );
+        storage
+            .try_acquire_session_lock("s1", "u1", "agent_x", 60.0, 1)
+            .expect("lock");
+        match reset_sandbox_container(&workspace, storage.as_ref(), None, "u1", 1).expect("busy") {
+            SandboxResetOutcome::Busy { session_ids, .. } => assert_eq!(session_ids, vec!["s1"]),
+            SandboxResetOutcome::Reset(_) => panic!("reset an active container"),
+        }
+        storage.release_session_lock("s1").expect("unlock");
+
+        let SandboxResetOutcome::Reset(report) =
+            reset_sandbox_container(&workspace, storage.as_ref(), None, "u1", 1).expect("reset")
+        else {
+            panic!("idle container reported busy");
+        };
+        assert_eq!(report.container_id, 1);
+        assert!(report.removed_entries > 0);
+        let root = |container_id| {
+            workspace.workspace_root(&workspace.scoped_user_id_by_container("u1", container_id))
+        };
+        assert!(!root(1).join("notes").exists());
+        assert!(root(2).join("notes/state.txt").exists());
+    }
+}
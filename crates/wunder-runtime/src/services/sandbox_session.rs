@@ -0,0 +1,149 @@
+// 会话级沙盒容器分配：为会话在 1-10 号容器中挑选当前负载最低者并持久化，同一会话的工具调用复用同一容器。
+use crate::storage::{
+    hex_meta_segment, StorageBackend, MAX_SANDBOX_CONTAINER_ID, MIN_SANDBOX_CONTAINER_ID,
+};
+use anyhow::Result;
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const SESSION_SANDBOX_META_PREFIX: &str = "sandbox:session_container:";
+const ASSIGN_LOCK_STRIPES: usize = 64;
+
+/// Serializes first-time assignment per user so concurrent turns agree on the slot. Users are
+/// striped over a fixed set of locks; assignments of different users rarely wait on each other.
+static ASSIGN_LOCKS: [Mutex<()>; ASSIGN_LOCK_STRIPES] =
+    [const { Mutex::new(()) }; ASSIGN_LOCK_STRIPES];
+
+fn assign_lock(user_id: &str) -> &'static Mutex<()> {
+    let mut hasher = DefaultHasher::new();
+    user_id.trim().hash(&mut hasher);
+    &ASSIGN_LOCKS[hasher.finish() as usize % ASSIGN_LOCK_STRIPES]
+}
+
+/// User and session ids are hex encoded for `LIKE` prefix queries, and the trailing `:` keeps
+/// `s1` from matching `s10` when one session is released.
+fn user_prefix(user_id: &str) -> String {
+    format!(
+        "{SESSION_SANDBOX_META_PREFIX}{}:",
+        hex_meta_segment(user_id.trim())
+    )
+}
+
+fn session_key(user_id: &str, session_id: &str) -> String {
+    format!(
+        "{}{}:",
+        user_prefix(user_id),
+        hex_meta_segment(session_id.trim())
+    )
+}
+
+fn parse_container_id(raw: &str) -> Option<i32> {
+    raw.trim()
+        .parse::<i32>()
+        .ok()
+        .filter(|value| (MIN_SANDBOX_CONTAINER_ID..=MAX_SANDBOX_CONTAINER_ID).contains(value))
+}
+
+/// Container id already assigned to the session, if any.
+pub fn load_session_container_id(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    session_id: &str,
+) -> Result<Option<i32>> {
+    if user_id.trim().is_empty() || session_id.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(storage
+        .get_meta(&session_key(user_id, session_id))?
+        .as_deref()
+        .and_then(parse_container_id))
+}
+
+/// Return the session's container id, assigning the least-loaded slot of the user on first use.
+/// Ties go to the lowest id, so new sessions fill the slots round-robin. Blocks on storage; call
+/// it through `blocking::run_db` from async code.
+pub fn assign_session_container_id(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    session_id: &str,
+) -> Result<i32> {
+    let _guard = assign_lock(user_id).lock();
+    if let Some(existing) = load_session_container_id(storage, user_id, session_id)? {
+        return Ok(existing);
+    }
+    let mut loads = [0usize; (MAX_SANDBOX_CONTAINER_ID - MIN_SANDBOX_CONTAINER_ID + 1) as usize];
+    for (_, raw) in storage.list_meta_prefix(&user_prefix(user_id))? {
+        if let Some(container_id) = parse_container_id(&raw) {
+            loads[(container_id - MIN_SANDBOX_CONTAINER_ID) as usize] += 1;
+        }
+    }
+    let slot = loads
+        .iter()
+        .enumerate()
+        .min_by_key(|(index, load)| (**load, *index))
+        .map(|(index, _)| index as i32)
+        .unwrap_or(0);
+    let container_id = MIN_SANDBOX_CONTAINER_ID + slot;
+    storage.set_meta(&session_key(user_id, session_id), &container_id.to_string())?;
+    Ok(container_id)
+}
+
+/// Free the session's slot so it no longer counts towards the load of its container.
+pub fn release_session_container_id(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    session_id: &str,
+) -> Result<usize> {
+    storage.delete_meta_prefix(&session_key(user_id, session_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SqliteStorage;
+
+    #[test]
+    fn sessions_keep_their_container_and_spread_across_slots() {
+        let db_path = std::env::temp_dir().join(format!(
+            "wunder_sandbox_session_{}.db",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let storage = SqliteStorage::new(db_path.to_string_lossy().to_string());
+
+        let first = assign_session_container_id(&storage, "u1", "s1").expect("assign s1");
+        for _ in 0..3 {
+            assert_eq!(
+                assign_session_container_id(&storage, "u1", "s1").expect("reuse s1"),
+                first
+            );
+        }
+        let second = assign_session_container_id(&storage, "u1", "s10").expect("assign s10");
+        assert_ne!(first, second);
+        assert_eq!(
+            load_session_container_id(&storage, "u1", "s10").expect("load"),
+            Some(second)
+        );
+        assert_eq!(
+            assign_session_container_id(&storage, "u_2", "s1").expect("other user"),
+            MIN_SANDBOX_CONTAINER_ID
+        );
+
+        assert_eq!(
+            release_session_container_id(&storage, "u1", "s1").expect("release"),
+            1
+        );
+        assert_eq!(
+            load_session_container_id(&storage, "u1", "s1").expect("released"),
+            None
+        );
+        assert_eq!(
+            load_session_container_id(&storage, "u1", "s10").expect("kept"),
+            Some(second)
+        );
+        assert_eq!(
+            assign_session_container_id(&storage, "u1", "s2").expect("refill"),
+            first
+        );
+    }
+}
@@ -0,0 +1,396 @@
+// 会话标题：首条消息截断作为默认标题；开启 llm.auto_title 时在首轮结束后由模型生成简短标题，失败则保留截断标题。
+use crate::config::Config;
+use crate::core::blocking;
+use crate::i18n;
+use crate::knowledge::resolve_llm_config;
+use crate::llm::{build_llm_client, is_llm_configured, ChatMessage};
+use crate::orchestrator_constants::OBSERVATION_PREFIX;
+use crate::storage::StorageBackend;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+const TRUNCATED_TITLE_CHARS: usize = 20;
+const DEFAULT_GENERATED_TITLE_CHARS: usize = 24;
+/// Question and answer are clipped before the title call; the opening is enough to name a chat.
+const TITLE_SOURCE_CHARS: usize = 1200;
+const HISTORY_TURN_CHARS: usize = 400;
+/// Short prompts such as "ok" or "continue" say nothing about the topic.
+const HEURISTIC_MIN_PROMPT_CHARS: usize = 6;
+
+/// Titles a session gets before anything better is known.
+pub fn is_placeholder_title(title: &str) -> bool {
+    let cleaned = title.trim();
+    cleaned.is_empty() || cleaned == "新会话" || cleaned == "未命名会话"
+}
+
+/// The first prompt on one line, cut to 20 characters.
+pub fn truncate_session_title(content: &str) -> Option<String> {
+    let cleaned = content.trim().replace('\n', " ");
+    if cleaned.is_empty() {
+        return None;
+    }
+    let mut output = cleaned;
+    if output.chars().count() > TRUNCATED_TITLE_CHARS {
+        output = output
+            .chars()
+            .take(TRUNCATED_TITLE_CHARS)
+            .collect::<String>();
+        output.push_str("...");
+    }
+    Some(output)
+}
+
+/// Replace an automatic title with a model-generated one. Only sessions whose title is still a
+/// placeholder or the truncated `question` are touched, so manual renames are never overwritten.
+/// Returns the new title, or `None` when the truncated title stays.
+pub async fn refine_session_title(
+    config: &Config,
+    http: reqwest::Client,
+    storage: Arc<dyn StorageBackend>,
+    user_id: &str,
+    session_id: &str,
+    question: &str,
+    answer: &str,
+) -> Option<String> {
+    if !config.llm.auto_title.enabled {
+        return None;
+    }
+    let (lookup_storage, lookup_user, lookup_session) =
+        (storage.clone(), user_id.to_string(), session_id.to_string());
+    let record = blocking::run_db("session_title.load", move || {
+        lookup_storage.get_chat_session(&lookup_user, &lookup_session)
+    })
+    .await
+    .ok()??;
+    let auto_titled = is_placeholder_title(&record.title)
+        || truncate_session_title(question).as_deref() == Some(record.title.trim());
+    if !auto_titled {
+        return None;
+    }
+    let conversation = format!(
+        "User: {}\n\nAssistant: {}",
+        clip(question, TITLE_SOURCE_CHARS),
+        clip(answer, TITLE_SOURCE_CHARS)
+    );
+    let title = generate_session_title(config, http, &conversation).await?;
+    if title == record.title.trim() {
+        return None;
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs_f64())
+        .unwrap_or(0.0);
+    let (user_id, session_id, saved) = (user_id.to_string(), session_id.to_string(), title.clone());
+    let updated = blocking::run_db("session_title.update", move || {
+        storage.update_chat_session_title(&user_id, &session_id, &saved, now)
+    })
+    .await;
+    if let Err(err) = updated {
+        warn!("update generated session title failed: {err}");
+        return None;
+    }
+    Some(title)
+}
+
+/// Title a whole session from its history, for sessions that drifted away from their first
+/// prompt. Uses the title model when one is available (regardless of `auto_title.enabled`, since
+/// the caller asked explicitly) and falls back to [`heuristic_history_title`].
+pub async fn summarize_history_title(
+    config: &Config,
+    http: reqwest::Client,
+    history: &[Value],
+) -> Option<String> {
+    let turns = history_text_turns(history);
+    if turns.is_empty() {
+        return None;
+    }
+    let digest = history_digest(&turns);
+    if let Some(title) = generate_session_title(config, http, &digest).await {
+        return Some(title);
+    }
+    heuristic_history_title(history, title_max_chars(config))
+}
+
+/// Model-free title: the most recent substantial user prompt, on one line and bounded, so a
+/// session is named after what it is about now rather than how it started.
+pub fn heuristic_history_title(history: &[Value], max_chars: usize) -> Option<String> {
+    let max_chars = max_chars.max(1);
+    let turns = history_text_turns(history);
+    let prompts = turns
+        .iter()
+        .filter(|(role, _)| *role == "user")
+        .map(|(_, text)| {
+            text.lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .unwrap_or("")
+        })
+        .collect::<Vec<_>>();
+    let prompt = prompts
+        .iter()
+        .rev()
+        .find(|line| line.chars().count() >= HEURISTIC_MIN_PROMPT_CHARS)
+        .or_else(|| prompts.iter().rev().find(|line| !line.is_empty()))?;
+    let mut title = prompt.chars().take(max_chars).collect::<String>();
+    if prompt.chars().count() > max_chars {
+        title = title.trim_end().to_string();
+        title.push_str("...");
+    }
+    Some(title)
+}
+
+/// User prompts and assistant replies as plain text; tool traffic and system prompts are skipped.
+fn history_text_turns(history: &[Value]) -> Vec<(&'static str, String)> {
+    history
+        .iter()
+        .filter_map(|item| {
+            let role = match item.get("role").and_then(Value::as_str)? {
+                "user" => "user",
+                "assistant" => "assistant",
+                _ => return None,
+            };
+            let text = content_text(item.get("content")?);
+            let text = text.trim();
+            if text.is_empty() || text.starts_with(OBSERVATION_PREFIX) {
+                return None;
+            }
+            Some((role, text.to_string()))
+        })
+        .collect()
+}
+
+/// The latest turns, newest kept, within the title source budget.
+fn history_digest(turns: &[(&'static str, String)]) -> String {
+    let mut budget = TITLE_SOURCE_CHARS * 2;
+    let mut lines = Vec::new();
+    for (role, text) in turns.iter().rev() {
+        if budget == 0 {
+            break;
+        }
+        let label = if *role == "user" { "User" } else { "Assistant" };
+        let line = clip(text, HISTORY_TURN_CHARS.min(budget));
+        budget = budget.saturating_sub(line.chars().count());
+        lines.push(format!("{label}: {line}"));
+    }
+    lines.reverse();
+    lines.join("\n\n")
+}
+
+fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn title_max_chars(config: &Config) -> usize {
+    config
+        .llm
+        .auto_title
+        .max_chars
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_GENERATED_TITLE_CHARS)
+}
+
+async fn generate_session_title(
+    config: &Config,
+    http: reqwest::Client,
+    conversation: &str,
+) -> Option<String> {
+    let llm_config = resolve_llm_config(config, config.llm.auto_title.model.as_deref())
+        .filter(is_llm_configured)?;
+    let max_chars = title_max_chars(config);
+    let prompt = i18n::t_with_params(
+        "session_title.prompt",
+        &HashMap::from([("max_chars".to_string(), max_chars.to_string())]),
+    );
+    let messages = [
+        ChatMessage {
+            role: "system".to_string(),
+            content: json!(prompt),
+            reasoning_content: None,
+            tool_calls: None,
+            tool_call_id: None,
+        },
+        ChatMessage {
+            role: "user".to_string(),
+            content: json!(conversation),
+            reasoning_content: None,
+            tool_calls: None,
+            tool_call_id: None,
+        },
+    ];
+    match build_llm_client(&llm_config, http)
+        .complete(&messages)
+        .await
+    {
+        Ok(response) => clean_generated_title(&response.content, max_chars),
+        Err(err) => {
+            warn!("session title generation failed: {err}");
+            None
+        }
+    }
+}
+
+/// First non-empty line without quotes or a `Title:` label, bounded to `max_chars`.
+fn clean_generated_title(raw: &str, max_chars: usize) -> Option<String> {
+    let line = raw.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let line = ["Title:", "title:", "标题：", "标题:"]
+        .iter()
+        .find_map(|label| line.strip_prefix(label))
+        .unwrap_or(line);
+    let quotes: &[char] = &['"', '\'', '“', '”', '「', '」', '《', '》', '`', '*', '#'];
+    let cleaned = line.trim().trim_matches(quotes).trim();
+    if cleaned.is_empty() {
+        return None;
+    }
+    Some(
+        cleaned
+            .chars()
+            .take(max_chars)
+            .collect::<String>()
+            .trim_end()
+            .to_string(),
+    )
+}
+
+fn clip(text: &str, max_chars: usize) -> String {
+    text.trim().chars().take(max_chars).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LlmModelConfig;
+    use crate::storage::{ChatSessionRecord, ChatSessionStore, SqliteStorage};
+
+    fn storage_with_session(title: &str) -> Arc<dyn StorageBackend> {
+        let db_path = std::env::temp_dir().join(format!(
+            "wunder_session_title_{}.db",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let storage = SqliteStorage::new(db_path.to_string_lossy().to_string());
+        storage
+            .upsert_chat_session(&ChatSessionRecord {
+                session_id: "sess_title".to_string(),
+                user_id: "user_title".to_string(),
+                title: title.to_string(),
+                status: "active".to_string(),
+                created_at: 1.0,
+                updated_at: 1.0,
+                last_message_at: 1.0,
+                agent_id: None,
+                tool_overrides: Vec::new(),
+                parent_session_id: None,
+                parent_message_id: None,
+                spawn_label: None,
+                spawned_by: None,
+            })
+            .expect("upsert session");
+        Arc::new(storage)
+    }
+
+    fn stored_title(storage: &Arc<dyn StorageBackend>) -> String {
+        storage
+            .get_chat_session("user_title", "sess_title")
+            .expect("load session")
+            .expect("session exists")
+            .title
+    }
+
+    #[tokio::test]
+    async fn truncated_title_stays_when_no_model_is_available() {
+        let question = "How do I rotate the signing keys for the staging cluster?";
+        let truncated = truncate_session_title(question).expect("title");
+        assert_eq!(truncated, "How do I rotate the ...");
+        let storage = storage_with_session(&truncated);
+
+        let mut config = Config::default();
+        let refined = refine_session_title(
+            &config,
+            reqwest::Client::new(),
+            storage.clone(),
+            "user_title",
+            "sess_title",
+            question,
+            "Use the key rotation job.",
+        )
+        .await;
+        assert_eq!(refined, None, "disabled by default");
+
+        config.llm.auto_title.enabled = true;
+        config.llm.default = "unreachable".to_string();
+        config.llm.models.insert(
+            "unreachable".to_string(),
+            LlmModelConfig {
+                model_type: Some("llm".to_string()),
+                ..Default::default()
+            },
+        );
+        let refined = refine_session_title(
+            &config,
+            reqwest::Client::new(),
+            storage.clone(),
+            "user_title",
+            "sess_title",
+            question,
+            "Use the key rotation job.",
+        )
+        .await;
+        assert_eq!(refined, None, "unconfigured model falls back");
+        assert_eq!(stored_title(&storage), truncated);
+    }
+
+    #[test]
+    fn generated_titles_are_cleaned_and_bounded() {
+        assert_eq!(
+            clean_generated_title("\n\"Rotate staging signing keys\"\nextra", 40),
+            Some("Rotate staging signing keys".to_string())
+        );
+        assert_eq!(
+            clean_generated_title("标题：《预发环境密钥轮换》", 40),
+            Some("预发环境密钥轮换".to_string())
+        );
+        assert_eq!(
+            clean_generated_title("Title: Rotate staging signing keys", 6),
+            Some("Rotate".to_string())
+        );
+        assert_eq!(clean_generated_title("  \n\"\"", 10), None);
+    }
+
+    #[tokio::test]
+    async fn history_title_falls_back_to_recent_prompt() {
+        let history = vec![
+            json!({ "role": "system", "content": "You are helpful." }),
+            json!({ "role": "user", "content": "Set up the staging cluster" }),
+            json!({ "role": "assistant", "content": "Done." }),
+            json!({ "role": "user", "content": format!("{OBSERVATION_PREFIX}{{\"ok\":true}}") }),
+            json!({ "role": "user", "content": [{ "type": "text", "text": "Now migrate the billing database to postgres 16 and verify replicas\nthen report" }] }),
+            json!({ "role": "user", "content": "ok" }),
+        ];
+        let title = heuristic_history_title(&history, 24).expect("title");
+        assert_eq!(title, "Now migrate the billing...");
+        assert!(title.chars().count() <= 24 + 3);
+
+        let title = summarize_history_title(&Config::default(), reqwest::Client::new(), &history)
+            .await
+            .expect("heuristic title");
+        assert!(!title.is_empty());
+        assert!(title.chars().count() <= DEFAULT_GENERATED_TITLE_CHARS + 3);
+
+        let empty = [json!({ "role": "system", "content": "You are helpful." })];
+        assert_eq!(heuristic_history_title(&empty, 24), None);
+        assert_eq!(
+            summarize_history_title(&Config::default(), reqwest::Client::new(), &empty).await,
+            None
+        );
+    }
+}
@@ -624,6 +624,9 @@ async fn run_swarm_flow(
             agent_prompt: None,
             preview_skill: false,
             attachments: None,
+            max_rounds: None,
+            cite: false,
+            remember_attachments: false,
             allow_queue: true,
             is_admin: false,
             enforce_runtime_queue: false,
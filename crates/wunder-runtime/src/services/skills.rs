@@ -2,6 +2,7 @@
 use crate::config::Config;
 use crate::core::{command_utils, python_runtime};
 use crate::i18n;
+use crate::tools::ToolError;
 use anyhow::{anyhow, Result};
 use serde_json::{json, Value};
 use serde_yaml::Value as YamlValue;
@@ -136,6 +137,16 @@ pub fn load_skills(
 
 /// 执行技能入口脚本，返回技能输出 JSON。
 pub async fn execute_skill(spec: &SkillSpec, args: &Value, timeout_s: u64) -> Result<Value> {
+    if let Err(detail) = validate_skill_inputs(&spec.input_schema, args) {
+        return Err(ToolError::InvalidArgs(i18n::t_with_params(
+            "tool.invoke.skill_inputs_invalid",
+            &HashMap::from([
+                ("name".to_string(), spec.name.clone()),
+                ("detail".to_string(), detail),
+            ]),
+        ))
+        .into());
+    }
     let entrypoint = spec.entrypoint.clone().ok_or_else(|| {
         anyhow!(i18n::t_with_params(
             "error.skill_not_executable",
@@ -398,5 +409,185 @@ fn build_input_schema(meta: &HashMap<String, YamlValue>) -> Value {
                 .unwrap_or(json!({"type": "object", "properties": {}}));
         }
     }
+    if let Some(inputs) = meta
+        .get("inputs")
+        .and_then(|value| serde_json::to_value(value).ok())
+    {
+        return build_inputs_schema(inputs);
+    }
     json!({"type": "object", "properties": {}})
 }
+
+/// Turn an `inputs` front-matter block into a JSON schema. A block that already looks like a
+/// schema is used as is; otherwise each key is an input, given either as a bare type name
+/// (`days: integer`) or as a property object that may carry `required: true`.
+fn build_inputs_schema(inputs: Value) -> Value {
+    let Value::Object(fields) = inputs else {
+        return json!({"type": "object", "properties": {}});
+    };
+    if fields.contains_key("properties") || fields.get("type") == Some(&json!("object")) {
+        return Value::Object(fields);
+    }
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for (name, field) in fields {
+        let mut property = match field {
+            Value::String(kind) => json!({ "type": kind }),
+            Value::Object(map) => Value::Object(map),
+            _ => json!({}),
+        };
+        if let Some(map) = property.as_object_mut() {
+            if map.remove("required").and_then(|flag| flag.as_bool()) == Some(true) {
+                required.push(Value::String(name.clone()));
+            }
+        }
+        properties.insert(name, property);
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Check `args` against a skill's declared input schema: required keys, top-level property
+/// types, `enum` values and `additionalProperties: false`. Skills without declared inputs
+/// accept anything, as they always have. Returns a short description of the first mismatch.
+pub fn validate_skill_inputs(schema: &Value, args: &Value) -> Result<(), String> {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+        .unwrap_or_default();
+    if properties.is_none_or(|map| map.is_empty()) && required.is_empty() {
+        return Ok(());
+    }
+    let Some(args) = args.as_object() else {
+        return Err("inputs must be a JSON object".to_string());
+    };
+    for name in required {
+        if args.get(name).is_none_or(Value::is_null) {
+            return Err(format!("missing required input `{name}`"));
+        }
+    }
+    let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+    for (name, value) in args {
+        let Some(property) = properties.and_then(|map| map.get(name)) else {
+            if closed {
+                return Err(format!("unexpected input `{name}`"));
+            }
+            continue;
+        };
+        if value.is_null() {
+            continue;
+        }
+        if let Some(kind) = property.get("type").and_then(Value::as_str) {
+            if !json_value_matches_type(value, kind) {
+                return Err(format!("input `{name}` must be of type {kind}"));
+            }
+        }
+        if let Some(allowed) = property.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                let options = allowed
+                    .iter()
+                    .map(Value::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(format!("input `{name}` must be one of {options}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn json_value_matches_type(value: &Value, kind: &str) -> bool {
+    match kind {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        // Unknown or composite types are left to the skill itself.
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_skills_parses_inputs_and_rejects_bad_invocation() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let skill_dir = root.path().join("forecast");
+        std::fs::create_dir_all(&skill_dir).expect("create skill dir");
+        std::fs::write(
+            skill_dir.join(SKILL_FILE_NAME),
+            "---\nname: forecast\ndescription: weather\ninputs:\n  city:\n    type: string\n    description: city name\n    required: true\n  days: integer\n  unit:\n    type: string\n    enum: [c, f]\n---\n",
+        )
+        .expect("write skill file");
+        let mut config = Config::default();
+        config.skills.paths = vec![root.path().to_string_lossy().to_string()];
+
+        let spec = load_skills(&config, false, false, false)
+            .get("forecast")
+            .expect("forecast skill");
+        assert_eq!(
+            spec.input_schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "city": { "type": "string", "description": "city name" },
+                    "days": { "type": "integer" },
+                    "unit": { "type": "string", "enum": ["c", "f"] },
+                },
+                "required": ["city"],
+            })
+        );
+
+        let results = [
+            json!({ "city": "Paris", "days": 3, "unit": "c" }),
+            json!({ "days": 3 }),
+            json!({ "city": "Paris", "days": "three" }),
+            json!({ "city": "Paris", "unit": "k" }),
+            json!("Paris"),
+        ]
+        .iter()
+        .map(|args| validate_skill_inputs(&spec.input_schema, args))
+        .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Err("missing required input `city`".to_string()),
+                Err("input `days` must be of type integer".to_string()),
+                Err("input `unit` must be one of \"c\", \"f\"".to_string()),
+                Err("inputs must be a JSON object".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_skill_rejects_invalid_inputs_before_running() {
+        let spec = SkillSpec {
+            name: "forecast".to_string(),
+            description: String::new(),
+            path: String::new(),
+            input_schema: build_inputs_schema(
+                json!({ "city": { "type": "string", "required": true } }),
+            ),
+            frontmatter: String::new(),
+            root: PathBuf::new(),
+            entrypoint: Some(PathBuf::from("run.py")),
+        };
+        let err = execute_skill(&spec, &json!({}), 1)
+            .await
+            .expect_err("missing input must fail");
+        assert!(matches!(
+            ToolError::from(err),
+            ToolError::InvalidArgs(message) if message.contains("city")
+        ));
+    }
+}
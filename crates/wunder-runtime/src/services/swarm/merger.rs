@@ -1,4 +1,57 @@
-﻿use serde_json::Value;
+﻿use crate::orchestrator_constants::truncate_tool_result_text;
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+
+/// How finished worker outputs are combined into one swarm result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SwarmAggregation {
+    /// Every successful output in dispatch order.
+    #[default]
+    Concat,
+    /// The answer most workers agree on.
+    Vote,
+    /// Outputs handed to the model to synthesize one answer.
+    Reduce,
+}
+
+impl SwarmAggregation {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "concat" | "join" | "all" => Some(Self::Concat),
+            "vote" | "majority" => Some(Self::Vote),
+            "reduce" | "llm" | "synthesize" | "summarize" => Some(Self::Reduce),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Concat => "concat",
+            Self::Vote => "vote",
+            Self::Reduce => "reduce",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SwarmWorkerOutput {
+    pub run_id: String,
+    pub agent_id: Option<String>,
+    pub success: bool,
+    pub text: String,
+}
+
+impl SwarmWorkerOutput {
+    fn usable(&self) -> bool {
+        self.success && !self.text.trim().is_empty()
+    }
+
+    fn source(&self) -> &str {
+        self.agent_id.as_deref().unwrap_or(&self.run_id)
+    }
+}
 
 pub fn merge_first_success(results: &[Value]) -> Option<Value> {
     results
@@ -12,3 +65,205 @@ pub fn merge_first_success(results: &[Value]) -> Option<Value> {
         .cloned()
         .or_else(|| results.first().cloned())
 }
+
+/// Combine worker outputs with `strategy`. `reduce` receives the synthesis prompt and is only
+/// called for [`SwarmAggregation::Reduce`]; when it fails or returns nothing the outputs are
+/// concatenated instead and `fallback` records that. `result` is bounded like any tool result
+/// text, with `truncated` set when it was cut.
+pub async fn aggregate_worker_outputs<F, Fut>(
+    strategy: SwarmAggregation,
+    outputs: &[SwarmWorkerOutput],
+    reduce: F,
+) -> Value
+where
+    F: FnOnce(String) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let usable = outputs.iter().filter(|output| output.usable()).count();
+    let mut payload = json!({
+        "strategy": strategy.as_str(),
+        "inputs": usable,
+    });
+    match strategy {
+        SwarmAggregation::Concat => {
+            payload["result"] = json!(concat_outputs(outputs));
+        }
+        SwarmAggregation::Vote => match vote_outputs(outputs) {
+            Some((winner, votes)) => {
+                payload["result"] = json!(winner);
+                payload["votes"] = json!(votes);
+            }
+            None => payload["result"] = json!(""),
+        },
+        SwarmAggregation::Reduce => {
+            let reduced = if usable == 0 {
+                None
+            } else {
+                match reduce(build_reduce_prompt(outputs)).await {
+                    Ok(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+                    Ok(_) => None,
+                    Err(err) => {
+                        payload["error"] = json!(err.to_string());
+                        None
+                    }
+                }
+            };
+            match reduced {
+                Some(text) => payload["result"] = json!(text),
+                None => {
+                    payload["result"] = json!(concat_outputs(outputs));
+                    payload["fallback"] = json!(SwarmAggregation::Concat.as_str());
+                }
+            }
+        }
+    }
+    if let Some(result) = payload["result"].as_str() {
+        let bounded = truncate_tool_result_text(result);
+        if bounded.len() < result.len() {
+            payload["result"] = json!(bounded);
+            payload["truncated"] = json!(true);
+        }
+    }
+    payload
+}
+
+fn concat_outputs(outputs: &[SwarmWorkerOutput]) -> String {
+    outputs
+        .iter()
+        .filter(|output| output.usable())
+        .map(|output| format!("[{}]\n{}", output.source(), output.text.trim()))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Majority answer compared case- and whitespace-insensitively; ties go to the earliest output.
+fn vote_outputs(outputs: &[SwarmWorkerOutput]) -> Option<(String, usize)> {
+    let mut tally: Vec<(String, &str, usize)> = Vec::new();
+    let mut index_by_key: HashMap<String, usize> = HashMap::new();
+    for output in outputs.iter().filter(|output| output.usable()) {
+        let key = output
+            .text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        match index_by_key.get(&key) {
+            Some(&index) => tally[index].2 += 1,
+            None => {
+                index_by_key.insert(key.clone(), tally.len());
+                tally.push((key, output.text.trim(), 1));
+            }
+        }
+    }
+    let best = tally.iter().map(|entry| entry.2).max()?;
+    tally
+        .into_iter()
+        .find(|entry| entry.2 == best)
+        .map(|(_, text, votes)| (text.to_string(), votes))
+}
+
+fn build_reduce_prompt(outputs: &[SwarmWorkerOutput]) -> String {
+    format!(
+        "The following are results from parallel workers on parts of the same task. Merge them into one consistent answer: keep every distinct finding, resolve contradictions, and drop repetition. Reply with the merged answer only.\n\n{}",
+        concat_outputs(outputs)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn outputs() -> Vec<SwarmWorkerOutput> {
+        [
+            ("run_1", "worker_a", true, "Paris"),
+            ("run_2", "worker_b", true, "  paris "),
+            ("run_3", "worker_c", true, "Lyon"),
+            ("run_4", "worker_d", false, "Marseille"),
+        ]
+        .into_iter()
+        .map(|(run_id, agent_id, success, text)| SwarmWorkerOutput {
+            run_id: run_id.to_string(),
+            agent_id: Some(agent_id.to_string()),
+            success,
+            text: text.to_string(),
+        })
+        .collect()
+    }
+
+    async fn unused_reducer(_prompt: String) -> Result<String> {
+        panic!("only reduce calls the model");
+    }
+
+    #[tokio::test]
+    async fn each_strategy_combines_worker_outputs() {
+        let outputs = outputs();
+
+        let concat =
+            aggregate_worker_outputs(SwarmAggregation::default(), &outputs, unused_reducer).await;
+        assert_eq!(concat["strategy"], "concat");
+        assert_eq!(concat["inputs"], 3);
+        assert_eq!(
+            concat["result"],
+            "[worker_a]\nParis\n\n[worker_b]\nparis\n\n[worker_c]\nLyon"
+        );
+
+        let vote = aggregate_worker_outputs(SwarmAggregation::Vote, &outputs, unused_reducer).await;
+        assert_eq!(vote["strategy"], "vote");
+        assert_eq!(vote["result"], "Paris");
+        assert_eq!(vote["votes"], 2);
+
+        let reduce =
+            aggregate_worker_outputs(SwarmAggregation::Reduce, &outputs, |prompt| async move {
+                assert!(prompt.contains("[worker_c]\nLyon"));
+                assert!(!prompt.contains("Marseille"));
+                Ok("Paris, with one worker suggesting Lyon".to_string())
+            })
+            .await;
+        assert_eq!(reduce["strategy"], "reduce");
+        assert_eq!(reduce["result"], "Paris, with one worker suggesting Lyon");
+        assert!(reduce.get("fallback").is_none());
+
+        let fallback = aggregate_worker_outputs(SwarmAggregation::Reduce, &outputs, |_| async {
+            Err(anyhow!("model unavailable"))
+        })
+        .await;
+        assert_eq!(fallback["fallback"], "concat");
+        assert_eq!(fallback["result"], concat["result"]);
+    }
+
+    #[tokio::test]
+    async fn concat_result_is_bounded_like_a_tool_result() {
+        let outputs = (0..40)
+            .map(|index| SwarmWorkerOutput {
+                run_id: format!("run_{index}"),
+                agent_id: None,
+                success: true,
+                text: "finding ".repeat(2_000),
+            })
+            .collect::<Vec<_>>();
+        let concat =
+            aggregate_worker_outputs(SwarmAggregation::Concat, &outputs, unused_reducer).await;
+        let result = concat["result"].as_str().expect("result text");
+        assert_eq!(concat["truncated"], true);
+        assert_eq!(result, truncate_tool_result_text(result));
+        assert!(result.len() < outputs.len() * outputs[0].text.len());
+    }
+
+    #[test]
+    fn strategy_names_parse() {
+        assert_eq!(
+            SwarmAggregation::parse("Majority"),
+            Some(SwarmAggregation::Vote)
+        );
+        assert_eq!(
+            SwarmAggregation::parse("llm"),
+            Some(SwarmAggregation::Reduce)
+        );
+        assert_eq!(
+            SwarmAggregation::parse("concat"),
+            Some(SwarmAggregation::Concat)
+        );
+        assert_eq!(SwarmAggregation::parse("random"), None);
+    }
+}
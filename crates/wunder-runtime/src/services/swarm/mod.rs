@@ -7,6 +7,7 @@ pub mod policy;
 pub mod repo;
 pub mod scope;
 pub mod service;
+pub mod shared_state;
 pub mod types;
 
 pub use service::SwarmService;
@@ -0,0 +1,249 @@
+// 蜂巢共享状态：同一蜂巢内的工蜂按键读写共享数据，键按用户与规范化 hive id 隔离，不同蜂巢互不可见。
+use crate::storage::{hex_meta_segment, normalize_hive_id, StorageBackend};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const HIVE_SHARED_META_PREFIX: &str = "beeroom:shared:";
+const MAX_SHARED_KEY_CHARS: usize = 128;
+const MAX_SHARED_VALUE_BYTES: usize = 16 * 1024;
+/// Keys one hive may hold; writes of new keys beyond this are rejected.
+pub const MAX_SHARED_ENTRIES_PER_HIVE: usize = 256;
+
+/// One shared entry as returned to agents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HiveSharedEntry {
+    pub key: String,
+    pub value: Value,
+    pub agent_id: Option<String>,
+    pub updated_time: f64,
+}
+
+impl HiveSharedEntry {
+    pub fn to_value(&self) -> Value {
+        json!({
+            "key": self.key,
+            "value": self.value,
+            "agent_id": self.agent_id,
+            "updated_time": self.updated_time,
+        })
+    }
+}
+
+/// Meta prefix holding every shared entry of one hive; also used to clear them when the hive
+/// is deleted. User and hive ids are hex encoded so `hive_a` cannot match `hive-a` under `LIKE`.
+pub fn hive_shared_meta_prefix(user_id: &str, hive_id: &str) -> String {
+    format!(
+        "{HIVE_SHARED_META_PREFIX}{}:{}:",
+        hex_meta_segment(user_id.trim()),
+        hex_meta_segment(&normalize_hive_id(hive_id))
+    )
+}
+
+/// Keys are plain names; `:` is reserved as the meta key separator.
+pub fn normalize_shared_key(raw: &str) -> Result<String> {
+    let key = raw.trim();
+    if key.is_empty() {
+        return Err(anyhow!("shared state key is required"));
+    }
+    if key.chars().count() > MAX_SHARED_KEY_CHARS {
+        return Err(anyhow!(
+            "shared state key exceeds {MAX_SHARED_KEY_CHARS} characters"
+        ));
+    }
+    if key.contains(':') {
+        return Err(anyhow!("shared state key must not contain ':'"));
+    }
+    Ok(key.to_string())
+}
+
+pub fn put_hive_shared_state(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    hive_id: &str,
+    key: &str,
+    value: &Value,
+    agent_id: Option<&str>,
+) -> Result<HiveSharedEntry> {
+    let key = normalize_shared_key(key)?;
+    let entry = HiveSharedEntry {
+        key: key.clone(),
+        value: value.clone(),
+        agent_id: agent_id
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToString::to_string),
+        updated_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or(0.0),
+    };
+    let encoded = serde_json::to_string(&json!({
+        "value": entry.value,
+        "agent_id": entry.agent_id,
+        "updated_time": entry.updated_time,
+    }))?;
+    if encoded.len() > MAX_SHARED_VALUE_BYTES {
+        return Err(anyhow!(
+            "shared state value exceeds {MAX_SHARED_VALUE_BYTES} bytes"
+        ));
+    }
+    let prefix = hive_shared_meta_prefix(user_id, hive_id);
+    let meta_key = format!("{prefix}{key}");
+    if storage.get_meta(&meta_key)?.is_none()
+        && storage.list_meta_prefix(&prefix)?.len() >= MAX_SHARED_ENTRIES_PER_HIVE
+    {
+        return Err(anyhow!(
+            "hive shared state is full ({MAX_SHARED_ENTRIES_PER_HIVE} keys); delete or overwrite an existing key"
+        ));
+    }
+    storage.set_meta(&meta_key, &encoded)?;
+    Ok(entry)
+}
+
+pub fn get_hive_shared_state(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    hive_id: &str,
+    key: &str,
+) -> Result<Option<HiveSharedEntry>> {
+    let key = normalize_shared_key(key)?;
+    let meta_key = format!("{}{key}", hive_shared_meta_prefix(user_id, hive_id));
+    Ok(storage
+        .get_meta(&meta_key)?
+        .and_then(|raw| decode_entry(key, &raw)))
+}
+
+/// Every entry of the hive, sorted by key.
+pub fn list_hive_shared_state(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    hive_id: &str,
+) -> Result<Vec<HiveSharedEntry>> {
+    let prefix = hive_shared_meta_prefix(user_id, hive_id);
+    let mut entries = storage
+        .list_meta_prefix(&prefix)?
+        .into_iter()
+        .filter_map(|(meta_key, raw)| {
+            let key = meta_key.strip_prefix(&prefix)?.to_string();
+            decode_entry(key, &raw)
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|left, right| left.key.cmp(&right.key));
+    Ok(entries)
+}
+
+pub fn clear_hive_shared_state(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+    hive_id: &str,
+) -> Result<usize> {
+    storage.delete_meta_prefix(&hive_shared_meta_prefix(user_id, hive_id))
+}
+
+fn decode_entry(key: String, raw: &str) -> Option<HiveSharedEntry> {
+    let stored: Value = serde_json::from_str(raw).ok()?;
+    Some(HiveSharedEntry {
+        key,
+        value: stored.get("value").cloned().unwrap_or(Value::Null),
+        agent_id: stored
+            .get("agent_id")
+            .and_then(Value::as_str)
+            .map(ToString::to_string),
+        updated_time: stored
+            .get("updated_time")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SqliteStorage;
+
+    #[test]
+    fn hives_do_not_see_each_other_state() {
+        let db_path = std::env::temp_dir().join(format!(
+            "wunder_hive_shared_{}.db",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let storage = SqliteStorage::new(db_path.to_string_lossy().to_string());
+
+        put_hive_shared_state(
+            &storage,
+            "u1",
+            "Hive-A",
+            "plan",
+            &json!("a"),
+            Some("worker_a"),
+        )
+        .expect("put a");
+        put_hive_shared_state(&storage, "u1", "hive_b", "plan", &json!("b"), None).expect("put b");
+        put_hive_shared_state(&storage, "u1", "hive_ab", "notes", &json!([1]), None)
+            .expect("put ab");
+
+        let entry = get_hive_shared_state(&storage, "u1", "hive-a", "plan")
+            .expect("get a")
+            .expect("entry a");
+        assert_eq!(entry.value, json!("a"));
+        assert_eq!(entry.agent_id.as_deref(), Some("worker_a"));
+        assert_eq!(
+            get_hive_shared_state(&storage, "u1", "hive_b", "plan")
+                .expect("get b")
+                .map(|entry| entry.value),
+            Some(json!("b"))
+        );
+        assert!(get_hive_shared_state(&storage, "u2", "hive-a", "plan")
+            .expect("other user")
+            .is_none());
+
+        let keys = |hive: &str| {
+            list_hive_shared_state(&storage, "u1", hive)
+                .expect("list")
+                .into_iter()
+                .map(|entry| entry.key)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys("hive-a"), vec!["plan"]);
+        assert_eq!(keys("hive_a"), Vec::<String>::new());
+        assert_eq!(keys("hive_ab"), vec!["notes"]);
+
+        put_hive_shared_state(&storage, "u1", "hive_a", "plan", &json!("a2"), None)
+            .expect("put a2");
+        assert_eq!(
+            clear_hive_shared_state(&storage, "u1", "hive_a").expect("clear"),
+            1
+        );
+        assert!(keys("hive_a").is_empty());
+        assert_eq!(keys("hive-a"), vec!["plan"]);
+        assert_eq!(keys("hive_b"), vec!["plan"]);
+        assert!(normalize_shared_key("a:b").is_err());
+    }
+
+    #[test]
+    fn new_keys_are_rejected_once_the_hive_is_full() {
+        let db_path = std::env::temp_dir().join(format!(
+            "wunder_hive_shared_cap_{}.db",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let storage = SqliteStorage::new(db_path.to_string_lossy().to_string());
+        for index in 0..MAX_SHARED_ENTRIES_PER_HIVE {
+            put_hive_shared_state(
+                &storage,
+                "u1",
+                "hive",
+                &format!("k{index}"),
+                &json!(index),
+                None,
+            )
+            .expect("put within cap");
+        }
+
+        assert!(put_hive_shared_state(&storage, "u1", "hive", "extra", &json!(1), None).is_err());
+        put_hive_shared_state(&storage, "u1", "hive", "k0", &json!("updated"), None)
+            .expect("overwrite existing key");
+        put_hive_shared_state(&storage, "u1", "other", "extra", &json!(1), None)
+            .expect("other hive has its own cap");
+    }
+}
@@ -14,11 +14,13 @@ pub(crate) mod command_options;
 pub(crate) mod command_output_guard;
 pub mod command_sessions;
 mod command_tool;
+mod command_workdir;
 mod context;
 mod desktop_control;
 #[cfg(feature = "desktop-control")]
 mod desktop_control_impl;
 mod dispatch;
+pub(crate) mod dry_run_plan;
 mod edit_file2_tool;
 mod file_tool;
 mod freeform;
@@ -33,6 +35,7 @@ mod read_file_guard;
 mod read_image_tool;
 mod read_indentation;
 mod schedule_task_tool;
+mod search_budget;
 mod search_content_tool;
 mod self_status_tool;
 mod session_announce_support;
@@ -48,12 +51,16 @@ mod sleep_tool;
 mod subagent_control;
 mod swarm_realtime;
 mod swarm_run_support;
+mod swarm_shared_tool;
 mod swarm_tool_error;
 mod swarm_tool_hint;
 mod thread_control_tool;
 pub(crate) mod tool_error;
+mod upstream_guard;
 mod user_tool_dispatch;
 mod user_world_tool;
+#[cfg(feature = "web-fetch")]
+mod web_fetch_denylist;
 #[cfg(any(feature = "web-fetch", test))]
 mod web_fetch_provider;
 #[cfg(feature = "web-fetch")]
@@ -86,7 +93,7 @@ pub use context::{build_tool_roots, ToolContext, ToolEventEmitter, ToolRoots};
 pub(crate) use context::{
     collect_allow_roots, collect_read_roots, resolve_tool_path, roots_allow_any_path,
 };
-pub use dispatch::{execute_builtin_tool, execute_tool};
+pub use dispatch::{execute_builtin_tool, execute_tool, execute_tool_with_timeout};
 pub(crate) use freeform::{
     build_responses_freeform_tool, extract_freeform_tool_input, is_freeform_tool_name,
     render_prompt_tool_spec,
@@ -107,6 +114,7 @@ pub(crate) use session_run_lifecycle::{
     spawn_session_run, PreparedChildSession, SessionRunMeta,
 };
 pub(crate) use session_tool::{sessions_history, sessions_list, sessions_send, sessions_spawn};
+pub use session_tool_access::resolve_session_request_tool_names;
 pub(crate) use session_tool_access::{
     apply_tool_overrides, build_effective_tool_names, collect_user_allowed_tools,
     finalize_tool_names, is_agent_allowed_by_access, load_agent_record,
@@ -129,9 +137,10 @@ pub(crate) use session_tool_support::{
 };
 pub(crate) use swarm_run_support::{
     claim_swarm_mother_for_context, create_swarm_team_run_record, create_swarm_team_task_record,
-    wait_for_swarm_runs,
+    parse_swarm_aggregation, wait_for_swarm_runs,
 };
 pub(crate) use thread_control_tool::execute_thread_control_tool;
+pub use tool_error::ToolError;
 pub(crate) use user_tool_dispatch::{execute_mcp_tool, execute_user_tool, is_mcp_tool_name};
 
 use crate::config::Config;
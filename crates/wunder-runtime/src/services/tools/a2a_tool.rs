@@ -1,9 +1,10 @@
 use super::{
     build_model_tool_success, build_model_tool_success_with_hint, catalog::yaml_to_json,
-    context::ToolContext,
+    context::ToolContext, upstream_guard::call_with_breaker,
 };
 use crate::a2a_store::A2aTask;
 use crate::config::{A2aServiceConfig, Config};
+use crate::services::upstream_breaker::UpstreamKind;
 use anyhow::{anyhow, Result};
 use chrono::{Local, Utc};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
@@ -156,14 +157,8 @@ pub(crate) async fn execute_a2a_service_tool(
         .get("timeout_s")
         .and_then(Value::as_u64)
         .unwrap_or(context.config.a2a.timeout_s);
-    let response = send_a2a_request(
-        context.http,
-        &service.endpoint,
-        headers,
-        &payload,
-        timeout_s,
-    )
-    .await?;
+    let response =
+        send_a2a_request(context, &service.endpoint, headers, &payload, timeout_s).await?;
     let info = parse_a2a_task_info(&response).ok_or_else(|| anyhow!("A2A 返回缺少任务信息"))?;
     let now = Utc::now();
     context.a2a_store.insert(A2aTask {
@@ -509,7 +504,7 @@ async fn refresh_a2a_task(
         "method": "GetTask",
         "params": { "name": format!("tasks/{}", snapshot.task_id) }
     });
-    let response = send_a2a_request(context.http, &endpoint, headers, &payload, timeout_s).await?;
+    let response = send_a2a_request(context, &endpoint, headers, &payload, timeout_s).await?;
     if let Some(info) = parse_a2a_task_info(&response) {
         snapshot.context_id = info.context_id.clone();
         snapshot.status = info.status.clone();
@@ -619,17 +614,26 @@ fn should_attach_a2a_api_key(config: &Config, service: &A2aServiceConfig) -> boo
 }
 
 async fn send_a2a_request(
-    client: &reqwest::Client,
+    context: &ToolContext<'_>,
     endpoint: &str,
     headers: HeaderMap,
     payload: &Value,
     timeout_s: u64,
 ) -> Result<Value> {
-    let mut request = client.post(endpoint).headers(headers).json(payload);
+    let mut request = context.http.post(endpoint).headers(headers).json(payload);
     if timeout_s > 0 {
         request = request.timeout(Duration::from_secs(timeout_s));
     }
-    let response = request.send().await?;
+    // Only transport errors and 5xx count against the endpoint's breaker; 4xx and JSON-RPC
+    // errors mean the service is up and answered.
+    let response = call_with_breaker(context, UpstreamKind::A2a, endpoint, async move {
+        let response = request.send().await?;
+        if response.status().is_server_error() {
+            return Err(anyhow!("A2A 请求失败: {}", response.status()));
+        }
+        Ok(response)
+    })
+    .await?;
     let status = response.status();
     let text = response.text().await?;
     let body: Value =
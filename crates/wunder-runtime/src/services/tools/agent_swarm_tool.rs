@@ -68,6 +68,9 @@ async fn dispatch_swarm_batch_task(
         agent_prompt: task.agent_prompt,
         preview_skill: task.preview_skill,
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: context.is_admin,
         enforce_runtime_queue: false,
@@ -215,7 +218,7 @@ pub(crate) async fn agent_swarm(context: &ToolContext<'_>, args: &Value) -> Resu
                 json!({ "action": "list" }),
                 args,
                 json!({
-                    "allowed_actions": ["list", "status", "send", "history", "spawn", "batch_send", "wait"]
+                    "allowed_actions": ["list", "status", "send", "history", "spawn", "batch_send", "wait", "share_set", "share_get"]
                 }),
             ));
         }
@@ -231,7 +234,7 @@ pub(crate) async fn agent_swarm(context: &ToolContext<'_>, args: &Value) -> Resu
             json!({ "action": "list" }),
             args,
             json!({
-                "allowed_actions": ["list", "status", "send", "history", "spawn", "batch_send", "wait"]
+                "allowed_actions": ["list", "status", "send", "history", "spawn", "batch_send", "wait", "share_set", "share_get"]
             }),
         ));
     }
@@ -255,16 +258,22 @@ pub(crate) async fn agent_swarm(context: &ToolContext<'_>, args: &Value) -> Resu
         "spawn" | "agent_spawn" | "agents_spawn" | "swarm_spawn" => {
             agent_swarm_spawn(context, args).await
         }
+        "share_set" | "shared_set" | "share" => {
+            super::swarm_shared_tool::agent_swarm_share_set(context, args).await
+        }
+        "share_get" | "shared_get" | "shared" => {
+            super::swarm_shared_tool::agent_swarm_share_get(context, args).await
+        }
         _ => Ok(build_agent_swarm_args_failure(
             action,
             "TOOL_ARGS_INVALID",
             format!("unknown agent_swarm action: {action}"),
-            "Use one of list/status/send/history/spawn/batch_send/wait with the required fields.",
+            "Use one of list/status/send/history/spawn/batch_send/wait/share_set/share_get with the required fields.",
             &["action"],
             json!({ "action": "list" }),
             args,
             json!({
-                "allowed_actions": ["list", "status", "send", "history", "spawn", "batch_send", "wait"]
+                "allowed_actions": ["list", "status", "send", "history", "spawn", "batch_send", "wait", "share_set", "share_get"]
             }),
         )),
     }
@@ -884,6 +893,11 @@ pub(crate) async fn agent_swarm_batch_send(
         ));
     }
 
+    let aggregation =
+        match parse_swarm_aggregation("batch_send", args, agent_swarm_batch_send_example()) {
+            Ok(aggregation) => aggregation,
+            Err(failure) => return Ok(failure),
+        };
     let shared_message = normalize_optional_string(payload.message.clone());
     let shared_label = normalize_optional_string(payload.label.clone());
     let shared_thread_strategy = match parse_swarm_worker_thread_strategy(
@@ -1559,6 +1573,7 @@ pub(crate) async fn agent_swarm_batch_send(
             swarm_wait_seconds_value(wait_mode),
             poll_interval_seconds,
             true,
+            aggregation,
         )
         .await?;
         if let Some(wait_items) = tool_result_field(&wait_result, "items").and_then(Value::as_array)
@@ -1711,6 +1726,10 @@ async fn agent_swarm_wait(context: &ToolContext<'_>, args: &Value) -> Result<Val
             json!({}),
         ));
     }
+    let aggregation = match parse_swarm_aggregation("wait", args, agent_swarm_wait_example()) {
+        Ok(aggregation) => aggregation,
+        Err(failure) => return Ok(failure),
+    };
     let wait_mode = resolve_swarm_wait_mode(
         payload.wait_seconds,
         context.config.tools.swarm.default_timeout_s,
@@ -1724,6 +1743,7 @@ async fn agent_swarm_wait(context: &ToolContext<'_>, args: &Value) -> Result<Val
         swarm_wait_seconds_value(wait_mode),
         poll_interval_seconds,
         true,
+        aggregation,
     )
     .await
 }
@@ -2036,7 +2056,7 @@ fn ensure_swarm_target_not_current(
     Ok(())
 }
 
-fn swarm_hive_arg(args: &Value) -> Option<&str> {
+pub(super) fn swarm_hive_arg(args: &Value) -> Option<&str> {
     args.get("hiveId")
         .or_else(|| args.get("hive_id"))
         .and_then(Value::as_str)
@@ -9,6 +9,7 @@ use crate::path_utils::is_within_root;
 
 mod matching;
 mod parser;
+mod unified_diff;
 pub(super) use matching::build_context_not_found_hint;
 use matching::{analyze_update_chunk_effect, build_patch_no_effect_hint, UpdateChunkEffect};
 #[cfg(test)]
@@ -306,6 +307,8 @@ async fn apply_patch_inner(context: &ToolContext<'_>, args: &Value) -> Result<Va
                     "path": item.path,
                     "to_path": item.to_path,
                     "hunks": item.hunks,
+                    "added_lines": count_diff_lines(&item.diff_blocks, "add"),
+                    "removed_lines": count_diff_lines(&item.diff_blocks, "delete"),
                     "diff_blocks": item.diff_blocks.iter().map(|block| json!({
                         "header": block.header,
                         "start_line_before": block.start_line_before,
@@ -404,6 +407,8 @@ async fn apply_patch_inner(context: &ToolContext<'_>, args: &Value) -> Result<Va
                 "path": item.path,
                 "to_path": item.to_path,
                 "hunks": item.hunks,
+                "added_lines": count_diff_lines(&item.diff_blocks, "add"),
+                "removed_lines": count_diff_lines(&item.diff_blocks, "delete"),
                 "diff_blocks": item.diff_blocks.iter().map(|block| json!({
                     "header": block.header,
                     "start_line_before": block.start_line_before,
@@ -1020,6 +1025,14 @@ fn build_add_file_diff_block(lines: &[String]) -> FileDiffBlock {
     }
 }
 
+fn count_diff_lines(blocks: &[FileDiffBlock], kind: &str) -> usize {
+    blocks
+        .iter()
+        .flat_map(|block| block.lines.iter())
+        .filter(|line| line.kind == kind)
+        .count()
+}
+
 fn build_delete_file_diff_block(source: &str) -> FileDiffBlock {
     let lines = split_lines(source);
     FileDiffBlock {
@@ -1,3 +1,4 @@
+use super::unified_diff::{looks_like_unified_diff, unified_diff_to_patch};
 use super::{
     ensure_patch_not_cancelled_probe, patch_empty_update_line_error, patch_error_with_hint,
     patch_format_error, PatchCancelProbe, ADD_FILE_MARKER, BEGIN_PATCH_MARKER, DELETE_FILE_MARKER,
@@ -236,6 +237,9 @@ pub(super) fn value_to_patch_candidate(value: &Value) -> Option<String> {
 
 pub(super) fn normalize_patch_text(input: &str) -> String {
     let normalized = input.replace("\r\n", "\n").replace('\r', "\n");
+    // Plain `git diff` / `diff -u` output is translated into the envelope format first.
+    let normalized = unified_diff_to_patch(strip_surrounding_markdown_fence(normalized.trim()))
+        .unwrap_or(normalized);
     let normalized = repair_patch_envelope(&normalized);
     repair_common_patch_format_issues(&normalized)
 }
@@ -283,7 +287,9 @@ pub(super) fn strip_surrounding_markdown_fence(input: &str) -> &str {
 }
 
 pub(super) fn starts_with_patch_payload(input: &str) -> bool {
-    input.starts_with(BEGIN_PATCH_MARKER) || starts_with_patch_file_op(input)
+    input.starts_with(BEGIN_PATCH_MARKER)
+        || starts_with_patch_file_op(input)
+        || looks_like_unified_diff(input)
 }
 
 pub(super) fn starts_with_patch_file_op(input: &str) -> bool {
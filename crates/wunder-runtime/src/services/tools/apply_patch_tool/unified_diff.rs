@@ -0,0 +1,345 @@
+// 标准 unified diff（git diff / diff -u）输入适配：转换为内部 *** Begin Patch 补丁格式。
+use super::{
+    ADD_FILE_MARKER, BEGIN_PATCH_MARKER, DELETE_FILE_MARKER, END_PATCH_MARKER, MOVE_TO_MARKER,
+    UPDATE_FILE_MARKER,
+};
+
+const DEV_NULL: &str = "/dev/null";
+
+#[derive(Debug, Default)]
+struct DiffFileHeader {
+    git_paths: Option<(String, String)>,
+    old_path: Option<String>,
+    new_path: Option<String>,
+    old_is_null: bool,
+    new_is_null: bool,
+}
+
+/// True when `input` opens like `git diff` / `diff -u` output instead of the envelope format.
+pub(super) fn looks_like_unified_diff(input: &str) -> bool {
+    let lines = input
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .collect::<Vec<_>>();
+    !lines.is_empty() && is_file_section_start(&lines, 0)
+}
+
+/// Rewrite a unified diff into the envelope `parse_patch` understands. Only the per-file
+/// headers are translated; hunk headers and body lines pass through so the regular parser
+/// (and its repairs) still applies to them. Returns `None` for non-diff input.
+pub(super) fn unified_diff_to_patch(input: &str) -> Option<String> {
+    if !looks_like_unified_diff(input) {
+        return None;
+    }
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut output = vec![BEGIN_PATCH_MARKER.to_string()];
+    let mut index = lines
+        .iter()
+        .take_while(|line| line.trim().is_empty())
+        .count();
+    while index < lines.len() {
+        let header = parse_file_header(&lines, &mut index);
+        let body_start = index;
+        while index < lines.len() && !is_file_section_start(&lines, index) {
+            index += 1;
+        }
+        let mut body = lines[body_start..index]
+            .iter()
+            .copied()
+            // "\ No newline at end of file" annotates the previous line; it is not content.
+            .filter(|line| !line.starts_with('\\'))
+            .collect::<Vec<_>>();
+        while body.last().is_some_and(|line| line.trim().is_empty()) {
+            body.pop();
+        }
+        emit_file_section(&header, &body, &mut output)?;
+    }
+    output.push(END_PATCH_MARKER.to_string());
+    Some(output.join("\n"))
+}
+
+fn is_file_section_start(lines: &[&str], index: usize) -> bool {
+    let line = lines[index];
+    if line.starts_with("diff --git ") {
+        return true;
+    }
+    // Require the hunk header too, so a deleted "-- x" line followed by an added "++ y"
+    // line inside a hunk is not mistaken for a new file section.
+    line.starts_with("--- ")
+        && lines
+            .get(index + 1)
+            .is_some_and(|next| next.starts_with("+++ "))
+        && lines
+            .get(index + 2)
+            .is_some_and(|next| next.starts_with("@@"))
+}
+
+fn parse_file_header(lines: &[&str], index: &mut usize) -> DiffFileHeader {
+    let mut header = DiffFileHeader::default();
+    if let Some(rest) = lines[*index].strip_prefix("diff --git ") {
+        header.git_paths = split_git_paths(rest);
+        *index += 1;
+    }
+    while *index < lines.len() {
+        let line = lines[*index];
+        if line.starts_with("@@") || line.starts_with("diff --git ") {
+            break;
+        }
+        if let Some(rest) = line.strip_prefix("--- ") {
+            header.old_is_null = diff_path_is_null(rest);
+            header.old_path = parse_diff_path(rest, "a/");
+        } else if let Some(rest) = line.strip_prefix("+++ ") {
+            header.new_is_null = diff_path_is_null(rest);
+            header.new_path = parse_diff_path(rest, "b/");
+        } else if let Some(rest) = line.strip_prefix("rename from ") {
+            header.old_path = Some(rest.trim().to_string());
+        } else if let Some(rest) = line.strip_prefix("rename to ") {
+            header.new_path = Some(rest.trim().to_string());
+        } else if line.starts_with("new file mode") {
+            header.old_is_null = true;
+        } else if line.starts_with("deleted file mode") {
+            header.new_is_null = true;
+        }
+        // index/similarity/mode lines carry nothing the patch format can express.
+        *index += 1;
+    }
+    header
+}
+
+fn emit_file_section(
+    header: &DiffFileHeader,
+    body: &[&str],
+    output: &mut Vec<String>,
+) -> Option<()> {
+    let (git_old, git_new) = header
+        .git_paths
+        .clone()
+        .map_or((None, None), |(old, new)| (Some(old), Some(new)));
+    let old_path = header.old_path.clone().or(git_old);
+    let new_path = header.new_path.clone().or(git_new);
+
+    if header.old_is_null {
+        output.push(format!("{ADD_FILE_MARKER}{}", new_path?));
+        output.extend(
+            body.iter()
+                .filter(|line| line.starts_with('+'))
+                .map(|line| line.to_string()),
+        );
+        return Some(());
+    }
+    if header.new_is_null {
+        output.push(format!("{DELETE_FILE_MARKER}{}", old_path?));
+        return Some(());
+    }
+    let old_path = old_path?;
+    let move_to = new_path.filter(|path| *path != old_path);
+    if body.is_empty() && move_to.is_none() {
+        // Mode-only changes have nothing to apply.
+        return Some(());
+    }
+    output.push(format!("{UPDATE_FILE_MARKER}{old_path}"));
+    if let Some(path) = move_to {
+        output.push(format!("{MOVE_TO_MARKER}{path}"));
+    }
+    output.extend(body.iter().map(|line| line.to_string()));
+    Some(())
+}
+
+fn split_git_paths(rest: &str) -> Option<(String, String)> {
+    let split = rest.rfind(" b/")?;
+    let old = rest[..split].trim();
+    let new = rest[split + 1..].trim();
+    Some((
+        old.strip_prefix("a/").unwrap_or(old).to_string(),
+        new.strip_prefix("b/").unwrap_or(new).to_string(),
+    ))
+}
+
+fn diff_path_token(raw: &str) -> &str {
+    // `diff -u` appends "\t<timestamp>" after the path.
+    let path = raw.split('\t').next().unwrap_or(raw).trim();
+    path.strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(path)
+}
+
+fn diff_path_is_null(raw: &str) -> bool {
+    diff_path_token(raw) == DEV_NULL
+}
+
+fn parse_diff_path(raw: &str, git_prefix: &str) -> Option<String> {
+    let path = diff_path_token(raw);
+    if path.is_empty() || path == DEV_NULL {
+        return None;
+    }
+    Some(path.strip_prefix(git_prefix).unwrap_or(path).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parser::parse_patch;
+    use super::super::{
+        apply_patch_ops, count_diff_lines, ParsedPatchOp, PatchToolError, ResolvedPatchOp,
+    };
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn resolve_in(dir: &Path, ops: Vec<ParsedPatchOp>) -> Vec<ResolvedPatchOp> {
+        ops.into_iter()
+            .map(|op| match op {
+                ParsedPatchOp::Add { path, lines } => ResolvedPatchOp::Add {
+                    target: dir.join(&path),
+                    path,
+                    lines,
+                },
+                ParsedPatchOp::Delete { path } => ResolvedPatchOp::Delete {
+                    target: dir.join(&path),
+                    path,
+                },
+                ParsedPatchOp::Update {
+                    path,
+                    move_to,
+                    chunks,
+                } => ResolvedPatchOp::Update {
+                    target: dir.join(&path),
+                    move_to_target: move_to.as_ref().map(|value| dir.join(value)),
+                    move_to_path: move_to,
+                    path,
+                    chunks,
+                },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unified_diff_to_patch_translates_git_headers() {
+        let diff = r#"diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,2 @@
+ fn main() {
+-    old();
++    new();
+\ No newline at end of file
+diff --git a/notes.md b/notes.md
+new file mode 100644
+--- /dev/null
++++ b/notes.md
+@@ -0,0 +1 @@
++hello
+diff --git a/stale.txt b/stale.txt
+deleted file mode 100644
+--- a/stale.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+"#;
+        let expected = r#"*** Begin Patch
+*** Update File: src/lib.rs
+@@ -1,2 +1,2 @@
+ fn main() {
+-    old();
++    new();
+*** Add File: notes.md
++hello
+*** Delete File: stale.txt
+*** End Patch"#;
+        assert_eq!(unified_diff_to_patch(diff).as_deref(), Some(expected));
+        assert_eq!(
+            unified_diff_to_patch("*** Begin Patch\n*** End Patch"),
+            None
+        );
+    }
+
+    #[test]
+    fn unified_diff_applies_cleanly_across_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("a.txt"), "one\ntwo\nthree\n").expect("seed a.txt");
+        fs::write(dir.path().join("b.txt"), "alpha\nbeta\n").expect("seed b.txt");
+        let diff = "--- a/a.txt\t2026-01-01 00:00:00\n+++ b/a.txt\t2026-01-01 00:00:00\n"
+            .to_string()
+            + r#"@@ -1,3 +1,3 @@
+ one
+-two
++TWO
+ three
+--- a/b.txt
++++ b/b.txt
+@@ -1,2 +1,3 @@
+ alpha
+ beta
++gamma
+"#;
+
+        let ops = parse_patch(&diff).expect("unified diff should parse");
+        let summary = apply_patch_ops(resolve_in(dir.path(), ops), None).expect("apply");
+        assert_eq!(
+            (
+                summary.updated,
+                summary.hunks_applied,
+                summary.changed_files.len()
+            ),
+            (2, 2, 2)
+        );
+        let line_counts = summary
+            .file_summaries
+            .iter()
+            .map(|file| {
+                (
+                    file.path.as_str(),
+                    count_diff_lines(&file.diff_blocks, "add"),
+                    count_diff_lines(&file.diff_blocks, "delete"),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(line_counts, vec![("a.txt", 1, 1), ("b.txt", 1, 0)]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).expect("read a.txt"),
+            "one\nTWO\nthree\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("b.txt")).expect("read b.txt"),
+            "alpha\nbeta\ngamma\n"
+        );
+    }
+
+    #[test]
+    fn unified_diff_context_mismatch_leaves_every_file_untouched() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("a.txt"), "one\ntwo\n").expect("seed a.txt");
+        fs::write(dir.path().join("b.txt"), "alpha\nbeta\n").expect("seed b.txt");
+        let diff = r#"diff --git a/a.txt b/a.txt
+--- a/a.txt
++++ b/a.txt
+@@ -1,2 +1,2 @@
+ one
+-two
++TWO
+diff --git a/b.txt b/b.txt
+--- a/b.txt
++++ b/b.txt
+@@ -1,2 +1,2 @@
+ alpha
+-not in this file
++delta
+"#;
+
+        let ops = parse_patch(diff).expect("unified diff should parse");
+        let err = apply_patch_ops(resolve_in(dir.path(), ops), None)
+            .expect_err("mismatched context must fail the whole patch");
+        assert_eq!(
+            err.downcast_ref::<PatchToolError>().map(|error| error.code),
+            Some("PATCH_CONTEXT_NOT_FOUND")
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).expect("read a.txt"),
+            "one\ntwo\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("b.txt")).expect("read b.txt"),
+            "alpha\nbeta\n"
+        );
+    }
+}
@@ -500,7 +500,9 @@ pub(crate) fn builtin_tool_specs_with_language(language: &str) -> Vec<ToolSpec>
                 "properties": {
                     "content": {"type": "string", "description": t("tool.spec.exec.args.content")},
                     "workdir": {"type": "string", "description": t("tool.spec.exec.args.workdir")},
+                    "create": {"type": "boolean", "description": t("tool.spec.exec.args.create")},
                     "timeout_s": {"type": "number", "description": t("tool.spec.exec.args.timeout")},
+                    "env": {"type": "object", "description": t("tool.spec.exec.args.env"), "additionalProperties": {"type": "string"}},
                     "dry_run": {"type": "boolean", "description": "Validate command only without execution."}
                 },
                 "required": ["content"],
@@ -551,6 +553,7 @@ pub(crate) fn builtin_tool_specs_with_language(language: &str) -> Vec<ToolSpec>
                     "case_sensitive": {"type": "boolean", "description": t("tool.spec.search.args.case_sensitive")},
                     "max_matches": {"type": "integer", "minimum": 1, "maximum": 2000, "description": "Maximum number of matches to return (default 200)."},
                     "timeout_ms": {"type": "integer", "minimum": 1, "maximum": 120000, "description": "Search timeout in milliseconds (default 30000)."},
+                    "max_scan_bytes": {"type": "integer", "minimum": 1, "description": "Stop after reading this many file bytes in total (default 64 MiB)."},
                     "context_before": {"type": "integer", "minimum": 0, "maximum": 20, "description": t("tool.spec.search.args.context_before")},
                     "context_after": {"type": "integer", "minimum": 0, "maximum": 20, "description": t("tool.spec.search.args.context_after")}
                 },
@@ -914,7 +917,7 @@ pub(crate) fn builtin_tool_specs_with_language(language: &str) -> Vec<ToolSpec>
                     "action": {
                         "type": "string",
                         "description": t("tool.spec.agent_swarm.args.action"),
-                        "enum": ["list", "status", "send", "history", "spawn", "batch_send", "wait"]
+                        "enum": ["list", "status", "send", "history", "spawn", "batch_send", "wait", "share_set", "share_get"]
                     },
                     "agent_id": {"type": "string", "description": "目标智能体 ID。仅在名称不可用、存在重名歧义或用户明确指定时使用，避免手抄长 ID。"},
                     "agent_name": {"type": "string", "description": "目标智能体名称。派发工蜂时优先使用名称。"},
@@ -924,6 +927,14 @@ pub(crate) fn builtin_tool_specs_with_language(language: &str) -> Vec<ToolSpec>
                     "limit": {"type": "integer", "description": "Maximum number of items to return for list/status.", "minimum": 1},
                     "wait_seconds": {"type": "number", "description": "Optional wait duration in seconds for wait/batch_send."},
                     "poll_interval_seconds": {"type": "number", "description": "Polling interval in seconds while waiting."},
+                    "aggregation": {
+                        "type": "string",
+                        "description": t("tool.spec.agent_swarm.args.aggregation"),
+                        "enum": ["concat", "vote", "reduce"]
+                    },
+                    "hive_id": {"type": "string", "description": t("tool.spec.agent_swarm.args.hive_id")},
+                    "key": {"type": "string", "description": t("tool.spec.agent_swarm.args.key")},
+                    "value": {"description": t("tool.spec.agent_swarm.args.value")},
                     "tasks": {
                         "type": "array",
                         "description": "batch_send 任务列表。每个 task 都必须指定一个目标(agent_name/agent_id/session_id 之一)；message 建议每个 task 都显式填写，不要传空对象 {}。",
@@ -1264,6 +1275,7 @@ pub fn builtin_aliases() -> HashMap<String, String> {
     map.insert("programmatic_tool_call".to_string(), "ptc".to_string());
     map.insert("list_files".to_string(), "列出文件".to_string());
     map.insert("search_content".to_string(), "搜索内容".to_string());
+    map.insert("search_files".to_string(), "搜索内容".to_string());
     map.insert("read_file".to_string(), "读取文件".to_string());
     map.insert(
         read_image_tool::TOOL_READ_IMAGE_ALIAS.to_string(),
@@ -2204,6 +2216,7 @@ mod tests {
     #[test]
     fn search_spec_exposes_canonical_model_side_fields_in_english() {
         let canonical = resolve_tool_name("search_content");
+        assert_eq!(resolve_tool_name("search_files"), canonical);
         let spec = builtin_tool_specs_with_language("en-US")
             .into_iter()
             .find(|spec| spec.name == canonical)
@@ -1,5 +1,6 @@
 use super::{
     apply_patch_tool, build_model_tool_success, build_model_tool_success_with_hint,
+    collect_orchestration_run_roots,
     command_options::{apply_time_budget_secs, parse_command_budget, parse_dry_run},
     command_output_guard::{
         derive_capture_policies, render_command_output, CommandOutputCapture,
@@ -7,6 +8,8 @@ use super::{
         DEFAULT_CAPTURE_TOTAL_BYTES, STDERR_CAPTURE_POLICY, STDOUT_CAPTURE_POLICY,
     },
     command_sessions::{CommandSessionLaunchMode, CommandSessionStream, CommandSessionTracker},
+    command_workdir::{parse_create_workdir, resolve_command_workdir, CommandWorkdirError},
+    context::{build_allow_roots, collect_skill_roots},
     execute_in_sandbox, recover_tool_args_value, resolve_tool_name,
     tool_error::{
         build_execute_command_failure_data, build_execute_command_failure_message,
@@ -16,6 +19,7 @@ use super::{
 };
 use crate::command_utils;
 use crate::config::Config;
+use crate::core::command_env::{ChildEnv, EnvPassthrough};
 use crate::core::long_task;
 use crate::core::python_runtime;
 use crate::i18n;
@@ -23,7 +27,7 @@ use anyhow::{anyhow, Result};
 #[cfg(windows)]
 use encoding_rs::GBK;
 use serde_json::{json, Value};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::time::Duration;
@@ -309,6 +313,7 @@ async fn execute_command_in_sandbox_streaming_auto(
     context: &ToolContext<'_>,
     args: &Value,
     content: &str,
+    cwd: &Path,
 ) -> Option<Value> {
     if content.trim().is_empty() {
         return None;
@@ -316,21 +321,6 @@ async fn execute_command_in_sandbox_streaming_auto(
     if !crate::sandbox::sandbox_enabled(context.config) {
         return None;
     }
-    let workdir = args.get("workdir").and_then(Value::as_str).unwrap_or("");
-    let cwd = if workdir.is_empty() {
-        match context.workspace.ensure_user_root(context.workspace_id) {
-            Ok(path) => path,
-            Err(_) => return None,
-        }
-    } else {
-        match context
-            .workspace
-            .resolve_path(context.workspace_id, workdir)
-        {
-            Ok(path) => path,
-            Err(_) => return None,
-        }
-    };
     let allow_all = context
         .config
         .security
@@ -347,7 +337,7 @@ async fn execute_command_in_sandbox_streaming_auto(
             .map(ToString::to_string)
             .collect::<Vec<_>>()
     };
-    let result = execute_command_in_sandbox_streaming(context, args, &commands, &cwd).await?;
+    let result = execute_command_in_sandbox_streaming(context, args, &commands, cwd).await?;
     let transport_failed = result
         .get("error")
         .and_then(Value::as_str)
@@ -759,6 +749,7 @@ async fn run_command_streaming(
     stdout_policy: CommandOutputPolicy,
     stderr_policy: CommandOutputPolicy,
     command_index: usize,
+    child_env: &ChildEnv,
 ) -> Result<CommandRunResult> {
     let command_text = command.to_string();
     let command_env = python_runtime::resolve_desktop_command_env();
@@ -783,6 +774,7 @@ async fn run_command_streaming(
     } else {
         (command_utils::build_shell_command(command, cwd), false)
     };
+    child_env.apply(&mut cmd);
     python_runtime::apply_desktop_command_env(&mut cmd, &command_env);
     apply_streaming_command_env(&mut cmd);
     let initial_launch_mode = if used_direct {
@@ -806,6 +798,7 @@ async fn run_command_streaming(
         ),
         Err(err) if used_direct && command_utils::is_not_found_error(&err) => {
             let mut cmd = command_utils::build_shell_command(command, cwd);
+            child_env.apply(&mut cmd);
             python_runtime::apply_desktop_command_env(&mut cmd, &command_env);
             apply_streaming_command_env(&mut cmd);
             cmd.kill_on_drop(true);
@@ -886,6 +879,11 @@ async fn run_ptc_python_script_streaming(
 
     let tool_name = resolve_tool_name("ptc");
     let script_text = script_path.to_string_lossy().to_string();
+    // Scripts get the same inherited environment as execute_command; ptc takes no per-call env.
+    let child_env = ChildEnv::new(
+        EnvPassthrough::from_names(&context.config.security.env_passthrough),
+        BTreeMap::new(),
+    );
     let mut last_error: Option<anyhow::Error> = None;
     let mut tried = Vec::new();
 
@@ -895,6 +893,7 @@ async fn run_ptc_python_script_streaming(
         let mut cmd = tokio::process::Command::new(&program);
         cmd.arg(script_path);
         cmd.current_dir(workdir);
+        child_env.apply(&mut cmd);
         apply_streaming_command_env(&mut cmd);
         python_runtime::apply_python_env(&mut cmd, &runtime);
         command_utils::apply_platform_spawn_options(&mut cmd);
@@ -928,6 +927,7 @@ async fn run_ptc_python_script_streaming(
         cmd.args(*prefix_args);
         cmd.arg(script_path);
         cmd.current_dir(workdir);
+        child_env.apply(&mut cmd);
         apply_streaming_command_env(&mut cmd);
         if system_python_runtime {
             python_runtime::apply_system_python_env_if_configured(&mut cmd);
@@ -998,18 +998,54 @@ pub(crate) async fn execute_command(context: &ToolContext<'_>, args: &Value) ->
         }
         return Ok(result);
     }
-    if let Some(result) = execute_command_in_sandbox_streaming_auto(context, &args, &content).await
-    {
-        if !dry_run {
-            context.workspace.mark_tree_dirty(context.workspace_id);
-        }
-        return Ok(result);
+    let env_passthrough = EnvPassthrough::from_names(&context.config.security.env_passthrough);
+    let (call_env, rejected_env) = env_passthrough.split_call_env(&args);
+    if !rejected_env.is_empty() {
+        return Ok(build_failed_tool_result(
+            i18n::t("tool.exec.env_not_allowed"),
+            json!({ "env": rejected_env }),
+            ToolErrorMeta::new(
+                "TOOL_EXEC_ENV_NOT_ALLOWED",
+                Some("env 中的变量需在 security.env_passthrough 白名单内。".to_string()),
+                false,
+                None,
+            ),
+            false,
+        ));
     }
-    if let Some(result) = execute_in_sandbox(context, "执行命令", &args).await {
-        if !dry_run {
-            context.workspace.mark_tree_dirty(context.workspace_id);
+    let child_env = ChildEnv::new(env_passthrough, call_env);
+    // Resolve before dispatching: the sandbox shares the workspace volume but neither creates
+    // workdir nor checks it against the host allow roots.
+    let workdir = args.get("workdir").and_then(Value::as_str).unwrap_or("");
+    let create_workdir = parse_create_workdir(&args);
+    let (cwd, workdir_pending) = match resolve_command_workdir(
+        context.workspace.as_ref(),
+        context.workspace_id,
+        workdir,
+        &command_workdir_roots(context),
+        create_workdir && !dry_run,
+    ) {
+        Ok(cwd) => (cwd, false),
+        // A dry run only reports the directory it would create.
+        Err(CommandWorkdirError::NotFound(cwd)) if create_workdir && dry_run => (cwd, true),
+        Err(err) => return Ok(build_workdir_failure(workdir, err)),
+    };
+    // The sandbox would reject a workdir that does not exist yet, so that dry run stays local.
+    if !workdir_pending {
+        if let Some(result) =
+            execute_command_in_sandbox_streaming_auto(context, &args, &content, &cwd).await
+        {
+            if !dry_run {
+                context.workspace.mark_tree_dirty(context.workspace_id);
+            }
+            return Ok(result);
+        }
+        if let Some(result) = execute_in_sandbox(context, "执行命令", &args).await {
+            if !dry_run {
+                context.workspace.mark_tree_dirty(context.workspace_id);
+            }
+            return Ok(result);
         }
-        return Ok(result);
     }
 
     if content.is_empty() {
@@ -1049,41 +1085,6 @@ pub(crate) async fn execute_command(context: &ToolContext<'_>, args: &Value) ->
     } else {
         None
     };
-    let workdir = args.get("workdir").and_then(Value::as_str).unwrap_or("");
-    let cwd = if workdir.is_empty() {
-        context.workspace.ensure_user_root(context.workspace_id)?
-    } else {
-        context
-            .workspace
-            .resolve_path(context.workspace_id, workdir)?
-    };
-    if !cwd.exists() {
-        return Ok(build_failed_tool_result(
-            i18n::t("tool.exec.workdir_not_found"),
-            json!({ "workdir": workdir }),
-            ToolErrorMeta::new(
-                "TOOL_EXEC_WORKDIR_NOT_FOUND",
-                Some("请确认 workdir 路径存在且在允许范围内。".to_string()),
-                false,
-                None,
-            ),
-            false,
-        ));
-    }
-    if !cwd.is_dir() {
-        return Ok(build_failed_tool_result(
-            i18n::t("tool.exec.workdir_not_dir"),
-            json!({ "workdir": workdir }),
-            ToolErrorMeta::new(
-                "TOOL_EXEC_WORKDIR_NOT_DIR",
-                Some("请将 workdir 指向目录而非文件。".to_string()),
-                false,
-                None,
-            ),
-            false,
-        ));
-    }
-
     let mut results = Vec::new();
     let mut guarded_total_bytes: usize = 0;
     let mut guarded_omitted_bytes: usize = 0;
@@ -1194,6 +1195,7 @@ pub(crate) async fn execute_command(context: &ToolContext<'_>, args: &Value) ->
             stdout_policy,
             stderr_policy,
             command_index,
+            &child_env,
         )
         .await?;
         let command_total_bytes = run
@@ -1295,6 +1297,7 @@ pub(crate) async fn execute_command(context: &ToolContext<'_>, args: &Value) ->
         format!("Executed {guarded_total_commands} commands."),
         json!({
             "results": compact_command_results_for_model(&results),
+            "workdir": cwd.to_string_lossy().to_string(),
             "budget": command_budget.to_json(),
             "output_guard": {
                 "truncated": guarded_truncated_commands > 0,
@@ -1313,6 +1316,62 @@ pub(crate) async fn execute_command(context: &ToolContext<'_>, args: &Value) ->
     ))
 }
 
+/// Directories a local command may run in besides the workspace. Unlike file tools this leaves
+/// out the implicit filesystem roots, so `security.allow_paths` bounds `workdir`.
+fn command_workdir_roots(context: &ToolContext<'_>) -> Vec<PathBuf> {
+    let mut roots = match context.allow_roots.as_ref() {
+        Some(roots) => roots.as_ref().clone(),
+        None => build_allow_roots(context.config),
+    };
+    roots.extend(collect_skill_roots(context));
+    roots.extend(collect_orchestration_run_roots(context));
+    roots
+}
+
+fn build_workdir_failure(workdir: &str, err: CommandWorkdirError) -> Value {
+    let (message, code, hint, resolved) = match err {
+        CommandWorkdirError::Invalid(detail) => (
+            detail,
+            "TOOL_EXEC_WORKDIR_INVALID",
+            "请确认 workdir 路径存在且在允许范围内。",
+            None,
+        ),
+        CommandWorkdirError::OutsideRoots(path) => (
+            i18n::t("tool.exec.workdir_out_of_bounds"),
+            "TOOL_EXEC_WORKDIR_OUT_OF_BOUNDS",
+            "workdir 需位于当前工作区或 security.allow_paths 允许的目录内。",
+            Some(path),
+        ),
+        CommandWorkdirError::NotFound(path) => (
+            i18n::t("tool.exec.workdir_not_found"),
+            "TOOL_EXEC_WORKDIR_NOT_FOUND",
+            "请确认 workdir 路径存在，或传入 create=true 自动创建。",
+            Some(path),
+        ),
+        CommandWorkdirError::NotDir(path) => (
+            i18n::t("tool.exec.workdir_not_dir"),
+            "TOOL_EXEC_WORKDIR_NOT_DIR",
+            "请将 workdir 指向目录而非文件。",
+            Some(path),
+        ),
+        CommandWorkdirError::CreateFailed(path, detail) => (
+            format!("{}: {detail}", i18n::t("tool.exec.workdir_create_failed")),
+            "TOOL_EXEC_WORKDIR_CREATE_FAILED",
+            "请检查目录权限后重试。",
+            Some(path),
+        ),
+    };
+    build_failed_tool_result(
+        message,
+        json!({
+            "workdir": workdir,
+            "resolved_workdir": resolved.map(|path| path.to_string_lossy().to_string()),
+        }),
+        ToolErrorMeta::new(code, Some(hint.to_string()), false, None),
+        false,
+    )
+}
+
 pub(crate) fn normalize_ptc_script_name(
     raw_filename: &str,
 ) -> std::result::Result<PathBuf, &'static str> {
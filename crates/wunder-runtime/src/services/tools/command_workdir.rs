@@ -0,0 +1,140 @@
+// execute_command 工作目录解析：workdir 需落在当前工作区或允许目录内，可选在缺失时创建。
+use crate::path_utils::is_within_root;
+use crate::workspace::WorkspaceManager;
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug)]
+pub(crate) enum CommandWorkdirError {
+    Invalid(String),
+    OutsideRoots(PathBuf),
+    NotFound(PathBuf),
+    NotDir(PathBuf),
+    CreateFailed(PathBuf, String),
+}
+
+pub(crate) fn parse_create_workdir(args: &Value) -> bool {
+    args.get("create").and_then(Value::as_bool).unwrap_or(false)
+}
+
+/// Resolve `workdir` to an absolute directory inside the workspace or one of `allow_roots`.
+/// With `create`, a missing directory is created once it has passed the root check.
+pub(crate) fn resolve_command_workdir(
+    workspace: &WorkspaceManager,
+    workspace_id: &str,
+    raw_workdir: &str,
+    allow_roots: &[PathBuf],
+    create: bool,
+) -> Result<PathBuf, CommandWorkdirError> {
+    let workspace_root = workspace
+        .ensure_user_root(workspace_id)
+        .map_err(|err| CommandWorkdirError::Invalid(err.to_string()))?;
+    let raw_workdir = raw_workdir.trim();
+    if raw_workdir.is_empty() {
+        return Ok(workspace_root);
+    }
+    let resolved = workspace
+        .resolve_path(workspace_id, raw_workdir)
+        .map_err(|err| CommandWorkdirError::Invalid(err.to_string()))?;
+    // `is_within_root` compares prefixes, so fold `..` first: a missing `a/../../x` would
+    // otherwise still start with the workspace root.
+    let cwd = collapse_dot_segments(&resolved);
+    let inside = std::iter::once(&workspace_root)
+        .chain(allow_roots)
+        .any(|root| is_within_root(root, &cwd));
+    if !inside {
+        return Err(CommandWorkdirError::OutsideRoots(cwd));
+    }
+    if !cwd.exists() {
+        if !create {
+            return Err(CommandWorkdirError::NotFound(cwd));
+        }
+        std::fs::create_dir_all(&cwd)
+            .map_err(|err| CommandWorkdirError::CreateFailed(cwd.clone(), err.to_string()))?;
+    }
+    if !cwd.is_dir() {
+        return Err(CommandWorkdirError::NotDir(cwd));
+    }
+    Ok(cwd)
+}
+
+fn collapse_dot_segments(path: &Path) -> PathBuf {
+    let mut output = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                output.pop();
+            }
+            other => output.push(other.as_os_str()),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path_utils::normalize_existing_path;
+    use crate::storage::SqliteStorage;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn workspace_in(dir: &Path) -> WorkspaceManager {
+        let storage = Arc::new(SqliteStorage::new(
+            dir.join("state.sqlite3").to_string_lossy().to_string(),
+        ));
+        WorkspaceManager::new(
+            &dir.join("workspaces").to_string_lossy(),
+            storage,
+            0,
+            &HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn workdir_outside_roots_is_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workspace = workspace_in(dir.path());
+        let outside = dir.path().join("outside");
+        std::fs::create_dir_all(&outside).expect("mkdir outside");
+
+        for raw in [outside.to_string_lossy().to_string(), "../outside".into()] {
+            let err = resolve_command_workdir(&workspace, "alice", &raw, &[], true)
+                .expect_err("outside workdir accepted");
+            assert!(matches!(err, CommandWorkdirError::OutsideRoots(_)), "{raw}");
+        }
+        let escaped = resolve_command_workdir(&workspace, "alice", "new/../../../x", &[], true);
+        assert!(matches!(escaped, Err(CommandWorkdirError::OutsideRoots(_))));
+        assert!(!dir.path().join("x").exists());
+
+        let allowed = resolve_command_workdir(
+            &workspace,
+            "alice",
+            &outside.to_string_lossy(),
+            std::slice::from_ref(&outside),
+            false,
+        )
+        .expect("allow root");
+        assert_eq!(
+            normalize_existing_path(&allowed),
+            normalize_existing_path(&outside)
+        );
+    }
+
+    #[test]
+    fn missing_workdir_in_root_is_created_only_on_request() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workspace = workspace_in(dir.path());
+
+        let missing = resolve_command_workdir(&workspace, "alice", "build/out", &[], false);
+        assert!(matches!(missing, Err(CommandWorkdirError::NotFound(_))));
+
+        let created = resolve_command_workdir(&workspace, "alice", "build/out", &[], true)
+            .expect("create workdir");
+        assert!(created.is_absolute());
+        assert!(created.is_dir());
+        assert!(created.ends_with("build/out"));
+        assert!(is_within_root(&workspace.workspace_root("alice"), &created));
+    }
+}
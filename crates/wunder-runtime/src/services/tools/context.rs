@@ -109,6 +109,8 @@ pub struct ToolContext<'a> {
     pub command_sessions: Option<Arc<CommandSessionBroker>>,
     pub event_emitter: Option<ToolEventEmitter>,
     pub http: &'a reqwest::Client,
+    /// Plan mode: write tools and high-risk commands report what they would do instead.
+    pub dry_run: bool,
 }
 
 impl<'a> ToolContext<'a> {
@@ -142,8 +144,15 @@ impl<'a> ToolContext<'a> {
             command_sessions: self.command_sessions.as_ref().map(Arc::clone),
             event_emitter,
             http: self.http,
+            dry_run: self.dry_run,
         }
     }
+
+    pub fn with_dry_run(&self, dry_run: bool) -> ToolContext<'a> {
+        let mut context = self.with_event_emitter(self.event_emitter.clone());
+        context.dry_run = dry_run;
+        context
+    }
 }
 
 #[derive(Clone)]
@@ -3,6 +3,7 @@ use super::catalog::resolve_tool_name;
 use super::channel_tool;
 use super::command_tool;
 use super::context::ToolContext;
+use super::dry_run_plan::apply_context_dry_run;
 use super::file_tool;
 use super::knowledge_tool;
 use super::lsp_tool;
@@ -13,6 +14,7 @@ use super::schedule_task_tool;
 use super::search_content_tool::search_content;
 use super::sessions_yield_tool;
 use super::skill_call;
+use super::tool_error::ToolError;
 use super::user_world_tool;
 use super::{
     agent_swarm, edit_file2, execute_mcp_tool, execute_memory_manager_tool,
@@ -25,16 +27,26 @@ use super::{
 };
 use crate::services::goal;
 use crate::skills::execute_skill;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use serde_json::{json, Value};
+use std::time::Duration;
 
 /// 工具调度入口：优先处理 A2A 与 MCP，再回落到内置工具。
-pub async fn execute_tool(context: &ToolContext<'_>, name: &str, args: &Value) -> Result<Value> {
-    let _ = context.session_id;
+pub async fn execute_tool(
+    context: &ToolContext<'_>,
+    name: &str,
+    args: &Value,
+) -> Result<Value, ToolError> {
+    // A stop request can land between the model emitting a call and dispatch reaching it.
+    if is_session_cancelled(context) {
+        return Err(ToolError::Cancelled);
+    }
     let canonical = resolve_tool_name(name);
+    let args = apply_context_dry_run(context, &canonical, args);
+    let args = args.as_ref();
     if let Some(bindings) = context.user_tool_bindings {
         if let Some(alias) = bindings.alias_map.get(&canonical) {
-            return execute_user_tool(context, alias, args).await;
+            return Ok(execute_user_tool(context, alias, args).await?);
         }
     }
     if let Some(skill) = context.skills.get(&canonical) {
@@ -43,26 +55,61 @@ pub async fn execute_tool(context: &ToolContext<'_>, name: &str, args: &Value) -
         return Ok(result);
     }
     if a2a_tool::is_a2a_service_tool(&canonical) {
-        return a2a_tool::execute_a2a_service_tool(context, &canonical, args).await;
+        return Ok(a2a_tool::execute_a2a_service_tool(context, &canonical, args).await?);
     }
     if is_mcp_tool_name(&canonical) {
-        return execute_mcp_tool(context, &canonical, args).await;
+        return Ok(execute_mcp_tool(context, &canonical, args).await?);
     }
     if let Some(base) = knowledge_tool::find_knowledge_base(context.config, &canonical) {
-        return knowledge_tool::execute_knowledge_tool(context, &base, args).await;
+        return Ok(knowledge_tool::execute_knowledge_tool(context, &base, args).await?);
     }
-    execute_builtin_tool(context, &canonical, args).await
+    Ok(dispatch_builtin_tool(context, &canonical, args).await?)
+}
+
+/// `execute_tool` bounded by `timeout`; a call that runs past it fails with
+/// `ToolError::Timeout`.
+pub async fn execute_tool_with_timeout(
+    context: &ToolContext<'_>,
+    name: &str,
+    args: &Value,
+    timeout: Option<Duration>,
+) -> Result<Value, ToolError> {
+    let Some(timeout) = timeout else {
+        return execute_tool(context, name, args).await;
+    };
+    tokio::time::timeout(timeout, execute_tool(context, name, args))
+        .await
+        .unwrap_or(Err(ToolError::Timeout))
 }
 
 pub async fn execute_builtin_tool(
     context: &ToolContext<'_>,
     name: &str,
     args: &Value,
-) -> Result<Value> {
+) -> Result<Value, ToolError> {
     let canonical = resolve_tool_name(name);
-    match canonical.as_str() {
+    // Direct builtin callers bypass `execute_tool`, so the dry-run plan is applied here too.
+    let args = apply_context_dry_run(context, &canonical, args);
+    Ok(dispatch_builtin_tool(context, &canonical, args.as_ref()).await?)
+}
+
+fn is_session_cancelled(context: &ToolContext<'_>) -> bool {
+    let session_id = context.session_id.trim();
+    !session_id.is_empty()
+        && context
+            .monitor
+            .as_ref()
+            .is_some_and(|monitor| monitor.is_cancelled(session_id))
+}
+
+async fn dispatch_builtin_tool(
+    context: &ToolContext<'_>,
+    canonical: &str,
+    args: &Value,
+) -> Result<Value> {
+    match canonical {
         canonical if goal::is_goal_tool_name(canonical) => {
-            goal::execute_goal_tool(context, &canonical, args).await
+            goal::execute_goal_tool(context, canonical, args).await
         }
         self_status_tool::TOOL_SELF_STATUS => {
             self_status_tool::execute_self_status_tool(context, args).await
@@ -137,6 +184,6 @@ pub async fn execute_builtin_tool(
         "用户世界工具" => user_world_tool::execute_user_world_tool(context, args).await,
         channel_tool::TOOL_CHANNEL => channel_tool::channel_tool(context, args).await,
         "记忆管理" => execute_memory_manager_tool(context, args).await,
-        _ => Err(anyhow!("未知内置工具: {canonical}")),
+        _ => Err(ToolError::NotFound(format!("未知内置工具: {canonical}")).into()),
     }
 }
@@ -0,0 +1,61 @@
+// 预演模式：ToolContext 开启 dry_run 时，写入类工具与高风险命令只返回执行计划，不落盘。
+use super::catalog::resolve_tool_name;
+use super::context::ToolContext;
+use serde_json::Value;
+use std::borrow::Cow;
+use wunder_core::exec_policy::{extract_command_text, is_high_risk_command};
+
+/// True for calls that change files or run a command the exec policy treats as high risk.
+/// Read-only tools and ordinary commands are never rewritten into plans.
+pub(crate) fn is_destructive_tool_call(canonical: &str, args: &Value) -> bool {
+    let write_tools = ["write_file", "edit_file2", "apply_patch"];
+    if write_tools
+        .iter()
+        .any(|name| canonical == resolve_tool_name(name))
+    {
+        return true;
+    }
+    canonical == resolve_tool_name("execute_command")
+        && extract_command_text(args).is_some_and(|command| is_high_risk_command(&command))
+}
+
+/// Force the per-call `dry_run` flag on destructive calls when the context is in plan mode.
+/// Each write tool already honours `dry_run`, so plan mode reuses their preview payloads.
+pub(crate) fn apply_context_dry_run<'a>(
+    context: &ToolContext<'_>,
+    canonical: &str,
+    args: &'a Value,
+) -> Cow<'a, Value> {
+    if !context.dry_run || !is_destructive_tool_call(canonical, args) {
+        return Cow::Borrowed(args);
+    }
+    let Value::Object(map) = args else {
+        return Cow::Borrowed(args);
+    };
+    let mut map = map.clone();
+    map.insert("dry_run".to_string(), Value::Bool(true));
+    Cow::Owned(Value::Object(map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn is_destructive_tool_call_covers_writes_and_high_risk_commands() {
+        let exec = resolve_tool_name("execute_command");
+        let cases = [
+            (resolve_tool_name("write_file"), json!({ "path": "a.txt" })),
+            (resolve_tool_name("apply_patch"), json!({ "input": "" })),
+            (exec.clone(), json!({ "content": "rm -rf build" })),
+            (exec, json!({ "content": "ls -la" })),
+            (resolve_tool_name("read_file"), json!({ "path": "a.txt" })),
+        ];
+        let flags = cases
+            .iter()
+            .map(|(name, args)| is_destructive_tool_call(name, args))
+            .collect::<Vec<_>>();
+        assert_eq!(flags, vec![true, true, true, false, false]);
+    }
+}
@@ -15,6 +15,7 @@ use crate::core::blocking;
 use crate::i18n;
 use crate::path_utils::{is_within_root, normalize_path_for_compare, normalize_target_path};
 use crate::workspace::WorkspaceManager;
+use crate::workspace_ignore::WorkspaceIgnore;
 use anyhow::{anyhow, Result};
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
@@ -255,6 +256,7 @@ enum ReadFailureKind {
     PathInvalid,
     NotFound,
     Binary,
+    Ignored,
 }
 
 #[derive(Clone, Debug)]
@@ -792,12 +794,15 @@ pub(crate) async fn read_files(context: &ToolContext<'_>, args: &Value) -> Resul
     let specs_for_lsp = specs.clone();
     let workspace = context.workspace.clone();
     let extra_roots = collect_read_roots(context);
+    let ignore_files = context.config.workspace.ignore_files.clone();
     let budget_for_task = read_budget;
     let result = blocking::run_fs("tools.file.read", move || {
+        let ignore = WorkspaceIgnore::load(&workspace.workspace_root(&user_id), &ignore_files);
         read_files_inner(
             workspace.as_ref(),
             &user_id,
             &extra_roots,
+            ignore.as_ref(),
             specs,
             budget_for_task,
             dry_run,
@@ -823,6 +828,7 @@ pub(crate) fn read_files_inner(
     workspace: &WorkspaceManager,
     user_id: &str,
     extra_roots: &[PathBuf],
+    ignore: Option<&WorkspaceIgnore>,
     specs: Vec<ReadFileSpec>,
     budget: ReadBudget,
     dry_run: bool,
@@ -883,6 +889,21 @@ pub(crate) fn read_files_inner(
                 Value::String(target.to_string_lossy().to_string()),
             );
         }
+        if let Some(rule) = ignore.and_then(|ignore| ignore.ignore_reason(&target, target.is_dir()))
+        {
+            let params = HashMap::from([("rule".to_string(), rule)]);
+            let message = i18n::t_with_params("tool.read.ignored", &params);
+            outputs.push(format!(">>> {}\n{}", raw_path, message));
+            failures.push(ReadFailure {
+                kind: ReadFailureKind::Ignored,
+            });
+            if let Value::Object(ref mut map) = summary {
+                map.insert("ignored".to_string(), Value::Bool(true));
+                map.insert("error".to_string(), Value::String(message));
+            }
+            summaries.push(summary);
+            continue;
+        }
         if !target.exists() {
             let message = i18n::t("tool.read.not_found");
             outputs.push(format!(">>> {}\n{}", raw_path, message));
@@ -1174,6 +1195,16 @@ fn compact_read_failure_for_model(summary: &Value, failure: &ReadFailure) -> Val
         ReadFailureKind::NotFound => {
             map.insert("reason".to_string(), Value::String("not_found".to_string()));
         }
+        ReadFailureKind::Ignored => {
+            map.insert("reason".to_string(), Value::String("ignored".to_string()));
+            if let Some(error) = summary
+                .get("error")
+                .cloned()
+                .filter(|value| !value.is_null())
+            {
+                map.insert("detail".to_string(), error);
+            }
+        }
         ReadFailureKind::Binary => {
             map.insert("reason".to_string(), Value::String("binary".to_string()));
             if let Some(kind) = summary
@@ -1226,6 +1257,7 @@ fn summarize_read_failure_for_model(failure: &Value) -> String {
     {
         "path_invalid" => format!("{path} 路径无效或无法解析。"),
         "not_found" => format!("{path} 不存在。"),
+        "ignored" => format!("{path} 被工作区忽略规则排除，无权读取。"),
         "binary" => {
             if failure
                 .get("kind")
@@ -1258,6 +1290,13 @@ fn classify_read_failure(failures: &[ReadFailure]) -> (&'static str, String) {
             "请使用当前工作目录相对路径、绝对路径，或直接传入 /workspaces/... 公共路径；若仍失败，请先 list_files 确认真实位置。".to_string(),
         );
     }
+    if all_are(ReadFailureKind::Ignored) {
+        return (
+            "TOOL_READ_PERMISSION_DENIED",
+            "该路径被 .wunderignore 等工作区忽略规则排除；不要尝试绕过，如确需内容请向用户确认。"
+                .to_string(),
+        );
+    }
     if all_are(ReadFailureKind::Binary) {
         return (
             "TOOL_READ_BINARY_FILE",
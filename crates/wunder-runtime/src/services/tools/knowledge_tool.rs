@@ -59,6 +59,8 @@ pub(crate) async fn execute_user_knowledge_tool(
         chunk_overlap: base_info.chunk_overlap,
         top_k: base_info.top_k,
         score_threshold: base_info.score_threshold,
+        search_mode: None,
+        keyword_weight: None,
     };
     if base_type == KnowledgeBaseType::Ragflow {
         return execute_ragflow_knowledge(context, &base, args).await;
@@ -285,21 +287,18 @@ async fn execute_vector_knowledge(
                         let embedding_name = embedding_name.clone();
                         let owner_id = owner_id.map(str::to_string);
                         let keyword = queries.get(index).cloned().unwrap_or_default();
-                        let score_threshold = base.score_threshold;
                         async move {
-                            let mut hits = vector_knowledge::query_chunks_by_vector(
+                            let mut hits = vector_knowledge::query_chunks(
                                 storage.as_ref(),
                                 owner_id.as_deref(),
                                 &base,
                                 &root,
                                 &embedding_name,
                                 &vector,
+                                &keyword,
                                 top_k,
                             )
                             .await?;
-                            if let Some(threshold) = score_threshold {
-                                hits.retain(|hit| hit.score.unwrap_or(0.0) >= f64::from(threshold));
-                            }
                             if hits.len() > top_k {
                                 hits.truncate(top_k);
                             }
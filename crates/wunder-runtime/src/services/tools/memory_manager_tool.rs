@@ -1,3 +1,4 @@
+use super::tool_error::ToolError;
 use super::{build_model_tool_success_with_hint, context::ToolContext};
 use crate::i18n;
 use crate::memory::{build_agent_memory_owner, normalize_agent_memory_scope, MemoryStore};
@@ -302,7 +303,7 @@ fn resolve_related_memory_record_id(
             .get_fragment(user_id, agent_id, &resolved)
             .is_none()
     {
-        return Err(anyhow!(i18n::t("tool.memory_manager.not_found")));
+        return Err(ToolError::NotFound(i18n::t("tool.memory_manager.not_found")).into());
     }
     Ok(Some(resolved))
 }
@@ -393,7 +394,7 @@ pub(crate) async fn execute_memory_manager_tool(
         serde_json::from_value(args.clone()).map_err(|err| anyhow!(err.to_string()))?;
     let action = normalize_memory_manager_action(&payload.action);
     if action.is_empty() {
-        return Err(anyhow!(i18n::t("tool.memory_manager.invalid_action")));
+        return Err(ToolError::InvalidArgs(i18n::t("tool.memory_manager.invalid_action")).into());
     }
 
     let agent_scope = normalize_agent_memory_scope(context.agent_id);
@@ -438,11 +439,14 @@ pub(crate) async fn execute_memory_manager_tool(
                 &payload,
             )?;
             if memory_id.is_empty() {
-                return Err(anyhow!(i18n::t("tool.memory_manager.memory_id_required")));
+                return Err(ToolError::InvalidArgs(i18n::t(
+                    "tool.memory_manager.memory_id_required",
+                ))
+                .into());
             }
             let record = fragment_store
                 .get_fragment(context.user_id, context.agent_id, &memory_id)
-                .ok_or_else(|| anyhow!(i18n::t("tool.memory_manager.not_found")))?;
+                .ok_or_else(|| ToolError::NotFound(i18n::t("tool.memory_manager.not_found")))?;
             build_memory_manager_success(
                 action.as_str(),
                 &agent_scope,
@@ -519,7 +523,10 @@ pub(crate) async fn execute_memory_manager_tool(
                 &payload,
             )?;
             if memory_id.is_empty() {
-                return Err(anyhow!(i18n::t("tool.memory_manager.memory_id_required")));
+                return Err(ToolError::InvalidArgs(i18n::t(
+                    "tool.memory_manager.memory_id_required",
+                ))
+                .into());
             }
             let content = normalize_memory_content(&payload);
             if content.is_empty() {
@@ -588,7 +595,10 @@ pub(crate) async fn execute_memory_manager_tool(
                 &payload,
             )?;
             if memory_id.is_empty() {
-                return Err(anyhow!(i18n::t("tool.memory_manager.memory_id_required")));
+                return Err(ToolError::InvalidArgs(i18n::t(
+                    "tool.memory_manager.memory_id_required",
+                ))
+                .into());
             }
             let fragment_deleted =
                 fragment_store.delete_fragment(context.user_id, context.agent_id, &memory_id);
@@ -661,7 +671,11 @@ pub(crate) async fn execute_memory_manager_tool(
                 Some(i18n::t("tool.memory_manager.note_recall_current_session")),
             )
         }
-        _ => return Err(anyhow!(i18n::t("tool.memory_manager.invalid_action"))),
+        _ => {
+            return Err(
+                ToolError::InvalidArgs(i18n::t("tool.memory_manager.invalid_action")).into(),
+            )
+        }
     };
 
     Ok(response)
@@ -771,6 +785,7 @@ mod tests {
                 command_sessions: None,
                 event_emitter: None,
                 http: &self.http,
+                dry_run: false,
             }
         }
     }
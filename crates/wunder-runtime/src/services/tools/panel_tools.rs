@@ -1,3 +1,4 @@
+use super::tool_error::ToolError;
 use super::{build_model_tool_success, context::ToolContext};
 use crate::i18n;
 use anyhow::{anyhow, Result};
@@ -22,7 +23,7 @@ pub(crate) async fn execute_plan_tool(context: &ToolContext<'_>, args: &Value) -
     let payload: PlanUpdateArgs =
         serde_json::from_value(args.clone()).map_err(|err| anyhow!(err.to_string()))?;
     if payload.plan.is_empty() {
-        return Err(anyhow!(i18n::t("tool.plan.plan_required")));
+        return Err(ToolError::InvalidArgs(i18n::t("tool.plan.plan_required")).into());
     }
     let mut seen_in_progress = false;
     let mut normalized_plan = Vec::new();
@@ -45,7 +46,7 @@ pub(crate) async fn execute_plan_tool(context: &ToolContext<'_>, args: &Value) -
         }));
     }
     if normalized_plan.is_empty() {
-        return Err(anyhow!(i18n::t("tool.plan.plan_required")));
+        return Err(ToolError::InvalidArgs(i18n::t("tool.plan.plan_required")).into());
     }
     let explanation = payload.explanation.and_then(|text| {
         let trimmed = text.trim().to_string();
@@ -142,7 +143,7 @@ pub(crate) async fn execute_question_panel_tool(
 
 fn normalize_question_panel_payload(args: &Value) -> Result<QuestionPanelPayload> {
     let Some(obj) = args.as_object() else {
-        return Err(anyhow!(i18n::t("tool.question_panel.routes_required")));
+        return Err(ToolError::InvalidArgs(i18n::t("tool.question_panel.routes_required")).into());
     };
     let question = obj
         .get("question")
@@ -219,7 +220,7 @@ fn normalize_question_panel_payload(args: &Value) -> Result<QuestionPanelPayload
         });
     }
     if normalized.is_empty() {
-        return Err(anyhow!(i18n::t("tool.question_panel.routes_required")));
+        return Err(ToolError::InvalidArgs(i18n::t("tool.question_panel.routes_required")).into());
     }
     Ok(QuestionPanelPayload {
         question,
@@ -1,11 +1,14 @@
+use super::tool_error::ToolError;
 use super::{build_model_tool_success, ToolContext};
 use crate::schemas::AttachmentPayload;
 use crate::services::chat_media::{detect_media_kind_from_path, process_visual_media_path};
+use crate::workspace_ignore::WorkspaceIgnore;
 use anyhow::{anyhow, Result};
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 use serde::Deserialize;
 use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tokio::io::AsyncReadExt;
 
@@ -40,7 +43,7 @@ pub async fn tool_read_image(context: &ToolContext<'_>, args: &Value) -> Result<
         serde_json::from_value(args.clone()).map_err(|err| anyhow!(err.to_string()))?;
     let raw_path = payload.path.trim();
     if raw_path.is_empty() {
-        return Err(anyhow!(crate::i18n::t("tool.read.no_path")));
+        return Err(ToolError::InvalidArgs(crate::i18n::t("tool.read.no_path")).into());
     }
 
     let extra_roots = super::collect_read_roots(context);
@@ -50,10 +53,22 @@ pub async fn tool_read_image(context: &ToolContext<'_>, args: &Value) -> Result<
         raw_path,
         &extra_roots,
     )?;
+    let ignore = WorkspaceIgnore::load(
+        &context.workspace.workspace_root(context.workspace_id),
+        &context.config.workspace.ignore_files,
+    );
+    if let Some(rule) = ignore.and_then(|ignore| ignore.ignore_reason(&resolved, false)) {
+        let params = HashMap::from([("rule".to_string(), rule)]);
+        return Err(ToolError::PermissionDenied(crate::i18n::t_with_params(
+            "tool.read.ignored",
+            &params,
+        ))
+        .into());
+    }
 
     let metadata = tokio::fs::metadata(&resolved)
         .await
-        .map_err(|_| anyhow!(crate::i18n::t("tool.read.not_found")))?;
+        .map_err(|_| ToolError::NotFound(crate::i18n::t("tool.read.not_found")))?;
     if !metadata.is_file() {
         return Err(anyhow!(crate::i18n::t("tool.read_image.not_file")));
     }
@@ -127,6 +142,8 @@ pub async fn build_followup_user_message(
                     .get("public_path")
                     .and_then(Value::as_str)
                     .map(ToString::to_string),
+                attachment_id: None,
+                metadata: None,
             };
             if let Some(image_url) = crate::services::chat_media::load_image_attachment_data_url(
                 context.workspace.as_ref(),
@@ -218,7 +235,6 @@ mod tests {
     use crate::storage::{SqliteStorage, StorageBackend};
     use crate::workspace::WorkspaceManager;
     use image::{DynamicImage, ImageBuffer, Rgba};
-    use std::collections::HashMap;
     use std::sync::Arc;
     use tempfile::tempdir;
 
@@ -230,6 +246,70 @@ mod tests {
         assert!(!is_read_image_tool_name("read_file"));
     }
 
+    #[tokio::test]
+    async fn read_image_denies_workspace_ignored_paths() {
+        let dir = tempdir().expect("tempdir");
+        let db_path = dir.path().join("read-image-tool.db");
+        let storage: Arc<dyn StorageBackend> =
+            Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+        let workspace = Arc::new(WorkspaceManager::new(
+            dir.path().to_string_lossy().as_ref(),
+            storage.clone(),
+            0,
+            &HashMap::new(),
+        ));
+        let user_root = workspace.ensure_user_root("user").expect("user root");
+        std::fs::write(user_root.join(".wunderignore"), "secret/\n").expect("write ignore");
+        std::fs::create_dir_all(user_root.join("secret")).expect("mkdir secret");
+        let image = ImageBuffer::<Rgba<u8>, _>::from_pixel(2, 2, Rgba([255, 0, 0, 255]));
+        DynamicImage::ImageRgba8(image)
+            .save(user_root.join("secret/shot.png"))
+            .expect("write test image");
+        let lsp_manager = LspManager::new(workspace.clone());
+        let config = Config::default();
+        let a2a_store = A2aStore::default();
+        let skills = SkillRegistry::default();
+        let http = reqwest::Client::new();
+        let context = ToolContext {
+            user_id: "user",
+            session_id: "session",
+            workspace_id: "user",
+            agent_id: None,
+            user_round: None,
+            model_round: None,
+            is_admin: false,
+            storage,
+            orchestrator: None,
+            monitor: None,
+            beeroom_realtime: None,
+            workspace,
+            lsp_manager,
+            config: &config,
+            a2a_store: &a2a_store,
+            skills: &skills,
+            gateway: None,
+            user_world: None,
+            cron_wake_signal: None,
+            user_tool_manager: None,
+            user_tool_bindings: None,
+            user_tool_store: None,
+            request_config_overrides: None,
+            allow_roots: None,
+            read_roots: None,
+            command_sessions: None,
+            event_emitter: None,
+            http: &http,
+            dry_run: false,
+        };
+
+        let err = tool_read_image(&context, &json!({ "path": "secret/shot.png" }))
+            .await
+            .expect_err("ignored image must be denied");
+        let err = err.downcast::<ToolError>().expect("tool error");
+        assert_eq!(err.code(), "TOOL_PERMISSION_DENIED");
+        assert!(err.to_string().contains("`secret/`"), "{err}");
+    }
+
     #[tokio::test]
     async fn followup_user_message_uses_default_text_without_prompt_arg() {
         let dir = tempdir().expect("tempdir");
@@ -276,6 +356,7 @@ mod tests {
             command_sessions: None,
             event_emitter: None,
             http: &http,
+            dry_run: false,
         };
         let image = ImageBuffer::<Rgba<u8>, _>::from_pixel(2, 2, Rgba([255, 0, 0, 255]));
         let mut bytes = Vec::new();
@@ -0,0 +1,278 @@
+// 内容搜索预算：解析 max_matches/max_files/max_candidates/max_scan_bytes/timeout_ms 与 budget 对象，预算只能收紧显式参数；扫描字节计数与截断原因也在此统一。
+use super::MAX_SEARCH_MATCHES;
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub(super) const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+const MAX_TIMEOUT_MS: u64 = 120_000;
+pub(super) const DEFAULT_MAX_CANDIDATES: usize = 4000;
+const MAX_MAX_CANDIDATES: usize = 20_000;
+const MAX_MATCHES_CAP: usize = 2000;
+pub(super) const DEFAULT_MAX_SCAN_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_MAX_SCAN_BYTES: u64 = 1024 * 1024 * 1024;
+const MIN_OUTPUT_BUDGET_BYTES: usize = 2 * 1024;
+const MAX_OUTPUT_BUDGET_BYTES: usize = 4 * 1024 * 1024;
+
+/// Effective search limits; `max_files` of 0 means unlimited.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) struct SearchLimits {
+    pub(super) max_files: usize,
+    pub(super) max_matches: usize,
+    pub(super) max_candidates: usize,
+    pub(super) max_scan_bytes: u64,
+    pub(super) timeout_ms: u64,
+    pub(super) output_budget_bytes: Option<usize>,
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+struct SearchBudget {
+    time_budget_ms: Option<u64>,
+    output_budget_bytes: Option<usize>,
+    max_files: Option<usize>,
+    max_matches: Option<usize>,
+    max_candidates: Option<usize>,
+    max_scan_bytes: Option<u64>,
+}
+
+/// Which limits cut a search short.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct SearchResultFlags {
+    pub(super) timeout_hit: bool,
+    pub(super) file_limit_hit: bool,
+    pub(super) match_limit_hit: bool,
+    pub(super) candidate_limit_hit: bool,
+    pub(super) scan_limit_hit: bool,
+    pub(super) output_budget_hit: bool,
+}
+
+/// Running total of file bytes a search has read, shared by parallel walker threads.
+pub(super) struct ScanByteBudget {
+    scanned: AtomicU64,
+    limit: u64,
+}
+
+impl ScanByteBudget {
+    pub(super) fn new(limit: u64) -> Self {
+        Self {
+            scanned: AtomicU64::new(0),
+            limit,
+        }
+    }
+
+    /// Count a file of `bytes` about to be read; false once the total goes over the limit, so
+    /// a handful of large files cannot cost more I/O than thousands of small ones.
+    pub(super) fn charge(&self, bytes: u64) -> bool {
+        let total = self
+            .scanned
+            .fetch_add(bytes, Ordering::Relaxed)
+            .saturating_add(bytes);
+        total <= self.limit
+    }
+}
+
+pub(super) fn parse_search_limits(args: &Value) -> SearchLimits {
+    let budget = parse_search_budget(args);
+    let mut max_files = parse_optional_usize(args.get("max_files")).unwrap_or(0);
+    let mut max_matches = parse_optional_usize(args.get("max_matches"))
+        .or_else(|| parse_optional_usize(args.get("max_count")))
+        .or_else(|| parse_optional_usize(args.get("head_limit")))
+        .unwrap_or(MAX_SEARCH_MATCHES)
+        .clamp(1, MAX_MATCHES_CAP);
+    let mut max_candidates = parse_optional_usize(args.get("max_candidates"))
+        .unwrap_or(DEFAULT_MAX_CANDIDATES)
+        .clamp(1, MAX_MAX_CANDIDATES);
+    let mut max_scan_bytes = parse_optional_u64(args.get("max_scan_bytes"))
+        .unwrap_or(DEFAULT_MAX_SCAN_BYTES)
+        .clamp(1, MAX_MAX_SCAN_BYTES);
+    let mut timeout_ms = parse_optional_u64(args.get("timeout_ms"))
+        .unwrap_or(DEFAULT_TIMEOUT_MS)
+        .clamp(1, MAX_TIMEOUT_MS);
+    if let Some(limit) = budget.max_files {
+        max_files = if max_files == 0 {
+            limit
+        } else {
+            max_files.min(limit)
+        };
+    }
+    if let Some(limit) = budget.max_matches {
+        max_matches = max_matches.min(limit.clamp(1, MAX_MATCHES_CAP));
+    }
+    if let Some(limit) = budget.max_candidates {
+        max_candidates = max_candidates.min(limit.clamp(1, MAX_MAX_CANDIDATES));
+    }
+    if let Some(limit) = budget.max_scan_bytes {
+        max_scan_bytes = max_scan_bytes.min(limit.clamp(1, MAX_MAX_SCAN_BYTES));
+    }
+    if let Some(limit) = budget.time_budget_ms {
+        timeout_ms = timeout_ms.min(limit.clamp(1, MAX_TIMEOUT_MS));
+    }
+    let output_budget_bytes = budget
+        .output_budget_bytes
+        .map(|value| value.clamp(MIN_OUTPUT_BUDGET_BYTES, MAX_OUTPUT_BUDGET_BYTES));
+    SearchLimits {
+        max_files,
+        max_matches,
+        max_candidates,
+        max_scan_bytes,
+        timeout_ms,
+        output_budget_bytes,
+    }
+}
+
+fn parse_search_budget(args: &Value) -> SearchBudget {
+    let budget_obj = args.get("budget").and_then(Value::as_object);
+    SearchBudget {
+        time_budget_ms: budget_obj
+            .and_then(|obj| obj.get("time_budget_ms"))
+            .and_then(parse_optional_u64_value)
+            .or_else(|| {
+                args.get("time_budget_ms")
+                    .and_then(parse_optional_u64_value)
+            }),
+        output_budget_bytes: budget_obj
+            .and_then(|obj| obj.get("output_budget_bytes"))
+            .and_then(parse_optional_usize_value)
+            .or_else(|| {
+                args.get("output_budget_bytes")
+                    .and_then(parse_optional_usize_value)
+            }),
+        max_files: budget_obj
+            .and_then(|obj| obj.get("max_files"))
+            .and_then(parse_optional_usize_value),
+        max_matches: budget_obj
+            .and_then(|obj| obj.get("max_matches"))
+            .and_then(parse_optional_usize_value),
+        max_candidates: budget_obj
+            .and_then(|obj| obj.get("max_candidates"))
+            .and_then(parse_optional_usize_value),
+        max_scan_bytes: budget_obj
+            .and_then(|obj| obj.get("max_scan_bytes"))
+            .and_then(parse_optional_u64_value),
+    }
+}
+
+pub(super) fn search_truncation_reasons(flags: SearchResultFlags) -> Vec<&'static str> {
+    let mut reasons = Vec::new();
+    if flags.output_budget_hit {
+        reasons.push("output_budget");
+    }
+    if flags.match_limit_hit {
+        reasons.push("max_matches");
+    }
+    if flags.file_limit_hit {
+        reasons.push("max_files");
+    }
+    if flags.candidate_limit_hit {
+        reasons.push("max_candidates");
+    }
+    if flags.scan_limit_hit {
+        reasons.push("max_scan_bytes");
+    }
+    if flags.timeout_hit {
+        reasons.push("timeout");
+    }
+    reasons
+}
+
+pub(super) fn parse_optional_usize(value: Option<&Value>) -> Option<usize> {
+    value.and_then(parse_optional_usize_value)
+}
+
+pub(super) fn parse_optional_u64(value: Option<&Value>) -> Option<u64> {
+    value.and_then(parse_optional_u64_value)
+}
+
+pub(super) fn parse_optional_usize_value(value: &Value) -> Option<usize> {
+    match value {
+        Value::Number(num) => num.as_u64().map(|item| item as usize),
+        Value::String(text) => text.trim().parse::<usize>().ok(),
+        _ => None,
+    }
+}
+
+pub(super) fn parse_optional_u64_value(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(num) => num.as_u64(),
+        Value::String(text) => text.trim().parse::<u64>().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_search_limits_applies_budget_caps() {
+        let limits = parse_search_limits(&json!({
+            "query": "foo",
+            "max_matches": 200,
+            "timeout_ms": 30000,
+            "max_candidates": 5000,
+            "max_files": 100,
+            "budget": {
+                "time_budget_ms": 1200,
+                "max_matches": 20,
+                "max_candidates": 80,
+                "max_files": 10,
+                "max_scan_bytes": 2048,
+                "output_budget_bytes": 4096
+            }
+        }));
+        assert_eq!(
+            limits,
+            SearchLimits {
+                max_files: 10,
+                max_matches: 20,
+                max_candidates: 80,
+                max_scan_bytes: 2048,
+                timeout_ms: 1200,
+                output_budget_bytes: Some(4096),
+            }
+        );
+    }
+
+    #[test]
+    fn budget_only_tightens_explicit_limits() {
+        let limits = parse_search_limits(&json!({
+            "max_scan_bytes": "1024",
+            "max_count": 5,
+            "budget": { "max_scan_bytes": 1u64 << 40, "max_matches": 50, "output_budget_bytes": 1 }
+        }));
+        assert_eq!(limits.max_scan_bytes, 1024);
+        assert_eq!(limits.max_matches, 5);
+        assert_eq!(limits.output_budget_bytes, Some(MIN_OUTPUT_BUDGET_BYTES));
+        assert_eq!(limits.timeout_ms, DEFAULT_TIMEOUT_MS);
+
+        let defaults = parse_search_limits(&json!({ "max_scan_bytes": 0 }));
+        assert_eq!(defaults.max_scan_bytes, 1);
+        assert_eq!(defaults.max_matches, MAX_SEARCH_MATCHES);
+        assert_eq!(defaults.max_files, 0);
+    }
+
+    #[test]
+    fn scan_byte_budget_stops_once_the_total_exceeds_the_limit() {
+        let budget = ScanByteBudget::new(10);
+        assert!(budget.charge(4));
+        assert!(budget.charge(6));
+        assert!(!budget.charge(1));
+        assert!(!ScanByteBudget::new(10).charge(u64::MAX));
+    }
+
+    #[test]
+    fn truncation_reasons_list_every_limit_hit() {
+        let flags = SearchResultFlags {
+            timeout_hit: true,
+            file_limit_hit: false,
+            match_limit_hit: true,
+            candidate_limit_hit: false,
+            scan_limit_hit: true,
+            output_budget_hit: false,
+        };
+        assert_eq!(
+            search_truncation_reasons(flags),
+            vec!["max_matches", "max_scan_bytes", "timeout"]
+        );
+    }
+}
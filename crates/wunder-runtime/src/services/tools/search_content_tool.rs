@@ -1,13 +1,17 @@
 use super::command_options::parse_dry_run;
+use super::search_budget::{
+    parse_optional_u64_value, parse_optional_usize, parse_search_limits, search_truncation_reasons,
+    ScanByteBudget, SearchLimits, SearchResultFlags,
+};
 use super::{
     build_model_tool_success, collect_read_roots, resolve_tool_path, roots_allow_any_path,
     tool_error::build_failed_tool_result, tool_error::ToolErrorMeta, ToolContext, MAX_READ_BYTES,
-    MAX_SEARCH_MATCHES,
 };
 use crate::core::blocking;
 use crate::core::command_utils::{apply_platform_spawn_options, is_not_found_error};
 use crate::core::python_runtime;
 use crate::i18n;
+use crate::workspace_ignore::WorkspaceIgnore;
 use anyhow::{anyhow, Result};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use ignore::{WalkBuilder, WalkState};
@@ -26,13 +30,6 @@ use tokio::process::Command;
 use tokio::time::timeout;
 
 const MAX_CONTEXT_LINES: usize = 20;
-const DEFAULT_TIMEOUT_MS: u64 = 30_000;
-const MAX_TIMEOUT_MS: u64 = 120_000;
-const DEFAULT_MAX_CANDIDATES: usize = 4000;
-const MAX_MAX_CANDIDATES: usize = 20_000;
-const MAX_MATCHES_CAP: usize = 2000;
-const MIN_OUTPUT_BUDGET_BYTES: usize = 2 * 1024;
-const MAX_OUTPUT_BUDGET_BYTES: usize = 4 * 1024 * 1024;
 const BINARY_SAMPLE_BYTES: usize = 4096;
 const CONTROL_BYTE_RATIO_THRESHOLD: f64 = 0.12;
 const RG_BINARY_ENV: &str = "WUNDER_RG_BIN";
@@ -131,20 +128,12 @@ struct SearchParams {
     max_files: usize,
     max_matches: usize,
     max_candidates: usize,
+    max_scan_bytes: u64,
     timeout_ms: u64,
     engine: SearchEngine,
     output_budget_bytes: Option<usize>,
 }
 
-#[derive(Debug, Copy, Clone, Default)]
-struct SearchBudget {
-    time_budget_ms: Option<u64>,
-    output_budget_bytes: Option<usize>,
-    max_files: Option<usize>,
-    max_matches: Option<usize>,
-    max_candidates: Option<usize>,
-}
-
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 struct HighlightSegment {
     text: String,
@@ -200,15 +189,7 @@ struct SearchComputation {
     timeout_hit: bool,
     file_limit_hit: bool,
     match_limit_hit: bool,
-}
-
-#[derive(Debug, Copy, Clone)]
-struct SearchResultFlags {
-    timeout_hit: bool,
-    file_limit_hit: bool,
-    match_limit_hit: bool,
-    candidate_limit_hit: bool,
-    output_budget_hit: bool,
+    scan_limit_hit: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -242,6 +223,7 @@ struct SearchAttemptResult {
     timeout_hit: bool,
     file_limit_hit: bool,
     match_limit_hit: bool,
+    scan_limit_hit: bool,
     resolved_engine: SearchEngine,
     rg_program: Option<String>,
     fallback_reason: Option<String>,
@@ -274,10 +256,13 @@ pub(super) async fn search_content(context: &ToolContext<'_>, args: &Value) -> R
     let user_id = context.workspace_id.to_string();
     let extra_roots = collect_read_roots(context);
     let unrestricted_paths = roots_allow_any_path(&extra_roots);
-    let root = {
+    let ignore_files = context.config.workspace.ignore_files.clone();
+    let (root, ignore) = {
         let path = params.path.clone();
         blocking::run_fs("tools.search.resolve_path", move || {
-            resolve_tool_path(workspace.as_ref(), &user_id, &path, &extra_roots)
+            let root = resolve_tool_path(workspace.as_ref(), &user_id, &path, &extra_roots)?;
+            let ignore = WorkspaceIgnore::load(&workspace.workspace_root(&user_id), &ignore_files);
+            Ok::<_, anyhow::Error>((root, ignore))
         })
         .await?
     };
@@ -358,6 +343,7 @@ pub(super) async fn search_content(context: &ToolContext<'_>, args: &Value) -> R
                 "max_files": params.max_files,
                 "max_matches": params.max_matches,
                 "max_candidates": params.max_candidates,
+                "max_scan_bytes": params.max_scan_bytes,
                 "timeout_ms": params.timeout_ms,
                 "output_budget_bytes": params.output_budget_bytes,
                 "attempts": attempts
@@ -394,6 +380,7 @@ pub(super) async fn search_content(context: &ToolContext<'_>, args: &Value) -> R
             &params,
             attempt,
             file_filter.as_ref(),
+            ignore.as_ref(),
             unrestricted_paths,
             deadline,
             &rg_launch_candidates,
@@ -457,6 +444,7 @@ pub(super) async fn search_content(context: &ToolContext<'_>, args: &Value) -> R
             timeout_hit: any_timeout_hit,
             file_limit_hit: false,
             match_limit_hit: false,
+            scan_limit_hit: false,
             resolved_engine: SearchEngine::Rust,
             rg_program: None,
             fallback_reason: None,
@@ -468,6 +456,7 @@ pub(super) async fn search_content(context: &ToolContext<'_>, args: &Value) -> R
         timeout_hit: selected_timeout_hit,
         file_limit_hit,
         match_limit_hit,
+        scan_limit_hit,
         resolved_engine: _resolved_engine,
         rg_program: _rg_program,
         fallback_reason: _fallback_reason,
@@ -508,6 +497,7 @@ pub(super) async fn search_content(context: &ToolContext<'_>, args: &Value) -> R
             file_limit_hit,
             match_limit_hit,
             candidate_limit_hit,
+            scan_limit_hit,
             output_budget_hit,
         },
         elapsed_ms,
@@ -530,39 +520,15 @@ fn parse_search_params(args: &Value) -> Result<SearchParams> {
     let (query_mode, query_mode_inferred) = parse_query_mode(args, query_source);
     let case_sensitive = parse_case_sensitive(args);
     let (context_before, context_after) = parse_context_windows(args);
-    let budget = parse_search_budget(args);
     let max_depth = parse_optional_usize(args.get("max_depth")).unwrap_or(0);
-    let mut max_files = parse_optional_usize(args.get("max_files")).unwrap_or(0);
-    let mut max_matches = parse_optional_usize(args.get("max_matches"))
-        .or_else(|| parse_optional_usize(args.get("max_count")))
-        .or_else(|| parse_optional_usize(args.get("head_limit")))
-        .unwrap_or(MAX_SEARCH_MATCHES)
-        .clamp(1, MAX_MATCHES_CAP);
-    let mut max_candidates = parse_optional_usize(args.get("max_candidates"))
-        .unwrap_or(DEFAULT_MAX_CANDIDATES)
-        .clamp(1, MAX_MAX_CANDIDATES);
-    let mut timeout_ms = parse_optional_u64(args.get("timeout_ms"))
-        .unwrap_or(DEFAULT_TIMEOUT_MS)
-        .clamp(1, MAX_TIMEOUT_MS);
-    if let Some(limit) = budget.max_files {
-        max_files = if max_files == 0 {
-            limit
-        } else {
-            max_files.min(limit)
-        };
-    }
-    if let Some(limit) = budget.max_matches {
-        max_matches = max_matches.min(limit.clamp(1, MAX_MATCHES_CAP));
-    }
-    if let Some(limit) = budget.max_candidates {
-        max_candidates = max_candidates.min(limit.clamp(1, MAX_MAX_CANDIDATES));
-    }
-    if let Some(limit) = budget.time_budget_ms {
-        timeout_ms = timeout_ms.min(limit.clamp(1, MAX_TIMEOUT_MS));
-    }
-    let output_budget_bytes = budget
-        .output_budget_bytes
-        .map(|value| value.clamp(MIN_OUTPUT_BUDGET_BYTES, MAX_OUTPUT_BUDGET_BYTES));
+    let SearchLimits {
+        max_files,
+        max_matches,
+        max_candidates,
+        max_scan_bytes,
+        timeout_ms,
+        output_budget_bytes,
+    } = parse_search_limits(args);
     let engine = parse_search_engine(args);
 
     Ok(SearchParams {
@@ -579,6 +545,7 @@ fn parse_search_params(args: &Value) -> Result<SearchParams> {
         max_files,
         max_matches,
         max_candidates,
+        max_scan_bytes,
         timeout_ms,
         engine,
         output_budget_bytes,
@@ -603,43 +570,6 @@ fn parse_search_query(args: &Value) -> (String, QuerySource) {
     (pattern, QuerySource::Pattern)
 }
 
-fn parse_search_budget(args: &Value) -> SearchBudget {
-    let budget_obj = args.get("budget").and_then(Value::as_object);
-    SearchBudget {
-        time_budget_ms: budget_obj
-            .and_then(|obj| obj.get("time_budget_ms"))
-            .and_then(parse_optional_u64_value)
-            .or_else(|| {
-                args.get("time_budget_ms")
-                    .and_then(parse_optional_u64_value)
-            }),
-        output_budget_bytes: budget_obj
-            .and_then(|obj| obj.get("output_budget_bytes"))
-            .and_then(parse_optional_usize_value)
-            .or_else(|| {
-                args.get("output_budget_bytes")
-                    .and_then(parse_optional_usize_value)
-            }),
-        max_files: budget_obj
-            .and_then(|obj| obj.get("max_files"))
-            .and_then(parse_optional_usize_value),
-        max_matches: budget_obj
-            .and_then(|obj| obj.get("max_matches"))
-            .and_then(parse_optional_usize_value),
-        max_candidates: budget_obj
-            .and_then(|obj| obj.get("max_candidates"))
-            .and_then(parse_optional_usize_value),
-    }
-}
-
-fn parse_optional_usize(value: Option<&Value>) -> Option<usize> {
-    value.and_then(parse_optional_usize_value)
-}
-
-fn parse_optional_u64(value: Option<&Value>) -> Option<u64> {
-    value.and_then(parse_optional_u64_value)
-}
-
 fn parse_optional_bool_value(value: &Value) -> Option<bool> {
     match value {
         Value::Bool(flag) => Some(*flag),
@@ -656,22 +586,6 @@ fn parse_optional_bool_value(value: &Value) -> Option<bool> {
     }
 }
 
-fn parse_optional_usize_value(value: &Value) -> Option<usize> {
-    match value {
-        Value::Number(num) => num.as_u64().map(|item| item as usize),
-        Value::String(text) => text.trim().parse::<usize>().ok(),
-        _ => None,
-    }
-}
-
-fn parse_optional_u64_value(value: &Value) -> Option<u64> {
-    match value {
-        Value::Number(num) => num.as_u64(),
-        Value::String(text) => text.trim().parse::<u64>().ok(),
-        _ => None,
-    }
-}
-
 fn parse_search_engine(args: &Value) -> SearchEngine {
     let raw = args
         .get("engine")
@@ -1321,6 +1235,7 @@ async fn execute_search_attempt(
     params: &SearchParams,
     attempt: &SearchAttempt,
     file_filter: Option<&GlobSet>,
+    ignore: Option<&WorkspaceIgnore>,
     unrestricted_paths: bool,
     deadline: Instant,
     rg_launch_candidates: &[RgLaunchCandidate],
@@ -1363,6 +1278,7 @@ async fn execute_search_attempt(
         let candidates = rg_candidates.unwrap_or_default();
         let matcher = attempt.matcher.clone();
         let file_filter = file_filter.cloned();
+        let ignore = ignore.cloned();
         let root_for_task = root.to_path_buf();
         let params_for_task = params.clone();
         blocking::run_fs("tools.search.candidates", move || {
@@ -1371,6 +1287,7 @@ async fn execute_search_attempt(
                 candidates,
                 matcher.as_ref(),
                 file_filter.as_ref(),
+                ignore.as_ref(),
                 &params_for_task,
                 unrestricted_paths,
                 deadline,
@@ -1380,6 +1297,7 @@ async fn execute_search_attempt(
     } else {
         let matcher = attempt.matcher.clone();
         let file_filter = file_filter.cloned();
+        let ignore = ignore.cloned();
         let root_for_task = root.to_path_buf();
         let params_for_task = params.clone();
         blocking::run_fs("tools.search.walk", move || {
@@ -1387,6 +1305,7 @@ async fn execute_search_attempt(
                 &root_for_task,
                 matcher.as_ref(),
                 file_filter.as_ref(),
+                ignore.as_ref(),
                 &params_for_task,
                 unrestricted_paths,
                 deadline,
@@ -1401,6 +1320,7 @@ async fn execute_search_attempt(
         timeout_hit: rg_timeout_hit || computation.timeout_hit,
         file_limit_hit: computation.file_limit_hit,
         match_limit_hit: computation.match_limit_hit,
+        scan_limit_hit: computation.scan_limit_hit,
         resolved_engine,
         rg_program,
         fallback_reason,
@@ -1459,6 +1379,7 @@ fn search_content_with_candidates(
     candidates: Vec<PathBuf>,
     matcher: &Regex,
     file_filter: Option<&GlobSet>,
+    ignore: Option<&WorkspaceIgnore>,
     params: &SearchParams,
     _unrestricted_paths: bool,
     deadline: Instant,
@@ -1470,9 +1391,11 @@ fn search_content_with_candidates(
     };
     let mut hits = Vec::new();
     let mut scanned_files = 0usize;
+    let scan_budget = ScanByteBudget::new(params.max_scan_bytes);
     let mut timeout_hit = false;
     let mut file_limit_hit = false;
     let mut match_limit_hit = false;
+    let mut scan_limit_hit = false;
 
     for candidate in candidates {
         if Instant::now() >= deadline {
@@ -1488,12 +1411,22 @@ fn search_content_with_candidates(
                 continue;
             }
         }
+        if ignore.is_some_and(|ignore| ignore.is_ignored(&candidate, false)) {
+            continue;
+        }
 
         scanned_files = scanned_files.saturating_add(1);
         if params.max_files > 0 && scanned_files > params.max_files {
             file_limit_hit = true;
             break;
         }
+        let file_bytes = std::fs::metadata(&candidate)
+            .map(|meta| meta.len())
+            .unwrap_or(0);
+        if !scan_budget.charge(file_bytes) {
+            scan_limit_hit = true;
+            break;
+        }
 
         let remaining = params.max_matches.saturating_sub(hits.len());
         if remaining == 0 {
@@ -1527,6 +1460,7 @@ fn search_content_with_candidates(
         timeout_hit,
         file_limit_hit,
         match_limit_hit,
+        scan_limit_hit,
     })
 }
 
@@ -1534,16 +1468,19 @@ fn search_content_walk(
     root: &Path,
     matcher: &Regex,
     file_filter: Option<&GlobSet>,
+    ignore: Option<&WorkspaceIgnore>,
     params: &SearchParams,
     _unrestricted_paths: bool,
     deadline: Instant,
 ) -> Result<SearchComputation> {
     let hit_list = Arc::new(Mutex::new(Vec::<SearchHit>::new()));
     let scanned_files = Arc::new(AtomicUsize::new(0));
+    let scan_budget = Arc::new(ScanByteBudget::new(params.max_scan_bytes));
     let should_stop = Arc::new(AtomicBool::new(false));
     let timeout_hit = Arc::new(AtomicBool::new(false));
     let match_limit_hit = Arc::new(AtomicBool::new(false));
     let file_limit_hit = Arc::new(AtomicBool::new(false));
+    let scan_limit_hit = Arc::new(AtomicBool::new(false));
 
     let root = Arc::new(root.to_path_buf());
     let display_base = Arc::new(if root.is_dir() {
@@ -1567,14 +1504,23 @@ fn search_content_walk(
     if params.max_depth > 0 {
         walker.max_depth(Some(params.max_depth));
     }
+    if let Some(ignore) = ignore.cloned() {
+        // Prune ignored directories instead of skipping their files one by one.
+        walker.filter_entry(move |entry| {
+            let is_dir = entry.file_type().is_some_and(|kind| kind.is_dir());
+            !ignore.is_ignored(entry.path(), is_dir)
+        });
+    }
 
     walker.build_parallel().run(|| {
         let hit_list = Arc::clone(&hit_list);
         let scanned_files = Arc::clone(&scanned_files);
+        let scan_budget = Arc::clone(&scan_budget);
         let should_stop = Arc::clone(&should_stop);
         let timeout_hit = Arc::clone(&timeout_hit);
         let match_limit_hit = Arc::clone(&match_limit_hit);
         let file_limit_hit = Arc::clone(&file_limit_hit);
+        let scan_limit_hit = Arc::clone(&scan_limit_hit);
         let display_base = Arc::clone(&display_base);
         let matcher = Arc::clone(&matcher);
         let file_filter = file_filter.clone();
@@ -1617,6 +1563,12 @@ fn search_content_walk(
                 should_stop.store(true, Ordering::Relaxed);
                 return WalkState::Quit;
             }
+            let file_bytes = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+            if !scan_budget.charge(file_bytes) {
+                scan_limit_hit.store(true, Ordering::Relaxed);
+                should_stop.store(true, Ordering::Relaxed);
+                return WalkState::Quit;
+            }
 
             let remaining = {
                 let all_hits = match hit_list.lock() {
@@ -1670,6 +1622,7 @@ fn search_content_walk(
         timeout_hit: timeout_hit.load(Ordering::Relaxed),
         file_limit_hit: file_limit_hit.load(Ordering::Relaxed),
         match_limit_hit: match_limit_hit.load(Ordering::Relaxed),
+        scan_limit_hit: scan_limit_hit.load(Ordering::Relaxed),
     })
 }
 
@@ -2141,26 +2094,6 @@ fn compact_search_hits_for_model(hits: &[SearchHit]) -> Vec<Value> {
         .collect()
 }
 
-fn search_truncation_reasons(flags: SearchResultFlags) -> Vec<&'static str> {
-    let mut reasons = Vec::new();
-    if flags.output_budget_hit {
-        reasons.push("output_budget");
-    }
-    if flags.match_limit_hit {
-        reasons.push("max_matches");
-    }
-    if flags.file_limit_hit {
-        reasons.push("max_files");
-    }
-    if flags.candidate_limit_hit {
-        reasons.push("max_candidates");
-    }
-    if flags.timeout_hit {
-        reasons.push("timeout");
-    }
-    reasons
-}
-
 fn build_model_search_success(
     params: &SearchParams,
     attempt: &SearchAttempt,
@@ -2285,6 +2218,9 @@ fn trim_line_endings(bytes: &[u8]) -> &[u8] {
 
 #[cfg(test)]
 mod tests {
+    use super::super::search_budget::{
+        DEFAULT_MAX_CANDIDATES, DEFAULT_MAX_SCAN_BYTES, DEFAULT_TIMEOUT_MS,
+    };
     use super::*;
     use std::io::Write;
     use tempfile::tempdir;
@@ -2389,28 +2325,142 @@ mod tests {
         assert!(parsed.candidate_limit_hit);
     }
 
+    fn write_search_tree(root: &Path) {
+        std::fs::create_dir_all(root.join("src/nested")).expect("create dirs");
+        std::fs::write(
+            root.join("src/lib.rs"),
+            "pub mod nested;\nfn alpha_one() {}\n",
+        )
+        .expect("write lib.rs");
+        std::fs::write(
+            root.join("src/nested/mod.rs"),
+            "// alpha_two lives here\nfn beta() {}\nfn alpha_three() {}\n",
+        )
+        .expect("write mod.rs");
+        std::fs::write(root.join("notes.md"), "alpha in markdown\n").expect("write notes.md");
+    }
+
+    #[test]
+    fn search_content_walk_reports_paths_lines_and_respects_glob() {
+        let dir = tempdir().expect("tempdir");
+        write_search_tree(dir.path());
+        let mut params = parse_search_params(&json!({
+            "query": "alpha_\\w+",
+            "query_mode": "regex",
+            "glob": "*.rs",
+            "engine": "rust",
+        }))
+        .expect("params");
+        params.context_before = 0;
+        params.context_after = 0;
+        let matcher =
+            build_query_matcher(&params.query, params.query_mode, false).expect("matcher");
+        let filter = build_file_filter(&params.file_pattern_items).expect("filter");
+        let deadline = Instant::now() + Duration::from_millis(params.timeout_ms);
+
+        let computation = search_content_walk(
+            dir.path(),
+            &matcher,
+            filter.as_ref(),
+            None,
+            &params,
+            false,
+            deadline,
+        )
+        .expect("walk");
+        let mut hits = computation
+            .hits
+            .into_iter()
+            .map(|hit| (hit.path, hit.line, hit.content))
+            .collect::<Vec<_>>();
+        hits.sort();
+        assert_eq!(
+            hits,
+            vec![
+                ("src/lib.rs".to_string(), 2, "fn alpha_one() {}".to_string()),
+                (
+                    "src/nested/mod.rs".to_string(),
+                    1,
+                    "// alpha_two lives here".to_string()
+                ),
+                (
+                    "src/nested/mod.rs".to_string(),
+                    3,
+                    "fn alpha_three() {}".to_string()
+                ),
+            ]
+        );
+        assert!(!computation.scan_limit_hit);
+    }
+
     #[test]
-    fn parse_search_params_applies_budget_caps() {
+    fn search_content_walk_skips_workspace_ignored_paths() {
+        let dir = tempdir().expect("tempdir");
+        write_search_tree(dir.path());
+        std::fs::write(dir.path().join(".wunderignore"), "src/nested/\n").expect("write ignore");
+        let ignore = WorkspaceIgnore::load(dir.path(), &[".wunderignore".to_string()])
+            .expect("ignore rules");
         let params = parse_search_params(&json!({
-            "query": "foo",
-            "max_matches": 200,
-            "timeout_ms": 30000,
-            "max_candidates": 5000,
-            "max_files": 100,
-            "budget": {
-                "time_budget_ms": 1200,
-                "max_matches": 20,
-                "max_candidates": 80,
-                "max_files": 10,
-                "output_budget_bytes": 4096
-            }
+            "query": "alpha",
+            "glob": "*.rs",
+            "engine": "rust",
         }))
         .expect("params");
-        assert_eq!(params.timeout_ms, 1200);
-        assert_eq!(params.max_matches, 20);
-        assert_eq!(params.max_candidates, 80);
-        assert_eq!(params.max_files, 10);
-        assert_eq!(params.output_budget_bytes, Some(4096));
+        let matcher =
+            build_query_matcher(&params.query, params.query_mode, false).expect("matcher");
+        let filter = build_file_filter(&params.file_pattern_items).expect("filter");
+        let deadline = Instant::now() + Duration::from_millis(params.timeout_ms);
+
+        let walked = search_content_walk(
+            dir.path(),
+            &matcher,
+            filter.as_ref(),
+            Some(&ignore),
+            &params,
+            false,
+            deadline,
+        )
+        .expect("walk");
+        let candidates = search_content_with_candidates(
+            dir.path(),
+            vec![
+                dir.path().join("src/lib.rs"),
+                dir.path().join("src/nested/mod.rs"),
+            ],
+            &matcher,
+            filter.as_ref(),
+            Some(&ignore),
+            &params,
+            false,
+            deadline,
+        )
+        .expect("candidates");
+        for computation in [walked, candidates] {
+            let paths = collect_matched_files(&computation.hits);
+            assert_eq!(paths, vec!["src/lib.rs".to_string()]);
+        }
+    }
+
+    #[test]
+    fn search_content_walk_stops_at_scan_byte_budget() {
+        let dir = tempdir().expect("tempdir");
+        write_search_tree(dir.path());
+        let params = parse_search_params(&json!({
+            "query": "alpha",
+            "engine": "rust",
+            "budget": { "max_scan_bytes": 8 },
+        }))
+        .expect("params");
+        let matcher =
+            build_query_matcher(&params.query, params.query_mode, false).expect("matcher");
+        let deadline = Instant::now() + Duration::from_millis(params.timeout_ms);
+
+        let computation =
+            search_content_walk(dir.path(), &matcher, None, None, &params, false, deadline)
+                .expect("walk");
+        // Every file in the tree is larger than the budget, so nothing gets read.
+        assert!(computation.scan_limit_hit);
+        assert_eq!(computation.hits, Vec::new());
     }
 
     #[test]
@@ -2670,6 +2720,7 @@ mod tests {
             max_files: 0,
             max_matches: 50,
             max_candidates: 4000,
+            max_scan_bytes: DEFAULT_MAX_SCAN_BYTES,
             timeout_ms: 30_000,
             engine: SearchEngine::Rust,
             output_budget_bytes: Some(4096),
@@ -2761,6 +2812,7 @@ mod tests {
             max_files: 0,
             max_matches: 20,
             max_candidates: DEFAULT_MAX_CANDIDATES,
+            max_scan_bytes: DEFAULT_MAX_SCAN_BYTES,
             timeout_ms: DEFAULT_TIMEOUT_MS,
             engine: SearchEngine::Auto,
             output_budget_bytes: None,
@@ -2803,6 +2855,7 @@ mod tests {
             max_files: 0,
             max_matches: 20,
             max_candidates: DEFAULT_MAX_CANDIDATES,
+            max_scan_bytes: DEFAULT_MAX_SCAN_BYTES,
             timeout_ms: DEFAULT_TIMEOUT_MS,
             engine: SearchEngine::Auto,
             output_budget_bytes: Some(4096),
@@ -2862,6 +2915,7 @@ mod tests {
                 file_limit_hit: false,
                 match_limit_hit: false,
                 candidate_limit_hit: false,
+                scan_limit_hit: false,
                 output_budget_hit: true,
             },
             42,
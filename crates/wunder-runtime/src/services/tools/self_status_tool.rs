@@ -1,4 +1,5 @@
 use super::context::ToolContext;
+use crate::monitor::MonitorState;
 use anyhow::{anyhow, Result};
 use serde::Deserialize;
 use serde_json::{json, Map, Value};
@@ -101,6 +102,10 @@ pub(crate) async fn execute_self_status_tool(
     let workspace_context_overflow = context
         .workspace
         .load_session_context_overflow(context.user_id, context.session_id);
+    let turn_snapshot = context
+        .monitor
+        .as_ref()
+        .and_then(|monitor| monitor.get_turn_snapshot(context.session_id));
     let runtime_snapshot = context.orchestrator.as_ref().and_then(|orchestrator| {
         orchestrator.get_tool_session_runtime_snapshot(context.session_id)
     });
@@ -156,6 +161,11 @@ pub(crate) async fn execute_self_status_tool(
             "updated_time": monitor_updated_time,
         },
         "thread": runtime_snapshot,
+        "turn": build_turn_status(
+            turn_snapshot.as_ref(),
+            context.user_round,
+            context.model_round,
+        ),
         "rounds": {
             "user_rounds": user_rounds,
             "model_rounds_peak": event_stats.latest_model_round,
@@ -208,6 +218,53 @@ pub(crate) async fn execute_self_status_tool(
     Ok(response)
 }
 
+/// Live progress of the current user round, so the model can wrap up before hitting limits.
+fn build_turn_status(
+    snapshot: Option<&Value>,
+    user_round: Option<i64>,
+    model_round: Option<i64>,
+) -> Value {
+    let Some(snapshot) = snapshot else {
+        return json!({
+            "available": false,
+            "user_round": user_round,
+            "model_round": model_round,
+        });
+    };
+    let status = snapshot.get("status").and_then(Value::as_str).unwrap_or("");
+    let active = matches!(
+        status,
+        MonitorState::STATUS_RUNNING
+            | MonitorState::STATUS_WAITING
+            | MonitorState::STATUS_CANCELLING
+            | MonitorState::STATUS_QUEUED
+    );
+    let tools = snapshot
+        .get("tool_calls")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    let mut by_tool = BTreeMap::<&str, usize>::new();
+    for tool in tools.iter().filter_map(Value::as_str) {
+        *by_tool.entry(tool).or_default() += 1;
+    }
+    json!({
+        "available": true,
+        "active": active,
+        "status": status,
+        "stage": snapshot.get("stage").cloned().unwrap_or(Value::Null),
+        "started_at": snapshot.get("start_time").cloned().unwrap_or(Value::Null),
+        "elapsed_s": snapshot.get("elapsed_s").cloned().unwrap_or(Value::Null),
+        "cancel_requested": snapshot.get("cancel_requested").cloned().unwrap_or(Value::Null),
+        "user_round": user_round.or_else(|| value_to_i64(snapshot.get("user_rounds"))),
+        "model_round": model_round,
+        "tool_calls": {
+            "total": tools.len(),
+            "by_tool": by_tool,
+        },
+    })
+}
+
 fn clamp_events_limit(raw: Option<usize>) -> usize {
     raw.unwrap_or(DEFAULT_EVENTS_LIMIT)
         .clamp(1, MAX_EVENTS_LIMIT)
@@ -369,8 +426,13 @@ fn update_round_peak(target: &mut Option<i64>, incoming: Option<i64>) {
 
 #[cfg(test)]
 mod tests {
-    use super::{collect_event_stats, DetailLevel, TOOL_SELF_STATUS_ALIAS};
+    use super::{build_turn_status, collect_event_stats, DetailLevel, TOOL_SELF_STATUS_ALIAS};
+    use crate::config::ObservabilityConfig;
+    use crate::monitor::MonitorState;
+    use crate::storage::{SqliteStorage, StorageBackend};
     use serde_json::json;
+    use std::sync::Arc;
+    use tempfile::tempdir;
 
     #[test]
     fn detail_level_defaults_to_standard() {
@@ -409,6 +471,61 @@ mod tests {
         assert_eq!(stats.last_event_id, Some(3));
     }
 
+    #[test]
+    fn turn_status_reports_active_session_progress() {
+        let temp = tempdir().expect("tempdir");
+        let db_path = temp.path().join("turn-status.db");
+        let storage: Arc<dyn StorageBackend> =
+            Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+        storage.ensure_initialized().expect("initialize storage");
+        let monitor = MonitorState::new(
+            storage,
+            ObservabilityConfig::default(),
+            temp.path().to_string_lossy().to_string(),
+        );
+        let session_id = "sess-turn-status";
+        let user_round = monitor.register(session_id, "user", "agent", "question", true, false);
+        for tool in ["read_file", "read_file", "self_status"] {
+            monitor.record_event(
+                session_id,
+                "tool_call",
+                &json!({ "tool": tool, "user_round": user_round, "model_round": 2 }),
+            );
+        }
+        // A call from an earlier round must not be counted.
+        monitor.record_event(
+            session_id,
+            "tool_call",
+            &json!({ "tool": "write_file", "user_round": user_round - 1 }),
+        );
+        let events_before = monitor.get_detail(session_id).expect("detail")["events"].clone();
+
+        let snapshot = monitor.get_turn_snapshot(session_id);
+        let status = build_turn_status(snapshot.as_ref(), Some(user_round), Some(2));
+        assert_eq!(status["available"], true);
+        assert_eq!(status["active"], true);
+        assert_eq!(status["status"], MonitorState::STATUS_RUNNING);
+        assert_eq!(status["stage"], "tool_call");
+        assert!(status["elapsed_s"].as_f64().is_some());
+        assert!(status["started_at"].is_string());
+        assert_eq!(status["user_round"], user_round);
+        assert_eq!(status["model_round"], 2);
+        assert_eq!(status["tool_calls"]["total"], 3);
+        assert_eq!(status["tool_calls"]["by_tool"]["read_file"], 2);
+        assert!(status["tool_calls"]["by_tool"]["write_file"].is_null());
+        assert_eq!(
+            monitor.get_detail(session_id).expect("detail")["events"],
+            events_before
+        );
+
+        let missing = build_turn_status(
+            monitor.get_turn_snapshot("sess-missing").as_ref(),
+            None,
+            None,
+        );
+        assert_eq!(missing["available"], false);
+    }
+
     #[test]
     fn alias_is_stable() {
         assert_eq!(TOOL_SELF_STATUS_ALIAS, "self_status");
@@ -300,6 +300,9 @@ pub(crate) fn prepare_child_session(
             agent_prompt,
             preview_skill,
             attachments: None,
+            max_rounds: None,
+            cite: false,
+            remember_attachments: false,
             allow_queue: true,
             is_admin: context.is_admin,
             enforce_runtime_queue: false,
@@ -186,6 +186,9 @@ pub(crate) async fn sessions_send(context: &ToolContext<'_>, args: &Value) -> Re
             .map(|record| record.preview_skill)
             .unwrap_or(false),
         attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
         allow_queue: true,
         is_admin: context.is_admin,
         enforce_runtime_queue: false,
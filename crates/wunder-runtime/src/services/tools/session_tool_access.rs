@@ -6,6 +6,7 @@ use crate::skills::SkillRegistry;
 use crate::storage::{ChatSessionRecord, StorageBackend, UserAgentAccessRecord, UserAgentRecord};
 use crate::tools::resolve_tool_name;
 use crate::user_store::build_default_agent_record_from_storage;
+use crate::user_tools::UserToolBindings;
 use anyhow::{anyhow, Result};
 use std::collections::HashSet;
 
@@ -182,6 +183,23 @@ pub(crate) fn finalize_tool_names(mut allowed: HashSet<String>) -> Vec<String> {
     list
 }
 
+/// `tool_names` for a request on a resumed session: empty when the session has no overrides
+/// (the orchestrator then uses every available tool), otherwise the available tools narrowed
+/// to the persisted `tool_overrides`.
+pub fn resolve_session_request_tool_names(
+    config: &Config,
+    skills: &SkillRegistry,
+    user_tool_bindings: Option<&UserToolBindings>,
+    record: &ChatSessionRecord,
+) -> Vec<String> {
+    if record.tool_overrides.is_empty() {
+        return Vec::new();
+    }
+    let available = collect_available_tool_names(config, skills, user_tool_bindings);
+    let overrides = normalize_tool_overrides(record.tool_overrides.clone());
+    finalize_tool_names(apply_tool_overrides(available, &overrides, config, skills))
+}
+
 pub(crate) fn build_effective_tool_names(
     context: &ToolContext<'_>,
     user_id: &str,
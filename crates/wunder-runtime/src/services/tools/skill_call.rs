@@ -1,3 +1,4 @@
+use super::tool_error::ToolError;
 use super::{build_model_tool_success, context::ToolContext};
 use crate::config::Config;
 use crate::i18n;
@@ -52,7 +53,7 @@ pub(crate) async fn execute_skill_call(context: &ToolContext<'_>, args: &Value)
         .trim()
         .to_string();
     if raw_name.is_empty() {
-        return Err(anyhow!(i18n::t("tool.skill_call.name_required")));
+        return Err(ToolError::InvalidArgs(i18n::t("tool.skill_call.name_required")).into());
     }
 
     let mut selected: Option<SkillSpec> = None;
@@ -380,6 +381,7 @@ mod tests {
             command_sessions: None,
             event_emitter: None,
             http: &http,
+            dry_run: false,
         };
 
         let result = execute_skill_call(&context, &json!({ "name": "draft_skill" }))
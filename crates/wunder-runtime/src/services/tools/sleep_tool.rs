@@ -1,3 +1,4 @@
+use super::tool_error::ToolError;
 use super::{build_model_tool_success, ToolContext};
 use anyhow::{anyhow, Result};
 use serde::Deserialize;
@@ -86,10 +87,10 @@ fn resolve_sleep_seconds(payload: &SleepArgs) -> Result<f64> {
         .or(payload.wait_s)
         .unwrap_or(0.0);
     if !seconds.is_finite() || seconds <= 0.0 {
-        return Err(anyhow!(crate::i18n::t("tool.sleep.invalid_seconds")));
+        return Err(ToolError::InvalidArgs(crate::i18n::t("tool.sleep.invalid_seconds")).into());
     }
     if seconds > MAX_SLEEP_SECONDS {
-        return Err(anyhow!(crate::i18n::t("tool.sleep.seconds_too_large")));
+        return Err(ToolError::InvalidArgs(crate::i18n::t("tool.sleep.seconds_too_large")).into());
     }
     Ok(seconds)
 }
@@ -123,4 +124,17 @@ mod tests {
         );
         assert_eq!(extract_sleep_seconds(&json!({ "wait_s": 4 })), Some(4.0));
     }
+
+    #[test]
+    fn resolve_sleep_seconds_rejects_bad_durations_as_invalid_args() {
+        let codes = [json!({}), json!({ "seconds": MAX_SLEEP_SECONDS + 1.0 })]
+            .into_iter()
+            .map(|args| {
+                let payload: SleepArgs = serde_json::from_value(args).expect("sleep args");
+                let err = resolve_sleep_seconds(&payload).expect_err("duration should fail");
+                ToolError::from(err).code()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(codes, vec!["TOOL_INVALID_ARGS", "TOOL_INVALID_ARGS"]);
+    }
 }
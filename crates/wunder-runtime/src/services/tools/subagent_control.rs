@@ -1074,6 +1074,7 @@ mod tests {
                 command_sessions: None,
                 event_emitter: None,
                 http: &self.http,
+                dry_run: false,
             }
         }
     }
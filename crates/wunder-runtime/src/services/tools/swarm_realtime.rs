@@ -702,6 +702,7 @@ mod tests {
                 command_sessions: None,
                 event_emitter,
                 http: &self.http,
+                dry_run: false,
             }
         }
 
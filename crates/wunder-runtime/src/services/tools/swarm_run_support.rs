@@ -1,12 +1,17 @@
 use super::{
-    build_model_tool_success, build_model_tool_success_with_hint, compact_swarm_run_result_preview,
-    current_agent_id, dedupe_non_empty_strings, is_swarm_run_failed, is_swarm_run_terminal,
-    normalize_swarm_poll_interval, now_ts, ToolContext,
+    build_agent_swarm_args_failure, build_model_tool_success, build_model_tool_success_with_hint,
+    compact_swarm_run_result_preview, current_agent_id, dedupe_non_empty_strings,
+    is_swarm_run_failed, is_swarm_run_terminal, normalize_swarm_poll_interval, now_ts, ToolContext,
 };
 use crate::i18n;
+use crate::knowledge::resolve_llm_config;
+use crate::llm::{build_llm_client, is_llm_configured, ChatMessage};
 use crate::services::swarm::beeroom::claim_mother_agent as claim_swarm_mother_agent;
+use crate::services::swarm::merger::{
+    aggregate_worker_outputs, SwarmAggregation, SwarmWorkerOutput,
+};
 use crate::storage::{TeamRunRecord, TeamTaskRecord};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde_json::{json, Value};
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
@@ -14,9 +19,12 @@ use uuid::Uuid;
 
 #[derive(Debug, Clone)]
 struct SwarmRunSnapshot {
+    run_id: String,
+    agent_id: Option<String>,
     status: String,
     terminal: bool,
     failed: bool,
+    result: Option<String>,
     payload: Value,
 }
 
@@ -101,12 +109,42 @@ pub(crate) fn create_swarm_team_task_record(
     }
 }
 
+/// Read the `aggregation` argument of wait/batch_send; an unknown name is an argument error
+/// returned as the ready-made tool failure payload.
+pub(crate) fn parse_swarm_aggregation(
+    action: &str,
+    args: &Value,
+    example: Value,
+) -> std::result::Result<SwarmAggregation, Value> {
+    let Some(value) = args
+        .get("aggregation")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(SwarmAggregation::default());
+    };
+    SwarmAggregation::parse(value).ok_or_else(|| {
+        build_agent_swarm_args_failure(
+            action,
+            "TOOL_ARGS_INVALID",
+            format!("agent_swarm {action} aggregation is invalid: {value}"),
+            "aggregation must be concat, vote or reduce.",
+            &[],
+            example,
+            args,
+            json!({ "allowed_aggregations": ["concat", "vote", "reduce"] }),
+        )
+    })
+}
+
 pub(crate) async fn wait_for_swarm_runs(
     context: &ToolContext<'_>,
     run_ids: &[String],
     wait_seconds: Option<f64>,
     poll_interval_seconds: f64,
     emit_progress: bool,
+    aggregation: SwarmAggregation,
 ) -> Result<Value> {
     let run_ids = dedupe_non_empty_strings(run_ids.to_vec());
     if run_ids.is_empty() {
@@ -168,6 +206,29 @@ pub(crate) async fn wait_for_swarm_runs(
             } else {
                 "running"
             };
+            let aggregated = if all_finished {
+                aggregate_swarm_runs(context, aggregation, &snapshots).await
+            } else {
+                Value::Null
+            };
+            if emit_progress && all_finished {
+                if let Some(emitter) = context.event_emitter.as_ref() {
+                    emitter.emit(
+                        "progress",
+                        json!({
+                            "stage": "swarm_wait",
+                            "summary": i18n::t("monitor.summary.swarm_wait"),
+                            "state": state,
+                            "aggregation": aggregation.as_str(),
+                            "total": total,
+                            "done_total": done_total,
+                            "success_total": success_total,
+                            "failed_total": failed_total,
+                            "elapsed_s": elapsed_s,
+                        }),
+                    );
+                }
+            }
             let items = snapshots
                 .into_iter()
                 .map(|item| item.payload)
@@ -199,6 +260,7 @@ pub(crate) async fn wait_for_swarm_runs(
                         "running": running_total,
                     },
                     "items": items,
+                    "aggregation": aggregated,
                 }),
                 if timed_out || !all_finished {
                     Some(
@@ -234,6 +296,40 @@ pub(crate) async fn wait_for_swarm_runs(
     }
 }
 
+/// Combine the finished worker results; `reduce` asks the default model to merge them.
+async fn aggregate_swarm_runs(
+    context: &ToolContext<'_>,
+    aggregation: SwarmAggregation,
+    snapshots: &[SwarmRunSnapshot],
+) -> Value {
+    let outputs = snapshots
+        .iter()
+        .map(|item| SwarmWorkerOutput {
+            run_id: item.run_id.clone(),
+            agent_id: item.agent_id.clone(),
+            success: item.status == "success",
+            text: item.result.clone().unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    aggregate_worker_outputs(aggregation, &outputs, |prompt| async move {
+        let llm_config = resolve_llm_config(context.config, None)
+            .filter(is_llm_configured)
+            .ok_or_else(|| anyhow!("no model configured for swarm reduce"))?;
+        let messages = [ChatMessage {
+            role: "user".to_string(),
+            content: json!(prompt),
+            reasoning_content: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }];
+        let response = build_llm_client(&llm_config, context.http.clone())
+            .complete(&messages)
+            .await?;
+        Ok(response.content)
+    })
+    .await
+}
+
 fn collect_swarm_run_snapshots(
     context: &ToolContext<'_>,
     run_ids: &[String],
@@ -246,9 +342,12 @@ fn collect_swarm_run_snapshots(
             let terminal = is_swarm_run_terminal(&status);
             let failed = is_swarm_run_failed(&status);
             output.push(SwarmRunSnapshot {
+                run_id: record.run_id.clone(),
+                agent_id: record.agent_id.clone(),
                 status,
                 terminal,
                 failed,
+                result: record.result.clone(),
                 payload: json!({
                     "run_id": record.run_id,
                     "status": record.status,
@@ -266,9 +365,12 @@ fn collect_swarm_run_snapshots(
             });
         } else {
             output.push(SwarmRunSnapshot {
+                run_id: run_id.clone(),
+                agent_id: None,
                 status: "not_found".to_string(),
                 terminal: true,
                 failed: true,
+                result: None,
                 payload: json!({
                     "run_id": run_id,
                     "status": "not_found",
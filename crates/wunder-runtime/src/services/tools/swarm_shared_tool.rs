@@ -0,0 +1,106 @@
+// 智能体蜂群共享状态动作：share_set/share_get 在当前智能体所属蜂巢内读写共享数据，其他蜂巢不可见。
+use super::agent_swarm_tool::swarm_hive_arg;
+use super::{
+    build_agent_swarm_args_failure, build_model_tool_success, current_agent_id, ToolContext,
+};
+use crate::i18n;
+use crate::services::swarm::beeroom::resolve_swarm_hive_id;
+use crate::services::swarm::shared_state::{
+    get_hive_shared_state, list_hive_shared_state, put_hive_shared_state,
+};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+pub(crate) async fn agent_swarm_share_set(
+    context: &ToolContext<'_>,
+    args: &Value,
+) -> Result<Value> {
+    let (user_id, hive_id) = resolve_share_scope(context, args)?;
+    let key = share_key_arg(args);
+    let value = args.get("value").filter(|value| !value.is_null());
+    let (Some(key), Some(value)) = (key, value) else {
+        return Ok(build_agent_swarm_args_failure(
+            "share_set",
+            "TOOL_ARGS_MISSING_FIELD",
+            "agent_swarm share_set requires key and value",
+            "Provide a key and the value to share with the other agents of this hive.",
+            &["key", "value"],
+            json!({ "action": "share_set", "key": "plan", "value": "Split the report by chapter." }),
+            args,
+            json!({}),
+        ));
+    };
+    let entry = put_hive_shared_state(
+        context.storage.as_ref(),
+        &user_id,
+        &hive_id,
+        key,
+        value,
+        current_agent_id(context).as_deref(),
+    )?;
+    Ok(build_model_tool_success(
+        "share_set",
+        "completed",
+        format!("Shared `{}` with hive {hive_id}.", entry.key),
+        json!({ "hive_id": hive_id, "entry": entry.to_value() }),
+    ))
+}
+
+/// Read one key, or list every shared entry of the hive when no key is given.
+pub(crate) async fn agent_swarm_share_get(
+    context: &ToolContext<'_>,
+    args: &Value,
+) -> Result<Value> {
+    let (user_id, hive_id) = resolve_share_scope(context, args)?;
+    let storage = context.storage.as_ref();
+    if let Some(key) = share_key_arg(args) {
+        let entry = get_hive_shared_state(storage, &user_id, &hive_id, key)?;
+        let summary = if entry.is_some() {
+            format!("Loaded shared `{key}` from hive {hive_id}.")
+        } else {
+            format!("Hive {hive_id} has no shared `{key}`.")
+        };
+        return Ok(build_model_tool_success(
+            "share_get",
+            if entry.is_some() {
+                "completed"
+            } else {
+                "not_found"
+            },
+            summary,
+            json!({ "hive_id": hive_id, "entry": entry.map(|entry| entry.to_value()) }),
+        ));
+    }
+    let entries = list_hive_shared_state(storage, &user_id, &hive_id)?;
+    Ok(build_model_tool_success(
+        "share_get",
+        "completed",
+        format!("Hive {hive_id} has {} shared entries.", entries.len()),
+        json!({
+            "hive_id": hive_id,
+            "entries": entries.iter().map(|entry| entry.to_value()).collect::<Vec<_>>(),
+        }),
+    ))
+}
+
+/// The hive follows the calling agent; an explicit hive_id must match it.
+fn resolve_share_scope(context: &ToolContext<'_>, args: &Value) -> Result<(String, String)> {
+    let user_id = context.user_id.trim();
+    if user_id.is_empty() {
+        return Err(anyhow!(i18n::t("error.user_id_required")));
+    }
+    let hive_id = resolve_swarm_hive_id(
+        context.storage.as_ref(),
+        user_id,
+        current_agent_id(context).as_deref(),
+        swarm_hive_arg(args),
+    )?;
+    Ok((user_id.to_string(), hive_id))
+}
+
+fn share_key_arg(args: &Value) -> Option<&str> {
+    args.get("key")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
@@ -211,6 +211,7 @@ async fn write_file_uses_orchestration_run_root_for_round_short_paths() {
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let result = file_tool::write_file(
@@ -231,6 +232,434 @@ async fn write_file_uses_orchestration_run_root_for_round_short_paths() {
         .exists());
 }
 
+#[tokio::test]
+async fn dry_run_context_write_file_creates_nothing() {
+    let dir = tempdir().expect("tempdir");
+    let db_path = dir.path().join("state.sqlite3");
+    let storage = Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+    let workspace_root = dir.path().join("workspace");
+    let workspace = Arc::new(WorkspaceManager::new(
+        workspace_root.to_string_lossy().as_ref(),
+        storage.clone(),
+        0,
+        &HashMap::new(),
+    ));
+    let mut config = Config::default();
+    // Desktop mode writes in-process, so the plan comes from the local write_file path.
+    config.server.mode = "desktop".to_string();
+    let a2a_store = A2aStore::default();
+    let skills = SkillRegistry::default();
+    let http = reqwest::Client::new();
+    let lsp_manager = LspManager::new(workspace.clone());
+    let context = ToolContext {
+        user_id: "alice",
+        session_id: "sess_plan",
+        workspace_id: "workspace-test",
+        agent_id: None,
+        user_round: None,
+        model_round: None,
+        is_admin: false,
+        storage: storage.clone(),
+        orchestrator: None,
+        monitor: None,
+        beeroom_realtime: None,
+        workspace: workspace.clone(),
+        lsp_manager,
+        config: &config,
+        a2a_store: &a2a_store,
+        skills: &skills,
+        gateway: None,
+        user_world: None,
+        cron_wake_signal: None,
+        user_tool_manager: None,
+        user_tool_bindings: None,
+        user_tool_store: None,
+        request_config_overrides: None,
+        allow_roots: None,
+        read_roots: None,
+        command_sessions: None,
+        event_emitter: None,
+        http: &http,
+        dry_run: true,
+    };
+
+    let result = execute_tool(
+        &context,
+        "write_file",
+        &json!({ "path": "notes/plan.txt", "content": "should not land" }),
+    )
+    .await
+    .expect("dry-run write");
+
+    assert_eq!(
+        (
+            result["ok"].clone(),
+            result["state"].clone(),
+            result["data"]["dry_run"].clone()
+        ),
+        (json!(true), json!("dry_run"), json!(true))
+    );
+
+    // The sandbox server calls the builtin entry directly; it must honour dry-run as well.
+    let result = execute_builtin_tool(
+        &context,
+        "write_file",
+        &json!({ "path": "notes/plan.txt", "content": "should not land" }),
+    )
+    .await
+    .expect("dry-run builtin write");
+    assert_eq!(result["state"], json!("dry_run"));
+    assert!(!workspace_root.join("workspace-test").join("notes").exists());
+}
+
+/// Owns what a `ToolContext` borrows, for tests that drive `execute_tool` end to end.
+struct DispatchFixture {
+    _dir: tempfile::TempDir,
+    storage: Arc<SqliteStorage>,
+    workspace: Arc<WorkspaceManager>,
+    monitor: Arc<crate::monitor::MonitorState>,
+    config: Config,
+    a2a_store: A2aStore,
+    skills: SkillRegistry,
+    http: reqwest::Client,
+}
+
+impl DispatchFixture {
+    fn new() -> Self {
+        let dir = tempdir().expect("tempdir");
+        let db_path = dir.path().join("state.sqlite3");
+        let storage = Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+        storage.ensure_initialized().expect("initialize storage");
+        let workspace = Arc::new(WorkspaceManager::new(
+            dir.path().join("workspace").to_string_lossy().as_ref(),
+            storage.clone(),
+            0,
+            &HashMap::new(),
+        ));
+        let monitor = Arc::new(crate::monitor::MonitorState::new(
+            storage.clone(),
+            crate::config::ObservabilityConfig::default(),
+            dir.path().to_string_lossy().to_string(),
+        ));
+        Self {
+            _dir: dir,
+            storage,
+            workspace,
+            monitor,
+            config: Config::default(),
+            a2a_store: A2aStore::default(),
+            skills: SkillRegistry::default(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    fn context(&self, session_id: &'static str) -> ToolContext<'_> {
+        ToolContext {
+            user_id: "alice",
+            session_id,
+            workspace_id: "alice",
+            agent_id: None,
+            user_round: None,
+            model_round: None,
+            is_admin: false,
+            storage: self.storage.clone(),
+            orchestrator: None,
+            monitor: Some(self.monitor.clone()),
+            beeroom_realtime: None,
+            workspace: self.workspace.clone(),
+            lsp_manager: LspManager::new(self.workspace.clone()),
+            config: &self.config,
+            a2a_store: &self.a2a_store,
+            skills: &self.skills,
+            gateway: None,
+            user_world: None,
+            cron_wake_signal: None,
+            user_tool_manager: None,
+            user_tool_bindings: None,
+            user_tool_store: None,
+            request_config_overrides: None,
+            allow_roots: None,
+            read_roots: None,
+            command_sessions: None,
+            event_emitter: None,
+            http: &self.http,
+            dry_run: false,
+        }
+    }
+}
+
+#[tokio::test]
+async fn execute_tool_reports_unknown_tool_as_not_found() {
+    let fixture = DispatchFixture::new();
+    let error = execute_tool(&fixture.context("sess_unknown"), "no_such_tool", &json!({}))
+        .await
+        .expect_err("unknown tool");
+    assert!(matches!(error, ToolError::NotFound(_)), "{error:?}");
+    assert_eq!(error.exit_code(), 127);
+}
+
+#[tokio::test]
+async fn execute_tool_refuses_calls_of_a_cancelled_session() {
+    let fixture = DispatchFixture::new();
+    fixture
+        .monitor
+        .register("sess_cancelled", "alice", "", "question", true, false);
+    assert!(fixture.monitor.cancel("sess_cancelled"));
+    let error = execute_tool(
+        &fixture.context("sess_cancelled"),
+        "sleep",
+        &json!({ "seconds": 1 }),
+    )
+    .await
+    .expect_err("cancelled session");
+    assert!(matches!(error, ToolError::Cancelled), "{error:?}");
+    assert_eq!(error.exit_code(), 130);
+}
+
+#[tokio::test]
+async fn execute_tool_with_timeout_reports_an_elapsed_call_as_timeout() {
+    let fixture = DispatchFixture::new();
+    let error = execute_tool_with_timeout(
+        &fixture.context("sess_timeout"),
+        "sleep",
+        &json!({ "seconds": 30 }),
+        Some(std::time::Duration::from_millis(20)),
+    )
+    .await
+    .expect_err("timed out call");
+    assert!(matches!(error, ToolError::Timeout), "{error:?}");
+    assert_eq!(error.exit_code(), 124);
+}
+
+#[tokio::test]
+async fn read_image_of_a_workspace_ignored_path_is_permission_denied() {
+    let fixture = DispatchFixture::new();
+    let user_root = fixture
+        .workspace
+        .ensure_user_root("alice")
+        .expect("user root");
+    std::fs::write(user_root.join(".wunderignore"), "secret/\n").expect("write ignore");
+    std::fs::create_dir_all(user_root.join("secret")).expect("mkdir secret");
+    std::fs::write(user_root.join("secret/shot.png"), b"png").expect("write image");
+    let error = execute_tool(
+        &fixture.context("sess_read_image"),
+        "read_image",
+        &json!({ "path": "secret/shot.png" }),
+    )
+    .await
+    .expect_err("ignored path");
+    assert!(matches!(error, ToolError::PermissionDenied(_)), "{error:?}");
+    assert_eq!(error.exit_code(), 77);
+}
+
+#[tokio::test]
+async fn ptc_script_only_sees_allowlisted_environment() {
+    let dir = tempdir().expect("tempdir");
+    let db_path = dir.path().join("state.sqlite3");
+    let storage = Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+    let workspace_root = dir.path().join("workspace");
+    let workspace = Arc::new(WorkspaceManager::new(
+        workspace_root.to_string_lossy().as_ref(),
+        storage.clone(),
+        0,
+        &HashMap::new(),
+    ));
+    std::env::set_var("WUNDER_PTC_ENV_TEST_SECRET", "leaked");
+    std::env::set_var("WUNDER_PTC_ENV_TEST_SHARED", "visible");
+    let mut config = Config::default();
+    config.server.mode = "desktop".to_string();
+    config.security.env_passthrough = ["PATH", "HOME", "WUNDER_PTC_ENV_TEST_SHARED"]
+        .map(str::to_string)
+        .to_vec();
+    let a2a_store = A2aStore::default();
+    let skills = SkillRegistry::default();
+    let http = reqwest::Client::new();
+    let lsp_manager = LspManager::new(workspace.clone());
+    let context = ToolContext {
+        user_id: "alice",
+        session_id: "sess_ptc_env",
+        workspace_id: "workspace-test",
+        agent_id: None,
+        user_round: None,
+        model_round: None,
+        is_admin: false,
+        storage: storage.clone(),
+        orchestrator: None,
+        monitor: None,
+        beeroom_realtime: None,
+        workspace: workspace.clone(),
+        lsp_manager,
+        config: &config,
+        a2a_store: &a2a_store,
+        skills: &skills,
+        gateway: None,
+        user_world: None,
+        cron_wake_signal: None,
+        user_tool_manager: None,
+        user_tool_bindings: None,
+        user_tool_store: None,
+        request_config_overrides: None,
+        allow_roots: None,
+        read_roots: None,
+        command_sessions: None,
+        event_emitter: None,
+        http: &http,
+        dry_run: false,
+    };
+
+    let result = command_tool::execute_ptc(
+        &context,
+        &json!({
+            "filename": "env_probe.py",
+            "content": "import os\nfor name in ('WUNDER_PTC_ENV_TEST_SECRET', 'WUNDER_PTC_ENV_TEST_SHARED'):\n    print(name + '=' + os.environ.get(name, '<unset>'))\n",
+        }),
+    )
+    .await
+    .expect("run ptc");
+
+    assert_eq!(result["ok"], true, "{result}");
+    let stdout = result["data"]["stdout"].as_str().unwrap_or_default();
+    assert!(
+        stdout.contains("WUNDER_PTC_ENV_TEST_SECRET=<unset>"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("WUNDER_PTC_ENV_TEST_SHARED=visible"),
+        "{stdout}"
+    );
+}
+
+#[tokio::test]
+async fn sandbox_execute_command_creates_and_bounds_workdir_before_dispatch() {
+    use axum::{http::StatusCode, routing::post, Json, Router};
+    use std::sync::Mutex;
+
+    const SESSION_ID: &str = "sess_sandbox_workdir";
+    let dir = tempdir().expect("tempdir");
+    let db_path = dir.path().join("state.sqlite3");
+    let storage = Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+    let workspace_root = dir.path().join("workspace");
+    let workspace = Arc::new(WorkspaceManager::new(
+        workspace_root.to_string_lossy().as_ref(),
+        storage.clone(),
+        0,
+        &HashMap::new(),
+    ));
+    let host_workdir = workspace_root.join("workspace-test").join("build/out");
+
+    // Mock sandbox: record the workdir of this test's requests and whether it already existed.
+    let seen = Arc::new(Mutex::new(Vec::<(String, bool)>::new()));
+    let seen_by_server = seen.clone();
+    let probe_dir = host_workdir.clone();
+    let app = Router::new().route(
+        "/sandboxes/execute_command_stream",
+        post(move |Json(payload): Json<Value>| {
+            let seen = seen_by_server.clone();
+            let probe_dir = probe_dir.clone();
+            async move {
+                if payload["session_id"] != SESSION_ID {
+                    return Err(StatusCode::NOT_FOUND);
+                }
+                let workdir = payload["args"]["workdir"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string();
+                seen.lock().unwrap().push((workdir, probe_dir.is_dir()));
+                let final_line = json!({
+                    "type": "final",
+                    "payload": { "ok": true, "data": { "results": [] }, "error": "" },
+                });
+                Ok(format!("{final_line}\n"))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move {
+        axum::serve(listener, app)
+            .await
+            .expect("serve mock sandbox");
+    });
+    let _env_guard = crate::sandbox::SANDBOX_ENDPOINT_ENV_LOCK.lock().await;
+    std::env::set_var("WUNDER_SANDBOX_ENDPOINT", format!("http://{addr}"));
+
+    let mut config = Config::default();
+    config.server.mode = "api".to_string();
+    config.security.allow_commands = vec!["*".to_string()];
+    let a2a_store = A2aStore::default();
+    let skills = SkillRegistry::default();
+    let http = reqwest::Client::new();
+    let lsp_manager = LspManager::new(workspace.clone());
+    let context = ToolContext {
+        user_id: "alice",
+        session_id: SESSION_ID,
+        workspace_id: "workspace-test",
+        agent_id: None,
+        user_round: None,
+        model_round: None,
+        is_admin: false,
+        storage: storage.clone(),
+        orchestrator: None,
+        monitor: None,
+        beeroom_realtime: None,
+        workspace: workspace.clone(),
+        lsp_manager,
+        config: &config,
+        a2a_store: &a2a_store,
+        skills: &skills,
+        gateway: None,
+        user_world: None,
+        cron_wake_signal: None,
+        user_tool_manager: None,
+        user_tool_bindings: None,
+        user_tool_store: None,
+        request_config_overrides: None,
+        allow_roots: None,
+        read_roots: None,
+        command_sessions: None,
+        event_emitter: None,
+        http: &http,
+        dry_run: false,
+    };
+    assert!(crate::sandbox::sandbox_enabled(&config));
+
+    let outside = dir.path().join("outside");
+    let rejected = command_tool::execute_command(
+        &context,
+        &json!({ "content": "echo hi", "workdir": outside.to_string_lossy(), "create": true }),
+    )
+    .await
+    .expect("outside workdir");
+    assert_eq!(rejected["ok"], false, "{rejected}");
+    assert_eq!(
+        rejected["data"]["error_meta"]["code"],
+        json!("TOOL_EXEC_WORKDIR_OUT_OF_BOUNDS"),
+        "{rejected}"
+    );
+    assert!(!outside.exists());
+    assert!(seen.lock().unwrap().is_empty());
+
+    let result = command_tool::execute_command(
+        &context,
+        &json!({ "content": "echo hi", "workdir": "build/out", "create": true }),
+    )
+    .await
+    .expect("sandbox command");
+    std::env::remove_var("WUNDER_SANDBOX_ENDPOINT");
+
+    assert_eq!(
+        (result["ok"].clone(), result["sandbox"].clone()),
+        (json!(true), json!(true)),
+        "{result}"
+    );
+    assert_eq!(
+        seen.lock().unwrap().as_slice(),
+        &[("build/out".to_string(), true)]
+    );
+}
+
 #[test]
 fn session_spawn_args_accept_message_alias() {
     let payload: SessionSpawnArgs = serde_json::from_value(json!({
@@ -606,6 +1035,7 @@ fn read_files_inner_returns_failed_result_when_all_files_missing() {
         &workspace,
         "admin",
         &[],
+        None,
         vec![file_tool::ReadFileSpec {
             path: "missing.txt".to_string(),
             requested_ranges: vec![(1, 20)],
@@ -637,6 +1067,63 @@ fn read_files_inner_returns_failed_result_when_all_files_missing() {
     assert!(value.pointer("/data/content").is_none());
 }
 
+#[test]
+fn read_files_inner_denies_workspace_ignored_paths() {
+    let dir = tempdir().expect("tempdir");
+    let db_path = dir.path().join("read-files-ignored.db");
+    let storage = Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+    let workspace_root = dir.path().join("workspaces");
+    let workspace = WorkspaceManager::new(
+        workspace_root.to_string_lossy().as_ref(),
+        storage,
+        0,
+        &HashMap::new(),
+    );
+    let user_root = workspace.workspace_root("admin");
+    std::fs::create_dir_all(user_root.join("secrets")).expect("create secrets");
+    std::fs::write(user_root.join("secrets").join("token.txt"), "sk-live").expect("write secret");
+    std::fs::write(user_root.join(".wunderignore"), "secrets/\n").expect("write ignore");
+    let ignore =
+        crate::workspace_ignore::WorkspaceIgnore::load(&user_root, &[".wunderignore".to_string()]);
+    assert!(ignore.is_some());
+
+    let value = file_tool::read_files_inner(
+        &workspace,
+        "admin",
+        &[],
+        ignore.as_ref(),
+        vec![file_tool::ReadFileSpec {
+            path: "secrets/token.txt".to_string(),
+            requested_ranges: vec![(1, 20)],
+            ranges: vec![(1, 20)],
+            used_default_range: false,
+            mode: file_tool::ReadFileMode::Slice,
+            indentation: read_indentation::IndentationReadOptions::default(),
+        }],
+        file_tool::ReadBudget::default(),
+        false,
+        1,
+        false,
+    )
+    .expect("read files result");
+
+    assert_eq!(value.get("ok").and_then(Value::as_bool), Some(false));
+    assert_eq!(
+        value.pointer("/error_meta/code").and_then(Value::as_str),
+        Some("TOOL_READ_PERMISSION_DENIED")
+    );
+    assert_eq!(
+        value.pointer("/data/reason").and_then(Value::as_str),
+        Some("ignored")
+    );
+    let detail = value
+        .pointer("/data/detail")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    assert!(detail.contains("`secrets/` (.wunderignore)"), "{detail}");
+    assert!(!value.to_string().contains("sk-live"));
+}
+
 #[test]
 fn read_files_inner_returns_compact_binary_failure() {
     let dir = tempdir().expect("tempdir");
@@ -659,6 +1146,7 @@ fn read_files_inner_returns_compact_binary_failure() {
         &workspace,
         "admin",
         &[],
+        None,
         vec![file_tool::ReadFileSpec {
             path: "heart.png".to_string(),
             requested_ranges: vec![(1, 20)],
@@ -726,6 +1214,7 @@ fn read_files_inner_returns_truncated_excerpt_for_large_text_file() {
         &workspace,
         "admin",
         &[],
+        None,
         vec![file_tool::ReadFileSpec {
             path: "large.md".to_string(),
             requested_ranges: vec![(1, 5)],
@@ -787,6 +1276,7 @@ fn read_files_inner_prefers_workspace_file_for_relative_path_with_extra_root() {
         &workspace,
         "admin",
         &[extra_root.clone()],
+        None,
         vec![file_tool::ReadFileSpec {
             path: "note.txt".to_string(),
             requested_ranges: vec![(1, 20)],
@@ -837,6 +1327,7 @@ fn read_files_inner_marks_default_full_window_as_continuable() {
         &workspace,
         "admin",
         &[],
+        None,
         vec![file_tool::ReadFileSpec {
             path: "treaty.md".to_string(),
             requested_ranges: vec![(1, MAX_READ_LINES)],
@@ -1020,6 +1511,43 @@ fn resolve_session_tool_overrides_prefers_declared_agent_defaults() {
     );
 }
 
+#[test]
+fn resumed_session_tool_overrides_limit_available_tool_names() {
+    let mut config = Config::default();
+    config.tools.builtin.enabled = ["read_file", "list_files", "write_file", "execute_command"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let skills = SkillRegistry::default();
+    let available = collect_available_tool_names(&config, &skills, None);
+    let mut session = sample_chat_session_record("agent_policy_worker");
+
+    assert_eq!(
+        resolve_session_request_tool_names(&config, &skills, None, &session),
+        Vec::<String>::new()
+    );
+
+    session.tool_overrides = vec![
+        "read_file".to_string(),
+        "list_files".to_string(),
+        "not_a_tool".to_string(),
+    ];
+    let limited = resolve_session_request_tool_names(&config, &skills, None, &session);
+    // Overrides that name an available alias are kept as written.
+    assert_eq!(
+        limited,
+        vec!["list_files".to_string(), "read_file".to_string()]
+    );
+    assert!(limited.iter().all(|name| available.contains(name)));
+    assert!(available.contains(&resolve_tool_name("write_file")));
+
+    session.tool_overrides = vec![TOOL_OVERRIDE_NONE.to_string()];
+    assert_eq!(
+        resolve_session_request_tool_names(&config, &skills, None, &session),
+        vec![TOOL_OVERRIDE_NONE.to_string()]
+    );
+}
+
 #[test]
 fn resolve_child_session_tool_names_uses_target_agent_defaults_for_swarm_children() {
     let parent_tool_names = vec!["skill_creator".to_string()];
@@ -1126,6 +1654,7 @@ async fn prepare_swarm_child_session_creates_fresh_main_thread_even_when_worker_
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let prepared = prepare_swarm_child_session(
@@ -1236,6 +1765,7 @@ async fn prepare_child_session_does_not_rebind_parent_agent_main_thread_for_suba
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let prepared = prepare_child_session(
@@ -1345,6 +1875,7 @@ async fn swarm_worker_subagent_child_does_not_steal_worker_main_thread() {
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let worker_prepared = prepare_swarm_child_session(
@@ -1393,6 +1924,7 @@ async fn swarm_worker_subagent_child_does_not_steal_worker_main_thread() {
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let subagent_prepared = prepare_child_session(
@@ -1499,6 +2031,7 @@ fn prepare_child_session_inherits_effective_model_from_parent_agent() {
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let prepared = prepare_child_session(
@@ -1591,6 +2124,7 @@ async fn prepare_swarm_child_session_uses_target_agent_model_for_initial_run() {
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let prepared = prepare_swarm_child_session(
@@ -2022,6 +2556,7 @@ async fn agent_swarm_batch_send_missing_message_returns_actionable_failure_and_s
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let result = agent_swarm_batch_send(
@@ -2124,6 +2659,7 @@ async fn agent_swarm_batch_send_ignores_artifact_path_session_id_and_infers_agen
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let result = agent_swarm_batch_send(
@@ -2238,6 +2774,7 @@ async fn agent_swarm_batch_send_ignores_other_hive_agents_during_prefetch() {
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let err = agent_swarm_batch_send(
@@ -2323,6 +2860,7 @@ async fn agent_swarm_send_missing_target_returns_actionable_failure() {
         command_sessions: None,
         event_emitter: None,
         http: &http,
+        dry_run: false,
     };
 
     let result = agent_swarm_send(
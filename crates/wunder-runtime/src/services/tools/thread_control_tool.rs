@@ -1,3 +1,4 @@
+use super::tool_error::ToolError;
 use super::{build_model_tool_success_with_hint, context::ToolContext};
 use crate::i18n;
 use crate::storage::{AgentThreadRecord, ChatSessionRecord};
@@ -334,7 +335,7 @@ fn bind_main_session(
     let record_agent = session_agent_key(&session_record);
     let cleaned_agent = agent_id.trim();
     if !cleaned_agent.is_empty() && cleaned_agent != record_agent {
-        return Err(anyhow!(i18n::t("error.permission_denied")));
+        return Err(ToolError::PermissionDenied(i18n::t("error.permission_denied")).into());
     }
     let existing = context.storage.get_agent_thread(user_id, &record_agent)?;
     let now = now_ts();
@@ -600,7 +601,7 @@ async fn info_thread(context: &ToolContext<'_>, args: ThreadControlArgs) -> Resu
     if let Some(explicit_agent_id) = normalize_optional_string(args.agent_id.clone()) {
         validate_agent_access(context, user_id, &explicit_agent_id)?;
         if explicit_agent_id != agent_key {
-            return Err(anyhow!(i18n::t("error.permission_denied")));
+            return Err(ToolError::PermissionDenied(i18n::t("error.permission_denied")).into());
         }
     }
     let parent = record
@@ -1118,6 +1119,7 @@ mod tests {
                 command_sessions: None,
                 event_emitter: None,
                 http: &self.http,
+                dry_run: false,
             }
         }
 
@@ -1,4 +1,83 @@
 use serde_json::{json, Map, Value};
+use std::fmt;
+
+/// Failure returned by the tool dispatch layer when a call produces no result payload.
+///
+/// Tools report ordinary business failures as `ok: false` payloads; this type covers the
+/// cases where dispatch itself fails, so callers can branch on the variant instead of
+/// matching message text. `Display` keeps the messages callers saw before the enum existed.
+#[derive(Debug)]
+pub enum ToolError {
+    NotFound(String),
+    PermissionDenied(String),
+    InvalidArgs(String),
+    Timeout,
+    Execution(anyhow::Error),
+    Cancelled,
+}
+
+impl ToolError {
+    /// Stable error code surfaced in `error_meta.code` of tool result payloads.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "TOOL_NOT_FOUND",
+            Self::PermissionDenied(_) => "TOOL_PERMISSION_DENIED",
+            Self::InvalidArgs(_) => "TOOL_INVALID_ARGS",
+            Self::Timeout => "TOOL_TIMEOUT",
+            Self::Execution(_) => "TOOL_EXEC_FAILED",
+            Self::Cancelled => "TOOL_CANCELLED",
+        }
+    }
+
+    /// Process exit status used by the CLI `tool` command.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Execution(_) => 1,
+            Self::InvalidArgs(_) => 64,
+            Self::PermissionDenied(_) => 77,
+            Self::Timeout => 124,
+            Self::NotFound(_) => 127,
+            Self::Cancelled => 130,
+        }
+    }
+
+    pub fn retryable(&self) -> bool {
+        matches!(self, Self::Timeout | Self::Execution(_))
+    }
+}
+
+impl fmt::Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(message)
+            | Self::PermissionDenied(message)
+            | Self::InvalidArgs(message) => f.write_str(message),
+            Self::Timeout => f.write_str("tool_timeout"),
+            Self::Execution(error) => write!(f, "{error}"),
+            Self::Cancelled => f.write_str("tool_cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for ToolError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Execution(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<anyhow::Error> for ToolError {
+    /// Tools still propagate `anyhow` internally; a `ToolError` raised deep inside a tool
+    /// (e.g. `return Err(ToolError::InvalidArgs(..).into())`) keeps its variant here.
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<ToolError>() {
+            Ok(tool_error) => tool_error,
+            Err(error) => Self::Execution(error),
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct ToolErrorMeta {
@@ -176,6 +255,61 @@ fn ensure_object_payload(data: Value) -> Value {
 mod tests {
     use super::*;
 
+    #[test]
+    fn tool_error_from_anyhow_keeps_embedded_variant() {
+        let cases: [anyhow::Error; 6] = [
+            ToolError::NotFound("未知内置工具: x".to_string()).into(),
+            ToolError::PermissionDenied("denied".to_string()).into(),
+            ToolError::InvalidArgs("memory_id required".to_string()).into(),
+            ToolError::Timeout.into(),
+            ToolError::Cancelled.into(),
+            anyhow::anyhow!("disk full"),
+        ];
+        let actual = cases
+            .into_iter()
+            .map(|error| {
+                let error = ToolError::from(error);
+                (error.code(), error.to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![
+                ("TOOL_NOT_FOUND", "未知内置工具: x".to_string()),
+                ("TOOL_PERMISSION_DENIED", "denied".to_string()),
+                ("TOOL_INVALID_ARGS", "memory_id required".to_string()),
+                ("TOOL_TIMEOUT", "tool_timeout".to_string()),
+                ("TOOL_CANCELLED", "tool_cancelled".to_string()),
+                ("TOOL_EXEC_FAILED", "disk full".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn execution_error_exposes_the_wrapped_error_as_source() {
+        use std::error::Error as _;
+
+        let io = std::io::Error::other("disk full");
+        let error = ToolError::Execution(anyhow::Error::new(io));
+        let source = error.source().expect("execution error has a source");
+        assert_eq!(source.to_string(), "disk full");
+        assert!(source.downcast_ref::<std::io::Error>().is_some());
+        assert!(ToolError::Timeout.source().is_none());
+    }
+
+    #[test]
+    fn tool_error_exit_codes_are_distinct() {
+        let codes = [
+            ToolError::NotFound(String::new()).exit_code(),
+            ToolError::PermissionDenied(String::new()).exit_code(),
+            ToolError::InvalidArgs(String::new()).exit_code(),
+            ToolError::Timeout.exit_code(),
+            ToolError::Execution(anyhow::anyhow!("x")).exit_code(),
+            ToolError::Cancelled.exit_code(),
+        ];
+        assert_eq!(codes, [127, 77, 64, 124, 1, 130]);
+    }
+
     #[test]
     fn with_error_meta_wraps_non_object_payload() {
         let payload = with_error_meta(
@@ -0,0 +1,64 @@
+// MCP/A2A 工具调用的上游熔断包装：调用前检查熔断状态，调用后记录成败，并把状态变化写入事件流与监控。
+use super::context::ToolContext;
+use crate::services::upstream_breaker::{
+    upstream_breakers, BreakerSettings, BreakerTransition, UpstreamKind, UPSTREAM_BREAKER_EVENT,
+};
+use anyhow::Result;
+use std::future::Future;
+
+/// Run `call` behind the breaker for `endpoint`. Every error from `call` counts as an upstream
+/// failure, so callers keep application-level errors (tool results, JSON-RPC errors) outside it.
+pub(crate) async fn call_with_breaker<T, Fut>(
+    context: &ToolContext<'_>,
+    kind: UpstreamKind,
+    endpoint: &str,
+    call: Fut,
+) -> Result<T>
+where
+    Fut: Future<Output = Result<T>>,
+{
+    call_with_breaker_classified(context, kind, endpoint, call, |_| true).await
+}
+
+/// Like [`call_with_breaker`] for calls that cannot split transport from application errors:
+/// only errors matching `is_upstream_failure` count as failures, any other outcome means the
+/// upstream answered.
+pub(crate) async fn call_with_breaker_classified<T, Fut>(
+    context: &ToolContext<'_>,
+    kind: UpstreamKind,
+    endpoint: &str,
+    call: Fut,
+    is_upstream_failure: fn(&anyhow::Error) -> bool,
+) -> Result<T>
+where
+    Fut: Future<Output = Result<T>>,
+{
+    let Some(settings) = BreakerSettings::resolve(&context.config.gateway.circuit_breaker, kind)
+    else {
+        return call.await;
+    };
+    let breakers = upstream_breakers();
+    report_transition(context, breakers.check(kind, endpoint, settings)?);
+    let result = call.await;
+    let transition = match &result {
+        Err(err) if is_upstream_failure(err) => breakers.record_failure(kind, endpoint, settings),
+        _ => breakers.record_success(kind, endpoint, settings),
+    };
+    report_transition(context, transition);
+    result
+}
+
+fn report_transition(context: &ToolContext<'_>, transition: Option<BreakerTransition>) {
+    let Some(transition) = transition else {
+        return;
+    };
+    transition.log();
+    let payload = transition.to_value();
+    // The orchestrator's tool emitter also records into the monitor; fall back to the monitor
+    // directly for calls made outside a streamed turn.
+    if let Some(emitter) = context.event_emitter.as_ref() {
+        emitter.emit(UPSTREAM_BREAKER_EVENT, payload);
+    } else if let Some(monitor) = context.monitor.as_ref() {
+        monitor.record_event(context.session_id, UPSTREAM_BREAKER_EVENT, &payload);
+    }
+}
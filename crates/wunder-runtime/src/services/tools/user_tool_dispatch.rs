@@ -1,6 +1,10 @@
-use super::{a2a_tool, build_model_tool_success, knowledge_tool, mcp_pack, ToolContext};
+use super::tool_error::ToolError;
+use super::{
+    a2a_tool, build_model_tool_success, knowledge_tool, mcp_pack, upstream_guard, ToolContext,
+};
 use crate::i18n;
 use crate::mcp;
+use crate::services::upstream_breaker::UpstreamKind;
 use crate::skills::execute_skill;
 use crate::user_tools::{UserToolAlias, UserToolKind};
 use anyhow::{anyhow, Result};
@@ -38,8 +42,12 @@ async fn execute_user_skill(
         .ok_or_else(|| anyhow!(i18n::t("tool.invoke.user_skill_not_loaded")))?;
     let spec = registry
         .get(&alias.target)
-        .ok_or_else(|| anyhow!(i18n::t("tool.invoke.user_skill_not_found")))?;
+        .ok_or_else(|| ToolError::NotFound(i18n::t("tool.invoke.user_skill_not_found")))?;
     let result = execute_skill(&spec, args, 60).await.map_err(|err| {
+        // Input validation errors already name the skill; keep them typed as invalid args.
+        if err.is::<ToolError>() {
+            return err;
+        }
         anyhow!(i18n::t_with_params(
             "tool.invoke.user_skill_failed",
             &HashMap::from([("detail".to_string(), err.to_string())]),
@@ -56,7 +64,7 @@ async fn execute_user_mcp_tool(
 ) -> Result<Value> {
     let target = alias.target.trim();
     let Some((server_name, tool_name)) = split_mcp_target(target) else {
-        return Err(anyhow!(i18n::t("tool.invoke.mcp_name_invalid")));
+        return Err(ToolError::InvalidArgs(i18n::t("tool.invoke.mcp_name_invalid")).into());
     };
     let bindings = context
         .user_tool_bindings
@@ -77,14 +85,20 @@ async fn execute_user_mcp_tool(
             })?;
         return Ok(result);
     }
-    let result = mcp::call_tool_with_server(context.config, server_config, tool_name, args)
-        .await
-        .map_err(|err| {
-            anyhow!(i18n::t_with_params(
-                "tool.invoke.mcp_call_failed",
-                &HashMap::from([("detail".to_string(), err.to_string())]),
-            ))
-        })?;
+    let result = upstream_guard::call_with_breaker_classified(
+        context,
+        UpstreamKind::Mcp,
+        &user_mcp_breaker_key(&alias.owner_id, &server_config.endpoint),
+        mcp::call_tool_with_server(context.config, server_config, tool_name, args),
+        mcp::is_upstream_failure,
+    )
+    .await
+    .map_err(|err| {
+        anyhow!(i18n::t_with_params(
+            "tool.invoke.mcp_call_failed",
+            &HashMap::from([("detail".to_string(), err.to_string())]),
+        ))
+    })?;
     if result
         .get("is_error")
         .and_then(Value::as_bool)
@@ -137,7 +151,27 @@ pub(crate) async fn execute_mcp_tool(
         }
         return mcp_pack::execute(context.config, server, args).await;
     }
-    mcp::call_tool(context.config, &server_name, &tool_name, args).await
+    let server = context
+        .config
+        .mcp
+        .servers
+        .iter()
+        .find(|item| item.name == server_name)
+        .ok_or_else(|| anyhow!("MCP 服务不存在: {server_name}"))?;
+    upstream_guard::call_with_breaker_classified(
+        context,
+        UpstreamKind::Mcp,
+        &server.endpoint,
+        mcp::call_tool_with_server(context.config, server, &tool_name, args),
+        mcp::is_upstream_failure,
+    )
+    .await
+}
+
+/// User MCP servers get a breaker per owner, so one user's failing server config cannot open
+/// the breaker for another user pointing at the same endpoint.
+fn user_mcp_breaker_key(owner_id: &str, endpoint: &str) -> String {
+    format!("user:{owner_id}|{endpoint}")
 }
 
 fn split_mcp_target(target: &str) -> Option<(&str, &str)> {
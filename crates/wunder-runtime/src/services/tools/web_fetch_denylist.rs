@@ -0,0 +1,56 @@
+// 网页抓取主机黑名单：命中条目或其子域名的目标（含重定向目标）一律拒绝访问。
+use super::web_fetch_tool_impl::normalize_host_for_allowlist;
+use crate::config::WebFetchToolConfig;
+
+/// Return the `hostname_denylist` entry that covers `host`, if any. `example.com` matches
+/// `example.com` and `docs.example.com` but not `badexample.com`.
+pub(super) fn denied_host_entry<'a>(host: &str, config: &'a WebFetchToolConfig) -> Option<&'a str> {
+    let host = normalize_host_for_allowlist(host);
+    if host.is_empty() {
+        return None;
+    }
+    config
+        .hostname_denylist
+        .iter()
+        .map(String::as_str)
+        .find(|entry| {
+            let entry = normalize_host_for_allowlist(entry.trim_start_matches("*."));
+            !entry.is_empty()
+                && (host == entry
+                    || host
+                        .strip_suffix(entry.as_str())
+                        .is_some_and(|prefix| prefix.ends_with('.')))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_denylist(entries: &[&str]) -> WebFetchToolConfig {
+        WebFetchToolConfig {
+            hostname_denylist: entries.iter().map(ToString::to_string).collect(),
+            ..WebFetchToolConfig::default()
+        }
+    }
+
+    #[test]
+    fn denied_host_entry_matches_host_and_subdomains() {
+        let config = config_with_denylist(&["Example.com.", "*.tracker.io", "10.0.0.8"]);
+        assert_eq!(
+            denied_host_entry("example.com", &config),
+            Some("Example.com.")
+        );
+        assert_eq!(
+            denied_host_entry("docs.EXAMPLE.com", &config),
+            Some("Example.com.")
+        );
+        assert_eq!(
+            denied_host_entry("cdn.tracker.io", &config),
+            Some("*.tracker.io")
+        );
+        assert_eq!(denied_host_entry("10.0.0.8", &config), Some("10.0.0.8"));
+        assert_eq!(denied_host_entry("badexample.com", &config), None);
+        assert_eq!(denied_host_entry("example.org", &config), None);
+    }
+}
@@ -1,6 +1,8 @@
+use super::web_fetch_denylist::denied_host_entry;
 use super::web_fetch_provider;
 use super::{tool_error::build_failed_tool_result, tool_error::ToolErrorMeta, ToolContext};
 use crate::config::{Config, WebFetchToolConfig};
+use crate::doc2md::html_to_markdown;
 use crate::i18n;
 use crate::services::browser::{browser_service, browser_tools_enabled, BrowserSessionScope};
 use anyhow::{anyhow, Result};
@@ -354,6 +356,20 @@ pub async fn tool_web_fetch(context: &ToolContext<'_>, args: &Value) -> Result<V
         Ok(value) => value,
         Err(failure) => return Ok(failure.into_value()),
     };
+    if !crate::sandbox::sandbox_network_enabled(context.config) {
+        return Ok(web_fetch_failure(
+            &raw_url,
+            Some(&request_url),
+            "validation",
+            "TOOL_WEB_FETCH_NETWORK_DISABLED",
+            i18n::t("tool.web_fetch.network_disabled"),
+            Some(i18n::t("tool.web_fetch.network_disabled_hint")),
+            false,
+            None,
+            json!({}),
+        )
+        .into_value());
+    }
     let config = &context.config.tools.web.fetch;
     let extract_mode = ExtractMode::from_raw(request.extract_mode.as_deref());
     let max_chars = resolve_max_chars(request.max_chars, config);
@@ -1029,6 +1045,22 @@ async fn validate_remote_target(
         )
     })?;
 
+    if let Some(entry) = denied_host_entry(&ascii_host, config) {
+        let mut params = HashMap::new();
+        params.insert("host".to_string(), ascii_host.clone());
+        return Err(web_fetch_failure(
+            raw_url,
+            Some(url),
+            "validation",
+            "TOOL_WEB_FETCH_DENIED_HOST",
+            i18n::t_with_params("tool.web_fetch.denied_host", &params),
+            Some(i18n::t("tool.web_fetch.denied_host_hint")),
+            false,
+            None,
+            json!({ "denylist_entry": entry }),
+        ));
+    }
+
     let allow_private_target = web_fetch_allows_private_target(&ascii_host, config);
     if !allow_private_target && is_obviously_private_host(&ascii_host) {
         let mut params = HashMap::new();
@@ -1217,7 +1249,7 @@ fn web_fetch_allows_private_target(host: &str, config: &WebFetchToolConfig) -> b
         .any(|entry| normalize_host_for_allowlist(entry) == normalized_host)
 }
 
-fn normalize_host_for_allowlist(host: &str) -> String {
+pub(super) fn normalize_host_for_allowlist(host: &str) -> String {
     let trimmed = host
         .trim()
         .trim_end_matches('.')
@@ -1803,7 +1835,7 @@ fn extract_html_content(html: &str, mode: ExtractMode) -> Result<HtmlExtraction>
         markdown = body_node
             .as_ref()
             .map(render_markdown_from_node)
-            .unwrap_or_else(|| clean_markdown(&html2md::parse_html(&document.to_string())));
+            .unwrap_or_else(|| clean_markdown(&html_to_markdown(&document.to_string())));
         extractor = "sanitized-html".to_string();
     }
 
@@ -2103,7 +2135,7 @@ fn score_candidate(stats: &CandidateStats, base_bonus: f64, node: &NodeRef) -> f
 }
 
 fn render_markdown_from_node(node: &NodeRef) -> String {
-    clean_markdown(&html2md::parse_html(&node.to_string()))
+    clean_markdown(&html_to_markdown(&node.to_string()))
 }
 
 fn clean_markdown(markdown: &str) -> String {
@@ -2351,7 +2383,7 @@ fn markdown_ordered_list_regex() -> &'static Regex {
 #[cfg(test)]
 mod tests {
     use super::{
-        canonicalize_block_for_dedupe, diagnose_html_page, extract_html_content,
+        canonicalize_block_for_dedupe, diagnose_html_page, extract_html_content, fetch_url,
         ip_is_private_or_internal, is_noise_block, normalize_host_for_allowlist,
         normalize_request_url, normalize_text_block, strip_invisible_unicode, truncate_chars,
         web_fetch_allows_private_target, web_fetch_failure, ExtractMode, HtmlPageKind,
@@ -2575,4 +2607,74 @@ mod tests {
             Some("昇腾".to_string())
         );
     }
+
+    async fn spawn_html_server() -> std::net::SocketAddr {
+        use axum::response::{Html, Redirect};
+        use axum::{routing::get, Router};
+
+        let app = Router::new()
+            .route(
+                "/page",
+                get(|| async {
+                    Html(
+                        "<html><head><title>Mock Page</title></head><body><main>\
+                         <h1>Mock Heading</h1><p>Served by the local test server.</p>\
+                         </main></body></html>",
+                    )
+                }),
+            )
+            .route("/moved", get(|| async { Redirect::temporary("/page") }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind listener");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("serve test app");
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn fetch_url_follows_redirects_and_converts_html() {
+        let addr = spawn_html_server().await;
+        let config = WebFetchToolConfig {
+            allow_private_network: true,
+            ..WebFetchToolConfig::default()
+        };
+        let raw_url = format!("http://{addr}/moved");
+        let url = Url::parse(&raw_url).expect("url");
+        let fetched = fetch_url(&raw_url, &url, &config)
+            .await
+            .unwrap_or_else(|failure| panic!("fetch failed: {}", failure.message));
+        assert_eq!(fetched.status, 200);
+        assert_eq!(fetched.final_url, format!("http://{addr}/page"));
+
+        let html = String::from_utf8(fetched.body).expect("utf-8 body");
+        let extracted =
+            extract_html_content(&html, ExtractMode::Markdown).expect("html should extract");
+        assert_eq!(extracted.title.as_deref(), Some("Mock Page"));
+        assert!(extracted.content.contains("Mock Heading"));
+        assert!(extracted
+            .content
+            .contains("Served by the local test server."));
+    }
+
+    #[tokio::test]
+    async fn fetch_url_rejects_denylisted_hosts() {
+        let addr = spawn_html_server().await;
+        let config = WebFetchToolConfig {
+            allow_private_network: true,
+            hostname_denylist: vec!["127.0.0.1".to_string()],
+            ..WebFetchToolConfig::default()
+        };
+        let raw_url = format!("http://{addr}/page");
+        let url = Url::parse(&raw_url).expect("url");
+        let failure = fetch_url(&raw_url, &url, &config)
+            .await
+            .expect_err("denylisted host must be rejected");
+        assert_eq!(
+            failure.into_value()["data"]["error_meta"]["code"],
+            json!("TOOL_WEB_FETCH_DENIED_HOST")
+        );
+    }
 }
@@ -0,0 +1,213 @@
+// 回合完成通知：定义各前端（CLI、桌面端）共用的 TurnNotifier 扩展点与通知载荷，编排器在回合结束时分发给已注册的通知器。
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+pub const TURN_COMPLETE_EVENT: &str = "agent-turn-complete";
+pub const ANSWER_PREVIEW_CHARS: usize = 180;
+const DEFAULT_NOTIFICATION_BODY: &str = "wunder turn complete";
+
+/// Everything a backend may need about a finished turn.
+#[derive(Debug, Clone)]
+pub struct TurnNotification {
+    pub source: String,
+    pub session_id: String,
+    pub user_id: String,
+    pub cwd: Option<PathBuf>,
+    pub stop_reason: Option<String>,
+    pub answer_preview: String,
+    pub payload: Value,
+}
+
+impl TurnNotification {
+    pub fn new(
+        source: &str,
+        session_id: &str,
+        user_id: &str,
+        cwd: Option<&Path>,
+        answer: &str,
+        stop_reason: Option<&str>,
+    ) -> Self {
+        let answer_preview = preview_answer(answer, ANSWER_PREVIEW_CHARS);
+        let payload = json!({
+            "type": TURN_COMPLETE_EVENT,
+            "source": source,
+            "session_id": session_id,
+            "user_id": user_id,
+            "cwd": cwd,
+            "stop_reason": stop_reason,
+            "answer_preview": answer_preview,
+            "ts": SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|value| value.as_secs_f64())
+                .unwrap_or(0.0),
+        });
+        Self {
+            source: source.to_string(),
+            session_id: session_id.to_string(),
+            user_id: user_id.to_string(),
+            cwd: cwd.map(Path::to_path_buf),
+            stop_reason: stop_reason.map(str::to_string),
+            answer_preview,
+            payload,
+        }
+    }
+
+    /// Short human-readable body for backends that show text.
+    pub fn body(&self) -> &str {
+        if self.answer_preview.trim().is_empty() {
+            DEFAULT_NOTIFICATION_BODY
+        } else {
+            self.answer_preview.as_str()
+        }
+    }
+}
+
+/// A turn-complete notification backend. Frontends implement this and either deliver
+/// notifications themselves or register the backend on the orchestrator through
+/// [`TurnNotifierRegistry::register`] to be called whenever a turn finishes.
+pub trait TurnNotifier: Send + Sync {
+    fn notify(&self, notification: &TurnNotification) -> Result<()>;
+}
+
+/// Notifiers called by the orchestrator after each visible turn's `final` event.
+#[derive(Default)]
+pub struct TurnNotifierRegistry {
+    notifiers: RwLock<Vec<Arc<dyn TurnNotifier>>>,
+}
+
+impl TurnNotifierRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, notifier: Arc<dyn TurnNotifier>) {
+        self.notifiers.write().push(notifier);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notifiers.read().is_empty()
+    }
+
+    /// Calls every registered notifier in order. Backends may block (spawning a process,
+    /// talking to the OS notification service), so async callers should use
+    /// [`TurnNotifierRegistry::dispatch`]; failures are logged and never reach the turn.
+    pub fn notify_all(&self, notification: &TurnNotification) {
+        let notifiers = self.notifiers.read().clone();
+        for notifier in notifiers {
+            if let Err(err) = notifier.notify(notification) {
+                warn!(
+                    "turn notifier failed for session {}: {err}",
+                    notification.session_id
+                );
+            }
+        }
+    }
+
+    /// Runs [`TurnNotifierRegistry::notify_all`] off the async executor.
+    pub fn dispatch(self: &Arc<Self>, notification: TurnNotification) {
+        if self.is_empty() {
+            return;
+        }
+        let registry = Arc::clone(self);
+        tokio::task::spawn_blocking(move || registry.notify_all(&notification));
+    }
+}
+
+/// Trimmed text cut to `limit` characters, with `…` appended when anything was dropped.
+pub fn preview_answer(text: &str, limit: usize) -> String {
+    if limit == 0 {
+        return String::new();
+    }
+    let cleaned = text.trim();
+    let mut out: String = cleaned.chars().take(limit).collect();
+    if cleaned.chars().count() > limit {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        payloads: Mutex<Vec<Value>>,
+    }
+
+    impl TurnNotifier for RecordingNotifier {
+        fn notify(&self, notification: &TurnNotification) -> Result<()> {
+            self.payloads
+                .lock()
+                .unwrap()
+                .push(notification.payload.clone());
+            Ok(())
+        }
+    }
+
+    struct FailingNotifier;
+
+    impl TurnNotifier for FailingNotifier {
+        fn notify(&self, _notification: &TurnNotification) -> Result<()> {
+            Err(anyhow!("backend unavailable"))
+        }
+    }
+
+    #[test]
+    fn payload_carries_bounded_answer_preview() {
+        let notification = TurnNotification::new(
+            "tui",
+            "sess_1",
+            "alice",
+            Some(Path::new("/tmp/project")),
+            &"x".repeat(400),
+            Some("model_response"),
+        );
+        let payload = &notification.payload;
+        assert_eq!(payload["type"], TURN_COMPLETE_EVENT);
+        assert_eq!(payload["source"], "tui");
+        assert_eq!(payload["session_id"], "sess_1");
+        assert_eq!(payload["user_id"], "alice");
+        assert_eq!(payload["cwd"], "/tmp/project");
+        assert_eq!(payload["stop_reason"], "model_response");
+        let preview = payload["answer_preview"].as_str().unwrap();
+        assert_eq!(preview.chars().count(), ANSWER_PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+        assert_eq!(preview_answer("  short  ", 10), "short");
+        assert_eq!(preview_answer("anything", 0), "");
+
+        let empty = TurnNotification::new("desktop", "sess_2", "alice", None, " \n ", None);
+        assert_eq!(empty.body(), DEFAULT_NOTIFICATION_BODY);
+        assert!(empty.payload["cwd"].is_null());
+    }
+
+    #[test]
+    fn registry_reaches_every_notifier_despite_failures() {
+        let registry = TurnNotifierRegistry::new();
+        assert!(registry.is_empty());
+        let recorder = Arc::new(RecordingNotifier::default());
+        registry.register(Arc::new(FailingNotifier));
+        registry.register(recorder.clone());
+
+        registry.notify_all(&TurnNotification::new(
+            "desktop",
+            "sess_3",
+            "alice",
+            None,
+            "done",
+            Some("model_response"),
+        ));
+
+        let payloads = recorder.payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0]["session_id"], "sess_3");
+        assert_eq!(payloads[0]["answer_preview"], "done");
+    }
+}
@@ -0,0 +1,436 @@
+// 上游熔断：按 (类型, 端点) 统计连续失败，达到阈值后在冷却期内快速失败，冷却结束放行单个探测请求决定恢复或重新打开。
+use crate::config::{CircuitBreakerSettings, UpstreamCircuitBreakerConfig};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Stream/monitor event emitted whenever a breaker changes state.
+pub const UPSTREAM_BREAKER_EVENT: &str = "upstream_breaker";
+/// Upstreams with a failure streak tracked at once; user MCP servers are keyed per owner.
+const MAX_TRACKED_UPSTREAMS: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpstreamKind {
+    Llm,
+    Mcp,
+    A2a,
+}
+
+impl UpstreamKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Llm => "llm",
+            Self::Mcp => "mcp",
+            Self::A2a => "a2a",
+        }
+    }
+}
+
+/// Thresholds for one upstream type, resolved from `gateway.circuit_breaker`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreakerSettings {
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl BreakerSettings {
+    /// `None` when the breaker is disabled globally or for this upstream type.
+    pub fn resolve(config: &UpstreamCircuitBreakerConfig, kind: UpstreamKind) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let settings: &CircuitBreakerSettings = match kind {
+            UpstreamKind::Llm => &config.llm,
+            UpstreamKind::Mcp => &config.mcp,
+            UpstreamKind::A2a => &config.a2a,
+        };
+        if settings.failure_threshold == 0 {
+            return None;
+        }
+        let cooldown_s = if settings.cooldown_s.is_finite() {
+            settings.cooldown_s.max(0.0)
+        } else {
+            0.0
+        };
+        Some(Self {
+            failure_threshold: settings.failure_threshold,
+            cooldown: Duration::from_secs_f64(cooldown_s),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// A state change worth reporting to the monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakerTransition {
+    pub kind: UpstreamKind,
+    pub endpoint: String,
+    pub from: BreakerState,
+    pub to: BreakerState,
+    pub consecutive_failures: u32,
+    pub cooldown: Duration,
+}
+
+impl BreakerTransition {
+    pub fn to_value(&self) -> Value {
+        json!({
+            "upstream": self.kind.as_str(),
+            "endpoint": self.endpoint,
+            "from": self.from.as_str(),
+            "to": self.to.as_str(),
+            "consecutive_failures": self.consecutive_failures,
+            "cooldown_s": self.cooldown.as_secs_f64(),
+        })
+    }
+
+    pub fn log(&self) {
+        if self.to == BreakerState::Open {
+            tracing::warn!(
+                "upstream circuit opened: {} {} after {} consecutive failures, cooldown {:.1}s",
+                self.kind.as_str(),
+                self.endpoint,
+                self.consecutive_failures,
+                self.cooldown.as_secs_f64()
+            );
+        } else {
+            tracing::info!(
+                "upstream circuit {} -> {}: {} {}",
+                self.from.as_str(),
+                self.to.as_str(),
+                self.kind.as_str(),
+                self.endpoint
+            );
+        }
+    }
+}
+
+/// Error returned instead of calling an upstream whose breaker is open.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpstreamUnavailable {
+    pub kind: UpstreamKind,
+    pub endpoint: String,
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for UpstreamUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "upstream unavailable: {} endpoint {} failed repeatedly, circuit open (retry in {:.1}s)",
+            self.kind.as_str(),
+            self.endpoint,
+            self.retry_after.as_secs_f64()
+        )
+    }
+}
+
+impl std::error::Error for UpstreamUnavailable {}
+
+#[derive(Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// Set while the single half-open probe is in flight.
+    probe_started_at: Option<Instant>,
+}
+
+impl Breaker {
+    fn state(&self) -> BreakerState {
+        match (self.opened_at, self.probe_started_at) {
+            (None, _) => BreakerState::Closed,
+            (Some(_), None) => BreakerState::Open,
+            (Some(_), Some(_)) => BreakerState::HalfOpen,
+        }
+    }
+}
+
+/// Breakers shared by every session of the process, keyed by upstream type and endpoint.
+#[derive(Default)]
+pub struct UpstreamBreakers {
+    breakers: Mutex<HashMap<(UpstreamKind, String), Breaker>>,
+}
+
+impl UpstreamBreakers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self, kind: UpstreamKind, endpoint: &str) -> BreakerState {
+        self.breakers
+            .lock()
+            .get(&(kind, endpoint.to_string()))
+            .map_or(BreakerState::Closed, Breaker::state)
+    }
+
+    /// Admit a call, or reject it while the breaker is open. Once the cooldown has passed the
+    /// first caller becomes the half-open probe; others keep failing fast until it reports back.
+    pub fn check(
+        &self,
+        kind: UpstreamKind,
+        endpoint: &str,
+        settings: BreakerSettings,
+    ) -> Result<Option<BreakerTransition>, UpstreamUnavailable> {
+        let mut breakers = self.breakers.lock();
+        let Some(breaker) = breakers.get_mut(&(kind, endpoint.to_string())) else {
+            return Ok(None);
+        };
+        let Some(opened_at) = breaker.opened_at else {
+            return Ok(None);
+        };
+        let now = Instant::now();
+        // A probe that never reported back (e.g. its caller was cancelled) is replaced after
+        // another cooldown rather than holding the breaker half-open forever.
+        let waiting_since = breaker.probe_started_at.unwrap_or(opened_at);
+        let elapsed = now.duration_since(waiting_since);
+        if elapsed < settings.cooldown {
+            return Err(UpstreamUnavailable {
+                kind,
+                endpoint: endpoint.to_string(),
+                retry_after: settings.cooldown - elapsed,
+            });
+        }
+        let from = breaker.state();
+        breaker.probe_started_at = Some(now);
+        Ok((from != BreakerState::HalfOpen).then(|| BreakerTransition {
+            kind,
+            endpoint: endpoint.to_string(),
+            from,
+            to: BreakerState::HalfOpen,
+            consecutive_failures: breaker.consecutive_failures,
+            cooldown: settings.cooldown,
+        }))
+    }
+
+    /// The upstream answered; closes the breaker if it was open or probing.
+    pub fn record_success(
+        &self,
+        kind: UpstreamKind,
+        endpoint: &str,
+        settings: BreakerSettings,
+    ) -> Option<BreakerTransition> {
+        let breaker = self.breakers.lock().remove(&(kind, endpoint.to_string()))?;
+        let from = breaker.state();
+        (from != BreakerState::Closed).then(|| BreakerTransition {
+            kind,
+            endpoint: endpoint.to_string(),
+            from,
+            to: BreakerState::Closed,
+            consecutive_failures: 0,
+            cooldown: settings.cooldown,
+        })
+    }
+
+    /// The upstream failed; opens the breaker at the threshold or when the probe fails.
+    pub fn record_failure(
+        &self,
+        kind: UpstreamKind,
+        endpoint: &str,
+        settings: BreakerSettings,
+    ) -> Option<BreakerTransition> {
+        let mut breakers = self.breakers.lock();
+        let key = (kind, endpoint.to_string());
+        if !breakers.contains_key(&key) && breakers.len() >= MAX_TRACKED_UPSTREAMS {
+            // Closed breakers only hold a failure streak; drop those before giving up on
+            // tracking a new upstream.
+            breakers.retain(|_, breaker| breaker.opened_at.is_some());
+            if breakers.len() >= MAX_TRACKED_UPSTREAMS {
+                return None;
+            }
+        }
+        let breaker = breakers.entry(key).or_default();
+        breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+        let from = breaker.state();
+        let opens = match from {
+            BreakerState::Closed => breaker.consecutive_failures >= settings.failure_threshold,
+            BreakerState::HalfOpen => true,
+            // A call admitted before the breaker opened; the cooldown already runs.
+            BreakerState::Open => false,
+        };
+        if !opens {
+            return None;
+        }
+        breaker.opened_at = Some(Instant::now());
+        breaker.probe_started_at = None;
+        Some(BreakerTransition {
+            kind,
+            endpoint: endpoint.to_string(),
+            from,
+            to: BreakerState::Open,
+            consecutive_failures: breaker.consecutive_failures,
+            cooldown: settings.cooldown,
+        })
+    }
+}
+
+/// Process-wide breakers used by the LLM, MCP and A2A call paths.
+pub fn upstream_breakers() -> &'static UpstreamBreakers {
+    static BREAKERS: OnceLock<UpstreamBreakers> = OnceLock::new();
+    BREAKERS.get_or_init(UpstreamBreakers::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(failure_threshold: u32, cooldown_ms: u64) -> BreakerSettings {
+        BreakerSettings {
+            failure_threshold,
+            cooldown: Duration::from_millis(cooldown_ms),
+        }
+    }
+
+    fn transition_states(
+        transition: Option<BreakerTransition>,
+    ) -> Option<(BreakerState, BreakerState)> {
+        transition.map(|transition| (transition.from, transition.to))
+    }
+
+    #[tokio::test]
+    async fn repeated_failures_open_the_breaker_until_a_probe_succeeds() {
+        let breakers = UpstreamBreakers::new();
+        let settings = settings(3, 50);
+        let endpoint = "https://mcp.example.com/sse";
+
+        for _ in 0..2 {
+            assert_eq!(
+                breakers.check(UpstreamKind::Mcp, endpoint, settings),
+                Ok(None)
+            );
+            assert_eq!(
+                breakers.record_failure(UpstreamKind::Mcp, endpoint, settings),
+                None
+            );
+        }
+        let opened = breakers
+            .record_failure(UpstreamKind::Mcp, endpoint, settings)
+            .expect("third failure opens the breaker");
+        assert_eq!(
+            (opened.from, opened.to),
+            (BreakerState::Closed, BreakerState::Open)
+        );
+        assert_eq!(opened.consecutive_failures, 3);
+        assert_eq!(opened.to_value()["upstream"], "mcp");
+
+        let rejected = breakers
+            .check(UpstreamKind::Mcp, endpoint, settings)
+            .expect_err("open breaker fails fast");
+        assert!(rejected.to_string().contains("upstream unavailable"));
+        assert!(breakers
+            .check(UpstreamKind::A2a, endpoint, settings)
+            .is_ok());
+
+        // Cooldown over: one probe is admitted, a failed probe reopens the breaker.
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(
+            transition_states(
+                breakers
+                    .check(UpstreamKind::Mcp, endpoint, settings)
+                    .unwrap()
+            ),
+            Some((BreakerState::Open, BreakerState::HalfOpen))
+        );
+        assert!(breakers
+            .check(UpstreamKind::Mcp, endpoint, settings)
+            .is_err());
+        assert_eq!(
+            transition_states(breakers.record_failure(UpstreamKind::Mcp, endpoint, settings)),
+            Some((BreakerState::HalfOpen, BreakerState::Open))
+        );
+        assert!(breakers
+            .check(UpstreamKind::Mcp, endpoint, settings)
+            .is_err());
+
+        // A successful probe closes it again.
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(breakers
+            .check(UpstreamKind::Mcp, endpoint, settings)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            transition_states(breakers.record_success(UpstreamKind::Mcp, endpoint, settings)),
+            Some((BreakerState::HalfOpen, BreakerState::Closed))
+        );
+        assert_eq!(
+            breakers.state(UpstreamKind::Mcp, endpoint),
+            BreakerState::Closed
+        );
+        assert_eq!(
+            breakers.check(UpstreamKind::Mcp, endpoint, settings),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn success_resets_the_failure_count() {
+        let breakers = UpstreamBreakers::new();
+        let settings = settings(2, 1_000);
+        assert_eq!(
+            breakers.record_failure(UpstreamKind::Llm, "model", settings),
+            None
+        );
+        assert_eq!(
+            breakers.record_success(UpstreamKind::Llm, "model", settings),
+            None
+        );
+        assert_eq!(
+            breakers.record_failure(UpstreamKind::Llm, "model", settings),
+            None
+        );
+        assert_eq!(
+            breakers.state(UpstreamKind::Llm, "model"),
+            BreakerState::Closed
+        );
+    }
+
+    #[test]
+    fn tracked_upstreams_are_bounded() {
+        let breakers = UpstreamBreakers::new();
+        let settings = settings(2, 1_000);
+        for index in 0..MAX_TRACKED_UPSTREAMS {
+            breakers.record_failure(UpstreamKind::Mcp, &format!("user:{index}|mcp"), settings);
+        }
+        breakers.record_failure(UpstreamKind::Mcp, "user:new|mcp", settings);
+        assert_eq!(breakers.breakers.lock().len(), 1);
+        assert!(breakers
+            .record_failure(UpstreamKind::Mcp, "user:new|mcp", settings)
+            .is_some());
+    }
+
+    #[test]
+    fn settings_are_resolved_per_upstream_type() {
+        let mut config = UpstreamCircuitBreakerConfig::default();
+        assert_eq!(BreakerSettings::resolve(&config, UpstreamKind::Llm), None);
+
+        config.enabled = true;
+        config.mcp.failure_threshold = 0;
+        config.a2a.cooldown_s = 2.5;
+        assert_eq!(
+            BreakerSettings::resolve(&config, UpstreamKind::Llm),
+            Some(settings(5, 30_000))
+        );
+        assert_eq!(BreakerSettings::resolve(&config, UpstreamKind::Mcp), None);
+        assert_eq!(
+            BreakerSettings::resolve(&config, UpstreamKind::A2a),
+            Some(settings(5, 2_500))
+        );
+    }
+}
@@ -4,12 +4,17 @@ use crate::skills::SkillRegistry;
 use crate::state::AppState;
 use crate::storage::OrgUnitRecord;
 use crate::storage::{
-    UserAccountRecord, UserAgentAccessRecord, UserAgentRecord, UserToolAccessRecord,
+    StorageBackend, UserAccountRecord, UserAgentAccessRecord, UserAgentRecord,
+    UserTokenBalanceStatus, UserToolAccessRecord,
 };
 use crate::tools::{
     collect_available_tool_names, collect_enabled_tool_names_for_catalog, resolve_tool_name,
 };
+use crate::user_store::UserStore;
 use crate::user_tools::UserToolBindings;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Local};
+use serde::Serialize;
 use std::collections::HashSet;
 
 pub struct UserToolContext {
@@ -196,12 +201,241 @@ pub fn is_agent_allowed(
     true
 }
 
+/// Token allowance as shown to the user. Field names keep the legacy daily-quota vocabulary used
+/// by the `quota_usage` event; `daily_quota <= 0` means the user is not metered at all.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserQuotaStatus {
+    pub daily_quota: i64,
+    pub used: i64,
+    pub remaining: i64,
+    pub date: Option<String>,
+    pub allowed: bool,
+    pub unlimited: bool,
+    /// Local time of the next daily grant; `None` when unlimited.
+    pub resets_at: Option<String>,
+}
+
+impl UserQuotaStatus {
+    pub fn unlimited() -> Self {
+        Self {
+            daily_quota: 0,
+            used: 0,
+            remaining: 0,
+            date: None,
+            allowed: true,
+            unlimited: true,
+            resets_at: None,
+        }
+    }
+
+    pub fn from_token_status(status: &UserTokenBalanceStatus) -> Self {
+        if status.granted_total <= 0 && status.daily_grant <= 0 {
+            return Self::unlimited();
+        }
+        Self {
+            daily_quota: status.granted_total,
+            used: status.used_total,
+            remaining: status.balance,
+            date: status.last_grant_date.clone(),
+            allowed: status.allowed,
+            unlimited: false,
+            resets_at: Some(next_quota_reset(Local::now()).to_rfc3339()),
+        }
+    }
+}
+
+/// The daily grant is applied by the first request of each local day, so the allowance resets
+/// at the next local midnight.
+pub fn next_quota_reset(now: DateTime<Local>) -> DateTime<Local> {
+    now.date_naive()
+        .succ_opt()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .unwrap_or_else(|| now + Duration::days(1))
+}
+
+/// Daily token grant for `user_id`, resolved from the level of the user's org unit.
+pub fn daily_token_grant_for_user(storage: &dyn StorageBackend, user_id: &str) -> Result<i64> {
+    let user = storage.get_user_account(user_id)?;
+    let unit_level = match user.as_ref().and_then(|record| record.unit_id.as_deref()) {
+        Some(unit_id) => storage
+            .get_org_unit(unit_id)
+            .ok()
+            .flatten()
+            .map(|unit| unit.level),
+        None => None,
+    };
+    Ok(UserStore::default_daily_token_grant_by_level(unit_level))
+}
+
+/// Token status after today's grant is applied; `None` for users without an account, who are
+/// never metered. Admin bypass is left to the caller.
+pub fn prepare_user_token_status(
+    storage: &dyn StorageBackend,
+    user_id: &str,
+) -> Result<Option<UserTokenBalanceStatus>> {
+    let daily_grant = daily_token_grant_for_user(storage, user_id)?;
+    storage.prepare_user_token_balance(user_id, &UserStore::today_string(), daily_grant)
+}
+
+/// Quota status for `user_id`, applying today's grant first so the numbers match what the next
+/// model call will see. Admins and users without an account are never metered.
+pub fn load_user_quota_status(state: &AppState, user_id: &str) -> Result<UserQuotaStatus> {
+    let Some(user) = state.storage.get_user_account(user_id)? else {
+        return Ok(UserQuotaStatus::unlimited());
+    };
+    if UserStore::is_admin(&user) {
+        return Ok(UserQuotaStatus::unlimited());
+    }
+    let status = prepare_user_token_status(state.storage.as_ref(), &user.user_id)?;
+    Ok(status
+        .as_ref()
+        .map(UserQuotaStatus::from_token_status)
+        .unwrap_or_else(UserQuotaStatus::unlimited))
+}
+
+/// Allow-list after a grant or revoke; `allowed_tools: None` means every available tool is allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolAccessUpdate {
+    pub allowed_tools: Option<Vec<String>>,
+    pub changed: Vec<String>,
+}
+
+/// Tool names a grant or revoke for `user_id` may refer to, including the user's own tools.
+pub async fn available_tool_names_for_user(state: &AppState, user_id: &str) -> HashSet<String> {
+    let context = build_user_tool_context(state, user_id).await;
+    collect_available_tool_names(&context.config, &context.skills, Some(&context.bindings))
+}
+
+/// Trims and dedupes `raw`, mapping builtin aliases to their canonical names. Every name must be
+/// in `available` so a typo cannot end up in an allow-list.
+pub fn normalize_tool_access_names(
+    raw: &[String],
+    available: &HashSet<String>,
+) -> Result<Vec<String>> {
+    let mut output = Vec::new();
+    let mut unknown = Vec::new();
+    for name in raw
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+    {
+        let canonical = resolve_tool_name(name);
+        let resolved = if available.contains(&canonical) {
+            canonical
+        } else if available.contains(name) {
+            name.to_string()
+        } else {
+            unknown.push(name.to_string());
+            continue;
+        };
+        if !output.contains(&resolved) {
+            output.push(resolved);
+        }
+    }
+    if !unknown.is_empty() {
+        return Err(anyhow!("unknown tools: {}", unknown.join(", ")));
+    }
+    if output.is_empty() {
+        return Err(anyhow!("no tool names given"));
+    }
+    Ok(output)
+}
+
+/// Adds `tools` to the user's allow-list. A user without an allow-list can already use every
+/// tool, so nothing is stored in that case.
+pub fn grant_tools(
+    user_store: &UserStore,
+    user_id: &str,
+    tools: &[String],
+    available: &HashSet<String>,
+) -> Result<ToolAccessUpdate> {
+    let tools = normalize_tool_access_names(tools, available)?;
+    let Some(mut allowed) = load_tool_allow_list(user_store, user_id)? else {
+        return Ok(ToolAccessUpdate {
+            allowed_tools: None,
+            changed: Vec::new(),
+        });
+    };
+    let mut changed = Vec::new();
+    for tool in tools {
+        if !allowed.iter().any(|name| same_tool(name, &tool)) {
+            allowed.push(tool.clone());
+            changed.push(tool);
+        }
+    }
+    if !changed.is_empty() {
+        user_store.set_user_tool_access(user_id, Some(&allowed))?;
+    }
+    Ok(ToolAccessUpdate {
+        allowed_tools: Some(allowed),
+        changed,
+    })
+}
+
+/// Removes `tools` from the user's allow-list. A user without one first gets every available
+/// tool listed. Revoking the last tool is refused because an empty list means unrestricted.
+pub fn revoke_tools(
+    user_store: &UserStore,
+    user_id: &str,
+    tools: &[String],
+    available: &HashSet<String>,
+) -> Result<ToolAccessUpdate> {
+    let tools = normalize_tool_access_names(tools, available)?;
+    let mut allowed = match load_tool_allow_list(user_store, user_id)? {
+        Some(allowed) => allowed,
+        None => {
+            let mut all = available.iter().cloned().collect::<Vec<_>>();
+            all.sort();
+            all
+        }
+    };
+    let mut changed = Vec::new();
+    allowed.retain(
+        |name| match tools.iter().find(|tool| same_tool(name, tool)) {
+            Some(tool) => {
+                if !changed.contains(tool) {
+                    changed.push(tool.clone());
+                }
+                false
+            }
+            None => true,
+        },
+    );
+    if allowed.is_empty() {
+        return Err(anyhow!(
+            "cannot revoke every allowed tool: an empty allow-list means unrestricted access"
+        ));
+    }
+    if !changed.is_empty() {
+        user_store.set_user_tool_access(user_id, Some(&allowed))?;
+    }
+    Ok(ToolAccessUpdate {
+        allowed_tools: Some(allowed),
+        changed,
+    })
+}
+
+fn load_tool_allow_list(user_store: &UserStore, user_id: &str) -> Result<Option<Vec<String>>> {
+    Ok(user_store
+        .get_user_tool_access(user_id)?
+        .and_then(|record| record.allowed_tools)
+        .filter(|items| !items.is_empty()))
+}
+
+fn same_tool(left: &str, right: &str) -> bool {
+    left == right || resolve_tool_name(left) == resolve_tool_name(right)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{compute_allowed_tool_names, UserToolContext};
+    use super::{
+        compute_allowed_tool_names, grant_tools, next_quota_reset, revoke_tools, UserQuotaStatus,
+        UserToolContext,
+    };
     use crate::config::Config;
     use crate::skills::SkillRegistry;
-    use crate::storage::{UserAccountRecord, UserToolAccessRecord};
+    use crate::storage::{UserAccountRecord, UserTokenBalanceStatus, UserToolAccessRecord};
     use crate::user_tools::UserToolBindings;
 
     fn sample_user() -> UserAccountRecord {
@@ -246,4 +480,117 @@ mod tests {
         let allowed = compute_allowed_tool_names(&sample_user(), &context);
         assert!(allowed.contains("读取文件") || allowed.contains("read_file"));
     }
+
+    #[test]
+    fn token_status_without_any_grant_is_unlimited() {
+        let status = UserTokenBalanceStatus {
+            balance: 0,
+            granted_total: 0,
+            used_total: 0,
+            daily_grant: 0,
+            last_grant_date: None,
+            allowed: false,
+            overspent_tokens: 0,
+        };
+        assert_eq!(
+            UserQuotaStatus::from_token_status(&status),
+            UserQuotaStatus::unlimited()
+        );
+
+        let metered = UserQuotaStatus::from_token_status(&UserTokenBalanceStatus {
+            balance: 40,
+            granted_total: 1000,
+            used_total: 960,
+            daily_grant: 1000,
+            last_grant_date: Some("2026-10-16".to_string()),
+            allowed: true,
+            overspent_tokens: 0,
+        });
+        assert!(!metered.unlimited);
+        assert_eq!(
+            (metered.daily_quota, metered.used, metered.remaining),
+            (1000, 960, 40)
+        );
+    }
+
+    #[test]
+    fn grant_and_revoke_tools_keep_other_entries() {
+        use crate::storage::{SqliteStorage, StorageBackend};
+        use crate::user_store::UserStore;
+        use std::collections::HashSet;
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let storage: Arc<dyn StorageBackend> = Arc::new(SqliteStorage::new(
+            dir.path()
+                .join("tool-access.db")
+                .to_string_lossy()
+                .to_string(),
+        ));
+        storage.ensure_initialized().expect("initialize storage");
+        let user_store = UserStore::new(storage);
+        let available = ["读取文件", "写入文件", "列出文件", "docs@search"]
+            .into_iter()
+            .map(str::to_string)
+            .collect::<HashSet<_>>();
+        let names = |values: &[&str]| -> Vec<String> {
+            values.iter().map(|value| value.to_string()).collect()
+        };
+        user_store
+            .set_user_tool_access("alice", Some(&names(&["读取文件", "docs@search"])))
+            .expect("seed allow-list");
+
+        let granted = grant_tools(
+            &user_store,
+            "alice",
+            &names(&["write_file", " 写入文件 ", "读取文件"]),
+            &available,
+        )
+        .expect("grant");
+        assert_eq!(granted.changed, names(&["写入文件"]));
+        assert_eq!(
+            granted.allowed_tools,
+            Some(names(&["读取文件", "docs@search", "写入文件"]))
+        );
+
+        let revoked =
+            revoke_tools(&user_store, "alice", &names(&["read_file"]), &available).expect("revoke");
+        assert_eq!(revoked.changed, names(&["读取文件"]));
+        let stored = user_store
+            .get_user_tool_access("alice")
+            .expect("load access")
+            .and_then(|record| record.allowed_tools);
+        assert_eq!(stored, Some(names(&["docs@search", "写入文件"])));
+
+        assert!(grant_tools(&user_store, "alice", &names(&["no_such_tool"]), &available).is_err());
+        assert!(revoke_tools(
+            &user_store,
+            "alice",
+            &names(&["docs@search", "写入文件"]),
+            &available
+        )
+        .is_err());
+
+        let unrestricted =
+            grant_tools(&user_store, "bob", &names(&["read_file"]), &available).expect("grant");
+        assert_eq!(unrestricted.allowed_tools, None);
+        let narrowed =
+            revoke_tools(&user_store, "bob", &names(&["docs@search"]), &available).expect("revoke");
+        let mut expected = names(&["写入文件", "列出文件", "读取文件"]);
+        expected.sort();
+        assert_eq!(narrowed.allowed_tools, Some(expected));
+    }
+
+    #[test]
+    fn quota_resets_at_the_next_local_midnight() {
+        use chrono::{Local, TimeZone, Timelike};
+        let now = Local
+            .with_ymd_and_hms(2026, 10, 16, 21, 30, 0)
+            .earliest()
+            .expect("valid local time");
+        let reset = next_quota_reset(now);
+        assert_eq!(reset.date_naive().to_string(), "2026-10-17");
+        assert_eq!((reset.hour(), reset.minute()), (0, 0));
+        assert!(reset > now);
+    }
 }
@@ -870,7 +870,15 @@ impl UserStore {
     }
 
     pub fn delete_chat_session(&self, user_id: &str, session_id: &str) -> Result<i64> {
-        self.storage.delete_chat_session(user_id, session_id)
+        let deleted = self.storage.delete_chat_session(user_id, session_id)?;
+        if deleted > 0 {
+            let _ = crate::session_approvals::clear_session_approvals(
+                self.storage.as_ref(),
+                user_id,
+                session_id,
+            );
+        }
+        Ok(deleted)
     }
 
     pub fn list_beeroom_chat_messages(
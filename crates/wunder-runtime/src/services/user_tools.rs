@@ -18,6 +18,10 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::UNIX_EPOCH;
 
+mod org_unit_skills;
+mod skill_sharing;
+mod skill_watch;
+
 const USER_TOOLS_ROOT_ENV: &str = "WUNDER_USER_TOOLS_ROOT";
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -545,7 +549,10 @@ impl UserToolStore {
     }
 
     fn resolve_default_skill_enabled(&self, user_id: &str) -> Vec<String> {
-        let skill_root = self.get_skill_root(user_id);
+        self.discover_skill_names(&self.get_skill_root(user_id))
+    }
+
+    fn discover_skill_names(&self, skill_root: &Path) -> Vec<String> {
         if !skill_root.exists() || !skill_root.is_dir() {
             return Vec::new();
         }
@@ -569,6 +576,7 @@ pub struct UserToolManager {
     store: Arc<UserToolStore>,
     skill_cache: Mutex<SkillCache>,
     skill_cache_max: usize,
+    skill_watch: Mutex<skill_watch::SkillWatchState>,
 }
 
 impl UserToolManager {
@@ -577,6 +585,7 @@ impl UserToolManager {
             store,
             skill_cache: Mutex::new(SkillCache::default()),
             skill_cache_max: 128,
+            skill_watch: Mutex::new(skill_watch::SkillWatchState::default()),
         }
     }
 
@@ -687,6 +696,8 @@ impl UserToolManager {
         } else {
             user_payload.user_id.clone()
         };
+        self.watch_skill_root(config, &current_owner_id);
+        let skill_unit_chain = self.store.resolve_skill_unit_chain(&current_owner_id);
 
         {
             let mut append_alias = |alias: String,
@@ -845,9 +856,16 @@ impl UserToolManager {
             };
 
             let shared_tools_filter = shared_tools_filter.cloned();
+            // `require_opt_in` applies the user's `shared_tools` selection; org unit skills
+            // are handed to members without it. `source_key` names the skill source in the
+            // bindings and cache; org units use a key no user id can collide with.
             let mut collect_skill_tools =
-                |skill_owner_id: &str, names: &[String], shared_only: bool| {
-                    let skill_root = self.store.get_skill_root(skill_owner_id);
+                |skill_owner_id: &str,
+                 source_key: &str,
+                 skill_root: PathBuf,
+                 names: &[String],
+                 shared_only: bool,
+                 require_opt_in: bool| {
                     if !skill_root.exists() {
                         return;
                     }
@@ -864,12 +882,8 @@ impl UserToolManager {
                     } else {
                         HashSet::new()
                     };
-                    let specs = self.load_cached_skill_specs(
-                        config,
-                        skill_owner_id,
-                        &skill_root,
-                        &cache_names,
-                    );
+                    let specs =
+                        self.load_cached_skill_specs(config, source_key, &skill_root, &cache_names);
                     if specs.is_empty() {
                         return;
                     }
@@ -882,7 +896,7 @@ impl UserToolManager {
                         return;
                     }
                     register_skill_source(
-                        skill_owner_id,
+                        source_key,
                         skill_root.clone(),
                         enabled.iter().cloned().collect(),
                     );
@@ -899,7 +913,7 @@ impl UserToolManager {
                         let legacy_alias = allow_bare_name
                             .then(|| self.store.build_alias_name(skill_owner_id, &spec.name))
                             .filter(|legacy_name| legacy_name != &alias_name);
-                        if shared_only {
+                        if require_opt_in {
                             if let Some(filter) = shared_tools_filter.as_ref() {
                                 if !filter.contains(&alias_name) {
                                     continue;
@@ -916,7 +930,7 @@ impl UserToolManager {
                         }
                         let alias_info = UserToolAlias {
                             kind: UserToolKind::Skill,
-                            owner_id: skill_owner_id.to_string(),
+                            owner_id: source_key.to_string(),
                             target: spec.name.clone(),
                         };
                         blocked_names.insert(alias_name.clone());
@@ -936,14 +950,39 @@ impl UserToolManager {
                     }
                 };
 
-            collect_skill_tools(&current_owner_id, &user_payload.skills.enabled, false);
+            collect_skill_tools(
+                &current_owner_id,
+                &current_owner_id,
+                self.store.get_skill_root(&current_owner_id),
+                &user_payload.skills.enabled,
+                false,
+                false,
+            );
             for shared_payload in &shared_payloads {
                 let shared_owner = if shared_payload.user_id.trim().is_empty() {
                     user_id.to_string()
                 } else {
                     shared_payload.user_id.clone()
                 };
-                collect_skill_tools(&shared_owner, &shared_payload.skills.shared, true);
+                collect_skill_tools(
+                    &shared_owner,
+                    &shared_owner,
+                    self.store.get_skill_root(&shared_owner),
+                    &shared_payload.skills.shared,
+                    true,
+                    true,
+                );
+            }
+            for unit_id in &skill_unit_chain {
+                let unit_skills = self.store.load_org_unit_skills(unit_id);
+                collect_skill_tools(
+                    unit_id,
+                    &org_unit_skills::org_unit_skill_owner(unit_id),
+                    self.store.get_org_unit_skill_root(unit_id),
+                    &unit_skills,
+                    true,
+                    false,
+                );
             }
         }
 
@@ -1029,7 +1068,10 @@ impl UserToolManager {
             mcp_servers,
             shared_tools_enabled,
             user_version: user_payload.version,
-            shared_version: self.store.shared_version(),
+            shared_version: self
+                .store
+                .shared_version()
+                .max(self.store.org_unit_skills_version(&skill_unit_chain)),
         }
     }
 
@@ -1356,14 +1398,16 @@ fn normalize_mcp_servers(mut servers: Vec<UserMcpServer>) -> Vec<UserMcpServer>
 
 fn normalize_skill_config(enabled: Vec<String>, shared: Vec<String>) -> UserSkillConfig {
     let enabled = normalize_name_list(enabled);
+    let shared = normalize_name_list(shared);
+    // Legacy configs only listed shared skills; they were enabled for the primary agent too.
     let effective_enabled = if enabled.is_empty() {
-        normalize_name_list(shared)
+        shared.clone()
     } else {
         enabled
     };
     UserSkillConfig {
         enabled: effective_enabled,
-        shared: Vec::new(),
+        shared,
     }
 }
 
@@ -1652,14 +1696,14 @@ mod tests {
     }
 
     #[test]
-    fn normalize_skill_config_preserves_shared_names_without_enabled_list() {
+    fn normalize_skill_config_keeps_shared_names_and_falls_back_for_enabled() {
         let config =
             normalize_skill_config(Vec::new(), vec!["alpha".to_string(), "beta".to_string()]);
         assert_eq!(
             config.enabled,
             vec!["alpha".to_string(), "beta".to_string()]
         );
-        assert!(config.shared.is_empty());
+        assert_eq!(config.shared, vec!["alpha".to_string(), "beta".to_string()]);
     }
 
     #[test]
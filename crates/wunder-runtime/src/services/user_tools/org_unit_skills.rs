@@ -0,0 +1,262 @@
+// 组织单元技能：单元技能放在独立的组织单元技能根目录（与用户目录隔离），本单元及下级单元成员自动获得。
+use super::{file_modified_ts, safe_user_id, UserToolManager, UserToolStore};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Owner key of org unit skills in bindings and the skill cache. `@` never appears in a
+/// user id used as an owner (aliases are `owner@name`), so a user cannot take this key over.
+const ORG_UNIT_SKILL_OWNER_PREFIX: &str = "@org_unit/";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OrgUnitSkillsFile {
+    #[serde(default)]
+    skills: Vec<String>,
+}
+
+pub(super) fn org_unit_skill_owner(unit_id: &str) -> String {
+    format!("{ORG_UNIT_SKILL_OWNER_PREFIX}{}", unit_id.trim())
+}
+
+impl UserToolStore {
+    /// Skill folder of an org unit. It lives under `.org_units` of the user tools root;
+    /// `safe_user_id` never yields a leading dot, so no user directory can overlap it,
+    /// whatever id (e.g. a predictable `unit_*` one) the user registers.
+    pub fn get_org_unit_skill_root(&self, unit_id: &str) -> PathBuf {
+        self.org_unit_dir(unit_id).join("skills")
+    }
+
+    /// Skills the unit hands to its members, as set through the admin API.
+    pub fn load_org_unit_skills(&self, unit_id: &str) -> Vec<String> {
+        let path = self.org_unit_skills_path(unit_id);
+        let Ok(raw) = std::fs::read_to_string(&path) else {
+            return Vec::new();
+        };
+        match serde_json::from_str::<OrgUnitSkillsFile>(&raw) {
+            Ok(file) => file.skills,
+            Err(err) => {
+                tracing::warn!("invalid org unit skills file {}: {err}", path.display());
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn update_org_unit_skills(&self, unit_id: &str, names: Vec<String>) -> Result<Vec<String>> {
+        let mut skills = names
+            .into_iter()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>();
+        skills.sort();
+        skills.dedup();
+        std::fs::create_dir_all(self.org_unit_dir(unit_id))?;
+        let payload = serde_json::to_string_pretty(&OrgUnitSkillsFile {
+            skills: skills.clone(),
+        })?;
+        std::fs::write(self.org_unit_skills_path(unit_id), payload)?;
+        Ok(skills)
+    }
+
+    /// Skill names found under the unit's skill root, whether or not they are shared yet.
+    pub fn list_org_unit_skill_candidates(&self, unit_id: &str) -> Vec<String> {
+        self.discover_skill_names(&self.get_org_unit_skill_root(unit_id))
+    }
+
+    /// Latest change of the skill lists along `unit_ids`, folded into the member's bindings
+    /// version so prompt caches built from the old list are not reused.
+    pub(super) fn org_unit_skills_version(&self, unit_ids: &[String]) -> f64 {
+        unit_ids
+            .iter()
+            .map(|unit_id| file_modified_ts(&self.org_unit_skills_path(unit_id)))
+            .fold(0.0, f64::max)
+    }
+
+    fn org_unit_dir(&self, unit_id: &str) -> PathBuf {
+        self.legacy_root
+            .join(".org_units")
+            .join(safe_user_id(unit_id))
+    }
+
+    fn org_unit_skills_path(&self, unit_id: &str) -> PathBuf {
+        self.org_unit_dir(unit_id).join("skills.json")
+    }
+
+    /// Units whose skills `user_id` inherits: every unit on the `path` of the user's own unit,
+    /// root first, so a member of a sub-team also gets the department's skills.
+    pub(super) fn resolve_skill_unit_chain(&self, user_id: &str) -> Vec<String> {
+        let storage = self.workspace.storage();
+        let unit_id = match storage.get_user_account(user_id) {
+            Ok(Some(account)) => account.unit_id,
+            Ok(None) => None,
+            Err(err) => {
+                tracing::warn!("failed to load account {user_id} for org unit skills: {err}");
+                None
+            }
+        };
+        let Some(unit_id) = unit_id
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+        else {
+            return Vec::new();
+        };
+        match storage.get_org_unit(&unit_id) {
+            Ok(Some(unit)) if !unit.path.trim().is_empty() => unit
+                .path
+                .split('/')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+            Ok(_) => vec![unit_id],
+            Err(err) => {
+                tracing::warn!("failed to load org unit {unit_id} for skills: {err}");
+                vec![unit_id]
+            }
+        }
+    }
+}
+
+impl UserToolManager {
+    /// Drop the unit's cached skill specs after its list or files changed; members pick the
+    /// new list up on their next bindings build.
+    pub fn invalidate_org_unit_skills(&self, unit_id: &str) {
+        self.clear_skill_cache(Some(&org_unit_skill_owner(unit_id)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{UserToolManager, USER_TOOLS_ROOT_ENV};
+    use super::*;
+    use crate::config::Config;
+    use crate::skills::SkillRegistry;
+    use crate::storage::{OrgUnitRecord, SqliteStorage, UserAccountRecord, UserAccountStore};
+    use crate::workspace::WorkspaceManager;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    fn unit(unit_id: &str, parent_id: Option<&str>, path: &str) -> OrgUnitRecord {
+        OrgUnitRecord {
+            unit_id: unit_id.to_string(),
+            parent_id: parent_id.map(ToString::to_string),
+            name: unit_id.to_string(),
+            level: path.split('/').count() as i32,
+            path: path.to_string(),
+            path_name: path.replace('/', " / "),
+            sort_order: 0,
+            leader_ids: Vec::new(),
+            created_at: 0.0,
+            updated_at: 0.0,
+        }
+    }
+
+    fn account(user_id: &str, unit_id: Option<&str>) -> UserAccountRecord {
+        UserAccountRecord {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: None,
+            password_hash: String::new(),
+            roles: vec!["user".to_string()],
+            status: "active".to_string(),
+            access_level: "A".to_string(),
+            unit_id: unit_id.map(ToString::to_string),
+            token_balance: 0,
+            token_granted_total: 0,
+            token_used_total: 0,
+            last_token_grant_date: None,
+            experience_total: 0,
+            is_demo: false,
+            created_at: 0.0,
+            updated_at: 0.0,
+            last_login_at: None,
+        }
+    }
+
+    #[test]
+    fn members_inherit_skills_of_their_unit_and_its_ancestors() {
+        let root = tempdir().expect("tempdir");
+        let db_path = root.path().join("user-tools-org-units.db");
+        let storage = Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+        let workspace_root = root.path().join("workspaces");
+        let workspace = Arc::new(WorkspaceManager::new(
+            workspace_root.to_string_lossy().as_ref(),
+            storage.clone(),
+            0,
+            &HashMap::new(),
+        ));
+        storage
+            .upsert_org_unit(&unit("unit_dept", None, "unit_dept"))
+            .expect("upsert dept");
+        storage
+            .upsert_org_unit(&unit("unit_team", Some("unit_dept"), "unit_dept/unit_team"))
+            .expect("upsert team");
+        storage
+            .upsert_user_account(&account("alice", Some("unit_team")))
+            .expect("upsert alice");
+        storage
+            .upsert_user_account(&account("bob", None))
+            .expect("upsert bob");
+
+        // Org unit skills live under the legacy root, which defaults to the cwd-relative
+        // `config/data/user_tools`; keep them inside the tempdir.
+        std::env::set_var(USER_TOOLS_ROOT_ENV, root.path().join("user_tools"));
+        let store = UserToolStore::new(&Config::default(), workspace).expect("create store");
+        std::env::remove_var(USER_TOOLS_ROOT_ENV);
+        for name in ["team_skill", "draft_skill"] {
+            let skill_dir = store.get_org_unit_skill_root("unit_dept").join(name);
+            std::fs::create_dir_all(&skill_dir).expect("create skill dir");
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: {name}\n---\n"),
+            )
+            .expect("write skill file");
+        }
+        store
+            .update_org_unit_skills("unit_dept", vec!["team_skill".to_string()])
+            .expect("update unit skills");
+        // A user who registers the unit's id only controls their own skill root.
+        let squatted = store.get_skill_root("unit_dept").join("team_skill");
+        std::fs::create_dir_all(&squatted).expect("create squatted dir");
+        std::fs::write(
+            squatted.join("SKILL.md"),
+            "---\nname: team_skill\ndescription: injected\n---\n",
+        )
+        .expect("write squatted skill");
+        store
+            .update_skills(
+                "unit_dept",
+                vec!["team_skill".to_string()],
+                vec!["team_skill".to_string()],
+            )
+            .expect("update squatter skills");
+
+        let manager = UserToolManager::new(Arc::new(store));
+        let config = Config::default();
+        let skill_names = |user_id: &str| {
+            manager
+                .build_bindings(&config, &SkillRegistry::default(), user_id)
+                .skill_specs
+                .into_iter()
+                .map(|spec| (spec.name, spec.description))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            skill_names("alice"),
+            vec![("unit_dept@team_skill".to_string(), "team_skill".to_string())]
+        );
+        assert_eq!(skill_names("bob"), Vec::new());
+        // Members' prompt caches key on this version, so a list update invalidates them.
+        let version = |user_id: &str| {
+            manager
+                .build_bindings(&config, &SkillRegistry::default(), user_id)
+                .shared_version
+        };
+        assert!(version("alice") > 0.0);
+        assert_eq!(version("bob"), 0.0);
+        assert_eq!(
+            manager.store().list_org_unit_skill_candidates("unit_dept"),
+            vec!["draft_skill", "team_skill"]
+        );
+    }
+}
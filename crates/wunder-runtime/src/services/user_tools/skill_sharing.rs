@@ -0,0 +1,118 @@
+// 技能共享范围：enabled 技能供主智能体使用，只有 shared 技能会下发给派生的子智能体。
+use super::{UserToolBindings, UserToolKind, UserToolManager};
+use crate::config::Config;
+use crate::skills::SkillRegistry;
+use std::collections::HashSet;
+
+impl UserToolManager {
+    /// Bindings for a spawned sub-agent session. Same as `build_bindings`, except the user's
+    /// own skills are narrowed to `skills.shared`; MCP tools and knowledge bases are unchanged.
+    pub fn build_subagent_bindings(
+        &self,
+        config: &Config,
+        skills: &SkillRegistry,
+        user_id: &str,
+    ) -> UserToolBindings {
+        let mut bindings = self.build_bindings(config, skills, user_id);
+        let shared = self
+            .store
+            .load_user_tools(user_id)
+            .skills
+            .shared
+            .into_iter()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect::<HashSet<_>>();
+        retain_shared_owner_skills(&mut bindings, user_id, &shared);
+        bindings
+    }
+}
+
+fn retain_shared_owner_skills(
+    bindings: &mut UserToolBindings,
+    owner_id: &str,
+    shared: &HashSet<String>,
+) {
+    let owner_id = owner_id.trim();
+    bindings.alias_map.retain(|_, alias| {
+        !matches!(alias.kind, UserToolKind::Skill)
+            || alias.owner_id.trim() != owner_id
+            || shared.contains(alias.target.trim())
+    });
+    let alias_map = &bindings.alias_map;
+    bindings
+        .skill_specs
+        .retain(|spec| alias_map.contains_key(&spec.name));
+    // The skill registry for the owner is rebuilt from these names, so scripts of unshared
+    // skills stay unreachable even through a stale alias.
+    if let Some(source) = bindings.skill_sources.get_mut(owner_id) {
+        source.names.retain(|name| shared.contains(name.trim()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::UserToolStore;
+    use super::*;
+    use crate::storage::SqliteStorage;
+    use crate::workspace::WorkspaceManager;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn subagent_bindings_only_offer_shared_skills() {
+        let root = tempdir().expect("tempdir");
+        let db_path = root.path().join("user-tools-sharing.db");
+        let storage = Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+        let workspace_root = root.path().join("workspaces");
+        let workspace = Arc::new(WorkspaceManager::new(
+            workspace_root.to_string_lossy().as_ref(),
+            storage,
+            0,
+            &HashMap::new(),
+        ));
+        let store = UserToolStore::new(&Config::default(), workspace).expect("create store");
+        for name in ["private_skill", "team_skill"] {
+            let skill_dir = store.get_skill_root("alice").join(name);
+            std::fs::create_dir_all(&skill_dir).expect("create skill dir");
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: {name}\n---\n"),
+            )
+            .expect("write skill file");
+        }
+        store
+            .update_skills(
+                "alice",
+                vec!["private_skill".to_string(), "team_skill".to_string()],
+                vec!["team_skill".to_string()],
+            )
+            .expect("update user skills");
+
+        let manager = UserToolManager::new(Arc::new(store));
+        let skill_names = |bindings: &UserToolBindings| {
+            let mut names = bindings
+                .skill_specs
+                .iter()
+                .map(|spec| spec.name.clone())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+        let config = Config::default();
+        let main = manager.build_bindings(&config, &SkillRegistry::default(), "alice");
+        let subagent = manager.build_subagent_bindings(&config, &SkillRegistry::default(), "alice");
+
+        assert_eq!(skill_names(&main), vec!["private_skill", "team_skill"]);
+        assert_eq!(skill_names(&subagent), vec!["team_skill"]);
+        assert!(!subagent.alias_map.contains_key("private_skill"));
+        assert_eq!(
+            subagent
+                .skill_sources
+                .get("alice")
+                .map(|source| source.names.clone()),
+            Some(vec!["team_skill".to_string()])
+        );
+    }
+}
@@ -0,0 +1,266 @@
+// 技能目录监听：登记用户技能根目录（有上限，闲置淘汰），在锁外轮询文件指纹，变更稳定后清理该用户的技能缓存。
+use super::UserToolManager;
+use crate::config::Config;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+const DEFAULT_SKILL_WATCH_DEBOUNCE_MS: u64 = 500;
+const SKILL_WATCH_POLL_INTERVAL_MS: u64 = 1000;
+/// Upper bound on polled roots; the least recently used owner is dropped beyond it.
+const MAX_WATCHED_SKILL_ROOTS: usize = 256;
+/// Owners that have not built bindings for this long stop being polled.
+const SKILL_WATCH_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+
+type SkillRootFingerprint = Vec<(PathBuf, u64, u128)>;
+
+#[derive(Default)]
+pub(super) struct SkillWatchState {
+    roots: HashMap<String, WatchedSkillRoot>,
+}
+
+struct WatchedSkillRoot {
+    root: PathBuf,
+    fingerprint: SkillRootFingerprint,
+    changed_at: Option<Instant>,
+    last_used: Instant,
+}
+
+impl SkillWatchState {
+    fn insert(&mut self, owner_id: &str, watched: WatchedSkillRoot) {
+        if !self.roots.contains_key(owner_id) && self.roots.len() >= MAX_WATCHED_SKILL_ROOTS {
+            let oldest = self
+                .roots
+                .iter()
+                .min_by_key(|(_, watched)| watched.last_used)
+                .map(|(owner_id, _)| owner_id.clone());
+            if let Some(oldest) = oldest {
+                self.roots.remove(&oldest);
+            }
+        }
+        self.roots.entry(owner_id.to_string()).or_insert(watched);
+    }
+}
+
+impl UserToolManager {
+    /// Start watching `owner_id`'s own skill root when `skills.watch` is enabled. Only roots
+    /// of users that actually build bindings are polled, and idle ones are dropped again.
+    pub(super) fn watch_skill_root(&self, config: &Config, owner_id: &str) {
+        if !config.skills.watch {
+            return;
+        }
+        {
+            let mut state = self
+                .skill_watch
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            if let Some(watched) = state.roots.get_mut(owner_id) {
+                watched.last_used = Instant::now();
+                return;
+            }
+        }
+        // Walk the tree without holding the lock other owners' bindings need.
+        let root = self.store.get_skill_root(owner_id);
+        let fingerprint = skill_root_fingerprint(&root);
+        self.skill_watch
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(
+                owner_id,
+                WatchedSkillRoot {
+                    root,
+                    fingerprint,
+                    changed_at: None,
+                    last_used: Instant::now(),
+                },
+            );
+    }
+
+    /// Check every watched root once. An owner's cache is cleared after its files changed and
+    /// then stayed unchanged for `debounce`, so a burst of edits causes a single reload.
+    /// Returns the owners whose cache was cleared.
+    pub fn poll_skill_watch(&self, debounce: Duration) -> Vec<String> {
+        let now = Instant::now();
+        let targets = {
+            let mut state = self
+                .skill_watch
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            state
+                .roots
+                .retain(|_, watched| now.duration_since(watched.last_used) < SKILL_WATCH_IDLE_TTL);
+            state
+                .roots
+                .iter()
+                .map(|(owner_id, watched)| (owner_id.clone(), watched.root.clone()))
+                .collect::<Vec<_>>()
+        };
+        let fingerprints = targets
+            .into_iter()
+            .map(|(owner_id, root)| (owner_id, skill_root_fingerprint(&root)))
+            .collect::<Vec<_>>();
+
+        let mut settled = Vec::new();
+        {
+            let mut state = self
+                .skill_watch
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            for (owner_id, fingerprint) in fingerprints {
+                // The owner may have been evicted while the lock was released.
+                let Some(watched) = state.roots.get_mut(&owner_id) else {
+                    continue;
+                };
+                if fingerprint != watched.fingerprint {
+                    watched.fingerprint = fingerprint;
+                    watched.changed_at = Some(now);
+                }
+                if watched
+                    .changed_at
+                    .is_some_and(|changed_at| now.duration_since(changed_at) >= debounce)
+                {
+                    watched.changed_at = None;
+                    settled.push(owner_id);
+                }
+            }
+        }
+        for owner_id in &settled {
+            self.clear_skill_cache(Some(owner_id));
+        }
+        settled
+    }
+
+    /// Spawn the polling loop behind `skills.watch`; does nothing when the option is off.
+    pub fn spawn_skill_watch(self: Arc<Self>, config: &Config) {
+        if !config.skills.watch {
+            return;
+        }
+        let debounce = Duration::from_millis(
+            config
+                .skills
+                .watch_debounce_ms
+                .unwrap_or(DEFAULT_SKILL_WATCH_DEBOUNCE_MS),
+        );
+        tokio::spawn(async move {
+            let mut tick =
+                tokio::time::interval(Duration::from_millis(SKILL_WATCH_POLL_INTERVAL_MS));
+            loop {
+                tick.tick().await;
+                let manager = self.clone();
+                // Walking skill trees is blocking filesystem work.
+                let _ =
+                    tokio::task::spawn_blocking(move || manager.poll_skill_watch(debounce)).await;
+            }
+        });
+    }
+}
+
+/// Path, size and mtime of every file under `root`. The cache signature only tracks the root
+/// directory's mtime, which does not move when a nested `SKILL.md` is edited in place.
+fn skill_root_fingerprint(root: &Path) -> SkillRootFingerprint {
+    if !root.is_dir() {
+        return Vec::new();
+    }
+    WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_nanos())
+                .unwrap_or(0);
+            Some((entry.into_path(), meta.len(), modified))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::UserToolStore;
+    use super::*;
+    use crate::skills::SkillRegistry;
+    use crate::storage::SqliteStorage;
+    use crate::workspace::WorkspaceManager;
+    use tempfile::tempdir;
+
+    #[test]
+    fn watched_skill_edit_reloads_on_next_build_bindings() {
+        let root = tempdir().expect("tempdir");
+        let db_path = root.path().join("user-tools-watch.db");
+        let storage = Arc::new(SqliteStorage::new(db_path.to_string_lossy().to_string()));
+        let workspace_root = root.path().join("workspaces");
+        let workspace = Arc::new(WorkspaceManager::new(
+            workspace_root.to_string_lossy().as_ref(),
+            storage,
+            0,
+            &HashMap::new(),
+        ));
+        let mut config = Config::default();
+        config.skills.watch = true;
+        let store = UserToolStore::new(&config, workspace).expect("create store");
+        let skill_file = store.get_skill_root("alice").join("demo").join("SKILL.md");
+        std::fs::create_dir_all(skill_file.parent().expect("skill dir")).expect("create dir");
+        std::fs::write(&skill_file, "---\nname: demo\ndescription: v1\n---\n").expect("write");
+        store
+            .update_skills("alice", vec!["demo".to_string()], Vec::new())
+            .expect("update user skills");
+
+        let manager = UserToolManager::new(Arc::new(store));
+        let description = |manager: &UserToolManager| {
+            manager
+                .build_bindings(&config, &SkillRegistry::default(), "alice")
+                .skill_specs
+                .into_iter()
+                .find(|spec| spec.name == "demo")
+                .map(|spec| spec.description)
+        };
+        assert_eq!(description(&manager).as_deref(), Some("v1"));
+
+        std::fs::write(
+            &skill_file,
+            "---\nname: demo\ndescription: edited on disk\n---\n",
+        )
+        .expect("edit skill");
+        // Without a poll the root signature is unchanged and the cached spec is served.
+        assert_eq!(description(&manager).as_deref(), Some("v1"));
+
+        assert_eq!(
+            manager.poll_skill_watch(Duration::ZERO),
+            vec!["alice".to_string()]
+        );
+        assert_eq!(description(&manager).as_deref(), Some("edited on disk"));
+        assert_eq!(
+            manager.poll_skill_watch(Duration::ZERO),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn skill_watch_state_evicts_least_recently_used_root() {
+        let mut state = SkillWatchState::default();
+        let start = Instant::now();
+        for index in 0..=MAX_WATCHED_SKILL_ROOTS {
+            state.insert(
+                &format!("user_{index}"),
+                WatchedSkillRoot {
+                    root: PathBuf::from(format!("/skills/user_{index}")),
+                    fingerprint: Vec::new(),
+                    changed_at: None,
+                    last_used: start + Duration::from_millis(index as u64),
+                },
+            );
+        }
+        assert_eq!(state.roots.len(), MAX_WATCHED_SKILL_ROOTS);
+        assert!(!state.roots.contains_key("user_0"));
+        assert!(state
+            .roots
+            .contains_key(&format!("user_{MAX_WATCHED_SKILL_ROOTS}")));
+    }
+}
@@ -29,6 +29,9 @@ const DEFAULT_CHUNK_SIZE: usize = 800;
 const DEFAULT_CHUNK_OVERLAP: usize = 100;
 const DEFAULT_TOP_K: usize = 5;
 const VECTOR_SEARCH_CANDIDATE_LIMIT: i64 = 2048;
+const DEFAULT_KEYWORD_WEIGHT: f64 = 0.3;
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VectorDocumentMeta {
@@ -123,6 +126,9 @@ pub fn resolve_embedding_model(config: &Config, name: &str) -> Result<LlmModelCo
     if model_name.is_empty() {
         return Err(anyhow!(i18n::t("error.embedding_model_required")));
     }
+    if let Some(model) = config.embedding.models.get(model_name) {
+        return Ok(model.to_model_config());
+    }
     let Some(model) = config.llm.models.get(model_name) else {
         return Err(anyhow!(i18n::t("error.embedding_model_not_found")));
     };
@@ -201,6 +207,20 @@ pub fn resolve_top_k(base: &KnowledgeBaseConfig) -> usize {
     base.top_k.unwrap_or(DEFAULT_TOP_K).max(1)
 }
 
+/// Keyword share of the hybrid score, or `None` when the base uses plain vector search.
+pub fn resolve_keyword_weight(base: &KnowledgeBaseConfig) -> Option<f64> {
+    let mode = base.search_mode.as_deref().unwrap_or("").trim();
+    if !mode.eq_ignore_ascii_case("hybrid") {
+        return None;
+    }
+    let weight = base
+        .keyword_weight
+        .filter(|value| value.is_finite())
+        .map(f64::from)
+        .unwrap_or(DEFAULT_KEYWORD_WEIGHT);
+    Some(weight.clamp(0.0, 1.0))
+}
+
 pub fn split_text_into_chunks(
     text: &str,
     chunk_size: usize,
@@ -833,6 +853,75 @@ pub async fn query_chunks_by_vector(
     vector: &[f32],
     top_k: usize,
 ) -> Result<Vec<VectorSearchHit>> {
+    let scored = score_chunk_embeddings(
+        storage,
+        owner_id,
+        base,
+        root,
+        embedding_model,
+        vector,
+        top_k,
+    )
+    .await?;
+    Ok(take_top_hits(scored, top_k))
+}
+
+/// Search used for retrieval: vector similarity, blended with BM25 keyword scores for `query`
+/// when the base is configured for hybrid search. `score_threshold` applies to the cosine score.
+#[allow(clippy::too_many_arguments)]
+pub async fn query_chunks(
+    storage: &dyn StorageBackend,
+    owner_id: Option<&str>,
+    base: &KnowledgeBaseConfig,
+    root: &Path,
+    embedding_model: &str,
+    vector: &[f32],
+    query: &str,
+    top_k: usize,
+) -> Result<Vec<VectorSearchHit>> {
+    let scored = score_chunk_embeddings(
+        storage,
+        owner_id,
+        base,
+        root,
+        embedding_model,
+        vector,
+        top_k,
+    )
+    .await?;
+    Ok(rank_retrieval_hits(scored, base, query, top_k))
+}
+
+/// Drop candidates under the base's cosine `score_threshold`, then rank the rest. The threshold
+/// is checked before fusion because fused scores are normalized over the candidates and say
+/// nothing about absolute similarity.
+fn rank_retrieval_hits(
+    mut scored: Vec<(f64, VectorSearchHit)>,
+    base: &KnowledgeBaseConfig,
+    query: &str,
+    top_k: usize,
+) -> Vec<VectorSearchHit> {
+    if let Some(threshold) = base.score_threshold {
+        scored.retain(|(score, _)| *score >= f64::from(threshold));
+    }
+    if scored.is_empty() {
+        return Vec::new();
+    }
+    if let Some(keyword_weight) = resolve_keyword_weight(base) {
+        fuse_keyword_scores(&mut scored, query, keyword_weight);
+    }
+    take_top_hits(scored, top_k)
+}
+
+async fn score_chunk_embeddings(
+    storage: &dyn StorageBackend,
+    owner_id: Option<&str>,
+    base: &KnowledgeBaseConfig,
+    root: &Path,
+    embedding_model: &str,
+    vector: &[f32],
+    top_k: usize,
+) -> Result<Vec<(f64, VectorSearchHit)>> {
     ensure_vector_base_type(base)?;
     ensure_vector_documents_migrated(storage, owner_id, &base.name, root).await?;
     if vector.is_empty() {
@@ -846,11 +935,17 @@ pub async fn query_chunks_by_vector(
         embedding_model,
         candidate_limit,
     )?;
+    let candidates = records
+        .into_iter()
+        .filter_map(|record| parse_vector_json(&record.vector_json).map(|parsed| (parsed, record)))
+        .collect::<Vec<_>>();
+    ensure_embedding_dimensions(
+        &base.name,
+        vector.len(),
+        candidates.iter().map(|(candidate, _)| candidate.len()),
+    )?;
     let mut scored = Vec::new();
-    for record in records {
-        let Some(candidate) = parse_vector_json(&record.vector_json) else {
-            continue;
-        };
+    for (candidate, record) in candidates {
         let Some(score) = cosine_similarity(vector, &candidate) else {
             continue;
         };
@@ -868,6 +963,32 @@ pub async fn query_chunks_by_vector(
             },
         ));
     }
+    Ok(scored)
+}
+
+/// A corpus must be embedded with one vector size, and that size must match the query vector;
+/// otherwise cosine scores are meaningless and every chunk would silently drop out.
+fn ensure_embedding_dimensions(
+    base_name: &str,
+    query_dimensions: usize,
+    corpus_dimensions: impl IntoIterator<Item = usize>,
+) -> Result<()> {
+    for dimensions in corpus_dimensions {
+        if dimensions != query_dimensions {
+            return Err(anyhow!(i18n::t_with_params(
+                "error.embedding_dimension_mismatch",
+                &HashMap::from([
+                    ("base".to_string(), base_name.to_string()),
+                    ("expected".to_string(), dimensions.to_string()),
+                    ("actual".to_string(), query_dimensions.to_string()),
+                ]),
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn take_top_hits(mut scored: Vec<(f64, VectorSearchHit)>, top_k: usize) -> Vec<VectorSearchHit> {
     scored.sort_by(|left, right| {
         right
             .0
@@ -876,13 +997,101 @@ pub async fn query_chunks_by_vector(
             .then_with(|| left.1.chunk_index.cmp(&right.1.chunk_index))
     });
     scored.truncate(top_k.max(1));
-    Ok(scored.into_iter().map(|(_, hit)| hit).collect())
+    scored.into_iter().map(|(_, hit)| hit).collect()
+}
+
+/// Replace each candidate's vector score with `(1 - w) * vector + w * keyword`, both min-max
+/// normalized over the candidates, so exact terms like error codes can outrank near misses.
+fn fuse_keyword_scores(scored: &mut [(f64, VectorSearchHit)], query: &str, keyword_weight: f64) {
+    let query_tokens = extract_text_query_tokens(query);
+    if scored.is_empty() || query_tokens.is_empty() {
+        return;
+    }
+    let keyword_scores = bm25_scores(
+        &query_tokens,
+        scored.iter().map(|(_, hit)| hit.content.as_str()),
+    );
+    let max_keyword = keyword_scores.iter().copied().fold(0.0, f64::max);
+    let (min_vector, max_vector) = scored
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(low, high), (score, _)| {
+            (low.min(*score), high.max(*score))
+        });
+    let vector_span = max_vector - min_vector;
+    for ((score, hit), keyword) in scored.iter_mut().zip(keyword_scores) {
+        let vector = if vector_span > 0.0 {
+            (*score - min_vector) / vector_span
+        } else {
+            1.0
+        };
+        let keyword = if max_keyword > 0.0 {
+            keyword / max_keyword
+        } else {
+            0.0
+        };
+        *score = (1.0 - keyword_weight) * vector + keyword_weight * keyword;
+        hit.score = Some(*score);
+    }
+}
+
+/// Okapi BM25 score of each document for the query terms.
+fn bm25_scores<'a>(query_tokens: &[String], documents: impl Iterator<Item = &'a str>) -> Vec<f64> {
+    let documents = documents
+        .map(|text| {
+            let tokens = split_text_tokens(text);
+            let mut frequencies: HashMap<String, usize> = HashMap::new();
+            for token in &tokens {
+                *frequencies.entry(token.clone()).or_default() += 1;
+            }
+            (tokens.len(), frequencies)
+        })
+        .collect::<Vec<_>>();
+    if documents.is_empty() {
+        return Vec::new();
+    }
+    let count = documents.len() as f64;
+    let average_len =
+        (documents.iter().map(|(len, _)| *len).sum::<usize>() as f64 / count).max(1.0);
+    let idf = query_tokens
+        .iter()
+        .map(|token| {
+            let df = documents
+                .iter()
+                .filter(|(_, frequencies)| frequencies.contains_key(token))
+                .count() as f64;
+            ((count - df + 0.5) / (df + 0.5) + 1.0).ln()
+        })
+        .collect::<Vec<_>>();
+    documents
+        .iter()
+        .map(|(len, frequencies)| {
+            query_tokens
+                .iter()
+                .zip(&idf)
+                .map(|(token, idf)| {
+                    let frequency = frequencies.get(token).copied().unwrap_or(0) as f64;
+                    let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * *len as f64 / average_len);
+                    idf * frequency * (BM25_K1 + 1.0) / (frequency + norm)
+                })
+                .sum()
+        })
+        .collect()
 }
 
 fn extract_text_query_tokens(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    split_text_tokens(query)
+        .into_iter()
+        .filter(|token| seen.insert(token.clone()))
+        .take(32)
+        .collect()
+}
+
+/// Lowercased ASCII words of two or more characters, plus single CJK characters.
+fn split_text_tokens(text: &str) -> Vec<String> {
     let mut tokens = Vec::new();
     let mut current = String::new();
-    for ch in query.chars() {
+    for ch in text.chars() {
         if ch.is_ascii_alphanumeric() {
             current.push(ch.to_ascii_lowercase());
             continue;
@@ -898,12 +1107,7 @@ fn extract_text_query_tokens(query: &str) -> Vec<String> {
     if current.len() >= 2 {
         tokens.push(current);
     }
-    let mut seen = HashSet::new();
     tokens
-        .into_iter()
-        .filter(|token| seen.insert(token.clone()))
-        .take(32)
-        .collect()
 }
 
 fn score_text_chunk(
@@ -1219,6 +1423,101 @@ mod tests {
         assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), None);
     }
 
+    #[test]
+    fn query_against_corpus_with_other_dimensions_is_rejected() {
+        assert!(ensure_embedding_dimensions("manuals", 3, [3, 3]).is_ok());
+
+        let err = ensure_embedding_dimensions("manuals", 1024, [768, 768])
+            .expect_err("mismatched query must be rejected");
+        let message = err.to_string();
+        assert!(message.contains("manuals"), "{message}");
+        assert!(message.contains("768"), "{message}");
+        assert!(message.contains("1024"), "{message}");
+    }
+
+    fn hit(index: usize, content: &str) -> VectorSearchHit {
+        VectorSearchHit {
+            doc_id: format!("doc-{index}"),
+            doc_name: "manual.md".to_string(),
+            chunk_index: index,
+            start: 0,
+            end: content.len(),
+            content: content.to_string(),
+            embedding_model: "model-a".to_string(),
+            score: None,
+        }
+    }
+
+    #[test]
+    fn hybrid_search_ranks_exact_term_match_above_closer_vector() {
+        let candidates = vec![
+            (
+                0.82,
+                hit(0, "Gateway timeout configuration and retry settings."),
+            ),
+            (
+                0.74,
+                hit(
+                    1,
+                    "Error E4041 is raised when the upload quota is exceeded.",
+                ),
+            ),
+            (0.60, hit(2, "General notes about deployment.")),
+        ];
+        let query = "what does E4041 mean";
+
+        let vector_only = take_top_hits(candidates.clone(), 1);
+        assert_eq!(vector_only[0].chunk_index, 0);
+
+        let base = KnowledgeBaseConfig {
+            search_mode: Some("hybrid".to_string()),
+            ..Default::default()
+        };
+        let keyword_weight = resolve_keyword_weight(&base).expect("hybrid mode");
+        let mut fused = candidates;
+        fuse_keyword_scores(&mut fused, query, keyword_weight);
+        let hybrid = take_top_hits(fused, 3);
+        assert_eq!(hybrid[0].chunk_index, 1);
+        assert!(hybrid[0].score > hybrid[1].score);
+
+        assert_eq!(
+            resolve_keyword_weight(&KnowledgeBaseConfig::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn score_threshold_filters_cosine_scores_before_fusion() {
+        let candidates = vec![
+            (0.82, hit(0, "Gateway timeout configuration.")),
+            (
+                0.74,
+                hit(1, "Error E4041 means the upload quota is exceeded."),
+            ),
+            (
+                0.40,
+                hit(2, "Error E4041 appears in the deployment notes too."),
+            ),
+        ];
+        let base = KnowledgeBaseConfig {
+            search_mode: Some("hybrid".to_string()),
+            score_threshold: Some(0.7),
+            ..Default::default()
+        };
+        let hits = rank_retrieval_hits(candidates.clone(), &base, "E4041", 5);
+        let mut kept = hits.iter().map(|hit| hit.chunk_index).collect::<Vec<_>>();
+        kept.sort();
+        // The weak match stays out even though its keyword score would lift it after fusion,
+        // and each passing hit is kept even when its fused score normalizes to zero.
+        assert_eq!(kept, vec![0, 1]);
+
+        let strict = KnowledgeBaseConfig {
+            score_threshold: Some(0.9),
+            ..base
+        };
+        assert!(rank_retrieval_hits(candidates, &strict, "E4041", 5).is_empty());
+    }
+
     #[test]
     fn vector_chunk_embedding_records_preserve_chunk_identity() {
         let chunks = vec![VectorChunk {
@@ -320,6 +320,10 @@ impl WorkspaceManager {
         &self.root
     }
 
+    pub fn storage(&self) -> &Arc<dyn StorageBackend> {
+        &self.storage
+    }
+
     pub fn container_roots(&self) -> HashMap<i32, String> {
         self.container_roots
             .read()
@@ -1551,6 +1555,46 @@ impl WorkspaceManager {
         let _ = self.delete_session_context_tokens(cleaned_user, cleaned_session);
         let _ = self.delete_session_context_overflow(cleaned_user, cleaned_session);
         let _ = self.delete_session_context_limit_hint(cleaned_user, cleaned_session);
+        let _ = crate::services::sandbox_session::release_session_container_id(
+            self.storage.as_ref(),
+            cleaned_user,
+            cleaned_session,
+        );
+    }
+
+    /// Start a session over without deleting it: chat history, model context, replayable stream
+    /// events and context accounting are cleared, while tool and artifact logs stay for auditing.
+    /// Returns the number of deleted chat records.
+    pub async fn clear_session_messages(
+        self: &Arc<Self>,
+        user_id: &str,
+        session_id: &str,
+    ) -> Result<i64> {
+        let cleaned_user = user_id.trim().to_string();
+        let cleaned_session = session_id.trim().to_string();
+        if cleaned_user.is_empty() || cleaned_session.is_empty() {
+            return Ok(0);
+        }
+        // Queued appends would otherwise land after the delete and resurrect messages.
+        self.flush_writes_async().await;
+        let workspace = Arc::clone(self);
+        let (user, session) = (cleaned_user.clone(), cleaned_session.clone());
+        let deleted = run_workspace_db("workspace.clear_session_messages", move || {
+            let deleted = workspace
+                .storage
+                .delete_chat_history_by_session(&user, &session)?;
+            workspace
+                .storage
+                .replace_model_context_entries(&user, &session, &[])?;
+            let _ = workspace.storage.delete_stream_events_by_session(&session);
+            let _ = workspace.delete_session_context_overflow(&user, &session);
+            let _ = workspace.delete_session_context_limit_hint(&user, &session);
+            Ok(deleted)
+        })
+        .await?;
+        self.save_session_context_tokens_async(&cleaned_user, &cleaned_session, 0)
+            .await;
+        Ok(deleted)
     }
 
     pub fn purge_user_data(&self, user_id: &str) -> PurgeResult {
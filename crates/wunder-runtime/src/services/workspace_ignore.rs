@@ -0,0 +1,121 @@
+// 工作区忽略规则：按 workspace.ignore_files（默认 .wunderignore，可追加 .gitignore）加载工作区根目录下的 gitignore 语法规则，供搜索、读取文件与 @ 提及跳过敏感或大体积路径。
+use crate::path_utils::{
+    normalize_existing_path, normalize_path_for_compare, normalize_target_path,
+};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+#[derive(Debug, Clone)]
+pub struct WorkspaceIgnore {
+    root: PathBuf,
+    matcher: Gitignore,
+}
+
+impl WorkspaceIgnore {
+    /// Rules from the configured ignore files under `root`; `None` when none of them exist.
+    pub fn load(root: &Path, ignore_files: &[String]) -> Option<Self> {
+        let mut builder = GitignoreBuilder::new(root);
+        let mut loaded = false;
+        for name in ignore_files {
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            let path = root.join(name);
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(err) = builder.add(&path) {
+                warn!("skip invalid ignore rules in {}: {err}", path.display());
+            }
+            loaded = true;
+        }
+        if !loaded {
+            return None;
+        }
+        match builder.build() {
+            Ok(matcher) if !matcher.is_empty() => Some(Self {
+                root: root.to_path_buf(),
+                matcher,
+            }),
+            Ok(_) => None,
+            Err(err) => {
+                warn!("build workspace ignore rules failed: {err}");
+                None
+            }
+        }
+    }
+
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.ignore_reason(path, is_dir).is_some()
+    }
+
+    /// The rule that hides `path`, e.g. "`secrets/` (.wunderignore)". Paths outside the
+    /// workspace root are never ignored.
+    pub fn ignore_reason(&self, path: &Path, is_dir: bool) -> Option<String> {
+        let relative = self.relative_path(path)?;
+        if relative.as_os_str().is_empty() {
+            return None;
+        }
+        let matched = self.matcher.matched_path_or_any_parents(&relative, is_dir);
+        if !matched.is_ignore() {
+            return None;
+        }
+        let glob = matched.inner()?;
+        let source = glob
+            .from()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "ignore".to_string());
+        Some(format!("`{}` ({source})", glob.original()))
+    }
+
+    fn relative_path(&self, path: &Path) -> Option<PathBuf> {
+        if !path.is_absolute() {
+            return Some(path.to_path_buf());
+        }
+        if let Ok(relative) = path.strip_prefix(&self.root) {
+            return Some(relative.to_path_buf());
+        }
+        // Tool paths are canonicalized while the configured root may not be.
+        let root = normalize_path_for_compare(&normalize_existing_path(&self.root));
+        normalize_path_for_compare(&normalize_target_path(path))
+            .strip_prefix(&root)
+            .ok()
+            .map(Path::to_path_buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combines_configured_ignore_files() {
+        let root = tempfile::tempdir().expect("tempdir");
+        std::fs::write(root.path().join(".wunderignore"), "secrets/\n*.pem\n").expect("write");
+        std::fs::write(root.path().join(".gitignore"), "target/\n").expect("write");
+
+        let only_wunder =
+            WorkspaceIgnore::load(root.path(), &[".wunderignore".to_string()]).expect("rules");
+        assert!(only_wunder.is_ignored(&root.path().join("secrets/api.txt"), false));
+        assert!(only_wunder.is_ignored(Path::new("keys/server.pem"), false));
+        assert!(!only_wunder.is_ignored(&root.path().join("target/out.log"), false));
+        assert!(!only_wunder.is_ignored(Path::new("/elsewhere/secrets/a.txt"), false));
+        assert_eq!(
+            only_wunder
+                .ignore_reason(&root.path().join("secrets"), true)
+                .as_deref(),
+            Some("`secrets/` (.wunderignore)")
+        );
+
+        let combined = WorkspaceIgnore::load(
+            root.path(),
+            &[".wunderignore".to_string(), ".gitignore".to_string()],
+        )
+        .expect("rules");
+        assert!(combined.is_ignored(&root.path().join("target/out.log"), false));
+        assert!(WorkspaceIgnore::load(root.path(), &["missing".to_string()]).is_none());
+    }
+}
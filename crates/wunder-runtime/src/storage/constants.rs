@@ -20,7 +20,7 @@ pub(crate) const TOOL_LOG_EXCLUDED_NAMES: &[&str] = &[
 ];
 
 pub use wunder_core::storage_constants::{
-    normalize_hive_id, normalize_sandbox_container_id, normalize_workspace_container_id,
-    DEFAULT_HIVE_ID, DEFAULT_SANDBOX_CONTAINER_ID, MAX_SANDBOX_CONTAINER_ID,
-    MIN_SANDBOX_CONTAINER_ID, USER_PRIVATE_CONTAINER_ID,
+    hex_meta_segment, normalize_hive_id, normalize_sandbox_container_id,
+    normalize_workspace_container_id, DEFAULT_HIVE_ID, DEFAULT_SANDBOX_CONTAINER_ID,
+    MAX_SANDBOX_CONTAINER_ID, MIN_SANDBOX_CONTAINER_ID, USER_PRIVATE_CONTAINER_ID,
 };
@@ -32,11 +32,18 @@ fn build_sqlite_storage(_config: &StorageConfig) -> Result<Arc<dyn StorageBacken
 
 #[cfg(feature = "postgres-storage")]
 fn build_postgres_storage(config: &StorageConfig) -> Result<Arc<dyn StorageBackend>> {
-    Ok(Arc::new(super::PostgresStorage::new(
-        config.postgres.dsn.clone(),
-        config.postgres.connect_timeout_s,
-        config.postgres.pool_size,
-    )?))
+    let postgres = &config.postgres;
+    let storage = super::PostgresStorage::new(
+        postgres.dsn.clone(),
+        postgres.connect_timeout_s,
+        postgres.pool_size,
+    )?
+    .with_read_replicas(
+        &postgres.read_replicas,
+        postgres.connect_timeout_s,
+        postgres.pool_size,
+    )?;
+    Ok(Arc::new(storage))
 }
 
 #[cfg(not(feature = "postgres-storage"))]
@@ -0,0 +1,44 @@
+// 存储结构迁移清单：按版本号顺序登记 SQLite/Postgres 的结构变更，启动时由各后端补齐未执行的版本。
+
+/// One ordered schema change, recorded in the `schema_version` table once applied.
+///
+/// Version 1 marks the schema laid down by the idempotent bootstrap in `ensure_initialized`
+/// (`CREATE TABLE IF NOT EXISTS` plus the `ensure_*_columns` helpers). Later changes are
+/// appended here with the next version instead of growing those helpers.
+pub(crate) struct SchemaMigration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sqlite: &'static str,
+    pub postgres: &'static str,
+}
+
+pub(crate) const SCHEMA_MIGRATIONS: &[SchemaMigration] = &[SchemaMigration {
+    version: 1,
+    name: "baseline",
+    sqlite: "",
+    postgres: "",
+}];
+
+pub(crate) fn latest_schema_version() -> i64 {
+    SCHEMA_MIGRATIONS
+        .last()
+        .map(|migration| migration.version)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_migrations_are_strictly_ordered() {
+        assert!(SCHEMA_MIGRATIONS
+            .windows(2)
+            .all(|pair| pair[0].version < pair[1].version));
+        assert!(SCHEMA_MIGRATIONS.iter().all(|item| item.version > 0));
+        assert_eq!(
+            latest_schema_version(),
+            SCHEMA_MIGRATIONS.last().unwrap().version
+        );
+    }
+}
@@ -4,6 +4,8 @@ mod backend;
 mod bridge;
 mod constants;
 mod factory;
+#[cfg(any(feature = "postgres-storage", feature = "sqlite-storage", test))]
+mod migrations;
 #[cfg(feature = "postgres-storage")]
 mod postgres;
 mod records;
@@ -13,9 +15,9 @@ mod sqlite;
 pub use backend::*;
 pub use bridge::*;
 pub use constants::{
-    normalize_hive_id, normalize_sandbox_container_id, normalize_workspace_container_id,
-    DEFAULT_HIVE_ID, DEFAULT_SANDBOX_CONTAINER_ID, MAX_SANDBOX_CONTAINER_ID,
-    MIN_SANDBOX_CONTAINER_ID, USER_PRIVATE_CONTAINER_ID,
+    hex_meta_segment, normalize_hive_id, normalize_sandbox_container_id,
+    normalize_workspace_container_id, DEFAULT_HIVE_ID, DEFAULT_SANDBOX_CONTAINER_ID,
+    MAX_SANDBOX_CONTAINER_ID, MIN_SANDBOX_CONTAINER_ID, USER_PRIVATE_CONTAINER_ID,
 };
 #[cfg(any(feature = "postgres-storage", feature = "sqlite-storage", test))]
 pub(crate) use constants::{TOOL_LOG_EXCLUDED_NAMES, TOOL_LOG_SKILL_READ_MARKER};
@@ -12,14 +12,14 @@ use crate::storage::{
     ListBridgeUserRoutesQuery, ListChannelUserBindingsQuery, MediaAssetRecord,
     MemoryFragmentEmbeddingRecord, MemoryFragmentRecord, MemoryHitRecord, MemoryJobRecord,
     OrgUnitRecord, SessionGoalRecord, SessionLockRecord, SessionLockStatus, SessionRunRecord,
-    SpeechJobRecord, TeamRunRecord, TeamTaskRecord, UpdateAgentTaskStatusParams,
-    UpdateChannelOutboxStatusParams, UpsertMemoryTaskLogParams, UserAccountRecord,
-    UserAgentAccessRecord, UserAgentPresetBinding, UserAgentRecord, UserExperienceUpdateResult,
-    UserSessionScopeRecord, UserTokenBalanceStatus, UserTokenRecord, UserToolAccessRecord,
-    UserWorldConversationRecord, UserWorldConversationSummaryRecord, UserWorldEventRecord,
-    UserWorldGroupRecord, UserWorldMemberRecord, UserWorldMessageRecord, UserWorldReadResult,
-    UserWorldSendMessageResult, VectorChunkEmbeddingRecord, VectorDocumentRecord,
-    VectorDocumentSummaryRecord,
+    SpeechJobRecord, StorageCompactionReport, StreamEventWrite, TeamRunRecord, TeamTaskRecord,
+    UpdateAgentTaskStatusParams, UpdateChannelOutboxStatusParams, UpsertMemoryTaskLogParams,
+    UserAccountRecord, UserAgentAccessRecord, UserAgentPresetBinding, UserAgentRecord,
+    UserExperienceUpdateResult, UserSessionScopeRecord, UserTokenBalanceStatus, UserTokenRecord,
+    UserToolAccessRecord, UserWorldConversationRecord, UserWorldConversationSummaryRecord,
+    UserWorldEventRecord, UserWorldGroupRecord, UserWorldMemberRecord, UserWorldMessageRecord,
+    UserWorldReadResult, UserWorldSendMessageResult, VectorChunkEmbeddingRecord,
+    VectorDocumentRecord, VectorDocumentSummaryRecord,
 };
 use anyhow::{anyhow, Result};
 use chrono::Utc;
@@ -33,6 +33,7 @@ use std::sync::OnceLock;
 use std::time::Duration;
 use tokio_postgres::types::ToSql;
 use tokio_postgres::NoTls;
+use tracing::warn;
 
 mod agent_directory_store;
 mod agent_runtime_store;
@@ -49,7 +50,9 @@ mod log_stats_store;
 mod media_store;
 mod memory_store;
 mod meta_store;
+mod migrations;
 mod monitor_store;
+mod read_replica;
 mod retention_store;
 mod schema;
 mod session_goal;
@@ -75,6 +78,7 @@ use media_store::PostgresMediaStorage;
 use memory_store::PostgresMemoryStorage;
 use meta_store::PostgresMetaStorage;
 use monitor_store::PostgresMonitorStorage;
+use read_replica::ReplicaRouter;
 use retention_store::PostgresRetentionStorage;
 use schema::PostgresSchemaStorage;
 use session_goal::PostgresSessionGoalStorage;
@@ -105,6 +109,9 @@ fn postgres_fallback_runtime() -> Result<&'static tokio::runtime::Runtime> {
 
 pub struct PostgresStorage {
     pool: Pool,
+    /// Read-only replicas for the read-heavy queries that opt in via `read_conn`.
+    replicas: Vec<Pool>,
+    replica_router: ReplicaRouter,
     initialized: AtomicBool,
     init_guard: Mutex<()>,
 }
@@ -169,6 +176,11 @@ struct PgTx<'a> {
 }
 
 impl PgTx<'_> {
+    fn batch_execute(&mut self, query: &str) -> Result<()> {
+        self.storage.block_on(self.tx.batch_execute(query))??;
+        Ok(())
+    }
+
     fn execute(&mut self, query: &str, params: &[&(dyn ToSql + Sync)]) -> Result<u64> {
         Ok(self.storage.block_on(self.tx.execute(query, params))??)
     }
@@ -209,26 +221,34 @@ impl PostgresStorage {
         if cleaned.is_empty() {
             return Err(anyhow!("postgres dsn is empty"));
         }
-        let timeout = Duration::from_secs(connect_timeout_s.max(1));
-        let pool_size = if pool_size == 0 {
-            DEFAULT_POOL_SIZE
-        } else {
-            pool_size
-        };
-        let mut config = cleaned.parse::<tokio_postgres::Config>()?;
-        config.connect_timeout(timeout);
-        let manager_config = ManagerConfig {
-            recycling_method: RecyclingMethod::Fast,
-        };
-        let manager = Manager::from_config(config, NoTls, manager_config);
-        let pool = Pool::builder(manager).max_size(pool_size).build()?;
+        let pool = build_pool(&cleaned, connect_timeout_s, pool_size)?;
         Ok(Self {
             pool,
+            replicas: Vec::new(),
+            replica_router: ReplicaRouter::new(0),
             initialized: AtomicBool::new(false),
             init_guard: Mutex::new(()),
         })
     }
 
+    /// Attach read replicas; blank DSNs are ignored. Pools connect lazily, so an unreachable
+    /// replica only shows up on the first routed read.
+    pub fn with_read_replicas(
+        mut self,
+        dsns: &[String],
+        connect_timeout_s: u64,
+        pool_size: usize,
+    ) -> Result<Self> {
+        self.replicas = dsns
+            .iter()
+            .map(|dsn| dsn.trim())
+            .filter(|dsn| !dsn.is_empty())
+            .map(|dsn| build_pool(dsn, connect_timeout_s, pool_size))
+            .collect::<Result<Vec<_>>>()?;
+        self.replica_router = ReplicaRouter::new(self.replicas.len());
+        Ok(self)
+    }
+
     fn block_on<F, T>(&self, fut: F) -> Result<T>
     where
         F: Future<Output = T>,
@@ -444,6 +464,43 @@ impl PostgresStorage {
             client,
         })
     }
+
+    /// Connection for read-only queries that tolerate replication lag. Tries the replicas in
+    /// rotation and falls back to the primary when none can hand out a connection.
+    fn read_conn(&self) -> Result<PgConn<'_>> {
+        let now_ms = Utc::now().timestamp_millis();
+        for index in self.replica_router.read_order(now_ms) {
+            match self.block_on(self.replicas[index].get())? {
+                Ok(client) => {
+                    return Ok(PgConn {
+                        storage: self,
+                        client,
+                    })
+                }
+                Err(err) => {
+                    warn!(replica = index, "postgres read replica unavailable: {err}");
+                    self.replica_router.mark_down(index, now_ms);
+                }
+            }
+        }
+        self.conn()
+    }
+}
+
+fn build_pool(dsn: &str, connect_timeout_s: u64, pool_size: usize) -> Result<Pool> {
+    let timeout = Duration::from_secs(connect_timeout_s.max(1));
+    let pool_size = if pool_size == 0 {
+        DEFAULT_POOL_SIZE
+    } else {
+        pool_size
+    };
+    let mut config = dsn.parse::<tokio_postgres::Config>()?;
+    config.connect_timeout(timeout);
+    let manager_config = ManagerConfig {
+        recycling_method: RecyclingMethod::Fast,
+    };
+    let manager = Manager::from_config(config, NoTls, manager_config);
+    Ok(Pool::builder(manager).max_size(pool_size).build()?)
 }
 
 #[cfg(test)]
@@ -457,4 +514,32 @@ mod tests {
         assert!(std::ptr::eq(first, second));
         assert_eq!(first.block_on(async { 7 }), 7);
     }
+
+    #[test]
+    fn read_conn_marks_unreachable_replica_down_and_falls_back_to_primary() {
+        // Port 1 refuses connections, so both pools fail fast without a database.
+        let storage = PostgresStorage::new("postgresql://wunder@127.0.0.1:1/primary".into(), 1, 1)
+            .and_then(|storage| {
+                storage.with_read_replicas(
+                    &[
+                        "postgresql://wunder@127.0.0.1:1/replica".to_string(),
+                        " ".to_string(),
+                    ],
+                    1,
+                    1,
+                )
+            })
+            .expect("build storage");
+        assert_eq!(storage.replicas.len(), 1);
+
+        let err = storage
+            .read_conn()
+            .err()
+            .expect("primary is unreachable too");
+        assert!(!err.to_string().is_empty());
+        let now_ms = Utc::now().timestamp_millis();
+        assert!(storage.replica_router.read_order(now_ms).is_empty());
+        // Writes never consult the replica router.
+        assert!(storage.conn().is_err());
+    }
 }
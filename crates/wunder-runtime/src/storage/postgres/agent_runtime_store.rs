@@ -1,6 +1,7 @@
 use super::PostgresStorage;
 use crate::storage::{
-    AgentTaskRecord, AgentThreadRecord, StorageLifecycle, UpdateAgentTaskStatusParams,
+    AgentTaskRecord, AgentThreadRecord, StorageLifecycle, StreamEventWrite,
+    UpdateAgentTaskStatusParams,
 };
 use anyhow::Result;
 use serde_json::{json, Value};
@@ -38,6 +39,7 @@ pub(super) trait PostgresAgentRuntimeStorage {
         event_id: i64,
         payload: &Value,
     ) -> Result<()>;
+    fn append_stream_events_impl(&self, events: &[StreamEventWrite]) -> Result<()>;
     fn load_stream_events_impl(
         &self,
         session_id: &str,
@@ -380,6 +382,33 @@ impl PostgresAgentRuntimeStorage for PostgresStorage {
         Ok(())
     }
 
+    fn append_stream_events_impl(&self, events: &[StreamEventWrite]) -> Result<()> {
+        self.ensure_initialized()?;
+        if events.is_empty() {
+            return Ok(());
+        }
+        let now = Self::now_ts();
+        let mut conn = self.conn()?;
+        let mut tx = conn.transaction()?;
+        for event in events {
+            let cleaned_session = event.session_id.trim();
+            let cleaned_user = event.user_id.trim();
+            if cleaned_session.is_empty() || cleaned_user.is_empty() {
+                continue;
+            }
+            let payload_text = Self::json_to_string(&event.payload);
+            let event_type = stream_event_type(&event.payload);
+            let user_round = stream_event_user_round(&event.payload);
+            tx.execute(
+                "INSERT INTO stream_events (session_id, event_id, user_id, event_type, user_round, payload, created_time) VALUES ($1, $2, $3, $4, $5, $6, $7) \
+                 ON CONFLICT (session_id, event_id) DO UPDATE SET user_id = EXCLUDED.user_id, event_type = EXCLUDED.event_type, user_round = EXCLUDED.user_round, payload = EXCLUDED.payload, created_time = EXCLUDED.created_time",
+                &[&cleaned_session, &event.event_id, &cleaned_user, &event_type, &user_round, &payload_text, &now],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     fn load_stream_events_impl(
         &self,
         _session_id: &str,
@@ -391,7 +420,7 @@ impl PostgresAgentRuntimeStorage for PostgresStorage {
         if cleaned_session.is_empty() || _limit <= 0 {
             return Ok(Vec::new());
         }
-        let mut conn = self.conn()?;
+        let mut conn = self.read_conn()?;
         let rows = conn.query(
             "SELECT event_id, payload FROM stream_events WHERE session_id = $1 AND event_id > $2 ORDER BY event_id ASC LIMIT $3",
             &[&cleaned_session, &_after_event_id, &_limit],
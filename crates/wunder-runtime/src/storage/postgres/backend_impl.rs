@@ -79,6 +79,23 @@ impl ConversationLogStore for PostgresStorage {
     ) -> Result<Vec<Value>> {
         self.load_chat_history_page_impl(user_id, session_id, before_id, limit)
     }
+    fn delete_chat_history_items(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        history_ids: &[i64],
+    ) -> Result<i64> {
+        self.delete_chat_history_items_impl(user_id, session_id, history_ids)
+    }
+    fn update_chat_history_item(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        history_id: i64,
+        payload: &Value,
+    ) -> Result<bool> {
+        self.update_chat_history_item_impl(user_id, session_id, history_id, payload)
+    }
     fn load_artifact_logs(
         &self,
         user_id: &str,
@@ -263,6 +280,9 @@ impl AgentRuntimeStore for PostgresStorage {
     ) -> Result<()> {
         self.append_stream_event_impl(session_id, user_id, event_id, payload)
     }
+    fn append_stream_events(&self, events: &[StreamEventWrite]) -> Result<()> {
+        self.append_stream_events_impl(events)
+    }
     fn load_stream_events(
         &self,
         session_id: &str,
@@ -556,6 +576,9 @@ impl RetentionStore for PostgresStorage {
     fn cleanup_retention(&self, retention_days: i64) -> Result<HashMap<String, i64>> {
         self.cleanup_retention_impl(retention_days)
     }
+    fn compact_storage(&self) -> Result<StorageCompactionReport> {
+        self.compact_storage_impl()
+    }
 }
 
 impl UserAccountStore for PostgresStorage {
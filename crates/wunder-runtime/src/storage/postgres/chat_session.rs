@@ -143,7 +143,7 @@ impl PostgresChatSessionStorage for PostgresStorage {
         if cleaned_user.is_empty() {
             return Ok((Vec::new(), 0));
         }
-        let mut conn = self.conn()?;
+        let mut conn = self.read_conn()?;
         let mut conditions = Vec::new();
         let mut params: Vec<Box<dyn ToSql + Sync>> = Vec::new();
         params.push(Box::new(cleaned_user.to_string()));
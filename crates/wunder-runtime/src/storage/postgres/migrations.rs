@@ -0,0 +1,63 @@
+use super::{PgConn, PostgresStorage};
+use crate::storage::migrations::SchemaMigration;
+use anyhow::{Context, Result};
+use tracing::info;
+
+/// Apply every migration newer than the recorded `schema_version`, each in its own transaction
+/// holding an exclusive lock on `schema_version`, and return the resulting version. Stops at the
+/// first failing step.
+pub(super) fn run_schema_migrations(
+    conn: &mut PgConn<'_>,
+    migrations: &[SchemaMigration],
+) -> Result<i64> {
+    conn.batch_execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+           version BIGINT PRIMARY KEY,
+           name TEXT NOT NULL,
+           applied_time DOUBLE PRECISION NOT NULL
+         );",
+    )?;
+    let mut current: i64 = conn
+        .query_one("SELECT COALESCE(MAX(version), 0) FROM schema_version", &[])?
+        .get(0);
+    for migration in migrations {
+        if migration.version <= current {
+            continue;
+        }
+        let mut tx = conn.transaction()?;
+        // Serialize concurrent starters; whoever waits re-reads the version after the lock.
+        tx.batch_execute("LOCK TABLE schema_version IN EXCLUSIVE MODE")?;
+        current = tx
+            .query_one("SELECT COALESCE(MAX(version), 0) FROM schema_version", &[])?
+            .get(0);
+        if migration.version <= current {
+            continue;
+        }
+        if !migration.postgres.trim().is_empty() {
+            tx.batch_execute(migration.postgres).with_context(|| {
+                format!(
+                    "postgres schema migration {} ({}) failed",
+                    migration.version, migration.name
+                )
+            })?;
+        }
+        let applied_time = PostgresStorage::now_ts();
+        tx.execute(
+            "INSERT INTO schema_version (version, name, applied_time) VALUES ($1, $2, $3)",
+            &[&migration.version, &migration.name, &applied_time],
+        )?;
+        tx.commit().with_context(|| {
+            format!(
+                "commit postgres schema migration {} ({}) failed",
+                migration.version, migration.name
+            )
+        })?;
+        info!(
+            version = migration.version,
+            name = migration.name,
+            "postgres schema migration applied"
+        );
+        current = migration.version;
+    }
+    Ok(current)
+}
@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+
+/// How long a replica that failed to hand out a connection is skipped before being retried.
+pub(super) const REPLICA_COOLDOWN_MS: i64 = 30_000;
+
+/// Round-robin picker over read replicas that skips replicas seen failing recently. An empty
+/// order means the read goes to the primary.
+pub(super) struct ReplicaRouter {
+    cursor: AtomicUsize,
+    down_until_ms: Vec<AtomicI64>,
+}
+
+impl ReplicaRouter {
+    pub(super) fn new(replica_count: usize) -> Self {
+        Self {
+            cursor: AtomicUsize::new(0),
+            down_until_ms: (0..replica_count).map(|_| AtomicI64::new(0)).collect(),
+        }
+    }
+
+    /// Healthy replicas to try for one read, starting from the next one in rotation.
+    pub(super) fn read_order(&self, now_ms: i64) -> Vec<usize> {
+        let count = self.down_until_ms.len();
+        if count == 0 {
+            return Vec::new();
+        }
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed) % count;
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .filter(|index| self.down_until_ms[*index].load(Ordering::Relaxed) <= now_ms)
+            .collect()
+    }
+
+    pub(super) fn mark_down(&self, index: usize, now_ms: i64) {
+        if let Some(slot) = self.down_until_ms.get(index) {
+            slot.store(now_ms + REPLICA_COOLDOWN_MS, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rotate_across_replicas_and_skip_failed_ones() {
+        let router = ReplicaRouter::new(2);
+        assert_eq!(router.read_order(0), vec![0, 1]);
+        assert_eq!(router.read_order(0), vec![1, 0]);
+
+        router.mark_down(0, 1_000);
+        assert_eq!(router.read_order(1_000), vec![1]);
+        assert_eq!(router.read_order(1_000), vec![1]);
+
+        router.mark_down(1, 1_000);
+        assert!(router.read_order(1_000).is_empty());
+        assert_eq!(router.read_order(1_000 + REPLICA_COOLDOWN_MS).len(), 2);
+    }
+
+    #[test]
+    fn no_replicas_routes_reads_to_primary() {
+        assert!(ReplicaRouter::new(0).read_order(0).is_empty());
+    }
+}
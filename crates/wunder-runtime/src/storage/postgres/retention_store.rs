@@ -1,10 +1,11 @@
 use super::PostgresStorage;
-use crate::storage::StorageLifecycle;
+use crate::storage::{StorageCompactionReport, StorageLifecycle};
 use anyhow::Result;
 use std::collections::HashMap;
 
 pub(super) trait PostgresRetentionStorage {
     fn cleanup_retention_impl(&self, retention_days: i64) -> Result<HashMap<String, i64>>;
+    fn compact_storage_impl(&self) -> Result<StorageCompactionReport>;
 }
 
 impl PostgresRetentionStorage for PostgresStorage {
@@ -66,4 +67,16 @@ impl PostgresRetentionStorage for PostgresStorage {
         results.insert("session_runs".to_string(), session_runs);
         Ok(results)
     }
+
+    fn compact_storage_impl(&self) -> Result<StorageCompactionReport> {
+        Ok(StorageCompactionReport {
+            backend: "postgres".to_string(),
+            supported: false,
+            note: Some(
+                "postgres reclaims space through autovacuum; run VACUUM (FULL) from psql if needed"
+                    .to_string(),
+            ),
+            ..StorageCompactionReport::default()
+        })
+    }
 }
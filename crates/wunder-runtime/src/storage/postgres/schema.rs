@@ -1,4 +1,6 @@
+use super::migrations::run_schema_migrations;
 use super::{PgConn, PostgresStorage};
+use crate::storage::migrations::SCHEMA_MIGRATIONS;
 use anyhow::Result;
 use chrono::Local;
 use std::collections::{HashMap, HashSet};
@@ -1902,6 +1904,7 @@ impl PostgresSchemaStorage for PostgresStorage {
                     self.ensure_cron_columns(&mut conn)?;
                     self.ensure_memory_fragment_columns(&mut conn)?;
                     self.ensure_performance_indexes(&mut conn)?;
+                    run_schema_migrations(&mut conn, SCHEMA_MIGRATIONS)?;
                     self.initialized.store(true, Ordering::SeqCst);
                     return Ok(());
                 }
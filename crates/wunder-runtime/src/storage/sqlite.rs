@@ -11,14 +11,14 @@ use crate::storage::{
     ListBridgeUserRoutesQuery, ListChannelUserBindingsQuery, MediaAssetRecord,
     MemoryFragmentEmbeddingRecord, MemoryFragmentRecord, MemoryHitRecord, MemoryJobRecord,
     OrgUnitRecord, SessionGoalRecord, SessionLockRecord, SessionLockStatus, SessionRunRecord,
-    SpeechJobRecord, TeamRunRecord, TeamTaskRecord, UpdateAgentTaskStatusParams,
-    UpdateChannelOutboxStatusParams, UpsertMemoryTaskLogParams, UserAccountRecord,
-    UserAgentAccessRecord, UserAgentPresetBinding, UserAgentRecord, UserExperienceUpdateResult,
-    UserSessionScopeRecord, UserTokenBalanceStatus, UserTokenRecord, UserToolAccessRecord,
-    UserWorldConversationRecord, UserWorldConversationSummaryRecord, UserWorldEventRecord,
-    UserWorldGroupRecord, UserWorldMemberRecord, UserWorldMessageRecord, UserWorldReadResult,
-    UserWorldSendMessageResult, VectorChunkEmbeddingRecord, VectorDocumentRecord,
-    VectorDocumentSummaryRecord,
+    SpeechJobRecord, StorageCompactionReport, StreamEventWrite, TeamRunRecord, TeamTaskRecord,
+    UpdateAgentTaskStatusParams, UpdateChannelOutboxStatusParams, UpsertMemoryTaskLogParams,
+    UserAccountRecord, UserAgentAccessRecord, UserAgentPresetBinding, UserAgentRecord,
+    UserExperienceUpdateResult, UserSessionScopeRecord, UserTokenBalanceStatus, UserTokenRecord,
+    UserToolAccessRecord, UserWorldConversationRecord, UserWorldConversationSummaryRecord,
+    UserWorldEventRecord, UserWorldGroupRecord, UserWorldMemberRecord, UserWorldMessageRecord,
+    UserWorldReadResult, UserWorldSendMessageResult, VectorChunkEmbeddingRecord,
+    VectorDocumentRecord, VectorDocumentSummaryRecord,
 };
 use anyhow::Result;
 use chrono::Utc;
@@ -45,6 +45,7 @@ mod log_stats_store;
 mod media_store;
 mod memory_store;
 mod meta_store;
+mod migrations;
 mod monitor_store;
 mod retention_store;
 mod schema;
@@ -1,6 +1,7 @@
 use super::SqliteStorage;
 use crate::storage::{
-    AgentTaskRecord, AgentThreadRecord, StorageLifecycle, UpdateAgentTaskStatusParams,
+    AgentTaskRecord, AgentThreadRecord, StorageLifecycle, StreamEventWrite,
+    UpdateAgentTaskStatusParams,
 };
 use anyhow::Result;
 use rusqlite::{params, OptionalExtension};
@@ -39,6 +40,7 @@ pub(super) trait SqliteAgentRuntimeStorage {
         event_id: i64,
         payload: &Value,
     ) -> Result<()>;
+    fn append_stream_events_impl(&self, events: &[StreamEventWrite]) -> Result<()>;
     fn load_stream_events_impl(
         &self,
         session_id: &str,
@@ -397,6 +399,39 @@ impl SqliteAgentRuntimeStorage for SqliteStorage {
         Ok(())
     }
 
+    fn append_stream_events_impl(&self, events: &[StreamEventWrite]) -> Result<()> {
+        self.ensure_initialized()?;
+        if events.is_empty() {
+            return Ok(());
+        }
+        let now = Self::now_ts();
+        let mut conn = self.open()?;
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR REPLACE INTO stream_events (session_id, event_id, user_id, event_type, user_round, payload, created_time) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )?;
+            for event in events {
+                let cleaned_session = event.session_id.trim();
+                let cleaned_user = event.user_id.trim();
+                if cleaned_session.is_empty() || cleaned_user.is_empty() {
+                    continue;
+                }
+                stmt.execute(params![
+                    cleaned_session,
+                    event.event_id,
+                    cleaned_user,
+                    stream_event_type(&event.payload),
+                    stream_event_user_round(&event.payload),
+                    Self::json_to_string(&event.payload),
+                    now
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     fn load_stream_events_impl(
         &self,
         session_id: &str,
@@ -46,6 +46,19 @@ pub(super) trait SqliteConversationLogStorage {
         before_id: Option<i64>,
         limit: i64,
     ) -> Result<Vec<Value>>;
+    fn delete_chat_history_items_impl(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        history_ids: &[i64],
+    ) -> Result<i64>;
+    fn update_chat_history_item_impl(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        history_id: i64,
+        payload: &Value,
+    ) -> Result<bool>;
     fn load_artifact_logs_impl(
         &self,
         user_id: &str,
@@ -440,6 +453,71 @@ impl SqliteConversationLogStorage for SqliteStorage {
         Ok(records)
     }
 
+    fn delete_chat_history_items_impl(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        history_ids: &[i64],
+    ) -> Result<i64> {
+        self.ensure_initialized()?;
+        let cleaned_user = user_id.trim();
+        let cleaned_session = session_id.trim();
+        if cleaned_user.is_empty() || cleaned_session.is_empty() || history_ids.is_empty() {
+            return Ok(0);
+        }
+        let mut conn = self.open()?;
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let mut affected = 0;
+        {
+            let mut stmt = tx.prepare(
+                "DELETE FROM chat_history WHERE id = ? AND user_id = ? AND session_id = ?",
+            )?;
+            for history_id in history_ids {
+                affected += stmt.execute(params![history_id, cleaned_user, cleaned_session])?;
+            }
+        }
+        tx.commit()?;
+        Ok(affected as i64)
+    }
+
+    fn update_chat_history_item_impl(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        history_id: i64,
+        payload: &Value,
+    ) -> Result<bool> {
+        self.ensure_initialized()?;
+        let cleaned_user = user_id.trim();
+        let cleaned_session = session_id.trim();
+        if cleaned_user.is_empty() || cleaned_session.is_empty() || history_id <= 0 {
+            return Ok(false);
+        }
+        let mut payload = sanitize_persisted_chat_payload(payload);
+        if let Value::Object(ref mut map) = payload {
+            map.remove("_history_id");
+        }
+        let content = Self::parse_string(payload.get("content"));
+        let meta = payload
+            .get("meta")
+            .and_then(|value| serde_json::to_string(value).ok());
+        let payload_text = Self::json_to_string(&payload);
+        let conn = self.open()?;
+        let affected = conn.execute(
+            "UPDATE chat_history SET content = ?, meta = ?, payload = ? \
+             WHERE id = ? AND user_id = ? AND session_id = ?",
+            params![
+                content,
+                meta,
+                payload_text,
+                history_id,
+                cleaned_user,
+                cleaned_session
+            ],
+        )?;
+        Ok(affected > 0)
+    }
+
     fn load_artifact_logs_impl(
         &self,
         user_id: &str,
@@ -0,0 +1,132 @@
+use super::SqliteStorage;
+use crate::storage::migrations::SchemaMigration;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, TransactionBehavior};
+use tracing::info;
+
+/// Apply every migration newer than the recorded `schema_version`, each in its own immediate
+/// transaction, and return the resulting version. Stops at the first failing step.
+pub(super) fn run_schema_migrations(
+    conn: &mut Connection,
+    migrations: &[SchemaMigration],
+) -> Result<i64> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+           version INTEGER PRIMARY KEY,
+           name TEXT NOT NULL,
+           applied_time REAL NOT NULL
+         );",
+    )?;
+    let mut current = current_schema_version(conn)?;
+    for migration in migrations {
+        if migration.version <= current {
+            continue;
+        }
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        // Another process may have applied this step between our read and the write lock.
+        current = current_schema_version(&tx)?;
+        if migration.version <= current {
+            continue;
+        }
+        if !migration.sqlite.trim().is_empty() {
+            tx.execute_batch(migration.sqlite).with_context(|| {
+                format!(
+                    "sqlite schema migration {} ({}) failed",
+                    migration.version, migration.name
+                )
+            })?;
+        }
+        tx.execute(
+            "INSERT INTO schema_version (version, name, applied_time) VALUES (?, ?, ?)",
+            params![migration.version, migration.name, SqliteStorage::now_ts()],
+        )?;
+        tx.commit().with_context(|| {
+            format!(
+                "commit sqlite schema migration {} ({}) failed",
+                migration.version, migration.name
+            )
+        })?;
+        info!(
+            version = migration.version,
+            name = migration.name,
+            "sqlite schema migration applied"
+        );
+        current = migration.version;
+    }
+    Ok(current)
+}
+
+fn current_schema_version(conn: &Connection) -> Result<i64> {
+    Ok(conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::migrations::{latest_schema_version, SCHEMA_MIGRATIONS};
+    use crate::storage::StorageLifecycle;
+    use tempfile::tempdir;
+
+    #[test]
+    fn fresh_database_reaches_latest_version_and_rerun_is_idempotent() {
+        let temp = tempdir().expect("tempdir");
+        let db_path = temp.path().join("migrations.db");
+        let storage = SqliteStorage::new(db_path.to_string_lossy().to_string());
+        storage.ensure_initialized().expect("initialize storage");
+
+        let mut conn = storage.open().expect("open sqlite");
+        assert_eq!(
+            current_schema_version(&conn).expect("version"),
+            latest_schema_version()
+        );
+        let version =
+            run_schema_migrations(&mut conn, SCHEMA_MIGRATIONS).expect("rerun migrations");
+        assert_eq!(version, latest_schema_version());
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+            .expect("count versions");
+        assert_eq!(rows, SCHEMA_MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn failing_step_stops_the_runner_and_keeps_earlier_versions() {
+        let temp = tempdir().expect("tempdir");
+        let mut conn = Connection::open(temp.path().join("broken.db")).expect("open sqlite");
+        let migrations = [
+            SchemaMigration {
+                version: 1,
+                name: "create_widgets",
+                sqlite: "CREATE TABLE widgets (id INTEGER PRIMARY KEY);",
+                postgres: "",
+            },
+            SchemaMigration {
+                version: 2,
+                name: "broken_step",
+                sqlite: "ALTER TABLE missing_table ADD COLUMN label TEXT;",
+                postgres: "",
+            },
+            SchemaMigration {
+                version: 3,
+                name: "never_reached",
+                sqlite: "CREATE TABLE gadgets (id INTEGER PRIMARY KEY);",
+                postgres: "",
+            },
+        ];
+
+        let err = run_schema_migrations(&mut conn, &migrations).expect_err("step 2 fails");
+        assert!(err.to_string().contains("2 (broken_step)"));
+        assert_eq!(current_schema_version(&conn).expect("version"), 1);
+        let gadgets: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'gadgets'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("lookup gadgets");
+        assert_eq!(gadgets, 0);
+    }
+}
@@ -1,4 +1,6 @@
+use super::migrations::run_schema_migrations;
 use super::SqliteStorage;
+use crate::storage::migrations::SCHEMA_MIGRATIONS;
 use anyhow::Result;
 use chrono::Local;
 use rusqlite::{params, Connection};
@@ -446,7 +448,7 @@ impl SqliteSchemaStorage for SqliteStorage {
         if self.initialized.load(Ordering::SeqCst) {
             return Ok(());
         }
-        let conn = self.open()?;
+        let mut conn = self.open()?;
         conn.execute_batch(
             r#"
             CREATE TABLE IF NOT EXISTS meta (
@@ -1479,6 +1481,7 @@ impl SqliteSchemaStorage for SqliteStorage {
         self.ensure_user_world_group_columns(&conn)?;
         self.ensure_cron_columns(&conn)?;
         self.ensure_memory_fragment_columns(&conn)?;
+        run_schema_migrations(&mut conn, SCHEMA_MIGRATIONS)?;
         self.initialized.store(true, Ordering::SeqCst);
         Ok(())
     }
@@ -0,0 +1,210 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::AUTHORIZATION, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tower::ServiceExt;
+use wunder_server::{
+    auth::is_admin_path,
+    build_router,
+    config::{Config, LlmModelConfig, MockLlmStep},
+    config_store::ConfigStore,
+    state::{AppState, AppStateInitOptions},
+};
+
+const MOCK_MODEL_NAME: &str = "offline-mock";
+const UPLOAD_BODY: &str = "quarterly numbers\nrevenue: 42\n";
+
+struct TestContext {
+    app: Router,
+    token: String,
+    _temp_dir: TempDir,
+}
+
+/// Mock-provider state with one regular (non-admin) user holding a bearer token.
+async fn build_user_context() -> TestContext {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    // The upload store is process-wide; keep its files inside this test's temp dir.
+    std::env::set_var("WUNDER_TEMP_DIR_ROOT", temp_dir.path().join("temp_dir"));
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("attachment-upload-routes.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+    config.llm.default = MOCK_MODEL_NAME.to_string();
+    config.llm.models.insert(
+        MOCK_MODEL_NAME.to_string(),
+        LlmModelConfig {
+            enable: Some(true),
+            provider: Some("mock".to_string()),
+            model: Some(MOCK_MODEL_NAME.to_string()),
+            model_type: Some("llm".to_string()),
+            mock_script: vec![MockLlmStep {
+                content: "read the upload".to_string(),
+                ..MockLlmStep::default()
+            }],
+            ..Default::default()
+        },
+    );
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+
+    let created = state
+        .user_store
+        .create_user(
+            "upload_user",
+            Some("upload_user@example.test".to_string()),
+            "password-123",
+            Some("A"),
+            None,
+            vec!["user".to_string()],
+            "active",
+            false,
+        )
+        .expect("create user");
+    let token = state
+        .user_store
+        .create_session_token(&created.user_id)
+        .expect("create token")
+        .token;
+
+    TestContext {
+        app: build_router(state),
+        token,
+        _temp_dir: temp_dir,
+    }
+}
+
+async fn send(
+    context: &TestContext,
+    uri: &str,
+    content_type: &str,
+    body: Body,
+) -> (StatusCode, Value) {
+    let response = context
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header(AUTHORIZATION, format!("Bearer {}", context.token))
+                .header("content-type", content_type)
+                .body(body)
+                .expect("build request"),
+        )
+        .await
+        .expect("send request");
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+async fn send_json(context: &TestContext, uri: &str, payload: Value) -> (StatusCode, Value) {
+    send(
+        context,
+        uri,
+        "application/json",
+        Body::from(payload.to_string()),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn regular_user_uploads_in_chunks_and_references_attachment_in_chat() {
+    // The server's API-key guard must let bearer-token users reach the upload routes.
+    for path in [
+        "/wunder/attachments/init",
+        "/wunder/attachments/chunk",
+        "/wunder/attachments/complete",
+        "/wunder/attachments/uploads/upl_1",
+    ] {
+        assert!(!is_admin_path(path), "{path} should be user-scoped");
+    }
+
+    let context = build_user_context().await;
+    let (status, init) = send_json(
+        &context,
+        "/wunder/attachments/init",
+        json!({
+            "name": "numbers.txt",
+            "size": UPLOAD_BODY.len(),
+            "content_type": "text/plain",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{init}");
+    let upload_id = init["data"]["upload_id"]
+        .as_str()
+        .expect("upload id")
+        .to_string();
+
+    let (head, tail) = UPLOAD_BODY.split_at(10);
+    for (offset, chunk) in [(0, head), (head.len(), tail)] {
+        let (status, received) = send(
+            &context,
+            &format!("/wunder/attachments/chunk?upload_id={upload_id}&offset={offset}"),
+            "application/octet-stream",
+            Body::from(chunk.to_string()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{received}");
+        assert_eq!(
+            received["data"]["received_bytes"],
+            (offset + chunk.len()) as u64
+        );
+    }
+
+    let (status, completed) = send_json(
+        &context,
+        "/wunder/attachments/complete",
+        json!({
+            "upload_id": upload_id,
+            "sha256": hex::encode(Sha256::digest(UPLOAD_BODY.as_bytes())),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{completed}");
+    let attachment_id = completed["data"]["attachment_id"]
+        .as_str()
+        .expect("attachment id")
+        .to_string();
+
+    let (status, reply) = send_json(
+        &context,
+        "/wunder/chat/sessions/sess_upload/messages",
+        json!({
+            "content": "summarize the attachment",
+            "stream": false,
+            "attachments": [{ "attachment_id": attachment_id }],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{reply}");
+    assert_eq!(reply["data"]["answer"], "read the upload");
+}
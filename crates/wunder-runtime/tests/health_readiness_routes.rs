@@ -0,0 +1,111 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Method, Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tower::ServiceExt;
+use wunder_server::{
+    build_router,
+    config::Config,
+    config_store::ConfigStore,
+    state::{AppState, AppStateInitOptions},
+    storage::{SqliteStorage, StorageBackend},
+};
+
+struct TestContext {
+    app: Router,
+    _temp_dir: TempDir,
+}
+
+/// With `broken_storage` the state's storage handle points at a database that can never be
+/// opened, so every storage call fails the same way on each run.
+async fn build_test_context(broken_storage: bool) -> TestContext {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let db_path = temp_dir.path().join("health-routes.db");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = db_path.to_string_lossy().to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+
+    let mut state =
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state");
+    if broken_storage {
+        state.storage = unopenable_storage(temp_dir.path());
+    }
+    TestContext {
+        app: build_router(Arc::new(state)),
+        _temp_dir: temp_dir,
+    }
+}
+
+async fn get_json(app: &Router, path: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(path)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("send request");
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    let payload = serde_json::from_slice(&body).expect("parse body");
+    (status, payload)
+}
+
+/// A SQLite backend whose database sits under a regular file, so opening it always fails.
+fn unopenable_storage(root: &Path) -> Arc<dyn StorageBackend> {
+    let blocker = root.join("not-a-directory");
+    std::fs::write(&blocker, b"").expect("write blocker file");
+    Arc::new(SqliteStorage::new(
+        blocker.join("wunder.db").to_string_lossy().to_string(),
+    ))
+}
+
+#[tokio::test]
+async fn healthz_is_always_ok() {
+    let context = build_test_context(false).await;
+    let (status, payload) = get_json(&context.app, "/healthz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(payload["status"], "ok");
+
+    let broken = build_test_context(true).await;
+    let (status, _) = get_json(&broken.app, "/healthz").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn readyz_reports_storage_failure_as_unavailable() {
+    let context = build_test_context(false).await;
+    let (status, payload) = get_json(&context.app, "/readyz").await;
+    assert_eq!(status, StatusCode::OK, "{payload}");
+    assert_eq!(payload["status"], "ready");
+    assert_eq!(payload["checks"]["storage"]["ok"], true);
+    assert_eq!(payload["checks"]["config"]["ok"], true);
+
+    let broken = build_test_context(true).await;
+    let (status, payload) = get_json(&broken.app, "/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE, "{payload}");
+    assert_eq!(payload["status"], "not_ready");
+    assert_eq!(payload["checks"]["storage"]["ok"], false);
+    assert!(payload["checks"]["storage"]["error"]
+        .as_str()
+        .is_some_and(|error| !error.is_empty()));
+    assert_eq!(payload["checks"]["config"]["ok"], true);
+}
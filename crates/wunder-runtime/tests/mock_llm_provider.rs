@@ -0,0 +1,445 @@
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use wunder_server::{
+    config::{Config, KnowledgeBaseConfig, LlmModelConfig, MockLlmStep, MockToolCall},
+    config_store::ConfigStore,
+    schemas::{AttachmentPayload, WunderRequest},
+    state::{AppState, AppStateInitOptions},
+    storage::DEFAULT_SANDBOX_CONTAINER_ID,
+};
+
+const MOCK_MODEL_NAME: &str = "offline-mock";
+
+fn list_files_step() -> MockLlmStep {
+    MockLlmStep {
+        tool_calls: vec![MockToolCall {
+            name: "list_files".to_string(),
+            arguments: serde_yaml::from_str("path: .").expect("yaml arguments"),
+        }],
+        ..MockLlmStep::default()
+    }
+}
+
+async fn build_mock_state() -> (Arc<AppState>, TempDir) {
+    build_mock_state_with_script(vec![
+        list_files_step(),
+        MockLlmStep {
+            content: "scripted final answer".to_string(),
+            ..MockLlmStep::default()
+        },
+    ])
+    .await
+}
+
+async fn build_mock_state_with_script(mock_script: Vec<MockLlmStep>) -> (Arc<AppState>, TempDir) {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("mock-llm-provider.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+    // Desktop mode runs the file tools in-process instead of through the sandbox service.
+    config.server.mode = "desktop".to_string();
+    config.tools.builtin.enabled = vec!["列出文件".to_string(), "读取文件".to_string()];
+    config.llm.default = MOCK_MODEL_NAME.to_string();
+    config.llm.models.insert(
+        MOCK_MODEL_NAME.to_string(),
+        LlmModelConfig {
+            enable: Some(true),
+            provider: Some("mock".to_string()),
+            model: Some(MOCK_MODEL_NAME.to_string()),
+            max_rounds: Some(4),
+            tool_call_mode: Some("function_call".to_string()),
+            model_type: Some("llm".to_string()),
+            mock_script,
+            ..Default::default()
+        },
+    );
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+    (state, temp_dir)
+}
+
+fn mock_request(session_id: &str, stream: bool) -> WunderRequest {
+    WunderRequest {
+        user_id: "mock_llm_user".to_string(),
+        question: "list the workspace".to_string(),
+        client_message_id: None,
+        tool_names: Vec::new(),
+        skip_tool_calls: false,
+        stream,
+        debug_payload: false,
+        session_id: Some(session_id.to_string()),
+        agent_id: None,
+        workspace_container_id: None,
+        model_name: Some(MOCK_MODEL_NAME.to_string()),
+        language: None,
+        config_overrides: None,
+        agent_prompt: None,
+        preview_skill: false,
+        attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
+        allow_queue: true,
+        is_admin: true,
+        enforce_runtime_queue: false,
+        approval_tx: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn mock_provider_runs_scripted_tool_call_then_final_answer() {
+    let (state, _temp_dir) = build_mock_state().await;
+    for (session_id, stream) in [("mock_llm_sync", false), ("mock_llm_stream", true)] {
+        let response = state
+            .kernel
+            .orchestrator
+            .run(mock_request(session_id, stream))
+            .await
+            .unwrap_or_else(|err| panic!("mock run failed (stream={stream}): {err}"));
+        assert_eq!(
+            response.answer, "scripted final answer",
+            "stream={stream} should reach the scripted second round"
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn request_max_rounds_stops_tool_loop_with_progress_summary() {
+    // Every scripted round asks for another tool call, so only the limit ends the turn.
+    let (state, _temp_dir) = build_mock_state_with_script(vec![list_files_step(); 6]).await;
+
+    let mut request = mock_request("mock_llm_max_rounds", false);
+    request.max_rounds = Some(2);
+    let response = state
+        .kernel
+        .orchestrator
+        .run(request)
+        .await
+        .expect("limited run should finish gracefully");
+    assert_eq!(response.stop_reason.as_deref(), Some("max_rounds"));
+    assert!(
+        response.answer.contains("列出文件 x2"),
+        "answer should summarize tool progress: {}",
+        response.answer
+    );
+
+    let mut request = mock_request("mock_llm_max_rounds_stream", true);
+    request.max_rounds = Some(2);
+    let mut stream = Box::pin(
+        state
+            .kernel
+            .orchestrator
+            .stream(request)
+            .await
+            .expect("start limited stream"),
+    );
+    let mut reached = None;
+    let mut saw_final = false;
+    while let Ok(Some(Ok(event))) =
+        tokio::time::timeout(Duration::from_secs(30), stream.next()).await
+    {
+        match event.event.as_str() {
+            "max_rounds_reached" => reached = Some(event.data["data"].clone()),
+            "final" => {
+                saw_final = true;
+                break;
+            }
+            _ => {}
+        }
+    }
+    let reached = reached.expect("max_rounds_reached event");
+    assert_eq!(reached["max_rounds"], 2);
+    assert_eq!(reached["source"], "request");
+    assert_eq!(reached["tool_calls"], 2);
+    assert!(
+        saw_final,
+        "limited stream should still end with a final event"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn over_budget_history_is_trimmed_before_the_provider_call() {
+    let (state, _temp_dir) = build_mock_state_with_script(vec![MockLlmStep {
+        content: "scripted final answer".to_string(),
+        ..MockLlmStep::default()
+    }])
+    .await;
+    state
+        .config_store
+        .update(|config| {
+            if let Some(model) = config.llm.models.get_mut(MOCK_MODEL_NAME) {
+                model.max_context = Some(64_000);
+                model.context_reserve_tokens = Some(4_000);
+            }
+        })
+        .await
+        .expect("set context window");
+
+    // 60 turns of ~2k tokens each, well past the 60k prompt budget.
+    let session_id = "mock_llm_context_trim";
+    let filler = "history ".repeat(1_000);
+    for index in 0..60 {
+        let role = if index % 2 == 0 { "user" } else { "assistant" };
+        state
+            .storage
+            .append_chat(
+                "mock_llm_user",
+                &json!({
+                    "role": role,
+                    "content": format!("{index}: {filler}"),
+                    "session_id": session_id,
+                    "timestamp": chrono::Local::now().to_rfc3339(),
+                }),
+            )
+            .expect("seed history");
+    }
+
+    let mut stream = Box::pin(
+        state
+            .kernel
+            .orchestrator
+            .stream(mock_request(session_id, true))
+            .await
+            .expect("start stream"),
+    );
+    let mut trimmed = None;
+    let mut answer = None;
+    while let Ok(Some(Ok(event))) =
+        tokio::time::timeout(Duration::from_secs(30), stream.next()).await
+    {
+        match event.event.as_str() {
+            "context_trimmed" => trimmed = Some(event.data["data"].clone()),
+            "final" => {
+                answer = event.data["data"]["answer"].as_str().map(str::to_string);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let trimmed = trimmed.expect("context_trimmed event before the provider call");
+    assert_eq!(trimmed["budget_tokens"], 60_000);
+    assert!(trimmed["dropped_messages"].as_u64().unwrap_or(0) > 0);
+    assert!(trimmed["tokens_before"].as_i64() > trimmed["budget_tokens"].as_i64());
+    assert!(trimmed["tokens_after"].as_i64() <= trimmed["budget_tokens"].as_i64());
+    assert_eq!(answer.as_deref(), Some("scripted final answer"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cited_turn_returns_knowledge_sources_in_final_event() {
+    let (state, temp_dir) = build_mock_state_with_script(vec![
+        MockLlmStep {
+            tool_calls: vec![MockToolCall {
+                name: "manuals".to_string(),
+                arguments: serde_yaml::from_str("query: timeout").expect("yaml arguments"),
+            }],
+            ..MockLlmStep::default()
+        },
+        MockLlmStep {
+            content: "The timeout defaults to 120 seconds.".to_string(),
+            ..MockLlmStep::default()
+        },
+    ])
+    .await;
+    let knowledge_root = temp_dir.path().join("knowledge");
+    std::fs::create_dir_all(&knowledge_root).expect("create knowledge root");
+    std::fs::write(
+        knowledge_root.join("guide.md"),
+        "# Timeout\n\nThe request timeout defaults to 120 seconds.\n",
+    )
+    .expect("write knowledge document");
+    state
+        .config_store
+        .update(|config| {
+            config.knowledge.bases.push(KnowledgeBaseConfig {
+                name: "manuals".to_string(),
+                root: knowledge_root.to_string_lossy().to_string(),
+                enabled: true,
+                ..Default::default()
+            });
+        })
+        .await
+        .expect("add knowledge base");
+
+    let mut request = mock_request("mock_llm_cite", true);
+    request.cite = true;
+    let mut stream = Box::pin(
+        state
+            .kernel
+            .orchestrator
+            .stream(request)
+            .await
+            .expect("start stream"),
+    );
+    let mut final_data = None;
+    while let Ok(Some(Ok(event))) =
+        tokio::time::timeout(Duration::from_secs(30), stream.next()).await
+    {
+        if event.event == "final" {
+            final_data = Some(event.data["data"].clone());
+            break;
+        }
+    }
+    let final_data = final_data.expect("final event");
+    assert_eq!(final_data["answer"], "The timeout defaults to 120 seconds.");
+    let sources = final_data["sources"].as_array().expect("sources array");
+    assert!(!sources.is_empty(), "retrieved sources: {final_data}");
+    assert_eq!(sources[0]["knowledge_base"], "manuals");
+    assert_eq!(sources[0]["document"], "guide");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remembered_attachment_is_retrievable_in_a_later_session() {
+    let (state, _temp_dir) = build_mock_state_with_script(vec![
+        MockLlmStep {
+            tool_calls: vec![MockToolCall {
+                name: "mock_llm_user@attachments".to_string(),
+                arguments: serde_yaml::from_str("query: ZX-41").expect("yaml arguments"),
+            }],
+            ..MockLlmStep::default()
+        },
+        MockLlmStep {
+            content: "scripted final answer".to_string(),
+            ..MockLlmStep::default()
+        },
+    ])
+    .await;
+
+    let mut request = mock_request("mock_llm_remember_first", false);
+    request.attachments = Some(vec![AttachmentPayload {
+        name: Some("tolerances.md".to_string()),
+        content: Some("# Tolerances\n\nPart ZX-41 must stay within 7 microns.\n".to_string()),
+        content_type: Some("text/markdown".to_string()),
+        public_path: None,
+        attachment_id: None,
+        metadata: None,
+    }]);
+    request.remember_attachments = true;
+    state
+        .kernel
+        .orchestrator
+        .run(request)
+        .await
+        .expect("remembering turn");
+
+    let mut stream = Box::pin(
+        state
+            .kernel
+            .orchestrator
+            .stream(mock_request("mock_llm_remember_later", true))
+            .await
+            .expect("start later stream"),
+    );
+    let mut tool_result = None;
+    while let Ok(Some(Ok(event))) =
+        tokio::time::timeout(Duration::from_secs(30), stream.next()).await
+    {
+        match event.event.as_str() {
+            "tool_result" => tool_result = Some(event.data["data"].to_string()),
+            "final" => break,
+            _ => {}
+        }
+    }
+    let tool_result = tool_result.expect("knowledge tool result in the later session");
+    assert!(tool_result.contains("7 microns"), "{tool_result}");
+    assert!(
+        tool_result.contains("session mock_llm_remember_first, attachment tolerances.md"),
+        "provenance header should come back with the chunk: {tool_result}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn parallel_tool_results_map_back_to_their_call_ids() {
+    let files = [
+        ("alpha.txt", "alpha file body"),
+        ("beta.txt", "beta file body"),
+        ("gamma.txt", "gamma file body"),
+    ];
+    let (state, _temp_dir) = build_mock_state_with_script(vec![
+        MockLlmStep {
+            tool_calls: files
+                .iter()
+                .map(|(path, _)| MockToolCall {
+                    name: "read_file".to_string(),
+                    arguments: serde_yaml::from_str(&format!("path: {path}"))
+                        .expect("yaml arguments"),
+                })
+                .collect(),
+            ..MockLlmStep::default()
+        },
+        MockLlmStep {
+            content: "scripted final answer".to_string(),
+            ..MockLlmStep::default()
+        },
+    ])
+    .await;
+    // Tools run in the default container's scoped workspace, not the bare user root.
+    let workspace_id = state
+        .workspace
+        .scoped_user_id_by_container("mock_llm_user", DEFAULT_SANDBOX_CONTAINER_ID);
+    for (path, body) in files {
+        state
+            .workspace
+            .write_file(&workspace_id, path, body, true)
+            .expect("seed workspace file");
+    }
+
+    let mut stream = Box::pin(
+        state
+            .kernel
+            .orchestrator
+            .stream(mock_request("mock_llm_parallel_ids", true))
+            .await
+            .expect("start stream"),
+    );
+    let mut results = std::collections::HashMap::new();
+    while let Ok(Some(Ok(event))) =
+        tokio::time::timeout(Duration::from_secs(30), stream.next()).await
+    {
+        match event.event.as_str() {
+            "tool_result" => {
+                let data = &event.data["data"];
+                let id = data["tool_call_id"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                results.insert(id, data.to_string());
+            }
+            "final" => break,
+            _ => {}
+        }
+    }
+
+    assert_eq!(results.len(), files.len(), "{results:?}");
+    for (index, (_, body)) in files.iter().enumerate() {
+        let result = results
+            .get(&format!("call_mock_1_1_{index}"))
+            .unwrap_or_else(|| panic!("no result for call {index}: {results:?}"));
+        assert!(result.contains(body), "call {index} got {result}");
+        for (other, (_, other_body)) in files.iter().enumerate() {
+            if other != index {
+                assert!(!result.contains(other_body), "call {index} got {result}");
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use axum::{extract::State, routing::post, Json, Router};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use wunder_server::{
+    config::{Config, LlmModelConfig},
+    config_store::ConfigStore,
+    i18n,
+    schemas::WunderRequest,
+    state::{AppState, AppStateInitOptions},
+};
+
+const MODEL_NAME: &str = "language-mock";
+
+/// OpenAI-compatible mock that records every request payload and answers immediately.
+#[derive(Default)]
+struct MockProvider {
+    payloads: Mutex<Vec<Value>>,
+}
+
+async fn mock_chat_completions(
+    State(provider): State<Arc<MockProvider>>,
+    Json(payload): Json<Value>,
+) -> Json<Value> {
+    provider
+        .payloads
+        .lock()
+        .expect("payloads lock")
+        .push(payload);
+    Json(json!({
+        "id": "chatcmpl_request_language",
+        "object": "chat.completion",
+        "created": 1_773_620_812,
+        "model": MODEL_NAME,
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": "ok" },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 16, "completion_tokens": 1, "total_tokens": 17 }
+    }))
+}
+
+async fn spawn_mock_provider() -> (String, Arc<MockProvider>) {
+    let provider = Arc::new(MockProvider::default());
+    let app = Router::new()
+        .route("/v1/chat/completions", post(mock_chat_completions))
+        .with_state(provider.clone());
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind mock llm listener");
+    let addr = listener.local_addr().expect("mock llm addr");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    (format!("http://{addr}"), provider)
+}
+
+async fn build_state(base_url: String) -> (Arc<AppState>, TempDir) {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("request-language.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+    config.llm.auto_title.enabled = false;
+    config.llm.default = MODEL_NAME.to_string();
+    config.llm.models.insert(
+        MODEL_NAME.to_string(),
+        LlmModelConfig {
+            enable: Some(true),
+            provider: Some("openai".to_string()),
+            base_url: Some(base_url),
+            api_key: Some("test-key".to_string()),
+            model: Some(MODEL_NAME.to_string()),
+            max_rounds: Some(2),
+            stream: Some(false),
+            tool_call_mode: Some("tool_call".to_string()),
+            model_type: Some("llm".to_string()),
+            ..Default::default()
+        },
+    );
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+    (state, temp_dir)
+}
+
+fn request(session_id: &str, question: &str, language: Option<&str>) -> WunderRequest {
+    WunderRequest {
+        user_id: "language_user".to_string(),
+        question: question.to_string(),
+        client_message_id: None,
+        tool_names: Vec::new(),
+        skip_tool_calls: true,
+        stream: false,
+        debug_payload: false,
+        session_id: Some(session_id.to_string()),
+        agent_id: None,
+        workspace_container_id: None,
+        model_name: Some(MODEL_NAME.to_string()),
+        language: language.map(str::to_string),
+        config_overrides: None,
+        agent_prompt: None,
+        preview_skill: false,
+        attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
+        allow_queue: true,
+        is_admin: true,
+        enforce_runtime_queue: false,
+        approval_tx: None,
+    }
+}
+
+fn system_prompt_for(provider: &MockProvider, question: &str) -> String {
+    let payloads = provider.payloads.lock().expect("payloads lock");
+    let payload = payloads
+        .iter()
+        .find(|payload| payload.to_string().contains(question))
+        .expect("chat payload for question");
+    payload["messages"][0]["content"]
+        .as_str()
+        .expect("system prompt text")
+        .to_string()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn request_language_adds_response_language_instruction() {
+    let (base_url, provider) = spawn_mock_provider().await;
+    let (state, _temp_dir) = build_state(base_url).await;
+    let orchestrator = &state.kernel.orchestrator;
+
+    orchestrator
+        .run(request("lang_en", "question in english", Some("en")))
+        .await
+        .expect("english run");
+    // No request language: the caller's language (set from headers by the API layer) applies.
+    i18n::with_language(
+        "en-US".to_string(),
+        orchestrator.run(request("lang_header", "question via header", None)),
+    )
+    .await
+    .expect("header language run");
+    orchestrator
+        .run(request("lang_zh", "question in chinese", Some("zh-CN")))
+        .await
+        .expect("chinese run");
+
+    let english = system_prompt_for(&provider, "question in english");
+    assert!(
+        english.contains("Respond to the user in English (en-US)"),
+        "{english}"
+    );
+    assert!(system_prompt_for(&provider, "question via header")
+        .contains("Respond to the user in English (en-US)"));
+    let chinese = system_prompt_for(&provider, "question in chinese");
+    assert!(
+        chinese.contains("请使用简体中文 (zh-CN)回复用户"),
+        "{chinese}"
+    );
+    assert!(!chinese.contains("Respond to the user in"));
+}
@@ -0,0 +1,322 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::AUTHORIZATION, Method, Request, StatusCode},
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tower::ServiceExt;
+use wunder_server::{
+    build_router,
+    config::Config,
+    config_store::ConfigStore,
+    state::{AppState, AppStateInitOptions},
+    storage::ChatSessionRecord,
+};
+
+const SESSION_ID: &str = "sess_history_clear";
+
+struct TestContext {
+    state: Arc<AppState>,
+    user_id: String,
+    token: String,
+    _temp_dir: TempDir,
+}
+
+/// One user with a titled session holding a short conversation and a context token count.
+async fn build_session_with_history() -> TestContext {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("session-history-clear.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+
+    let created = state
+        .user_store
+        .create_user(
+            "history_clear",
+            Some("history_clear@example.test".to_string()),
+            "password-123",
+            Some("A"),
+            None,
+            vec!["user".to_string()],
+            "active",
+            false,
+        )
+        .expect("create user");
+    let token = state
+        .user_store
+        .create_session_token(&created.user_id)
+        .expect("create token")
+        .token;
+
+    let now = chrono::Utc::now().timestamp_millis() as f64 / 1000.0;
+    state
+        .user_store
+        .upsert_chat_session(&ChatSessionRecord {
+            session_id: SESSION_ID.to_string(),
+            user_id: created.user_id.clone(),
+            title: "Release planning".to_string(),
+            status: "active".to_string(),
+            created_at: now,
+            updated_at: now,
+            last_message_at: now,
+            agent_id: None,
+            tool_overrides: vec!["read_file".to_string()],
+            parent_session_id: None,
+            parent_message_id: None,
+            spawn_label: None,
+            spawned_by: None,
+        })
+        .expect("create chat session");
+    for (role, content) in [("user", "hello"), ("assistant", "hi there")] {
+        state
+            .workspace
+            .append_chat(
+                &created.user_id,
+                &json!({
+                    "role": role,
+                    "content": content,
+                    "session_id": SESSION_ID,
+                    "timestamp": "2026-01-01T00:00:00Z",
+                }),
+            )
+            .expect("append chat");
+    }
+    state
+        .workspace
+        .save_session_context_tokens_async(&created.user_id, SESSION_ID, 1234)
+        .await;
+
+    TestContext {
+        state,
+        user_id: created.user_id,
+        token,
+        _temp_dir: temp_dir,
+    }
+}
+
+fn clear_request(token: &str, session_id: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::DELETE)
+        .uri(format!("/wunder/chat/sessions/{session_id}/messages"))
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::empty())
+        .expect("build request")
+}
+
+fn message_request(
+    method: Method,
+    token: &str,
+    history_id: i64,
+    query: &str,
+    body: Option<Value>,
+) -> Request<Body> {
+    let builder = Request::builder()
+        .method(method)
+        .uri(format!(
+            "/wunder/chat/sessions/{SESSION_ID}/messages/{history_id}{query}"
+        ))
+        .header(AUTHORIZATION, format!("Bearer {token}"));
+    match body {
+        Some(body) => builder
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string())),
+        None => builder.body(Body::empty()),
+    }
+    .expect("build request")
+}
+
+/// Stored history as `(history_id, role, content)` once queued writes have landed.
+async fn stored_history(context: &TestContext) -> Vec<(i64, String, String)> {
+    context.state.workspace.flush_writes_async().await;
+    context
+        .state
+        .storage
+        .load_chat_history_page(&context.user_id, SESSION_ID, None, 50)
+        .expect("load history")
+        .iter()
+        .map(|item| {
+            (
+                item["_history_id"].as_i64().expect("history id"),
+                item["role"].as_str().unwrap_or_default().to_string(),
+                item["content"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn clearing_history_keeps_the_session_record() {
+    let context = build_session_with_history().await;
+    let app = build_router(context.state.clone());
+
+    let response = app
+        .oneshot(clear_request(&context.token, SESSION_ID))
+        .await
+        .expect("send request");
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    let payload: Value = serde_json::from_slice(&bytes).expect("parse response json");
+    assert_eq!(payload["data"]["id"], SESSION_ID);
+    assert_eq!(payload["data"]["deleted_messages"], 2);
+
+    let history = context
+        .state
+        .storage
+        .load_chat_history(&context.user_id, SESSION_ID, None)
+        .expect("load history");
+    assert!(history.is_empty(), "history should be cleared: {history:?}");
+    assert_eq!(
+        context
+            .state
+            .workspace
+            .load_session_context_tokens(&context.user_id, SESSION_ID),
+        0
+    );
+
+    let record = context
+        .state
+        .user_store
+        .get_chat_session(&context.user_id, SESSION_ID)
+        .expect("load session")
+        .expect("session record is kept");
+    assert_eq!(record.title, "Release planning");
+    assert_eq!(record.tool_overrides, vec!["read_file".to_string()]);
+}
+
+#[tokio::test]
+async fn clearing_unknown_session_returns_404() {
+    let context = build_session_with_history().await;
+    let app = build_router(context.state.clone());
+
+    let response = app
+        .oneshot(clear_request(&context.token, "sess_missing"))
+        .await
+        .expect("send request");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn deleting_a_user_message_can_cascade_to_its_replies() {
+    let context = build_session_with_history().await;
+    for (role, content) in [("user", "second question"), ("assistant", "second answer")] {
+        context
+            .state
+            .workspace
+            .append_chat(
+                &context.user_id,
+                &json!({
+                    "role": role,
+                    "content": content,
+                    "session_id": SESSION_ID,
+                    "timestamp": "2026-01-01T00:01:00Z",
+                }),
+            )
+            .expect("append chat");
+    }
+    let history = stored_history(&context).await;
+    assert_eq!(history.len(), 4);
+    let app = build_router(context.state.clone());
+
+    let response = app
+        .clone()
+        .oneshot(message_request(
+            Method::DELETE,
+            &context.token,
+            history[0].0,
+            "?cascade=true",
+            None,
+        ))
+        .await
+        .expect("send request");
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    let payload: Value = serde_json::from_slice(&bytes).expect("parse response json");
+    assert_eq!(
+        payload["data"]["deleted_history_ids"],
+        json!([history[0].0, history[1].0])
+    );
+
+    let remaining = stored_history(&context).await;
+    assert_eq!(remaining, history[2..].to_vec());
+
+    let response = app
+        .oneshot(message_request(
+            Method::DELETE,
+            &context.token,
+            history[3].0,
+            "",
+            None,
+        ))
+        .await
+        .expect("send request");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(stored_history(&context).await, history[2..3].to_vec());
+}
+
+#[tokio::test]
+async fn edited_message_is_returned_after_reload() {
+    let context = build_session_with_history().await;
+    let history = stored_history(&context).await;
+    let (user_message_id, _, _) = history[0].clone();
+    let app = build_router(context.state.clone());
+
+    let response = app
+        .clone()
+        .oneshot(message_request(
+            Method::PATCH,
+            &context.token,
+            user_message_id,
+            "",
+            Some(json!({ "content": "hello, corrected" })),
+        ))
+        .await
+        .expect("send request");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let reloaded = stored_history(&context).await;
+    assert_eq!(reloaded[0].0, user_message_id);
+    assert_eq!(reloaded[0].2, "hello, corrected");
+    assert_eq!(reloaded[1], history[1]);
+
+    let response = app
+        .oneshot(message_request(
+            Method::GET,
+            &context.token,
+            user_message_id,
+            "",
+            None,
+        ))
+        .await
+        .expect("send request");
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    let payload: Value = serde_json::from_slice(&bytes).expect("parse response json");
+    assert_eq!(payload["data"]["message"]["content"], "hello, corrected");
+}
@@ -0,0 +1,202 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::AUTHORIZATION, Method, Request, StatusCode},
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tower::ServiceExt;
+use wunder_server::{
+    build_router,
+    config::Config,
+    config_store::ConfigStore,
+    state::{AppState, AppStateInitOptions},
+    storage::ChatSessionRecord,
+};
+
+const SESSION_ID: &str = "sess_tool_calls";
+
+struct TestContext {
+    state: Arc<AppState>,
+    token: String,
+    _temp_dir: TempDir,
+}
+
+/// One user with a session where the assistant read two files and listed a directory.
+async fn build_session_with_tool_calls() -> TestContext {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("session-tool-calls.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+
+    let created = state
+        .user_store
+        .create_user(
+            "tool_calls",
+            Some("tool_calls@example.test".to_string()),
+            "password-123",
+            Some("A"),
+            None,
+            vec!["user".to_string()],
+            "active",
+            false,
+        )
+        .expect("create user");
+    let token = state
+        .user_store
+        .create_session_token(&created.user_id)
+        .expect("create token")
+        .token;
+
+    let now = chrono::Utc::now().timestamp_millis() as f64 / 1000.0;
+    state
+        .user_store
+        .upsert_chat_session(&ChatSessionRecord {
+            session_id: SESSION_ID.to_string(),
+            user_id: created.user_id.clone(),
+            title: "Inspect files".to_string(),
+            status: "active".to_string(),
+            created_at: now,
+            updated_at: now,
+            last_message_at: now,
+            agent_id: None,
+            tool_overrides: Vec::new(),
+            parent_session_id: None,
+            parent_message_id: None,
+            spawn_label: None,
+            spawned_by: None,
+        })
+        .expect("create chat session");
+    let call = |id: &str, name: &str, arguments: &str| json!({ "id": id, "type": "function", "function": { "name": name, "arguments": arguments } });
+    let items = [
+        json!({ "role": "user", "content": "what is in the project?" }),
+        json!({
+            "role": "assistant",
+            "content": "",
+            "meta": { "type": "tool_call" },
+            "tool_calls": [
+                call("call_1", "list_files", "{\"path\":\".\"}"),
+                call("call_2", "read_file", "{\"path\":\"README.md\"}"),
+            ],
+        }),
+        json!({ "role": "tool", "tool_call_id": "call_1", "content": "{\"tool\":\"list_files\",\"ok\":true}" }),
+        json!({ "role": "tool", "tool_call_id": "call_2", "content": "{\"tool\":\"read_file\",\"ok\":true}" }),
+        json!({
+            "role": "assistant",
+            "content": "",
+            "meta": { "type": "tool_call" },
+            "tool_calls": [call("call_3", "read_file", "{\"path\":\"Cargo.toml\"}")],
+        }),
+        json!({ "role": "tool", "tool_call_id": "call_3", "content": "{\"tool\":\"read_file\",\"ok\":true}" }),
+        json!({ "role": "assistant", "content": "It is a Rust project." }),
+    ];
+    for mut item in items {
+        item["session_id"] = json!(SESSION_ID);
+        item["timestamp"] = json!("2026-01-01T00:00:00Z");
+        state
+            .workspace
+            .append_chat(&created.user_id, &item)
+            .expect("append chat");
+    }
+    state.workspace.flush_writes_async().await;
+
+    TestContext {
+        state,
+        token,
+        _temp_dir: temp_dir,
+    }
+}
+
+async fn get_tool_calls(context: &TestContext, query: &str) -> (StatusCode, Value) {
+    let response = build_router(context.state.clone())
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri(format!(
+                    "/wunder/chat/sessions/{SESSION_ID}/tool-calls{query}"
+                ))
+                .header(AUTHORIZATION, format!("Bearer {}", context.token))
+                .body(Body::empty())
+                .expect("build request"),
+        )
+        .await
+        .expect("send request");
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    (
+        status,
+        serde_json::from_slice(&bytes).expect("parse response json"),
+    )
+}
+
+fn call_ids(payload: &Value) -> Vec<String> {
+    payload["data"]["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .map(|item| {
+            item["tool_call_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn tool_calls_endpoint_returns_only_tool_events() {
+    let context = build_session_with_tool_calls().await;
+
+    let (status, payload) = get_tool_calls(&context, "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(payload["data"]["total"], 3);
+    assert_eq!(call_ids(&payload), vec!["call_1", "call_2", "call_3"]);
+    let first = &payload["data"]["items"][0];
+    assert_eq!(first["name"], "list_files");
+    assert_eq!(first["arguments"], "{\"path\":\".\"}");
+    assert_eq!(first["result"], "{\"tool\":\"list_files\",\"ok\":true}");
+
+    let (_, page) = get_tool_calls(&context, "?offset=1&limit=1").await;
+    assert_eq!(call_ids(&page), vec!["call_2"]);
+    assert_eq!(page["data"]["has_more"], true);
+}
+
+#[tokio::test]
+async fn tool_calls_endpoint_honors_the_tool_name_filter() {
+    let context = build_session_with_tool_calls().await;
+
+    let (status, payload) = get_tool_calls(&context, "?tool=read_file").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(payload["data"]["total"], 2);
+    assert_eq!(call_ids(&payload), vec!["call_2", "call_3"]);
+    assert!(payload["data"]["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .all(|item| item["name"] == "read_file"));
+
+    let (_, none) = get_tool_calls(&context, "?tool=write_file").await;
+    assert_eq!(none["data"]["total"], 0);
+}
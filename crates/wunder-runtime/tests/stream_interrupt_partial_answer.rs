@@ -0,0 +1,256 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::header::CONTENT_TYPE,
+    response::Response,
+    routing::post,
+    Router,
+};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use wunder_server::{
+    config::{Config, LlmModelConfig},
+    config_store::ConfigStore,
+    schemas::WunderRequest,
+    state::{AppState, AppStateInitOptions},
+};
+
+const MODEL_NAME: &str = "stream-interrupt-mock";
+const PARTIAL_ANSWER: &str = "Partial answer before the drop";
+const FULL_ANSWER: &str = "Full answer after retry";
+
+/// OpenAI-compatible streaming mock: the first `dropped_calls` calls drop the connection after
+/// a few content chunks, later calls stream a complete answer.
+struct MockProvider {
+    calls: AtomicUsize,
+    dropped_calls: usize,
+}
+
+fn sse_delta(content: &str) -> Bytes {
+    let chunk = json!({
+        "id": "chatcmpl_stream_interrupt",
+        "object": "chat.completion.chunk",
+        "model": MODEL_NAME,
+        "choices": [{ "index": 0, "delta": { "content": content } }]
+    });
+    Bytes::from(format!("data: {chunk}\n\n"))
+}
+
+async fn mock_chat_completions(State(provider): State<Arc<MockProvider>>) -> Response {
+    let call = provider.calls.fetch_add(1, Ordering::SeqCst);
+    let mut chunks: Vec<Result<Bytes, std::io::Error>> = Vec::new();
+    if call < provider.dropped_calls {
+        let (head, tail) = PARTIAL_ANSWER.split_at(8);
+        chunks.push(Ok(sse_delta(head)));
+        chunks.push(Ok(sse_delta(tail)));
+        chunks.push(Err(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "upstream dropped",
+        )));
+    } else {
+        chunks.push(Ok(sse_delta(FULL_ANSWER)));
+        chunks.push(Ok(Bytes::from("data: [DONE]\n\n")));
+    }
+    // Space the chunks out so the client reads the deltas before the connection is cut.
+    let body = futures::stream::iter(chunks).then(|chunk| async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        chunk
+    });
+    Response::builder()
+        .header(CONTENT_TYPE, "text/event-stream")
+        .body(Body::from_stream(body))
+        .expect("build stream response")
+}
+
+async fn spawn_mock_provider(dropped_calls: usize) -> (String, Arc<MockProvider>) {
+    let provider = Arc::new(MockProvider {
+        calls: AtomicUsize::new(0),
+        dropped_calls,
+    });
+    let app = Router::new()
+        .route("/v1/chat/completions", post(mock_chat_completions))
+        .with_state(provider.clone());
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind mock llm listener");
+    let addr = listener.local_addr().expect("mock llm addr");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    (format!("http://{addr}"), provider)
+}
+
+async fn build_state(base_url: String, retry: bool) -> (Arc<AppState>, TempDir) {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("stream-interrupt.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+    config.llm.auto_title.enabled = false;
+    config.llm.stream_interrupt.retry = retry;
+    config.llm.default = MODEL_NAME.to_string();
+    config.llm.models.insert(
+        MODEL_NAME.to_string(),
+        LlmModelConfig {
+            enable: Some(true),
+            provider: Some("openai".to_string()),
+            base_url: Some(base_url),
+            api_key: Some("test-key".to_string()),
+            model: Some(MODEL_NAME.to_string()),
+            max_rounds: Some(2),
+            stream: Some(true),
+            stream_include_usage: Some(false),
+            tool_call_mode: Some("tool_call".to_string()),
+            model_type: Some("llm".to_string()),
+            ..Default::default()
+        },
+    );
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+    (state, temp_dir)
+}
+
+fn request(session_id: &str) -> WunderRequest {
+    WunderRequest {
+        user_id: "stream_interrupt_user".to_string(),
+        question: "tell me something".to_string(),
+        client_message_id: None,
+        tool_names: Vec::new(),
+        skip_tool_calls: true,
+        stream: true,
+        debug_payload: false,
+        session_id: Some(session_id.to_string()),
+        agent_id: None,
+        workspace_container_id: None,
+        model_name: Some(MODEL_NAME.to_string()),
+        language: None,
+        config_overrides: None,
+        agent_prompt: None,
+        preview_skill: false,
+        attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
+        allow_queue: true,
+        is_admin: true,
+        enforce_runtime_queue: false,
+        approval_tx: None,
+    }
+}
+
+/// Events of one streamed turn that the interrupt handling touches.
+#[derive(Default)]
+struct TurnEvents {
+    interrupted: Vec<Value>,
+    retries: Vec<Value>,
+    final_data: Option<Value>,
+}
+
+/// Runs one streamed turn and collects its `stream_interrupted`, `llm_stream_retry` and
+/// `final` payloads.
+async fn collect_turn(state: &AppState, session_id: &str) -> TurnEvents {
+    let mut stream = Box::pin(
+        state
+            .kernel
+            .orchestrator
+            .stream(request(session_id))
+            .await
+            .expect("start stream"),
+    );
+    let mut events = TurnEvents::default();
+    while let Ok(Some(Ok(event))) =
+        tokio::time::timeout(Duration::from_secs(30), stream.next()).await
+    {
+        match event.event.as_str() {
+            "stream_interrupted" => events.interrupted.push(event.data["data"].clone()),
+            "llm_stream_retry" => events.retries.push(event.data["data"].clone()),
+            "final" => {
+                events.final_data = Some(event.data["data"].clone());
+                break;
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn mid_stream_disconnect_ends_turn_with_partial_answer_by_default() {
+    assert!(!Config::default().llm.stream_interrupt.retry);
+    let (base_url, provider) = spawn_mock_provider(1).await;
+    let (state, _temp_dir) = build_state(base_url, false).await;
+
+    let events = collect_turn(&state, "stream_interrupt_partial").await;
+
+    assert_eq!(events.interrupted.len(), 1, "{:?}", events.interrupted);
+    assert_eq!(events.interrupted[0]["will_retry"], false);
+    assert_eq!(
+        events.interrupted[0]["partial_chars"],
+        PARTIAL_ANSWER.chars().count()
+    );
+    assert!(events.retries.is_empty(), "{:?}", events.retries);
+    let final_data = events.final_data.expect("final event");
+    assert_eq!(final_data["answer"], PARTIAL_ANSWER);
+    assert_eq!(final_data["incomplete"], true);
+    assert_eq!(final_data["stop_reason"], "stream_interrupted");
+    assert_eq!(final_data["stop_meta"]["incomplete"], true);
+    assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn opted_in_retry_resets_partial_output_and_resends_once() {
+    let (base_url, provider) = spawn_mock_provider(1).await;
+    let (state, _temp_dir) = build_state(base_url, true).await;
+
+    let events = collect_turn(&state, "stream_interrupt_retry").await;
+
+    assert_eq!(events.interrupted.len(), 1, "{:?}", events.interrupted);
+    assert_eq!(events.interrupted[0]["will_retry"], true);
+    assert_eq!(events.interrupted[0]["max_attempts"], 2);
+    assert_eq!(events.retries.len(), 1, "{:?}", events.retries);
+    assert_eq!(events.retries[0]["retry_reason"], "stream_interrupted");
+    assert_eq!(events.retries[0]["reset_output"], true);
+    assert!(events.retries[0]["delay_s"].as_f64().unwrap_or(0.0) > 0.0);
+    let final_data = events.final_data.expect("final event");
+    assert_eq!(final_data["answer"], FULL_ANSWER);
+    assert!(final_data.get("incomplete").is_none(), "{final_data}");
+    assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn opted_in_retry_keeps_partial_answer_when_the_retry_drops_too() {
+    let (base_url, provider) = spawn_mock_provider(usize::MAX).await;
+    let (state, _temp_dir) = build_state(base_url, true).await;
+
+    let events = collect_turn(&state, "stream_interrupt_retry_exhausted").await;
+
+    assert_eq!(events.interrupted.len(), 2, "{:?}", events.interrupted);
+    assert_eq!(events.interrupted[1]["will_retry"], false);
+    assert_eq!(events.retries.len(), 1, "{:?}", events.retries);
+    let final_data = events.final_data.expect("final event");
+    assert_eq!(final_data["answer"], PARTIAL_ANSWER);
+    assert_eq!(final_data["incomplete"], true);
+    assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+}
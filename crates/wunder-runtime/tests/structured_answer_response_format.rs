@@ -0,0 +1,179 @@
+use axum::{extract::State, routing::post, Json, Router};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use wunder_server::{
+    config::{Config, LlmModelConfig},
+    config_store::ConfigStore,
+    schemas::WunderRequest,
+    state::{AppState, AppStateInitOptions},
+};
+
+const MODEL_NAME: &str = "structured-mock";
+const QUESTION: &str = "Extract the title of: Quarterly report";
+
+/// OpenAI-compatible mock that answers with prose first and with JSON afterwards,
+/// recording every request payload.
+#[derive(Default)]
+struct MockProvider {
+    payloads: Mutex<Vec<Value>>,
+}
+
+async fn mock_chat_completions(
+    State(provider): State<Arc<MockProvider>>,
+    Json(payload): Json<Value>,
+) -> Json<Value> {
+    let mut payloads = provider.payloads.lock().expect("payloads lock");
+    payloads.push(payload);
+    let content = if payloads.len() == 1 {
+        "The title is Quarterly report."
+    } else {
+        "{\"title\": \"Quarterly report\"}"
+    };
+    Json(json!({
+        "id": "chatcmpl_structured_answer",
+        "object": "chat.completion",
+        "created": 1_773_620_812,
+        "model": MODEL_NAME,
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": content },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 32, "completion_tokens": 8, "total_tokens": 40 }
+    }))
+}
+
+async fn spawn_mock_provider() -> (String, Arc<MockProvider>) {
+    let provider = Arc::new(MockProvider::default());
+    let app = Router::new()
+        .route("/v1/chat/completions", post(mock_chat_completions))
+        .with_state(provider.clone());
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind mock llm listener");
+    let addr = listener.local_addr().expect("mock llm addr");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    (format!("http://{addr}"), provider)
+}
+
+async fn build_state(base_url: String) -> (Arc<AppState>, TempDir) {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("structured-answer.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+    config.llm.auto_title.enabled = false;
+    config.llm.default = MODEL_NAME.to_string();
+    config.llm.models.insert(
+        MODEL_NAME.to_string(),
+        LlmModelConfig {
+            enable: Some(true),
+            provider: Some("openai".to_string()),
+            base_url: Some(base_url),
+            api_key: Some("test-key".to_string()),
+            model: Some(MODEL_NAME.to_string()),
+            max_rounds: Some(4),
+            stream: Some(false),
+            tool_call_mode: Some("tool_call".to_string()),
+            model_type: Some("llm".to_string()),
+            response_format_retry: Some(true),
+            ..Default::default()
+        },
+    );
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+    (state, temp_dir)
+}
+
+fn structured_request(response_format: Value) -> WunderRequest {
+    WunderRequest {
+        user_id: "structured_user".to_string(),
+        question: QUESTION.to_string(),
+        client_message_id: None,
+        tool_names: Vec::new(),
+        skip_tool_calls: true,
+        stream: false,
+        debug_payload: false,
+        session_id: Some("structured_answer".to_string()),
+        agent_id: None,
+        workspace_container_id: None,
+        model_name: Some(MODEL_NAME.to_string()),
+        language: None,
+        // Request-scoped, the same way the CLI passes --json-schema.
+        config_overrides: Some(json!({
+            "llm": { "models": { MODEL_NAME: { "response_format": response_format } } }
+        })),
+        agent_prompt: None,
+        preview_skill: false,
+        attachments: None,
+        max_rounds: None,
+        cite: false,
+        remember_attachments: false,
+        allow_queue: true,
+        is_admin: true,
+        enforce_runtime_queue: false,
+        approval_tx: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn response_format_is_sent_and_invalid_answer_is_retried_once() {
+    let (base_url, provider) = spawn_mock_provider().await;
+    let (state, _temp_dir) = build_state(base_url).await;
+    let response_format = json!({
+        "type": "json_schema",
+        "json_schema": {
+            "name": "title",
+            "schema": { "type": "object", "required": ["title"] }
+        }
+    });
+
+    let response = state
+        .kernel
+        .orchestrator
+        .run(structured_request(response_format.clone()))
+        .await
+        .expect("structured run");
+
+    let answer: Value = serde_json::from_str(&response.answer).expect("answer is JSON");
+    assert_eq!(answer["title"], "Quarterly report");
+
+    let payloads = provider.payloads.lock().expect("payloads lock").clone();
+    let chat_payloads = payloads
+        .iter()
+        .filter(|payload| payload.to_string().contains(QUESTION))
+        .collect::<Vec<_>>();
+    assert_eq!(chat_payloads.len(), 2, "one retry after the prose answer");
+    for payload in &chat_payloads {
+        assert_eq!(payload["response_format"], response_format);
+        let system_prompt = payload["messages"][0]["content"].to_string();
+        assert_eq!(payload["messages"][0]["role"], "system");
+        assert!(
+            system_prompt.contains("JSON"),
+            "json mode instruction missing"
+        );
+    }
+    let retry_messages = chat_payloads[1]["messages"].to_string();
+    assert!(retry_messages.contains("final_answer_not_valid_json"));
+}
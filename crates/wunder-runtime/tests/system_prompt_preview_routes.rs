@@ -0,0 +1,211 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::AUTHORIZATION, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tower::ServiceExt;
+use wunder_server::{
+    build_desktop_router,
+    config::{Config, LlmModelConfig},
+    config_store::ConfigStore,
+    state::{AppState, AppStateInitOptions},
+};
+
+struct TestContext {
+    app: Router,
+    state: Arc<AppState>,
+    token: String,
+    user_id: String,
+    _temp_dir: TempDir,
+}
+
+async fn build_test_context(username: &str) -> TestContext {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("system-prompt-preview.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+    let user = state
+        .user_store
+        .create_user(
+            username,
+            Some(format!("{username}@example.test")),
+            "password-123",
+            Some("A"),
+            None,
+            vec!["user".to_string()],
+            "active",
+            false,
+        )
+        .expect("create user");
+    let token = state
+        .user_store
+        .create_session_token(&user.user_id)
+        .expect("create token")
+        .token;
+
+    TestContext {
+        app: build_desktop_router(state.clone()),
+        state,
+        token,
+        user_id: user.user_id,
+        _temp_dir: temp_dir,
+    }
+}
+
+async fn post_json(app: &Router, token: &str, path: &str, payload: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(path)
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("send request");
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    let payload = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).expect("parse response json")
+    };
+    (status, payload)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn preview_matches_direct_build_system_prompt() {
+    let context = build_test_context("preview_user").await;
+    let (status, payload) = post_json(
+        &context.app,
+        &context.token,
+        "/wunder/system_prompt/preview",
+        json!({
+            "user_id": context.user_id,
+            "workspace_container_id": 2,
+            "agent_prompt": "Always answer in haiku.",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{payload}");
+    let preview = payload["prompt"].as_str().expect("prompt").to_string();
+    assert!(preview.contains("Always answer in haiku."));
+
+    let state = &context.state;
+    let config = state.config_store.get().await;
+    let skills = state.skills.read().await.clone();
+    let bindings = state
+        .user_tool_manager
+        .build_bindings(&config, &skills, &context.user_id);
+    let workspace_id = state
+        .workspace
+        .scoped_user_id_by_container(&context.user_id, 2);
+    let direct = state
+        .kernel
+        .orchestrator
+        .build_system_prompt(
+            &config,
+            &[],
+            &skills,
+            Some(&bindings),
+            &context.user_id,
+            None,
+            false,
+            &workspace_id,
+            None,
+            None,
+            Some("Always answer in haiku."),
+            false,
+        )
+        .await;
+    assert_eq!(preview, direct);
+}
+
+#[tokio::test]
+async fn preview_rejects_unknown_model() {
+    let context = build_test_context("preview_model_user").await;
+    let (status, payload) = post_json(
+        &context.app,
+        &context.token,
+        "/wunder/system_prompt/preview",
+        json!({ "user_id": context.user_id, "model_name": "missing-model" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{payload}");
+    assert_eq!(payload["detail"]["fields"][0]["field"], "model_name");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn prompt_budget_applies_request_overrides() {
+    let context = build_test_context("budget_user").await;
+    let state = &context.state;
+    let mut config = state.config_store.get().await;
+    config.llm.models.insert(
+        "budget-model".to_string(),
+        LlmModelConfig {
+            enable: Some(true),
+            provider: Some("openai".to_string()),
+            model: Some("budget-model".to_string()),
+            tool_call_mode: Some("tool_call".to_string()),
+            model_type: Some("llm".to_string()),
+            ..Default::default()
+        },
+    );
+    let skills = state.skills.read().await.clone();
+    let bindings = state
+        .user_tool_manager
+        .build_bindings(&config, &skills, &context.user_id);
+    let workspace_id = state.workspace.scoped_user_id(&context.user_id, None);
+    // Function calling sends tool specs through the API, so the prompt stops listing them.
+    let function_call_overrides = json!({
+        "llm": { "models": { "budget-model": { "tool_call_mode": "function_call" } } }
+    });
+    let mut tools_tokens = Vec::new();
+    for overrides in [None, Some(&function_call_overrides)] {
+        let breakdown = state
+            .kernel
+            .orchestrator
+            .build_system_prompt_breakdown(
+                &config,
+                &[],
+                &skills,
+                Some(&bindings),
+                &context.user_id,
+                None,
+                &workspace_id,
+                overrides,
+                Some("budget-model"),
+                None,
+                false,
+            )
+            .await;
+        tools_tokens.push(breakdown.section_tokens("tools"));
+    }
+    let (tool_call, function_call) = (tools_tokens[0], tools_tokens[1]);
+    assert!(tool_call > 0);
+    assert!(function_call < tool_call, "{function_call} >= {tool_call}");
+}
@@ -0,0 +1,198 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::AUTHORIZATION, header::RETRY_AFTER, Method, Request, StatusCode},
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tower::ServiceExt;
+use wunder_server::{
+    build_router,
+    config::{Config, LlmModelConfig, MockLlmStep},
+    config_store::ConfigStore,
+    orchestrator_error_payload,
+    schemas::WunderRequest,
+    state::{AppState, AppStateInitOptions},
+    user_store::UserStore,
+};
+
+const MOCK_MODEL_NAME: &str = "offline-mock";
+
+struct TestContext {
+    state: Arc<AppState>,
+    user_id: String,
+    token: String,
+    _temp_dir: TempDir,
+}
+
+/// Mock-provider state with one regular user whose balance for today is already spent.
+async fn build_exhausted_user_context() -> TestContext {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+    config.storage.backend = "sqlite".to_string();
+    config.storage.db_path = temp_dir
+        .path()
+        .join("user-quota-gate.db")
+        .to_string_lossy()
+        .to_string();
+    config.workspace.root = temp_dir
+        .path()
+        .join("workspaces")
+        .to_string_lossy()
+        .to_string();
+    config.llm.default = MOCK_MODEL_NAME.to_string();
+    config.llm.models.insert(
+        MOCK_MODEL_NAME.to_string(),
+        LlmModelConfig {
+            enable: Some(true),
+            provider: Some("mock".to_string()),
+            model: Some(MOCK_MODEL_NAME.to_string()),
+            model_type: Some("llm".to_string()),
+            mock_script: vec![MockLlmStep {
+                content: "should never be produced".to_string(),
+                ..MockLlmStep::default()
+            }],
+            ..Default::default()
+        },
+    );
+
+    let config_store = ConfigStore::new(temp_dir.path().join("wunder.yaml"));
+    let config_for_store = config.clone();
+    config_store
+        .update(|current| *current = config_for_store.clone())
+        .await
+        .expect("update config store");
+    let state = Arc::new(
+        AppState::new_with_options(config_store, config, AppStateInitOptions::cli_default())
+            .expect("create app state"),
+    );
+
+    let created = state
+        .user_store
+        .create_user(
+            "quota_exhausted",
+            Some("quota_exhausted@example.test".to_string()),
+            "password-123",
+            Some("A"),
+            None,
+            vec!["user".to_string()],
+            "active",
+            false,
+        )
+        .expect("create user");
+    let mut record = state
+        .user_store
+        .get_user_by_id(&created.user_id)
+        .expect("load user")
+        .expect("user exists");
+    record.token_balance = 0;
+    record.token_granted_total = 1000;
+    record.token_used_total = 1000;
+    record.last_token_grant_date = Some(UserStore::today_string());
+    state
+        .user_store
+        .update_user(&record)
+        .expect("seed exhausted balance");
+    let token = state
+        .user_store
+        .create_session_token(&created.user_id)
+        .expect("create token")
+        .token;
+
+    TestContext {
+        state,
+        user_id: created.user_id,
+        token,
+        _temp_dir: temp_dir,
+    }
+}
+
+fn quota_request(user_id: &str, session_id: &str) -> WunderRequest {
+    serde_json::from_value(json!({
+        "user_id": user_id,
+        "question": "hello",
+        "session_id": session_id,
+        "model_name": MOCK_MODEL_NAME,
+    }))
+    .expect("valid request")
+}
+
+#[tokio::test]
+async fn over_quota_turn_is_rejected_before_any_model_call() {
+    let context = build_exhausted_user_context().await;
+    let session_id = "quota_gate_run";
+
+    let err = context
+        .state
+        .kernel
+        .orchestrator
+        .run(quota_request(&context.user_id, session_id))
+        .await
+        .expect_err("over-quota run must be rejected");
+    let payload = orchestrator_error_payload(&err).expect("orchestrator error payload");
+    assert_eq!(payload["code"], "USER_TOKEN_INSUFFICIENT");
+    assert_eq!(payload["error_meta"]["category"], "quota");
+    let resets_at = payload["detail"]["resets_at"]
+        .as_str()
+        .expect("resets_at detail");
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(resets_at).is_ok(),
+        "{resets_at}"
+    );
+    assert!(payload["detail"]["retry_after_s"].as_i64().unwrap_or(0) >= 1);
+
+    // The question is persisted when a turn starts, so an empty history means the turn never
+    // reached the model.
+    let history = context
+        .state
+        .storage
+        .load_chat_history(&context.user_id, session_id, None)
+        .expect("load history");
+    assert!(
+        history.is_empty(),
+        "no turn should be recorded: {history:?}"
+    );
+}
+
+#[tokio::test]
+async fn over_quota_http_request_returns_429_with_retry_after() {
+    let context = build_exhausted_user_context().await;
+    let app = build_router(context.state.clone());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/wunder")
+                .header(AUTHORIZATION, format!("Bearer {}", context.token))
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "user_id": context.user_id,
+                        "question": "hello",
+                        "model_name": MOCK_MODEL_NAME,
+                    })
+                    .to_string(),
+                ))
+                .expect("build request"),
+        )
+        .await
+        .expect("send request");
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .expect("Retry-After header");
+    assert!((1..=25 * 3600).contains(&retry_after));
+
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    let payload: Value = serde_json::from_slice(&bytes).expect("parse response json");
+    assert_eq!(payload["error"]["code"], "USER_TOKEN_INSUFFICIENT");
+    assert_eq!(payload["detail"]["detail"]["retry_after_s"], retry_after);
+    assert!(payload["detail"]["detail"]["resets_at"].is_string());
+}
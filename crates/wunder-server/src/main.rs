@@ -125,6 +125,8 @@ async fn run_server(runtime_threads: runtime_tuning::ServerRuntimeThreads) -> an
     if let Err(err) = server.await {
         warn!(error = %err, "server exited unexpectedly");
     }
+    // 退出前刷新尚未导出的链路 span。
+    let _ = tokio::task::spawn_blocking(logging::shutdown_server_tracing).await;
 
     Ok(())
 }
@@ -3044,6 +3044,73 @@ test('canonical visible workflow events project retry slow-client and compaction
   assert.equal(selectSessionBusy(projection, 'session-1'), false);
 });
 
+test('tool_started and tool_finished annotate the tool item with execution timing', () => {
+  const projection = createChatRuntimeProjection();
+  const apply = (eventType: string, eventId: number, data: Record<string, unknown>) =>
+    buildCanonicalChatRuntimeEvents({
+      sessionId: 'session-1',
+      eventType,
+      eventId,
+      requestId: 'req-tool-run',
+      payload: { data: { user_round: 1, model_round: 1, tool_call_id: 'call-run', ...data } }
+    }).forEach((event) => applyChatRuntimeEvent(projection, event));
+
+  apply('tool_call', 50, { tool: 'read_file', arguments: { path: 'a.txt' } });
+  apply('tool_started', 51, { tool: 'read_file', mode: 'parallel_read' });
+
+  let assistant = selectVisibleMessageProjections(projection, 'session-1')
+    .find((message) => message.role === 'assistant');
+  assert.ok(assistant);
+  assert.equal(assistant.workflowItems?.length, 1);
+  assert.equal(assistant.workflowItems?.[0]?.executionMode, 'parallel_read');
+  assert.equal(assistant.workflowItems?.[0]?.executing, true);
+  assert.equal(assistant.workflowItems?.[0]?.status, 'loading');
+
+  apply('tool_finished', 52, { tool: 'read_file', mode: 'parallel_read', ok: true, duration_ms: 42 });
+  apply('tool_result', 53, { tool: 'read_file', result: 'hello' });
+
+  assistant = selectVisibleMessageProjections(projection, 'session-1')
+    .find((message) => message.role === 'assistant');
+  assert.ok(assistant);
+  assert.equal(assistant.workflowItems?.length, 1);
+  assert.equal(assistant.workflowItems?.[0]?.executing, false);
+  assert.equal(assistant.workflowItems?.[0]?.durationMs, 42);
+  assert.equal(assistant.workflowItems?.[0]?.status, 'completed');
+});
+
+test('stream-interrupt retry with reset_output drops the partial answer before the re-send', () => {
+  const projection = createChatRuntimeProjection();
+  const apply = (eventType: string, eventId: number, data: Record<string, unknown>) =>
+    buildCanonicalChatRuntimeEvents({
+      sessionId: 'session-1',
+      eventType,
+      eventId,
+      requestId: 'req-interrupt',
+      payload: { data: { user_round: 1, model_round: 1, ...data } }
+    }).forEach((event) => applyChatRuntimeEvent(projection, event));
+
+  apply('llm_output_delta', 40, { delta: 'Partial answer' });
+  apply('llm_stream_retry', 41, {
+    attempt: 1,
+    max_attempts: 2,
+    delay_s: 1,
+    retry_reason: 'stream_interrupted',
+    reset_output: true
+  });
+
+  let assistant = selectVisibleMessageProjections(projection, 'session-1')
+    .find((message) => message.role === 'assistant');
+  assert.ok(assistant);
+  assert.equal(assistant.content, '');
+  assert.equal(assistant.display?.retry_reason, 'stream_interrupted');
+
+  apply('llm_output_delta', 42, { delta: 'Full answer' });
+  assistant = selectVisibleMessageProjections(projection, 'session-1')
+    .find((message) => message.role === 'assistant');
+  assert.ok(assistant);
+  assert.equal(assistant.content, 'Full answer');
+});
+
 test('canonical command session events project into execute command workflow item', () => {
   const projection = createChatRuntimeProjection();
   const startEvents = buildCanonicalChatRuntimeEvents({
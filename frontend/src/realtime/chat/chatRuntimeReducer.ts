@@ -78,6 +78,8 @@ const ACTIVE_SUBAGENT_STATUSES = new Set([
 ]);
 const TOOL_RESULT_EVENT_TYPES = new Set(['tool_call_completed', 'tool_call_failed']);
 const PROJECTED_TOOL_RESULT_EVENT_TYPES = new Set(['tool_result']);
+// Per-call execution timing; annotates the tool item instead of adding a workflow row.
+const TOOL_RUN_WORKFLOW_EVENT_TYPES = new Set(['tool_started', 'tool_finished']);
 const isTerminalWorkflowStatus = (status: string): boolean =>
   Boolean(status) && !ACTIVE_WORKFLOW_STATUSES.has(status);
 const WORKFLOW_CONTEXT_SNAPSHOT_KEY = '__workflowContextSnapshot';
@@ -1200,8 +1202,14 @@ const applyWorkflowEvent = (
   const sourceType = normalizeText(event.payload.source_event_type);
   const modelTurn = ensureModelTurn(session, event.modelTurnId, event.userTurnId, event.eventSeq);
   const message = ensureAssistantMessageForModelTurn(session, event, 'tooling');
+  if (TOOL_RUN_WORKFLOW_EVENT_TYPES.has(sourceType)) {
+    applyProjectedToolRunTiming(message, event, sourceType);
+    message.updatedSeq = event.eventSeq ?? message.updatedSeq;
+    markMessageStructureChanged(message);
+    return;
+  }
   const status = resolveProjectedWorkflowStatus(sourceType, event.payload);
-  if (shouldClearAssistantTextAtWorkflowBoundary(sourceType, status)) {
+  if (shouldClearAssistantTextAtWorkflowBoundary(sourceType, status, event.payload)) {
     clearAssistantTextAtToolBoundary(message);
   }
   upsertProjectedWorkflowEventItem(message, event, sourceType, status, modelTurn);
@@ -1239,6 +1247,39 @@ const applyWorkflowEvent = (
   }
 };
 
+const applyProjectedToolRunTiming = (
+  message: ChatRuntimeMessageProjection,
+  event: NormalizedRuntimeEvent,
+  sourceType: string
+): void => {
+  if (message.role !== 'assistant') return;
+  const payload = event.payload;
+  const data = asRecord(payload.data);
+  const detailSource = Object.keys(data).length > 0 ? data : payload;
+  const toolCallId = firstText(detailSource.tool_call_id, detailSource.toolCallId);
+  if (!toolCallId) return;
+  const item = ensureProjectedWorkflowItems(message).find(
+    (candidate) => firstText(candidate.toolCallId, candidate.tool_call_id) === toolCallId
+  );
+  if (!item) return;
+  const mode = firstText(detailSource.mode);
+  if (mode) {
+    item.executionMode = mode;
+    item.execution_mode = mode;
+  }
+  if (sourceType === 'tool_started') {
+    item.executing = true;
+  } else {
+    item.executing = false;
+    const durationMs = parseNonNegativeInt(detailSource.duration_ms ?? detailSource.durationMs);
+    if (durationMs !== null) {
+      item.durationMs = durationMs;
+      item.duration_ms = durationMs;
+    }
+  }
+  item.updatedSeq = event.eventSeq ?? item.updatedSeq;
+};
+
 const applyUsageStats = (
   session: ChatRuntimeSessionProjection,
   event: NormalizedRuntimeEvent
@@ -2597,8 +2638,14 @@ const shouldMergeRuntimeSnapshotText = (event: NormalizedRuntimeEvent): boolean
 
 const shouldClearAssistantTextAtWorkflowBoundary = (
   sourceType: string,
-  status: string
+  status: string,
+  payload: Record<string, unknown>
 ): boolean => {
+  if (sourceType === 'llm_stream_retry') {
+    // An interrupted stream is re-sent from scratch; drop the partial text it left behind.
+    const data = asRecord(payload.data);
+    return (Object.keys(data).length > 0 ? data : payload).reset_output === true;
+  }
   if (status === 'completed') return false;
   if (sourceType === 'command_session_start') return true;
   if (COMMAND_SESSION_WORKFLOW_EVENT_TYPES.has(sourceType)) return false;
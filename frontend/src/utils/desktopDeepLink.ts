@@ -0,0 +1,52 @@
+// 桌面深链：接收桌面壳转发的 wunder://session/<id> 会话 id（含冷启动时暂存的链接），交给页面打开对应会话。
+const OPEN_SESSION_DOM_EVENT = 'wunder-desktop:open-session';
+
+type DesktopDeepLinkBridge = {
+  takePendingOpenSession?: () => Promise<string | null> | string | null;
+};
+
+const resolveDesktopDeepLinkBridge = (): DesktopDeepLinkBridge | null => {
+  if (typeof window === 'undefined') return null;
+  const candidate = (window as Window & { wunderDesktop?: DesktopDeepLinkBridge }).wunderDesktop;
+  if (candidate && typeof candidate.takePendingOpenSession === 'function') {
+    return candidate;
+  }
+  return null;
+};
+
+const normalizeSessionId = (value: unknown): string => String(value || '').trim();
+
+/**
+ * Subscribes to deep-link session requests from the desktop shell and drains the one
+ * received before the page loaded; returns the unsubscribe.
+ */
+export const onDesktopOpenSessionRequested = (
+  handler: (sessionId: string) => void
+): (() => void) => {
+  if (typeof window === 'undefined') return () => undefined;
+  let active = true;
+  // Live links also stay pending in the shell until drained, so clear it to avoid a replay.
+  const drainPending = () => {
+    const bridge = resolveDesktopDeepLinkBridge();
+    if (!bridge?.takePendingOpenSession) return;
+    try {
+      void Promise.resolve(bridge.takePendingOpenSession())
+        .then((value) => {
+          const sessionId = normalizeSessionId(value);
+          if (active && sessionId) {
+            handler(sessionId);
+          }
+        })
+        .catch(() => undefined);
+    } catch {
+      // Deep links are best-effort.
+    }
+  };
+  const listener = () => drainPending();
+  window.addEventListener(OPEN_SESSION_DOM_EVENT, listener);
+  drainPending();
+  return () => {
+    active = false;
+    window.removeEventListener(OPEN_SESSION_DOM_EVENT, listener);
+  };
+};
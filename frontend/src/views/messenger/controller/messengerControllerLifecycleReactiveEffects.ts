@@ -123,6 +123,7 @@ import WorkerCardImportWaitingOverlay from '@/components/agent/WorkerCardImportW
 import { downloadWorkerCardBundle, parseWorkerCardText, workerCardToAgentPayload } from '@/utils/workerCard';
 import { redirectToLoginAfterLogout } from '@/utils/authNavigation';
 import { copyText } from '@/utils/clipboard';
+import { onDesktopOpenSessionRequested } from '@/utils/desktopDeepLink';
 import { confirmWithFallback } from '@/utils/confirm';
 import {
   buildAssistantDisplayContent,
@@ -1187,6 +1188,9 @@ export function installMessengerControllerLifecycleReactiveEffects(ctx: Messenge
           window.addEventListener('pageshow', ctx.audioRecordingSupportHandler);
           document.addEventListener('visibilitychange', ctx.audioRecordingSupportHandler);
           ctx.refreshAudioRecordingSupport();
+          ctx.desktopDeepLinkCleanup = onDesktopOpenSessionRequested((sessionId) => {
+              void ctx.openAgentSession(sessionId);
+          });
           if (ctx.audioRecordingSupportRetryTimer !== null) {
               window.clearTimeout(ctx.audioRecordingSupportRetryTimer);
           }
@@ -1340,6 +1344,10 @@ export function installMessengerControllerLifecycleReactiveEffects(ctx: Messenge
               document.removeEventListener('visibilitychange', ctx.audioRecordingSupportHandler);
               ctx.audioRecordingSupportHandler = null;
           }
+          if (ctx.desktopDeepLinkCleanup) {
+              ctx.desktopDeepLinkCleanup();
+              ctx.desktopDeepLinkCleanup = null;
+          }
           if (ctx.audioRecordingSupportRetryTimer !== null) {
               window.clearTimeout(ctx.audioRecordingSupportRetryTimer);
               ctx.audioRecordingSupportRetryTimer = null;
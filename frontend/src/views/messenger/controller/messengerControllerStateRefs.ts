@@ -987,6 +987,7 @@ export function installMessengerControllerStateRefs(ctx: MessengerControllerCont
   ctx.viewportResizeHandler = null;
 
   ctx.audioRecordingSupportHandler = null;
+  ctx.desktopDeepLinkCleanup = null;
 
   ctx.audioRecordingSupportRetryTimer = null;
 
@@ -1,7 +1,10 @@
 import type { MessengerSection } from '@/stores/sessionHub';
 
+export type DesktopUpdateChannel = 'stable' | 'beta';
+
 export type DesktopUpdateState = {
   phase?: string;
+  channel?: DesktopUpdateChannel;
   currentVersion?: string;
   latestVersion?: string;
   downloaded?: boolean;
@@ -29,6 +32,10 @@ export type DesktopBridge = {
   checkForUpdates?: () => Promise<DesktopUpdateState> | DesktopUpdateState;
   getUpdateState?: () => Promise<DesktopUpdateState> | DesktopUpdateState;
   installUpdate?: () => Promise<DesktopInstallResult | boolean> | DesktopInstallResult | boolean;
+  getUpdateChannel?: () => Promise<DesktopUpdateChannel> | DesktopUpdateChannel;
+  setUpdateChannel?: (
+    channel: DesktopUpdateChannel
+  ) => Promise<DesktopUpdateState> | DesktopUpdateState;
   copyText?: (text: string) => Promise<boolean> | boolean;
   getMediaAccessStatus?: (kind: 'microphone' | 'camera') => Promise<string> | string;
   requestMediaAccess?: (kind: 'microphone' | 'camera') => Promise<boolean> | boolean;